@@ -269,4 +269,19 @@ pub struct CompactorConfig {
         action
     )]
     pub max_partition_fetch_queries_per_second: Option<usize>,
+
+    /// Write parquet bloom filters for the tag columns of the listed tables,
+    /// allowing queries with equality predicates on high-cardinality tags to
+    /// skip row groups. Specify "*" to write bloom filters for all tables.
+    ///
+    /// Each bloom filter adds up to 128KiB per tag column, per row group to
+    /// the files the compactor writes. No bloom filters are written if unset.
+    #[clap(
+        long = "parquet-bloom-filter-tables",
+        env = "INFLUXDB_IOX_PARQUET_BLOOM_FILTER_TABLES",
+        required = false,
+        num_args=0..,
+        value_delimiter = ','
+    )]
+    pub parquet_bloom_filter_tables: Vec<String>,
 }
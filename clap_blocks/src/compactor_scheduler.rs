@@ -90,6 +90,32 @@ pub struct PartitionSourceConfigForLocalScheduler {
     pub ignore_partition_skip_marker: bool,
 }
 
+/// CLI config for the client of a remote scheduler.
+#[derive(Debug, Clone, Default, clap::Parser)]
+pub struct ClientConfigForRemoteScheduler {
+    /// gRPC address of the compaction scheduler, e.g. `http://compaction-scheduler:8082`.
+    ///
+    /// Required if the remote scheduler is used.
+    #[clap(
+        long = "compaction-scheduler-address",
+        env = "INFLUXDB_IOX_COMPACTION_SCHEDULER_ADDRESS",
+        required_if_eq("compactor_scheduler_type", "remote"),
+        action
+    )]
+    pub scheduler_address: Option<String>,
+
+    /// Interval in seconds at which the leases of active compaction jobs are renewed.
+    ///
+    /// Must be well below the lease duration configured on the compaction scheduler.
+    #[clap(
+        long = "compaction-scheduler-heartbeat-interval-secs",
+        env = "INFLUXDB_IOX_COMPACTION_SCHEDULER_HEARTBEAT_INTERVAL_SECS",
+        default_value = "10",
+        action
+    )]
+    pub heartbeat_interval_secs: u64,
+}
+
 /// CLI config for compactor scheduler.
 #[derive(Debug, Clone, Default, clap::Parser)]
 pub struct CompactorSchedulerConfig {
@@ -110,6 +136,40 @@ pub struct CompactorSchedulerConfig {
     /// Shard config used by the local scheduler.
     #[clap(flatten)]
     pub shard_config: ShardConfigForLocalScheduler,
+
+    /// Client config used by the remote scheduler.
+    #[clap(flatten)]
+    pub remote_config: ClientConfigForRemoteScheduler,
+}
+
+/// CLI config for the compaction scheduler server.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct CompactionSchedulerServerConfig {
+    /// Partition source config used to find partitions to compact.
+    #[clap(flatten)]
+    pub partition_source_config: PartitionSourceConfigForLocalScheduler,
+
+    /// Duration in seconds after which a compaction job is handed out to another compactor,
+    /// unless its lease is renewed.
+    #[clap(
+        long = "compaction-scheduler-lease-duration-secs",
+        env = "INFLUXDB_IOX_COMPACTION_SCHEDULER_LEASE_DURATION_SECS",
+        default_value = "60",
+        action
+    )]
+    pub lease_duration_secs: u64,
+
+    /// Shadow mode.
+    ///
+    /// This will NOT commit any output to the catalog.
+    ///
+    /// This is mostly useful for debugging.
+    #[clap(
+        long = "compaction-shadow-mode",
+        env = "INFLUXDB_IOX_COMPACTION_SHADOW_MODE",
+        action
+    )]
+    pub shadow_mode: bool,
 }
 
 #[cfg(test)]
@@ -156,4 +216,41 @@ mod tests {
         );
         assert_contains!(&error, "[possible values: local, remote]");
     }
+
+    #[test]
+    fn remote_requires_address() {
+        let error = CompactorSchedulerConfig::try_parse_from([
+            "my_binary",
+            "--compactor-scheduler",
+            "remote",
+        ])
+        .unwrap_err()
+        .to_string();
+        assert_contains!(&error, "--compaction-scheduler-address <SCHEDULER_ADDRESS>");
+
+        let config = CompactorSchedulerConfig::try_parse_from([
+            "my_binary",
+            "--compactor-scheduler",
+            "remote",
+            "--compaction-scheduler-address",
+            "http://scheduler:8082",
+        ])
+        .unwrap();
+        assert_eq!(
+            config.compactor_scheduler_type,
+            CompactorSchedulerType::Remote
+        );
+        assert_eq!(
+            config.remote_config.scheduler_address.as_deref(),
+            Some("http://scheduler:8082")
+        );
+        assert_eq!(config.remote_config.heartbeat_interval_secs, 10);
+    }
+
+    #[test]
+    fn server_defaults() {
+        let config = CompactionSchedulerServerConfig::try_parse_from(["my_binary"]).unwrap();
+        assert_eq!(config.lease_duration_secs, 60);
+        assert!(!config.shadow_mode);
+    }
 }
@@ -1,4 +1,5 @@
 //! Garbage Collector configuration
+use crate::namespace_delete::NamespaceDeleteConfig;
 use clap::Parser;
 use humantime::parse_duration;
 use std::{fmt::Debug, time::Duration};
@@ -82,4 +83,8 @@ pub struct GarbageCollectorConfig {
         env = "INFLUXDB_IOX_GC_RETENTION_SLEEP_INTERVAL_MINUTES"
     )]
     pub retention_sleep_interval_minutes: u64,
+
+    /// Soft-deleted namespace config.
+    #[clap(flatten)]
+    pub namespace_delete_config: NamespaceDeleteConfig,
 }
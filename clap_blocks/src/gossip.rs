@@ -3,7 +3,7 @@
 use crate::socket_addr::SocketAddr;
 
 /// Configuration parameters for the cluster gossip communication mechanism.
-#[derive(Debug, Clone, clap::Parser)]
+#[derive(Debug, Clone, PartialEq, Eq, clap::Parser)]
 #[allow(missing_copy_implementations)]
 pub struct GossipConfig {
     /// A comma-delimited set of seed gossip peer addresses.
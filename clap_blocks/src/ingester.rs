@@ -1,8 +1,19 @@
 //! CLI config for the ingester using the RPC write path
 
-use std::{num::NonZeroUsize, path::PathBuf};
+use std::{num::NonZeroUsize, path::PathBuf, time::Duration};
 
-use crate::gossip::GossipConfig;
+use crate::{gossip::GossipConfig, memory_size::MemorySize};
+
+/// Write-ahead log replay mode.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum WalReplayMode {
+    /// Abort startup when a WAL entry cannot be read.
+    #[default]
+    Strict,
+
+    /// Salvage as much data as possible from damaged WAL segments.
+    Recover,
+}
 
 /// CLI config for the ingester using the RPC write path
 #[derive(Debug, Clone, clap::Parser)]
@@ -35,6 +46,49 @@ pub struct IngesterConfig {
     )]
     pub wal_rotation_period_seconds: u64,
 
+    /// How write-ahead log replay at startup reacts to damaged segment files.
+    ///
+    /// In `strict` mode an unreadable WAL entry aborts startup. In `recover`
+    /// mode, torn writes are truncated and corrupt entries are skipped, the
+    /// lost sequence number ranges are logged, and damaged segment files are
+    /// moved into the `quarantine` directory within the WAL directory.
+    #[clap(
+        value_enum,
+        long = "wal-replay-mode",
+        env = "INFLUXDB_IOX_WAL_REPLAY_MODE",
+        default_value = "strict",
+        action
+    )]
+    pub wal_replay_mode: WalReplayMode,
+
+    /// Archive each write-ahead log segment to the object store once all the
+    /// writes it contains are persisted, retaining the archived segments for
+    /// the specified duration (e.g. `7d`).
+    ///
+    /// Archived segments can be re-ingested with
+    /// `influxdb_iox debug wal replay-archive`.
+    ///
+    /// Archival is disabled by default.
+    #[clap(
+        long = "wal-archive-retention",
+        env = "INFLUXDB_IOX_WAL_ARCHIVE_RETENTION",
+        value_parser = humantime::parse_duration,
+        action
+    )]
+    pub wal_archive_retention: Option<Duration>,
+
+    /// The object store path prefix archived write-ahead log segments are
+    /// written under.
+    ///
+    /// Has no effect unless `--wal-archive-retention` is set.
+    #[clap(
+        long = "wal-archive-prefix",
+        env = "INFLUXDB_IOX_WAL_ARCHIVE_PREFIX",
+        default_value = "wal_archive",
+        action
+    )]
+    pub wal_archive_prefix: String,
+
     /// Sets how many queries the ingester will handle simultaneously before
     /// rejecting further incoming requests.
     #[clap(
@@ -76,6 +130,48 @@ pub struct IngesterConfig {
     )]
     pub persist_hot_partition_cost: usize,
 
+    /// The amount of memory that buffered and persisting data may use before
+    /// the partitions holding the most buffered data are persisted early,
+    /// independently of the WAL rotation period.
+    ///
+    /// Can be given as absolute value or in percentage of the total available
+    /// memory (e.g. `40%`).
+    ///
+    /// This limit is disabled by default.
+    #[clap(
+        long = "buffer-memory-limit-bytes",
+        env = "INFLUXDB_IOX_BUFFER_MEMORY_LIMIT_BYTES",
+        action
+    )]
+    pub buffer_memory_limit_bytes: Option<MemorySize>,
+
+    /// The percentage of `--buffer-memory-limit-bytes` above which writes are
+    /// rejected with a retryable error until enough buffered data has been
+    /// persisted.
+    ///
+    /// Has no effect unless `--buffer-memory-limit-bytes` is set.
+    #[clap(
+        long = "buffer-memory-hard-limit-percent",
+        env = "INFLUXDB_IOX_BUFFER_MEMORY_HARD_LIMIT_PERCENT",
+        default_value = "125",
+        value_parser = clap::value_parser!(u64).range(100..),
+        action
+    )]
+    pub buffer_memory_hard_limit_percent: u64,
+
+    /// The interval at which the memory used by buffered and persisting data
+    /// is evaluated against `--buffer-memory-limit-bytes`.
+    ///
+    /// Has no effect unless `--buffer-memory-limit-bytes` is set.
+    #[clap(
+        long = "buffer-memory-check-interval",
+        env = "INFLUXDB_IOX_BUFFER_MEMORY_CHECK_INTERVAL",
+        default_value = "1s",
+        value_parser = humantime::parse_duration,
+        action
+    )]
+    pub buffer_memory_check_interval: Duration,
+
     /// Limit the number of partitions that may be buffered in a single
     /// namespace (across all tables) at any one time.
     ///
@@ -85,4 +181,19 @@ pub struct IngesterConfig {
         env = "INFLUXDB_IOX_MAX_PARTITIONS_PER_NAMESPACE"
     )]
     pub max_partitions_per_namespace: Option<NonZeroUsize>,
+
+    /// Write parquet bloom filters for the tag columns of the listed tables,
+    /// allowing queries with equality predicates on high-cardinality tags to
+    /// skip row groups. Specify "*" to write bloom filters for all tables.
+    ///
+    /// Each bloom filter adds up to 128KiB per tag column, per row group to
+    /// the files the ingester persists. No bloom filters are written if unset.
+    #[clap(
+        long = "parquet-bloom-filter-tables",
+        env = "INFLUXDB_IOX_PARQUET_BLOOM_FILTER_TABLES",
+        required = false,
+        num_args=0..,
+        value_delimiter = ','
+    )]
+    pub parquet_bloom_filter_tables: Vec<String>,
 }
@@ -26,6 +26,7 @@ pub mod gossip;
 pub mod ingester;
 pub mod ingester_address;
 pub mod memory_size;
+pub mod namespace_delete;
 pub mod object_store;
 pub mod querier;
 pub mod router;
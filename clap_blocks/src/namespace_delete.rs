@@ -0,0 +1,62 @@
+//! CLI config for the soft-deletion of namespaces, shared by the router and
+//! the garbage collector.
+
+use std::time::Duration;
+
+/// The default grace period of soft-deleted namespaces.
+pub const DEFAULT_NAMESPACE_DELETE_GRACE_PERIOD: Duration = Duration::from_secs(14 * 24 * 60 * 60);
+
+/// Configuration of soft-deleted namespaces.
+///
+/// Routers restore namespaces within the grace period, and the garbage
+/// collector removes their data after it, so both must use the same value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::Parser)]
+pub struct NamespaceDeleteConfig {
+    /// The period after a namespace is soft-deleted during which it can still
+    /// be restored, after which the parquet files of the namespace are flagged
+    /// for deletion by the garbage collector.
+    /// Parsed with <https://docs.rs/humantime/latest/humantime/fn.parse_duration.html>
+    ///
+    /// The routers and the garbage collector MUST be configured with the same
+    /// grace period.
+    #[clap(
+        long = "namespace-delete-grace-period",
+        env = "INFLUXDB_IOX_NAMESPACE_DELETE_GRACE_PERIOD",
+        default_value = "14d",
+        value_parser = humantime::parse_duration,
+        action
+    )]
+    pub namespace_delete_grace_period: Duration,
+}
+
+impl Default for NamespaceDeleteConfig {
+    fn default() -> Self {
+        Self {
+            namespace_delete_grace_period: DEFAULT_NAMESPACE_DELETE_GRACE_PERIOD,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn test_default() {
+        let config = NamespaceDeleteConfig::try_parse_from(["server"]).unwrap();
+        assert_eq!(config, NamespaceDeleteConfig::default());
+
+        let config = NamespaceDeleteConfig::try_parse_from([
+            "server",
+            "--namespace-delete-grace-period",
+            "1h",
+        ])
+        .unwrap();
+        assert_eq!(
+            config.namespace_delete_grace_period,
+            Duration::from_secs(60 * 60)
+        );
+    }
+}
@@ -1,15 +1,23 @@
 //! Querier-related configs.
 
 use crate::{
+    gossip::GossipConfig,
     ingester_address::IngesterAddress,
     memory_size::MemorySize,
     single_tenant::{CONFIG_AUTHZ_ENV_NAME, CONFIG_AUTHZ_FLAG},
 };
-use std::{collections::HashMap, num::NonZeroUsize};
+use std::{collections::HashMap, num::NonZeroUsize, path::PathBuf};
 
 /// CLI config for querier configuration
 #[derive(Debug, Clone, PartialEq, Eq, clap::Parser)]
 pub struct QuerierConfig {
+    /// Gossip config.
+    ///
+    /// If enabled, the querier subscribes to parquet file and compaction
+    /// notifications to keep its caches up to date.
+    #[clap(flatten)]
+    pub gossip_config: GossipConfig,
+
     /// Addr for connection to authz
     #[clap(long = CONFIG_AUTHZ_FLAG, env = CONFIG_AUTHZ_ENV_NAME)]
     pub authz_address: Option<String>,
@@ -57,6 +65,16 @@ pub struct QuerierConfig {
     )]
     pub ingester_addresses: Vec<IngesterAddress>,
 
+    /// gRPC address of the router, used to write the results of InfluxQL
+    /// `SELECT ... INTO` queries, and to apply the InfluxQL `DELETE`,
+    /// `DROP MEASUREMENT` and `DROP SERIES` statements. For example:
+    ///
+    /// "http://127.0.0.1:8081"
+    ///
+    /// If not specified, these statements are rejected.
+    #[clap(long = "router-address", env = "INFLUXDB_IOX_ROUTER_ADDRESS", action)]
+    pub router_address: Option<String>,
+
     /// Size of the RAM cache used to store catalog metadata information in bytes.
     ///
     /// Can be given as absolute value or in percentage of the total available memory (e.g. `10%`).
@@ -79,6 +97,27 @@ pub struct QuerierConfig {
     )]
     pub ram_pool_data_bytes: MemorySize,
 
+    /// Directory for the on-disk cache of parquet data.
+    ///
+    /// Data evicted from the RAM cache is kept here, and the content survives restarts. If not
+    /// specified, data is only cached in RAM.
+    ///
+    /// The directory must be empty or already used as a disk cache: the querier refuses to start
+    /// on a directory holding other data.
+    #[clap(long = "disk-cache-dir", env = "INFLUXDB_IOX_DISK_CACHE_DIR", action)]
+    pub disk_cache_dir: Option<PathBuf>,
+
+    /// Size of the on-disk cache of parquet data in bytes.
+    ///
+    /// Only used if `--disk-cache-dir` is set.
+    #[clap(
+        long = "disk-cache-bytes",
+        env = "INFLUXDB_IOX_DISK_CACHE_BYTES",
+        default_value = "10737418240",  // 10GB
+        action
+    )]
+    pub disk_cache_bytes: u64,
+
     /// Limit the number of concurrent queries.
     #[clap(
         long = "max-concurrent-queries",
@@ -111,6 +150,18 @@ pub struct QuerierConfig {
     )]
     pub ingester_circuit_breaker_threshold: u64,
 
+    /// Use the parquet page index to skip data pages that cannot match a query predicate.
+    ///
+    /// This sets the `datafusion.execution.parquet.enable_page_index` option, which can still be
+    /// overridden with `--datafusion-config`.
+    #[clap(
+        long = "parquet-page-pruning",
+        env = "INFLUXDB_IOX_PARQUET_PAGE_PRUNING",
+        default_value = "true",
+        action
+    )]
+    pub parquet_page_pruning: bool,
+
     /// DataFusion config.
     #[clap(
         long = "datafusion-config",
@@ -165,7 +216,11 @@ mod tests {
 
         assert_eq!(actual.num_query_threads, None);
         assert!(actual.ingester_addresses.is_empty());
+        assert_eq!(actual.router_address, None);
         assert!(actual.datafusion_config.is_empty());
+        assert!(actual.parquet_page_pruning);
+        assert_eq!(actual.gossip_config, GossipConfig::disabled());
+        assert_eq!(actual.disk_cache_dir, None);
     }
 
     #[test]
@@ -3,6 +3,7 @@
 use crate::{
     gossip::GossipConfig,
     ingester_address::IngesterAddress,
+    namespace_delete::NamespaceDeleteConfig,
     single_tenant::{
         CONFIG_AUTHZ_ENV_NAME, CONFIG_AUTHZ_FLAG, CONFIG_CST_ENV_NAME, CONFIG_CST_FLAG,
     },
@@ -97,6 +98,18 @@ pub struct RouterConfig {
     )]
     pub namespace_autocreation_enabled: bool,
 
+    /// Accept the valid lines of a line protocol write that contains invalid
+    /// lines, rather than rejecting the whole write.
+    ///
+    /// The rejected lines are listed in the 400 response to the write.
+    #[clap(
+        long = "partial-writes",
+        env = "INFLUXDB_IOX_PARTIAL_WRITES",
+        default_value = "false",
+        action
+    )]
+    pub partial_writes: bool,
+
     /// Specify the timeout in seconds for a single RPC write request to an
     /// ingester.
     #[clap(
@@ -141,6 +154,10 @@ pub struct RouterConfig {
         default_value = "10"
     )]
     pub rpc_write_health_num_probes: u64,
+
+    /// Soft-deleted namespace config.
+    #[clap(flatten)]
+    pub namespace_delete_config: NamespaceDeleteConfig,
 }
 
 /// Map a string containing an integer number of seconds into a [`Duration`].
@@ -0,0 +1,142 @@
+//! Benchmarks scanning a compacted parquet file with a selective predicate,
+//! with and without page-level pruning, measuring both the scan time and the
+//! number of bytes read from object storage.
+
+use compactor_test_utils::parquet_scan::{init, run, scan, selective_predicate, session};
+use criterion::{
+    criterion_group, criterion_main,
+    measurement::{Measurement, ValueFormatter},
+    BenchmarkId, Criterion, Throughput,
+};
+
+const ROWS: usize = 500_000;
+
+/// A criterion [`Measurement`] of the bytes read from object storage by a
+/// scan, recorded with `iter_custom`.
+struct BytesScanned;
+
+impl Measurement for BytesScanned {
+    type Intermediate = ();
+    type Value = u64;
+
+    fn start(&self) -> Self::Intermediate {}
+
+    fn end(&self, _i: Self::Intermediate) -> Self::Value {
+        0
+    }
+
+    fn add(&self, v1: &Self::Value, v2: &Self::Value) -> Self::Value {
+        v1 + v2
+    }
+
+    fn zero(&self) -> Self::Value {
+        0
+    }
+
+    fn to_f64(&self, value: &Self::Value) -> f64 {
+        *value as f64
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        &BytesFormatter
+    }
+}
+
+struct BytesFormatter;
+
+impl ValueFormatter for BytesFormatter {
+    fn scale_values(&self, _typical_value: f64, _values: &mut [f64]) -> &'static str {
+        "B"
+    }
+
+    fn scale_throughputs(
+        &self,
+        _typical_value: f64,
+        throughput: &Throughput,
+        values: &mut [f64],
+    ) -> &'static str {
+        match *throughput {
+            Throughput::Elements(n) => {
+                for v in values {
+                    *v /= n as f64;
+                }
+                "B/row"
+            }
+            _ => "B",
+        }
+    }
+
+    fn scale_for_machines(&self, _values: &mut [f64]) -> &'static str {
+        "B"
+    }
+}
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to initialise tokio runtime for benchmark")
+}
+
+fn bench_parquet_scan(c: &mut Criterion) {
+    let runtime = runtime();
+    let (setup, input) = runtime.block_on(init(ROWS));
+    let predicate = selective_predicate(ROWS);
+
+    let mut group = c.benchmark_group("parquet_scan");
+    group.throughput(Throughput::Elements(ROWS as _));
+
+    for enabled in [false, true] {
+        let ctx = session(&setup, enabled);
+
+        group.bench_function(
+            BenchmarkId::new("page_pruning", format!("{enabled}/rows_{ROWS}")),
+            |b| {
+                let (setup, input, predicate, ctx) = (&setup, &input, &predicate, &ctx);
+                b.to_async(&runtime)
+                    .iter(|| async move { run(scan(setup, input, predicate).await, ctx).await });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn bench_parquet_scan_bytes(c: &mut Criterion<BytesScanned>) {
+    let runtime = runtime();
+    let (setup, input) = runtime.block_on(init(ROWS));
+    let predicate = selective_predicate(ROWS);
+
+    let mut group = c.benchmark_group("parquet_scan_bytes");
+    group.throughput(Throughput::Elements(ROWS as _));
+    // The bytes scanned are deterministic, so a few samples suffice.
+    group.sample_size(10);
+
+    for enabled in [false, true] {
+        let ctx = session(&setup, enabled);
+
+        group.bench_function(
+            BenchmarkId::new("page_pruning", format!("{enabled}/rows_{ROWS}")),
+            |b| {
+                let (setup, input, predicate, ctx) = (&setup, &input, &predicate, &ctx);
+                b.to_async(&runtime).iter_custom(|iters| async move {
+                    let mut bytes = 0;
+                    for _ in 0..iters {
+                        bytes += run(scan(setup, input, predicate).await, ctx).await as u64;
+                    }
+                    bytes
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_parquet_scan);
+criterion_group! {
+    name = bytes_benches;
+    config = Criterion::default().with_measurement(BytesScanned);
+    targets = bench_parquet_scan_bytes
+}
+criterion_main!(benches, bytes_benches);
@@ -1,7 +1,7 @@
 //! QueryableParquetChunk for building query plan
 use std::{any::Any, sync::Arc};
 
-use data_types::{ChunkId, ChunkOrder, TransitionPartitionId};
+use data_types::{ChunkId, ChunkOrder, DeletePredicate, TransitionPartitionId};
 use datafusion::physical_plan::Statistics;
 use iox_query::{util::create_basic_summary, QueryChunk, QueryChunkData};
 use observability_deps::tracing::debug;
@@ -20,6 +20,8 @@ pub struct QueryableParquetChunk {
     sort_key: Option<SortKey>,
     order: ChunkOrder,
     stats: Arc<Statistics>,
+    // Deletes applied to the data of the parquet file
+    delete_predicates: Vec<Arc<DeletePredicate>>,
 }
 
 impl QueryableParquetChunk {
@@ -29,6 +31,7 @@ impl QueryableParquetChunk {
         data: Arc<ParquetChunk>,
         sort_key: Option<SortKey>,
         order: ChunkOrder,
+        delete_predicates: Vec<Arc<DeletePredicate>>,
     ) -> Self {
         let stats = Arc::new(create_basic_summary(
             data.rows() as u64,
@@ -41,6 +44,7 @@ impl QueryableParquetChunk {
             sort_key,
             order,
             stats,
+            delete_predicates,
         }
     }
 
@@ -105,6 +109,11 @@ impl QueryChunk for QueryableParquetChunk {
         self.order
     }
 
+    // Rows deleted by tombstones are dropped from the compaction output
+    fn delete_predicates(&self) -> &[Arc<DeletePredicate>] {
+        &self.delete_predicates
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -118,10 +127,13 @@ pub fn to_query_chunks(
     files
         .iter()
         .map(|file| {
+            let delete_predicates = partition_info.tombstones.predicates_for(&file.file);
+
             Arc::new(to_queryable_parquet_chunk(
                 file,
                 partition_info,
                 store.clone(),
+                delete_predicates,
             )) as _
         })
         .collect()
@@ -132,6 +144,7 @@ fn to_queryable_parquet_chunk(
     file: &FileIR,
     partition_info: &PartitionInfo,
     store: ParquetStorage,
+    delete_predicates: Vec<Arc<DeletePredicate>>,
 ) -> QueryableParquetChunk {
     let column_id_lookup = partition_info.table_schema.column_id_map();
     let selection: Vec<_> = file
@@ -170,5 +183,11 @@ fn to_queryable_parquet_chunk(
     );
 
     let parquet_chunk = ParquetChunk::new(Arc::new(file.file.clone()), schema, store);
-    QueryableParquetChunk::new(partition_id, Arc::new(parquet_chunk), sort_key, file.order)
+    QueryableParquetChunk::new(
+        partition_id,
+        Arc::new(parquet_chunk),
+        sort_key,
+        file.order,
+        delete_predicates,
+    )
 }
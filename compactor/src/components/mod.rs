@@ -40,6 +40,7 @@ pub mod scratchpad;
 pub mod split_or_compact;
 pub mod tables_source;
 pub mod timeout;
+pub mod tombstones_source;
 
 /// Pluggable system to determine compactor behavior. Please see
 /// [Crate Level Documentation](crate) for more details on the
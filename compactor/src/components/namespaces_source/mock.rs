@@ -187,6 +187,7 @@ mod tests {
                         name: "ns".to_string(),
                         max_tables: MaxTables::new(10),
                         max_columns_per_table: MaxColumnsPerTable::new(10),
+                        query_limits: Default::default(),
                         retention_period_ns: None,
                         deleted_at: None,
                         partition_template: Default::default(),
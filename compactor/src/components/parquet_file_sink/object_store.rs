@@ -66,6 +66,7 @@ impl ParquetFileSink for ObjectStoreParquetFileSink {
             compaction_level: level,
             sort_key: partition.sort_key.clone(),
             max_l0_created_at,
+            bloom_filters: self.store.bloom_filters_enabled(&partition.table.name),
         };
 
         // Stream the record batches from the compaction exec, serialize
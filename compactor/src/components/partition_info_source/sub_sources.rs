@@ -2,12 +2,14 @@ use std::{fmt::Display, sync::Arc};
 
 use async_trait::async_trait;
 use data_types::PartitionId;
+use predicate::delete_predicate::TombstonePredicates;
 use schema::sort::SortKey;
 
 use crate::{
     components::{
         columns_source::ColumnsSource, namespaces_source::NamespacesSource,
         partition_source::PartitionSource, tables_source::TablesSource,
+        tombstones_source::TombstonesSource,
     },
     error::DynError,
     partition_info::PartitionInfo,
@@ -16,64 +18,74 @@ use crate::{
 use super::PartitionInfoSource;
 
 #[derive(Debug)]
-pub struct SubSourcePartitionInfoSource<C, P, T, N>
+pub struct SubSourcePartitionInfoSource<C, P, T, N, D>
 where
     C: ColumnsSource,
     P: PartitionSource,
     T: TablesSource,
     N: NamespacesSource,
+    D: TombstonesSource,
 {
     columns_source: C,
     partition_source: P,
     tables_source: T,
     namespaces_source: N,
+    tombstones_source: D,
 }
 
-impl<C, P, T, N> SubSourcePartitionInfoSource<C, P, T, N>
+impl<C, P, T, N, D> SubSourcePartitionInfoSource<C, P, T, N, D>
 where
     C: ColumnsSource,
     P: PartitionSource,
     T: TablesSource,
     N: NamespacesSource,
+    D: TombstonesSource,
 {
     pub fn new(
         columns_source: C,
         partition_source: P,
         tables_source: T,
         namespaces_source: N,
+        tombstones_source: D,
     ) -> Self {
         Self {
             columns_source,
             partition_source,
             tables_source,
             namespaces_source,
+            tombstones_source,
         }
     }
 }
 
-impl<C, P, T, N> Display for SubSourcePartitionInfoSource<C, P, T, N>
+impl<C, P, T, N, D> Display for SubSourcePartitionInfoSource<C, P, T, N, D>
 where
     C: ColumnsSource,
     P: PartitionSource,
     T: TablesSource,
     N: NamespacesSource,
+    D: TombstonesSource,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "sub_sources(partition={}, tables={}, namespaces={})",
-            self.partition_source, self.tables_source, self.namespaces_source
+            "sub_sources(partition={}, tables={}, namespaces={}, tombstones={})",
+            self.partition_source,
+            self.tables_source,
+            self.namespaces_source,
+            self.tombstones_source
         )
     }
 }
 
 #[async_trait]
-impl<C, P, T, N> PartitionInfoSource for SubSourcePartitionInfoSource<C, P, T, N>
+impl<C, P, T, N, D> PartitionInfoSource for SubSourcePartitionInfoSource<C, P, T, N, D>
 where
     C: ColumnsSource,
     P: PartitionSource,
     T: TablesSource,
     N: NamespacesSource,
+    D: TombstonesSource,
 {
     async fn fetch(&self, partition_id: PartitionId) -> Result<Arc<PartitionInfo>, DynError> {
         // Get info for the partition
@@ -145,6 +157,14 @@ where
         // This wil be removed once sort_key is removed from partition
         assert_eq!(sort_key, p_sort_key);
 
+        // deletes recorded against the table, to be applied to the files of the partition.
+        //
+        // A tombstone that cannot be parsed fails (and eventually skips) the partition: compacting
+        // its files without applying the tombstone would resurrect the deleted data.
+        let tombstones = self.tombstones_source.fetch(table.id).await;
+        let tombstones =
+            TombstonePredicates::try_new(tombstones).map_err(|e| Box::new(e) as DynError)?;
+
         Ok(Arc::new(PartitionInfo {
             partition_id,
             partition_hash_id: partition.hash_id().cloned(),
@@ -154,6 +174,7 @@ where
             table_schema: Arc::new(table_schema.clone()),
             sort_key,
             partition_key: partition.partition_key,
+            tombstones,
         }))
     }
 }
@@ -0,0 +1,73 @@
+use std::{collections::HashMap, fmt::Display};
+
+use async_trait::async_trait;
+use data_types::{TableId, Tombstone};
+
+use super::TombstonesSource;
+
+#[derive(Debug)]
+pub struct MockTombstonesSource {
+    tables: HashMap<TableId, Vec<Tombstone>>,
+}
+
+impl MockTombstonesSource {
+    #[allow(dead_code)] // not used anywhere
+    pub fn new(tables: HashMap<TableId, Vec<Tombstone>>) -> Self {
+        Self { tables }
+    }
+}
+
+impl Display for MockTombstonesSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "mock")
+    }
+}
+
+#[async_trait]
+impl TombstonesSource for MockTombstonesSource {
+    async fn fetch(&self, table: TableId) -> Vec<Tombstone> {
+        self.tables.get(&table).cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::{Timestamp, TombstoneId};
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            MockTombstonesSource::new(HashMap::default()).to_string(),
+            "mock",
+        )
+    }
+
+    #[tokio::test]
+    async fn test_fetch() {
+        let t1 = TableId::new(1);
+        let t2 = TableId::new(2);
+        let tombstone = Tombstone {
+            id: TombstoneId::new(1),
+            table_id: t1,
+            min_time: Timestamp::new(1),
+            max_time: Timestamp::new(10),
+            serialized_predicate: String::from(r#""tag"='A'"#),
+            created_at: Timestamp::new(100),
+        };
+
+        let tables = HashMap::from([(t1, vec![tombstone.clone()]), (t2, vec![])]);
+        let source = MockTombstonesSource::new(tables);
+
+        // different tables
+        assert_eq!(source.fetch(t1).await, vec![tombstone.clone()]);
+        assert_eq!(source.fetch(t2).await, vec![]);
+
+        // fetching does not drain
+        assert_eq!(source.fetch(t1).await, vec![tombstone]);
+
+        // unknown table => empty result
+        assert_eq!(source.fetch(TableId::new(3)).await, vec![]);
+    }
+}
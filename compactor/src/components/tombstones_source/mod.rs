@@ -0,0 +1,15 @@
+use std::fmt::{Debug, Display};
+
+use async_trait::async_trait;
+use data_types::{TableId, Tombstone};
+
+pub mod catalog;
+pub mod mock;
+
+#[async_trait]
+pub trait TombstonesSource: Debug + Display + Send + Sync {
+    /// Get the tombstones recorded for a given table
+    ///
+    /// This method performs retries.
+    async fn fetch(&self, table: TableId) -> Vec<Tombstone>;
+}
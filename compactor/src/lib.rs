@@ -206,6 +206,8 @@
 #![allow(rustdoc::private_intra_doc_links)]
 
 // Workaround for "unused crate" lint false positives.
+#[cfg(test)]
+use criterion as _;
 use workspace_hack as _;
 
 pub mod compactor;
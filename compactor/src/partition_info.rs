@@ -6,6 +6,7 @@ use data_types::{
     NamespaceId, PartitionHashId, PartitionId, PartitionKey, Table, TableSchema,
     TransitionPartitionId,
 };
+use predicate::delete_predicate::TombstonePredicates;
 use schema::sort::SortKey;
 
 /// Information about the Partition being compacted
@@ -34,6 +35,11 @@ pub struct PartitionInfo {
 
     /// partition_key
     pub partition_key: PartitionKey,
+
+    /// Tombstones recorded against the table.
+    ///
+    /// The rows they delete are dropped from the files they apply to when these are rewritten.
+    pub tombstones: TombstonePredicates,
 }
 
 impl PartitionInfo {
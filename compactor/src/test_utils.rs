@@ -4,6 +4,7 @@ use data_types::{
     Column, ColumnId, ColumnType, ColumnsByName, NamespaceId, PartitionHashId, PartitionId,
     PartitionKey, Table, TableId, TableSchema,
 };
+use predicate::delete_predicate::TombstonePredicates;
 
 use crate::PartitionInfo;
 
@@ -36,6 +37,7 @@ impl PartitionInfoBuilder {
                 table_schema,
                 sort_key: None,
                 partition_key,
+                tombstones: TombstonePredicates::default(),
             },
         }
     }
@@ -0,0 +1,215 @@
+//! Scheduling compaction jobs via a remote scheduler service.
+pub(crate) mod convert;
+pub(crate) mod lease;
+pub(crate) mod service;
+
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use generated_types::influxdata::iox::compactor::v1::{
+    self as proto, compaction_scheduler_service_client::CompactionSchedulerServiceClient,
+};
+use observability_deps::tracing::{debug, warn};
+use parking_lot::Mutex;
+use tokio::task::JoinHandle;
+use tonic::transport::{Channel, Endpoint};
+use uuid::Uuid;
+
+use crate::{
+    CompactionJob, CompactionJobEnd, CompactionJobStatus, CompactionJobStatusResponse, Scheduler,
+};
+
+/// Configuration specific to the remote scheduler.
+#[derive(Debug, Clone)]
+pub struct RemoteSchedulerConfig {
+    /// gRPC address of the scheduler service, e.g. `http://compaction-scheduler:8082`.
+    pub address: String,
+    /// Identifies this compactor in the logs of the scheduler service.
+    pub compactor_id: String,
+    /// The maximum number of jobs leased per request; 0 means no limit.
+    pub max_jobs: usize,
+    /// How often the leases of active jobs are renewed.
+    ///
+    /// This must be well below the lease duration of the scheduler service.
+    pub heartbeat_interval: Duration,
+}
+
+/// Implementation of the scheduler that leases jobs from a remote
+/// [`SchedulerService`](crate::SchedulerService).
+///
+/// The leases of all jobs that were not ended yet are renewed in the background.
+#[derive(Debug)]
+pub(crate) struct RemoteScheduler {
+    address: String,
+    compactor_id: String,
+    max_jobs: usize,
+    client: CompactionSchedulerServiceClient<Channel>,
+    /// Jobs that were handed out but not ended yet.
+    active_jobs: Arc<Mutex<HashSet<Uuid>>>,
+    heartbeat_task: JoinHandle<()>,
+}
+
+impl RemoteScheduler {
+    /// Create a new [`RemoteScheduler`].
+    ///
+    /// The connection is established lazily. Must be called within a tokio runtime.
+    ///
+    /// # Panics
+    /// If the configured address is not a valid URI.
+    pub(crate) fn new(config: RemoteSchedulerConfig) -> Self {
+        let RemoteSchedulerConfig {
+            address,
+            compactor_id,
+            max_jobs,
+            heartbeat_interval,
+        } = config;
+
+        let endpoint = Endpoint::from_shared(address.clone()).unwrap_or_else(|e| {
+            panic!("invalid compaction scheduler address '{address}': {e}");
+        });
+        let client = CompactionSchedulerServiceClient::new(endpoint.connect_lazy());
+
+        let active_jobs = Arc::new(Mutex::new(HashSet::new()));
+        let heartbeat_task = tokio::spawn(heartbeat_loop(
+            client.clone(),
+            Arc::clone(&active_jobs),
+            heartbeat_interval,
+        ));
+
+        Self {
+            address,
+            compactor_id,
+            max_jobs,
+            client,
+            active_jobs,
+            heartbeat_task,
+        }
+    }
+}
+
+impl Drop for RemoteScheduler {
+    fn drop(&mut self) {
+        self.heartbeat_task.abort();
+    }
+}
+
+async fn heartbeat_loop(
+    mut client: CompactionSchedulerServiceClient<Channel>,
+    active_jobs: Arc<Mutex<HashSet<Uuid>>>,
+    interval: Duration,
+) {
+    let mut interval = tokio::time::interval(interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        let job_uuids = active_jobs
+            .lock()
+            .iter()
+            .map(|uuid| uuid.to_string())
+            .collect::<Vec<_>>();
+        if job_uuids.is_empty() {
+            continue;
+        }
+
+        let lost = match client
+            .heartbeat(proto::HeartbeatRequest { job_uuids })
+            .await
+        {
+            Ok(response) => response.into_inner().lost_job_uuids,
+            Err(e) => {
+                warn!(error=%e, "failed to renew compaction job leases");
+                continue;
+            }
+        };
+
+        // The scheduler rejects any further request for these jobs, so stop renewing them.
+        let mut active_jobs = active_jobs.lock();
+        for uuid in lost {
+            warn!(job_uuid=%uuid, "lost lease of compaction job");
+            if let Ok(uuid) = uuid.parse() {
+                active_jobs.remove(&uuid);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Scheduler for RemoteScheduler {
+    async fn get_jobs(&self) -> Vec<CompactionJob> {
+        let response = match self
+            .client
+            .clone()
+            .get_jobs(proto::GetJobsRequest {
+                compactor_id: self.compactor_id.clone(),
+                max_jobs: self.max_jobs as u32,
+            })
+            .await
+        {
+            Ok(response) => response.into_inner(),
+            Err(e) => {
+                warn!(error=%e, address=%self.address, "failed to fetch compaction jobs");
+                return vec![];
+            }
+        };
+
+        let jobs = response
+            .jobs
+            .into_iter()
+            .filter_map(|job| match CompactionJob::try_from(job) {
+                Ok(job) => Some(job),
+                Err(e) => {
+                    warn!(error=%e, "invalid compaction job from scheduler");
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        debug!(
+            n_jobs = jobs.len(),
+            lease_duration_ns = response.lease_duration_ns,
+            "leased compaction jobs"
+        );
+
+        self.active_jobs
+            .lock()
+            .extend(jobs.iter().map(|job| job.uuid()));
+
+        jobs
+    }
+
+    async fn update_job_status(
+        &self,
+        job_status: CompactionJobStatus,
+    ) -> Result<CompactionJobStatusResponse, Box<dyn std::error::Error + Send + Sync>> {
+        let response = self
+            .client
+            .clone()
+            .update_job_status(proto::UpdateJobStatusRequest::from(job_status))
+            .await?
+            .into_inner();
+
+        Ok(CompactionJobStatusResponse::try_from(response)?)
+    }
+
+    async fn end_job(
+        &self,
+        end: CompactionJobEnd,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // Stop renewing the lease whether or not the request succeeds.
+        self.active_jobs.lock().remove(&end.job.uuid());
+
+        self.client
+            .clone()
+            .end_job(proto::EndJobRequest::from(end))
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for RemoteScheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "remote_compaction_scheduler({})", self.address)
+    }
+}
@@ -0,0 +1,363 @@
+//! Conversions between the [`Scheduler`](crate::Scheduler) API types and their protobuf
+//! representation.
+
+use data_types::{
+    CompactionLevel, ParquetFile, ParquetFileId, ParquetFileParams, ParquetFileProtoError,
+    PartitionId,
+};
+use generated_types::influxdata::iox::{catalog::v1 as catalog_proto, compactor::v1 as proto};
+use thiserror::Error;
+
+use crate::{
+    CommitUpdate, CompactionJob, CompactionJobEnd, CompactionJobEndVariant, CompactionJobStatus,
+    CompactionJobStatusResponse, CompactionJobStatusVariant, ErrorKind, SkipReason,
+};
+
+/// Errors converting a protobuf message into its [`Scheduler`](crate::Scheduler) API type.
+#[derive(Debug, Error)]
+pub(crate) enum ConvertError {
+    #[error("missing field: {0}")]
+    MissingField(&'static str),
+
+    #[error("invalid job uuid: {0}")]
+    InvalidUuid(#[from] uuid::Error),
+
+    #[error("invalid parquet file: {0}")]
+    InvalidParquetFile(#[from] ParquetFileProtoError),
+
+    #[error("invalid compaction level: {0}")]
+    InvalidCompactionLevel(Box<dyn std::error::Error + Send + Sync + 'static>),
+}
+
+impl From<CompactionJob> for proto::CompactionJob {
+    fn from(job: CompactionJob) -> Self {
+        Self {
+            uuid: job.uuid().to_string(),
+            partition_id: job.partition_id.get(),
+        }
+    }
+}
+
+impl TryFrom<proto::CompactionJob> for CompactionJob {
+    type Error = ConvertError;
+
+    fn try_from(job: proto::CompactionJob) -> Result<Self, Self::Error> {
+        Ok(Self::new_with_uuid(
+            job.uuid.parse()?,
+            PartitionId::new(job.partition_id),
+        ))
+    }
+}
+
+fn job_from_proto(job: Option<proto::CompactionJob>) -> Result<CompactionJob, ConvertError> {
+    job.ok_or(ConvertError::MissingField("job"))?.try_into()
+}
+
+impl From<ErrorKind> for proto::JobError {
+    fn from(kind: ErrorKind) -> Self {
+        let (kind, message) = match kind {
+            ErrorKind::ObjectStore => (proto::ErrorKind::ObjectStore, String::new()),
+            ErrorKind::OutOfMemory => (proto::ErrorKind::OutOfMemory, String::new()),
+            ErrorKind::Timeout => (proto::ErrorKind::Timeout, String::new()),
+            ErrorKind::Unknown(msg) => (proto::ErrorKind::Unknown, msg),
+        };
+
+        Self {
+            kind: kind.into(),
+            message,
+        }
+    }
+}
+
+impl From<proto::JobError> for ErrorKind {
+    fn from(error: proto::JobError) -> Self {
+        match error.kind() {
+            proto::ErrorKind::ObjectStore => Self::ObjectStore,
+            proto::ErrorKind::OutOfMemory => Self::OutOfMemory,
+            proto::ErrorKind::Timeout => Self::Timeout,
+            // Kinds added to the protocol later are reported as unknown errors.
+            proto::ErrorKind::Unknown | proto::ErrorKind::Unspecified => {
+                Self::Unknown(error.message)
+            }
+        }
+    }
+}
+
+fn params_to_proto(params: ParquetFileParams) -> catalog_proto::ParquetFile {
+    // The ID is assigned by the catalog on commit and ignored by the receiver.
+    ParquetFile::from_params(params, ParquetFileId::new(0)).into()
+}
+
+fn params_from_proto(file: catalog_proto::ParquetFile) -> Result<ParquetFileParams, ConvertError> {
+    Ok(ParquetFile::try_from(file)?.into())
+}
+
+fn files_from_proto(
+    files: Vec<catalog_proto::ParquetFile>,
+) -> Result<Vec<ParquetFile>, ConvertError> {
+    files
+        .into_iter()
+        .map(|f| ParquetFile::try_from(f).map_err(ConvertError::from))
+        .collect()
+}
+
+impl From<CommitUpdate> for proto::CommitUpdate {
+    fn from(update: CommitUpdate) -> Self {
+        let CommitUpdate {
+            partition_id: _,
+            delete,
+            upgrade,
+            target_level,
+            create,
+        } = update;
+
+        Self {
+            delete: delete.into_iter().map(Into::into).collect(),
+            upgrade: upgrade.into_iter().map(Into::into).collect(),
+            target_level: target_level as i32,
+            create: create.into_iter().map(params_to_proto).collect(),
+        }
+    }
+}
+
+fn commit_update_from_proto(
+    partition_id: PartitionId,
+    update: proto::CommitUpdate,
+) -> Result<CommitUpdate, ConvertError> {
+    Ok(CommitUpdate::new(
+        partition_id,
+        files_from_proto(update.delete)?,
+        files_from_proto(update.upgrade)?,
+        update
+            .create
+            .into_iter()
+            .map(params_from_proto)
+            .collect::<Result<_, _>>()?,
+        CompactionLevel::try_from(update.target_level)
+            .map_err(ConvertError::InvalidCompactionLevel)?,
+    ))
+}
+
+impl From<CompactionJobStatus> for proto::UpdateJobStatusRequest {
+    fn from(job_status: CompactionJobStatus) -> Self {
+        let status = match job_status.status {
+            CompactionJobStatusVariant::Update(update) => {
+                proto::update_job_status_request::Status::Update(update.into())
+            }
+            CompactionJobStatusVariant::Error(kind) => {
+                proto::update_job_status_request::Status::Error(kind.into())
+            }
+        };
+
+        Self {
+            job: Some(job_status.job.into()),
+            status: Some(status),
+        }
+    }
+}
+
+impl TryFrom<proto::UpdateJobStatusRequest> for CompactionJobStatus {
+    type Error = ConvertError;
+
+    fn try_from(request: proto::UpdateJobStatusRequest) -> Result<Self, Self::Error> {
+        let job = job_from_proto(request.job)?;
+
+        let status = match request.status.ok_or(ConvertError::MissingField("status"))? {
+            proto::update_job_status_request::Status::Update(update) => {
+                CompactionJobStatusVariant::Update(commit_update_from_proto(
+                    job.partition_id,
+                    update,
+                )?)
+            }
+            proto::update_job_status_request::Status::Error(error) => {
+                CompactionJobStatusVariant::Error(error.into())
+            }
+        };
+
+        Ok(Self { job, status })
+    }
+}
+
+impl From<CompactionJobStatusResponse> for proto::UpdateJobStatusResponse {
+    fn from(response: CompactionJobStatusResponse) -> Self {
+        let response = match response {
+            CompactionJobStatusResponse::Ack => {
+                proto::update_job_status_response::Response::Ack(proto::Ack {})
+            }
+            CompactionJobStatusResponse::CreatedParquetFiles(ids) => {
+                proto::update_job_status_response::Response::CreatedParquetFiles(
+                    proto::CreatedParquetFiles {
+                        parquet_file_ids: ids.into_iter().map(|id| id.get()).collect(),
+                    },
+                )
+            }
+        };
+
+        Self {
+            response: Some(response),
+        }
+    }
+}
+
+impl TryFrom<proto::UpdateJobStatusResponse> for CompactionJobStatusResponse {
+    type Error = ConvertError;
+
+    fn try_from(response: proto::UpdateJobStatusResponse) -> Result<Self, Self::Error> {
+        match response
+            .response
+            .ok_or(ConvertError::MissingField("response"))?
+        {
+            proto::update_job_status_response::Response::Ack(_) => Ok(Self::Ack),
+            proto::update_job_status_response::Response::CreatedParquetFiles(files) => {
+                Ok(Self::CreatedParquetFiles(
+                    files
+                        .parquet_file_ids
+                        .into_iter()
+                        .map(ParquetFileId::new)
+                        .collect(),
+                ))
+            }
+        }
+    }
+}
+
+impl From<CompactionJobEnd> for proto::EndJobRequest {
+    fn from(end: CompactionJobEnd) -> Self {
+        let end_action = match end.end_action {
+            CompactionJobEndVariant::RequestToSkip(SkipReason(reason)) => {
+                proto::end_job_request::EndAction::Skip(proto::SkipPartition { reason })
+            }
+            CompactionJobEndVariant::Complete => {
+                proto::end_job_request::EndAction::Complete(proto::Complete {})
+            }
+        };
+
+        Self {
+            job: Some(end.job.into()),
+            end_action: Some(end_action),
+        }
+    }
+}
+
+impl TryFrom<proto::EndJobRequest> for CompactionJobEnd {
+    type Error = ConvertError;
+
+    fn try_from(request: proto::EndJobRequest) -> Result<Self, Self::Error> {
+        let job = job_from_proto(request.job)?;
+
+        let end_action = match request
+            .end_action
+            .ok_or(ConvertError::MissingField("end_action"))?
+        {
+            proto::end_job_request::EndAction::Skip(skip) => {
+                CompactionJobEndVariant::RequestToSkip(SkipReason(skip.reason))
+            }
+            proto::end_job_request::EndAction::Complete(_) => CompactionJobEndVariant::Complete,
+        };
+
+        Ok(Self { job, end_action })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::{ColumnId, ColumnSet, NamespaceId, TableId, Timestamp, TransitionPartitionId};
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn file(id: i64) -> ParquetFile {
+        ParquetFile {
+            id: ParquetFileId::new(id),
+            namespace_id: NamespaceId::new(1),
+            table_id: TableId::new(2),
+            partition_id: TransitionPartitionId::Deprecated(PartitionId::new(3)),
+            object_store_id: Uuid::new_v4(),
+            min_time: Timestamp::new(10),
+            max_time: Timestamp::new(20),
+            to_delete: None,
+            file_size_bytes: 1337,
+            row_count: 42,
+            compaction_level: CompactionLevel::Initial,
+            created_at: Timestamp::new(30),
+            column_set: ColumnSet::new([ColumnId::new(1), ColumnId::new(2)]),
+            max_l0_created_at: Timestamp::new(30),
+        }
+    }
+
+    #[test]
+    fn test_update_job_status_roundtrip() {
+        let job = CompactionJob::new(PartitionId::new(3));
+        let created = ParquetFileParams::from(file(0));
+
+        let request = proto::UpdateJobStatusRequest::from(CompactionJobStatus {
+            job: job.clone(),
+            status: CompactionJobStatusVariant::Update(CommitUpdate::new(
+                job.partition_id,
+                vec![file(1)],
+                vec![file(2)],
+                vec![created.clone()],
+                CompactionLevel::FileNonOverlapped,
+            )),
+        });
+        let got = CompactionJobStatus::try_from(request).unwrap();
+
+        assert_eq!(got.job, job);
+        assert_matches!(got.status, CompactionJobStatusVariant::Update(update) => {
+            assert_eq!(update.partition_id, job.partition_id);
+            assert_eq!(update.delete, vec![file(1)]);
+            assert_eq!(update.upgrade, vec![file(2)]);
+            assert_eq!(update.target_level, CompactionLevel::FileNonOverlapped);
+            assert_eq!(update.create, vec![created]);
+        });
+    }
+
+    #[test]
+    fn test_error_kind_roundtrip() {
+        for kind in [
+            ErrorKind::ObjectStore,
+            ErrorKind::OutOfMemory,
+            ErrorKind::Timeout,
+            ErrorKind::Unknown("foo".into()),
+        ] {
+            let got = ErrorKind::from(proto::JobError::from(kind.clone()));
+            assert_eq!(got, kind);
+        }
+    }
+
+    #[test]
+    fn test_end_job_roundtrip() {
+        let job = CompactionJob::new(PartitionId::new(3));
+
+        let request = proto::EndJobRequest::from(CompactionJobEnd {
+            job: job.clone(),
+            end_action: CompactionJobEndVariant::RequestToSkip(SkipReason("foo".into())),
+        });
+        let got = CompactionJobEnd::try_from(request).unwrap();
+
+        assert_eq!(got.job, job);
+        assert_matches!(
+            got.end_action,
+            CompactionJobEndVariant::RequestToSkip(SkipReason(reason)) if reason == "foo"
+        );
+    }
+
+    #[test]
+    fn test_invalid_uuid() {
+        let request = proto::EndJobRequest {
+            job: Some(proto::CompactionJob {
+                uuid: "foo".into(),
+                partition_id: 1,
+            }),
+            end_action: Some(proto::end_job_request::EndAction::Complete(
+                proto::Complete {},
+            )),
+        };
+
+        assert_matches!(
+            CompactionJobEnd::try_from(request),
+            Err(ConvertError::InvalidUuid(_))
+        );
+    }
+}
@@ -0,0 +1,323 @@
+//! Lease tracking for jobs handed out to remote compactors.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
+
+use iox_time::{Time, TimeProvider};
+use metric::U64Counter;
+use observability_deps::tracing::{debug, warn};
+use parking_lot::Mutex;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    CompactionJob, CompactionJobEnd, CompactionJobStatus, CompactionJobStatusResponse, Scheduler,
+};
+
+/// Errors returned by the [`LeaseTracker`].
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    #[error("job {0} is not leased to the caller")]
+    LeaseLost(Uuid),
+
+    #[error("scheduler error: {0}")]
+    Scheduler(Box<dyn std::error::Error + Send + Sync>),
+}
+
+#[derive(Debug)]
+struct Lease {
+    job: CompactionJob,
+    holder: String,
+    expires_at: Time,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Jobs fetched from the inner scheduler that are not leased yet.
+    pending: VecDeque<CompactionJob>,
+
+    /// Active leases, keyed by job uuid.
+    leases: HashMap<Uuid, Lease>,
+}
+
+/// Hands out the jobs of an inner [`Scheduler`] as leases.
+///
+/// A lease must be renewed within the lease duration, otherwise the partition is handed out
+/// again under a new job uuid and the previous holder can no longer report on it.
+#[derive(Debug)]
+pub(crate) struct LeaseTracker {
+    inner: Arc<dyn Scheduler>,
+    time_provider: Arc<dyn TimeProvider>,
+    lease_duration: Duration,
+    state: Mutex<State>,
+    expired: U64Counter,
+}
+
+impl LeaseTracker {
+    pub(crate) fn new(
+        inner: Arc<dyn Scheduler>,
+        time_provider: Arc<dyn TimeProvider>,
+        lease_duration: Duration,
+        metrics: &metric::Registry,
+    ) -> Self {
+        let expired = metrics
+            .register_metric::<U64Counter>(
+                "compaction_scheduler_lease_expired",
+                "Number of compaction job leases that expired and were reassigned",
+            )
+            .recorder(&[]);
+
+        Self {
+            inner,
+            time_provider,
+            lease_duration,
+            state: Mutex::default(),
+            expired,
+        }
+    }
+
+    pub(crate) fn lease_duration(&self) -> Duration {
+        self.lease_duration
+    }
+
+    /// Lease up to `max_jobs` jobs to `holder`; 0 means no limit.
+    pub(crate) async fn get_jobs(&self, holder: &str, max_jobs: usize) -> Vec<CompactionJob> {
+        let need_jobs = {
+            let mut state = self.state.lock();
+            self.reclaim_expired(&mut state);
+            state.pending.is_empty()
+        };
+
+        if need_jobs {
+            let jobs = self.inner.get_jobs().await;
+
+            // The inner scheduler hands out every partition needing compaction on each call, under
+            // a new job uuid, so skip the partitions that are already leased or queued.
+            let mut state = self.state.lock();
+            let mut known = state
+                .leases
+                .values()
+                .map(|lease| lease.job.partition_id)
+                .chain(state.pending.iter().map(|job| job.partition_id))
+                .collect::<HashSet<_>>();
+            let jobs = jobs
+                .into_iter()
+                .filter(|job| known.insert(job.partition_id))
+                .collect::<Vec<_>>();
+            state.pending.extend(jobs);
+        }
+
+        let expires_at = self.time_provider.now() + self.lease_duration;
+        let mut state = self.state.lock();
+        let n = match max_jobs {
+            0 => state.pending.len(),
+            n => n.min(state.pending.len()),
+        };
+        let jobs = state.pending.drain(..n).collect::<Vec<_>>();
+
+        for job in &jobs {
+            debug!(
+                partition_id=job.partition_id.get(),
+                job_uuid=%job.uuid(),
+                holder,
+                "leasing compaction job"
+            );
+            state.leases.insert(
+                job.uuid(),
+                Lease {
+                    job: job.clone(),
+                    holder: holder.to_owned(),
+                    expires_at,
+                },
+            );
+        }
+
+        jobs
+    }
+
+    /// Renew the given leases, returning the uuids of those that are no longer valid.
+    pub(crate) fn heartbeat(&self, uuids: impl IntoIterator<Item = Uuid>) -> Vec<Uuid> {
+        let mut state = self.state.lock();
+
+        uuids
+            .into_iter()
+            .filter(|uuid| self.renew(&mut state, *uuid).is_err())
+            .collect()
+    }
+
+    pub(crate) async fn update_job_status(
+        &self,
+        job_status: CompactionJobStatus,
+    ) -> Result<CompactionJobStatusResponse, Error> {
+        self.renew(&mut self.state.lock(), job_status.job.uuid())?;
+
+        self.inner
+            .update_job_status(job_status)
+            .await
+            .map_err(Error::Scheduler)
+    }
+
+    pub(crate) async fn end_job(&self, end: CompactionJobEnd) -> Result<(), Error> {
+        {
+            let mut state = self.state.lock();
+            self.renew(&mut state, end.job.uuid())?;
+            state.leases.remove(&end.job.uuid());
+        }
+
+        self.inner.end_job(end).await.map_err(Error::Scheduler)
+    }
+
+    fn renew(&self, state: &mut State, uuid: Uuid) -> Result<(), Error> {
+        let now = self.time_provider.now();
+
+        match state.leases.get_mut(&uuid) {
+            Some(lease) if lease.expires_at > now => {
+                lease.expires_at = now + self.lease_duration;
+                Ok(())
+            }
+            _ => Err(Error::LeaseLost(uuid)),
+        }
+    }
+
+    /// Move the partitions of expired leases back to the front of the queue.
+    fn reclaim_expired(&self, state: &mut State) {
+        let now = self.time_provider.now();
+
+        let mut expired = vec![];
+        state.leases.retain(|_, lease| {
+            if lease.expires_at > now {
+                return true;
+            }
+            expired.push(CompactionJob::new(lease.job.partition_id));
+            warn!(
+                partition_id=lease.job.partition_id.get(),
+                job_uuid=%lease.job.uuid(),
+                holder=%lease.holder,
+                "compaction job lease expired, reassigning partition"
+            );
+            false
+        });
+
+        self.expired.inc(expired.len() as u64);
+        for job in expired {
+            state.pending.push_front(job);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::PartitionId;
+    use iox_tests::TestCatalog;
+    use iox_time::MockProvider;
+    use metric::{Attributes, Metric};
+
+    use super::*;
+    use crate::{create_test_scheduler, CompactionJobEndVariant};
+
+    const LEASE: Duration = Duration::from_secs(60);
+
+    fn tracker(partitions: &[i64]) -> (LeaseTracker, Arc<MockProvider>, metric::Registry) {
+        let time_provider = Arc::new(MockProvider::new(Time::MIN));
+        let inner = create_test_scheduler(
+            TestCatalog::new().catalog(),
+            Arc::clone(&time_provider) as Arc<dyn TimeProvider>,
+            Some(partitions.iter().copied().map(PartitionId::new).collect()),
+        );
+        let metrics = metric::Registry::default();
+        let tracker = LeaseTracker::new(
+            inner,
+            Arc::clone(&time_provider) as Arc<dyn TimeProvider>,
+            LEASE,
+            &metrics,
+        );
+
+        (tracker, time_provider, metrics)
+    }
+
+    fn complete(job: &CompactionJob) -> CompactionJobEnd {
+        CompactionJobEnd {
+            job: job.clone(),
+            end_action: CompactionJobEndVariant::Complete,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_jobs_are_leased_once() {
+        let (tracker, _time_provider, _metrics) = tracker(&[1, 2]);
+
+        let jobs = tracker.get_jobs("a", 1).await;
+        assert_eq!(jobs.len(), 1);
+        let other = tracker.get_jobs("b", 0).await;
+        assert_eq!(other.len(), 1);
+        assert_ne!(jobs[0].partition_id, other[0].partition_id);
+
+        // everything is leased, and the inner scheduler returning the same partitions again does
+        // not hand them out twice
+        assert!(tracker.get_jobs("c", 0).await.is_empty());
+        assert!(tracker.get_jobs("c", 0).await.is_empty());
+        assert!(tracker.get_jobs("a", 0).await.is_empty());
+
+        tracker.end_job(complete(&jobs[0])).await.unwrap();
+        assert_matches!(
+            tracker.end_job(complete(&jobs[0])).await,
+            Err(Error::LeaseLost(uuid)) if uuid == jobs[0].uuid()
+        );
+
+        // once the lease ends the partition can be handed out again
+        let again = tracker.get_jobs("c", 0).await;
+        assert_eq!(again.len(), 1);
+        assert_eq!(again[0].partition_id, jobs[0].partition_id);
+        assert_ne!(again[0].uuid(), jobs[0].uuid());
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_renews_lease() {
+        let (tracker, time_provider, _metrics) = tracker(&[1]);
+
+        let jobs = tracker.get_jobs("a", 0).await;
+        assert_eq!(jobs.len(), 1);
+
+        time_provider.inc(LEASE / 2);
+        assert!(tracker.heartbeat([jobs[0].uuid()]).is_empty());
+        time_provider.inc(LEASE / 2);
+        assert!(tracker.heartbeat([jobs[0].uuid()]).is_empty());
+
+        assert!(tracker.get_jobs("b", 0).await.is_empty());
+        tracker.end_job(complete(&jobs[0])).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_expired_lease_is_reassigned() {
+        let (tracker, time_provider, metrics) = tracker(&[1]);
+
+        let jobs = tracker.get_jobs("a", 0).await;
+        assert_eq!(jobs.len(), 1);
+
+        time_provider.inc(LEASE);
+        let reassigned = tracker.get_jobs("b", 0).await;
+        assert_eq!(reassigned.len(), 1);
+        assert_eq!(reassigned[0].partition_id, jobs[0].partition_id);
+        assert_ne!(reassigned[0].uuid(), jobs[0].uuid());
+
+        // the previous holder lost the lease
+        assert_eq!(tracker.heartbeat([jobs[0].uuid()]), vec![jobs[0].uuid()]);
+        assert_matches!(
+            tracker.end_job(complete(&jobs[0])).await,
+            Err(Error::LeaseLost(_))
+        );
+        tracker.end_job(complete(&reassigned[0])).await.unwrap();
+
+        let expired = metrics
+            .get_instrument::<Metric<U64Counter>>("compaction_scheduler_lease_expired")
+            .unwrap()
+            .get_observer(&Attributes::from(&[]))
+            .unwrap()
+            .fetch();
+        assert_eq!(expired, 1);
+    }
+}
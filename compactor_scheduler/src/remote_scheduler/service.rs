@@ -0,0 +1,128 @@
+//! gRPC service exposing a [`Scheduler`] to remote compactors.
+
+use std::{sync::Arc, time::Duration};
+
+use generated_types::influxdata::iox::compactor::v1::{
+    self as proto, compaction_scheduler_service_server,
+};
+use iox_time::TimeProvider;
+use observability_deps::tracing::warn;
+use tonic::{Request, Response, Status};
+use uuid::Uuid;
+
+use super::lease::{Error as LeaseError, LeaseTracker};
+use crate::{CompactionJobEnd, CompactionJobStatus, Scheduler};
+
+/// Implementation of the compaction scheduler gRPC service.
+///
+/// Jobs of the wrapped [`Scheduler`] are leased to the calling compactors, see
+/// [`RemoteScheduler`](crate::RemoteScheduler) for the client side.
+#[derive(Debug)]
+pub struct SchedulerService {
+    leases: LeaseTracker,
+}
+
+impl SchedulerService {
+    /// Serve jobs of `scheduler`, expiring leases that are not renewed within `lease_duration`.
+    pub fn new(
+        scheduler: Arc<dyn Scheduler>,
+        time_provider: Arc<dyn TimeProvider>,
+        lease_duration: Duration,
+        metrics: &metric::Registry,
+    ) -> Self {
+        Self {
+            leases: LeaseTracker::new(scheduler, time_provider, lease_duration, metrics),
+        }
+    }
+
+    /// Wrap this service into a tonic server.
+    pub fn into_server(
+        self: Arc<Self>,
+    ) -> compaction_scheduler_service_server::CompactionSchedulerServiceServer<Self> {
+        compaction_scheduler_service_server::CompactionSchedulerServiceServer::from_arc(self)
+    }
+}
+
+impl From<LeaseError> for Status {
+    fn from(e: LeaseError) -> Self {
+        match e {
+            LeaseError::LeaseLost(_) => Self::failed_precondition(e.to_string()),
+            LeaseError::Scheduler(_) => Self::internal(e.to_string()),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl compaction_scheduler_service_server::CompactionSchedulerService for SchedulerService {
+    async fn get_jobs(
+        &self,
+        request: Request<proto::GetJobsRequest>,
+    ) -> Result<Response<proto::GetJobsResponse>, Status> {
+        let request = request.into_inner();
+
+        let jobs = self
+            .leases
+            .get_jobs(&request.compactor_id, request.max_jobs as usize)
+            .await;
+
+        Ok(Response::new(proto::GetJobsResponse {
+            jobs: jobs.into_iter().map(Into::into).collect(),
+            lease_duration_ns: self.leases.lease_duration().as_nanos() as i64,
+        }))
+    }
+
+    async fn heartbeat(
+        &self,
+        request: Request<proto::HeartbeatRequest>,
+    ) -> Result<Response<proto::HeartbeatResponse>, Status> {
+        let uuids = request
+            .into_inner()
+            .job_uuids
+            .iter()
+            .map(|uuid| uuid.parse::<Uuid>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Status::invalid_argument(format!("invalid job uuid: {e}")))?;
+
+        let lost = self.leases.heartbeat(uuids);
+
+        Ok(Response::new(proto::HeartbeatResponse {
+            lost_job_uuids: lost.into_iter().map(|uuid| uuid.to_string()).collect(),
+        }))
+    }
+
+    async fn update_job_status(
+        &self,
+        request: Request<proto::UpdateJobStatusRequest>,
+    ) -> Result<Response<proto::UpdateJobStatusResponse>, Status> {
+        let job_status = CompactionJobStatus::try_from(request.into_inner())
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let job = job_status.job.clone();
+
+        let response = self
+            .leases
+            .update_job_status(job_status)
+            .await
+            .map_err(|e| {
+                warn!(error=%e, ?job, "failed to update compaction job status");
+                Status::from(e)
+            })?;
+
+        Ok(Response::new(response.into()))
+    }
+
+    async fn end_job(
+        &self,
+        request: Request<proto::EndJobRequest>,
+    ) -> Result<Response<proto::EndJobResponse>, Status> {
+        let end = CompactionJobEnd::try_from(request.into_inner())
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let job = end.job.clone();
+
+        self.leases.end_job(end).await.map_err(|e| {
+            warn!(error=%e, ?job, "failed to end compaction job");
+            Status::from(e)
+        })?;
+
+        Ok(Response::new(proto::EndJobResponse {}))
+    }
+}
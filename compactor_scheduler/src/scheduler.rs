@@ -7,13 +7,17 @@ use async_trait::async_trait;
 use data_types::{CompactionLevel, ParquetFile, ParquetFileId, ParquetFileParams, PartitionId};
 use uuid::Uuid;
 
-use crate::{CommitWrapper, ErrorKind, LocalSchedulerConfig, PartitionsSourceConfig};
+use crate::{
+    CommitWrapper, ErrorKind, LocalSchedulerConfig, PartitionsSourceConfig, RemoteSchedulerConfig,
+};
 
 /// Scheduler configuration.
 #[derive(Debug, Clone)]
 pub enum SchedulerConfig {
     /// Configuration specific to the [`LocalScheduler`](crate::LocalScheduler).
     Local(LocalSchedulerConfig),
+    /// Configuration specific to the [`RemoteScheduler`](crate::RemoteScheduler).
+    Remote(RemoteSchedulerConfig),
 }
 
 impl SchedulerConfig {
@@ -57,6 +61,9 @@ impl std::fmt::Display for SchedulerConfig {
                     write!(f, "local_compaction_scheduler_cfg(commit_wrapper=Some)",)
                 }
             },
+            SchedulerConfig::Remote(RemoteSchedulerConfig { address, .. }) => {
+                write!(f, "remote_compaction_scheduler_cfg({address})")
+            }
         }
     }
 }
@@ -64,7 +71,6 @@ impl std::fmt::Display for SchedulerConfig {
 /// Job assignment for a given partition.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CompactionJob {
-    #[allow(dead_code)]
     /// Unique identifier for this job.
     /// Should not be the same as the partition id.
     uuid: Uuid,
@@ -81,6 +87,11 @@ impl CompactionJob {
         }
     }
 
+    /// Re-create a job handed out by a remote scheduler.
+    pub(crate) fn new_with_uuid(uuid: Uuid, partition_id: PartitionId) -> Self {
+        Self { uuid, partition_id }
+    }
+
     /// Get job uuid.
     pub fn uuid(&self) -> Uuid {
         self.uuid
@@ -0,0 +1,153 @@
+use std::{sync::Arc, time::Duration};
+
+use assert_matches::assert_matches;
+use compactor_scheduler::{
+    create_scheduler, CompactionJobEnd, CompactionJobEndVariant, RemoteSchedulerConfig, Scheduler,
+    SchedulerConfig, SchedulerService,
+};
+use tokio::{net::TcpListener, task::JoinHandle};
+use tonic::transport::{server::TcpIncoming, Server};
+
+use super::{helpers, local_scheduler::TestLocalScheduler};
+
+const LEASE_DURATION: Duration = Duration::from_secs(60);
+
+/// Serves a [`TestLocalScheduler`] via the [`SchedulerService`].
+#[derive(Debug)]
+struct TestRemoteScheduler {
+    local: TestLocalScheduler,
+    address: String,
+    server: JoinHandle<Result<(), tonic::transport::Error>>,
+}
+
+impl TestRemoteScheduler {
+    async fn builder() -> Self {
+        let local = TestLocalScheduler::builder().await;
+
+        let listener = TcpListener::bind("localhost:0").await.unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let incoming = TcpIncoming::from_listener(listener, false, None).unwrap();
+
+        let service = Arc::new(SchedulerService::new(
+            Arc::clone(&local.scheduler),
+            local.catalog.time_provider(),
+            LEASE_DURATION,
+            &metric::Registry::default(),
+        ));
+        let server = tokio::spawn(
+            Server::builder()
+                .add_service(service.into_server())
+                .serve_with_incoming(incoming),
+        );
+
+        Self {
+            local,
+            address,
+            server,
+        }
+    }
+
+    /// Create a new client, acting as a separate compactor.
+    fn client(&self, compactor_id: &str) -> Arc<dyn Scheduler> {
+        create_scheduler(
+            SchedulerConfig::Remote(RemoteSchedulerConfig {
+                address: self.address.clone(),
+                compactor_id: compactor_id.to_owned(),
+                max_jobs: 0,
+                heartbeat_interval: Duration::from_secs(3600),
+            }),
+            self.local.catalog.catalog(),
+            self.local.catalog.time_provider(),
+            Arc::new(metric::Registry::default()),
+            false,
+        )
+    }
+}
+
+impl Drop for TestRemoteScheduler {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+#[tokio::test]
+async fn test_remote_job_lifecycle() {
+    test_helpers::maybe_start_logging();
+
+    let test_scheduler = TestRemoteScheduler::builder().await;
+    let scheduler = test_scheduler.client("compactor-0");
+    assert_eq!(
+        scheduler.to_string(),
+        format!("remote_compaction_scheduler({})", test_scheduler.address)
+    );
+
+    let jobs = scheduler.get_jobs().await;
+    test_scheduler
+        .local
+        .assert_matches_seeded_hot_partition(&jobs);
+    helpers::assert_all_partitions_leased(Arc::clone(&scheduler)).await;
+
+    // commit, report an error and end the job through the service
+    let (existing_1, existing_2) = test_scheduler.local.get_seeded_files();
+    helpers::can_do_upgrade_commit(Arc::clone(&scheduler), jobs[0].clone(), existing_1).await;
+    helpers::can_do_replacement_commit(
+        Arc::clone(&scheduler),
+        jobs[0].clone(),
+        vec![existing_2],
+        vec![
+            test_scheduler
+                .local
+                .create_params_for_new_parquet_file()
+                .await,
+        ],
+    )
+    .await;
+    helpers::can_send_error(Arc::clone(&scheduler), jobs[0].clone()).await;
+    helpers::can_do_complete(Arc::clone(&scheduler), jobs[0].clone()).await;
+
+    // TEST: partition is available again
+    let jobs = scheduler.get_jobs().await;
+    test_scheduler
+        .local
+        .assert_matches_seeded_hot_partition(&jobs);
+}
+
+#[tokio::test]
+async fn test_expired_lease_is_reassigned() {
+    test_helpers::maybe_start_logging();
+
+    let test_scheduler = TestRemoteScheduler::builder().await;
+    let scheduler_1 = test_scheduler.client("compactor-0");
+    let scheduler_2 = test_scheduler.client("compactor-1");
+
+    let jobs_1 = scheduler_1.get_jobs().await;
+    test_scheduler
+        .local
+        .assert_matches_seeded_hot_partition(&jobs_1);
+    helpers::assert_all_partitions_leased(Arc::clone(&scheduler_2)).await;
+
+    // compactor-0 stops renewing its lease
+    test_scheduler
+        .local
+        .catalog
+        .mock_time_provider()
+        .inc(LEASE_DURATION);
+
+    // TEST: partition is handed out to compactor-1
+    let jobs_2 = scheduler_2.get_jobs().await;
+    test_scheduler
+        .local
+        .assert_matches_seeded_hot_partition(&jobs_2);
+    assert_ne!(jobs_1[0].uuid(), jobs_2[0].uuid());
+
+    // TEST: compactor-0 can no longer end the job
+    let res = scheduler_1
+        .end_job(CompactionJobEnd {
+            job: jobs_1[0].clone(),
+            end_action: CompactionJobEndVariant::Complete,
+        })
+        .await;
+    assert_matches!(res, Err(_), "expected lost lease, got {:?}", res);
+
+    helpers::can_do_complete(Arc::clone(&scheduler_2), jobs_2[0].clone()).await;
+}
@@ -19,6 +19,7 @@ use workspace_hack as _;
 
 mod commit_wrapper;
 mod display;
+pub mod parquet_scan;
 mod simulator;
 
 pub use display::{display_format, display_size, format_files, format_files_split, format_ranges};
@@ -58,6 +59,7 @@ use iox_tests::{
 use iox_time::{MockProvider, Time, TimeProvider};
 use object_store::{path::Path, DynObjectStore};
 use parquet_file::storage::{ParquetStorage, StorageId};
+use predicate::delete_predicate::TombstonePredicates;
 use schema::sort::SortKey;
 use trace::{RingBufferTraceCollector, TraceCollector};
 use tracker::AsyncSemaphoreMetrics;
@@ -630,6 +632,17 @@ impl<const WITH_FILES: bool> TestSetupBuilder<WITH_FILES> {
 
     /// Create a [`TestSetup`]
     pub async fn build(self) -> TestSetup {
+        let tombstones = self
+            .catalog
+            .catalog()
+            .repositories()
+            .await
+            .tombstones()
+            .list_by_table_id(self.table.table.id)
+            .await
+            .unwrap();
+        let tombstones = TombstonePredicates::try_new(tombstones).unwrap();
+
         let candidate_partition = Arc::new(PartitionInfo {
             partition_id: self.partition.partition.id,
             partition_hash_id: self.partition.partition.hash_id().cloned(),
@@ -639,6 +652,7 @@ impl<const WITH_FILES: bool> TestSetupBuilder<WITH_FILES> {
             table_schema: Arc::new(self.table.catalog_schema().await),
             sort_key: self.partition.partition.sort_key(),
             partition_key: self.partition.partition.partition_key.clone(),
+            tombstones,
         });
 
         TestSetup {
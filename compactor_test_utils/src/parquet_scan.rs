@@ -0,0 +1,166 @@
+//! Helpers to scan a compacted parquet file with a selective predicate, with
+//! and without the querier's page pruning option, and report the bytes read
+//! from object storage.
+
+use std::{fmt::Write, sync::Arc};
+
+use data_types::CompactionLevel;
+use datafusion::{
+    common::ToDFSchema,
+    datasource::{
+        listing::PartitionedFile,
+        physical_plan::{FileScanConfig, ParquetExec},
+    },
+    physical_expr::{create_physical_expr, execution_props::ExecutionProps},
+    physical_plan::{collect, ExecutionPlan, Statistics},
+    prelude::{col, lit, Expr, SessionContext},
+    scalar::ScalarValue,
+};
+use datafusion_util::config::{
+    iox_session_config, register_iox_object_store, PARQUET_PAGE_PRUNING_OPTION,
+};
+use iox_tests::TestParquetFileBuilder;
+use parquet_file::{storage::ParquetExecInput, ParquetFilePath};
+
+use crate::TestSetup;
+
+const TAG1_CARDINALITY: usize = 10;
+const TAG2_CARDINALITY: usize = 100;
+
+/// Generate `rows` of line protocol for the test table, one row per
+/// nanosecond.
+fn generate_lp(rows: usize) -> String {
+    let mut buf = String::new();
+    for i in 0..rows {
+        writeln!(
+            &mut buf,
+            "table,tag1=A{},tag2=B{},tag3=C field_int={i}i {i}",
+            i % TAG1_CARDINALITY,
+            i % TAG2_CARDINALITY,
+        )
+        .unwrap();
+    }
+    buf
+}
+
+/// Write a single L2 file of `rows` rows to the test partition, returning the
+/// test setup and the object store input to scan the file with.
+pub async fn init(rows: usize) -> (TestSetup, ParquetExecInput) {
+    let setup = TestSetup::builder().await.build().await;
+
+    let builder = TestParquetFileBuilder::default()
+        .with_line_protocol(&generate_lp(rows))
+        .with_min_time(0)
+        .with_max_time(rows as i64 - 1)
+        .with_compaction_level(CompactionLevel::Final);
+    let file = setup.partition.create_parquet_file(builder).await;
+
+    let input = setup.catalog.parquet_store.parquet_exec_input(
+        &ParquetFilePath::from(&file.parquet_file),
+        file.parquet_file.file_size_bytes as usize,
+    );
+
+    (setup, input)
+}
+
+/// A predicate selecting the second half of the rows with one `tag1` value.
+///
+/// The file is sorted on tag1 first, so the matching rows are contiguous and
+/// most pages can be skipped.
+pub fn selective_predicate(rows: usize) -> Expr {
+    col("tag1")
+        .eq(lit("A3"))
+        .and(col("time").gt_eq(lit(ScalarValue::TimestampNanosecond(
+            Some(rows as i64 / 2),
+            None,
+        ))))
+}
+
+/// A session with the querier's page pruning option set to `enabled`.
+pub fn session(setup: &TestSetup, enabled: bool) -> SessionContext {
+    let config = iox_session_config().set_bool(PARQUET_PAGE_PRUNING_OPTION, enabled);
+    let ctx = SessionContext::with_config(config);
+
+    let store = &setup.catalog.parquet_store;
+    register_iox_object_store(
+        ctx.runtime_env(),
+        store.id(),
+        Arc::clone(store.object_store()),
+    );
+
+    ctx
+}
+
+/// Build a scan of `input` filtered by `predicate`.
+pub async fn scan(
+    setup: &TestSetup,
+    input: &ParquetExecInput,
+    predicate: &Expr,
+) -> Arc<ParquetExec> {
+    let schema = setup.table.schema().await.as_arrow();
+    let predicate = create_physical_expr(
+        predicate,
+        &Arc::clone(&schema).to_dfschema().unwrap(),
+        &schema,
+        &ExecutionProps::new(),
+    )
+    .unwrap();
+
+    let config = FileScanConfig {
+        object_store_url: input.object_store_url.clone(),
+        file_schema: schema,
+        file_groups: vec![vec![PartitionedFile {
+            object_meta: input.object_meta.clone(),
+            partition_values: vec![],
+            range: None,
+            extensions: None,
+        }]],
+        statistics: Statistics::default(),
+        projection: None,
+        limit: None,
+        table_partition_cols: vec![],
+        output_ordering: vec![],
+        infinite_source: false,
+    };
+
+    Arc::new(ParquetExec::new(config, Some(predicate), None))
+}
+
+/// Run `exec` to completion, returning the number of bytes read from object
+/// storage.
+pub async fn run(exec: Arc<ParquetExec>, ctx: &SessionContext) -> usize {
+    collect(Arc::clone(&exec) as _, ctx.task_ctx())
+        .await
+        .expect("scan failed");
+
+    exec.metrics()
+        .and_then(|m| m.sum_by_name("bytes_scanned"))
+        .map(|v| v.as_usize())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_page_pruning_reduces_bytes_scanned() {
+        const ROWS: usize = 100_000;
+
+        let (setup, input) = init(ROWS).await;
+        let predicate = selective_predicate(ROWS);
+
+        let mut bytes = vec![];
+        for enabled in [false, true] {
+            let ctx = session(&setup, enabled);
+            bytes.push(run(scan(&setup, &input, &predicate).await, &ctx).await);
+        }
+
+        let (without, with) = (bytes[0], bytes[1]);
+        assert!(without > 0);
+        assert!(
+            with < without,
+            "page pruning read {with} bytes, without it {without} bytes"
+        );
+    }
+}
@@ -274,6 +274,27 @@ impl std::fmt::Display for ParquetFileId {
     }
 }
 
+/// Unique ID for a `Tombstone`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct TombstoneId(i64);
+
+#[allow(missing_docs)]
+impl TombstoneId {
+    pub fn new(v: i64) -> Self {
+        Self(v)
+    }
+    pub fn get(&self) -> i64 {
+        self.0
+    }
+}
+
+impl std::fmt::Display for TombstoneId {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// Data object for a namespace
 #[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
 pub struct Namespace {
@@ -287,6 +308,9 @@ pub struct Namespace {
     pub max_tables: MaxTables,
     /// The maximum number of columns per table in this namespace
     pub max_columns_per_table: MaxColumnsPerTable,
+    /// Limits on the queries run against this namespace
+    #[sqlx(flatten)]
+    pub query_limits: NamespaceQueryLimits,
     /// When this file was marked for deletion.
     pub deleted_at: Option<Timestamp>,
     /// The partition template to use for new tables in this namespace either created implicitly or
@@ -784,6 +808,52 @@ impl From<ParquetFile> for ParquetFileParams {
     }
 }
 
+/// Data object for a tombstone: a predicate delete recorded against a table.
+///
+/// A tombstone applies to all the rows of the table that match the
+/// predicate and that were persisted to a parquet file created at or before
+/// [`Tombstone::created_at`]. Data written after the tombstone was created is
+/// not affected.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, sqlx::FromRow)]
+pub struct Tombstone {
+    /// the id of the tombstone
+    pub id: TombstoneId,
+    /// the table the tombstone is associated with
+    pub table_id: TableId,
+    /// the inclusive min time of the delete predicate
+    pub min_time: Timestamp,
+    /// the exclusive max time of the delete predicate
+    pub max_time: Timestamp,
+    /// the full delete predicate
+    pub serialized_predicate: String,
+    /// the time at which the tombstone was recorded in the catalog
+    pub created_at: Timestamp,
+}
+
+impl Tombstone {
+    /// Returns true if this tombstone must be applied to the rows of `file`.
+    ///
+    /// This is the case when the file belongs to the same table, contains only
+    /// data written no later than the creation of this tombstone, and its time
+    /// range overlaps the deleted time range.
+    ///
+    /// The write time of the data in a file is bounded by its
+    /// `max_l0_created_at`, which for files persisted by an ingester is the
+    /// time the data was snapshot from the buffer, and for compacted files is
+    /// carried over from the input files.
+    pub fn applies_to(&self, file: &ParquetFile) -> bool {
+        file.table_id == self.table_id
+            && file.max_l0_created_at <= self.created_at
+            && file.min_time < self.max_time
+            && file.max_time >= self.min_time
+    }
+
+    /// Estimate the memory consumption of this object and its contents
+    pub fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.serialized_predicate.capacity()
+    }
+}
+
 /// ID of a chunk.
 ///
 /// This ID is unique within a single partition.
@@ -1034,6 +1104,96 @@ impl std::fmt::Display for Scalar {
     }
 }
 
+impl From<&DeletePredicate> for generated_types::influxdata::iox::predicate::v1::Predicate {
+    fn from(v: &DeletePredicate) -> Self {
+        use generated_types::influxdata::iox::predicate::v1 as proto;
+
+        Self {
+            range: Some(proto::TimestampRange {
+                start: v.range.start(),
+                end: v.range.end(),
+            }),
+            exprs: v
+                .exprs
+                .iter()
+                .map(|e| proto::Expr {
+                    column: e.column.clone(),
+                    op: match e.op {
+                        Op::Eq => proto::Op::Eq,
+                        Op::Ne => proto::Op::Ne,
+                    }
+                    .into(),
+                    scalar: Some(proto::Scalar {
+                        value: Some(match &e.scalar {
+                            Scalar::Bool(v) => proto::scalar::Value::ValueBool(*v),
+                            Scalar::I64(v) => proto::scalar::Value::ValueI64(*v),
+                            Scalar::F64(v) => proto::scalar::Value::ValueF64(v.into_inner()),
+                            Scalar::String(v) => proto::scalar::Value::ValueString(v.clone()),
+                        }),
+                    }),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Errors deserialising a protobuf serialised [`DeletePredicate`].
+#[derive(Debug, Error)]
+pub enum DeletePredicateProtoError {
+    /// The predicate does not specify a time range.
+    #[error("no time range specified for delete predicate")]
+    NoTimeRange,
+
+    /// An expression uses an unknown or unspecified operator.
+    #[error("invalid operator in delete expression on column {0}")]
+    InvalidOp(String),
+
+    /// An expression has no scalar value to compare against.
+    #[error("no scalar value in delete expression on column {0}")]
+    NoScalar(String),
+}
+
+impl TryFrom<generated_types::influxdata::iox::predicate::v1::Predicate> for DeletePredicate {
+    type Error = DeletePredicateProtoError;
+
+    fn try_from(
+        v: generated_types::influxdata::iox::predicate::v1::Predicate,
+    ) -> Result<Self, Self::Error> {
+        use generated_types::influxdata::iox::predicate::v1 as proto;
+
+        let range = v.range.ok_or(DeletePredicateProtoError::NoTimeRange)?;
+
+        let exprs = v
+            .exprs
+            .into_iter()
+            .map(|e| {
+                let op = match proto::Op::from_i32(e.op) {
+                    Some(proto::Op::Eq) => Op::Eq,
+                    Some(proto::Op::Ne) => Op::Ne,
+                    Some(proto::Op::Unspecified) | None => {
+                        return Err(DeletePredicateProtoError::InvalidOp(e.column))
+                    }
+                };
+
+                let scalar = match e.scalar.and_then(|v| v.value) {
+                    Some(proto::scalar::Value::ValueBool(v)) => Scalar::Bool(v),
+                    Some(proto::scalar::Value::ValueI64(v)) => Scalar::I64(v),
+                    Some(proto::scalar::Value::ValueF64(v)) => Scalar::F64(v.into()),
+                    Some(proto::scalar::Value::ValueString(v)) => Scalar::String(v),
+                    None => return Err(DeletePredicateProtoError::NoScalar(e.column)),
+                };
+
+                Ok(DeleteExpr::new(e.column, op, scalar))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            range: TimestampRange::new(range.start, range.end),
+            exprs,
+        })
+    }
+}
+
 /// A string that cannot be empty
 ///
 /// This is particularly useful for types that map to/from protobuf, where string fields
@@ -1946,6 +2106,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_delete_predicate_proto_round_trip() {
+        let pred = DeletePredicate {
+            range: TimestampRange::new(1, 42),
+            exprs: vec![
+                DeleteExpr::new(String::from("a"), Op::Eq, Scalar::Bool(true)),
+                DeleteExpr::new(String::from("b"), Op::Ne, Scalar::I64(-1)),
+                DeleteExpr::new(String::from("c"), Op::Eq, Scalar::F64(OrderedFloat(1.5))),
+                DeleteExpr::new(String::from("d"), Op::Ne, Scalar::String(String::from("x"))),
+            ],
+        };
+
+        let proto = generated_types::influxdata::iox::predicate::v1::Predicate::from(&pred);
+        let got = DeletePredicate::try_from(proto).expect("round trip must succeed");
+
+        assert_eq!(got, pred);
+    }
+
+    #[test]
+    fn test_delete_predicate_proto_no_range() {
+        let proto = generated_types::influxdata::iox::predicate::v1::Predicate {
+            range: None,
+            exprs: vec![],
+        };
+
+        assert_matches::assert_matches!(
+            DeletePredicate::try_from(proto),
+            Err(DeletePredicateProtoError::NoTimeRange)
+        );
+    }
+
     #[test]
     fn statistics_new_non_null() {
         let actual = StatValues::new_non_null(Some(-1i64), Some(1i64), 3);
@@ -71,6 +71,90 @@ impl std::fmt::Display for MaxColumnsPerTable {
     }
 }
 
+/// Max queries allowed to run concurrently against a namespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct MaxConcurrentQueries(i32);
+
+#[allow(missing_docs)]
+impl MaxConcurrentQueries {
+    pub const fn new(v: i32) -> Self {
+        Self(v)
+    }
+
+    pub fn get(&self) -> i32 {
+        self.0
+    }
+}
+
+impl std::fmt::Display for MaxConcurrentQueries {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Max bytes of memory a single query against a namespace may reserve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct MaxQueryMemoryBytes(i64);
+
+#[allow(missing_docs)]
+impl MaxQueryMemoryBytes {
+    pub const fn new(v: i64) -> Self {
+        Self(v)
+    }
+
+    pub fn get(&self) -> i64 {
+        self.0
+    }
+}
+
+impl std::fmt::Display for MaxQueryMemoryBytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Max wall-clock time in milliseconds a single query against a namespace may run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct QueryTimeout(i64);
+
+#[allow(missing_docs)]
+impl QueryTimeout {
+    pub const fn new(v: i64) -> Self {
+        Self(v)
+    }
+
+    pub fn get(&self) -> i64 {
+        self.0
+    }
+
+    /// The timeout as a [`Duration`](std::time::Duration).
+    pub fn as_duration(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.0 as u64)
+    }
+}
+
+impl std::fmt::Display for QueryTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}ms", self.0)
+    }
+}
+
+/// Per-namespace limits on the queries run by the querier.
+///
+/// A limit that is not set is not enforced per namespace, only the querier-wide limits apply.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, sqlx::FromRow)]
+pub struct NamespaceQueryLimits {
+    /// The maximum number of queries running concurrently against this namespace
+    pub max_concurrent_queries: Option<MaxConcurrentQueries>,
+    /// The maximum number of bytes of memory a single query may reserve
+    pub max_query_memory_bytes: Option<MaxQueryMemoryBytes>,
+    /// The maximum wall-clock time a single query may run
+    pub query_timeout_ms: Option<QueryTimeout>,
+}
+
 /// Overrides for service protection limits.
 #[derive(Debug, Copy, Clone)]
 pub struct NamespaceServiceProtectionLimitsOverride {
@@ -102,6 +186,15 @@ pub enum ServiceLimitUpdate {
     /// Requesting an update to the maximum number of columns allowed in each table in this
     /// namespace
     MaxColumnsPerTable(MaxColumnsPerTable),
+    /// Requesting an update to the maximum number of queries running concurrently against this
+    /// namespace
+    MaxConcurrentQueries(MaxConcurrentQueries),
+    /// Requesting an update to the maximum number of bytes of memory a single query against this
+    /// namespace may reserve
+    MaxQueryMemoryBytes(MaxQueryMemoryBytes),
+    /// Requesting an update to the maximum wall-clock time a single query against this namespace
+    /// may run
+    QueryTimeout(QueryTimeout),
 }
 
 /// Errors converting from raw values to the service limits
@@ -135,6 +228,28 @@ impl TryFrom<Option<LimitUpdate>> for ServiceLimitUpdate {
                     MaxColumnsPerTable::new(n),
                 ))
             }
+            Some(LimitUpdate::MaxConcurrentQueries(n)) => {
+                if n <= 0 {
+                    return Err(ServiceLimitError::MustBeGreaterThanZero);
+                }
+                Ok(ServiceLimitUpdate::MaxConcurrentQueries(
+                    MaxConcurrentQueries::new(n),
+                ))
+            }
+            Some(LimitUpdate::MaxQueryMemoryBytes(n)) => {
+                if n <= 0 {
+                    return Err(ServiceLimitError::MustBeGreaterThanZero);
+                }
+                Ok(ServiceLimitUpdate::MaxQueryMemoryBytes(
+                    MaxQueryMemoryBytes::new(n),
+                ))
+            }
+            Some(LimitUpdate::QueryTimeoutMs(n)) => {
+                if n <= 0 {
+                    return Err(ServiceLimitError::MustBeGreaterThanZero);
+                }
+                Ok(ServiceLimitUpdate::QueryTimeout(QueryTimeout::new(n)))
+            }
             None => Err(ServiceLimitError::NoValueSpecified),
         }
     }
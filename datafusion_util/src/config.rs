@@ -14,6 +14,12 @@ pub const DEFAULT_SCHEMA: &str = "iox";
 /// The maximum number of rows that DataFusion should create in each RecordBatch
 pub const BATCH_SIZE: usize = 8 * 1024;
 
+/// The DataFusion option that controls page-level pruning of parquet scans.
+///
+/// With this enabled, the page index of a parquet file is used to skip data
+/// pages that cannot match the scan predicate.
+pub const PARQUET_PAGE_PRUNING_OPTION: &str = "datafusion.execution.parquet.enable_page_index";
+
 /// Return a SessionConfig object configured for IOx
 pub fn iox_session_config() -> SessionConfig {
     // Enable parquet predicate pushdown optimization
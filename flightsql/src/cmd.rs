@@ -2,30 +2,51 @@
 
 use std::fmt::Display;
 
+use arrow::{
+    ipc::{reader::StreamReader, writer::StreamWriter},
+    record_batch::RecordBatch,
+};
 use arrow_flight::sql::{
     ActionClosePreparedStatementRequest, ActionCreatePreparedStatementRequest, Any,
     CommandGetCatalogs, CommandGetCrossReference, CommandGetDbSchemas, CommandGetExportedKeys,
     CommandGetImportedKeys, CommandGetPrimaryKeys, CommandGetSqlInfo, CommandGetTableTypes,
     CommandGetTables, CommandGetXdbcTypeInfo, CommandPreparedStatementQuery, CommandStatementQuery,
 };
-use bytes::Bytes;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 use prost::Message;
-use snafu::ResultExt;
+use snafu::{OptionExt, ResultExt};
 
 use crate::error::*;
 
+/// Leading byte of an encoded [`PreparedStatementHandle`] carrying bound
+/// parameter values.
+///
+/// `0xFF` never appears in valid UTF-8, so these handles can not be confused
+/// with handles containing only the query text.
+const BOUND_HANDLE_MARKER: u8 = 0xFF;
+
 /// Represents a prepared statement "handle". IOx passes all state
 /// required to run the prepared statement back and forth to the
 /// client, so any querier instance can run it
+///
+/// This includes the parameter values bound through `DoPut`, if any, which
+/// are returned to the client in an updated handle.
 #[derive(Debug, Clone, PartialEq)]
 pub struct PreparedStatementHandle {
     /// The raw SQL query text
     query: String,
+
+    /// The bound parameter values, a single row with one column per
+    /// parameter
+    params: Option<RecordBatch>,
 }
 
 impl PreparedStatementHandle {
     pub fn new(query: String) -> Self {
-        Self { query }
+        Self {
+            query,
+            params: None,
+        }
     }
 
     /// return the query
@@ -33,29 +54,94 @@ impl PreparedStatementHandle {
         self.query.as_ref()
     }
 
+    /// return the bound parameter values, if any
+    pub fn params(&self) -> Option<&RecordBatch> {
+        self.params.as_ref()
+    }
+
+    /// Bind `params` to this handle, replacing any previously bound values
+    pub fn with_params(self, params: RecordBatch) -> Self {
+        Self {
+            params: Some(params),
+            ..self
+        }
+    }
+
     fn try_decode(handle: Bytes) -> Result<Self> {
         // Note: in IOx  handles are the entire decoded query
         // It will likely need to get more sophisticated as part of
         // https://github.com/influxdata/influxdb_iox/issues/6699
+        if handle.first() == Some(&BOUND_HANDLE_MARKER) {
+            return Self::try_decode_bound(handle.slice(1..));
+        }
+
         let query = String::from_utf8(handle.to_vec()).context(InvalidHandleSnafu)?;
-        Ok(Self { query })
+        Ok(Self::new(query))
     }
 
-    fn encode(self) -> Bytes {
-        Bytes::from(self.query.into_bytes())
+    /// Decode a handle with bound parameters, encoded as the big endian `u32`
+    /// length of the query, the query text and an Arrow IPC stream
+    /// containing the parameter values.
+    fn try_decode_bound(mut handle: Bytes) -> Result<Self> {
+        if handle.len() < 4 {
+            return MalformedHandleSnafu {
+                description: "truncated query length",
+            }
+            .fail();
+        }
+        let query_len = handle.get_u32() as usize;
+        if handle.len() < query_len {
+            return MalformedHandleSnafu {
+                description: "truncated query",
+            }
+            .fail();
+        }
+        let query = handle.split_to(query_len);
+        let query = String::from_utf8(query.to_vec()).context(InvalidHandleSnafu)?;
+
+        let params = StreamReader::try_new(handle.reader(), None)?
+            .next()
+            .transpose()?
+            .context(MalformedHandleSnafu {
+                description: "missing parameter values",
+            })?;
+
+        Ok(Self {
+            query,
+            params: Some(params),
+        })
     }
-}
 
-impl Display for PreparedStatementHandle {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Pepared({})", self.query)
+    /// Encode this handle, see [`Self::try_decode`].
+    pub fn try_encode(self) -> Result<Bytes> {
+        let Some(params) = self.params else {
+            return Ok(Bytes::from(self.query.into_bytes()));
+        };
+
+        let mut buf = BytesMut::new();
+        buf.put_u8(BOUND_HANDLE_MARKER);
+        buf.put_u32(self.query.len() as u32);
+        buf.put_slice(self.query.as_bytes());
+
+        let mut writer = StreamWriter::try_new(buf.writer(), &params.schema())?;
+        writer.write(&params)?;
+        writer.finish()?;
+
+        Ok(writer.into_inner()?.into_inner().freeze())
     }
 }
 
-/// Encode a PreparedStatementHandle as Bytes
-impl From<PreparedStatementHandle> for Bytes {
-    fn from(value: PreparedStatementHandle) -> Self {
-        Self::from(value.query.into_bytes())
+impl Display for PreparedStatementHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.params {
+            Some(params) => write!(
+                f,
+                "Pepared({}, {} bound parameters)",
+                self.query,
+                params.num_columns()
+            ),
+            None => write!(f, "Pepared({})", self.query),
+        }
     }
 }
 
@@ -306,7 +392,7 @@ impl FlightSQLCommand {
         let msg = match self {
             Self::CommandStatementQuery(cmd) => Any::pack(&cmd),
             Self::CommandPreparedStatementQuery(handle) => {
-                let prepared_statement_handle = handle.encode();
+                let prepared_statement_handle = handle.try_encode()?;
                 let cmd = CommandPreparedStatementQuery {
                     prepared_statement_handle,
                 };
@@ -324,7 +410,7 @@ impl FlightSQLCommand {
             Self::CommandGetXdbcTypeInfo(cmd) => Any::pack(&cmd),
             Self::ActionCreatePreparedStatementRequest(cmd) => Any::pack(&cmd),
             Self::ActionClosePreparedStatementRequest(handle) => {
-                let prepared_statement_handle = handle.encode();
+                let prepared_statement_handle = handle.try_encode()?;
                 Any::pack(&ActionClosePreparedStatementRequest {
                     prepared_statement_handle,
                 })
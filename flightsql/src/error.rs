@@ -1,7 +1,7 @@
 //! FlightSQL errors
 use std::string::FromUtf8Error;
 
-use arrow::error::ArrowError;
+use arrow::{datatypes::DataType, error::ArrowError};
 use arrow_flight::error::FlightError;
 use datafusion::error::DataFusionError;
 use prost::DecodeError;
@@ -19,6 +19,30 @@ pub enum Error {
     #[snafu(display("Invalid PreparedStatement handle (invalid UTF-8:) {}", source))]
     InvalidHandle { source: FromUtf8Error },
 
+    #[snafu(display("Invalid PreparedStatement handle: {}", description))]
+    MalformedHandle { description: String },
+
+    #[snafu(display("Invalid prepared statement parameters: {}", description))]
+    InvalidParameters { description: String },
+
+    #[snafu(display(
+        "Prepared statement requires {} parameter(s), but none were bound",
+        expected
+    ))]
+    MissingParameters { expected: usize },
+
+    #[snafu(display(
+        "Invalid value for parameter {}: expected {}, got {}",
+        name,
+        expected,
+        actual
+    ))]
+    InvalidParameter {
+        name: String,
+        expected: DataType,
+        actual: DataType,
+    },
+
     #[snafu(display("{}", source))]
     #[snafu(context(false))]
     Flight { source: FlightError },
@@ -45,6 +69,12 @@ impl From<Error> for DataFusionError {
         match value {
             Error::DataFusion { source } => source,
             Error::Arrow { source } => Self::ArrowError(source),
+            // Report errors in the bound parameters as planning errors, so
+            // that they are returned to the user
+            Error::MalformedHandle { .. }
+            | Error::InvalidParameters { .. }
+            | Error::MissingParameters { .. }
+            | Error::InvalidParameter { .. } => Self::Plan(value.to_string()),
             value => Self::External(Box::new(value)),
         }
     }
@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use arrow::{
     array::{ArrayRef, StringArray},
+    compute::{cast_with_options, concat_batches, CastOptions},
     datatypes::{DataType, Field, Schema, SchemaRef},
     error::ArrowError,
     ipc::writer::IpcWriteOptions,
@@ -22,12 +23,15 @@ use bytes::Bytes;
 use datafusion::{
     logical_expr::{LogicalPlan, TableType},
     physical_plan::ExecutionPlan,
+    scalar::ScalarValue,
     sql::TableReference,
 };
+use generated_types::influxdata::iox::querier::v1::DoPutPreparedStatementResult;
 use iox_query::{exec::IOxSessionContext, QueryNamespace};
 use observability_deps::tracing::debug;
 use once_cell::sync::Lazy;
 use prost::Message;
+use snafu::OptionExt;
 
 use crate::{error::*, sql_info::iox_sql_info_data, xdbc_type_info::xdbc_type_info_data};
 use crate::{FlightSQLCommand, PreparedStatementHandle};
@@ -55,7 +59,8 @@ impl FlightSQLPlanner {
                 get_schema_for_query(&query, ctx).await
             }
             FlightSQLCommand::CommandPreparedStatementQuery(handle) => {
-                get_schema_for_query(handle.query(), ctx).await
+                let plan = plan_prepared_statement(&handle, ctx).await?;
+                Ok(get_schema_for_plan(plan))
             }
             FlightSQLCommand::CommandGetSqlInfo(CommandGetSqlInfo { .. }) => {
                 Ok(iox_sql_info_data().schema())
@@ -108,7 +113,8 @@ impl FlightSQLPlanner {
             FlightSQLCommand::CommandPreparedStatementQuery(handle) => {
                 let query = handle.query();
                 debug!(%query, "Planning FlightSQL prepared query");
-                Ok(ctx.sql_to_physical_plan(query).await?)
+                let plan = plan_prepared_statement(&handle, ctx).await?;
+                Ok(ctx.create_physical_plan(&plan).await?)
             }
             FlightSQLCommand::CommandGetSqlInfo(cmd) => {
                 debug!(?cmd, "Planning GetSqlInfo query");
@@ -249,18 +255,25 @@ impl FlightSQLPlanner {
             ) => {
                 debug!(%query, "Creating prepared statement");
 
-                // todo run the planner here and actually figure out parameter schemas
-                // see https://github.com/apache/arrow-datafusion/pull/4701
-                let parameter_schema = vec![];
+                let plan = ctx.sql_to_logical_plan(&query).await?;
+
+                // The parameter schema is empty for queries without
+                // parameters
+                let parameter_types = parameter_types(&plan)?;
+                let parameter_schema = if parameter_types.is_empty() {
+                    Bytes::new()
+                } else {
+                    encode_schema(&parameter_schema(&parameter_types))?
+                };
 
-                let dataset_schema = get_schema_for_query(&query, ctx).await?;
+                let dataset_schema = get_schema_for_plan(plan);
                 let dataset_schema = encode_schema(dataset_schema.as_ref())?;
                 let handle = PreparedStatementHandle::new(query);
 
                 let result = ActionCreatePreparedStatementResult {
-                    prepared_statement_handle: Bytes::from(handle),
+                    prepared_statement_handle: handle.try_encode()?,
                     dataset_schema,
-                    parameter_schema: Bytes::from(parameter_schema),
+                    parameter_schema,
                 };
 
                 let msg = Any::pack(&result)?;
@@ -280,6 +293,206 @@ impl FlightSQLPlanner {
             .fail(),
         }
     }
+
+    /// Binds the parameter values in `batches` to the prepared statement in
+    /// `cmd`, returning the bytes of the [`DoPutPreparedStatementResult`]
+    /// containing the updated handle, for the `app_metadata` of the
+    /// [`arrow_flight::PutResult`].
+    ///
+    /// The values are type checked against the parameters of the query when
+    /// the prepared statement is planned.
+    pub fn do_put(
+        namespace_name: impl Into<String>,
+        cmd: FlightSQLCommand,
+        batches: Vec<RecordBatch>,
+    ) -> Result<Bytes> {
+        let namespace_name = namespace_name.into();
+        debug!(%namespace_name, %cmd, "Handling flightsql do_put");
+
+        match cmd {
+            FlightSQLCommand::CommandPreparedStatementQuery(handle) => {
+                let params = match batches.first() {
+                    Some(first) => concat_batches(&first.schema(), &batches)?,
+                    None => {
+                        return InvalidParametersSnafu {
+                            description: "no parameter values provided",
+                        }
+                        .fail()
+                    }
+                };
+
+                // Each row is a set of values for a single execution, only
+                // one execution per statement is supported.
+                if params.num_rows() != 1 {
+                    return InvalidParametersSnafu {
+                        description: format!(
+                            "expected a single row of parameter values, got {}",
+                            params.num_rows()
+                        ),
+                    }
+                    .fail();
+                }
+
+                debug!(
+                    query = %handle.query(),
+                    num_params = params.num_columns(),
+                    "Binding prepared statement parameters"
+                );
+
+                let result = DoPutPreparedStatementResult {
+                    prepared_statement_handle: Some(
+                        handle.with_params(params).try_encode()?.to_vec(),
+                    ),
+                };
+                Ok(result.encode_to_vec().into())
+            }
+            _ => ProtocolSnafu {
+                cmd: format!("{cmd:?}"),
+                method: "DoPut",
+            }
+            .fail(),
+        }
+    }
+}
+
+/// Plan the query of the prepared statement `handle`, substituting the bound
+/// parameter values, if any, into the plan.
+async fn plan_prepared_statement(
+    handle: &PreparedStatementHandle,
+    ctx: &IOxSessionContext,
+) -> Result<LogicalPlan> {
+    let plan = ctx.sql_to_logical_plan(handle.query()).await?;
+    let parameter_types = parameter_types(&plan)?;
+
+    match handle.params() {
+        Some(params) => {
+            let values = bind_parameters(&parameter_types, params)?;
+            Ok(plan.replace_params_with_values(&values)?)
+        }
+        None if parameter_types.is_empty() => Ok(plan),
+        None => MissingParametersSnafu {
+            expected: parameter_types.len(),
+        }
+        .fail(),
+    }
+}
+
+/// Return the types of the parameters of `plan`, indexed by position (`$1` is
+/// at index 0), or [`None`] where the type of a parameter can not be inferred
+/// from the query.
+///
+/// The parameters must be numbered consecutively from `$1`.
+fn parameter_types(plan: &LogicalPlan) -> Result<Vec<Option<DataType>>> {
+    let parameters = plan.get_parameter_types()?;
+    let mut types = vec![None; parameters.len()];
+    for (name, data_type) in parameters {
+        let idx = name
+            .strip_prefix('$')
+            .and_then(|idx| idx.parse::<usize>().ok())
+            .filter(|idx| (1..=types.len()).contains(idx))
+            .with_context(|| InvalidParametersSnafu {
+                description: format!(
+                    "unsupported placeholder {name}, parameters must be named $1, $2, ... \
+                    up to the number of parameters of the query"
+                ),
+            })?;
+
+        types[idx - 1] = data_type;
+    }
+    Ok(types)
+}
+
+/// Return the schema of parameters with the specified types, as returned in
+/// the `parameter_schema` of a prepared statement.
+///
+/// Parameters of unknown type have type [`DataType::Null`].
+fn parameter_schema(parameter_types: &[Option<DataType>]) -> Schema {
+    Schema::new(
+        parameter_types
+            .iter()
+            .enumerate()
+            .map(|(idx, data_type)| {
+                Field::new(
+                    format!("${}", idx + 1),
+                    data_type.clone().unwrap_or(DataType::Null),
+                    true,
+                )
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Convert the single row of `params` to values for the parameters with
+/// `parameter_types`, checking each value is compatible with the type of its
+/// parameter.
+fn bind_parameters(
+    parameter_types: &[Option<DataType>],
+    params: &RecordBatch,
+) -> Result<Vec<ScalarValue>> {
+    if params.num_columns() != parameter_types.len() {
+        return InvalidParametersSnafu {
+            description: format!(
+                "expected {} parameter value(s), got {}",
+                parameter_types.len(),
+                params.num_columns()
+            ),
+        }
+        .fail();
+    }
+
+    parameter_types
+        .iter()
+        .zip(params.columns())
+        .enumerate()
+        .map(|(idx, (expected, column))| {
+            let actual = column.data_type();
+            let column = match expected {
+                Some(expected) if expected != actual => {
+                    let invalid = || Error::InvalidParameter {
+                        name: format!("${}", idx + 1),
+                        expected: expected.clone(),
+                        actual: actual.clone(),
+                    };
+                    if !is_compatible(actual, expected) {
+                        return Err(invalid());
+                    }
+                    // Casts are not "safe", so that values that do not fit in
+                    // the expected type are an error instead of NULL.
+                    let options = CastOptions {
+                        safe: false,
+                        ..Default::default()
+                    };
+                    cast_with_options(column, expected, &options).map_err(|_| invalid())?
+                }
+                _ => Arc::clone(column),
+            };
+            Ok(ScalarValue::try_from_array(&column, 0)?)
+        })
+        .collect()
+}
+
+/// Returns true if a parameter value of type `actual` can be bound to a
+/// parameter of type `expected`, such as an `Int32` value to an `Int64`
+/// parameter.
+fn is_compatible(actual: &DataType, expected: &DataType) -> bool {
+    matches!(actual, DataType::Null)
+        || (actual.is_integer() && expected.is_integer())
+        || (actual.is_floating() && expected.is_floating())
+        || (is_string(actual) && is_string(expected))
+        || matches!(
+            (actual, expected),
+            (DataType::Timestamp(..), DataType::Timestamp(..))
+        )
+}
+
+/// Returns true for string types, including dictionary encoded strings such
+/// as tag columns.
+fn is_string(data_type: &DataType) -> bool {
+    match data_type {
+        DataType::Utf8 | DataType::LargeUtf8 => true,
+        DataType::Dictionary(_, value) => is_string(value),
+        _ => false,
+    }
 }
 
 /// Return the schema for the specified query
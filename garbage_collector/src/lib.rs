@@ -43,7 +43,8 @@ use tokio_util::sync::CancellationToken;
 mod objectstore;
 /// Logic for deleting parquet files from the catalog
 mod parquetfile;
-/// Logic for flagging parquet files for deletion based on retention settings
+/// Logic for flagging parquet files for deletion based on retention settings, deleted tables and
+/// deleted namespaces
 mod retention;
 
 const BUFFER_SIZE: usize = 1000;
@@ -85,6 +86,7 @@ impl GarbageCollector {
             objectstore_sleep_interval_minutes = %sub_config.objectstore_sleep_interval_minutes,
             parquetfile_sleep_interval_minutes = %sub_config.parquetfile_sleep_interval_minutes,
             retention_sleep_interval_minutes = %sub_config.retention_sleep_interval_minutes,
+            namespace_delete_grace_period = %format_duration(sub_config.namespace_delete_config.namespace_delete_grace_period).to_string(),
             "GarbageCollector starting"
         );
 
@@ -163,10 +165,14 @@ impl GarbageCollector {
         ));
 
         // Initialise the retention code, which is just one thread that calls
-        // flag_for_delete_by_retention() on the catalog then sleeps.
+        // flag_for_delete_by_retention(), flag_for_delete_by_deleted_table() and
+        // flag_for_delete_by_deleted_namespace() on the catalog then sleeps.
         let retention_flagger = tokio::spawn(retention_flagger::perform(
             shutdown.clone(),
             catalog,
+            sub_config
+                .namespace_delete_config
+                .namespace_delete_grace_period,
             sub_config.retention_sleep_interval_minutes,
             sub_config.dry_run,
         ));
@@ -523,6 +523,21 @@ mod tests {
             self.inner.flag_for_delete_by_retention().await
         }
 
+        async fn flag_for_delete_by_deleted_table(
+            &mut self,
+        ) -> iox_catalog::interface::Result<Vec<ParquetFileId>> {
+            self.inner.flag_for_delete_by_deleted_table().await
+        }
+
+        async fn flag_for_delete_by_deleted_namespace(
+            &mut self,
+            deleted_before: Timestamp,
+        ) -> iox_catalog::interface::Result<Vec<ParquetFileId>> {
+            self.inner
+                .flag_for_delete_by_deleted_namespace(deleted_before)
+                .await
+        }
+
         async fn list_by_namespace_not_to_delete(
             &mut self,
             namespace_id: NamespaceId,
@@ -1,3 +1,4 @@
+use data_types::Timestamp;
 use iox_catalog::interface::Catalog;
 use observability_deps::tracing::*;
 use snafu::prelude::*;
@@ -8,6 +9,7 @@ use tokio_util::sync::CancellationToken;
 pub(crate) async fn perform(
     shutdown: CancellationToken,
     catalog: Arc<dyn Catalog>,
+    namespace_delete_grace_period: Duration,
     sleep_interval_minutes: u64,
     dry_run: bool,
 ) -> Result<()> {
@@ -21,6 +23,26 @@ pub(crate) async fn perform(
                 .await
                 .context(FlaggingSnafu)?;
             info!(flagged_count = %flagged.len(), "iox_catalog::flag_for_delete_by_retention()");
+
+            let flagged = catalog
+                .repositories()
+                .await
+                .parquet_files()
+                .flag_for_delete_by_deleted_table() //read/write
+                .await
+                .context(FlaggingDeletedTableSnafu)?;
+            info!(flagged_count = %flagged.len(), "iox_catalog::flag_for_delete_by_deleted_table()");
+
+            let deleted_before =
+                Timestamp::from(catalog.time_provider().now() - namespace_delete_grace_period);
+            let flagged = catalog
+                .repositories()
+                .await
+                .parquet_files()
+                .flag_for_delete_by_deleted_namespace(deleted_before) //read/write
+                .await
+                .context(FlaggingDeletedNamespaceSnafu)?;
+            info!(flagged_count = %flagged.len(), "iox_catalog::flag_for_delete_by_deleted_namespace()");
         } else {
             debug!("dry run enabled for parquet retention flagger");
         };
@@ -42,6 +64,16 @@ pub enum Error {
     Flagging {
         source: iox_catalog::interface::Error,
     },
+
+    #[snafu(display("Failed to flag parquet files of deleted tables for deletion"))]
+    FlaggingDeletedTable {
+        source: iox_catalog::interface::Error,
+    },
+
+    #[snafu(display("Failed to flag parquet files of deleted namespaces for deletion"))]
+    FlaggingDeletedNamespace {
+        source: iox_catalog::interface::Error,
+    },
 }
 
 pub(crate) type Result<T, E = Error> = std::result::Result<T, E>;
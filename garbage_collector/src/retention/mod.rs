@@ -1,2 +1,3 @@
-/// Logic for flagging parquet files for deletion based on retention settings
+/// Logic for flagging parquet files for deletion based on retention settings, deleted tables and
+/// deleted namespaces
 pub(crate) mod flagger;
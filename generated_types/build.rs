@@ -18,6 +18,7 @@ fn main() -> Result<()> {
 ///
 /// Creates:
 ///
+/// - `arrow.flight.protocol.sql.rs`
 /// - `influxdata.iox.authz.v1.rs`
 /// - `influxdata.iox.catalog.v1.rs`
 /// - `influxdata.iox.compactor.v1.rs`
@@ -51,10 +52,12 @@ fn generate_grpc_types(root: &Path) -> Result<()> {
     let wal_path = root.join("influxdata/iox/wal/v1");
 
     let proto_files = vec![
+        root.join("arrow/flight/protocol/sql/ingest.proto"),
         authz_path.join("authz.proto"),
         catalog_path.join("parquet_file.proto"),
         catalog_path.join("partition_identifier.proto"),
         catalog_path.join("service.proto"),
+        compactor_path.join("scheduler.proto"),
         compactor_path.join("service.proto"),
         delete_path.join("service.proto"),
         gossip_path.join("compaction.proto"),
@@ -69,6 +72,7 @@ fn generate_grpc_types(root: &Path) -> Result<()> {
         partition_template_path.join("template.proto"),
         predicate_path.join("predicate.proto"),
         querier_path.join("flight.proto"),
+        querier_path.join("service.proto"),
         root.join("google/longrunning/operations.proto"),
         root.join("google/rpc/error_details.proto"),
         root.join("google/rpc/status.proto"),
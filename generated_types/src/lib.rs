@@ -243,6 +243,22 @@ pub mod influxdata {
     }
 }
 
+/// FlightSQL messages not yet provided by the `arrow-flight` crate.
+pub mod arrow {
+    pub mod flight {
+        pub mod protocol {
+            pub mod sql {
+                include!(concat!(env!("OUT_DIR"), "/arrow.flight.protocol.sql.rs"));
+
+                /// The protobuf type of [`CommandStatementIngest`], for packing it
+                /// in a `google.protobuf.Any` message.
+                pub const COMMAND_STATEMENT_INGEST: &str =
+                    "arrow.flight.protocol.sql.CommandStatementIngest";
+            }
+        }
+    }
+}
+
 // Needed because of https://github.com/hyperium/tonic/issues/471
 pub mod grpc {
     pub mod health {
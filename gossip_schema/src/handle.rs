@@ -69,7 +69,10 @@ impl SchemaTx {
 async fn actor_loop(mut rx: mpsc::Receiver<Event>, gossip: gossip::GossipHandle<Topic>) {
     while let Some(event) = rx.recv().await {
         let frames = match event {
-            v @ Event::NamespaceCreated(_) => vec![v],
+            v @ (Event::NamespaceCreated(_)
+            | Event::NamespaceRenamed(_)
+            | Event::TableDeleted(_)
+            | Event::ColumnDeleted(_)) => vec![v],
             Event::TableCreated(v) => serialise_table_create_frames(v),
             Event::TableUpdated(v) => {
                 // Split the frame up into N frames, sized as big as the gossip
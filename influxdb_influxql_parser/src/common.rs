@@ -111,7 +111,30 @@ impl Display for QualifiedMeasurementName {
 /// measurement_name ::= identifier | regex_lit
 /// ```
 pub(crate) fn qualified_measurement_name(i: &str) -> ParseResult<&str, QualifiedMeasurementName> {
-    let (remaining_input, (opt_db_rp, name)) = pair(
+    let (remaining_input, ((database, retention_policy), name)) =
+        pair(database_retention_policy_prefix, MeasurementName::parse)(i)?;
+
+    Ok((
+        remaining_input,
+        QualifiedMeasurementName {
+            database,
+            retention_policy,
+            name,
+        },
+    ))
+}
+
+/// Match the optional database and retention policy prefix of a
+/// fully-qualified measurement name.
+///
+/// ```text
+/// prefix ::= ( policy_name "." ) |
+///            ( db_name "." policy_name? "." )
+/// ```
+pub(crate) fn database_retention_policy_prefix(
+    i: &str,
+) -> ParseResult<&str, (Option<Identifier>, Option<Identifier>)> {
+    map(
         opt(alt((
             // database "." retention_policy "."
             map(
@@ -126,23 +149,8 @@ pub(crate) fn qualified_measurement_name(i: &str) -> ParseResult<&str, Qualified
             // retention_policy "."
             map(terminated(identifier, tag(".")), |rp| (None, Some(rp))),
         ))),
-        MeasurementName::parse,
-    )(i)?;
-
-    // Extract possible `database` and / or `retention_policy`
-    let (database, retention_policy) = match opt_db_rp {
-        Some(db_rp) => db_rp,
-        _ => (None, None),
-    };
-
-    Ok((
-        remaining_input,
-        QualifiedMeasurementName {
-            database,
-            retention_policy,
-            name,
-        },
-    ))
+        Option::unwrap_or_default,
+    )(i)
 }
 
 /// Parse a SQL-style single-line comment
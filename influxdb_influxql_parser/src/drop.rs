@@ -1,12 +1,16 @@
-//! Types and parsers for the [`DROP MEASUREMENT`][sql] statement.
+//! Types and parsers for the [`DROP MEASUREMENT`][sql] and [`DROP SERIES`][series] statements.
 //!
 //! [sql]: https://docs.influxdata.com/influxdb/v1.8/query_language/manage-database/#delete-measurements-with-drop-measurement
+//! [series]: https://docs.influxdata.com/influxdb/v1.8/query_language/manage-database/#drop-series-from-the-index-with-drop-series
 
-use crate::common::ws1;
+use crate::common::{where_clause, ws0, ws1, WhereClause};
 use crate::identifier::{identifier, Identifier};
 use crate::internal::{expect, ParseResult};
 use crate::keywords::keyword;
-use nom::combinator::map;
+use crate::simple_from_clause::{delete_from_clause, DeleteFromClause};
+use crate::statement::Statement;
+use nom::branch::alt;
+use nom::combinator::{map, opt};
 use nom::sequence::{pair, preceded};
 use std::fmt::{Display, Formatter};
 
@@ -14,7 +18,7 @@ use std::fmt::{Display, Formatter};
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DropMeasurementStatement {
     /// The name of the measurement to delete.
-    name: Identifier,
+    pub name: Identifier,
 }
 
 impl Display for DropMeasurementStatement {
@@ -23,12 +27,44 @@ impl Display for DropMeasurementStatement {
     }
 }
 
-pub(crate) fn drop_statement(i: &str) -> ParseResult<&str, DropMeasurementStatement> {
+/// Represents a `DROP SERIES` statement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DropSeriesStatement {
+    /// Represents the optional `FROM` clause.
+    pub from: Option<DeleteFromClause>,
+
+    /// Represents the optional `WHERE` clause.
+    pub condition: Option<WhereClause>,
+}
+
+impl Display for DropSeriesStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DROP SERIES")?;
+
+        if let Some(from) = &self.from {
+            write!(f, " {from}")?;
+        }
+
+        if let Some(where_clause) = &self.condition {
+            write!(f, " {where_clause}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse a `DROP MEASUREMENT` or `DROP SERIES` statement.
+pub(crate) fn drop_statement(i: &str) -> ParseResult<&str, Statement> {
     preceded(
         pair(keyword("DROP"), ws1),
         expect(
-            "invalid DROP statement, expected MEASUREMENT",
-            drop_measurement,
+            "invalid DROP statement, expected MEASUREMENT or SERIES",
+            alt((
+                map(drop_measurement, |s| {
+                    Statement::DropMeasurement(Box::new(s))
+                }),
+                map(drop_series, |s| Statement::DropSeries(Box::new(s))),
+            )),
         ),
     )(i)
 }
@@ -46,6 +82,32 @@ fn drop_measurement(i: &str) -> ParseResult<&str, DropMeasurementStatement> {
     )(i)
 }
 
+fn drop_series(i: &str) -> ParseResult<&str, DropSeriesStatement> {
+    // drop_series ::= "SERIES" ( from_clause where_clause? | where_clause )
+    preceded(
+        keyword("SERIES"),
+        expect(
+            "invalid DROP SERIES statement, expected FROM or WHERE",
+            preceded(
+                ws1,
+                alt((
+                    map(
+                        pair(delete_from_clause, opt(preceded(ws0, where_clause))),
+                        |(from, condition)| DropSeriesStatement {
+                            from: Some(from),
+                            condition,
+                        },
+                    ),
+                    map(where_clause, |condition| DropSeriesStatement {
+                        from: None,
+                        condition: Some(condition),
+                    }),
+                )),
+            ),
+        ),
+    )(i)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -54,11 +116,12 @@ mod test {
     #[test]
     fn test_drop_statement() {
         drop_statement("DROP MEASUREMENT foo").unwrap();
+        drop_statement("DROP SERIES FROM foo").unwrap();
 
         // Fallible cases
         assert_expect_error!(
             drop_statement("DROP foo"),
-            "invalid DROP statement, expected MEASUREMENT"
+            "invalid DROP statement, expected MEASUREMENT or SERIES"
         );
     }
 
@@ -75,4 +138,32 @@ mod test {
             "invalid DROP MEASUREMENT statement, expected identifier"
         );
     }
+
+    #[test]
+    fn test_drop_series() {
+        // Validate via the Display trait, as we don't need to validate the contents of the
+        // FROM and / or WHERE clauses, given they are tested in their on modules.
+
+        let (_, got) = drop_series("SERIES FROM foo").unwrap();
+        assert_eq!(got.to_string(), "DROP SERIES FROM foo");
+
+        let (_, got) = drop_series("SERIES FROM foo, /bar/ WHERE host = 'a'").unwrap();
+        assert_eq!(
+            got.to_string(),
+            "DROP SERIES FROM foo, /bar/ WHERE host = 'a'"
+        );
+
+        let (_, got) = drop_series("SERIES WHERE host = 'a'").unwrap();
+        assert_eq!(got.to_string(), "DROP SERIES WHERE host = 'a'");
+
+        // Fallible cases
+        assert_expect_error!(
+            drop_series("SERIES"),
+            "invalid DROP SERIES statement, expected FROM or WHERE"
+        );
+        assert_expect_error!(
+            drop_series("SERIES foo"),
+            "invalid DROP SERIES statement, expected FROM or WHERE"
+        );
+    }
 }
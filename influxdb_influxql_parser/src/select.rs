@@ -3,9 +3,9 @@
 //! [sql]: https://docs.influxdata.com/influxdb/v1.8/query_language/explore-data/#the-basic-select-statement
 
 use crate::common::{
-    limit_clause, offset_clause, order_by_clause, qualified_measurement_name, where_clause, ws0,
-    ws1, LimitClause, OffsetClause, OrderByClause, ParseError, Parser, QualifiedMeasurementName,
-    WhereClause, ZeroOrMore,
+    database_retention_policy_prefix, limit_clause, offset_clause, order_by_clause,
+    qualified_measurement_name, where_clause, ws0, ws1, LimitClause, OffsetClause, OrderByClause,
+    ParseError, Parser, QualifiedMeasurementName, WhereClause, ZeroOrMore,
 };
 use crate::expression::arithmetic::Expr::Wildcard;
 use crate::expression::arithmetic::{
@@ -37,6 +37,9 @@ pub struct SelectStatement {
     /// Expressions returned by the selection.
     pub fields: FieldList,
 
+    /// The measurement to which the results of the selection are written.
+    pub into: Option<IntoClause>,
+
     /// A list of measurements or subqueries used as the source data for the selection.
     pub from: FromMeasurementClause,
 
@@ -84,7 +87,13 @@ impl SelectStatement {
 
 impl Display for SelectStatement {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "SELECT {} {}", self.fields, self.from)?;
+        write!(f, "SELECT {}", self.fields)?;
+
+        if let Some(into) = &self.into {
+            write!(f, " {into}")?;
+        }
+
+        write!(f, " {}", self.from)?;
 
         if let Some(where_clause) = &self.condition {
             write!(f, " {where_clause}")?;
@@ -133,6 +142,7 @@ pub(crate) fn select_statement(i: &str) -> ParseResult<&str, SelectStatement> {
             _, // SELECT
             _, // whitespace
             fields,
+            into,
             from,
             condition,
             group_by,
@@ -148,6 +158,7 @@ pub(crate) fn select_statement(i: &str) -> ParseResult<&str, SelectStatement> {
         keyword("SELECT"),
         ws0,
         field_list,
+        opt(preceded(ws0, into_clause)),
         preceded(ws0, from_clause),
         opt(preceded(ws0, where_clause)),
         opt(preceded(ws0, group_by_clause)),
@@ -164,6 +175,7 @@ pub(crate) fn select_statement(i: &str) -> ParseResult<&str, SelectStatement> {
         remaining,
         SelectStatement {
             fields,
+            into,
             from,
             condition,
             group_by,
@@ -213,6 +225,82 @@ impl Parser for MeasurementSelection {
     }
 }
 
+/// Represents the target measurement of an `INTO` clause.
+#[derive(Clone, Debug, PartialEq)]
+pub enum IntoMeasurementName {
+    /// The results are written to the named measurement.
+    Name(Identifier),
+
+    /// The results are written to a measurement with the same name as the
+    /// source measurement, specified as `:MEASUREMENT`.
+    BackReference,
+}
+
+impl Display for IntoMeasurementName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Name(ident) => write!(f, "{ident}"),
+            Self::BackReference => f.write_str(":MEASUREMENT"),
+        }
+    }
+}
+
+/// Represents an `INTO` clause for a `SELECT` statement.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntoClause {
+    /// An optional database name.
+    pub database: Option<Identifier>,
+
+    /// An optional retention policy.
+    pub retention_policy: Option<Identifier>,
+
+    /// The target measurement.
+    pub name: IntoMeasurementName,
+}
+
+impl Display for IntoClause {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("INTO ")?;
+        match (&self.database, &self.retention_policy) {
+            (None, None) => write!(f, "{}", self.name),
+            (Some(db), None) => write!(f, "{db}..{}", self.name),
+            (None, Some(rp)) => write!(f, "{rp}.{}", self.name),
+            (Some(db), Some(rp)) => write!(f, "{db}.{rp}.{}", self.name),
+        }
+    }
+}
+
+/// Parse an `INTO` clause.
+///
+/// ```text
+/// into_clause      ::= "INTO" ( db_name "." policy_name? "." | policy_name "." )? measurement_name
+/// measurement_name ::= identifier | ":MEASUREMENT"
+/// ```
+fn into_clause(i: &str) -> ParseResult<&str, IntoClause> {
+    let (remaining, ((database, retention_policy), name)) = preceded(
+        pair(keyword("INTO"), ws0),
+        expect(
+            "invalid INTO clause, expected identifier or :MEASUREMENT",
+            pair(
+                database_retention_policy_prefix,
+                alt((
+                    value(IntoMeasurementName::BackReference, tag(":MEASUREMENT")),
+                    map(identifier, IntoMeasurementName::Name),
+                )),
+            ),
+        ),
+    )(i)?;
+
+    Ok((
+        remaining,
+        IntoClause {
+            database,
+            retention_policy,
+            name,
+        },
+    ))
+}
+
 /// Represents a `FROM` clause for a `SELECT` statement.
 pub type FromMeasurementClause = ZeroOrMore<MeasurementSelection>;
 
@@ -890,6 +978,15 @@ mod test {
             r#"SELECT value FROM foo TZ('Australia/Hobart')"#
         );
 
+        let (_, got) = select_statement(
+            r#"SELECT mean(value) INTO "rp"."foo_1h" FROM foo GROUP BY time(1h), *"#,
+        )
+        .unwrap();
+        assert_eq!(
+            got.to_string(),
+            r#"SELECT mean(value) INTO rp.foo_1h FROM foo GROUP BY TIME(1h), *"#
+        );
+
         // validate spacing between keywords
 
         let (rem, _) = select_statement("SELECT value FROM(SELECT val FROM cpu)").unwrap();
@@ -1204,6 +1301,49 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_into_clause() {
+        let (rem, got) = into_clause("INTO foo").unwrap();
+        assert_eq!(rem, "");
+        assert_eq!(
+            got,
+            IntoClause {
+                database: None,
+                retention_policy: None,
+                name: IntoMeasurementName::Name("foo".into()),
+            }
+        );
+        assert_eq!(got.to_string(), "INTO foo");
+
+        let (_, got) = into_clause("INTO db.rp.:MEASUREMENT").unwrap();
+        assert_eq!(
+            got,
+            IntoClause {
+                database: Some("db".into()),
+                retention_policy: Some("rp".into()),
+                name: IntoMeasurementName::BackReference,
+            }
+        );
+        assert_eq!(got.to_string(), "INTO db.rp.:MEASUREMENT");
+
+        let (_, got) = into_clause("INTO db..foo").unwrap();
+        assert_eq!(got.to_string(), "INTO db..foo");
+
+        let (_, got) = into_clause(r#"INTO "rp"."foo bar""#).unwrap();
+        assert_eq!(got.to_string(), r#"INTO rp."foo bar""#);
+
+        // Fallible cases
+
+        assert_expect_error!(
+            into_clause("INTO /foo/"),
+            "invalid INTO clause, expected identifier or :MEASUREMENT"
+        );
+        assert_expect_error!(
+            into_clause("INTO FROM"),
+            "invalid INTO clause, expected identifier or :MEASUREMENT"
+        );
+    }
+
     #[test]
     fn test_dimension() {
         // Test the valid dimension expressions for a GROUP BY clause
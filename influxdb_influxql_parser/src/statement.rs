@@ -2,7 +2,7 @@
 
 use crate::create::{create_statement, CreateDatabaseStatement};
 use crate::delete::{delete_statement, DeleteStatement};
-use crate::drop::{drop_statement, DropMeasurementStatement};
+use crate::drop::{drop_statement, DropMeasurementStatement, DropSeriesStatement};
 use crate::explain::{explain_statement, ExplainStatement};
 use crate::internal::ParseResult;
 use crate::select::{select_statement, SelectStatement};
@@ -25,6 +25,8 @@ pub enum Statement {
     Delete(Box<DeleteStatement>),
     /// Represents a `DROP MEASUREMENT` statement.
     DropMeasurement(Box<DropMeasurementStatement>),
+    /// Represents a `DROP SERIES` statement.
+    DropSeries(Box<DropSeriesStatement>),
     /// Represents an `EXPLAIN` statement.
     Explain(Box<ExplainStatement>),
     /// Represents a `SELECT` statement.
@@ -49,6 +51,7 @@ impl Display for Statement {
             Self::CreateDatabase(s) => Display::fmt(s, f),
             Self::Delete(s) => Display::fmt(s, f),
             Self::DropMeasurement(s) => Display::fmt(s, f),
+            Self::DropSeries(s) => Display::fmt(s, f),
             Self::Explain(s) => Display::fmt(s, f),
             Self::Select(s) => Display::fmt(s, f),
             Self::ShowDatabases(s) => Display::fmt(s, f),
@@ -65,7 +68,7 @@ impl Display for Statement {
 pub fn statement(i: &str) -> ParseResult<&str, Statement> {
     alt((
         map(delete_statement, |s| Statement::Delete(Box::new(s))),
-        map(drop_statement, |s| Statement::DropMeasurement(Box::new(s))),
+        drop_statement,
         map(explain_statement, |s| Statement::Explain(Box::new(s))),
         map(select_statement, |s| Statement::Select(Box::new(s))),
         create_statement,
@@ -93,6 +96,9 @@ mod test {
         let (got, _) = statement("DROP MEASUREMENT foo").unwrap();
         assert_eq!(got, "");
 
+        let (got, _) = statement("DROP SERIES FROM foo WHERE host = 'a'").unwrap();
+        assert_eq!(got, "");
+
         // explain_statement combinator
         let (got, _) = statement("EXPLAIN SELECT * FROM cpu").unwrap();
         assert_eq!(got, "");
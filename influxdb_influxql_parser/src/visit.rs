@@ -30,14 +30,14 @@ use crate::common::{
 };
 use crate::create::CreateDatabaseStatement;
 use crate::delete::DeleteStatement;
-use crate::drop::DropMeasurementStatement;
+use crate::drop::{DropMeasurementStatement, DropSeriesStatement};
 use crate::explain::ExplainStatement;
 use crate::expression::arithmetic::Expr;
 use crate::expression::conditional::ConditionalExpression;
 use crate::expression::{Binary, Call, ConditionalBinary, VarRef};
 use crate::literal::Literal;
 use crate::select::{
-    Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause,
+    Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause, IntoClause,
     MeasurementSelection, SLimitClause, SOffsetClause, SelectStatement, TimeDimension,
     TimeZoneClause,
 };
@@ -150,6 +150,22 @@ pub trait Visitor: Sized {
         Ok(self)
     }
 
+    /// Invoked before any children of the `DROP SERIES` statement are visited.
+    fn pre_visit_drop_series_statement(
+        self,
+        _n: &DropSeriesStatement,
+    ) -> Result<Recursion<Self>, Self::Error> {
+        Ok(Continue(self))
+    }
+
+    /// Invoked after all children of the `DROP SERIES` statement are visited.
+    fn post_visit_drop_series_statement(
+        self,
+        _n: &DropSeriesStatement,
+    ) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
     /// Invoked before any children of the `EXPLAIN` statement are visited.
     fn pre_visit_explain_statement(
         self,
@@ -318,6 +334,16 @@ pub trait Visitor: Sized {
         Ok(self)
     }
 
+    /// Invoked before any children of the `INTO` clause of a `SELECT` statement are visited.
+    fn pre_visit_select_into_clause(self, _n: &IntoClause) -> Result<Recursion<Self>, Self::Error> {
+        Ok(Continue(self))
+    }
+
+    /// Invoked after all children of the `INTO` clause of a `SELECT` statement are visited.
+    fn post_visit_select_into_clause(self, _n: &IntoClause) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
     /// Invoked before any children of the `FROM` clause of a `SELECT` statement are visited.
     fn pre_visit_select_from_clause(
         self,
@@ -615,6 +641,7 @@ impl Visitable for Statement {
             Self::CreateDatabase(s) => s.accept(visitor),
             Self::Delete(s) => s.accept(visitor),
             Self::DropMeasurement(s) => s.accept(visitor),
+            Self::DropSeries(s) => s.accept(visitor),
             Self::Explain(s) => s.accept(visitor),
             Self::Select(s) => s.accept(visitor),
             Self::ShowDatabases(s) => s.accept(visitor),
@@ -712,6 +739,29 @@ impl Visitable for DropMeasurementStatement {
     }
 }
 
+impl Visitable for DropSeriesStatement {
+    fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
+        let visitor = match visitor.pre_visit_drop_series_statement(self)? {
+            Continue(visitor) => visitor,
+            Stop(visitor) => return Ok(visitor),
+        };
+
+        let visitor = if let Some(from) = &self.from {
+            from.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(condition) = &self.condition {
+            condition.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        visitor.post_visit_drop_series_statement(self)
+    }
+}
+
 impl Visitable for ExplainStatement {
     fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
         let visitor = match visitor.pre_visit_explain_statement(self)? {
@@ -734,6 +784,12 @@ impl Visitable for SelectStatement {
 
         let visitor = self.fields.accept(visitor)?;
 
+        let visitor = if let Some(into) = &self.into {
+            into.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
         let visitor = self.from.accept(visitor)?;
 
         let visitor = if let Some(condition) = &self.condition {
@@ -1140,6 +1196,17 @@ impl Visitable for Field {
     }
 }
 
+impl Visitable for IntoClause {
+    fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
+        let visitor = match visitor.pre_visit_select_into_clause(self)? {
+            Continue(visitor) => visitor,
+            Stop(visitor) => return Ok(visitor),
+        };
+
+        visitor.post_visit_select_into_clause(self)
+    }
+}
+
 impl Visitable for FromMeasurementClause {
     fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
         let visitor = match visitor.pre_visit_select_from_clause(self)? {
@@ -1347,14 +1414,14 @@ mod test {
         WhereClause,
     };
     use crate::delete::DeleteStatement;
-    use crate::drop::DropMeasurementStatement;
+    use crate::drop::{DropMeasurementStatement, DropSeriesStatement};
     use crate::explain::ExplainStatement;
     use crate::expression::arithmetic::Expr;
     use crate::expression::conditional::ConditionalExpression;
     use crate::expression::{Binary, Call, ConditionalBinary, VarRef};
     use crate::literal::Literal;
     use crate::select::{
-        Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause,
+        Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause, IntoClause,
         MeasurementSelection, SLimitClause, SOffsetClause, SelectStatement, TimeDimension,
         TimeZoneClause,
     };
@@ -1411,6 +1478,7 @@ mod test {
         trace_visit!(delete_from_clause, DeleteFromClause);
         trace_visit!(measurement_name, MeasurementName);
         trace_visit!(drop_measurement_statement, DropMeasurementStatement);
+        trace_visit!(drop_series_statement, DropSeriesStatement);
         trace_visit!(explain_statement, ExplainStatement);
         trace_visit!(select_statement, SelectStatement);
         trace_visit!(show_databases_statement, ShowDatabasesStatement);
@@ -1425,6 +1493,7 @@ mod test {
         trace_visit!(conditional_expression, ConditionalExpression);
         trace_visit!(expr, Expr);
         trace_visit!(select_field_list, FieldList);
+        trace_visit!(select_into_clause, IntoClause);
         trace_visit!(select_field, Field);
         trace_visit!(select_from_clause, FromMeasurementClause);
         trace_visit!(select_measurement_selection, MeasurementSelection);
@@ -1472,6 +1541,11 @@ mod test {
         insta::assert_yaml_snapshot!(visit_statement!("DROP MEASUREMENT cpu"))
     }
 
+    #[test]
+    fn test_drop_series_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!("DROP SERIES FROM cpu WHERE host = 'a'"))
+    }
+
     #[test]
     fn test_explain_statement() {
         insta::assert_yaml_snapshot!(visit_statement!("EXPLAIN SELECT * FROM cpu"));
@@ -1497,6 +1571,9 @@ mod test {
             TZ('Australia/Hobart')
         "#
         ));
+        insta::assert_yaml_snapshot!(visit_statement!(
+            r#"SELECT value INTO db.rp.:MEASUREMENT FROM temp"#
+        ));
     }
 
     #[test]
@@ -30,14 +30,14 @@ use crate::common::{
 };
 use crate::create::CreateDatabaseStatement;
 use crate::delete::DeleteStatement;
-use crate::drop::DropMeasurementStatement;
+use crate::drop::{DropMeasurementStatement, DropSeriesStatement};
 use crate::explain::ExplainStatement;
 use crate::expression::arithmetic::Expr;
 use crate::expression::conditional::ConditionalExpression;
 use crate::expression::{Binary, Call, ConditionalBinary, VarRef};
 use crate::literal::Literal;
 use crate::select::{
-    Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause,
+    Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause, IntoClause,
     MeasurementSelection, SLimitClause, SOffsetClause, SelectStatement, TimeDimension,
     TimeZoneClause,
 };
@@ -154,6 +154,22 @@ pub trait VisitorMut: Sized {
         Ok(())
     }
 
+    /// Invoked before any children of the `DROP SERIES` statement are visited.
+    fn pre_visit_drop_series_statement(
+        &mut self,
+        _n: &mut DropSeriesStatement,
+    ) -> Result<Recursion, Self::Error> {
+        Ok(Continue)
+    }
+
+    /// Invoked after all children of the `DROP SERIES` statement are visited.
+    fn post_visit_drop_series_statement(
+        &mut self,
+        _n: &mut DropSeriesStatement,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Invoked before any children of the `EXPLAIN` statement are visited.
     fn pre_visit_explain_statement(
         &mut self,
@@ -328,6 +344,19 @@ pub trait VisitorMut: Sized {
         Ok(())
     }
 
+    /// Invoked before any children of the `INTO` clause of a `SELECT` statement are visited.
+    fn pre_visit_select_into_clause(
+        &mut self,
+        _n: &mut IntoClause,
+    ) -> Result<Recursion, Self::Error> {
+        Ok(Continue)
+    }
+
+    /// Invoked after all children of the `INTO` clause of a `SELECT` statement are visited.
+    fn post_visit_select_into_clause(&mut self, _n: &mut IntoClause) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Invoked before any children of the `FROM` clause of a `SELECT` statement are visited.
     fn pre_visit_select_from_clause(
         &mut self,
@@ -645,6 +674,7 @@ impl VisitableMut for Statement {
             Self::CreateDatabase(s) => s.accept(visitor),
             Self::Delete(s) => s.accept(visitor),
             Self::DropMeasurement(s) => s.accept(visitor),
+            Self::DropSeries(s) => s.accept(visitor),
             Self::Explain(s) => s.accept(visitor),
             Self::Select(s) => s.accept(visitor),
             Self::ShowDatabases(s) => s.accept(visitor),
@@ -736,6 +766,24 @@ impl VisitableMut for DropMeasurementStatement {
     }
 }
 
+impl VisitableMut for DropSeriesStatement {
+    fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
+        if let Stop = visitor.pre_visit_drop_series_statement(self)? {
+            return Ok(());
+        };
+
+        if let Some(from) = &mut self.from {
+            from.accept(visitor)?;
+        }
+
+        if let Some(condition) = &mut self.condition {
+            condition.accept(visitor)?;
+        }
+
+        visitor.post_visit_drop_series_statement(self)
+    }
+}
+
 impl VisitableMut for ExplainStatement {
     fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
         if let Stop = visitor.pre_visit_explain_statement(self)? {
@@ -756,6 +804,10 @@ impl VisitableMut for SelectStatement {
 
         self.fields.accept(visitor)?;
 
+        if let Some(into) = &mut self.into {
+            into.accept(visitor)?;
+        }
+
         self.from.accept(visitor)?;
 
         if let Some(condition) = &mut self.condition {
@@ -1091,6 +1143,16 @@ impl VisitableMut for Field {
     }
 }
 
+impl VisitableMut for IntoClause {
+    fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
+        if let Stop = visitor.pre_visit_select_into_clause(self)? {
+            return Ok(());
+        };
+
+        visitor.post_visit_select_into_clause(self)
+    }
+}
+
 impl VisitableMut for FromMeasurementClause {
     fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
         if let Stop = visitor.pre_visit_select_from_clause(self)? {
@@ -1284,7 +1346,7 @@ mod test {
         WhereClause,
     };
     use crate::delete::DeleteStatement;
-    use crate::drop::DropMeasurementStatement;
+    use crate::drop::{DropMeasurementStatement, DropSeriesStatement};
     use crate::explain::ExplainStatement;
     use crate::expression::arithmetic::Expr;
     use crate::expression::conditional::ConditionalExpression;
@@ -1292,7 +1354,7 @@ mod test {
     use crate::literal::Literal;
     use crate::parse_statements;
     use crate::select::{
-        Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause,
+        Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause, IntoClause,
         MeasurementSelection, SLimitClause, SOffsetClause, SelectStatement, TimeDimension,
         TimeZoneClause,
     };
@@ -1347,6 +1409,7 @@ mod test {
         trace_visit!(delete_from_clause, DeleteFromClause);
         trace_visit!(measurement_name, MeasurementName);
         trace_visit!(drop_measurement_statement, DropMeasurementStatement);
+        trace_visit!(drop_series_statement, DropSeriesStatement);
         trace_visit!(explain_statement, ExplainStatement);
         trace_visit!(select_statement, SelectStatement);
         trace_visit!(show_databases_statement, ShowDatabasesStatement);
@@ -1361,6 +1424,7 @@ mod test {
         trace_visit!(conditional_expression, ConditionalExpression);
         trace_visit!(expr, Expr);
         trace_visit!(select_field_list, FieldList);
+        trace_visit!(select_into_clause, IntoClause);
         trace_visit!(select_field, Field);
         trace_visit!(select_from_clause, FromMeasurementClause);
         trace_visit!(select_measurement_selection, MeasurementSelection);
@@ -1410,6 +1474,11 @@ mod test {
         insta::assert_yaml_snapshot!(visit_statement!("DROP MEASUREMENT cpu"))
     }
 
+    #[test]
+    fn test_drop_series_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!("DROP SERIES FROM cpu WHERE host = 'a'"))
+    }
+
     #[test]
     fn test_explain_statement() {
         insta::assert_yaml_snapshot!(visit_statement!("EXPLAIN SELECT * FROM cpu"));
@@ -1435,6 +1504,9 @@ mod test {
             TZ('Australia/Hobart')
         "#
         ));
+        insta::assert_yaml_snapshot!(visit_statement!(
+            r#"SELECT value INTO db.rp.:MEASUREMENT FROM temp"#
+        ));
     }
 
     #[test]
@@ -7,6 +7,7 @@ use thiserror::Error;
 
 mod inspect;
 mod regenerate_lp;
+mod replay_archive;
 
 /// A command level error type to decorate WAL errors with some extra
 /// "human" context for the user
@@ -31,6 +32,30 @@ pub enum Error {
 
     #[error("errors occurred during inspection of the WAL file: {sources:?}")]
     IncompleteInspection { sources: Vec<wal::Error> },
+
+    #[error("invalid object store configuration: {0}")]
+    InvalidObjectStoreConfig(#[from] clap_blocks::object_store::ParseError),
+
+    #[error("failed to read archived WAL segments from object store: {0}")]
+    ObjectStore(#[from] object_store::Error),
+
+    #[error("failed to regenerate line protocol from archived WAL segment: {0}")]
+    UnableToRegenerateArchivedSegment(#[from] RegenerateError),
+
+    #[error("failed to write regenerated line protocol to the target router: {0}")]
+    ReplayWriteFailed(#[from] influxdb_iox_client::error::Error),
+
+    #[error("namespace {0} of archived writes is unknown to the target host")]
+    UnknownReplayNamespace(data_types::NamespaceId),
+
+    #[error("line protocol regenerated from archived WAL segment {segment_id} for namespace {namespace_id} is not valid utf-8")]
+    InvalidReplayLineProtocol {
+        segment_id: wal::SegmentId,
+        namespace_id: data_types::NamespaceId,
+    },
+
+    #[error("invalid time range, start {start} is after end {end}")]
+    InvalidTimeRange { start: String, end: String },
 }
 
 /// A set of non-fatal errors which can occur during the regeneration of write
@@ -58,6 +83,10 @@ enum Command {
     /// looking up measurement names from IOx, the target host must implement
     /// the namespace and schema APIs
     RegenerateLp(regenerate_lp::Config),
+    /// Re-ingest the WAL segments archived to object storage by ingesters
+    /// within a time window, writing them to the target router. The target
+    /// host must also implement the namespace and schema APIs
+    ReplayArchive(replay_archive::Config),
 }
 
 /// Executes a WAL debugging subcommand as directed by the config
@@ -69,5 +98,6 @@ where
     match config.command {
         Command::Inspect(config) => inspect::command(config),
         Command::RegenerateLp(config) => regenerate_lp::command(connection, config).await,
+        Command::ReplayArchive(config) => replay_archive::command(connection, config).await,
     }
 }
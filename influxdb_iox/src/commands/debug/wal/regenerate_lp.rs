@@ -27,13 +27,13 @@ pub enum TableIndexLookupError {
 
 // This type provides a convenience wrapper around the namespace and schema APIs
 // to enable the fetching of table name indexes from namespace and table IDs.
-struct TableIndexFetcher {
+pub(super) struct TableIndexFetcher {
     namespace_index: HashMap<NamespaceId, String>,
     schema_client: SchemaClient,
 }
 
 impl TableIndexFetcher {
-    async fn new(connection: Connection) -> Result<Self, TableIndexLookupError> {
+    pub(super) async fn new(connection: Connection) -> Result<Self, TableIndexLookupError> {
         let mut namespace_client = influxdb_iox_client::namespace::Client::new(connection.clone());
         Ok(Self {
             namespace_index: namespace_client
@@ -47,7 +47,13 @@ impl TableIndexFetcher {
         })
     }
 
-    async fn get_table_name_index(
+    /// Returns the name of the namespace identified by `namespace_id`, if it
+    /// is known.
+    pub(super) fn namespace_name(&self, namespace_id: NamespaceId) -> Option<&str> {
+        self.namespace_index.get(&namespace_id).map(String::as_str)
+    }
+
+    pub(super) async fn get_table_name_index(
         &self,
         namespace_id: NamespaceId,
     ) -> Result<HashMap<TableId, String>, TableIndexLookupError> {
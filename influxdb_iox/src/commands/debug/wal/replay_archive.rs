@@ -0,0 +1,251 @@
+//! A module providing a CLI command to re-ingest archived WAL segments from
+//! object storage into a target router.
+use std::{future::Future, io::Write, ops::RangeInclusive};
+
+use clap_blocks::object_store::{make_object_store, ObjectStoreConfig};
+use data_types::{NamespaceId, TableId};
+use futures::TryStreamExt;
+use hashbrown::HashMap;
+use influxdb_iox_client::{connection::Connection, write::Client as WriteClient};
+use iox_time::Time;
+use object_store::{path::Path, DynObjectStore, ObjectMeta};
+use observability_deps::tracing::{info, warn};
+use wal::{ClosedSegmentFileReader, SegmentId, WriteOpEntryDecoder};
+use wal_inspect::{LineProtoWriter, TableBatchWriter};
+
+use super::{regenerate_lp::TableIndexFetcher, Error, RegenerateError};
+
+/// A container for the possible arguments & flags of a `replay-archive`
+/// command.
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    #[clap(flatten)]
+    object_store: ObjectStoreConfig,
+
+    /// The object store prefix archived WAL segments are read from. This
+    /// should match the `--wal-archive-prefix` of the archiving ingester,
+    /// optionally followed by an ingester ID to restrict the replay to the
+    /// segments of a single ingester instance
+    #[clap(long, default_value = "wal_archive")]
+    prefix: String,
+
+    /// Only replay segments closed at or after this time, in RFC3339 format.
+    ///
+    /// A segment contains the writes received during the WAL rotation period
+    /// before it was closed, so to replay all writes from a given time this
+    /// should be one rotation period earlier
+    #[clap(long, value_parser = parse_time)]
+    start: Time,
+
+    /// Only replay segments closed at or before this time, in RFC3339 format
+    #[clap(long, value_parser = parse_time)]
+    end: Time,
+
+    /// List the archived segments that would be replayed, without writing
+    /// anything to the target router
+    #[clap(long)]
+    dry_run: bool,
+
+    /// Restricts the maximum amount of line protocol sent to the target
+    /// router per request to this many bytes. This must be below the
+    /// `--max-http-request-size` of the target router
+    #[clap(long, default_value = "1048576")]
+    max_request_payload_size_bytes: usize,
+}
+
+fn parse_time(s: &str) -> Result<Time, String> {
+    Time::from_rfc3339(s).map_err(|e| format!("{s} isn't a valid RFC3339 timestamp: {e}"))
+}
+
+/// Executes the `replay-archive` command with the provided configuration,
+/// regenerating line protocol from each archived WAL segment closed within
+/// the requested time window and writing it to the target router.
+///
+/// Segments are replayed in the order they were closed.
+pub async fn command<C, CFut>(connection: C, config: Config) -> Result<(), Error>
+where
+    C: Send + FnOnce() -> CFut,
+    CFut: Send + Future<Output = Connection>,
+{
+    if config.start > config.end {
+        return Err(Error::InvalidTimeRange {
+            start: config.start.to_rfc3339(),
+            end: config.end.to_rfc3339(),
+        });
+    }
+
+    let store = make_object_store(&config.object_store)?;
+    let window = config.start.timestamp_nanos()..=config.end.timestamp_nanos();
+    let segments = list_archived_segments(&*store, &Path::from(config.prefix), &window).await?;
+
+    info!(
+        n_segments = segments.len(),
+        "found archived wal segments to replay"
+    );
+
+    if config.dry_run {
+        for (_, _, meta) in &segments {
+            println!("{}", meta.location);
+        }
+        return Ok(());
+    }
+
+    let connection = connection().await;
+    let table_name_indexer = TableIndexFetcher::new(connection.clone())
+        .await
+        .map_err(Error::UnableToInitTableNameFetcher)?;
+    let mut write_client = WriteClient::new(connection)
+        .with_max_request_payload_size_bytes(Some(config.max_request_payload_size_bytes));
+    let mut table_name_indexes = HashMap::new();
+
+    for (id, _, meta) in segments {
+        info!(%id, location = %meta.location, "replaying archived wal segment");
+
+        // The WAL reader operates on files, so stage the segment on local
+        // disk.
+        let data = store.get(&meta.location).await?.bytes().await?;
+        let mut file = tempfile::NamedTempFile::new()?;
+        file.write_all(&data)?;
+        file.flush()?;
+
+        let line_protocol = regenerate_segment(
+            WriteOpEntryDecoder::from(ClosedSegmentFileReader::from_path(file.path())?),
+            &table_name_indexer,
+            &mut table_name_indexes,
+        )
+        .await?;
+
+        for (namespace_id, lp) in line_protocol {
+            let namespace_name = table_name_indexer
+                .namespace_name(namespace_id)
+                .ok_or(Error::UnknownReplayNamespace(namespace_id))?;
+            let lp = String::from_utf8(lp).map_err(|_| Error::InvalidReplayLineProtocol {
+                segment_id: id,
+                namespace_id,
+            })?;
+            let bytes = write_client.write_lp(namespace_name, lp).await?;
+            info!(%id, %namespace_id, %namespace_name, bytes, "replayed archived writes");
+        }
+    }
+
+    Ok(())
+}
+
+/// Lists the archived segments under `prefix` closed within `window`, ordered
+/// by the time they were closed.
+async fn list_archived_segments(
+    store: &DynObjectStore,
+    prefix: &Path,
+    window: &RangeInclusive<i64>,
+) -> Result<Vec<(SegmentId, i64, ObjectMeta)>, object_store::Error> {
+    let mut segments = store
+        .list(Some(prefix))
+        .await?
+        .try_filter_map(|meta| async move {
+            let Some((id, closed_at)) = meta
+                .location
+                .filename()
+                .and_then(wal::parse_archived_segment_name)
+            else {
+                warn!(location = %meta.location, "ignoring unrecognised object in wal archive");
+                return Ok(None);
+            };
+            Ok(window.contains(&closed_at).then_some((id, closed_at, meta)))
+        })
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    segments.sort_by_key(|(id, closed_at, _)| (*closed_at, *id));
+    Ok(segments)
+}
+
+/// Decodes the write entries from `decoder` and regenerates them as line
+/// protocol, returning the line protocol for each namespace written to.
+///
+/// Table name indexes are looked up through `table_name_indexer` and cached
+/// in `table_name_indexes`.
+async fn regenerate_segment(
+    decoder: WriteOpEntryDecoder,
+    table_name_indexer: &TableIndexFetcher,
+    table_name_indexes: &mut HashMap<NamespaceId, HashMap<TableId, String>>,
+) -> Result<HashMap<NamespaceId, Vec<u8>>, Error> {
+    let mut line_protocol: HashMap<NamespaceId, Vec<u8>> = HashMap::new();
+
+    for entry_batch in decoder {
+        for entry in entry_batch? {
+            if !table_name_indexes.contains_key(&entry.namespace) {
+                let index = table_name_indexer
+                    .get_table_name_index(entry.namespace)
+                    .await
+                    .map_err(RegenerateError::from)?;
+                table_name_indexes.insert(entry.namespace, index);
+            }
+
+            LineProtoWriter::new(
+                line_protocol.entry(entry.namespace).or_default(),
+                Some(table_name_indexes[&entry.namespace].clone()),
+            )
+            .write_table_batches(entry.table_batches.into_iter())
+            .map_err(RegenerateError::from)?;
+        }
+    }
+
+    Ok(line_protocol)
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use clap::Parser;
+    use influxdb_iox_client::write::DEFAULT_MAX_REQUEST_PAYLOAD_SIZE_BYTES;
+    use object_store::memory::InMemory;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn list_archived_segments_in_window() {
+        let store = InMemory::default();
+        let prefix = Path::from("wal_archive");
+
+        for (ingester, id, closed_at) in
+            [("a", 3, 300), ("a", 1, 100), ("b", 7, 200), ("b", 8, 400)]
+        {
+            let location = prefix
+                .child(ingester)
+                .child(wal::archived_segment_name(SegmentId::new(id), closed_at));
+            store
+                .put(&location, Bytes::from_static(b"data"))
+                .await
+                .unwrap();
+        }
+        store
+            .put(&prefix.child("bananas"), Bytes::from_static(b"data"))
+            .await
+            .unwrap();
+
+        let got = list_archived_segments(&store, &prefix, &(200..=300))
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(id, closed_at, _)| (id.get(), closed_at))
+            .collect::<Vec<_>>();
+
+        assert_eq!(got, [(7, 200), (3, 300)]);
+    }
+
+    #[test]
+    fn command_default_is_same_as_client_default() {
+        let config = Config::try_parse_from([
+            "replay-archive",
+            "--start",
+            "2023-01-01T00:00:00Z",
+            "--end",
+            "2023-01-02T00:00:00Z",
+        ])
+        .unwrap();
+        assert_eq!(
+            Some(config.max_request_payload_size_bytes),
+            DEFAULT_MAX_REQUEST_PAYLOAD_SIZE_BYTES
+        );
+    }
+}
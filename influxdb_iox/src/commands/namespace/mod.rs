@@ -5,7 +5,9 @@ use thiserror::Error;
 
 mod create;
 mod delete;
+mod rename;
 mod retention;
+mod undelete;
 mod update_limit;
 
 #[allow(clippy::enum_variant_names)]
@@ -44,6 +46,12 @@ enum Command {
 
     /// Delete a namespace
     Delete(delete::Config),
+
+    /// Restore a deleted namespace
+    Undelete(undelete::Config),
+
+    /// Rename an existing namespace
+    Rename(rename::Config),
 }
 
 pub async fn command(connection: Connection, config: Config) -> Result<()> {
@@ -64,6 +72,12 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
         }
         Command::Delete(config) => {
             delete::command(connection, config).await?;
+        }
+        Command::Undelete(config) => {
+            undelete::command(connection, config).await?;
+        }
+        Command::Rename(config) => {
+            rename::command(connection, config).await?;
         } // Deliberately not adding _ => so the compiler will direct people here to impl new
           // commands
     }
@@ -0,0 +1,29 @@
+use influxdb_iox_client::connection::Connection;
+
+use crate::commands::namespace::Result;
+
+/// Rename an existing namespace
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// The namespace to be renamed
+    #[clap(action)]
+    namespace: String,
+
+    /// The new name of the namespace
+    #[clap(action)]
+    new_name: String,
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    let Config {
+        namespace,
+        new_name,
+    } = config;
+
+    let mut client = influxdb_iox_client::namespace::Client::new(connection);
+
+    let namespace = client.rename_namespace(&namespace, &new_name).await?;
+    println!("{}", serde_json::to_string_pretty(&namespace)?);
+
+    Ok(())
+}
@@ -16,14 +16,20 @@ pub struct Config {
 #[derive(Debug, clap::Args)]
 #[clap(group(
             // This arg group "limit" links the members of the below struct 
-            // named "max_tables" and "max_columns_per_table" together as 
-            // mutually exclusive flags. As we specify all flags & commands
+            // named "max_tables", "max_columns_per_table" and the query limits
+            // together as mutually exclusive flags. As we specify all flags & commands
             // using clap-derive rather than the imperative builder, v3 only
             // properly supports this kind of behaviour in a macro code block.
             // NOTE: It takes the variable names and not the flag long names.
             clap::ArgGroup::new("limit")
                 .required(true)
-                .args(&["max_tables", "max_columns_per_table"])
+                .args(&[
+                    "max_tables",
+                    "max_columns_per_table",
+                    "max_concurrent_queries",
+                    "max_query_memory_bytes",
+                    "query_timeout_ms",
+                ])
         ))]
 pub struct Args {
     /// The maximum number of tables to allow for this namespace
@@ -33,6 +39,18 @@ pub struct Args {
     /// The maximum number of columns to allow per table for this namespace
     #[clap(action, long = "max-columns-per-table", short = 'c', group = "limit")]
     max_columns_per_table: Option<i32>,
+
+    /// The maximum number of queries to allow to run concurrently against this namespace
+    #[clap(action, long = "max-concurrent-queries", group = "limit")]
+    max_concurrent_queries: Option<i32>,
+
+    /// The maximum number of bytes of memory a single query against this namespace may reserve
+    #[clap(action, long = "max-query-memory-bytes", group = "limit")]
+    max_query_memory_bytes: Option<i64>,
+
+    /// The maximum wall-clock time in milliseconds a single query against this namespace may run
+    #[clap(action, long = "query-timeout-ms", group = "limit")]
+    query_timeout_ms: Option<i64>,
 }
 
 impl From<Args> for LimitUpdate {
@@ -40,6 +58,9 @@ impl From<Args> for LimitUpdate {
         let Args {
             max_tables,
             max_columns_per_table,
+            max_concurrent_queries,
+            max_query_memory_bytes,
+            query_timeout_ms,
         } = args;
 
         if let Some(n) = max_tables {
@@ -48,6 +69,15 @@ impl From<Args> for LimitUpdate {
         if let Some(n) = max_columns_per_table {
             return Self::MaxColumnsPerTable(n);
         }
+        if let Some(n) = max_concurrent_queries {
+            return Self::MaxConcurrentQueries(n);
+        }
+        if let Some(n) = max_query_memory_bytes {
+            return Self::MaxQueryMemoryBytes(n);
+        }
+        if let Some(n) = query_timeout_ms {
+            return Self::QueryTimeoutMs(n);
+        }
         unreachable!();
     }
 }
@@ -55,17 +85,29 @@ impl From<Args> for LimitUpdate {
 pub async fn command(connection: Connection, config: Config) -> Result<()> {
     let mut client = influxdb_iox_client::namespace::Client::new(connection);
 
+    let limit_update = LimitUpdate::from(config.args);
+    let is_query_limit = matches!(
+        limit_update,
+        LimitUpdate::MaxConcurrentQueries(_)
+            | LimitUpdate::MaxQueryMemoryBytes(_)
+            | LimitUpdate::QueryTimeoutMs(_)
+    );
+
     let namespace = client
-        .update_namespace_service_protection_limit(
-            &config.namespace,
-            LimitUpdate::from(config.args),
-        )
+        .update_namespace_service_protection_limit(&config.namespace, limit_update)
         .await?;
     println!("{}", serde_json::to_string_pretty(&namespace)?);
 
-    println!(
-        r"
+    if is_query_limit {
+        println!(
+            r"
+NOTE: This change takes effect once the queriers refresh their cached copy of the namespace!"
+        );
+    } else {
+        println!(
+            r"
 NOTE: This change will NOT take effect until all router instances have been restarted!"
-    );
+        );
+    }
     Ok(())
 }
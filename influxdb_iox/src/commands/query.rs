@@ -2,7 +2,7 @@ use arrow::record_batch::RecordBatch;
 use clap::ValueEnum;
 use futures::TryStreamExt;
 use influxdb_iox_client::format::influxql::{write_columnar, Options};
-use influxdb_iox_client::{connection::Connection, flight, format::QueryOutputFormat};
+use influxdb_iox_client::{connection::Connection, flight, format::QueryOutputFormat, querier};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -15,6 +15,9 @@ pub enum Error {
 
     #[error("Error formatting InfluxQL: {0}")]
     InfluxQlFormatting(#[from] influxdb_iox_client::format::influxql::Error),
+
+    #[error("Error cancelling query: {0}")]
+    Kill(#[from] influxdb_iox_client::error::Error),
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -30,14 +33,18 @@ enum QueryLanguage {
 
 /// Query the data with SQL
 #[derive(Debug, clap::Parser)]
+#[clap(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Config {
+    #[clap(subcommand)]
+    command: Option<Command>,
+
     /// The IOx namespace to query
-    #[clap(action)]
-    namespace: String,
+    #[clap(action, required = true)]
+    namespace: Option<String>,
 
     /// The query to run, in SQL format
-    #[clap(action)]
-    query: String,
+    #[clap(action, required = true)]
+    query: Option<String>,
 
     /// Output format of the query results
     #[clap(short, long, action)]
@@ -49,6 +56,21 @@ pub struct Config {
     query_lang: QueryLanguage,
 }
 
+/// Manage the queries running on a querier
+#[derive(Debug, clap::Subcommand)]
+enum Command {
+    /// Cancel a running query
+    Kill(KillConfig),
+}
+
+/// Cancel a running query
+#[derive(Debug, clap::Parser)]
+struct KillConfig {
+    /// The ID of the query, as shown in the `query_id` column of `system.queries`
+    #[clap(action)]
+    query_id: String,
+}
+
 #[derive(Debug, Clone, ValueEnum)]
 enum OutputFormat {
     /// Output the most appropriate format for the query language
@@ -75,15 +97,27 @@ impl From<OutputFormat> for QueryOutputFormat {
 }
 
 pub async fn command(connection: Connection, config: Config) -> Result<()> {
-    let mut client = flight::Client::new(connection);
-
     let Config {
+        command,
         namespace,
         format,
         query,
         query_lang,
     } = config;
 
+    if let Some(Command::Kill(KillConfig { query_id })) = command {
+        let mut client = querier::Client::new(connection);
+        client.cancel_query(&query_id).await?;
+        println!("Cancelled query {query_id}");
+        return Ok(());
+    }
+
+    // clap ensures both are set when no subcommand is given
+    let namespace = namespace.expect("namespace is required");
+    let query = query.expect("query is required");
+
+    let mut client = flight::Client::new(connection);
+
     let mut query_results = match query_lang {
         QueryLanguage::Sql => client.sql(namespace, query).await,
         QueryLanguage::InfluxQL => client.influxql(namespace, query).await,
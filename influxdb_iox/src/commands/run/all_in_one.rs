@@ -8,9 +8,10 @@ use clap_blocks::{
     compactor::CompactorConfig,
     compactor_scheduler::CompactorSchedulerConfig,
     gossip::GossipConfig,
-    ingester::IngesterConfig,
+    ingester::{IngesterConfig, WalReplayMode},
     ingester_address::IngesterAddress,
     memory_size::MemorySize,
+    namespace_delete::NamespaceDeleteConfig,
     object_store::{make_object_store, ObjectStoreConfig},
     querier::QuerierConfig,
     router::RouterConfig,
@@ -454,6 +455,7 @@ impl Config {
 
         let ingester_addresses =
             vec![IngesterAddress::from_str(&ingester_grpc_bind_address.to_string()).unwrap()];
+        let router_address = format!("http://{router_grpc_bind_address}");
 
         let router_run_config = RunConfig::new(
             logging_config,
@@ -479,13 +481,20 @@ impl Config {
         let ingester_config = IngesterConfig {
             wal_directory,
             wal_rotation_period_seconds,
+            wal_replay_mode: WalReplayMode::Strict,
+            wal_archive_retention: None,
+            wal_archive_prefix: "wal_archive".to_string(),
             concurrent_query_limit,
             persist_max_parallelism,
             persist_queue_depth,
             persist_hot_partition_cost,
             rpc_write_max_incoming_bytes: 1024 * 1024 * 1024, // 1GiB
             gossip_config: GossipConfig::disabled(),
+            buffer_memory_limit_bytes: None,
+            buffer_memory_hard_limit_percent: 125,
+            buffer_memory_check_interval: Duration::from_secs(1),
             max_partitions_per_namespace: None,
+            parquet_bloom_filter_tables: vec![],
         };
 
         let router_config = RouterConfig {
@@ -499,6 +508,8 @@ impl Config {
             rpc_write_replicas: 1.try_into().unwrap(),
             rpc_write_max_outgoing_bytes: ingester_config.rpc_write_max_incoming_bytes,
             rpc_write_health_num_probes: 10,
+            namespace_delete_config: NamespaceDeleteConfig::default(),
+            partial_writes: false,
             gossip_config: GossipConfig::disabled(),
         };
 
@@ -525,17 +536,23 @@ impl Config {
             max_num_files_per_plan: 200,
             max_partition_fetch_queries_per_second: Some(500),
             gossip_config: GossipConfig::disabled(),
+            parquet_bloom_filter_tables: vec![],
         };
 
         let querier_config = QuerierConfig {
+            gossip_config: GossipConfig::disabled(),
             authz_address,
             num_query_threads: None, // will be ignored
             ingester_addresses,
+            router_address: Some(router_address),
             ram_pool_metadata_bytes: querier_ram_pool_metadata_bytes,
             ram_pool_data_bytes: querier_ram_pool_data_bytes,
+            disk_cache_dir: None,
+            disk_cache_bytes: 0,
             max_concurrent_queries: querier_max_concurrent_queries,
             exec_mem_pool_bytes,
             ingester_circuit_breaker_threshold: u64::MAX, // never for all-in-one-mode
+            parquet_page_pruning: true,
             datafusion_config: Default::default(),
         };
 
@@ -0,0 +1,80 @@
+//! Command line options for running the compaction scheduler
+
+use super::main;
+use crate::process_info::setup_metric_registry;
+use clap_blocks::{
+    catalog_dsn::CatalogDsnConfig, compactor_scheduler::CompactionSchedulerServerConfig,
+    run_config::RunConfig,
+};
+use iox_time::{SystemProvider, TimeProvider};
+use ioxd_common::{
+    server_type::{CommonServerState, CommonServerStateError},
+    Service,
+};
+use ioxd_compactor::create_compaction_scheduler_server_type;
+use observability_deps::tracing::*;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Run: {0}")]
+    Run(#[from] main::Error),
+
+    #[error("Invalid config: {0}")]
+    InvalidConfig(#[from] CommonServerStateError),
+
+    #[error("Catalog DSN error: {0}")]
+    CatalogDsn(#[from] clap_blocks::catalog_dsn::Error),
+}
+
+#[derive(Debug, clap::Parser)]
+#[clap(
+    name = "run",
+    about = "Runs in compaction scheduler mode",
+    long_about = "Run the IOx compaction scheduler server.\n\nThe compaction scheduler hands \
+    out compaction jobs to compactors configured with `--compactor-scheduler remote`.\n\nThe \
+    configuration options below can be set either with the command line flags or with the \
+    specified environment variable. If there is a file named '.env' in the current working \
+    directory, it is sourced before loading the configuration.
+
+Configuration is loaded from the following sources (highest precedence first):
+        - command line arguments
+        - user set environment variables
+        - .env file contents
+        - pre-configured default values"
+)]
+pub struct Config {
+    #[clap(flatten)]
+    pub(crate) run_config: RunConfig,
+
+    #[clap(flatten)]
+    pub(crate) catalog_dsn: CatalogDsnConfig,
+
+    #[clap(flatten)]
+    pub(crate) scheduler_config: CompactionSchedulerServerConfig,
+}
+
+pub async fn command(config: Config) -> Result<(), Error> {
+    let common_state = CommonServerState::from_config(config.run_config.clone())?;
+
+    let time_provider = Arc::new(SystemProvider::new()) as Arc<dyn TimeProvider>;
+    let metric_registry = setup_metric_registry();
+    let catalog = config
+        .catalog_dsn
+        .get_catalog("compaction_scheduler", Arc::clone(&metric_registry))
+        .await?;
+
+    let server_type = create_compaction_scheduler_server_type(
+        &common_state,
+        Arc::clone(&metric_registry),
+        catalog,
+        time_provider,
+        config.scheduler_config,
+    );
+
+    info!("starting compaction scheduler");
+
+    let services = vec![Service::create(server_type, common_state.run_config())];
+    Ok(main::main(common_state, services, metric_registry).await?)
+}
@@ -2,6 +2,7 @@ use snafu::{ResultExt, Snafu};
 use trogging::cli::LoggingConfig;
 
 pub(crate) mod all_in_one;
+mod compaction_scheduler;
 mod compactor;
 mod garbage_collector;
 mod ingester;
@@ -16,6 +17,9 @@ pub enum Error {
     #[snafu(display("Error in compactor subcommand: {}", source))]
     CompactorError { source: compactor::Error },
 
+    #[snafu(display("Error in compaction scheduler subcommand: {}", source))]
+    CompactionSchedulerError { source: compaction_scheduler::Error },
+
     #[snafu(display("Error in garbage collector subcommand: {}", source))]
     GarbageCollectorError { source: garbage_collector::Error },
 
@@ -52,6 +56,7 @@ impl Config {
         match &self.command {
             None => &self.all_in_one_config.logging_config,
             Some(Command::Compactor(config)) => config.run_config.logging_config(),
+            Some(Command::CompactionScheduler(config)) => config.run_config.logging_config(),
             Some(Command::GarbageCollector(config)) => config.run_config.logging_config(),
             Some(Command::Querier(config)) => config.run_config.logging_config(),
             Some(Command::Router(config)) => config.run_config.logging_config(),
@@ -68,6 +73,9 @@ enum Command {
     #[clap(alias = "compactor2")]
     Compactor(compactor::Config),
 
+    /// Run the server in compaction scheduler mode
+    CompactionScheduler(compaction_scheduler::Config),
+
     /// Run the server in querier mode
     Querier(querier::Config),
 
@@ -97,6 +105,9 @@ pub async fn command(config: Config) -> Result<()> {
         Some(Command::Compactor(config)) => {
             compactor::command(config).await.context(CompactorSnafu)
         }
+        Some(Command::CompactionScheduler(config)) => compaction_scheduler::command(config)
+            .await
+            .context(CompactionSchedulerSnafu),
         Some(Command::GarbageCollector(config)) => garbage_collector::command(config)
             .await
             .context(GarbageCollectorSnafu),
@@ -234,6 +234,10 @@ fn parse_aggregate(aggs: &str) -> Result<AggregateType, ParseError> {
         "mean" => Ok(AggregateType::Mean),
         "first" => Ok(AggregateType::First),
         "last" => Ok(AggregateType::Last),
+        "median" => Ok(AggregateType::Median),
+        "stddev" => Ok(AggregateType::Stddev),
+        "spread" => Ok(AggregateType::Spread),
+        "mode" => Ok(AggregateType::Mode),
         _ => AggregateSnafu { agg: aggs }.fail(),
     }
 }
@@ -76,7 +76,10 @@ pub fn read_group(
         predicate,
         read_source: Some(org_bucket),
         range: Some(TimestampRange { start, end: stop }),
-        aggregate: aggregate.map(|a| Aggregate { r#type: a as i32 }),
+        aggregate: aggregate.map(|a| Aggregate {
+            r#type: a as i32,
+            quantile: 0.0,
+        }),
         group: group as i32,
         group_keys,
     }
@@ -108,7 +111,10 @@ pub fn read_window_aggregate(
     // wrap in the PB message type for aggregates.
     let aggregate = aggregates
         .into_iter()
-        .map(|a| Aggregate { r#type: a as i32 })
+        .map(|a| Aggregate {
+            r#type: a as i32,
+            quantile: 0.0,
+        })
         .collect::<Vec<_>>();
 
     Ok(generated_types::ReadWindowAggregateRequest {
@@ -399,6 +399,53 @@ async fn influxql_error_handling() {
         .await
 }
 
+/// Test the query kill CLI command
+#[tokio::test]
+async fn query_kill() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![Step::Custom(Box::new(|state: &mut StepTestState| {
+            async {
+                let querier_addr = state.cluster().querier().querier_grpc_base().to_string();
+
+                Command::cargo_bin("influxdb_iox")
+                    .unwrap()
+                    .arg("-h")
+                    .arg(&querier_addr)
+                    .arg("query")
+                    .arg("kill")
+                    .arg("not-a-query-id")
+                    .assert()
+                    .failure()
+                    .stderr(predicate::str::contains(
+                        "invalid query ID 'not-a-query-id'",
+                    ));
+
+                Command::cargo_bin("influxdb_iox")
+                    .unwrap()
+                    .arg("-h")
+                    .arg(&querier_addr)
+                    .arg("query")
+                    .arg("kill")
+                    .arg("67e55044-10b1-426f-9247-bb680e5fe0c8")
+                    .assert()
+                    .failure()
+                    .stderr(predicate::str::contains(
+                        "query 67e55044-10b1-426f-9247-bb680e5fe0c8 not found",
+                    ));
+            }
+            .boxed()
+        }))],
+    )
+    .run()
+    .await
+}
+
 /// Test the query_ingester CLI command
 #[tokio::test]
 async fn query_ingester() {
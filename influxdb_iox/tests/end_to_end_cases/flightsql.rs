@@ -1,7 +1,7 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 
 use arrow::{
-    array::as_generic_binary_array,
+    array::{as_generic_binary_array, ArrayRef, BooleanArray, Int32Array, StringArray},
     datatypes::{DataType, Schema, TimeUnit},
     record_batch::RecordBatch,
 };
@@ -158,6 +158,115 @@ async fn flightsql_prepared_query() {
     .await
 }
 
+#[tokio::test]
+async fn flightsql_prepared_query_with_parameters() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!(
+                "{table_name},tag1=A,tag2=B val=42i 123456\n\
+                 {table_name},tag1=A,tag2=C val=43i 123457"
+            )),
+            Step::Custom(Box::new(move |state: &mut StepTestState| {
+                async move {
+                    let sql = format!("select * from {table_name} where tag2 = $1 and val > $2");
+                    let mut client = flightsql_client(state.cluster());
+
+                    let mut handle = client.prepare(sql.clone()).await.unwrap();
+
+                    // The parameter types are inferred from the query
+                    let parameter_schema = handle.get_parameter_schema();
+                    let fields = parameter_schema
+                        .fields()
+                        .iter()
+                        .map(|f| (f.name().as_str(), f.data_type().clone()))
+                        .collect::<Vec<_>>();
+                    assert_eq!(
+                        fields,
+                        [
+                            (
+                                "$1",
+                                DataType::Dictionary(
+                                    Box::new(DataType::Int32),
+                                    Box::new(DataType::Utf8)
+                                )
+                            ),
+                            ("$2", DataType::Int64),
+                        ]
+                    );
+
+                    // A string is bound to the tag, and a narrower integer
+                    // to the field.
+                    handle.set_parameters(
+                        RecordBatch::try_from_iter(vec![
+                            ("$1", Arc::new(StringArray::from(vec!["C"])) as ArrayRef),
+                            ("$2", Arc::new(Int32Array::from(vec![1])) as ArrayRef),
+                        ])
+                        .unwrap(),
+                    );
+                    let stream = client.execute(handle).await.unwrap();
+
+                    let batches = collect_stream(stream).await;
+                    insta::assert_yaml_snapshot!(
+                        batches_to_sorted_lines(&batches),
+                        @r###"
+                    ---
+                    - +------+------+--------------------------------+-----+
+                    - "| tag1 | tag2 | time                           | val |"
+                    - +------+------+--------------------------------+-----+
+                    - "| A    | C    | 1970-01-01T00:00:00.000123457Z | 43  |"
+                    - +------+------+--------------------------------+-----+
+                    "###
+                    );
+
+                    // Values of the wrong type are rejected, naming the
+                    // parameter
+                    let mut handle = client.prepare(sql.clone()).await.unwrap();
+                    handle.set_parameters(
+                        RecordBatch::try_from_iter(vec![
+                            ("$1", Arc::new(StringArray::from(vec!["C"])) as ArrayRef),
+                            ("$2", Arc::new(BooleanArray::from(vec![true])) as ArrayRef),
+                        ])
+                        .unwrap(),
+                    );
+                    let err = client.execute(handle).await.unwrap_err();
+                    assert_contains!(
+                        err.to_string(),
+                        "Invalid value for parameter $2: expected Int64, got Boolean"
+                    );
+
+                    // Placeholders must be numbered up to the number of
+                    // parameters of the query
+                    let err = client
+                        .prepare(format!("select * from {table_name} where val > $1000000"))
+                        .await
+                        .unwrap_err();
+                    assert_contains!(err.to_string(), "unsupported placeholder $1000000");
+
+                    // Executing without binding values is an error
+                    let handle = client.prepare(sql).await.unwrap();
+                    let err = client.execute(handle).await.unwrap_err();
+                    assert_contains!(
+                        err.to_string(),
+                        "Prepared statement requires 2 parameter(s), but none were bound"
+                    );
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
 #[tokio::test]
 async fn flightsql_get_sql_infos() {
     test_helpers::maybe_start_logging();
@@ -189,6 +189,113 @@ async fn nanoseconds_measurement_count() {
     .await;
 }
 
+#[tokio::test]
+async fn nanoseconds_median() {
+    Arc::new(ReadWindowAggregateTest {
+        setup_name: "MeasurementForWindowAggregate",
+        aggregate_type: AggregateType::Median,
+        every: 200,
+        offset: 0,
+        request: GrpcRequestBuilder::new()
+            .or_tag_predicates([("city", "Boston"), ("city", "LA")].into_iter())
+            .timestamp_range(200, 400),
+        expected_results: vec![
+            "SeriesFrame, tags: _field=temp,_measurement=h2o,city=Boston,state=MA, type: 0",
+            "FloatPointsFrame, timestamps: [400], values: \"71.5\"",
+            "SeriesFrame, tags: _field=temp,_measurement=h2o,city=LA,state=CA, type: 0",
+            "FloatPointsFrame, timestamps: [400], values: \"91.5\"",
+        ],
+    })
+    .run()
+    .await;
+}
+
+#[tokio::test]
+async fn nanoseconds_stddev() {
+    Arc::new(ReadWindowAggregateTest {
+        setup_name: "MeasurementForWindowAggregate",
+        aggregate_type: AggregateType::Stddev,
+        every: 200,
+        offset: 0,
+        request: GrpcRequestBuilder::new()
+            .or_tag_predicates([("city", "Boston"), ("city", "LA")].into_iter())
+            .timestamp_range(200, 400),
+        expected_results: vec![
+            "SeriesFrame, tags: _field=temp,_measurement=h2o,city=Boston,state=MA, type: 0",
+            "FloatPointsFrame, timestamps: [400], values: \"0.7071067811865476\"",
+            "SeriesFrame, tags: _field=temp,_measurement=h2o,city=LA,state=CA, type: 0",
+            "FloatPointsFrame, timestamps: [400], values: \"0.7071067811865476\"",
+        ],
+    })
+    .run()
+    .await;
+}
+
+#[tokio::test]
+async fn nanoseconds_spread() {
+    Arc::new(ReadWindowAggregateTest {
+        setup_name: "MeasurementForWindowAggregate",
+        aggregate_type: AggregateType::Spread,
+        every: 200,
+        offset: 0,
+        request: GrpcRequestBuilder::new()
+            .or_tag_predicates([("city", "Boston"), ("city", "LA")].into_iter())
+            .timestamp_range(200, 400),
+        expected_results: vec![
+            "SeriesFrame, tags: _field=temp,_measurement=h2o,city=Boston,state=MA, type: 0",
+            "FloatPointsFrame, timestamps: [400], values: \"1\"",
+            "SeriesFrame, tags: _field=temp,_measurement=h2o,city=LA,state=CA, type: 0",
+            "FloatPointsFrame, timestamps: [400], values: \"1\"",
+        ],
+    })
+    .run()
+    .await;
+}
+
+#[tokio::test]
+async fn nanoseconds_quantile() {
+    Arc::new(ReadWindowAggregateTest {
+        setup_name: "MeasurementForWindowAggregate",
+        aggregate_type: AggregateType::Quantile,
+        every: 200,
+        offset: 0,
+        request: GrpcRequestBuilder::new()
+            .or_tag_predicates([("city", "Boston"), ("city", "LA")].into_iter())
+            .timestamp_range(200, 400)
+            .quantile(1.0),
+        expected_results: vec![
+            "SeriesFrame, tags: _field=temp,_measurement=h2o,city=Boston,state=MA, type: 0",
+            "FloatPointsFrame, timestamps: [400], values: \"72\"",
+            "SeriesFrame, tags: _field=temp,_measurement=h2o,city=LA,state=CA, type: 0",
+            "FloatPointsFrame, timestamps: [400], values: \"92\"",
+        ],
+    })
+    .run()
+    .await;
+}
+
+#[tokio::test]
+async fn nanoseconds_mode() {
+    Arc::new(ReadWindowAggregateTest {
+        setup_name: "MeasurementForWindowAggregate",
+        aggregate_type: AggregateType::Mode,
+        every: 200,
+        offset: 0,
+        request: GrpcRequestBuilder::new()
+            .or_tag_predicates([("city", "Boston"), ("city", "LA")].into_iter())
+            .timestamp_range(200, 400),
+        expected_results: vec![
+            // all values occur once, so the first value in each window is used
+            "SeriesFrame, tags: _field=temp,_measurement=h2o,city=Boston,state=MA, type: 0",
+            "FloatPointsFrame, timestamps: [400], values: \"71\"",
+            "SeriesFrame, tags: _field=temp,_measurement=h2o,city=LA,state=CA, type: 0",
+            "FloatPointsFrame, timestamps: [400], values: \"91\"",
+        ],
+    })
+    .run()
+    .await;
+}
+
 // See <https://github.com/influxdata/influxdb_iox/issues/2697>
 #[tokio::test]
 async fn min_defect_2697() {
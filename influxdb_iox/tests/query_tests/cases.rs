@@ -453,6 +453,45 @@ mod influxql {
         .await;
     }
 
+    /// Test INTEGRAL, SPREAD, MODE, ELAPSED and SAMPLE against InfluxDB 1.x results.
+    #[tokio::test]
+    async fn aggregate_functions() {
+        test_helpers::maybe_start_logging();
+
+        TestCase {
+            input: "cases/in/aggregate_functions.influxql",
+            chunk_stage: ChunkStage::Ingester,
+        }
+        .run()
+        .await;
+    }
+
+    /// Test SELECT INTO, which writes the results through the router.
+    #[tokio::test]
+    async fn select_into() {
+        test_helpers::maybe_start_logging();
+
+        TestCase {
+            input: "cases/in/select_into.influxql",
+            chunk_stage: ChunkStage::Ingester,
+        }
+        .run()
+        .await;
+    }
+
+    /// Test SLIMIT and SOFFSET series pagination.
+    #[tokio::test]
+    async fn series_limit() {
+        test_helpers::maybe_start_logging();
+
+        TestCase {
+            input: "cases/in/series_limit.influxql",
+            chunk_stage: ChunkStage::Ingester,
+        }
+        .run()
+        .await;
+    }
+
     #[tokio::test]
     async fn influxql_metadata() {
         test_helpers::maybe_start_logging();
@@ -1406,6 +1406,46 @@ pub static SETUPS: Lazy<HashMap<SetupName, SetupSteps>> = Lazy::new(|| {
                 },
             ],
         ),
+        (
+            // Used for InfluxQL SELECT INTO tests
+            "select_into",
+            vec![
+                Step::RecordNumParquetFiles,
+                Step::WriteLineProtocol(include_str!("data/select_into.lp").to_string()),
+                Step::Persist,
+                Step::WaitForPersisted {
+                    expected_increase: 2,
+                },
+            ],
+        ),
+        (
+            // Used for InfluxQL aggregate and selector function tests
+            "aggregate_functions",
+            vec![
+                Step::RecordNumParquetFiles,
+                Step::WriteLineProtocol(
+                    include_str!("data/aggregate_functions.lp").to_string()
+                ),
+                Step::Persist,
+                Step::WaitForPersisted {
+                    expected_increase: 1,
+                },
+            ],
+        ),
+        (
+            // Used for SLIMIT and SOFFSET tests for InfluxQL
+            "series_limit",
+            vec![
+                Step::RecordNumParquetFiles,
+                Step::WriteLineProtocol(
+                    include_str!("data/series_limit.lp").to_string()
+                ),
+                Step::Persist,
+                Step::WaitForPersisted {
+                    expected_increase: 2,
+                },
+            ],
+        ),
         (
             "DuplicateDifferentDomains",
             (0..2)
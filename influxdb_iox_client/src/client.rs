@@ -27,6 +27,9 @@ pub mod ingester;
 /// Client for namespace API
 pub mod namespace;
 
+/// Client for the querier API
+pub mod querier;
+
 /// Client for schema API
 pub mod schema;
 
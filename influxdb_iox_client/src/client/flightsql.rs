@@ -23,9 +23,13 @@
 
 use std::sync::Arc;
 
-use arrow::datatypes::{Schema, SchemaRef};
+use arrow::{
+    datatypes::{Schema, SchemaRef},
+    record_batch::RecordBatch,
+};
 use arrow_flight::{
     decode::FlightRecordBatchStream,
+    encode::FlightDataEncoderBuilder,
     error::{FlightError, Result},
     sql::{
         ActionCreatePreparedStatementRequest, ActionCreatePreparedStatementResult, Any,
@@ -34,10 +38,11 @@ use arrow_flight::{
         CommandGetTables, CommandGetXdbcTypeInfo, CommandPreparedStatementQuery,
         CommandStatementQuery, ProstMessageExt,
     },
-    Action, FlightClient, FlightDescriptor, FlightInfo, IpcMessage, Ticket,
+    Action, FlightClient, FlightData, FlightDescriptor, FlightInfo, IpcMessage, Ticket,
 };
 use bytes::Bytes;
-use futures_util::TryStreamExt;
+use futures_util::{stream, TryStreamExt};
+use generated_types::influxdata::iox::querier::v1::DoPutPreparedStatementResult;
 use prost::Message;
 use tonic::metadata::MetadataMap;
 use tonic::transport::Channel;
@@ -490,11 +495,16 @@ impl FlightSqlClient {
         ))
     }
 
-    /// Execute a SQL query on the server using [`CommandStatementQuery`]
+    /// Execute a prepared statement on the server using
+    /// [`CommandPreparedStatementQuery`]
     ///
-    /// This involves two round trips
+    /// This involves two round trips, or three if parameter values are bound
     ///
-    /// Step 1: send a [`CommandStatementQuery`] message to the
+    /// Step 0: if parameter values are bound with
+    /// [`PreparedStatement::set_parameters`], send them to the `DoPut`
+    /// endpoint, receiving an updated prepared statement handle.
+    ///
+    /// Step 1: send a [`CommandPreparedStatementQuery`] message to the
     /// `GetFlightInfo` endpoint of the FlightSQL server to receive a
     /// FlightInfo descriptor.
     ///
@@ -509,8 +519,16 @@ impl FlightSqlClient {
             prepared_statement_handle,
             dataset_schema: _,
             parameter_schema: _,
+            parameter_binding,
         } = statement;
-        // TODO handle parameters (via DoPut)
+
+        let prepared_statement_handle = match parameter_binding {
+            Some(params) => {
+                self.bind_parameters(prepared_statement_handle, params)
+                    .await?
+            }
+            None => prepared_statement_handle,
+        };
 
         let cmd = CommandPreparedStatementQuery {
             prepared_statement_handle,
@@ -518,6 +536,49 @@ impl FlightSqlClient {
 
         self.do_get_with_cmd(cmd.as_any()).await
     }
+
+    /// Send the parameter values in `params` for the prepared statement
+    /// `prepared_statement_handle` to the `DoPut` endpoint, returning the
+    /// updated handle to execute it with, if returned by the server.
+    async fn bind_parameters(
+        &mut self,
+        prepared_statement_handle: Bytes,
+        params: RecordBatch,
+    ) -> Result<Bytes> {
+        let cmd = CommandPreparedStatementQuery {
+            prepared_statement_handle: prepared_statement_handle.clone(),
+        };
+
+        let mut data: Vec<FlightData> = FlightDataEncoderBuilder::new()
+            .build(stream::iter([Ok(params)]))
+            .try_collect()
+            .await?;
+        // The command is passed in the descriptor of the first message
+        if let Some(first) = data.first_mut() {
+            first.flight_descriptor = Some(FlightDescriptor::new_cmd(cmd.as_any().encode_to_vec()));
+        }
+
+        let results: Vec<_> = self
+            .inner
+            .do_put(stream::iter(data.into_iter().map(Ok)))
+            .await?
+            .try_collect()
+            .await?;
+
+        // Servers may return an updated handle, which includes the bound
+        // parameter values
+        let updated_handle = match results.first() {
+            Some(result) if !result.app_metadata.is_empty() => {
+                DoPutPreparedStatementResult::decode(result.app_metadata.as_ref())
+                    .map_err(|e| FlightError::ExternalError(Box::new(e)))?
+                    .prepared_statement_handle
+                    .map(Bytes::from)
+            }
+            _ => None,
+        };
+
+        Ok(updated_handle.unwrap_or(prepared_statement_handle))
+    }
 }
 
 fn schema_bytes_to_schema(schema: Bytes) -> Result<SchemaRef> {
@@ -541,6 +602,9 @@ pub struct PreparedStatement {
 
     /// Schema of parameters, if any
     parameter_schema: SchemaRef,
+
+    /// Parameter values to bind when executing, if any
+    parameter_binding: Option<RecordBatch>,
 }
 
 impl PreparedStatement {
@@ -556,6 +620,7 @@ impl PreparedStatement {
             prepared_statement_handle,
             dataset_schema,
             parameter_schema,
+            parameter_binding: None,
         }
     }
 
@@ -568,4 +633,10 @@ impl PreparedStatement {
     pub fn get_parameter_schema(&self) -> SchemaRef {
         Arc::clone(&self.parameter_schema)
     }
+
+    /// Set the parameter values to bind when executing the statement, a
+    /// single row with a column for each parameter
+    pub fn set_parameters(&mut self, params: RecordBatch) {
+        self.parameter_binding = Some(params);
+    }
 }
@@ -118,4 +118,36 @@ impl Client {
 
         Ok(())
     }
+
+    /// Restore a deleted namespace
+    ///
+    /// Only namespaces deleted within the grace period configured on the
+    /// server can be restored.
+    pub async fn undelete_namespace(&mut self, namespace: &str) -> Result<Namespace, Error> {
+        let response = self
+            .inner
+            .undelete_namespace(UndeleteNamespaceRequest {
+                name: namespace.to_string(),
+            })
+            .await?;
+
+        Ok(response.into_inner().namespace.unwrap_field("namespace")?)
+    }
+
+    /// Rename a namespace
+    pub async fn rename_namespace(
+        &mut self,
+        namespace: &str,
+        new_name: &str,
+    ) -> Result<Namespace, Error> {
+        let response = self
+            .inner
+            .rename_namespace(RenameNamespaceRequest {
+                name: namespace.to_string(),
+                new_name: new_name.to_string(),
+            })
+            .await?;
+
+        Ok(response.into_inner().namespace.unwrap_field("namespace")?)
+    }
 }
@@ -0,0 +1,36 @@
+use self::generated_types::{query_service_client::QueryServiceClient, *};
+use crate::{connection::Connection, error::Error};
+use client_util::connection::GrpcConnection;
+
+/// Re-export generated_types
+pub mod generated_types {
+    pub use generated_types::influxdata::iox::querier::v1::{
+        query_service_client, CancelQueryRequest, CancelQueryResponse,
+    };
+}
+
+/// A basic client for managing the queries running on a querier.
+#[derive(Debug, Clone)]
+pub struct Client {
+    inner: QueryServiceClient<GrpcConnection>,
+}
+
+impl Client {
+    /// Creates a new client with the provided connection
+    pub fn new(connection: Connection) -> Self {
+        Self {
+            inner: QueryServiceClient::new(connection.into_grpc_connection()),
+        }
+    }
+
+    /// Cancel the running query with the given ID
+    pub async fn cancel_query(&mut self, query_id: &str) -> Result<(), Error> {
+        self.inner
+            .cancel_query(CancelQueryRequest {
+                query_id: query_id.to_string(),
+            })
+            .await?;
+
+        Ok(())
+    }
+}
@@ -57,4 +57,34 @@ impl Client {
 
         Ok(response.into_inner().table.unwrap_field("table")?)
     }
+
+    /// Soft-delete a table
+    pub async fn delete_table(&mut self, namespace: &str, table: &str) -> Result<(), Error> {
+        self.inner
+            .delete_table(DeleteTableRequest {
+                namespace_name: namespace.to_string(),
+                table_name: table.to_string(),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Soft-delete a column of a table
+    pub async fn delete_column(
+        &mut self,
+        namespace: &str,
+        table: &str,
+        column: &str,
+    ) -> Result<(), Error> {
+        self.inner
+            .delete_column(DeleteColumnRequest {
+                namespace_name: namespace.to_string(),
+                table_name: table.to_string(),
+                column_name: column.to_string(),
+            })
+            .await?;
+
+        Ok(())
+    }
 }
@@ -332,6 +332,26 @@ impl Client {
         Ok(responses)
     }
 
+    /// Make a request to query::read_series_cardinality and do the
+    /// required async dance to flatten the resulting stream to the
+    /// number of series
+    pub async fn read_series_cardinality(
+        &mut self,
+        request: ReadSeriesCardinalityRequest,
+    ) -> Result<i64, tonic::Status> {
+        let request = request.log_trace("read_series_cardinality request");
+        let responses: Vec<_> = self
+            .inner
+            .read_series_cardinality(request)
+            .await
+            .log_trace("read_series_cardinality response")?
+            .into_inner()
+            .try_collect()
+            .await?;
+
+        Ok(responses.into_iter().flat_map(|r| r.values).sum())
+    }
+
     /// Extract the data frames from the list of ReadResponse
     fn collect_data(responses: Vec<ReadResponse>) -> Vec<read_response::frame::Data> {
         responses
@@ -12,7 +12,7 @@ use ingester::internal_implementation_details::{
     write::{
         PartitionedData as PayloadPartitionedData, TableData as PayloadTableData, WriteOperation,
     },
-    DmlError, DmlSink, IngestOp, IngestState, PartitionData, PartitionIter,
+    DmlError, DmlSink, IngestOp, IngestState, PartitionData, PartitionIter, WalReplayMode,
 };
 use wal::SequencedWalOp;
 
@@ -67,10 +67,12 @@ fn wal_replay_bench(c: &mut Criterion) {
 
                 // Replay the wal into the NOP.
                 ingester::internal_implementation_details::replay(
+                    &wal,
                     &wal,
                     &sink,
                     Arc::new(persist),
                     Arc::new(IngestState::default()),
+                    WalReplayMode::Strict,
                     &metric::Registry::default(),
                 )
                 .await
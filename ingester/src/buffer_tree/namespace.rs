@@ -186,6 +186,13 @@ where
                         .await?;
                 }
             }
+            IngestOp::Delete(delete) => {
+                // A delete only affects buffered data - if there is no data
+                // for the table, there is nothing to delete.
+                if let Some(table_data) = self.tables.get(&delete.table()) {
+                    table_data.apply_delete(&delete);
+                }
+            }
         }
 
         Ok(())
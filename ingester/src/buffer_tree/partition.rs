@@ -3,9 +3,10 @@
 use std::sync::Arc;
 
 use data_types::{
-    sequence_number_set::SequenceNumberSet, NamespaceId, PartitionKey, SequenceNumber,
-    SortedColumnSet, TableId, TimestampMinMax, TransitionPartitionId,
+    sequence_number_set::SequenceNumberSet, DeletePredicate, NamespaceId, PartitionKey,
+    SequenceNumber, SortedColumnSet, TableId, TimestampMinMax, TransitionPartitionId,
 };
+use iox_time::{SystemProvider, TimeProvider};
 use mutable_batch::MutableBatch;
 use observability_deps::tracing::*;
 use schema::{merge::SchemaMerger, sort::SortKey, Schema};
@@ -18,7 +19,8 @@ use self::{
 };
 use super::{namespace::NamespaceName, table::metadata::TableMetadata, BufferWriteError};
 use crate::{
-    deferred_load::DeferredLoad, query::projection::OwnedProjection, query_adaptor::QueryAdaptor,
+    deferred_load::DeferredLoad, dml_payload::delete::DeleteReference,
+    query::projection::OwnedProjection, query_adaptor::QueryAdaptor,
 };
 
 mod buffer;
@@ -113,6 +115,16 @@ pub struct PartitionData {
     /// persisting with a unique, opaque identifier.
     persisting: PersistingList,
 
+    /// References to the deletes that removed rows from the "hot" `buffer`.
+    ///
+    /// These are held until the buffer is persisted, ensuring the deletes are
+    /// not dropped from the WAL before the data they applied to.
+    buffer_delete_refs: Vec<Arc<DeleteReference>>,
+
+    /// References to the deletes that removed rows from the batches in the
+    /// `persisting` list, released when the identified batch is persisted.
+    persisting_delete_refs: Vec<(BatchIdent, Arc<DeleteReference>)>,
+
     /// The number of persist operations started over the lifetime of this
     /// [`PartitionData`].
     started_persistence_count: BatchIdent,
@@ -154,6 +166,8 @@ impl PartitionData {
             table,
             buffer: DataBuffer::default(),
             persisting: PersistingList::default(),
+            buffer_delete_refs: Vec::new(),
+            persisting_delete_refs: Vec::new(),
             started_persistence_count: BatchIdent::default(),
             completed_persistence_count: 0,
             partition_counter,
@@ -200,12 +214,113 @@ impl PartitionData {
         Ok(())
     }
 
+    /// Remove all buffered and persisting rows matched by `predicate`.
+    ///
+    /// A reference to the delete is acquired from `reference` for each buffer
+    /// that had rows removed, and released when that buffer is persisted.
+    ///
+    /// If all the rows in the "hot" buffer are removed, the sequence numbers
+    /// of the writes that were buffered (and of any previous deletes applied to
+    /// them) are transferred to `reference` to be released with the delete.
+    pub(crate) fn apply_delete(
+        &mut self,
+        predicate: &DeletePredicate,
+        reference: &Arc<DeleteReference>,
+    ) {
+        match self.persisting.apply_delete(predicate) {
+            Ok(idents) => self
+                .persisting_delete_refs
+                .extend(idents.into_iter().map(|ident| (ident, reference.acquire()))),
+            Err(e) => warn!(
+                error=%e,
+                namespace_id = %self.namespace_id,
+                table_id = %self.table_id,
+                partition_id = %self.partition_id,
+                "failed to apply delete to persisting data"
+            ),
+        }
+
+        match self.buffer.apply_delete(predicate) {
+            Ok((0, _)) => {}
+            Ok((_, None)) => self.buffer_delete_refs.push(reference.acquire()),
+            Ok((_, Some(set))) => {
+                // The buffer is now empty and will never be persisted.
+                reference.adopt(set);
+                for r in self.buffer_delete_refs.drain(..) {
+                    if let Some(set) = r.release() {
+                        reference.adopt(set);
+                    }
+                }
+
+                if self.persisting.is_empty() {
+                    // This partition is transitioning from non-empty to empty.
+                    self.partition_counter.dec();
+                    self.is_empty = true;
+                }
+            }
+            Err(e) => warn!(
+                error=%e,
+                namespace_id = %self.namespace_id,
+                table_id = %self.table_id,
+                partition_id = %self.partition_id,
+                "failed to apply delete to buffered data"
+            ),
+        }
+
+        trace!(
+            namespace_id = %self.namespace_id,
+            table_id = %self.table_id,
+            table = %self.table,
+            partition_id = %self.partition_id,
+            partition_key = %self.partition_key,
+            "applied delete"
+        );
+    }
+
+    /// Drop the buffered and persisting values of the deleted `columns`.
+    ///
+    /// Once dropped, a column may be re-created with a different type and
+    /// buffered again, and is no longer returned by queries.
+    ///
+    /// As with [`Self::apply_delete()`], a reference to the delete is acquired
+    /// from `reference` for each buffer that contained any of the `columns`,
+    /// ensuring the delete is retained in the WAL until the affected data is
+    /// persisted.
+    pub(crate) fn drop_columns(&mut self, columns: &[String], reference: &Arc<DeleteReference>) {
+        let idents = self.persisting.drop_columns(columns);
+        self.persisting_delete_refs
+            .extend(idents.into_iter().map(|ident| (ident, reference.acquire())));
+
+        if self.buffer.drop_columns(columns) {
+            self.buffer_delete_refs.push(reference.acquire());
+        }
+
+        trace!(
+            namespace_id = %self.namespace_id,
+            table_id = %self.table_id,
+            table = %self.table,
+            partition_id = %self.partition_id,
+            partition_key = %self.partition_key,
+            ?columns,
+            "dropped deleted columns"
+        );
+    }
+
     /// Return an estimated cost of persisting the data buffered in this
     /// [`PartitionData`].
     pub(crate) fn persist_cost_estimate(&self) -> usize {
         self.buffer.persist_cost_estimate()
     }
 
+    /// Return the estimated amount of memory (in bytes) used by the data in
+    /// this [`PartitionData`].
+    ///
+    /// This value is inclusive of "hot" buffered data, and all currently
+    /// persisting data.
+    pub(crate) fn memory_bytes(&self) -> usize {
+        self.buffer.persist_cost_estimate() + self.persisting.size()
+    }
+
     /// Returns the number of rows currently buffered in this [`PartitionData`].
     ///
     /// The returned value will always match the row count of the data returned
@@ -396,19 +511,26 @@ impl PartitionData {
             "marking partition as persisting"
         );
 
-        // Wrap the persisting data in the type wrapper
+        // Wrap the persisting data in the type wrapper, recording the snapshot
+        // time for use as the upper bound on when the data was written.
         let data = PersistingData::new(
             QueryAdaptor::new(
                 self.partition_id.clone(),
                 fsm.get_query_data(&OwnedProjection::default()),
             ),
             batch_ident,
+            SystemProvider::new().now(),
         );
 
         // Push the buffer into the persisting list (which maintains batch
         // order).
         self.persisting.push(batch_ident, fsm);
 
+        // Any deletes applied to the buffer are now held until the batch is
+        // persisted.
+        self.persisting_delete_refs
+            .extend(self.buffer_delete_refs.drain(..).map(|r| (batch_ident, r)));
+
         // Invariant: the partition must not be marked as empty when there's an
         // entry in the persisting list.
         debug_assert!(!self.is_empty());
@@ -464,8 +586,20 @@ impl PartitionData {
             self.is_empty = true;
         }
 
-        // Return the set of IDs this buffer contained.
-        fsm.into_sequence_number_set()
+        // Return the set of IDs this buffer contained, and those of any deletes
+        // for which this buffer held the last reference.
+        let mut set = fsm.into_sequence_number_set();
+        self.persisting_delete_refs.retain(|(ident, r)| {
+            if *ident != batch.batch_ident() {
+                return true;
+            }
+            if let Some(released) = r.release() {
+                set.add_set(&released);
+            }
+            false
+        });
+
+        set
     }
 
     pub(crate) fn partition_id(&self) -> &TransitionPartitionId {
@@ -544,10 +678,12 @@ mod tests {
     use datafusion_util::test_collect;
     use iox_catalog::interface::Catalog;
     use mutable_batch_lp::test_helpers::lp_to_mutable_batch;
+    use predicate::delete_predicate::parse_delete_predicate;
 
     use super::*;
     use crate::{
         buffer_tree::partition::resolver::SortKeyResolver,
+        dml_payload::delete::DeleteOperation,
         test_util::{populate_catalog, PartitionDataBuilder, ARBITRARY_TRANSITION_PARTITION_ID},
     };
 
@@ -609,6 +745,159 @@ mod tests {
         }
     }
 
+    // Apply deletes to buffered and persisting data, ensuring the rows are
+    // removed from query results and the delete sequence numbers are retained
+    // until the affected data is persisted.
+    #[tokio::test]
+    async fn test_apply_delete() {
+        let mut p = PartitionDataBuilder::new().build();
+
+        let mb = lp_to_mutable_batch(
+            r#"bananas,city=London people=2 10
+            bananas,city=Madrid people=4 20"#,
+        )
+        .1;
+        p.buffer_write(mb, SequenceNumber::new(1))
+            .expect("write should succeed");
+
+        let persisting_data = p.mark_persisting().expect("must contain existing data");
+
+        let mb = lp_to_mutable_batch(r#"bananas,city=Paris people=6 30"#).1;
+        p.buffer_write(mb, SequenceNumber::new(2))
+            .expect("write should succeed");
+
+        // Delete a row from the persisting data.
+        let op1 = DeleteOperation::new(
+            NamespaceId::new(1),
+            p.table_id(),
+            Some(parse_delete_predicate("0", "100", "city=London").unwrap()),
+            vec![],
+            SequenceNumber::new(3),
+            None,
+        );
+        p.apply_delete(op1.predicate().unwrap(), op1.reference());
+
+        // Delete all the rows in the buffer.
+        let op2 = DeleteOperation::new(
+            NamespaceId::new(1),
+            p.table_id(),
+            Some(parse_delete_predicate("0", "100", "city=Paris").unwrap()),
+            vec![],
+            SequenceNumber::new(4),
+            None,
+        );
+        p.apply_delete(op2.predicate().unwrap(), op2.reference());
+
+        let data = p
+            .get_query_data(&OwnedProjection::default())
+            .expect("must have data");
+        let expected = [
+            "+--------+--------+--------------------------------+",
+            "| city   | people | time                           |",
+            "+--------+--------+--------------------------------+",
+            "| Madrid | 4.0    | 1970-01-01T00:00:00.000000020Z |",
+            "+--------+--------+--------------------------------+",
+        ];
+        assert_batches_eq!(expected, data.record_batches());
+        assert_eq!(p.rows(), 1);
+
+        // The second delete emptied the buffer, and is released alongside the
+        // removed write once the applier releases it.
+        let set = op2.reference().release().expect("must be last reference");
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [SequenceNumber::new(2), SequenceNumber::new(4)]
+        );
+
+        // The first delete is retained until the persisting data is persisted.
+        assert!(op1.reference().release().is_none());
+        let set = p.mark_persisted(persisting_data);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [SequenceNumber::new(1), SequenceNumber::new(3)]
+        );
+
+        assert!(p.is_empty());
+        assert!(p.get_query_data(&OwnedProjection::default()).is_none());
+    }
+
+    // Ensure a column delete drops the column from both the buffered and
+    // persisting data, and allows the column to be re-created with a new type.
+    #[tokio::test]
+    async fn test_drop_columns() {
+        let mut p = PartitionDataBuilder::new().build();
+
+        let mb = lp_to_mutable_batch(r#"bananas,city=London people=2 10"#).1;
+        p.buffer_write(mb, SequenceNumber::new(1))
+            .expect("write should succeed");
+
+        let persisting_data = p.mark_persisting().expect("must contain existing data");
+
+        let mb = lp_to_mutable_batch(r#"bananas,city=Paris people=6 30"#).1;
+        p.buffer_write(mb, SequenceNumber::new(2))
+            .expect("write should succeed");
+
+        let op = DeleteOperation::new(
+            NamespaceId::new(1),
+            p.table_id(),
+            None,
+            vec!["people".to_string()],
+            SequenceNumber::new(3),
+            None,
+        );
+        p.drop_columns(op.columns(), op.reference());
+
+        // The deleted column is no longer returned by queries.
+        let data = p
+            .get_query_data(&OwnedProjection::default())
+            .expect("must have data");
+        assert!(p.schema().unwrap().find_index_of("people").is_none());
+        let expected = [
+            "+--------+--------------------------------+",
+            "| city   | time                           |",
+            "+--------+--------------------------------+",
+            "| London | 1970-01-01T00:00:00.000000010Z |",
+            "| Paris  | 1970-01-01T00:00:00.000000030Z |",
+            "+--------+--------------------------------+",
+        ];
+        assert_batches_eq!(expected, data.record_batches());
+        assert_eq!(p.rows(), 2);
+
+        // Re-creating the column with a different type succeeds immediately.
+        let mb = lp_to_mutable_batch(r#"bananas,city=Madrid people="lots" 40"#).1;
+        p.buffer_write(mb, SequenceNumber::new(4))
+            .expect("write re-creating the column should succeed");
+
+        let schema = p.schema().expect("must have data");
+        let (col_type, _) = schema
+            .field_by_name("people")
+            .expect("re-created column must exist");
+        assert_eq!(
+            col_type,
+            schema::InfluxColumnType::Field(schema::InfluxFieldType::String)
+        );
+        assert_eq!(p.rows(), 3);
+
+        // The delete is retained until both the buffered and persisting data it
+        // was applied to are persisted.
+        assert!(op.reference().release().is_none());
+        let set = p.mark_persisted(persisting_data);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [SequenceNumber::new(1)]);
+
+        let persisting_data = p.mark_persisting().expect("must contain existing data");
+        let set = p.mark_persisted(persisting_data);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [
+                SequenceNumber::new(2),
+                SequenceNumber::new(3),
+                SequenceNumber::new(4)
+            ]
+        );
+
+        assert!(p.is_empty());
+    }
+
     // Test persist operations against the partition, ensuring data is readable
     // both before, during, and after a persist takes place.
     #[tokio::test]
@@ -1231,6 +1520,38 @@ mod tests {
         );
     }
 
+    // Ensure the memory estimate covers both the buffered and persisting data,
+    // and that persisting data is released once the persist completes.
+    #[tokio::test]
+    async fn test_memory_bytes() {
+        let mut p = PartitionDataBuilder::new().build();
+        assert_eq!(p.memory_bytes(), 0);
+
+        let mb = lp_to_mutable_batch(r#"bananas,city=London people=2,pigeons="millions" 10"#).1;
+        p.buffer_write(mb, SequenceNumber::new(1))
+            .expect("write should succeed");
+        let buffered = p.memory_bytes();
+        assert!(buffered > 0);
+        assert_eq!(buffered, p.persist_cost_estimate());
+
+        // Marking the data as persisting moves it out of the buffer, but it is
+        // still held in memory.
+        let data = p.mark_persisting().expect("must contain existing data");
+        assert_eq!(p.persist_cost_estimate(), 0);
+        let persisting = p.memory_bytes();
+        assert!(persisting > 0);
+
+        // Writes during the persist add to the total.
+        let mb = lp_to_mutable_batch(r#"bananas,city=Madrid people=4,pigeons="none" 20"#).1;
+        p.buffer_write(mb, SequenceNumber::new(2))
+            .expect("write should succeed");
+        assert_eq!(p.memory_bytes(), persisting + p.persist_cost_estimate());
+
+        // Once persisted, only the buffered write remains.
+        let _ = p.mark_persisted(data);
+        assert_eq!(p.memory_bytes(), p.persist_cost_estimate());
+    }
+
     #[tokio::test]
     async fn test_mark_persisting_no_data() {
         let mut p = PartitionDataBuilder::new().build();
@@ -1,5 +1,8 @@
 use arrow::record_batch::RecordBatch;
-use data_types::{SequenceNumber, TimestampMinMax};
+use data_types::{
+    sequence_number_set::SequenceNumberSet, DeletePredicate, SequenceNumber, TimestampMinMax,
+};
+use datafusion::error::DataFusionError;
 use mutable_batch::MutableBatch;
 
 mod always_some;
@@ -56,6 +59,34 @@ impl DataBuffer {
         })
     }
 
+    /// Remove all buffered rows matched by `predicate`, returning the number
+    /// of rows removed.
+    ///
+    /// If all buffered rows are removed, the [`SequenceNumberSet`] of the
+    /// removed writes is returned, and this buffer is reset.
+    pub(crate) fn apply_delete(
+        &mut self,
+        predicate: &DeletePredicate,
+    ) -> Result<(usize, Option<SequenceNumberSet>), DataFusionError> {
+        self.0.mutate(|fsm| match fsm {
+            FsmState::Buffering(mut b) => {
+                let ret = b.apply_delete(predicate);
+                (FsmState::Buffering(b), ret)
+            }
+        })
+    }
+
+    /// Drop the buffered values of `columns`, returning true if any of them
+    /// were buffered.
+    pub(crate) fn drop_columns(&mut self, columns: &[String]) -> bool {
+        self.0.mutate(|fsm| match fsm {
+            FsmState::Buffering(mut b) => {
+                let ret = b.drop_columns(columns);
+                (FsmState::Buffering(b), ret)
+            }
+        })
+    }
+
     pub(crate) fn persist_cost_estimate(&self) -> usize {
         match self.0.get() {
             FsmState::Buffering(b) => b.persist_cost_estimate(),
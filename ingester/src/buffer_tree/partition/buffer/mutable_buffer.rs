@@ -1,5 +1,8 @@
-use arrow::record_batch::RecordBatch;
+use arrow::{compute::SlicesIterator, record_batch::RecordBatch};
+use data_types::DeletePredicate;
+use datafusion::error::DataFusionError;
 use mutable_batch::MutableBatch;
+use predicate::delete_predicate::delete_predicate_retain_mask;
 use schema::Projection;
 
 /// A [`Buffer`] is an internal mutable buffer wrapper over a [`MutableBatch`]
@@ -29,6 +32,59 @@ impl Buffer {
         Ok(())
     }
 
+    /// Remove all rows matched by `predicate` from this [`Buffer`], returning
+    /// the number of rows removed.
+    ///
+    /// If all rows are removed, this [`Buffer`] becomes empty.
+    pub(super) fn apply_delete(
+        &mut self,
+        predicate: &DeletePredicate,
+    ) -> Result<usize, DataFusionError> {
+        let buffer = match self.buffer.as_ref() {
+            Some(v) => v,
+            None => return Ok(0),
+        };
+
+        let batch = buffer
+            .to_arrow(Projection::All)
+            .expect("failed to snapshot buffer data");
+        let mask = match delete_predicate_retain_mask(predicate, &batch)? {
+            Some(v) => v,
+            None => return Ok(0),
+        };
+
+        let removed = buffer.rows() - mask.true_count();
+
+        // Copy the retained row ranges into a new buffer.
+        let ranges = SlicesIterator::new(&mask)
+            .map(|(start, end)| start..end)
+            .collect::<Vec<_>>();
+        if ranges.is_empty() {
+            self.buffer = None;
+            return Ok(removed);
+        }
+
+        let mut retained = MutableBatch::new();
+        retained
+            .extend_from_ranges(buffer, &ranges)
+            .expect("copying rows from a valid buffer should succeed");
+        self.buffer = Some(retained);
+
+        Ok(removed)
+    }
+
+    /// Drop the values of `columns` from this [`Buffer`], returning true if
+    /// any of them were buffered.
+    pub(super) fn drop_columns(&mut self, columns: &[String]) -> bool {
+        let Some(buffer) = self.buffer.as_mut() else {
+            return false;
+        };
+
+        columns.iter().fold(false, |dropped, c| {
+            buffer.remove_column(c).is_some() || dropped
+        })
+    }
+
     /// Generates a [`RecordBatch`] from the data in this [`Buffer`].
     ///
     /// If this [`Buffer`] is empty when this method is called, the call is a
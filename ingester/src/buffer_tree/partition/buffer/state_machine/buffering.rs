@@ -1,7 +1,10 @@
 //! A write buffer.
 
 use arrow::record_batch::RecordBatch;
-use data_types::{StatValues, TimestampMinMax};
+use data_types::{
+    sequence_number_set::SequenceNumberSet, DeletePredicate, StatValues, TimestampMinMax,
+};
+use datafusion::error::DataFusionError;
 use mutable_batch::{column::ColumnData, MutableBatch};
 use schema::{Projection, TIME_COLUMN_NAME};
 
@@ -98,6 +101,32 @@ impl BufferState<Buffering> {
     pub(crate) fn persist_cost_estimate(&self) -> usize {
         self.state.buffer.persist_cost_estimate()
     }
+
+    /// Remove all buffered rows matched by `predicate`, returning the number
+    /// of rows removed.
+    ///
+    /// If this removes all the buffered rows, the [`SequenceNumberSet`] of the
+    /// writes that were buffered is returned and this buffer is reset to an
+    /// empty state, as the writes will no longer be released by persisting
+    /// this buffer.
+    pub(crate) fn apply_delete(
+        &mut self,
+        predicate: &DeletePredicate,
+    ) -> Result<(usize, Option<SequenceNumberSet>), DataFusionError> {
+        let removed = self.state.buffer.apply_delete(predicate)?;
+        if removed > 0 && self.state.buffer.is_empty() {
+            return Ok((removed, Some(std::mem::take(&mut self.sequence_numbers))));
+        }
+        Ok((removed, None))
+    }
+
+    /// Drop the buffered values of `columns`, returning true if any of them
+    /// were buffered.
+    ///
+    /// The row count is unchanged, as the time column cannot be deleted.
+    pub(crate) fn drop_columns(&mut self, columns: &[String]) -> bool {
+        self.state.buffer.drop_columns(columns)
+    }
 }
 
 /// Perform an O(1) extraction of the timestamp column statistics.
@@ -1,8 +1,10 @@
 //! A writfield1 buffer, with one or more snapshots.
 
 use arrow::record_batch::RecordBatch;
-use data_types::{sequence_number_set::SequenceNumberSet, TimestampMinMax};
+use data_types::{sequence_number_set::SequenceNumberSet, DeletePredicate, TimestampMinMax};
+use datafusion::error::DataFusionError;
 use iox_query::util::compute_timenanosecond_min_max;
+use predicate::delete_predicate::filter_deleted_rows;
 use schema::{merge::merge_record_batch_schemas, Schema};
 
 use super::BufferState;
@@ -15,12 +17,16 @@ use crate::{
 pub(crate) struct Persisting {
     /// Snapshots generated from previous buffer contents to be persisted.
     ///
-    /// INVARIANT: this array is always non-empty.
+    /// INVARIANT: this array is always non-empty, unless all the rows within
+    /// it have been removed by a delete. No snapshot contains zero rows.
     snapshots: Vec<RecordBatch>,
 
     /// Statistics describing the data in snapshots.
     row_count: usize,
-    timestamp_stats: TimestampMinMax,
+    timestamp_stats: Option<TimestampMinMax>,
+
+    /// The schema of the snapshots as originally generated - this is not
+    /// narrowed when rows are removed by a delete, only when columns are.
     schema: Schema,
 }
 
@@ -46,10 +52,89 @@ impl Persisting {
         Self {
             snapshots,
             row_count,
-            timestamp_stats,
+            timestamp_stats: Some(timestamp_stats),
             schema,
         }
     }
+
+    /// Evaluate `predicate` against the snapshots, returning the snapshots
+    /// with the matching rows removed, or [`None`] if no rows match.
+    fn delete_rows(
+        &self,
+        predicate: &DeletePredicate,
+    ) -> Result<Option<Vec<RecordBatch>>, DataFusionError> {
+        let snapshots = self
+            .snapshots
+            .iter()
+            .map(|v| filter_deleted_rows(predicate, v))
+            .filter(|v| v.as_ref().map(|v| v.num_rows() > 0).unwrap_or(true))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let row_count = snapshots.iter().map(|v| v.num_rows()).sum::<usize>();
+        if row_count == self.row_count {
+            return Ok(None);
+        }
+
+        Ok(Some(snapshots))
+    }
+
+    /// Remove `columns` from the snapshots and schema, returning true if any
+    /// of them were present.
+    fn drop_columns(&mut self, columns: &[String]) -> bool {
+        let retain = self
+            .schema
+            .iter()
+            .map(|(_, f)| f.name().as_str())
+            .filter(|name| !columns.iter().any(|c| c == name))
+            .collect::<Vec<_>>();
+        if retain.len() == self.schema.len() {
+            return false;
+        }
+
+        self.snapshots = self
+            .snapshots
+            .iter()
+            .map(|batch| {
+                let indices = batch
+                    .schema()
+                    .fields()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, f)| !columns.iter().any(|c| c == f.name()))
+                    .map(|(i, _)| i)
+                    .collect::<Vec<_>>();
+                batch
+                    .project(&indices)
+                    .expect("projection indices are in bounds")
+            })
+            .collect();
+        self.schema = self
+            .schema
+            .select_by_names(&retain)
+            .expect("retained columns exist in schema");
+
+        true
+    }
+
+    /// Returns the memory size of the snapshots, in bytes.
+    fn size(&self) -> usize {
+        self.snapshots
+            .iter()
+            .map(|v| v.get_array_memory_size())
+            .sum()
+    }
+
+    /// Replace the snapshots with the output of [`Self::delete_rows()`].
+    fn replace_snapshots(&mut self, snapshots: Vec<RecordBatch>) {
+        debug_assert!(snapshots.iter().all(|v| v.num_rows() > 0));
+
+        self.timestamp_stats = (!snapshots.is_empty()).then(|| {
+            compute_timenanosecond_min_max(snapshots.iter())
+                .expect("non-empty snapshots must contain timestamps")
+        });
+        self.row_count = snapshots.iter().map(|v| v.num_rows()).sum();
+        self.snapshots = snapshots;
+    }
 }
 
 impl Queryable for Persisting {
@@ -62,7 +147,7 @@ impl Queryable for Persisting {
     }
 
     fn timestamp_stats(&self) -> Option<TimestampMinMax> {
-        Some(self.timestamp_stats)
+        self.timestamp_stats
     }
 
     fn schema(&self) -> Option<schema::Schema> {
@@ -76,4 +161,37 @@ impl BufferState<Persisting> {
     pub(crate) fn into_sequence_number_set(self) -> SequenceNumberSet {
         self.sequence_numbers
     }
+
+    /// Returns the memory size of the persisting data, in bytes.
+    pub(crate) fn size(&self) -> usize {
+        self.state.size()
+    }
+
+    /// Evaluate `predicate` against this persisting data, returning the data
+    /// with the matching rows removed, or [`None`] if no rows match.
+    ///
+    /// The returned data is applied with [`Self::replace_snapshots()`]. This
+    /// does not affect the data already handed to the persist task, and the
+    /// [`SequenceNumberSet`] of this buffer is unchanged.
+    pub(crate) fn delete_rows(
+        &self,
+        predicate: &DeletePredicate,
+    ) -> Result<Option<Vec<RecordBatch>>, DataFusionError> {
+        self.state.delete_rows(predicate)
+    }
+
+    /// Replace the data in this buffer with the output of
+    /// [`Self::delete_rows()`].
+    pub(crate) fn replace_snapshots(&mut self, snapshots: Vec<RecordBatch>) {
+        self.state.replace_snapshots(snapshots)
+    }
+
+    /// Remove the values of `columns` from this persisting data, returning
+    /// true if any of them were present.
+    ///
+    /// Like [`Self::replace_snapshots()`], this does not affect the data
+    /// already handed to the persist task.
+    pub(crate) fn drop_columns(&mut self, columns: &[String]) -> bool {
+        self.state.drop_columns(columns)
+    }
 }
@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use iox_time::Time;
+
 use crate::query_adaptor::QueryAdaptor;
 
 /// An opaque, monotonic generational identifier of a buffer in a
@@ -45,17 +47,32 @@ impl Display for BatchIdent {
 pub struct PersistingData {
     data: QueryAdaptor,
     batch_ident: BatchIdent,
+
+    /// The wall clock time at which the data was snapshot from the buffer.
+    snapshot_at: Time,
 }
 
 impl PersistingData {
-    pub(super) fn new(data: QueryAdaptor, batch_ident: BatchIdent) -> Self {
-        Self { data, batch_ident }
+    pub(super) fn new(data: QueryAdaptor, batch_ident: BatchIdent, snapshot_at: Time) -> Self {
+        Self {
+            data,
+            batch_ident,
+            snapshot_at,
+        }
     }
 
     pub(super) fn batch_ident(&self) -> BatchIdent {
         self.batch_ident
     }
 
+    /// The wall clock time at which this data was snapshot from the buffer.
+    ///
+    /// No write or delete applied to the buffer after this point is reflected
+    /// in this data.
+    pub(crate) fn snapshot_at(&self) -> Time {
+        self.snapshot_at
+    }
+
     pub(crate) fn query_adaptor(&self) -> QueryAdaptor {
         self.data.clone()
     }
@@ -1,7 +1,8 @@
 use std::collections::VecDeque;
 
 use arrow::record_batch::RecordBatch;
-use data_types::TimestampMinMax;
+use data_types::{DeletePredicate, TimestampMinMax};
+use datafusion::error::DataFusionError;
 use schema::{merge::SchemaMerger, Schema};
 
 use crate::query::projection::OwnedProjection;
@@ -65,9 +66,11 @@ impl PersistingList {
                 // with, so skip merging schemas.
                 self.cached = Some(CachedStats {
                     rows: buffer.rows(),
-                    timestamps: buffer
-                        .timestamp_stats()
-                        .expect("persisting batch must contain timestamps"),
+                    timestamps: Some(
+                        buffer
+                            .timestamp_stats()
+                            .expect("persisting batch must contain timestamps"),
+                    ),
                     schema: buffer.schema().expect("persisting batch must have schema"),
                 });
             }
@@ -112,6 +115,52 @@ impl PersistingList {
         fsm
     }
 
+    /// Remove all rows matched by `predicate` from the batches in this list,
+    /// returning the [`BatchIdent`] of each batch that had rows removed.
+    ///
+    /// The delete is applied to either all, or none of the batches.
+    pub(crate) fn apply_delete(
+        &mut self,
+        predicate: &DeletePredicate,
+    ) -> Result<Vec<BatchIdent>, DataFusionError> {
+        // Evaluate the predicate against all batches before modifying any.
+        let filtered = self
+            .persisting
+            .iter()
+            .map(|(_, b)| b.delete_rows(predicate))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut affected = Vec::new();
+        for ((ident, b), snapshots) in self.persisting.iter_mut().zip(filtered) {
+            if let Some(snapshots) = snapshots {
+                b.replace_snapshots(snapshots);
+                affected.push(*ident);
+            }
+        }
+
+        if !affected.is_empty() {
+            self.cached = CachedStats::new(self.persisting.iter().map(|(_, v)| v));
+        }
+
+        Ok(affected)
+    }
+
+    /// Remove the values of `columns` from the batches in this list,
+    /// returning the [`BatchIdent`] of each batch that contained any of them.
+    pub(crate) fn drop_columns(&mut self, columns: &[String]) -> Vec<BatchIdent> {
+        let affected = self
+            .persisting
+            .iter_mut()
+            .filter_map(|(ident, b)| b.drop_columns(columns).then_some(*ident))
+            .collect::<Vec<_>>();
+
+        if !affected.is_empty() {
+            self.cached = CachedStats::new(self.persisting.iter().map(|(_, v)| v));
+        }
+
+        affected
+    }
+
     pub(crate) fn is_empty(&self) -> bool {
         self.persisting.is_empty()
     }
@@ -123,11 +172,19 @@ impl PersistingList {
         self.cached.as_ref().map(|v| v.rows).unwrap_or_default()
     }
 
+    /// Returns the memory size of all batches in this list, in bytes.
+    ///
+    /// This is an `O(n)` operation where `n` is the number of columns across
+    /// all batches in this list.
+    pub(crate) fn size(&self) -> usize {
+        self.persisting.iter().map(|(_, v)| v.size()).sum()
+    }
+
     /// Returns the timestamp min/max values across all batches in this list.
     ///
     /// This is an `O(1)` operation.
     pub(crate) fn timestamp_stats(&self) -> Option<TimestampMinMax> {
-        self.cached.as_ref().map(|v| v.timestamps)
+        self.cached.as_ref().and_then(|v| v.timestamps)
     }
 
     /// Returns the merged schema of all batches in this list.
@@ -156,7 +213,10 @@ impl PersistingList {
 #[derive(Debug)]
 struct CachedStats {
     rows: usize,
-    timestamps: TimestampMinMax,
+
+    /// The timestamp min/max of all batches, or [`None`] if all rows have
+    /// been removed by deletes.
+    timestamps: Option<TimestampMinMax>,
 
     /// The merged schema of all the persisting batches.
     schema: Schema,
@@ -167,8 +227,8 @@ impl CachedStats {
     ///
     /// # Panics
     ///
-    /// If any batches are empty (containing no schema or timestamp column), or
-    /// the batches do not contain compatible schemas, this call panics.
+    /// If any batches contain no schema, or the batches do not contain
+    /// compatible schemas, this call panics.
     fn new<'a, T>(mut iter: T) -> Option<Self>
     where
         T: Iterator<Item = &'a BufferState<Persisting>> + 'a,
@@ -181,27 +241,16 @@ impl CachedStats {
             .unwrap();
 
         let mut rows = v.rows();
-        debug_assert!(rows > 0);
-
-        let mut timestamps = v
-            .timestamp_stats()
-            .expect("unprojected batch should have timestamp");
+        let mut timestamps = v.timestamp_stats();
 
         for buf in iter {
             rows += buf.rows();
             if let Some(v) = buf.schema() {
-                debug_assert!(buf.rows() > 0);
-
                 schema = schema
                     .merge(&v)
                     .expect("persit list contains incompatible schemas");
 
-                let ts = buf
-                    .timestamp_stats()
-                    .expect("no timestamp for bach containing rows");
-
-                timestamps.min = timestamps.min.min(ts.min);
-                timestamps.max = timestamps.max.max(ts.max);
+                timestamps = merge_timestamps(timestamps, buf.timestamp_stats());
             }
         }
 
@@ -224,8 +273,7 @@ impl CachedStats {
             .timestamp_stats()
             .expect("persisting batch must contain timestamps");
 
-        self.timestamps.min = self.timestamps.min.min(ts.min);
-        self.timestamps.max = self.timestamps.max.max(ts.max);
+        self.timestamps = merge_timestamps(self.timestamps, Some(ts));
 
         let mut schema = SchemaMerger::new();
         schema = schema.merge(&self.schema).unwrap();
@@ -236,6 +284,19 @@ impl CachedStats {
     }
 }
 
+fn merge_timestamps(
+    a: Option<TimestampMinMax>,
+    b: Option<TimestampMinMax>,
+) -> Option<TimestampMinMax> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(TimestampMinMax {
+            min: a.min.min(b.min),
+            max: a.max.max(b.max),
+        }),
+        (a, b) => a.or(b),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeSet;
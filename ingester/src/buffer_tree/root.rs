@@ -190,6 +190,16 @@ where
 
     async fn apply(&self, op: IngestOp) -> Result<(), Self::Error> {
         let namespace_id = op.namespace();
+
+        // A delete only affects buffered data, and never initialises a
+        // namespace.
+        if let IngestOp::Delete(_) = op {
+            return match self.namespaces.get(&namespace_id) {
+                Some(v) => v.apply(op).await,
+                None => Ok(()),
+            };
+        }
+
         let namespace_data = self.namespaces.get_or_insert_with(&namespace_id, || {
             // Increase the metric that records the number of namespaces
             // buffered in this ingester instance.
@@ -32,6 +32,7 @@ use super::{
 use crate::{
     arcmap::ArcMap,
     deferred_load::DeferredLoad,
+    dml_payload::delete::DeleteOperation,
     query::{
         partition_response::PartitionResponse, projection::OwnedProjection,
         response::PartitionStream, QueryError, QueryExec,
@@ -182,6 +183,21 @@ where
 
         Ok(())
     }
+
+    /// Apply the delete `op` to all partitions of this table.
+    pub(super) fn apply_delete(&self, op: &DeleteOperation) {
+        debug_assert_eq!(op.table(), self.table_id);
+
+        for p in self.partitions() {
+            let mut p = p.lock();
+            if let Some(predicate) = op.predicate() {
+                p.apply_delete(predicate, op.reference());
+            }
+            if !op.columns().is_empty() {
+                p.drop_columns(op.columns(), op.reference());
+            }
+        }
+    }
 }
 
 #[async_trait]
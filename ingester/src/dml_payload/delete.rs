@@ -0,0 +1,182 @@
+use std::sync::Arc;
+
+use data_types::{
+    sequence_number_set::SequenceNumberSet, DeletePredicate, NamespaceId, SequenceNumber, TableId,
+};
+use parking_lot::Mutex;
+use trace::ctx::SpanContext;
+
+/// A delete of data in a single table, represented by an
+/// [`crate::dml_payload::IngestOp::Delete`].
+///
+/// A delete removes the rows matched by its predicate (if any), and drops the
+/// values of the columns deleted from the table (if any).
+#[derive(Debug, Clone)]
+pub struct DeleteOperation {
+    namespace: NamespaceId,
+    table: TableId,
+    predicate: Option<Arc<DeletePredicate>>,
+    columns: Arc<[String]>,
+
+    /// The reference counted sequence number of this delete, shared by all
+    /// the buffers it was applied to.
+    reference: Arc<DeleteReference>,
+
+    span_context: Option<SpanContext>,
+}
+
+impl DeleteOperation {
+    /// Construct a new [`DeleteOperation`] from the provided details.
+    pub fn new(
+        namespace: NamespaceId,
+        table: TableId,
+        predicate: Option<DeletePredicate>,
+        columns: Vec<String>,
+        sequence_number: SequenceNumber,
+        span_context: Option<SpanContext>,
+    ) -> Self {
+        Self {
+            namespace,
+            table,
+            predicate: predicate.map(Arc::new),
+            columns: columns.into(),
+            reference: Arc::new(DeleteReference::new(sequence_number)),
+            span_context,
+        }
+    }
+
+    /// The namespace which the delete is for.
+    pub fn namespace(&self) -> NamespaceId {
+        self.namespace
+    }
+
+    /// The table which the delete is for.
+    pub fn table(&self) -> TableId {
+        self.table
+    }
+
+    /// The predicate identifying the rows to delete, if any.
+    pub fn predicate(&self) -> Option<&Arc<DeletePredicate>> {
+        self.predicate.as_ref()
+    }
+
+    /// The names of the columns deleted from the table.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// The sequence number assigned to this delete.
+    pub fn sequence_number(&self) -> SequenceNumber {
+        self.reference.sequence_number
+    }
+
+    /// The [`DeleteReference`] tracking the buffers this delete was applied
+    /// to.
+    pub(crate) fn reference(&self) -> &Arc<DeleteReference> {
+        &self.reference
+    }
+
+    /// An optional tracing context associated with the [`DeleteOperation`]
+    pub fn span_context(&self) -> Option<&SpanContext> {
+        self.span_context.as_ref()
+    }
+}
+
+/// A reference count over the [`SequenceNumber`] of a delete.
+///
+/// A delete MUST remain in the WAL for as long as any of the buffered data it
+/// was applied to remains unpersisted, otherwise a WAL replay would resurrect
+/// the deleted rows. Because a delete may affect many partitions, each
+/// partition that had rows removed by the delete holds a reference, and the
+/// delete's sequence number is released only once all references have been
+/// released.
+///
+/// The applier of the delete holds the initial reference, releasing it once
+/// the delete has been applied to all partitions.
+///
+/// Partitions that had ALL their buffered rows removed by a delete can no
+/// longer release the sequence numbers of those writes through persistence -
+/// these sequence numbers are added to the reference with
+/// [`DeleteReference::adopt()`] and released alongside the delete.
+#[derive(Debug)]
+pub(crate) struct DeleteReference {
+    sequence_number: SequenceNumber,
+    state: Mutex<RefState>,
+}
+
+#[derive(Debug)]
+struct RefState {
+    refs: usize,
+    sequence_numbers: SequenceNumberSet,
+}
+
+impl DeleteReference {
+    fn new(sequence_number: SequenceNumber) -> Self {
+        Self {
+            sequence_number,
+            state: Mutex::new(RefState {
+                refs: 1,
+                sequence_numbers: [sequence_number].into_iter().collect(),
+            }),
+        }
+    }
+
+    /// Acquire an additional reference.
+    ///
+    /// # Panics
+    ///
+    /// Panics if all references have already been released.
+    pub(crate) fn acquire(self: &Arc<Self>) -> Arc<Self> {
+        let mut state = self.state.lock();
+        assert_ne!(state.refs, 0, "acquire of released delete reference");
+        state.refs += 1;
+        Arc::clone(self)
+    }
+
+    /// Add `set` to the sequence numbers released by this reference.
+    pub(crate) fn adopt(&self, set: SequenceNumberSet) {
+        let mut state = self.state.lock();
+        assert_ne!(state.refs, 0, "adopt by released delete reference");
+        state.sequence_numbers.add_set(&set);
+    }
+
+    /// Release a reference, returning the [`SequenceNumberSet`] to be
+    /// released if this was the last reference.
+    pub(crate) fn release(&self) -> Option<SequenceNumberSet> {
+        let mut state = self.state.lock();
+        state.refs = state
+            .refs
+            .checked_sub(1)
+            .expect("release of released delete reference");
+
+        if state.refs > 0 {
+            return None;
+        }
+
+        Some(std::mem::take(&mut state.sequence_numbers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delete_reference() {
+        let r = Arc::new(DeleteReference::new(SequenceNumber::new(42)));
+
+        let a = r.acquire();
+        let b = r.acquire();
+
+        a.adopt([SequenceNumber::new(1)].into_iter().collect());
+
+        assert!(r.release().is_none());
+        assert!(a.release().is_none());
+
+        let got = b.release().expect("last reference must release");
+        assert_eq!(
+            got.iter().collect::<Vec<_>>(),
+            [SequenceNumber::new(1), SequenceNumber::new(42)]
+        );
+    }
+}
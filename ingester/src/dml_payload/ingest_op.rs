@@ -1,7 +1,7 @@
 use data_types::{sequence_number_set::SequenceNumberSet, NamespaceId};
 use trace::ctx::SpanContext;
 
-use super::write::WriteOperation;
+use super::{delete::DeleteOperation, write::WriteOperation};
 
 /// The set of operations which the ingester can derive and process from wire
 /// requests
@@ -9,6 +9,8 @@ use super::write::WriteOperation;
 pub enum IngestOp {
     /// A write for ingest
     Write(WriteOperation),
+    /// A predicate delete of buffered data
+    Delete(DeleteOperation),
 }
 
 impl IngestOp {
@@ -16,6 +18,7 @@ impl IngestOp {
     pub fn namespace(&self) -> NamespaceId {
         match self {
             Self::Write(w) => w.namespace(),
+            Self::Delete(d) => d.namespace(),
         }
     }
 
@@ -23,6 +26,7 @@ impl IngestOp {
     pub fn span_context(&self) -> Option<&SpanContext> {
         match self {
             Self::Write(w) => w.span_context(),
+            Self::Delete(d) => d.span_context(),
         }
     }
 
@@ -34,6 +38,7 @@ impl IngestOp {
                 .tables()
                 .map(|(_, t)| t.partitioned_data().sequence_number())
                 .collect(),
+            Self::Delete(d) => [d.sequence_number()].into_iter().collect(),
         }
     }
 }
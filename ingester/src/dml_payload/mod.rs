@@ -4,5 +4,6 @@
 mod ingest_op;
 pub use ingest_op::*;
 
+pub mod delete;
 pub mod encode;
 pub mod write;
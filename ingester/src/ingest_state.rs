@@ -28,6 +28,11 @@ pub enum IngestStateError {
     /// writes.
     #[error("ingester disk full - persisting write-ahead log")]
     DiskFull = 1 << 2,
+
+    /// Indicates the data buffered in memory exceeds the configured hard
+    /// limit, and further writes must wait for buffered data to be persisted.
+    #[error("ingester buffer memory exhausted - persisting buffered data")]
+    MemoryExhausted = 1 << 3,
 }
 
 impl IngestStateError {
@@ -142,7 +147,8 @@ impl IngestState {
     ///
     ///   1. [`IngestStateError::GracefulStop`]
     ///   2. [`IngestStateError::DiskFull`]
-    ///   3. [`IngestStateError::PersistSaturated`].
+    ///   3. [`IngestStateError::MemoryExhausted`]
+    ///   4. [`IngestStateError::PersistSaturated`].
     ///
     pub(crate) fn read(&self) -> Result<(), IngestStateError> {
         let current = self.state.load(Ordering::Relaxed);
@@ -186,7 +192,7 @@ impl IngestState {
 ///
 /// Shutdown always takes precedence, ensuring that once set, this is the error
 /// the user always sees (instead of potentially flip-flopping between "shutting
-/// down", "persist saturated" & "disk full").
+/// down", "persist saturated", "memory exhausted" & "disk full").
 #[cold]
 fn as_err(state: usize) -> Result<(), IngestStateError> {
     if state & IngestStateError::GracefulStop.as_bits() != 0 {
@@ -197,6 +203,10 @@ fn as_err(state: usize) -> Result<(), IngestStateError> {
         return Err(IngestStateError::DiskFull);
     }
 
+    if state & IngestStateError::MemoryExhausted.as_bits() != 0 {
+        return Err(IngestStateError::MemoryExhausted);
+    }
+
     if state & IngestStateError::PersistSaturated.as_bits() != 0 {
         return Err(IngestStateError::PersistSaturated);
     }
@@ -229,6 +239,13 @@ mod tests {
             Err(IngestStateError::PersistSaturated)
         );
 
+        state.set(IngestStateError::MemoryExhausted);
+        assert_matches!(state.read(), Err(IngestStateError::MemoryExhausted));
+        assert_matches!(
+            state.read_with_exceptions([]),
+            Err(IngestStateError::MemoryExhausted)
+        );
+
         state.set(IngestStateError::DiskFull);
         assert_matches!(state.read(), Err(IngestStateError::DiskFull));
         assert_matches!(
@@ -279,8 +296,17 @@ mod tests {
             Err(IngestStateError::DiskFull)
         );
 
-        // Un-setting the disk full state then shows the persist saturated state.
+        // Un-setting the disk full state then shows the memory exhausted state.
         state.unset(IngestStateError::DiskFull);
+        assert_matches!(state.read(), Err(IngestStateError::MemoryExhausted));
+        assert_matches!(
+            state.read_with_exceptions([]),
+            Err(IngestStateError::MemoryExhausted)
+        );
+
+        // And un-setting the memory exhausted state shows the persist saturated
+        // state.
+        state.unset(IngestStateError::MemoryExhausted);
         assert_matches!(state.read(), Err(IngestStateError::PersistSaturated));
         assert_matches!(
             state.read_with_exceptions([]),
@@ -294,7 +320,8 @@ mod tests {
         prop_oneof![
             Just(IngestStateError::PersistSaturated),
             Just(IngestStateError::GracefulStop),
-            Just(IngestStateError::DiskFull)
+            Just(IngestStateError::DiskFull),
+            Just(IngestStateError::MemoryExhausted)
         ]
     }
 
@@ -303,6 +330,7 @@ mod tests {
             IngestStateError::PersistSaturated,
             IngestStateError::GracefulStop,
             IngestStateError::DiskFull,
+            IngestStateError::MemoryExhausted,
         ]
         .into_iter()
         .filter(|v| !not.iter().any(|w| discriminant(v) == discriminant(w)))
@@ -318,6 +346,7 @@ mod tests {
                 IngestStateError::PersistSaturated => {}
                 IngestStateError::GracefulStop => {}
                 IngestStateError::DiskFull => {}
+                IngestStateError::MemoryExhausted => {}
             }
         }
 
@@ -10,6 +10,8 @@ mod graceful_shutdown;
 #[cfg(not(feature = "benches"))]
 mod wal_replay;
 
+pub use wal_replay::WalReplayMode;
+
 use std::{net::SocketAddr, num::NonZeroUsize, path::PathBuf, sync::Arc, time::Duration};
 
 use arrow_flight::flight_service_server::FlightService;
@@ -17,11 +19,13 @@ use backoff::BackoffConfig;
 use futures::{future::Shared, Future, FutureExt};
 use generated_types::influxdata::iox::{
     catalog::v1::catalog_service_server::CatalogService,
+    delete::v1::delete_service_server::DeleteService,
     gossip::Topic,
     ingester::v1::{persist_service_server::PersistService, write_service_server::WriteService},
 };
 use iox_catalog::interface::Catalog;
 use iox_query::exec::Executor;
+use object_store::path::Path as ObjectStorePath;
 use observability_deps::tracing::*;
 use parquet_file::storage::ParquetStorage;
 use thiserror::Error;
@@ -47,6 +51,7 @@ use crate::{
     persist::{
         completion_observer::MaybeLayer, file_metrics::ParquetFileInstrumentation,
         handle::PersistHandle, hot_partitions::HotPartitionPersister,
+        memory_protection::BufferMemoryGuard,
     },
     query::{
         exec_instrumentation::QueryExecInstrumentation,
@@ -55,6 +60,7 @@ use crate::{
     server::grpc::GrpcDelegate,
     timestamp_oracle::TimestampOracle,
     wal::{
+        archiver::{WalArchiveRetention, WalArchiver},
         disk_full_protection::{self, guard_disk_capacity},
         reference_tracker::WalReferenceHandle,
         rotate_task::periodic_rotation,
@@ -64,6 +70,14 @@ use crate::{
 
 use self::graceful_shutdown::graceful_shutdown_handler;
 
+/// The default interval at which the memory used by buffered data is evaluated
+/// against the configured [`BufferMemoryLimit`].
+const DEFAULT_BUFFER_MEMORY_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The interval at which archived WAL segments older than the configured
+/// [`WalArchiveConfig::retention()`] are deleted.
+const WAL_ARCHIVE_RETENTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
 /// Acquire opaque handles to the Ingester RPC service implementations.
 ///
 /// This trait serves as the public crate API boundary - callers external to the
@@ -78,6 +92,8 @@ pub trait IngesterRpcInterface: Send + Sync + std::fmt::Debug {
     type CatalogHandler: CatalogService;
     /// The type of the [`WriteService`] implementation.
     type WriteHandler: WriteService;
+    /// The type of the [`DeleteService`] implementation.
+    type DeleteHandler: DeleteService;
     /// The type of the [`PersistService`] implementation.
     type PersistHandler: PersistService;
     /// The type of the [`FlightService`] implementation.
@@ -91,6 +107,10 @@ pub trait IngesterRpcInterface: Send + Sync + std::fmt::Debug {
     /// handler implementation.
     fn write_service(&self) -> Self::WriteHandler;
 
+    /// Acquire an opaque handle to the Ingester's [`DeleteService`] RPC
+    /// handler implementation.
+    fn delete_service(&self) -> Self::DeleteHandler;
+
     /// Acquire an opaque handle to the Ingester's [`PersistService`] RPC
     /// handler implementation.
     fn persist_service(&self) -> Self::PersistHandler;
@@ -117,6 +137,18 @@ pub struct IngesterGuard<T> {
     /// Aborted on drop.
     disk_metric_task: tokio::task::JoinHandle<()>,
 
+    /// The handle of the buffer memory protection task, if a
+    /// [`BufferMemoryLimit`] is configured.
+    ///
+    /// Aborted on drop.
+    memory_protection_task: Option<tokio::task::JoinHandle<()>>,
+
+    /// The handle of the task deleting expired archived WAL segments, if a
+    /// [`WalArchiveConfig`] is configured.
+    ///
+    /// Aborted on drop.
+    wal_archive_retention_task: Option<tokio::task::JoinHandle<()>>,
+
     /// The task handle executing the graceful shutdown once triggered.
     graceful_shutdown_handler: tokio::task::JoinHandle<()>,
     shutdown_complete: Shared<oneshot::Receiver<()>>,
@@ -144,6 +176,12 @@ impl<T> Drop for IngesterGuard<T> {
     fn drop(&mut self) {
         self.rotation_task.abort();
         self.disk_metric_task.abort();
+        if let Some(task) = &self.memory_protection_task {
+            task.abort();
+        }
+        if let Some(task) = &self.wal_archive_retention_task {
+            task.abort();
+        }
         self.graceful_shutdown_handler.abort();
     }
 }
@@ -169,6 +207,93 @@ pub enum GossipConfig {
     },
 }
 
+/// Limits on the amount of memory used by data buffered in the ingester,
+/// including data that is currently being persisted.
+///
+/// Once buffered data exceeds the soft limit, the partitions holding the most
+/// buffered data are persisted early. While buffered data exceeds the hard
+/// limit, writes are rejected until enough data has been persisted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferMemoryLimit {
+    soft_limit_bytes: usize,
+    hard_limit_bytes: usize,
+    check_interval: Duration,
+}
+
+impl BufferMemoryLimit {
+    /// Construct a new [`BufferMemoryLimit`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hard_limit_bytes` is less than `soft_limit_bytes`.
+    pub fn new(soft_limit_bytes: usize, hard_limit_bytes: usize) -> Self {
+        assert!(
+            hard_limit_bytes >= soft_limit_bytes,
+            "buffer memory hard limit must not be less than the soft limit"
+        );
+
+        Self {
+            soft_limit_bytes,
+            hard_limit_bytes,
+            check_interval: DEFAULT_BUFFER_MEMORY_CHECK_INTERVAL,
+        }
+    }
+
+    /// Evaluate the memory used by buffered data against the limits every
+    /// `check_interval`, instead of every second.
+    pub fn with_check_interval(self, check_interval: Duration) -> Self {
+        Self {
+            check_interval,
+            ..self
+        }
+    }
+
+    /// The number of buffered bytes above which partitions are persisted
+    /// early.
+    pub fn soft_limit_bytes(&self) -> usize {
+        self.soft_limit_bytes
+    }
+
+    /// The number of buffered bytes above which writes are rejected.
+    pub fn hard_limit_bytes(&self) -> usize {
+        self.hard_limit_bytes
+    }
+
+    /// The interval at which the memory used by buffered data is evaluated.
+    pub fn check_interval(&self) -> Duration {
+        self.check_interval
+    }
+}
+
+/// Configuration of the archival of fully persisted WAL segments to object
+/// storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalArchiveConfig {
+    prefix: String,
+    retention: Duration,
+}
+
+impl WalArchiveConfig {
+    /// Construct a new [`WalArchiveConfig`], archiving segments under `prefix`
+    /// in the object store and deleting them once `retention` has elapsed.
+    pub fn new(prefix: impl Into<String>, retention: Duration) -> Self {
+        Self {
+            prefix: prefix.into(),
+            retention,
+        }
+    }
+
+    /// The object store path prefix segments are archived under.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// The duration archived segments are retained for.
+    pub fn retention(&self) -> Duration {
+        self.retention
+    }
+}
+
 /// Errors that occur during initialisation of an `ingester` instance.
 #[derive(Debug, Error)]
 pub enum InitError {
@@ -207,7 +332,11 @@ pub enum InitError {
 ///
 /// These files are read and replayed fully before this function returns.
 ///
-/// Any error during replay is fatal.
+/// With [`WalReplayMode::Strict`], any error during replay is fatal. With
+/// [`WalReplayMode::Recover`], entries that cannot be read are discarded and
+/// the sequence number ranges lost are logged once replay completes, while
+/// damaged segment files are moved into the `quarantine` directory within
+/// `wal_directory` rather than deleted.
 ///
 /// ## Graceful Shutdown
 ///
@@ -265,6 +394,25 @@ pub enum InitError {
 /// Decreasing this value increases the frequency of persist operations, and
 /// usually decreases the size of the resulting parquet files.
 ///
+/// ## WAL Archival
+///
+/// If a [`WalArchiveConfig`] is provided, each WAL segment is uploaded to the
+/// object store under `<prefix>/<ingester ID>/` once all the writes it contains
+/// have been persisted, before it is deleted from `wal_directory`. Archived
+/// segments closed longer ago than the configured retention period are
+/// periodically deleted.
+///
+/// ## Buffer Memory Limit
+///
+/// If a [`BufferMemoryLimit`] is provided, the memory used by all buffered and
+/// persisting data is periodically evaluated. Once it exceeds the soft limit,
+/// the partitions holding the most buffered data are enqueued for persistence
+/// until enough memory will be released to fall below the soft limit again.
+///
+/// While the memory used exceeds the hard limit, writes are rejected with a
+/// retryable error until persistence releases enough memory. This also pauses
+/// WAL replay.
+///
 /// [`MutableBatch::size_data()`]: mutable_batch::MutableBatch::size_data
 #[allow(clippy::too_many_arguments)]
 pub async fn new<F>(
@@ -273,10 +421,13 @@ pub async fn new<F>(
     persist_background_fetch_time: Duration,
     wal_directory: PathBuf,
     wal_rotation_period: Duration,
+    wal_replay_mode: WalReplayMode,
+    wal_archive: Option<WalArchiveConfig>,
     persist_executor: Arc<Executor>,
     persist_workers: usize,
     persist_queue_depth: usize,
     persist_hot_partition_cost: usize,
+    buffer_memory_limit: Option<BufferMemoryLimit>,
     object_store: ParquetStorage,
     gossip: GossipConfig,
     max_partitions_per_namespace: NonZeroUsize,
@@ -363,9 +514,30 @@ where
     // Start defining the chain of persist completion observers so it can be
     // layered in gossip handlers if needed.
     //
-    // Prepare the WAL segment reference tracker
+    // Prepare the WAL segment reference tracker, archiving segments to object
+    // storage before they are deleted if configured.
+    let wal_archive_retention_task = wal_archive.as_ref().map(|config| {
+        tokio::spawn(
+            WalArchiveRetention::new(
+                Arc::clone(object_store.object_store()),
+                ObjectStorePath::from(config.prefix()),
+                config.retention(),
+                &metrics,
+            )
+            .run(WAL_ARCHIVE_RETENTION_INTERVAL),
+        )
+    });
+    let wal_file_deleter = match &wal_archive {
+        Some(config) => MaybeLayer::With(WalArchiver::new(
+            Arc::clone(&wal),
+            Arc::clone(object_store.object_store()),
+            ObjectStorePath::from(config.prefix()).child(ingester_id.to_string()),
+            &metrics,
+        )),
+        None => MaybeLayer::Without(Arc::clone(&wal)),
+    };
     let (wal_reference_handle, wal_reference_actor) =
-        WalReferenceHandle::new(Arc::clone(&wal), &metrics);
+        WalReferenceHandle::new(wal_file_deleter.clone(), &metrics);
     // Add file metric instrumentation.
     let persist_observer = ParquetFileInstrumentation::new(wal_reference_handle.clone(), &metrics);
 
@@ -462,12 +634,32 @@ where
         ),
     ));
 
-    // Replay the WAL log files, if any.
+    // Spawn the buffer memory protection task, if configured.
+    //
+    // This is started before the WAL is replayed, so that replaying a large
+    // WAL cannot exhaust the memory of the ingester either.
+    let memory_protection_task = buffer_memory_limit.map(|limit| {
+        tokio::spawn(
+            BufferMemoryGuard::new(
+                Arc::clone(&buffer),
+                Arc::clone(&persist_handle),
+                Arc::clone(&ingest_state),
+                limit,
+                &metrics,
+            )
+            .run(limit.check_interval()),
+        )
+    });
+
+    // Replay the WAL log files, if any, archiving the replayed segments in
+    // the same way as those persisted at runtime.
     let max_sequence_number = wal_replay::replay(
         &wal,
+        &wal_file_deleter,
         &buffer,
         Arc::clone(&persist_handle),
         Arc::clone(&ingest_state),
+        wal_replay_mode,
         &metrics,
     )
     .await
@@ -546,6 +738,8 @@ where
         ),
         rotation_task,
         disk_metric_task,
+        memory_protection_task,
+        wal_archive_retention_task,
         graceful_shutdown_handler: shutdown_task,
         shutdown_complete: shutdown_rx.shared(),
     })
@@ -1,9 +1,12 @@
+mod recovery;
+
 use std::fmt::Debug;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
-use data_types::{NamespaceId, PartitionKey, SequenceNumber, TableId};
+use data_types::{DeletePredicate, NamespaceId, PartitionKey, SequenceNumber, TableId};
 use generated_types::influxdata::iox::wal::v1::sequenced_wal_op::Op;
 use metric::U64Counter;
 use mutable_batch_pb::decode::decode_database_batch;
@@ -12,18 +15,40 @@ use thiserror::Error;
 use wal::{SegmentId, SequencedWalOp};
 
 use crate::{
+    dml_payload::delete::DeleteOperation,
     dml_payload::write::{PartitionedData, TableData, WriteOperation},
     dml_payload::IngestOp,
     dml_sink::{DmlError, DmlSink},
     ingest_state::{IngestState, IngestStateError},
     partition_iter::PartitionIter,
     persist::{drain_buffer::persist_partitions, queue::PersistQueue},
+    wal::reference_tracker::WalFileDeleter,
 };
 
+use self::recovery::{LossReason, RecoveryLog};
+
 /// This duration controls how long to wait between reads of the ingest state
 /// when WAL op replay is blocked on an unhealthy ingest state.
 const OP_REPLAY_BACKPRESSURE_WAIT_DURATION: Duration = Duration::from_millis(500);
 
+/// Controls how WAL replay reacts to damaged segment files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WalReplayMode {
+    /// Abort replay when a WAL entry cannot be read, with the exception of a
+    /// truncated write at the tail of the most recent segment.
+    #[default]
+    Strict,
+
+    /// Salvage as much data as possible from damaged segments.
+    ///
+    /// Truncated tails are discarded, corrupt entries are skipped and
+    /// segments that cannot be read are passed over, recording the sequence
+    /// number ranges lost. Segments that lost acknowledged data are moved
+    /// into the WAL quarantine directory instead of being deleted once
+    /// replayed.
+    Recover,
+}
+
 /// Errors returned when replaying the write-ahead log.
 #[derive(Debug, Error)]
 pub enum WalReplayError {
@@ -40,6 +65,10 @@ pub enum WalReplayError {
     #[error("failed converting wal entry to ingest operation: {0}")]
     MapToDml(#[from] mutable_batch_pb::decode::Error),
 
+    /// An error converting a WAL delete entry into a [`IngestOp`].
+    #[error("failed converting wal delete entry to ingest operation: {0}")]
+    MapDelete(#[from] data_types::DeletePredicateProtoError),
+
     /// A failure to apply a [`IngestOp`] from the WAL to the in-memory
     /// [`BufferTree`].
     ///
@@ -48,8 +77,8 @@ pub enum WalReplayError {
     Apply(#[from] DmlError),
 }
 
-/// A type that can list, read & delete closed WAL segment files. This abstracts
-/// away the type of segment reader to allow mocking.
+/// A type that can list, read & quarantine closed WAL segment files. This
+/// abstracts away the type of segment reader to allow mocking.
 #[async_trait]
 pub trait WalReader: Debug + Send + Sync + 'static {
     /// A reader for a closed WAL segment.
@@ -61,8 +90,9 @@ pub trait WalReader: Debug + Send + Sync + 'static {
     /// Lists the closed segments available for reading from the WAL as (id, size) tuples.
     fn closed_segments(&self) -> Vec<(SegmentId, u64)>;
 
-    /// Deletes the closed segment specified.
-    async fn delete(&self, id: SegmentId) -> Result<(), wal::Error>;
+    /// Moves the closed segment specified out of the WAL, retaining it for
+    /// inspection, and returns its new path.
+    async fn quarantine(&self, id: SegmentId) -> Result<PathBuf, wal::Error>;
 }
 
 #[async_trait]
@@ -80,8 +110,8 @@ impl WalReader for Arc<wal::Wal> {
             .collect()
     }
 
-    async fn delete(&self, id: SegmentId) -> Result<(), wal::Error> {
-        wal::Wal::delete(self, id).await
+    async fn quarantine(&self, id: SegmentId) -> Result<PathBuf, wal::Error> {
+        wal::Wal::quarantine(self, id).await
     }
 }
 
@@ -100,21 +130,26 @@ impl SegmentedWalOpBatchReader for wal::ClosedSegmentFileReader {
     }
 }
 
-// TODO: tolerate WAL replay errors
-//
-// https://github.com/influxdata/influxdb_iox/issues/6283
-
 /// Replay all the entries in `wal` to `sink`, returning the maximum observed
 /// [`SequenceNumber`].
-pub async fn replay<W, T, P>(
+///
+/// Replayed segments are deleted through `deleter`, so that they are
+/// archived in the same way as segments deleted once persisted at runtime.
+///
+/// The handling of damaged segment files is controlled by `mode`, see
+/// [`WalReplayMode`].
+pub async fn replay<W, D, T, P>(
     wal: &W,
+    deleter: &D,
     sink: &T,
     persist: P,
     ingest_state: Arc<IngestState>,
+    mode: WalReplayMode,
     metrics: &metric::Registry,
 ) -> Result<Option<SequenceNumber>, WalReplayError>
 where
     W: WalReader,
+    D: WalFileDeleter,
     T: DmlSink + PartitionIter,
     P: PersistQueue + Clone,
 {
@@ -157,8 +192,14 @@ where
     let ok_op_count_metric = op_count_metric.recorder(&[("outcome", "success")]);
     let empty_op_count_metric = op_count_metric.recorder(&[("outcome", "skipped_empty")]);
 
+    // Track the data discarded from damaged segments, if recovering them.
+    let mut recovery = match mode {
+        WalReplayMode::Strict => None,
+        WalReplayMode::Recover => Some(RecoveryLog::new(metrics)),
+    };
+
     let n_files = files.len();
-    info!(n_files, "found wal files for replay");
+    info!(n_files, ?mode, "found wal files for replay");
 
     // Replay each file, keeping track of the last observed sequence number.
     //
@@ -173,9 +214,29 @@ where
         file_count_metric.inc(1);
 
         // Read the segment
-        let reader = wal
-            .reader_for_closed_segment(file_id)
-            .map_err(WalReplayError::OpenSegment)?;
+        let reader = match (wal.reader_for_closed_segment(file_id), recovery.as_mut()) {
+            (Ok(reader), _) => reader,
+            (Err(error), Some(recovery)) => {
+                error!(
+                    file_number,
+                    n_files,
+                    %file_id,
+                    size = file_size,
+                    %error,
+                    "unable to open wal segment, skipping replay of file"
+                );
+                replayed_file_count_metric
+                    .recorder(&[
+                        ("result", "error"),
+                        ("reason", LossReason::Unreadable.as_str()),
+                    ])
+                    .inc(1);
+                recovery.record(file_id, LossReason::Unreadable);
+                quarantine_segment(wal, file_id, recovery).await;
+                continue;
+            }
+            (Err(e), None) => return Err(WalReplayError::OpenSegment(e)),
+        };
 
         // Emit a log entry so progress can be tracked (and a problematic file
         // be identified should an explosion happen during replay).
@@ -194,15 +255,26 @@ where
             &ok_op_count_metric,
             &empty_op_count_metric,
             &ingest_state,
+            recovery.as_mut(),
         )
         .await;
         if replay_result.is_ok() {
-            file_count_success_metric.inc(1);
+            match recovery.as_ref().and_then(|r| r.first_loss(file_id)) {
+                None => file_count_success_metric.inc(1),
+                Some(reason) => replayed_file_count_metric
+                    .recorder(&[("result", "error"), ("reason", reason.as_str())])
+                    .inc(1),
+            }
         }
 
+        // Segments that lost acknowledged data are retained for inspection
+        // once their salvaged data is persisted.
+        let quarantine = recovery.as_ref().map_or(false, |r| {
+            r.needs_quarantine(file_id, file_number == n_files)
+        });
+
         match replay_result {
-            Ok(seq @ Some(_)) => max_sequence = max_sequence.max(seq),
-            Ok(None) => {
+            Ok(None) if !quarantine => {
                 // This file was empty and should be deleted.
                 warn!(
                     file_number,
@@ -212,21 +284,11 @@ where
                     "dropping empty wal segment",
                 );
 
-                // A failure to delete an empty file MUST not prevent WAL
-                // replay from continuing.
-                if let Err(error) = wal.delete(file_id).await {
-                    error!(
-                        file_number,
-                        n_files,
-                        %file_id,
-                        size = file_size,
-                        %error,
-                        "error dropping empty wal segment",
-                    );
-                }
+                deleter.delete_file(file_id).await;
 
                 continue;
             }
+            Ok(seq) => max_sequence = max_sequence.max(seq),
             // If the replay results in an underlying end of file error when
             // this is the most recent segment file, it indicates there was
             // a truncated write that never succeeded with an ACK.
@@ -259,10 +321,14 @@ where
         // Persist all the data that was replayed from the WAL segment.
         persist_partitions(sink.partition_iter(), &persist).await;
 
+        if let Some(recovery) = recovery.as_mut().filter(|_| quarantine) {
+            // Retain the damaged segment - it should not be replayed either.
+            quarantine_segment(wal, file_id, recovery).await;
+            continue;
+        }
+
         // Drop the newly persisted data - it should not be replayed.
-        wal.delete(file_id)
-            .await
-            .expect("failed to drop wal segment");
+        deleter.delete_file(file_id).await;
 
         info!(
             file_number,
@@ -273,6 +339,10 @@ where
         );
     }
 
+    if let Some(recovery) = &recovery {
+        recovery.log_summary();
+    }
+
     info!(
         max_sequence_number = ?max_sequence,
         "wal replay complete"
@@ -281,10 +351,42 @@ where
     Ok(max_sequence)
 }
 
+/// Move the damaged segment `id` out of `wal`, recording the outcome in
+/// `recovery`.
+///
+/// A failure to quarantine the segment MUST not prevent WAL replay from
+/// continuing.
+async fn quarantine_segment<W>(wal: &W, id: SegmentId, recovery: &mut RecoveryLog)
+where
+    W: WalReader,
+{
+    match wal.quarantine(id).await {
+        Ok(path) => {
+            warn!(
+                file_id = %id,
+                path = %path.display(),
+                "quarantined damaged wal segment"
+            );
+            recovery.quarantined(id, path);
+        }
+        Err(error) => {
+            error!(
+                file_id = %id,
+                %error,
+                "error quarantining damaged wal segment"
+            );
+        }
+    }
+}
+
 /// Replay the entries in `file`, applying them to `buffer`. Returns the
 /// highest sequence number observed across the batches read from the file, or
 /// [`None`] if there were no entries read.
 ///
+/// If `recovery` is provided, entries that cannot be read are discarded and
+/// recorded instead of returning an error - replay skips corrupt entries and
+/// stops reading the file at the first error it cannot skip.
+///
 /// # Warnings
 ///
 /// This function relies on the [`wal::blocking::ReaderError::UnableToReadData`]
@@ -297,6 +399,7 @@ async fn replay_file<T, F>(
     ok_op_count_metric: &U64Counter,
     empty_op_count_metric: &U64Counter,
     ingest_state: &Arc<IngestState>,
+    mut recovery: Option<&mut RecoveryLog>,
 ) -> Result<Option<SequenceNumber>, WalReplayError>
 where
     T: DmlSink,
@@ -307,7 +410,26 @@ where
     let segment_id = file.id();
 
     for batch in file {
-        let ops = batch.map_err(|e| WalReplayError::ReadEntry(e, max_sequence))?;
+        let ops = match (batch, recovery.as_deref_mut()) {
+            (Ok(ops), _) => ops,
+            (Err(error), Some(recovery)) => {
+                let reason = LossReason::classify(&error);
+                warn!(
+                    ?segment_id,
+                    %error,
+                    %reason,
+                    last_sequence_number = ?max_sequence,
+                    "discarding unreadable wal entry"
+                );
+                recovery.record(segment_id, reason);
+
+                match reason {
+                    LossReason::CorruptEntry => continue,
+                    LossReason::TruncatedTail | LossReason::Unreadable => break,
+                }
+            }
+            (Err(e), None) => return Err(WalReplayError::ReadEntry(e, max_sequence)),
+        };
 
         for op in ops {
             let SequencedWalOp {
@@ -317,7 +439,48 @@ where
 
             let op = match op {
                 Op::Write(w) => w,
-                Op::Delete(_) => unreachable!(),
+                Op::Delete(d) => {
+                    let table_id = TableId::new(d.table_id);
+                    let sequence_number = SequenceNumber::new(
+                        *table_write_sequence_numbers
+                            .get(&table_id)
+                            .expect("attempt to apply unsequenced wal op"),
+                    );
+                    max_sequence = max_sequence.max(Some(sequence_number));
+                    if let Some(recovery) = recovery.as_deref_mut() {
+                        recovery.observe(sequence_number, sequence_number);
+                    }
+
+                    let predicate = match d.predicate {
+                        Some(p) => Some(DeletePredicate::try_from(p)?),
+                        None if !d.column_names.is_empty() => None,
+                        None => {
+                            return Err(data_types::DeletePredicateProtoError::NoTimeRange.into())
+                        }
+                    };
+                    let op = DeleteOperation::new(
+                        NamespaceId::new(d.database_id),
+                        table_id,
+                        predicate,
+                        d.column_names,
+                        sequence_number,
+                        None,
+                    );
+
+                    debug!(?op, "apply wal delete op");
+
+                    // The replayed buffer is persisted in full once replay
+                    // completes, so there is no need to retain the delete -
+                    // release the applier's reference.
+                    let reference = Arc::clone(op.reference());
+                    sink.apply(IngestOp::Delete(op))
+                        .await
+                        .map_err(Into::<DmlError>::into)?;
+                    let _ = reference.release();
+
+                    ok_op_count_metric.inc(1);
+                    continue;
+                }
                 Op::Persist(_) => unreachable!(),
             };
 
@@ -364,6 +527,14 @@ where
                 None,
             );
 
+            if let (Some(recovery), Some(min), Some(max)) = (
+                recovery.as_deref_mut(),
+                op_min_sequence_number,
+                op_max_sequence_number,
+            ) {
+                recovery.observe(min, max);
+            }
+
             loop {
                 match ingest_state.read_with_exceptions([IngestStateError::DiskFull]) {
                     Ok(_) => break,
@@ -604,10 +775,12 @@ mod tests {
         let ingest_state = Arc::new(IngestState::default());
         let metrics = metric::Registry::default();
         let max_sequence_number = replay(
+            &wal,
             &wal,
             &mock_iter,
             Arc::clone(&persist),
             Arc::clone(&ingest_state),
+            WalReplayMode::Strict,
             &metrics,
         )
         .with_timeout_panic(Duration::from_secs(2))
@@ -692,6 +865,7 @@ mod tests {
     struct MockWalReader {
         readers: Mutex<VecDeque<MockSegmentedWalOpBatchReader>>,
         closed_segment_ids: Mutex<HashSet<SegmentId>>,
+        quarantined_segment_ids: Mutex<Vec<SegmentId>>,
     }
 
     impl MockWalReader {
@@ -704,6 +878,7 @@ mod tests {
                 closed_segment_ids: Mutex::new(
                     closed_segment_ids.into_iter().map(SegmentId::new).collect(),
                 ),
+                quarantined_segment_ids: Default::default(),
             }
         }
     }
@@ -729,9 +904,17 @@ mod tests {
                 .collect()
         }
 
-        async fn delete(&self, id: SegmentId) -> Result<(), wal::Error> {
+        async fn quarantine(&self, id: SegmentId) -> Result<PathBuf, wal::Error> {
+            assert!(self.closed_segment_ids.lock().remove(&id));
+            self.quarantined_segment_ids.lock().push(id);
+            Ok(PathBuf::from(format!("quarantine/{id}.dat")))
+        }
+    }
+
+    #[async_trait]
+    impl WalFileDeleter for MockWalReader {
+        async fn delete_file(&self, id: SegmentId) {
             assert!(self.closed_segment_ids.lock().remove(&id));
-            Ok(())
         }
     }
 
@@ -832,10 +1015,12 @@ mod tests {
         let metrics = metric::Registry::default();
 
         let max_sequence_number = replay(
+            &wal,
             &wal,
             &mock_iter,
             Arc::clone(&persist),
             Arc::new(IngestState::default()),
+            WalReplayMode::Strict,
             &metrics,
         )
         .await
@@ -898,10 +1083,12 @@ mod tests {
         let metrics = metric::Registry::default();
 
         let replay_result = replay(
+            &wal,
             &wal,
             &mock_iter,
             Arc::clone(&persist),
             Arc::new(IngestState::default()),
+            WalReplayMode::Strict,
             &metrics,
         )
         .await;
@@ -946,6 +1133,7 @@ mod tests {
                     &metric.recorder(&[]),
                     &metric.recorder(&[]),
                     &ingest_state,
+                    None,
                 )
                 .await
             })
@@ -993,6 +1181,7 @@ mod tests {
                 &metric.recorder(&[]),
                 &metric.recorder(&[]),
                 &Arc::clone(&ingest_state),
+                None,
             )
             .with_timeout_panic(Duration::from_secs(2))
             .await,
@@ -1002,4 +1191,177 @@ mod tests {
         );
         assert_eq!(mock_sink.get_calls().len(), 2);
     }
+
+    /// Construct the [`wal::Error`] returned when a segment entry fails
+    /// checksum validation.
+    fn checksum_mismatch_error() -> wal::Error {
+        wal::Error::UnableToReadNextOps {
+            source: wal::blocking::ReaderError::ChecksumMismatch {
+                expected: 42,
+                actual: 24,
+            },
+        }
+    }
+
+    /// Construct the [`wal::Error`] returned when a segment ends part way
+    /// through an entry.
+    fn truncated_error() -> wal::Error {
+        wal::Error::UnableToReadNextOps {
+            source: wal::blocking::ReaderError::UnableToReadData {
+                source: std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "gremlins in the drive",
+                ),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_recover_mode() {
+        let wal = MockWalReader::new(
+            [
+                MockSegmentedWalOpBatchReader::new(SegmentId::new(1)).with_entry_results([Ok(
+                    vec![arbitrary_sequenced_wal_op(SequenceNumber::new(1))],
+                )]),
+                MockSegmentedWalOpBatchReader::new(SegmentId::new(2)).with_entry_results([
+                    Ok(vec![arbitrary_sequenced_wal_op(SequenceNumber::new(2))]),
+                    Err(checksum_mismatch_error()),
+                    Ok(vec![arbitrary_sequenced_wal_op(SequenceNumber::new(5))]),
+                ]),
+                MockSegmentedWalOpBatchReader::new(SegmentId::new(3)).with_entry_results([
+                    Ok(vec![arbitrary_sequenced_wal_op(SequenceNumber::new(6))]),
+                    Err(truncated_error()),
+                ]),
+            ],
+            [1, 2, 3],
+        );
+
+        let persist = Arc::new(MockPersistQueue::default());
+        let mock_sink =
+            MockDmlSink::default().with_apply_return(vec![Ok(()), Ok(()), Ok(()), Ok(())]);
+        let mock_iter = MockIter {
+            sink: mock_sink,
+            partitions: vec![],
+        };
+        let metrics = metric::Registry::default();
+
+        let max_sequence_number = replay(
+            &wal,
+            &wal,
+            &mock_iter,
+            Arc::clone(&persist),
+            Arc::new(IngestState::default()),
+            WalReplayMode::Recover,
+            &metrics,
+        )
+        .await
+        .expect("failed to replay WAL")
+        .expect("should receive max sequence number");
+        assert_eq!(max_sequence_number, SequenceNumber::new(6));
+
+        // All the readable entries were applied.
+        assert_eq!(mock_iter.sink.get_calls().len(), 4);
+
+        // The segment with the corrupt entry was quarantined, while the torn
+        // tail of the last segment alone does not require quarantine.
+        assert!(wal.closed_segment_ids.lock().is_empty());
+        assert_eq!(*wal.quarantined_segment_ids.lock(), [SegmentId::new(2)]);
+
+        assert_counter!(
+            metrics,
+            U64Counter,
+            "ingester_wal_replay_files_finished",
+            labels = Attributes::from(&[("result", "success")]),
+            value = 1,
+        );
+        assert_counter!(
+            metrics,
+            U64Counter,
+            "ingester_wal_replay_files_finished",
+            labels = Attributes::from(&[("result", "error"), ("reason", "corrupt")]),
+            value = 1,
+        );
+        assert_counter!(
+            metrics,
+            U64Counter,
+            "ingester_wal_replay_files_finished",
+            labels = Attributes::from(&[("result", "error"), ("reason", "truncated")]),
+            value = 1,
+        );
+        assert_counter!(
+            metrics,
+            U64Counter,
+            "ingester_wal_replay_entries_discarded",
+            labels = Attributes::from(&[("reason", "corrupt")]),
+            value = 1,
+        );
+        assert_counter!(
+            metrics,
+            U64Counter,
+            "ingester_wal_replay_entries_discarded",
+            labels = Attributes::from(&[("reason", "truncated")]),
+            value = 1,
+        );
+        assert_counter!(
+            metrics,
+            U64Counter,
+            "ingester_wal_replay_segments_quarantined",
+            value = 1,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replay_file_records_lost_ranges() {
+        let metrics = metric::Registry::default();
+        let metric = metrics.register_metric::<U64Counter>("foo", "bar");
+        let reader = MockSegmentedWalOpBatchReader::new(SegmentId::new(1)).with_entry_results([
+            Ok(vec![arbitrary_sequenced_wal_op(SequenceNumber::new(1))]),
+            Err(checksum_mismatch_error()),
+            Err(checksum_mismatch_error()),
+            Ok(vec![arbitrary_sequenced_wal_op(SequenceNumber::new(4))]),
+            Err(wal::Error::UnableToReadNextOps {
+                source: wal::blocking::ReaderError::UnableToReadChecksum {
+                    source: std::io::Error::new(std::io::ErrorKind::Other, "bad sector"),
+                },
+            }),
+            // Not read, as the segment is unreadable past the error above.
+            Ok(vec![arbitrary_sequenced_wal_op(SequenceNumber::new(9))]),
+        ]);
+        let mock_sink = MockDmlSink::default().with_apply_return(vec![Ok(()), Ok(())]);
+        let mut recovery = RecoveryLog::new(&metrics);
+
+        assert_matches!(
+            replay_file(
+                reader,
+                &mock_sink,
+                &metric.recorder(&[]),
+                &metric.recorder(&[]),
+                &Arc::new(IngestState::default()),
+                Some(&mut recovery),
+            )
+            .with_timeout_panic(Duration::from_secs(2))
+            .await,
+            Ok(Some(id)) => {
+                assert_eq!(id, SequenceNumber::new(4));
+            }
+        );
+        assert_eq!(mock_sink.get_calls().len(), 2);
+
+        assert_matches!(
+            recovery.lost(),
+            [corrupt, unreadable] => {
+                assert_eq!(corrupt.segment_id, SegmentId::new(1));
+                assert_eq!(corrupt.reason, LossReason::CorruptEntry);
+                assert_eq!(corrupt.read_failures, 2);
+                assert_eq!(corrupt.after, Some(SequenceNumber::new(1)));
+                assert_eq!(corrupt.before, Some(SequenceNumber::new(4)));
+
+                assert_eq!(unreadable.reason, LossReason::Unreadable);
+                assert_eq!(unreadable.read_failures, 1);
+                assert_eq!(unreadable.after, Some(SequenceNumber::new(4)));
+                assert_eq!(unreadable.before, None);
+            }
+        );
+        assert!(recovery.needs_quarantine(SegmentId::new(1), true));
+    }
 }
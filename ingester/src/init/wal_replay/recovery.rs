@@ -0,0 +1,237 @@
+//! Bookkeeping for the WAL data discarded by a [`WalReplayMode::Recover`]
+//! replay.
+//!
+//! [`WalReplayMode::Recover`]: super::WalReplayMode::Recover
+
+use std::{fmt::Display, io::ErrorKind, path::PathBuf};
+
+use data_types::SequenceNumber;
+use metric::U64Counter;
+use observability_deps::tracing::*;
+use wal::{blocking::ReaderError, SegmentId};
+
+/// The reason WAL entries were discarded during replay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum LossReason {
+    /// The segment ends with a partially written entry, typically the result
+    /// of a torn write during a crash or power loss.
+    TruncatedTail,
+
+    /// An entry failed checksum validation or could not be decoded, and was
+    /// skipped.
+    CorruptEntry,
+
+    /// The segment could not be read from this point onwards.
+    Unreadable,
+}
+
+impl LossReason {
+    /// Classify the WAL read error `e` by how much of the segment it makes
+    /// unreadable.
+    ///
+    /// Only [`LossReason::CorruptEntry`] errors leave the reader positioned at
+    /// the start of the next entry, allowing the remainder of the segment to
+    /// be replayed.
+    pub(super) fn classify(e: &wal::Error) -> Self {
+        let wal::Error::UnableToReadNextOps { source } = e else {
+            return Self::Unreadable;
+        };
+
+        match source {
+            ReaderError::UnableToReadLength { source }
+            | ReaderError::UnableToReadData { source }
+                if source.kind() == ErrorKind::UnexpectedEof =>
+            {
+                Self::TruncatedTail
+            }
+            ReaderError::LengthMismatch { .. } => Self::TruncatedTail,
+            ReaderError::UnableToReadData { source } if source.kind() == ErrorKind::InvalidData => {
+                Self::CorruptEntry
+            }
+            ReaderError::ChecksumMismatch { .. }
+            | ReaderError::UnableToDecompressData { .. }
+            | ReaderError::UnableToDeserializeData { .. }
+            | ReaderError::InvalidMessage { .. } => Self::CorruptEntry,
+            _ => Self::Unreadable,
+        }
+    }
+
+    pub(super) fn as_str(&self) -> &'static str {
+        match self {
+            Self::TruncatedTail => "truncated",
+            Self::CorruptEntry => "corrupt",
+            Self::Unreadable => "unreadable",
+        }
+    }
+}
+
+impl Display for LossReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A contiguous run of WAL entries discarded from a single segment.
+///
+/// The sequence numbers of discarded entries cannot be read, so the lost range
+/// is bounded by the sequence numbers of the entries replayed either side of
+/// it - every sequence number strictly between `after` and `before` was lost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct LostRange {
+    pub(super) segment_id: SegmentId,
+    pub(super) reason: LossReason,
+
+    /// The number of read failures merged into this range.
+    pub(super) read_failures: usize,
+
+    /// The highest sequence number replayed before the discarded entries, or
+    /// [`None`] if nothing was replayed before them.
+    pub(super) after: Option<SequenceNumber>,
+
+    /// The lowest sequence number replayed after the discarded entries, or
+    /// [`None`] if nothing was replayed after them.
+    pub(super) before: Option<SequenceNumber>,
+}
+
+/// Records the WAL entries and segments discarded during a
+/// [`WalReplayMode::Recover`] replay, reporting them through metrics and a
+/// summary log once replay completes.
+///
+/// [`WalReplayMode::Recover`]: super::WalReplayMode::Recover
+#[derive(Debug)]
+pub(super) struct RecoveryLog {
+    lost: Vec<LostRange>,
+    quarantined: Vec<(SegmentId, PathBuf)>,
+
+    /// The highest sequence number replayed so far.
+    last_sequence: Option<SequenceNumber>,
+
+    discarded_metric: metric::Metric<U64Counter>,
+    quarantined_metric: U64Counter,
+}
+
+impl RecoveryLog {
+    pub(super) fn new(metrics: &metric::Registry) -> Self {
+        let discarded_metric = metrics.register_metric::<U64Counter>(
+            "ingester_wal_replay_entries_discarded",
+            "Number of WAL read failures that caused entries to be discarded during replay",
+        );
+        let quarantined_metric = metrics
+            .register_metric::<U64Counter>(
+                "ingester_wal_replay_segments_quarantined",
+                "Number of damaged WAL segments moved to the quarantine directory during replay",
+            )
+            .recorder(&[]);
+
+        Self {
+            lost: Default::default(),
+            quarantined: Default::default(),
+            last_sequence: None,
+            discarded_metric,
+            quarantined_metric,
+        }
+    }
+
+    /// Record that an op with sequence numbers spanning `min` to `max` was
+    /// replayed, bounding any lost ranges still waiting for an upper bound.
+    pub(super) fn observe(&mut self, min: SequenceNumber, max: SequenceNumber) {
+        for range in self.lost.iter_mut().rev() {
+            if range.before.is_some() {
+                break;
+            }
+            range.before = Some(min);
+        }
+
+        self.last_sequence = self.last_sequence.max(Some(max));
+    }
+
+    /// Record that entries were discarded from `segment_id` for `reason`.
+    ///
+    /// Consecutive corrupt entries in the same segment are merged into a
+    /// single [`LostRange`].
+    pub(super) fn record(&mut self, segment_id: SegmentId, reason: LossReason) {
+        self.discarded_metric
+            .recorder(&[("reason", reason.as_str())])
+            .inc(1);
+
+        if let Some(last) = self.lost.last_mut() {
+            if last.before.is_none() && last.segment_id == segment_id && last.reason == reason {
+                last.read_failures += 1;
+                return;
+            }
+        }
+
+        self.lost.push(LostRange {
+            segment_id,
+            reason,
+            read_failures: 1,
+            after: self.last_sequence,
+            before: None,
+        });
+    }
+
+    /// Record that `segment_id` was moved to `path`.
+    pub(super) fn quarantined(&mut self, segment_id: SegmentId, path: PathBuf) {
+        self.quarantined_metric.inc(1);
+        self.quarantined.push((segment_id, path));
+    }
+
+    /// Returns the reason for the first entries discarded from `segment_id`,
+    /// if any.
+    pub(super) fn first_loss(&self, segment_id: SegmentId) -> Option<LossReason> {
+        self.lost
+            .iter()
+            .find(|r| r.segment_id == segment_id)
+            .map(|r| r.reason)
+    }
+
+    /// Returns true if `segment_id` lost entries that may have been
+    /// acknowledged to a client, and should be retained for inspection.
+    ///
+    /// A torn write at the tail of the most recent segment was never
+    /// acknowledged, and alone does not warrant quarantining the segment.
+    pub(super) fn needs_quarantine(&self, segment_id: SegmentId, is_last_segment: bool) -> bool {
+        self.lost.iter().any(|r| {
+            r.segment_id == segment_id
+                && !(is_last_segment && r.reason == LossReason::TruncatedTail)
+        })
+    }
+
+    #[cfg(test)]
+    pub(super) fn lost(&self) -> &[LostRange] {
+        &self.lost
+    }
+
+    /// Emit a summary of the data discarded during replay.
+    pub(super) fn log_summary(&self) {
+        if self.lost.is_empty() {
+            info!("wal replay recovery discarded no entries");
+            return;
+        }
+
+        warn!(
+            n_lost_ranges = self.lost.len(),
+            n_quarantined = self.quarantined.len(),
+            "wal replay recovery discarded damaged entries"
+        );
+
+        for range in &self.lost {
+            warn!(
+                segment_id = %range.segment_id,
+                reason = %range.reason,
+                read_failures = range.read_failures,
+                lost_after_sequence_number = ?range.after,
+                lost_before_sequence_number = ?range.before,
+                "wal entries lost during replay"
+            );
+        }
+
+        for (segment_id, path) in &self.quarantined {
+            warn!(
+                %segment_id,
+                path = %path.display(),
+                "damaged wal segment quarantined"
+            );
+        }
+    }
+}
@@ -133,7 +133,7 @@ where
 }
 
 /// An optional [`PersistCompletionObserver`] decorator layer.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum MaybeLayer<T, U> {
     /// With the optional layer.
     With(T),
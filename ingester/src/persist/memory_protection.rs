@@ -0,0 +1,392 @@
+//! Bound the amount of memory used by buffered data.
+
+use std::{sync::Arc, time::Duration};
+
+use observability_deps::tracing::*;
+use parking_lot::Mutex;
+
+use super::queue::PersistQueue;
+use crate::{
+    buffer_tree::partition::PartitionData,
+    ingest_state::{IngestState, IngestStateError},
+    init::BufferMemoryLimit,
+    partition_iter::PartitionIter,
+};
+
+/// Evaluates the memory used by all buffered partitions, persisting the
+/// partitions holding the most buffered data when the soft limit of the
+/// [`BufferMemoryLimit`] is exceeded, and blocking ingest by setting
+/// [`IngestStateError::MemoryExhausted`] while the hard limit is exceeded.
+#[derive(Debug)]
+pub(crate) struct BufferMemoryGuard<T, P> {
+    buffer: T,
+    persist: P,
+    ingest_state: Arc<IngestState>,
+    limit: BufferMemoryLimit,
+
+    /// The estimated number of bytes used by buffered and persisting data,
+    /// as of the last evaluation.
+    buffered_bytes: metric::U64Gauge,
+
+    /// The number of partitions persisted because the soft limit was
+    /// exceeded.
+    persist_count: metric::U64Counter,
+}
+
+impl<T, P> BufferMemoryGuard<T, P>
+where
+    T: PartitionIter + Sync,
+    P: PersistQueue + Clone,
+{
+    pub(crate) fn new(
+        buffer: T,
+        persist: P,
+        ingest_state: Arc<IngestState>,
+        limit: BufferMemoryLimit,
+        metrics: &metric::Registry,
+    ) -> Self {
+        let buffered_bytes = metrics
+            .register_metric::<metric::U64Gauge>(
+                "ingester_buffer_memory_bytes",
+                "estimated number of bytes used by buffered and persisting data",
+            )
+            .recorder(&[]);
+        let persist_count = metrics
+            .register_metric::<metric::U64Counter>(
+                "ingester_persist_memory_pressure_enqueue_count",
+                "number of times persistence of a partition has been triggered \
+                because the buffered data exceeded the configured memory limit",
+            )
+            .recorder(&[]);
+
+        Self {
+            buffer,
+            persist,
+            ingest_state,
+            limit,
+            buffered_bytes,
+            persist_count,
+        }
+    }
+
+    /// Evaluate the buffer memory usage every `interval`, forever.
+    pub(crate) async fn run(self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+            self.check().await;
+        }
+    }
+
+    /// Evaluate the buffer memory usage once, returning the number of
+    /// partitions enqueued for persistence.
+    pub(crate) async fn check(&self) -> usize {
+        let mut total_bytes = 0;
+        let mut buffered_bytes = 0;
+        let mut candidates = vec![];
+        for partition in self.buffer.partition_iter() {
+            let (memory, cost) = {
+                let guard = partition.lock();
+                (guard.memory_bytes(), guard.persist_cost_estimate())
+            };
+
+            total_bytes += memory;
+            buffered_bytes += cost;
+            if cost > 0 {
+                candidates.push((cost, partition));
+            }
+        }
+        self.buffered_bytes.set(total_bytes as u64);
+
+        if total_bytes >= self.limit.hard_limit_bytes() {
+            if self.ingest_state.set(IngestStateError::MemoryExhausted) {
+                warn!(
+                    total_bytes,
+                    hard_limit_bytes = self.limit.hard_limit_bytes(),
+                    "buffer memory hard limit exceeded, blocking ingest until buffered data is persisted"
+                );
+            }
+        } else if self.ingest_state.unset(IngestStateError::MemoryExhausted) {
+            info!(
+                total_bytes,
+                hard_limit_bytes = self.limit.hard_limit_bytes(),
+                "buffer memory back within hard limit, re-enabling ingest"
+            );
+        }
+
+        // Data that is already persisting is released once its persist job
+        // completes, so only the remainder has to be freed by persisting
+        // buffered data.
+        let persisting_bytes = total_bytes - buffered_bytes;
+        let mut to_free = total_bytes
+            .saturating_sub(self.limit.soft_limit_bytes())
+            .saturating_sub(persisting_bytes);
+        if to_free == 0 {
+            return 0;
+        }
+
+        debug!(
+            total_bytes,
+            persisting_bytes,
+            to_free,
+            soft_limit_bytes = self.limit.soft_limit_bytes(),
+            "buffer memory soft limit exceeded, persisting largest partitions"
+        );
+
+        // Persist the partitions holding the most buffered data first, freeing
+        // the most memory with the fewest persist jobs.
+        //
+        // How recently a partition was written to is not considered: marking
+        // a partition as persisting hands all of its buffered data to the
+        // persist job, and subsequent writes start a new, empty buffer. Even a
+        // partition that is still being written to frees all the memory it
+        // held once its persist job completes, so the persisted size alone
+        // determines how much memory is released.
+        candidates.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+
+        let mut n_persisted = 0;
+        for (_, partition) in candidates {
+            if to_free == 0 {
+                break;
+            }
+
+            if let Some(freed) = self.persist(partition).await {
+                to_free = to_free.saturating_sub(freed);
+                n_persisted += 1;
+            }
+        }
+
+        self.persist_count.inc(n_persisted as u64);
+        n_persisted
+    }
+
+    /// Mark `partition` as persisting and enqueue it, returning the number of
+    /// buffered bytes handed to the persist job, or [`None`] if the partition
+    /// was persisted concurrently.
+    async fn persist(&self, partition: Arc<Mutex<PartitionData>>) -> Option<usize> {
+        let (cost, data) = {
+            let mut guard = partition.lock();
+            let cost = guard.persist_cost_estimate();
+            let data = guard.mark_persisting()?;
+
+            info!(
+                partition_id = %guard.partition_id(),
+                cost_estimate = cost,
+                "marking partition for persistence due to buffer memory pressure"
+            );
+
+            (cost, data)
+        };
+
+        // There is no need to await on the completion handle.
+        let _ = self.persist.enqueue(partition, data).await;
+
+        Some(cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::SequenceNumber;
+    use mutable_batch_lp::test_helpers::lp_to_mutable_batch;
+    use test_helpers::timeout::FutureTimeout;
+
+    use super::*;
+    use crate::{
+        persist::{
+            queue::mock::MockPersistQueue,
+            tests::{assert_metric_counter, assert_metric_gauge},
+        },
+        test_util::{PartitionDataBuilder, ARBITRARY_TABLE_NAME},
+    };
+
+    /// Build a partition containing `n` rows.
+    fn partition_with_rows(n: usize) -> Arc<Mutex<PartitionData>> {
+        let lp = (0..n)
+            .map(|i| {
+                format!(
+                    r#"{},city=Hereford people={i},crisps="good" {i}"#,
+                    &*ARBITRARY_TABLE_NAME
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut p = PartitionDataBuilder::new().build();
+        p.buffer_write(lp_to_mutable_batch(&lp).1, SequenceNumber::new(1))
+            .expect("write should succeed");
+
+        Arc::new(Mutex::new(p))
+    }
+
+    fn memory_bytes(partitions: &[Arc<Mutex<PartitionData>>]) -> usize {
+        partitions.iter().map(|p| p.lock().memory_bytes()).sum()
+    }
+
+    #[tokio::test]
+    async fn test_below_soft_limit() {
+        let partitions = vec![partition_with_rows(1), partition_with_rows(10)];
+        let total = memory_bytes(&partitions);
+
+        let metrics = metric::Registry::default();
+        let persist = Arc::new(MockPersistQueue::default());
+        let ingest_state = Arc::new(IngestState::default());
+        let guard = BufferMemoryGuard::new(
+            partitions,
+            Arc::clone(&persist),
+            Arc::clone(&ingest_state),
+            BufferMemoryLimit::new(total, total + 1),
+            &metrics,
+        );
+
+        assert_eq!(guard.check().await, 0);
+        assert!(persist.calls().is_empty());
+        assert_matches!(ingest_state.read(), Ok(()));
+
+        assert_metric_gauge(&metrics, "ingester_buffer_memory_bytes", total as u64);
+    }
+
+    #[tokio::test]
+    async fn test_soft_limit_persists_largest() {
+        let small = partition_with_rows(1);
+        let large = partition_with_rows(100);
+        let partitions = vec![Arc::clone(&small), Arc::clone(&large)];
+        let total = memory_bytes(&partitions);
+
+        let metrics = metric::Registry::default();
+        let persist = Arc::new(MockPersistQueue::default());
+        let ingest_state = Arc::new(IngestState::default());
+        let guard = BufferMemoryGuard::new(
+            partitions,
+            Arc::clone(&persist),
+            Arc::clone(&ingest_state),
+            // Exceed the soft limit by a single byte.
+            BufferMemoryLimit::new(total - 1, total * 2),
+            &metrics,
+        );
+
+        // Only the largest partition is persisted, which frees enough memory.
+        assert_eq!(guard.check().await, 1);
+        assert_matches!(persist.calls().as_slice(), [p] => {
+            assert!(Arc::ptr_eq(p, &large));
+        });
+        assert!(small.lock().persist_cost_estimate() > 0);
+        assert_matches!(ingest_state.read(), Ok(()));
+
+        assert_metric_counter(
+            &metrics,
+            "ingester_persist_memory_pressure_enqueue_count",
+            1,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_persist_partition_written_to() {
+        let hot = partition_with_rows(100);
+        let cold = partition_with_rows(10);
+        let partitions = vec![Arc::clone(&hot), Arc::clone(&cold)];
+        let total = memory_bytes(&partitions);
+        let hot_bytes = memory_bytes(&[Arc::clone(&hot)]);
+
+        let metrics = metric::Registry::default();
+        let persist = Arc::new(MockPersistQueue::default());
+        let ingest_state = Arc::new(IngestState::default());
+        let guard = BufferMemoryGuard::new(
+            partitions,
+            Arc::clone(&persist),
+            Arc::clone(&ingest_state),
+            BufferMemoryLimit::new(total - 1, total * 2),
+            &metrics,
+        );
+
+        assert_eq!(guard.check().await, 1);
+        assert_matches!(persist.calls().as_slice(), [p] => {
+            assert!(Arc::ptr_eq(p, &hot));
+        });
+
+        // The persisted partition keeps receiving writes while persisting.
+        let lp = format!(
+            r#"{},city=Hereford people=1,crisps="good" 4242"#,
+            &*ARBITRARY_TABLE_NAME
+        );
+        hot.lock()
+            .buffer_write(lp_to_mutable_batch(&lp).1, SequenceNumber::new(2))
+            .expect("write should succeed");
+
+        // Once the persist job completes, only the new write remains
+        // buffered, and the memory used is back below the soft limit without
+        // persisting the hot partition again, nor the cold one.
+        async {
+            while hot.lock().memory_bytes() >= hot_bytes {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }
+        .with_timeout_panic(Duration::from_secs(5))
+        .await;
+        assert_eq!(guard.check().await, 0);
+        assert_eq!(persist.calls().len(), 1);
+        assert!(cold.lock().persist_cost_estimate() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_persisting_data_not_persisted_again() {
+        let partition = partition_with_rows(10);
+        let total = memory_bytes(&[Arc::clone(&partition)]);
+
+        // Mark the data as persisting, without completing the persist.
+        let _data = partition.lock().mark_persisting().unwrap();
+
+        let metrics = metric::Registry::default();
+        let persist = Arc::new(MockPersistQueue::default());
+        let ingest_state = Arc::new(IngestState::default());
+        let guard = BufferMemoryGuard::new(
+            vec![Arc::clone(&partition)],
+            Arc::clone(&persist),
+            Arc::clone(&ingest_state),
+            BufferMemoryLimit::new(1, total * 2),
+            &metrics,
+        );
+
+        // The persisting data still counts towards the limit, but there is
+        // nothing left to persist.
+        assert_eq!(guard.check().await, 0);
+        assert!(persist.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_hard_limit() {
+        let partitions = vec![partition_with_rows(10), partition_with_rows(10)];
+        let total = memory_bytes(&partitions);
+
+        let metrics = metric::Registry::default();
+        let persist = Arc::new(MockPersistQueue::default());
+        let ingest_state = Arc::new(IngestState::default());
+        let guard = BufferMemoryGuard::new(
+            partitions.clone(),
+            Arc::clone(&persist),
+            Arc::clone(&ingest_state),
+            BufferMemoryLimit::new(1, total),
+            &metrics,
+        );
+
+        // Both partitions must be persisted to get below the soft limit, and
+        // ingest is blocked while the hard limit is exceeded.
+        assert_eq!(guard.check().await, 2);
+        assert_eq!(persist.calls().len(), 2);
+        assert_matches!(ingest_state.read(), Err(IngestStateError::MemoryExhausted));
+
+        // Once the persist jobs complete, ingest is re-enabled.
+        async {
+            while memory_bytes(&partitions) > 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }
+        .with_timeout_panic(Duration::from_secs(5))
+        .await;
+        assert_eq!(guard.check().await, 0);
+        assert_matches!(ingest_state.read(), Ok(()));
+    }
+}
@@ -8,6 +8,7 @@ pub(crate) mod drain_buffer;
 pub(crate) mod file_metrics;
 pub(crate) mod handle;
 pub(crate) mod hot_partitions;
+pub(crate) mod memory_protection;
 pub mod queue;
 mod worker;
 
@@ -287,7 +288,7 @@ mod tests {
                 ..
             }] =>
             {
-                assert_eq!(created_at.get(), max_l0_created_at.get());
+                assert!(max_l0_created_at.get() <= created_at.get());
 
                 assert_eq!(got_namespace_id, &namespace_id);
                 assert_eq!(got_table_id, &table_id);
@@ -472,7 +473,7 @@ mod tests {
                 ..
             }] =>
             {
-                assert_eq!(created_at.get(), max_l0_created_at.get());
+                assert!(max_l0_created_at.get() <= created_at.get());
 
                 assert_eq!(got_namespace_id, &namespace_id);
                 assert_eq!(got_table_id, &table_id);
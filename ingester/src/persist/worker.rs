@@ -183,7 +183,7 @@ where
     // updated to include a column that does not exist in the column map.
     let column_map = fetch_column_map(ctx, worker_state, sort_key.as_ref()).await?;
 
-    let compacted = compact(ctx, worker_state, sort_key.as_ref()).await;
+    let compacted = compact(ctx, worker_state, sort_key.as_ref(), &column_map).await;
     let (sort_key_update, parquet_table_data) =
         upload(ctx, worker_state, compacted, &column_map).await;
 
@@ -205,10 +205,14 @@ where
 
 /// Compact the data in `ctx` using sorted by the sort key returned from
 /// [`Context::sort_key()`].
+///
+/// Any buffered columns that have since been deleted from the catalog (i.e.
+/// are not in `columns`, or have a different type) are dropped.
 async fn compact<O>(
     ctx: &Context,
     worker_state: &SharedWorkerState<O>,
     sort_key: Option<&SortKey>,
+    columns: &ColumnsByName,
 ) -> CompactedStream
 where
     O: Send + Sync,
@@ -234,7 +238,7 @@ where
         &worker_state.exec,
         sort_key,
         ctx.table().get().await.name().clone(),
-        ctx.data().query_adaptor(),
+        ctx.data().query_adaptor().retain_columns(columns),
     )
     .await
 }
@@ -273,18 +277,25 @@ where
     );
 
     // Construct the metadata for this parquet file.
+    //
+    // The max_l0_created_at is the time the data was snapshot from the buffer,
+    // which bounds the time at which any row in the file was written. A
+    // tombstone created after this point applies to the file, while deletes
+    // applied to the buffer before the snapshot are already reflected in it.
     let time_now = SystemProvider::new().now();
+    let table_name = Arc::clone(ctx.table().get().await.name());
     let iox_metadata = IoxMetadata {
         object_store_id,
         creation_timestamp: time_now,
         namespace_id: ctx.namespace_id(),
         namespace_name: Arc::clone(&*ctx.namespace_name().get().await),
         table_id: ctx.table_id(),
-        table_name: Arc::clone(ctx.table().get().await.name()),
+        bloom_filters: worker_state.store.bloom_filters_enabled(&table_name),
+        table_name,
         partition_key: ctx.partition_key().clone(),
         compaction_level: CompactionLevel::Initial,
         sort_key: Some(data_sort_key),
-        max_l0_created_at: time_now,
+        max_l0_created_at: ctx.data().snapshot_at(),
     };
 
     // Save the compacted data to a parquet file in object storage.
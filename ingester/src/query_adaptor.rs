@@ -5,14 +5,14 @@ use std::{any::Any, sync::Arc};
 
 use arrow::record_batch::RecordBatch;
 use arrow_util::util::ensure_schema;
-use data_types::{ChunkId, ChunkOrder, TimestampMinMax, TransitionPartitionId};
+use data_types::{ChunkId, ChunkOrder, ColumnsByName, TimestampMinMax, TransitionPartitionId};
 use datafusion::physical_plan::Statistics;
 use iox_query::{
     util::{compute_timenanosecond_min_max, create_basic_summary},
     QueryChunk, QueryChunkData,
 };
 use once_cell::sync::OnceCell;
-use schema::{merge::merge_record_batch_schemas, sort::SortKey, Schema};
+use schema::{merge::merge_record_batch_schemas, sort::SortKey, InfluxColumnType, Schema};
 
 /// A queryable wrapper over a set of ordered [`RecordBatch`] snapshot from a
 /// single [`PartitionData`].
@@ -97,6 +97,44 @@ impl QueryAdaptor {
         // This batch may have been projected to exclude the time column
         compute_timenanosecond_min_max(self.data.iter()).ok()
     }
+
+    /// Project this [`QueryAdaptor`] to the columns present in `columns` with
+    /// a matching type, dropping all others.
+    ///
+    /// Columns may be deleted from the catalog (and re-created with a
+    /// different type) after data for them was buffered. Returns `self`
+    /// unchanged if no columns need to be dropped.
+    pub(crate) fn retain_columns(self, columns: &ColumnsByName) -> Self {
+        let keep = |t: InfluxColumnType, name: &str| {
+            columns.get(name).map_or(false, |c| c.matches_type(t))
+        };
+
+        if self.schema.iter().all(|(t, f)| keep(t, f.name())) {
+            return self;
+        }
+
+        let data = self
+            .data
+            .into_iter()
+            .map(|batch| {
+                let schema =
+                    Schema::try_from(batch.schema()).expect("buffered data has a valid schema");
+                let indices = schema
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (t, f))| keep(*t, f.name()))
+                    .map(|(i, _)| i)
+                    .collect::<Vec<_>>();
+                batch
+                    .project(&indices)
+                    .expect("projection indices are in bounds")
+            })
+            .collect();
+
+        // The time column can never be deleted, so the row count is unchanged
+        // and the non-empty invariant is preserved.
+        Self::new(self.partition_id, data)
+    }
 }
 
 impl QueryChunk for QueryAdaptor {
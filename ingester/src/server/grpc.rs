@@ -2,6 +2,7 @@
 
 mod persist;
 mod query;
+mod rpc_delete;
 mod rpc_write;
 
 use std::{fmt::Debug, sync::Arc};
@@ -20,7 +21,7 @@ use crate::{
     timestamp_oracle::TimestampOracle,
 };
 
-use self::{persist::PersistHandler, rpc_write::RpcWrite};
+use self::{persist::PersistHandler, rpc_delete::RpcDelete, rpc_write::RpcWrite};
 
 /// This type is responsible for injecting internal dependencies that SHOULD NOT
 /// leak outside of the ingester crate into public gRPC handlers.
@@ -85,6 +86,7 @@ where
 {
     type CatalogHandler = CatalogService;
     type WriteHandler = RpcWrite<Arc<D>>;
+    type DeleteHandler = RpcDelete<Arc<D>>;
     type PersistHandler = PersistHandler<Arc<T>, Arc<P>>;
     type FlightHandler = query::FlightService<Arc<Q>>;
 
@@ -106,6 +108,17 @@ where
         )
     }
 
+    /// Return a [`DeleteService`] gRPC implementation.
+    ///
+    /// [`DeleteService`]: generated_types::influxdata::iox::delete::v1::delete_service_server::DeleteService.
+    fn delete_service(&self) -> Self::DeleteHandler {
+        RpcDelete::new(
+            Arc::clone(&self.dml_sink),
+            Arc::clone(&self.timestamp),
+            Arc::clone(&self.ingest_state),
+        )
+    }
+
     /// Return a [`PersistService`] gRPC implementation.
     ///
     /// [`PersistService`]: generated_types::influxdata::iox::ingester::v1::persist_service_server::PersistService.
@@ -0,0 +1,244 @@
+use std::sync::Arc;
+
+use data_types::{DeletePredicate, DeletePredicateProtoError, NamespaceId, TableId};
+use generated_types::influxdata::iox::delete::v1::{
+    self as proto, delete_service_server::DeleteService,
+};
+use observability_deps::tracing::*;
+use thiserror::Error;
+use tonic::{Code, Request, Response};
+use trace::{
+    ctx::SpanContext,
+    span::{SpanExt, SpanRecorder},
+};
+
+use crate::{
+    dml_payload::{delete::DeleteOperation, IngestOp},
+    dml_sink::DmlSink,
+    ingest_state::{IngestState, IngestStateError},
+    timestamp_oracle::TimestampOracle,
+};
+
+/// A list of error states when handling an RPC delete request.
+#[derive(Debug, Error)]
+enum RpcError {
+    /// The RPC delete request did not contain a delete payload.
+    #[error("rpc delete request does not contain a payload")]
+    NoPayload,
+
+    /// The delete payload contains neither a predicate, nor deleted columns.
+    #[error("rpc delete request does not contain a predicate or deleted columns")]
+    NoPredicate,
+
+    /// The delete predicate could not be decoded.
+    #[error(transparent)]
+    Decode(DeletePredicateProtoError),
+
+    /// The ingester's [`IngestState`] returns [`IngestStateError`] instances if
+    /// set by a subsystem. See [`IngestState`] for documentation.
+    #[error(transparent)]
+    SystemState(IngestStateError),
+}
+
+impl From<RpcError> for tonic::Status {
+    fn from(e: RpcError) -> Self {
+        let code = match e {
+            RpcError::Decode(_) | RpcError::NoPayload | RpcError::NoPredicate => {
+                Code::InvalidArgument
+            }
+            RpcError::SystemState(IngestStateError::PersistSaturated) => Code::ResourceExhausted,
+            RpcError::SystemState(IngestStateError::DiskFull) => Code::ResourceExhausted,
+            RpcError::SystemState(IngestStateError::MemoryExhausted) => Code::ResourceExhausted,
+            RpcError::SystemState(IngestStateError::GracefulStop) => Code::FailedPrecondition,
+        };
+
+        Self::new(code, e.to_string())
+    }
+}
+
+/// A gRPC [`DeleteService`] handler.
+///
+/// This handler accepts predicate deletes from an upstream, and applies them
+/// to the provided [`DmlSink`].
+#[derive(Debug)]
+pub(crate) struct RpcDelete<T> {
+    sink: T,
+    timestamp: Arc<TimestampOracle>,
+    ingest_state: Arc<IngestState>,
+}
+
+impl<T> RpcDelete<T> {
+    /// Instantiate a new [`RpcDelete`] that pushes [`IngestOp`] instances
+    /// into `sink`.
+    pub(crate) fn new(
+        sink: T,
+        timestamp: Arc<TimestampOracle>,
+        ingest_state: Arc<IngestState>,
+    ) -> Self {
+        Self {
+            sink,
+            timestamp,
+            ingest_state,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<T> DeleteService for RpcDelete<T>
+where
+    T: DmlSink + 'static,
+{
+    /// Handle an RPC delete request.
+    async fn delete(
+        &self,
+        request: Request<proto::DeleteRequest>,
+    ) -> Result<Response<proto::DeleteResponse>, tonic::Status> {
+        let span_ctx: Option<SpanContext> = request.extensions().get().cloned();
+        let span = span_ctx.child_span("ingester delete");
+        let mut span_recorder = SpanRecorder::new(span);
+
+        // Deletes are subject to the same backpressure as writes - they are
+        // appended to the WAL and applied to the buffer in the same way.
+        self.ingest_state.read().map_err(RpcError::SystemState)?;
+
+        let payload = request.into_inner().payload.ok_or(RpcError::NoPayload)?;
+        if payload.predicate.is_none() && payload.column_names.is_empty() {
+            return Err(RpcError::NoPredicate)?;
+        }
+        let predicate = payload
+            .predicate
+            .map(DeletePredicate::try_from)
+            .transpose()
+            .map_err(RpcError::Decode)?;
+        let columns = payload.column_names;
+
+        let namespace_id = NamespaceId::new(payload.database_id);
+        let table_id = TableId::new(payload.table_id);
+
+        trace!(%namespace_id, %table_id, ?predicate, ?columns, "received rpc delete");
+
+        let op = DeleteOperation::new(
+            namespace_id,
+            table_id,
+            predicate,
+            columns,
+            self.timestamp.next(),
+            span_recorder.span().map(|span| span.ctx.clone()),
+        );
+
+        match self.sink.apply(IngestOp::Delete(op)).await {
+            Ok(()) => {
+                span_recorder.ok("applied delete");
+                Ok(Response::new(proto::DeleteResponse {}))
+            }
+            Err(e) => {
+                error!(error=%e, "failed to apply ingest operation");
+                span_recorder.error(e.to_string());
+                Err(e.into())?
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::{SequenceNumber, TimestampRange};
+
+    use super::*;
+    use crate::dml_sink::mock_sink::MockDmlSink;
+
+    const NAMESPACE_ID: NamespaceId = NamespaceId::new(42);
+    const TABLE_ID: TableId = TableId::new(24);
+
+    #[tokio::test]
+    async fn test_delete() {
+        let mock = Arc::new(MockDmlSink::default().with_apply_return(vec![Ok(())]));
+        let timestamp = Arc::new(TimestampOracle::new(0));
+        let handler = RpcDelete::new(
+            Arc::clone(&mock),
+            timestamp,
+            Arc::new(IngestState::default()),
+        );
+
+        let predicate = DeletePredicate {
+            range: TimestampRange::new(1, 2),
+            exprs: vec![],
+        };
+
+        handler
+            .delete(Request::new(proto::DeleteRequest {
+                payload: Some(proto::DeletePayload {
+                    database_id: NAMESPACE_ID.get(),
+                    table_id: TABLE_ID.get(),
+                    table_name: String::new(),
+                    predicate: Some((&predicate).into()),
+                    column_names: vec![],
+                }),
+            }))
+            .await
+            .expect("rpc delete should succeed");
+
+        assert_matches!(&*mock.get_calls(), [IngestOp::Delete(d)] => {
+            assert_eq!(d.namespace(), NAMESPACE_ID);
+            assert_eq!(d.table(), TABLE_ID);
+            assert_eq!(d.predicate().map(|p| &**p), Some(&predicate));
+            assert!(d.columns().is_empty());
+            assert_eq!(d.sequence_number(), SequenceNumber::new(1));
+        });
+    }
+
+    #[tokio::test]
+    async fn test_delete_columns() {
+        let mock = Arc::new(MockDmlSink::default().with_apply_return(vec![Ok(())]));
+        let handler = RpcDelete::new(
+            Arc::clone(&mock),
+            Arc::new(TimestampOracle::new(0)),
+            Arc::new(IngestState::default()),
+        );
+
+        handler
+            .delete(Request::new(proto::DeleteRequest {
+                payload: Some(proto::DeletePayload {
+                    database_id: NAMESPACE_ID.get(),
+                    table_id: TABLE_ID.get(),
+                    table_name: String::new(),
+                    predicate: None,
+                    column_names: vec!["platanos".to_string()],
+                }),
+            }))
+            .await
+            .expect("rpc delete should succeed");
+
+        assert_matches!(&*mock.get_calls(), [IngestOp::Delete(d)] => {
+            assert!(d.predicate().is_none());
+            assert_eq!(d.columns(), ["platanos"]);
+        });
+    }
+
+    #[tokio::test]
+    async fn test_delete_no_predicate() {
+        let mock = Arc::new(MockDmlSink::default());
+        let handler = RpcDelete::new(
+            Arc::clone(&mock),
+            Arc::new(TimestampOracle::new(0)),
+            Arc::new(IngestState::default()),
+        );
+
+        let err = handler
+            .delete(Request::new(proto::DeleteRequest {
+                payload: Some(proto::DeletePayload {
+                    database_id: NAMESPACE_ID.get(),
+                    table_id: TABLE_ID.get(),
+                    table_name: String::new(),
+                    predicate: None,
+                    column_names: vec![],
+                }),
+            }))
+            .await
+            .expect_err("rpc delete should fail");
+
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(mock.get_calls().is_empty());
+    }
+}
@@ -0,0 +1,478 @@
+//! Archival of fully persisted WAL segments to object storage.
+
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use async_trait::async_trait;
+use backoff::{Backoff, BackoffConfig, BackoffError};
+use futures::TryStreamExt;
+use metric::U64Counter;
+use object_store::{path::Path, DynObjectStore};
+use observability_deps::tracing::*;
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use wal::SegmentId;
+
+use super::reference_tracker::WalFileDeleter;
+
+/// The maximum amount of time spent retrying the upload of a segment before
+/// giving up on archiving it.
+const ARCHIVE_RETRY_DEADLINE: Duration = Duration::from_secs(5 * 60);
+
+/// A [`WalFileDeleter`] decorator that uploads each closed WAL segment to
+/// object storage before deleting it from the local disk.
+///
+/// Segments are archived under `<prefix>/<ingester ID>/`, named by
+/// [`wal::archived_segment_name()`].
+///
+/// The upload and subsequent deletion run in a background task, so that a
+/// slow or unavailable object store does not stall the caller. Uploads are
+/// retried for at most [`ARCHIVE_RETRY_DEADLINE`], after which the segment is
+/// deleted without being archived, as a fully persisted segment must not be
+/// kept around (and replayed) indefinitely.
+#[derive(Debug, Clone)]
+pub(crate) struct WalArchiver {
+    wal: Arc<wal::Wal>,
+    store: Arc<DynObjectStore>,
+
+    /// The path archived segments of this ingester instance are written
+    /// under.
+    prefix: Path,
+
+    backoff_config: BackoffConfig,
+
+    archived_segments: U64Counter,
+    archived_bytes: U64Counter,
+    archive_errors: U64Counter,
+}
+
+impl WalArchiver {
+    pub(crate) fn new(
+        wal: Arc<wal::Wal>,
+        store: Arc<DynObjectStore>,
+        prefix: Path,
+        metrics: &metric::Registry,
+    ) -> Self {
+        let archived_segments = metrics
+            .register_metric::<U64Counter>(
+                "ingester_wal_archived_segments",
+                "number of closed wal segments uploaded to object storage",
+            )
+            .recorder(&[]);
+        let archived_bytes = metrics
+            .register_metric::<U64Counter>(
+                "ingester_wal_archived_bytes",
+                "number of bytes of closed wal segments uploaded to object storage",
+            )
+            .recorder(&[]);
+        let archive_errors = metrics
+            .register_metric::<U64Counter>(
+                "ingester_wal_archive_errors",
+                "number of closed wal segments deleted without being archived",
+            )
+            .recorder(&[]);
+
+        Self {
+            wal,
+            store,
+            prefix,
+            backoff_config: BackoffConfig {
+                deadline: Some(ARCHIVE_RETRY_DEADLINE),
+                ..Default::default()
+            },
+            archived_segments,
+            archived_bytes,
+            archive_errors,
+        }
+    }
+
+    /// Upload the closed segment `id` to object storage, and then delete it
+    /// from the WAL.
+    async fn archive_and_delete(&self, id: SegmentId) {
+        if let Err(error) = self.archive(id).await {
+            self.archive_errors.inc(1);
+            error!(%id, %error, "failed to archive wal segment, deleting it anyway");
+        }
+        self.wal.delete_file(id).await
+    }
+
+    /// Upload the closed segment `id` to object storage, retrying failed
+    /// uploads until the backoff deadline is reached.
+    async fn archive(&self, id: SegmentId) -> Result<(), ArchiveError> {
+        let Some(segment) = self.wal.closed_segment(id) else {
+            warn!(%id, "wal segment not found, skipping archival");
+            return Ok(());
+        };
+        let path = segment.path();
+
+        let metadata = tokio::fs::metadata(path)
+            .await
+            .map_err(ArchiveError::Read)?;
+        // A segment is last modified when it is closed.
+        let closed_at = metadata.modified().unwrap_or_else(|_| SystemTime::now());
+        let location = self
+            .prefix
+            .child(wal::archived_segment_name(id, unix_nanos(closed_at)));
+
+        Backoff::new(&self.backoff_config)
+            .retry_all_errors("archive wal segment", || {
+                let location = &location;
+                async move { self.upload(path, location).await }
+            })
+            .await
+            .map_err(|e| match e {
+                BackoffError::DeadlineExceeded { source, .. } => source,
+            })?;
+
+        self.archived_segments.inc(1);
+        self.archived_bytes.inc(metadata.len());
+
+        info!(%id, %location, size = metadata.len(), "archived wal segment");
+        Ok(())
+    }
+
+    /// Stream the file at `path` to `location` using a multipart upload.
+    async fn upload(&self, path: &std::path::Path, location: &Path) -> Result<(), ArchiveError> {
+        let mut file = tokio::fs::File::open(path)
+            .await
+            .map_err(ArchiveError::Read)?;
+
+        let (upload_id, mut writer) = self
+            .store
+            .put_multipart(location)
+            .await
+            .map_err(ArchiveError::Upload)?;
+
+        let res = async {
+            tokio::io::copy(&mut file, &mut writer).await?;
+            writer.shutdown().await
+        }
+        .await;
+
+        if let Err(error) = res {
+            if let Err(abort_error) = self.store.abort_multipart(location, &upload_id).await {
+                warn!(%location, error=%abort_error, "failed to abort wal segment upload");
+            }
+            return Err(ArchiveError::Write(error));
+        }
+
+        Ok(())
+    }
+}
+
+/// Errors archiving a closed WAL segment.
+#[derive(Debug, Error)]
+enum ArchiveError {
+    #[error("failed to read wal segment: {0}")]
+    Read(std::io::Error),
+
+    #[error("failed to start wal segment upload: {0}")]
+    Upload(object_store::Error),
+
+    #[error("failed to upload wal segment: {0}")]
+    Write(std::io::Error),
+}
+
+#[async_trait]
+impl WalFileDeleter for WalArchiver {
+    async fn delete_file(&self, id: SegmentId) {
+        let this = self.clone();
+        tokio::spawn(async move { this.archive_and_delete(id).await });
+    }
+}
+
+/// Periodically deletes archived WAL segments that were closed longer ago
+/// than the configured retention period.
+///
+/// All archived segments under the prefix are considered, including those
+/// archived by previous instances of the ingester.
+#[derive(Debug)]
+pub(crate) struct WalArchiveRetention {
+    store: Arc<DynObjectStore>,
+    prefix: Path,
+    retention: Duration,
+
+    deleted_segments: U64Counter,
+}
+
+impl WalArchiveRetention {
+    pub(crate) fn new(
+        store: Arc<DynObjectStore>,
+        prefix: Path,
+        retention: Duration,
+        metrics: &metric::Registry,
+    ) -> Self {
+        let deleted_segments = metrics
+            .register_metric::<U64Counter>(
+                "ingester_wal_archive_expired_segments",
+                "number of archived wal segments deleted after the retention period elapsed",
+            )
+            .recorder(&[]);
+
+        Self {
+            store,
+            prefix,
+            retention,
+            deleted_segments,
+        }
+    }
+
+    /// Delete expired archived segments every `interval`, forever.
+    pub(crate) async fn run(self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+            if let Err(error) = self.sweep(SystemTime::now()).await {
+                warn!(%error, "failed to delete expired archived wal segments");
+            }
+        }
+    }
+
+    /// Delete all archived segments closed before `now` minus the retention
+    /// period, returning the number of segments deleted.
+    pub(crate) async fn sweep(&self, now: SystemTime) -> Result<usize, object_store::Error> {
+        let cutoff = unix_nanos(now.checked_sub(self.retention).unwrap_or(UNIX_EPOCH));
+
+        let expired = self
+            .store
+            .list(Some(&self.prefix))
+            .await?
+            .try_filter_map(|meta| async move {
+                Ok(meta
+                    .location
+                    .filename()
+                    .and_then(wal::parse_archived_segment_name)
+                    .filter(|(_, closed_at)| *closed_at < cutoff)
+                    .map(|_| meta.location))
+            })
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        for location in &expired {
+            self.store.delete(location).await?;
+            self.deleted_segments.inc(1);
+            debug!(%location, "deleted expired archived wal segment");
+        }
+
+        if !expired.is_empty() {
+            info!(
+                n_deleted = expired.len(),
+                "deleted expired archived wal segments"
+            );
+        }
+
+        Ok(expired.len())
+    }
+}
+
+/// Returns the number of nanoseconds between the epoch and `t`.
+fn unix_nanos(t: SystemTime) -> i64 {
+    t.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use generated_types::influxdata::iox::wal::v1::sequenced_wal_op::Op as WalOp;
+    use metric::assert_counter;
+    use object_store::{local::LocalFileSystem, memory::InMemory};
+
+    use super::*;
+    use crate::{
+        dml_payload::encode::encode_write_op,
+        test_util::{
+            make_write_op, ARBITRARY_NAMESPACE_ID, ARBITRARY_PARTITION_KEY, ARBITRARY_TABLE_ID,
+            ARBITRARY_TABLE_NAME,
+        },
+    };
+
+    async fn list(store: &DynObjectStore, prefix: &Path) -> Vec<Path> {
+        let mut paths = store
+            .list(Some(prefix))
+            .await
+            .unwrap()
+            .map_ok(|meta| meta.location)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        paths.sort();
+        paths
+    }
+
+    /// Write an op to `wal` and rotate it, returning the closed segment.
+    async fn closed_segment(wal: &wal::Wal) -> wal::ClosedSegment {
+        let op = make_write_op(
+            &ARBITRARY_PARTITION_KEY,
+            ARBITRARY_NAMESPACE_ID,
+            &ARBITRARY_TABLE_NAME,
+            ARBITRARY_TABLE_ID,
+            42,
+            &format!("{},city=Madrid temp=35 4242424242", &*ARBITRARY_TABLE_NAME),
+            None,
+        );
+        wal.write_op(wal::SequencedWalOp {
+            table_write_sequence_numbers: [(ARBITRARY_TABLE_ID, 42)].into_iter().collect(),
+            op: WalOp::Write(encode_write_op(ARBITRARY_NAMESPACE_ID, &op)),
+        })
+        .changed()
+        .await
+        .unwrap();
+        let (closed, _) = wal.rotate().unwrap();
+        closed
+    }
+
+    /// Wait for the segment `id` to be deleted from `wal`.
+    async fn wait_for_delete(wal: &wal::Wal, id: SegmentId) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while wal.closed_segment(id).is_some() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("wal segment was not deleted");
+    }
+
+    #[tokio::test]
+    async fn test_archive_before_delete() {
+        let dir = tempfile::tempdir().unwrap();
+        let wal = wal::Wal::new(dir.path()).await.unwrap();
+        let closed = closed_segment(&wal).await;
+        let want = std::fs::read(closed.path()).unwrap();
+
+        let store: Arc<DynObjectStore> = Arc::new(InMemory::default());
+        let prefix = Path::from("wal_archive").child("ingester");
+        let metrics = metric::Registry::default();
+        let archiver = WalArchiver::new(
+            Arc::clone(&wal),
+            Arc::clone(&store),
+            prefix.clone(),
+            &metrics,
+        );
+
+        // The archival runs in the background.
+        archiver.delete_file(closed.id()).await;
+        wait_for_delete(&wal, closed.id()).await;
+
+        // The segment was removed from the WAL...
+        assert!(wal.closed_segment(closed.id()).is_none());
+        assert!(!closed.path().exists());
+
+        // ...after being uploaded to object storage.
+        let paths = list(&*store, &prefix).await;
+        assert_matches::assert_matches!(paths.as_slice(), [p] => {
+            let (id, _) = wal::parse_archived_segment_name(p.filename().unwrap()).unwrap();
+            assert_eq!(id, closed.id());
+
+            let got = store.get(p).await.unwrap().bytes().await.unwrap();
+            assert_eq!(got, want);
+        });
+
+        assert_counter!(
+            metrics,
+            U64Counter,
+            "ingester_wal_archived_segments",
+            value = 1,
+        );
+        assert_counter!(
+            metrics,
+            U64Counter,
+            "ingester_wal_archived_bytes",
+            value = want.len() as u64,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_after_archive_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let wal = wal::Wal::new(dir.path()).await.unwrap();
+        let closed = closed_segment(&wal).await;
+
+        // An object store rooted at a regular file, so that all uploads fail.
+        let store_dir = tempfile::tempdir().unwrap();
+        let root = store_dir.path().join("not_a_dir");
+        std::fs::write(&root, b"bananas").unwrap();
+        let store: Arc<DynObjectStore> = Arc::new(LocalFileSystem::new_with_prefix(&root).unwrap());
+
+        let metrics = metric::Registry::default();
+        let mut archiver =
+            WalArchiver::new(Arc::clone(&wal), store, Path::from("wal_archive"), &metrics);
+        archiver.backoff_config = BackoffConfig {
+            init_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+            base: 1.0,
+            deadline: Some(Duration::from_millis(50)),
+        };
+
+        archiver.archive_and_delete(closed.id()).await;
+
+        // The segment is deleted once the upload retries are exhausted.
+        assert!(wal.closed_segment(closed.id()).is_none());
+        assert!(!closed.path().exists());
+
+        assert_counter!(
+            metrics,
+            U64Counter,
+            "ingester_wal_archived_segments",
+            value = 0,
+        );
+        assert_counter!(
+            metrics,
+            U64Counter,
+            "ingester_wal_archive_errors",
+            value = 1,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retention_sweep() {
+        let store: Arc<DynObjectStore> = Arc::new(InMemory::default());
+        let prefix = Path::from("wal_archive");
+        let now = SystemTime::now();
+
+        // Two segments from different ingester instances, and one unrelated
+        // object that is never deleted.
+        let old = prefix.child("a").child(wal::archived_segment_name(
+            SegmentId::new(1),
+            unix_nanos(now - Duration::from_secs(7200)),
+        ));
+        let new = prefix.child("b").child(wal::archived_segment_name(
+            SegmentId::new(2),
+            unix_nanos(now - Duration::from_secs(60)),
+        ));
+        let other = prefix.child("a").child("bananas");
+        for p in [&old, &new, &other] {
+            store.put(p, Bytes::from_static(b"data")).await.unwrap();
+        }
+
+        let metrics = metric::Registry::default();
+        let retention = WalArchiveRetention::new(
+            Arc::clone(&store),
+            prefix.clone(),
+            Duration::from_secs(3600),
+            &metrics,
+        );
+
+        assert_eq!(retention.sweep(now).await.unwrap(), 1);
+        assert_eq!(list(&*store, &prefix).await, {
+            let mut want = vec![new, other];
+            want.sort();
+            want
+        });
+
+        // Nothing else expires.
+        assert_eq!(retention.sweep(now).await.unwrap(), 0);
+
+        assert_counter!(
+            metrics,
+            U64Counter,
+            "ingester_wal_archive_expired_segments",
+            value = 1,
+        );
+    }
+}
@@ -4,6 +4,7 @@
 //! [`DmlSink`]: crate::dml_sink::DmlSink
 //! [`IngestOp`]: crate::dml_payload::IngestOp
 
+pub(crate) mod archiver;
 pub(crate) mod disk_full_protection;
 pub(crate) mod reference_tracker;
 pub(crate) mod rotate_task;
@@ -3,12 +3,18 @@ use std::{fmt::Debug, sync::Arc};
 use async_trait::async_trait;
 use wal::SegmentId;
 
+use crate::persist::completion_observer::MaybeLayer;
+
 /// An abstraction defining the ability of an implementer to delete WAL segment
 /// files by ID.
 #[async_trait]
-pub(crate) trait WalFileDeleter: Debug + Send + Sync + 'static {
+pub trait WalFileDeleter: Debug + Send + Sync + 'static {
     /// Delete the WAL segment with the specified [`SegmentId`], or panic if
     /// deletion fails.
+    ///
+    /// Implementations may complete the deletion in the background after
+    /// returning, but must not block the caller for long, as it is called
+    /// from the reference tracker actor loop.
     async fn delete_file(&self, id: SegmentId);
 }
 
@@ -18,3 +24,17 @@ impl WalFileDeleter for Arc<wal::Wal> {
         self.delete(id).await.expect("failed to drop wal segment");
     }
 }
+
+#[async_trait]
+impl<T, U> WalFileDeleter for MaybeLayer<T, U>
+where
+    T: WalFileDeleter,
+    U: WalFileDeleter,
+{
+    async fn delete_file(&self, id: SegmentId) {
+        match self {
+            Self::With(v) => v.delete_file(id).await,
+            Self::Without(v) => v.delete_file(id).await,
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use data_types::{sequence_number_set::SequenceNumberSet, TableId};
-use generated_types::influxdata::iox::wal::v1::sequenced_wal_op::Op;
+use generated_types::influxdata::iox::{delete::v1::DeletePayload, wal::v1::sequenced_wal_op::Op};
 use std::{collections::HashMap, sync::Arc, time::Duration};
 use tokio::sync::watch::Receiver;
 use wal::{SequencedWalOp, WriteResult};
@@ -71,6 +71,15 @@ where
 
         let set = op.sequence_number_set();
 
+        // A delete retains its sequence number (and potentially those of the
+        // writes it removed from the buffer) until all the partitions it was
+        // applied to release their reference, instead of being persisted
+        // directly.
+        let delete_ref = match &op {
+            IngestOp::Write(_) => None,
+            IngestOp::Delete(d) => Some(Arc::clone(d.reference())),
+        };
+
         // Pass it to the inner handler while we wait for the write to be made
         // durable.
         //
@@ -96,8 +105,18 @@ where
             res.map_err(Into::into)
         })
         .await;
-        if inner_result.is_err() {
-            self.notifier_handle.notify_failed_write_buffer(set).await;
+        match delete_ref {
+            Some(delete_ref) => {
+                // Release the reference held by this applier - if no partition
+                // acquired a reference, the delete is immediately released.
+                if let Some(set) = delete_ref.release() {
+                    self.notifier_handle.notify_failed_write_buffer(set).await;
+                }
+            }
+            None if inner_result.is_err() => {
+                self.notifier_handle.notify_failed_write_buffer(set).await;
+            }
+            None => {}
         }
         inner_result?;
 
@@ -132,6 +151,18 @@ impl WalAppender for Arc<wal::Wal> {
                     partition_sequence_numbers,
                 )
             }
+            IngestOp::Delete(d) => (
+                Op::Delete(DeletePayload {
+                    database_id: namespace_id.get(),
+                    table_name: String::new(),
+                    table_id: d.table().get(),
+                    predicate: d.predicate().map(|p| p.as_ref().into()),
+                    column_names: d.columns().to_vec(),
+                }),
+                [(d.table(), d.sequence_number().get())]
+                    .into_iter()
+                    .collect(),
+            ),
         };
 
         self.write_op(SequencedWalOp {
@@ -370,4 +401,71 @@ mod tests {
             .collect::<Vec<_>>()
         );
     }
+
+    /// A delete that was not applied to any buffered data must be released
+    /// immediately, and encoded in the WAL with its table's sequence number.
+    #[tokio::test]
+    async fn test_delete_released() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let predicate = data_types::DeletePredicate {
+            range: data_types::TimestampRange::new(1, 2),
+            exprs: vec![],
+        };
+        let op = crate::dml_payload::delete::DeleteOperation::new(
+            ARBITRARY_NAMESPACE_ID,
+            ARBITRARY_TABLE_ID,
+            Some(predicate.clone()),
+            vec!["bananas".to_string()],
+            SequenceNumber::new(42),
+            None,
+        );
+
+        {
+            let inner = Arc::new(MockDmlSink::default().with_apply_return(vec![Ok(())]));
+            let wal = Wal::new(dir.path())
+                .await
+                .expect("failed to initialise WAL");
+            let notifier_handle = Arc::new(mock::MockUnbufferedWriteNotifier::default());
+
+            let wal_sink = WalSink::new(Arc::clone(&inner), wal, Arc::clone(&notifier_handle));
+
+            wal_sink
+                .apply(IngestOp::Delete(op))
+                .await
+                .expect("wal should not error");
+
+            assert_eq!(inner.get_calls().len(), 1);
+            assert_eq!(
+                notifier_handle.calls(),
+                [SequenceNumberSet::from_iter([SequenceNumber::new(42)])]
+            );
+        }
+
+        let wal = Wal::new(dir.path())
+            .await
+            .expect("failed to initialise WAL");
+        let files = wal.closed_segments();
+        let file = assert_matches!(&*files, [f] => f, "expected 1 file");
+        let ops: Vec<SequencedWalOp> = wal
+            .reader_for_segment(file.id())
+            .expect("failed to obtain reader for WAL segment")
+            .flat_map(|batch| batch.expect("failed to read WAL op batch"))
+            .collect();
+
+        let read_op = assert_matches!(&*ops, [op] => op, "expected 1 DML operation");
+        assert_eq!(
+            read_op.table_write_sequence_numbers,
+            [(ARBITRARY_TABLE_ID, 42)]
+                .into_iter()
+                .collect::<std::collections::HashMap<TableId, u64>>()
+        );
+        let payload = assert_matches!(&read_op.op, Op::Delete(d) => d, "expected delete WAL entry");
+        assert_eq!(payload.table_id, ARBITRARY_TABLE_ID.get());
+        assert_eq!(
+            data_types::DeletePredicate::try_from(payload.predicate.clone().unwrap()).unwrap(),
+            predicate
+        );
+        assert_eq!(payload.column_names, ["bananas"]);
+    }
 }
@@ -81,7 +81,7 @@ async fn write_persist() {
         assert_eq!(f.to_delete, None);
         assert_eq!(f.row_count, 1);
         assert_eq!(f.column_set.len(), 3);
-        assert_eq!(f.max_l0_created_at, f.created_at);
+        assert!(f.max_l0_created_at <= f.created_at);
 
         (ParquetFilePath::from(f), f.file_size_bytes)
     });
@@ -0,0 +1,23 @@
+-- The tombstone table left over from the sharded write path is keyed on
+-- (shard, sequence number), neither of which exist any more. Replace it with
+-- a table recording predicate deletes against a table, along with the time the
+-- delete was committed.
+--
+-- Any rows in the old table were never applied by the current query path and
+-- are dropped.
+DROP TABLE IF EXISTS tombstone;
+
+CREATE TABLE IF NOT EXISTS tombstone
+(
+    id                   BIGSERIAL PRIMARY KEY,
+    table_id             BIGINT NOT NULL
+        CONSTRAINT tombstone_table_id_fkey
+            REFERENCES table_name (id)
+            ON DELETE CASCADE,
+    min_time             BIGINT NOT NULL,
+    max_time             BIGINT NOT NULL,
+    serialized_predicate TEXT   NOT NULL,
+    created_at           BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS tombstone_table_id_idx ON tombstone (table_id);
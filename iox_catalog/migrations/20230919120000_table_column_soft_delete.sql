@@ -0,0 +1,19 @@
+-- Add a soft-deletion timestamp to the "table_name" and "column_name" tables.
+--
+-- Soft-deleted rows are renamed when they are deleted, so the existing
+-- uniqueness constraints on the names are left in place.
+ALTER TABLE
+    table_name
+ADD
+    COLUMN deleted_at BIGINT DEFAULT NULL;
+
+ALTER TABLE
+    column_name
+ADD
+    COLUMN deleted_at BIGINT DEFAULT NULL;
+
+-- Allow the garbage collector to find the tables whose files must be
+-- reclaimed without scanning all tables.
+CREATE INDEX IF NOT EXISTS table_name_deleted_at_idx
+ON table_name (deleted_at)
+WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,17 @@
+-- Add per-namespace limits on the queries run by the querier.
+--
+-- NULL means the namespace is only subject to the limits of the querier.
+ALTER TABLE
+    namespace
+ADD
+    COLUMN max_concurrent_queries INT DEFAULT NULL;
+
+ALTER TABLE
+    namespace
+ADD
+    COLUMN max_query_memory_bytes BIGINT DEFAULT NULL;
+
+ALTER TABLE
+    namespace
+ADD
+    COLUMN query_timeout_ms BIGINT DEFAULT NULL;
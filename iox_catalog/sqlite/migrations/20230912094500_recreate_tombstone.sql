@@ -0,0 +1,22 @@
+-- The tombstone table left over from the sharded write path is keyed on
+-- (shard, sequence number), neither of which exist any more. Replace it with
+-- a table recording predicate deletes against a table, along with the time the
+-- delete was committed.
+DROP TABLE IF EXISTS processed_tombstone;
+DROP TABLE IF EXISTS tombstone;
+
+CREATE TABLE IF NOT EXISTS tombstone
+(
+    id                   INTEGER
+        CONSTRAINT tombstone_pkey
+            PRIMARY KEY AUTOINCREMENT,
+    table_id             NUMERIC NOT NULL
+        REFERENCES table_name
+            ON DELETE CASCADE,
+    min_time             NUMERIC NOT NULL,
+    max_time             NUMERIC NOT NULL,
+    serialized_predicate TEXT    NOT NULL,
+    created_at           NUMERIC NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS tombstone_table_id_idx ON tombstone (table_id);
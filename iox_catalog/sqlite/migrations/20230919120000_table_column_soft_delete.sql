@@ -0,0 +1,17 @@
+-- Add a soft-deletion timestamp to the "table_name" and "column_name" tables.
+--
+-- Soft-deleted rows are renamed when they are deleted, so the existing
+-- uniqueness constraints on the names are left in place.
+ALTER TABLE
+    table_name
+ADD
+    COLUMN deleted_at numeric DEFAULT NULL;
+
+ALTER TABLE
+    column_name
+ADD
+    COLUMN deleted_at numeric DEFAULT NULL;
+
+CREATE INDEX IF NOT EXISTS table_name_deleted_at_idx
+ON table_name (deleted_at)
+WHERE deleted_at IS NOT NULL;
@@ -3,11 +3,11 @@
 use async_trait::async_trait;
 use data_types::{
     partition_template::{NamespacePartitionTemplateOverride, TablePartitionTemplateOverride},
-    Column, ColumnType, ColumnsByName, CompactionLevel, MaxColumnsPerTable, MaxTables, Namespace,
-    NamespaceId, NamespaceName, NamespaceSchema, NamespaceServiceProtectionLimitsOverride,
-    ParquetFile, ParquetFileId, ParquetFileParams, Partition, PartitionHashId, PartitionId,
-    PartitionKey, SkippedCompaction, SortedColumnSet, Table, TableId, TableSchema, Timestamp,
-    TransitionPartitionId,
+    Column, ColumnType, ColumnsByName, CompactionLevel, MaxColumnsPerTable, MaxConcurrentQueries,
+    MaxQueryMemoryBytes, MaxTables, Namespace, NamespaceId, NamespaceName, NamespaceSchema,
+    NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId, ParquetFileParams,
+    Partition, PartitionHashId, PartitionId, PartitionKey, QueryTimeout, SkippedCompaction,
+    SortedColumnSet, Table, TableId, TableSchema, Timestamp, Tombstone, TransitionPartitionId,
 };
 use iox_time::TimeProvider;
 use snafu::{OptionExt, Snafu};
@@ -23,6 +23,19 @@ pub const MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION: i64 = 1_000;
 /// Maximum number of files touched by [`ParquetFileRepo::delete_old_ids_only`] at a time.
 pub const MAX_PARQUET_FILES_SELECTED_ONCE_FOR_DELETE: i64 = 10_000;
 
+/// Separator placed between the original name and the row ID when renaming a soft-deleted table
+/// or column.
+///
+/// Table names are unique per namespace and column names are unique per table. Soft-deleting a
+/// table or column renames it to `<name>#deleted#<id>` so that the name can be reused.
+pub const SOFT_DELETED_NAME_SEPARATOR: &str = "#deleted#";
+
+/// Reason reported when deleting the time column of a table.
+pub(crate) const DELETE_TIME_COLUMN_REASON: &str = "the time column is required";
+
+/// Reason reported when deleting a column that is part of a partition sort key.
+pub(crate) const DELETE_SORT_KEY_COLUMN_REASON: &str = "the column is part of a partition sort key";
+
 /// An error wrapper detailing the reason for a compare-and-swap failure.
 #[derive(Debug)]
 pub enum CasFailure<T> {
@@ -77,12 +90,28 @@ pub enum Error {
     #[snafu(display("namespace {} not found", id))]
     NamespaceNotFoundById { id: NamespaceId },
 
+    #[snafu(display("namespace {} is not deleted", name))]
+    NamespaceNotDeleted { name: String },
+
+    #[snafu(display("namespace {} was deleted too long ago to be restored", name))]
+    NamespaceUndeleteExpired { name: String },
+
     #[snafu(display("table {} not found", id))]
     TableNotFound { id: TableId },
 
     #[snafu(display("table {} not found", name))]
     TableNotFoundByName { name: String },
 
+    #[snafu(display("column {} not found in table {}", name, table_id))]
+    ColumnNotFoundByName { name: String, table_id: TableId },
+
+    #[snafu(display("column {} in table {} cannot be deleted: {}", name, table_id, reason))]
+    ColumnDeleteNotAllowed {
+        name: String,
+        table_id: TableId,
+        reason: &'static str,
+    },
+
     #[snafu(display("partition {} not found", id))]
     PartitionNotFound { id: TransitionPartitionId },
 
@@ -249,6 +278,9 @@ pub trait RepoCollection: Send + Sync + Debug {
 
     /// Repository for [Parquet files](data_types::ParquetFile).
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo;
+
+    /// Repository for [tombstones](data_types::Tombstone).
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo;
 }
 
 /// Functions for working with namespaces in the catalog
@@ -292,6 +324,18 @@ pub trait NamespaceRepo: Send + Sync {
     /// Soft-delete a namespace by name
     async fn soft_delete(&mut self, name: &str) -> Result<()>;
 
+    /// Restore the soft-deleted namespace `name`, returning the restored row.
+    ///
+    /// Only namespaces deleted at or after `deleted_after` can be restored, returning
+    /// [`Error::NamespaceUndeleteExpired`] otherwise, as the parquet files of namespaces deleted
+    /// before then may have been flagged for deletion by
+    /// [`ParquetFileRepo::flag_for_delete_by_deleted_namespace`].
+    async fn undelete(&mut self, name: &str, deleted_after: Timestamp) -> Result<Namespace>;
+
+    /// Rename the namespace `name` (which must not be soft-deleted) to `new_name`, returning the
+    /// updated row.
+    async fn rename(&mut self, name: &str, new_name: &NamespaceName<'_>) -> Result<Namespace>;
+
     /// Update the limit on the number of tables that can exist per namespace.
     async fn update_table_limit(&mut self, name: &str, new_max: MaxTables) -> Result<Namespace>;
 
@@ -301,6 +345,28 @@ pub trait NamespaceRepo: Send + Sync {
         name: &str,
         new_max: MaxColumnsPerTable,
     ) -> Result<Namespace>;
+
+    /// Update the limit on the number of queries that can run concurrently against a given
+    /// namespace.
+    async fn update_concurrent_query_limit(
+        &mut self,
+        name: &str,
+        new_max: MaxConcurrentQueries,
+    ) -> Result<Namespace>;
+
+    /// Update the limit on the memory a single query against a given namespace can reserve.
+    async fn update_query_memory_limit(
+        &mut self,
+        name: &str,
+        new_max: MaxQueryMemoryBytes,
+    ) -> Result<Namespace>;
+
+    /// Update the limit on the wall-clock time a single query against a given namespace can run.
+    async fn update_query_timeout(
+        &mut self,
+        name: &str,
+        timeout: QueryTimeout,
+    ) -> Result<Namespace>;
 }
 
 /// Functions for working with tables in the catalog
@@ -316,20 +382,31 @@ pub trait TableRepo: Send + Sync {
     ) -> Result<Table>;
 
     /// get table by ID
+    ///
+    /// Soft-deleted tables are returned as well, under the name they were renamed to when they
+    /// were deleted.
     async fn get_by_id(&mut self, table_id: TableId) -> Result<Option<Table>>;
 
-    /// get table by namespace ID and name
+    /// get table by namespace ID and name, excluding soft-deleted tables
     async fn get_by_namespace_and_name(
         &mut self,
         namespace_id: NamespaceId,
         name: &str,
     ) -> Result<Option<Table>>;
 
-    /// Lists all tables in the catalog for the given namespace id.
+    /// Lists all tables in the catalog for the given namespace id, excluding soft-deleted tables.
     async fn list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<Table>>;
 
-    /// List all tables.
+    /// List all tables, excluding soft-deleted tables.
     async fn list(&mut self) -> Result<Vec<Table>>;
+
+    /// Soft-delete the table `name` in the given namespace, returning the deleted row.
+    ///
+    /// The table is renamed (see [`SOFT_DELETED_NAME_SEPARATOR`]) so that a new table with the
+    /// same name can be created, and no longer counts towards the namespace's table limit. Its
+    /// parquet files are flagged for deletion by
+    /// [`ParquetFileRepo::flag_for_delete_by_deleted_table`].
+    async fn soft_delete(&mut self, namespace_id: NamespaceId, name: &str) -> Result<Table>;
 }
 
 /// Functions for working with columns in the catalog
@@ -360,14 +437,24 @@ pub trait ColumnRepo: Send + Sync {
         columns: HashMap<&str, ColumnType>,
     ) -> Result<Vec<Column>>;
 
-    /// Lists all columns in the passed in namespace id.
+    /// Lists all columns in the passed in namespace id, excluding soft-deleted tables and
+    /// columns.
     async fn list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<Column>>;
 
-    /// List all columns for the given table ID.
+    /// List all columns for the given table ID, excluding soft-deleted columns.
     async fn list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Column>>;
 
-    /// List all columns.
+    /// List all columns, excluding soft-deleted tables and columns.
     async fn list(&mut self) -> Result<Vec<Column>>;
+
+    /// Soft-delete the column `name` of the given table, returning the deleted row.
+    ///
+    /// The column is renamed (see [`SOFT_DELETED_NAME_SEPARATOR`]) so that it can be re-created,
+    /// possibly with a different type, and no longer counts towards the namespace's column limit.
+    ///
+    /// Returns [`Error::ColumnDeleteNotAllowed`] for the time column and for columns that are
+    /// part of the sort key of any partition of the table, as existing data is sorted by them.
+    async fn soft_delete(&mut self, table_id: TableId, name: &str) -> Result<Column>;
 }
 
 /// Functions for working with IOx partitions in the catalog. These are how IOx splits up
@@ -402,6 +489,15 @@ pub trait PartitionRepo: Send + Sync {
     /// return the partitions by table id
     async fn list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Partition>>;
 
+    /// return the partitions with the partition key `key` of any of the tables `table_ids`
+    ///
+    /// the output order is undefined.
+    async fn list_by_table_ids_and_key(
+        &mut self,
+        table_ids: &[TableId],
+        key: &PartitionKey,
+    ) -> Result<Vec<Partition>>;
+
     /// return all partitions IDs
     async fn list_ids(&mut self) -> Result<Vec<PartitionId>>;
 
@@ -493,6 +589,22 @@ pub trait ParquetFileRepo: Send + Sync {
     /// Flag all parquet files for deletion that are older than their namespace's retention period.
     async fn flag_for_delete_by_retention(&mut self) -> Result<Vec<ParquetFileId>>;
 
+    /// Flag all parquet files for deletion that belong to a soft-deleted table.
+    ///
+    /// Like [`Self::flag_for_delete_by_retention`], this touches at most
+    /// [`MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION`] files per call.
+    async fn flag_for_delete_by_deleted_table(&mut self) -> Result<Vec<ParquetFileId>>;
+
+    /// Flag all parquet files for deletion that belong to a namespace soft-deleted before
+    /// `deleted_before`.
+    ///
+    /// Like [`Self::flag_for_delete_by_retention`], this touches at most
+    /// [`MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION`] files per call.
+    async fn flag_for_delete_by_deleted_namespace(
+        &mut self,
+        deleted_before: Timestamp,
+    ) -> Result<Vec<ParquetFileId>>;
+
     /// List all parquet files within a given namespace that are NOT marked as
     /// [`to_delete`](ParquetFile::to_delete).
     async fn list_by_namespace_not_to_delete(
@@ -544,6 +656,27 @@ pub trait ParquetFileRepo: Send + Sync {
     ) -> Result<Vec<ParquetFileId>>;
 }
 
+/// Functions for working with tombstones in the catalog
+#[async_trait]
+pub trait TombstoneRepo: Send + Sync {
+    /// Record a predicate delete against `table_id`, covering rows with a
+    /// timestamp in the range `[min_time, max_time)` that match
+    /// `serialized_predicate`.
+    ///
+    /// The [`Tombstone::created_at`] timestamp is assigned by the catalog.
+    async fn create(
+        &mut self,
+        table_id: TableId,
+        min_time: Timestamp,
+        max_time: Timestamp,
+        serialized_predicate: &str,
+    ) -> Result<Tombstone>;
+
+    /// List all tombstones recorded against the given table, ordered by
+    /// creation.
+    async fn list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Tombstone>>;
+}
+
 /// Gets the namespace schema including all tables and columns.
 pub async fn get_schema_by_id<R>(
     id: NamespaceId,
@@ -775,7 +908,10 @@ pub(crate) mod test_helpers {
     use super::*;
     use ::test_helpers::assert_error;
     use assert_matches::assert_matches;
-    use data_types::{ColumnId, CompactionLevel, MaxColumnsPerTable, MaxTables};
+    use data_types::{
+        ColumnId, CompactionLevel, MaxColumnsPerTable, MaxConcurrentQueries, MaxQueryMemoryBytes,
+        MaxTables, NamespaceQueryLimits, QueryTimeout,
+    };
     use futures::Future;
     use generated_types::influxdata::iox::partition_template::v1 as proto;
     use metric::{Attributes, DurationHistogram, Metric};
@@ -795,6 +931,11 @@ pub(crate) mod test_helpers {
         test_parquet_file_delete_broken(clean_state().await).await;
         test_update_to_compaction_level_1(clean_state().await).await;
         test_list_by_partiton_not_to_delete(clean_state().await).await;
+        test_tombstone(clean_state().await).await;
+        test_table_soft_delete(clean_state().await).await;
+        test_column_soft_delete(clean_state().await).await;
+        test_namespace_undelete(clean_state().await).await;
+        test_namespace_rename(clean_state().await).await;
         test_list_schemas(clean_state().await).await;
         test_list_schemas_soft_deleted_rows(clean_state().await).await;
         test_delete_namespace(clean_state().await).await;
@@ -818,6 +959,10 @@ pub(crate) mod test_helpers {
         let catalog = clean_state().await;
         test_parquet_file(Arc::clone(&catalog)).await;
         assert_metric_hit(&catalog.metrics(), "parquet_create");
+
+        let catalog = clean_state().await;
+        test_tombstone(Arc::clone(&catalog)).await;
+        assert_metric_hit(&catalog.metrics(), "tombstone_create");
     }
 
     async fn test_setup(catalog: Arc<dyn Catalog>) {
@@ -918,6 +1063,51 @@ pub(crate) mod test_helpers {
             .expect("namespace should be updateable");
         assert_eq!(new_column_limit, modified.max_columns_per_table);
 
+        assert_eq!(modified.query_limits, NamespaceQueryLimits::default());
+        let new_concurrent_query_limit = MaxConcurrentQueries::new(3);
+        let modified = repos
+            .namespaces()
+            .update_concurrent_query_limit(namespace_name.as_str(), new_concurrent_query_limit)
+            .await
+            .expect("namespace should be updateable");
+        assert_eq!(
+            Some(new_concurrent_query_limit),
+            modified.query_limits.max_concurrent_queries
+        );
+
+        let new_query_memory_limit = MaxQueryMemoryBytes::new(1024 * 1024);
+        let modified = repos
+            .namespaces()
+            .update_query_memory_limit(namespace_name.as_str(), new_query_memory_limit)
+            .await
+            .expect("namespace should be updateable");
+        assert_eq!(
+            Some(new_query_memory_limit),
+            modified.query_limits.max_query_memory_bytes
+        );
+
+        let new_query_timeout = QueryTimeout::new(30_000);
+        let modified = repos
+            .namespaces()
+            .update_query_timeout(namespace_name.as_str(), new_query_timeout)
+            .await
+            .expect("namespace should be updateable");
+        assert_eq!(
+            NamespaceQueryLimits {
+                max_concurrent_queries: Some(new_concurrent_query_limit),
+                max_query_memory_bytes: Some(new_query_memory_limit),
+                query_timeout_ms: Some(new_query_timeout),
+            },
+            modified.query_limits
+        );
+
+        let err = repos
+            .namespaces()
+            .update_query_timeout("does_not_exist", new_query_timeout)
+            .await
+            .expect_err("namespace should not exist");
+        assert_matches!(err, Error::NamespaceNotFoundByName { .. });
+
         const NEW_RETENTION_PERIOD_NS: i64 = 5 * 60 * 60 * 1000 * 1000 * 1000;
         let modified = repos
             .namespaces()
@@ -1654,6 +1844,33 @@ pub(crate) mod test_helpers {
 
         assert_eq!(created, listed);
 
+        let other_table = arbitrary_table(&mut *repos, "other_table", &namespace).await;
+        let other_partition = repos
+            .partitions()
+            .create_or_get("foo".into(), other_table.id)
+            .await
+            .unwrap();
+        let mut listed = repos
+            .partitions()
+            .list_by_table_ids_and_key(&[table.id, other_table.id], &"foo".into())
+            .await
+            .expect("failed to list partitions");
+        listed.sort_by_key(|p| p.id);
+        assert_eq!(listed, vec![partition.clone(), other_partition.clone()]);
+        let listed = repos
+            .partitions()
+            .list_by_table_ids_and_key(&[other_table.id], &"bar".into())
+            .await
+            .expect("failed to list partitions");
+        assert!(listed.is_empty());
+        let listed = repos
+            .partitions()
+            .list_by_table_ids_and_key(&[], &"foo".into())
+            .await
+            .expect("failed to list partitions");
+        assert!(listed.is_empty());
+        created.insert(other_partition.id, other_partition);
+
         let listed = repos
             .partitions()
             .list_ids()
@@ -2976,6 +3193,428 @@ pub(crate) mod test_helpers {
         assert!(partitions.is_empty());
     }
 
+    async fn test_tombstone(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, "namespace_tombstone_test").await;
+        let table = arbitrary_table(&mut *repos, "test_table", &namespace).await;
+        let other_table = arbitrary_table(&mut *repos, "other", &namespace).await;
+
+        // No tombstones exist initially.
+        let got = repos.tombstones().list_by_table_id(table.id).await.unwrap();
+        assert!(got.is_empty());
+
+        let t1 = repos
+            .tombstones()
+            .create(
+                table.id,
+                Timestamp::new(1),
+                Timestamp::new(10),
+                r#""tag"='bananas'"#,
+            )
+            .await
+            .unwrap();
+        assert_eq!(t1.table_id, table.id);
+        assert_eq!(t1.min_time, Timestamp::new(1));
+        assert_eq!(t1.max_time, Timestamp::new(10));
+        assert_eq!(t1.serialized_predicate, r#""tag"='bananas'"#);
+
+        let t2 = repos
+            .tombstones()
+            .create(table.id, Timestamp::new(5), Timestamp::new(50), "")
+            .await
+            .unwrap();
+        assert_ne!(t1.id, t2.id);
+        assert!(t2.created_at >= t1.created_at);
+
+        let t3 = repos
+            .tombstones()
+            .create(other_table.id, Timestamp::new(1), Timestamp::new(2), "")
+            .await
+            .unwrap();
+
+        // Tombstones are listed per-table, in creation order.
+        let got = repos.tombstones().list_by_table_id(table.id).await.unwrap();
+        assert_eq!(got, vec![t1, t2]);
+
+        let got = repos
+            .tombstones()
+            .list_by_table_id(other_table.id)
+            .await
+            .unwrap();
+        assert_eq!(got, vec![t3]);
+
+        // Tombstones can not be created for tables that do not exist.
+        let err = repos
+            .tombstones()
+            .create(
+                TableId::new(i64::MAX),
+                Timestamp::new(1),
+                Timestamp::new(2),
+                "",
+            )
+            .await
+            .expect_err("should fail for unknown table");
+        assert_matches!(err, Error::TableNotFound { .. });
+    }
+
+    async fn test_namespace_undelete(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, "namespace_undelete").await;
+        let table = arbitrary_table(&mut *repos, "test_table", &namespace).await;
+        let partition = repos
+            .partitions()
+            .create_or_get("one".into(), table.id)
+            .await
+            .unwrap();
+        let file = repos
+            .parquet_files()
+            .create(arbitrary_parquet_file_params(
+                &namespace, &table, &partition,
+            ))
+            .await
+            .unwrap();
+
+        // a namespace that is not deleted cannot be restored
+        let err = repos
+            .namespaces()
+            .undelete("namespace_undelete", Timestamp::new(0))
+            .await
+            .expect_err("namespace is not deleted");
+        assert_matches!(err, Error::NamespaceNotDeleted { .. });
+
+        // nor can a namespace that does not exist
+        let err = repos
+            .namespaces()
+            .undelete("does_not_exist", Timestamp::new(0))
+            .await
+            .expect_err("namespace does not exist");
+        assert_matches!(err, Error::NamespaceNotFoundByName { .. });
+
+        repos
+            .namespaces()
+            .soft_delete("namespace_undelete")
+            .await
+            .unwrap();
+        let deleted_at = repos
+            .namespaces()
+            .get_by_name("namespace_undelete", SoftDeletedRows::OnlyDeleted)
+            .await
+            .unwrap()
+            .expect("namespace is deleted")
+            .deleted_at
+            .unwrap();
+
+        // the files are not flagged for deletion within the grace period
+        let flagged = repos
+            .parquet_files()
+            .flag_for_delete_by_deleted_namespace(deleted_at)
+            .await
+            .unwrap();
+        assert!(flagged.is_empty());
+
+        // a namespace deleted before the grace period cannot be restored
+        let err = repos
+            .namespaces()
+            .undelete("namespace_undelete", Timestamp::new(deleted_at.get() + 1))
+            .await
+            .expect_err("grace period expired");
+        assert_matches!(err, Error::NamespaceUndeleteExpired { .. });
+
+        // but can be within it
+        let restored = repos
+            .namespaces()
+            .undelete("namespace_undelete", deleted_at)
+            .await
+            .unwrap();
+        assert_eq!(restored, namespace);
+        let got = repos
+            .namespaces()
+            .get_by_name("namespace_undelete", SoftDeletedRows::ExcludeDeleted)
+            .await
+            .unwrap();
+        assert_eq!(got, Some(namespace));
+
+        // once deleted past the grace period, the files are flagged for deletion, and only once
+        repos
+            .namespaces()
+            .soft_delete("namespace_undelete")
+            .await
+            .unwrap();
+        let flagged = repos
+            .parquet_files()
+            .flag_for_delete_by_deleted_namespace(Timestamp::new(i64::MAX))
+            .await
+            .unwrap();
+        assert_eq!(flagged, vec![file.id]);
+        let flagged = repos
+            .parquet_files()
+            .flag_for_delete_by_deleted_namespace(Timestamp::new(i64::MAX))
+            .await
+            .unwrap();
+        assert!(flagged.is_empty());
+    }
+
+    async fn test_namespace_rename(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, "namespace_rename").await;
+        arbitrary_namespace(&mut *repos, "namespace_other").await;
+
+        let new_name = NamespaceName::new("namespace_renamed").unwrap();
+        let renamed = repos
+            .namespaces()
+            .rename("namespace_rename", &new_name)
+            .await
+            .unwrap();
+        assert_eq!(renamed.id, namespace.id);
+        assert_eq!(renamed.name, "namespace_renamed");
+
+        // the namespace is only resolvable by its new name
+        let got = repos
+            .namespaces()
+            .get_by_name("namespace_renamed", SoftDeletedRows::AllRows)
+            .await
+            .unwrap();
+        assert_eq!(got, Some(renamed));
+        assert!(repos
+            .namespaces()
+            .get_by_name("namespace_rename", SoftDeletedRows::AllRows)
+            .await
+            .unwrap()
+            .is_none());
+
+        // the old name can be reused
+        let recreated = arbitrary_namespace(&mut *repos, "namespace_rename").await;
+        assert_ne!(recreated.id, namespace.id);
+
+        // renaming to an existing name fails
+        let err = repos
+            .namespaces()
+            .rename(
+                "namespace_renamed",
+                &NamespaceName::new("namespace_other").unwrap(),
+            )
+            .await
+            .expect_err("name is taken");
+        assert_matches!(err, Error::NameExists { .. });
+
+        // as does renaming a namespace that does not exist, or is deleted
+        let err = repos
+            .namespaces()
+            .rename("does_not_exist", &new_name)
+            .await
+            .expect_err("namespace does not exist");
+        assert_matches!(err, Error::NamespaceNotFoundByName { .. });
+
+        repos
+            .namespaces()
+            .soft_delete("namespace_other")
+            .await
+            .unwrap();
+        let err = repos
+            .namespaces()
+            .rename(
+                "namespace_other",
+                &NamespaceName::new("namespace_other_renamed").unwrap(),
+            )
+            .await
+            .expect_err("namespace is deleted");
+        assert_matches!(err, Error::NamespaceNotFoundByName { .. });
+    }
+
+    async fn test_table_soft_delete(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, "namespace_table_soft_delete").await;
+        let table = arbitrary_table(&mut *repos, "test_table", &namespace).await;
+        let other_table = arbitrary_table(&mut *repos, "other", &namespace).await;
+        repos
+            .columns()
+            .create_or_get("time", table.id, ColumnType::Time)
+            .await
+            .unwrap();
+
+        let partition = repos
+            .partitions()
+            .create_or_get("one".into(), table.id)
+            .await
+            .unwrap();
+        let file = repos
+            .parquet_files()
+            .create(arbitrary_parquet_file_params(
+                &namespace, &table, &partition,
+            ))
+            .await
+            .unwrap();
+        let other_partition = repos
+            .partitions()
+            .create_or_get("one".into(), other_table.id)
+            .await
+            .unwrap();
+        let other_file = repos
+            .parquet_files()
+            .create(arbitrary_parquet_file_params(
+                &namespace,
+                &other_table,
+                &other_partition,
+            ))
+            .await
+            .unwrap();
+
+        // nothing to flag while no table is deleted
+        let flagged = repos
+            .parquet_files()
+            .flag_for_delete_by_deleted_table()
+            .await
+            .unwrap();
+        assert!(flagged.is_empty());
+
+        let deleted = repos
+            .tables()
+            .soft_delete(namespace.id, "test_table")
+            .await
+            .unwrap();
+        assert_eq!(deleted.id, table.id);
+        assert_ne!(deleted.name, table.name);
+
+        // the table is no longer visible by name or in listings
+        assert!(repos
+            .tables()
+            .get_by_namespace_and_name(namespace.id, "test_table")
+            .await
+            .unwrap()
+            .is_none());
+        let tables = repos
+            .tables()
+            .list_by_namespace_id(namespace.id)
+            .await
+            .unwrap();
+        assert_eq!(tables, vec![other_table.clone()]);
+        assert_eq!(repos.tables().list().await.unwrap(), vec![other_table]);
+        let columns = repos
+            .columns()
+            .list_by_namespace_id(namespace.id)
+            .await
+            .unwrap();
+        assert!(columns.is_empty());
+
+        // but can still be resolved by ID
+        let got = repos.tables().get_by_id(table.id).await.unwrap().unwrap();
+        assert_eq!(got, deleted);
+
+        // deleting it again fails
+        let err = repos
+            .tables()
+            .soft_delete(namespace.id, "test_table")
+            .await
+            .expect_err("table is already deleted");
+        assert_matches!(err, Error::TableNotFoundByName { .. });
+
+        // the name can be reused
+        let recreated = arbitrary_table(&mut *repos, "test_table", &namespace).await;
+        assert_ne!(recreated.id, table.id);
+
+        // only the files of the deleted table are flagged, and only once
+        let flagged = repos
+            .parquet_files()
+            .flag_for_delete_by_deleted_table()
+            .await
+            .unwrap();
+        assert_eq!(flagged, vec![file.id]);
+        let flagged = repos
+            .parquet_files()
+            .flag_for_delete_by_deleted_table()
+            .await
+            .unwrap();
+        assert!(flagged.is_empty());
+        let files = repos
+            .parquet_files()
+            .list_by_namespace_not_to_delete(namespace.id)
+            .await
+            .unwrap();
+        assert_eq!(files, vec![other_file]);
+    }
+
+    async fn test_column_soft_delete(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, "namespace_column_soft_delete").await;
+        let table = arbitrary_table(&mut *repos, "test_table", &namespace).await;
+        let tag = repos
+            .columns()
+            .create_or_get("tag", table.id, ColumnType::Tag)
+            .await
+            .unwrap();
+        let field = repos
+            .columns()
+            .create_or_get("field", table.id, ColumnType::I64)
+            .await
+            .unwrap();
+        let time = repos
+            .columns()
+            .create_or_get("time", table.id, ColumnType::Time)
+            .await
+            .unwrap();
+
+        let deleted = repos
+            .columns()
+            .soft_delete(table.id, "field")
+            .await
+            .unwrap();
+        assert_eq!(deleted.id, field.id);
+        assert_ne!(deleted.name, field.name);
+
+        let mut columns = repos.columns().list_by_table_id(table.id).await.unwrap();
+        columns.sort_by_key(|c| c.id);
+        assert_eq!(columns, vec![tag.clone(), time.clone()]);
+
+        // the column can be re-created with a different type
+        let recreated = repos
+            .columns()
+            .create_or_get("field", table.id, ColumnType::F64)
+            .await
+            .unwrap();
+        assert_ne!(recreated.id, field.id);
+        assert_eq!(recreated.column_type, ColumnType::F64);
+
+        let err = repos
+            .columns()
+            .soft_delete(table.id, "missing")
+            .await
+            .expect_err("column does not exist");
+        assert_matches!(err, Error::ColumnNotFoundByName { .. });
+
+        let err = repos
+            .columns()
+            .soft_delete(table.id, "time")
+            .await
+            .expect_err("time column cannot be deleted");
+        assert_matches!(err, Error::ColumnDeleteNotAllowed { .. });
+
+        // columns in a partition sort key cannot be deleted
+        let partition = repos
+            .partitions()
+            .create_or_get("one".into(), table.id)
+            .await
+            .unwrap();
+        repos
+            .partitions()
+            .cas_sort_key(
+                &partition.transition_partition_id(),
+                None,
+                None,
+                &["tag", "time"],
+                &SortedColumnSet::from([tag.id.get(), time.id.get()]),
+            )
+            .await
+            .unwrap();
+        let err = repos
+            .columns()
+            .soft_delete(table.id, "tag")
+            .await
+            .expect_err("sort key column cannot be deleted");
+        assert_matches!(err, Error::ColumnDeleteNotAllowed { .. });
+        let columns = repos.columns().list_by_table_id(table.id).await.unwrap();
+        assert_eq!(columns.len(), 3);
+    }
+
     async fn test_list_by_partiton_not_to_delete(catalog: Arc<dyn Catalog>) {
         let mut repos = catalog.repositories().await;
         let namespace = arbitrary_namespace(
@@ -6,7 +6,8 @@ use crate::{
     interface::{
         CasFailure, Catalog, ColumnRepo, ColumnTypeMismatchSnafu, Error, NamespaceRepo,
         ParquetFileRepo, PartitionRepo, RepoCollection, Result, SoftDeletedRows, TableRepo,
-        MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION,
+        TombstoneRepo, DELETE_SORT_KEY_COLUMN_REASON, DELETE_TIME_COLUMN_REASON,
+        MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION, SOFT_DELETED_NAME_SEPARATOR,
     },
     metrics::MetricDecorator,
 };
@@ -16,10 +17,11 @@ use data_types::{
     partition_template::{
         NamespacePartitionTemplateOverride, TablePartitionTemplateOverride, TemplatePart,
     },
-    Column, ColumnId, ColumnType, CompactionLevel, MaxColumnsPerTable, MaxTables, Namespace,
-    NamespaceId, NamespaceName, NamespaceServiceProtectionLimitsOverride, ParquetFile,
-    ParquetFileId, ParquetFileParams, Partition, PartitionHashId, PartitionId, PartitionKey,
-    SkippedCompaction, Table, TableId, Timestamp, TransitionPartitionId,
+    Column, ColumnId, ColumnType, CompactionLevel, MaxColumnsPerTable, MaxConcurrentQueries,
+    MaxQueryMemoryBytes, MaxTables, Namespace, NamespaceId, NamespaceName,
+    NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId, ParquetFileParams,
+    Partition, PartitionHashId, PartitionId, PartitionKey, QueryTimeout, SkippedCompaction, Table,
+    TableId, Timestamp, Tombstone, TombstoneId, TransitionPartitionId,
 };
 use iox_time::{SystemProvider, TimeProvider};
 use snafu::ensure;
@@ -67,10 +69,13 @@ impl std::fmt::Debug for MemCatalog {
 struct MemCollections {
     namespaces: Vec<Namespace>,
     tables: Vec<Table>,
+    deleted_tables: HashSet<TableId>,
     columns: Vec<Column>,
+    deleted_columns: HashSet<ColumnId>,
     partitions: Vec<Partition>,
     skipped_compactions: Vec<SkippedCompaction>,
     parquet_files: Vec<ParquetFile>,
+    tombstones: Vec<Tombstone>,
 }
 
 /// transaction bound to an in-memory catalog.
@@ -140,6 +145,10 @@ impl RepoCollection for MemTxn {
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo {
         self
     }
+
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo {
+        self
+    }
 }
 
 #[async_trait]
@@ -171,6 +180,7 @@ impl NamespaceRepo for MemTxn {
             name: name.to_string(),
             max_tables,
             max_columns_per_table,
+            query_limits: Default::default(),
             retention_period_ns,
             deleted_at: None,
             partition_template: partition_template.unwrap_or_default(),
@@ -228,6 +238,51 @@ impl NamespaceRepo for MemTxn {
         }
     }
 
+    async fn undelete(&mut self, name: &str, deleted_after: Timestamp) -> Result<Namespace> {
+        let stage = self.stage();
+        match stage.namespaces.iter_mut().find(|n| n.name == name) {
+            Some(n) => match n.deleted_at {
+                None => Err(Error::NamespaceNotDeleted {
+                    name: name.to_string(),
+                }),
+                Some(deleted_at) if deleted_at < deleted_after => {
+                    Err(Error::NamespaceUndeleteExpired {
+                        name: name.to_string(),
+                    })
+                }
+                Some(_) => {
+                    n.deleted_at = None;
+                    Ok(n.clone())
+                }
+            },
+            None => Err(Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    async fn rename(&mut self, name: &str, new_name: &NamespaceName<'_>) -> Result<Namespace> {
+        let stage = self.stage();
+
+        let idx = stage
+            .namespaces
+            .iter()
+            .position(|n| n.name == name && n.deleted_at.is_none())
+            .ok_or_else(|| Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            })?;
+
+        if stage.namespaces.iter().any(|n| n.name == new_name.as_str()) {
+            return Err(Error::NameExists {
+                name: new_name.to_string(),
+            });
+        }
+
+        let n = &mut stage.namespaces[idx];
+        n.name = new_name.to_string();
+        Ok(n.clone())
+    }
+
     async fn update_table_limit(&mut self, name: &str, new_max: MaxTables) -> Result<Namespace> {
         let stage = self.stage();
         match stage.namespaces.iter_mut().find(|n| n.name == name) {
@@ -258,6 +313,57 @@ impl NamespaceRepo for MemTxn {
         }
     }
 
+    async fn update_concurrent_query_limit(
+        &mut self,
+        name: &str,
+        new_max: MaxConcurrentQueries,
+    ) -> Result<Namespace> {
+        let stage = self.stage();
+        match stage.namespaces.iter_mut().find(|n| n.name == name) {
+            Some(n) => {
+                n.query_limits.max_concurrent_queries = Some(new_max);
+                Ok(n.clone())
+            }
+            None => Err(Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    async fn update_query_memory_limit(
+        &mut self,
+        name: &str,
+        new_max: MaxQueryMemoryBytes,
+    ) -> Result<Namespace> {
+        let stage = self.stage();
+        match stage.namespaces.iter_mut().find(|n| n.name == name) {
+            Some(n) => {
+                n.query_limits.max_query_memory_bytes = Some(new_max);
+                Ok(n.clone())
+            }
+            None => Err(Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    async fn update_query_timeout(
+        &mut self,
+        name: &str,
+        timeout: QueryTimeout,
+    ) -> Result<Namespace> {
+        let stage = self.stage();
+        match stage.namespaces.iter_mut().find(|n| n.name == name) {
+            Some(n) => {
+                n.query_limits.query_timeout_ms = Some(timeout);
+                Ok(n.clone())
+            }
+            None => Err(Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            }),
+        }
+    }
+
     async fn update_retention_period(
         &mut self,
         name: &str,
@@ -304,7 +410,9 @@ impl TableRepo for MemTxn {
                     let tables_count = stage
                         .tables
                         .iter()
-                        .filter(|t| t.namespace_id == namespace_id)
+                        .filter(|t| {
+                            t.namespace_id == namespace_id && !stage.deleted_tables.contains(&t.id)
+                        })
                         .count();
                     if tables_count >= max_tables.get().try_into().unwrap() {
                         return Err(Error::TableCreateLimitError {
@@ -372,6 +480,7 @@ impl TableRepo for MemTxn {
         Ok(stage
             .tables
             .iter()
+            .filter(|t| !stage.deleted_tables.contains(&t.id))
             .find(|t| t.namespace_id == namespace_id && t.name == name)
             .cloned())
     }
@@ -382,7 +491,7 @@ impl TableRepo for MemTxn {
         let tables: Vec<_> = stage
             .tables
             .iter()
-            .filter(|t| t.namespace_id == namespace_id)
+            .filter(|t| t.namespace_id == namespace_id && !stage.deleted_tables.contains(&t.id))
             .cloned()
             .collect();
         Ok(tables)
@@ -390,7 +499,30 @@ impl TableRepo for MemTxn {
 
     async fn list(&mut self) -> Result<Vec<Table>> {
         let stage = self.stage();
-        Ok(stage.tables.clone())
+        Ok(stage
+            .tables
+            .iter()
+            .filter(|t| !stage.deleted_tables.contains(&t.id))
+            .cloned()
+            .collect())
+    }
+
+    async fn soft_delete(&mut self, namespace_id: NamespaceId, name: &str) -> Result<Table> {
+        let stage = self.stage();
+
+        let table = stage
+            .tables
+            .iter_mut()
+            .filter(|t| !stage.deleted_tables.contains(&t.id))
+            .find(|t| t.namespace_id == namespace_id && t.name == name)
+            .ok_or_else(|| Error::TableNotFoundByName {
+                name: name.to_string(),
+            })?;
+
+        table.name = format!("{name}{SOFT_DELETED_NAME_SEPARATOR}{}", table.id.get());
+        stage.deleted_tables.insert(table.id);
+
+        Ok(table.clone())
     }
 }
 
@@ -428,7 +560,9 @@ impl ColumnRepo for MemTxn {
                         let columns_count = stage
                             .columns
                             .iter()
-                            .filter(|t| t.table_id == table_id)
+                            .filter(|c| {
+                                c.table_id == table_id && !stage.deleted_columns.contains(&c.id)
+                            })
                             .count();
                         if columns_count >= max_columns_per_table.get().try_into().unwrap() {
                             return Err(Error::ColumnCreateLimitError {
@@ -444,6 +578,7 @@ impl ColumnRepo for MemTxn {
         let column = match stage
             .columns
             .iter()
+            .filter(|c| !stage.deleted_columns.contains(&c.id))
             .find(|t| t.name == name && t.table_id == table_id)
         {
             Some(c) => {
@@ -489,6 +624,7 @@ impl ColumnRepo for MemTxn {
                 match stage
                     .columns
                     .iter()
+                    .filter(|c| !stage.deleted_columns.contains(&c.id))
                     .find(|t| t.name == column_name && t.table_id == table_id)
                 {
                     Some(c) => {
@@ -525,13 +661,13 @@ impl ColumnRepo for MemTxn {
         let table_ids: Vec<_> = stage
             .tables
             .iter()
-            .filter(|t| t.namespace_id == namespace_id)
+            .filter(|t| t.namespace_id == namespace_id && !stage.deleted_tables.contains(&t.id))
             .map(|t| t.id)
             .collect();
         let columns: Vec<_> = stage
             .columns
             .iter()
-            .filter(|c| table_ids.contains(&c.table_id))
+            .filter(|c| table_ids.contains(&c.table_id) && !stage.deleted_columns.contains(&c.id))
             .cloned()
             .collect();
 
@@ -544,7 +680,7 @@ impl ColumnRepo for MemTxn {
         let columns: Vec<_> = stage
             .columns
             .iter()
-            .filter(|c| c.table_id == table_id)
+            .filter(|c| c.table_id == table_id && !stage.deleted_columns.contains(&c.id))
             .cloned()
             .collect();
 
@@ -553,7 +689,53 @@ impl ColumnRepo for MemTxn {
 
     async fn list(&mut self) -> Result<Vec<Column>> {
         let stage = self.stage();
-        Ok(stage.columns.clone())
+        Ok(stage
+            .columns
+            .iter()
+            .filter(|c| {
+                !stage.deleted_columns.contains(&c.id)
+                    && !stage.deleted_tables.contains(&c.table_id)
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn soft_delete(&mut self, table_id: TableId, name: &str) -> Result<Column> {
+        let stage = self.stage();
+
+        let column = stage
+            .columns
+            .iter_mut()
+            .filter(|c| !stage.deleted_columns.contains(&c.id))
+            .find(|c| c.table_id == table_id && c.name == name)
+            .ok_or_else(|| Error::ColumnNotFoundByName {
+                name: name.to_string(),
+                table_id,
+            })?;
+
+        let reason = if column.column_type == ColumnType::Time {
+            Some(DELETE_TIME_COLUMN_REASON)
+        } else if stage
+            .partitions
+            .iter()
+            .any(|p| p.table_id == table_id && p.sort_key_ids().contains(&column.id))
+        {
+            Some(DELETE_SORT_KEY_COLUMN_REASON)
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return Err(Error::ColumnDeleteNotAllowed {
+                name: name.to_string(),
+                table_id,
+                reason,
+            });
+        }
+
+        column.name = format!("{name}{SOFT_DELETED_NAME_SEPARATOR}{}", column.id.get());
+        stage.deleted_columns.insert(column.id);
+
+        Ok(column.clone())
     }
 }
 
@@ -657,6 +839,21 @@ impl PartitionRepo for MemTxn {
         Ok(partitions)
     }
 
+    async fn list_by_table_ids_and_key(
+        &mut self,
+        table_ids: &[TableId],
+        key: &PartitionKey,
+    ) -> Result<Vec<Partition>> {
+        let stage = self.stage();
+
+        Ok(stage
+            .partitions
+            .iter()
+            .filter(|p| &p.partition_key == key && table_ids.contains(&p.table_id))
+            .cloned()
+            .collect())
+    }
+
     async fn list_ids(&mut self) -> Result<Vec<PartitionId>> {
         let stage = self.stage();
 
@@ -875,6 +1072,50 @@ impl ParquetFileRepo for MemTxn {
             .collect())
     }
 
+    async fn flag_for_delete_by_deleted_table(&mut self) -> Result<Vec<ParquetFileId>> {
+        let now = Timestamp::from(self.time_provider.now());
+        let stage = self.stage();
+
+        Ok(stage
+            .parquet_files
+            .iter_mut()
+            // don't flag if already flagged for deletion
+            .filter(|f| f.to_delete.is_none() && stage.deleted_tables.contains(&f.table_id))
+            .take(MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION as usize)
+            .map(|f| {
+                f.to_delete = Some(now);
+                f.id
+            })
+            .collect())
+    }
+
+    async fn flag_for_delete_by_deleted_namespace(
+        &mut self,
+        deleted_before: Timestamp,
+    ) -> Result<Vec<ParquetFileId>> {
+        let now = Timestamp::from(self.time_provider.now());
+        let stage = self.stage();
+
+        let namespace_ids: HashSet<_> = stage
+            .namespaces
+            .iter()
+            .filter(|n| n.deleted_at.map_or(false, |d| d < deleted_before))
+            .map(|n| n.id)
+            .collect();
+
+        Ok(stage
+            .parquet_files
+            .iter_mut()
+            // don't flag if already flagged for deletion
+            .filter(|f| f.to_delete.is_none() && namespace_ids.contains(&f.namespace_id))
+            .take(MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION as usize)
+            .map(|f| {
+                f.to_delete = Some(now);
+                f.id
+            })
+            .collect())
+    }
+
     async fn list_by_namespace_not_to_delete(
         &mut self,
         namespace_id: NamespaceId,
@@ -1021,6 +1262,47 @@ impl ParquetFileRepo for MemTxn {
     }
 }
 
+#[async_trait]
+impl TombstoneRepo for MemTxn {
+    async fn create(
+        &mut self,
+        table_id: TableId,
+        min_time: Timestamp,
+        max_time: Timestamp,
+        serialized_predicate: &str,
+    ) -> Result<Tombstone> {
+        let created_at = Timestamp::from(self.time_provider.now());
+        let stage = self.stage();
+
+        if !stage.tables.iter().any(|t| t.id == table_id) {
+            return Err(Error::TableNotFound { id: table_id });
+        }
+
+        let tombstone = Tombstone {
+            id: TombstoneId::new(stage.tombstones.len() as i64 + 1),
+            table_id,
+            min_time,
+            max_time,
+            serialized_predicate: serialized_predicate.to_string(),
+            created_at,
+        };
+        stage.tombstones.push(tombstone.clone());
+
+        Ok(tombstone)
+    }
+
+    async fn list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Tombstone>> {
+        let stage = self.stage();
+
+        Ok(stage
+            .tombstones
+            .iter()
+            .filter(|t| t.table_id == table_id)
+            .cloned()
+            .collect())
+    }
+}
+
 fn filter_namespace_soft_delete<'a>(
     v: impl IntoIterator<Item = &'a Namespace>,
     deleted: SoftDeletedRows,
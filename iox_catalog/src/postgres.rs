@@ -5,7 +5,8 @@ use crate::{
     interface::{
         self, CasFailure, Catalog, ColumnRepo, ColumnTypeMismatchSnafu, Error, NamespaceRepo,
         ParquetFileRepo, PartitionRepo, RepoCollection, Result, SoftDeletedRows, TableRepo,
-        MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION,
+        TombstoneRepo, DELETE_SORT_KEY_COLUMN_REASON, DELETE_TIME_COLUMN_REASON,
+        MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION, SOFT_DELETED_NAME_SEPARATOR,
     },
     kafkaless_transition::{
         SHARED_QUERY_POOL, SHARED_QUERY_POOL_ID, SHARED_TOPIC_ID, SHARED_TOPIC_NAME,
@@ -20,10 +21,11 @@ use data_types::{
     partition_template::{
         NamespacePartitionTemplateOverride, TablePartitionTemplateOverride, TemplatePart,
     },
-    Column, ColumnType, CompactionLevel, MaxColumnsPerTable, MaxTables, Namespace, NamespaceId,
-    NamespaceName, NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId,
-    ParquetFileParams, Partition, PartitionHashId, PartitionId, PartitionKey, SkippedCompaction,
-    Table, TableId, Timestamp, TransitionPartitionId,
+    Column, ColumnType, CompactionLevel, MaxColumnsPerTable, MaxConcurrentQueries,
+    MaxQueryMemoryBytes, MaxTables, Namespace, NamespaceId, NamespaceName,
+    NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId, ParquetFileParams,
+    Partition, PartitionHashId, PartitionId, PartitionKey, QueryTimeout, SkippedCompaction, Table,
+    TableId, Timestamp, Tombstone, TransitionPartitionId,
 };
 use iox_time::{SystemProvider, TimeProvider};
 use metric::{Attributes, Instrument, MetricKind};
@@ -631,6 +633,10 @@ impl RepoCollection for PostgresTxn {
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo {
         self
     }
+
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo {
+        self
+    }
 }
 
 async fn insert_column_with_connection<'q, E>(
@@ -649,6 +655,7 @@ SELECT $1, table_id, $3 FROM (
     SELECT max_columns_per_table, namespace.id, table_name.id as table_id, COUNT(column_name.*) AS count
     FROM namespace LEFT JOIN table_name ON namespace.id = table_name.namespace_id
                    LEFT JOIN column_name ON table_name.id = column_name.table_id
+                                        AND column_name.deleted_at IS NULL
     WHERE table_name.id = $2
     GROUP BY namespace.max_columns_per_table, namespace.id, table_name.id
 ) AS get_count WHERE count < max_columns_per_table
@@ -710,7 +717,7 @@ INSERT INTO namespace (
 )
 VALUES ( $1, $2, $3, $4, $5, $6, $7 )
 RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-          partition_template;
+          partition_template, max_concurrent_queries, max_query_memory_bytes, query_timeout_ms;
             "#,
         )
         .bind(name.as_str()) // $1
@@ -741,7 +748,7 @@ RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, dele
             format!(
                 r#"
 SELECT id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-       partition_template
+       partition_template, max_concurrent_queries, max_query_memory_bytes, query_timeout_ms
 FROM namespace
 WHERE {v};
                 "#,
@@ -765,7 +772,7 @@ WHERE {v};
             format!(
                 r#"
 SELECT id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-       partition_template
+       partition_template, max_concurrent_queries, max_query_memory_bytes, query_timeout_ms
 FROM namespace
 WHERE id=$1 AND {v};
                 "#,
@@ -795,7 +802,7 @@ WHERE id=$1 AND {v};
             format!(
                 r#"
 SELECT id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-       partition_template
+       partition_template, max_concurrent_queries, max_query_memory_bytes, query_timeout_ms
 FROM namespace
 WHERE name=$1 AND {v};
                 "#,
@@ -829,6 +836,71 @@ WHERE name=$1 AND {v};
             .map(|_| ())
     }
 
+    async fn undelete(&mut self, name: &str, deleted_after: Timestamp) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"
+UPDATE namespace
+SET deleted_at = NULL
+WHERE name = $1 AND deleted_at IS NOT NULL AND deleted_at >= $2
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
+          partition_template, max_concurrent_queries, max_query_memory_bytes, query_timeout_ms;
+        "#,
+        )
+        .bind(name) // $1
+        .bind(deleted_after) // $2
+        .fetch_one(&mut self.inner)
+        .await;
+
+        match rec {
+            Ok(namespace) => Ok(namespace),
+            Err(sqlx::Error::RowNotFound) => {
+                // Determine why the namespace could not be restored.
+                match self.get_by_name(name, SoftDeletedRows::AllRows).await? {
+                    None => Err(Error::NamespaceNotFoundByName {
+                        name: name.to_string(),
+                    }),
+                    Some(Namespace {
+                        deleted_at: None, ..
+                    }) => Err(Error::NamespaceNotDeleted {
+                        name: name.to_string(),
+                    }),
+                    Some(_) => Err(Error::NamespaceUndeleteExpired {
+                        name: name.to_string(),
+                    }),
+                }
+            }
+            Err(e) => Err(Error::SqlxError { source: e }),
+        }
+    }
+
+    async fn rename(&mut self, name: &str, new_name: &NamespaceName<'_>) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"
+UPDATE namespace
+SET name = $1
+WHERE name = $2 AND deleted_at IS NULL
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
+          partition_template, max_concurrent_queries, max_query_memory_bytes, query_timeout_ms;
+        "#,
+        )
+        .bind(new_name.as_str()) // $1
+        .bind(name) // $2
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            e if is_unique_violation(&e) => Error::NameExists {
+                name: new_name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
+
     async fn update_table_limit(&mut self, name: &str, new_max: MaxTables) -> Result<Namespace> {
         let rec = sqlx::query_as::<_, Namespace>(
             r#"
@@ -836,7 +908,7 @@ UPDATE namespace
 SET max_tables = $1
 WHERE name = $2
 RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-          partition_template;
+          partition_template, max_concurrent_queries, max_query_memory_bytes, query_timeout_ms;
         "#,
         )
         .bind(new_max)
@@ -865,7 +937,36 @@ UPDATE namespace
 SET max_columns_per_table = $1
 WHERE name = $2
 RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-          partition_template;
+          partition_template, max_concurrent_queries, max_query_memory_bytes, query_timeout_ms;
+        "#,
+        )
+        .bind(new_max)
+        .bind(name)
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
+
+    async fn update_concurrent_query_limit(
+        &mut self,
+        name: &str,
+        new_max: MaxConcurrentQueries,
+    ) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"
+UPDATE namespace
+SET max_concurrent_queries = $1
+WHERE name = $2
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
+          partition_template, max_concurrent_queries, max_query_memory_bytes, query_timeout_ms;
         "#,
         )
         .bind(new_max)
@@ -883,6 +984,64 @@ RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, dele
         Ok(namespace)
     }
 
+    async fn update_query_memory_limit(
+        &mut self,
+        name: &str,
+        new_max: MaxQueryMemoryBytes,
+    ) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"
+UPDATE namespace
+SET max_query_memory_bytes = $1
+WHERE name = $2
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
+          partition_template, max_concurrent_queries, max_query_memory_bytes, query_timeout_ms;
+        "#,
+        )
+        .bind(new_max)
+        .bind(name)
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
+
+    async fn update_query_timeout(
+        &mut self,
+        name: &str,
+        timeout: QueryTimeout,
+    ) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"
+UPDATE namespace
+SET query_timeout_ms = $1
+WHERE name = $2
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
+          partition_template, max_concurrent_queries, max_query_memory_bytes, query_timeout_ms;
+        "#,
+        )
+        .bind(timeout)
+        .bind(name)
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
+
     async fn update_retention_period(
         &mut self,
         name: &str,
@@ -894,7 +1053,7 @@ UPDATE namespace
 SET retention_period_ns = $1
 WHERE name = $2
 RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-          partition_template;
+          partition_template, max_concurrent_queries, max_query_memory_bytes, query_timeout_ms;
         "#,
         )
         .bind(retention_period_ns) // $1
@@ -943,6 +1102,7 @@ INSERT INTO table_name ( name, namespace_id, partition_template )
 SELECT $1, id, $2 FROM (
     SELECT namespace.id AS id, max_tables, COUNT(table_name.*) AS count
     FROM namespace LEFT JOIN table_name ON namespace.id = table_name.namespace_id
+                                       AND table_name.deleted_at IS NULL
     WHERE namespace.id = $3
     GROUP BY namespace.max_tables, table_name.namespace_id, namespace.id
 ) AS get_count WHERE count < max_tables
@@ -1021,7 +1181,7 @@ WHERE id = $1;
             r#"
 SELECT *
 FROM table_name
-WHERE namespace_id = $1 AND name = $2;
+WHERE namespace_id = $1 AND name = $2 AND deleted_at IS NULL;
             "#,
         )
         .bind(namespace_id) // $1
@@ -1043,7 +1203,7 @@ WHERE namespace_id = $1 AND name = $2;
             r#"
 SELECT *
 FROM table_name
-WHERE namespace_id = $1;
+WHERE namespace_id = $1 AND deleted_at IS NULL;
             "#,
         )
         .bind(namespace_id)
@@ -1055,13 +1215,41 @@ WHERE namespace_id = $1;
     }
 
     async fn list(&mut self) -> Result<Vec<Table>> {
-        let rec = sqlx::query_as::<_, Table>("SELECT * FROM table_name;")
+        let rec = sqlx::query_as::<_, Table>("SELECT * FROM table_name WHERE deleted_at IS NULL;")
             .fetch_all(&mut self.inner)
             .await
             .map_err(|e| Error::SqlxError { source: e })?;
 
         Ok(rec)
     }
+
+    async fn soft_delete(&mut self, namespace_id: NamespaceId, name: &str) -> Result<Table> {
+        let flagged_at = Timestamp::from(self.time_provider.now());
+
+        // note that there is a uniqueness constraint on (namespace_id, name), so the deleted
+        // table is renamed to release its name
+        let rec = sqlx::query_as::<_, Table>(
+            r#"
+UPDATE table_name
+SET deleted_at = $1, name = name || $2 || id
+WHERE namespace_id = $3 AND name = $4 AND deleted_at IS NULL
+RETURNING *;
+            "#,
+        )
+        .bind(flagged_at) // $1
+        .bind(SOFT_DELETED_NAME_SEPARATOR) // $2
+        .bind(namespace_id) // $3
+        .bind(name) // $4
+        .fetch_one(&mut self.inner)
+        .await;
+
+        rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::TableNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })
+    }
 }
 
 #[async_trait]
@@ -1080,7 +1268,9 @@ impl ColumnRepo for PostgresTxn {
             r#"
 SELECT column_name.* FROM table_name
 INNER JOIN column_name on column_name.table_id = table_name.id
-WHERE table_name.namespace_id = $1;
+WHERE table_name.namespace_id = $1
+  AND table_name.deleted_at IS NULL
+  AND column_name.deleted_at IS NULL;
             "#,
         )
         .bind(namespace_id)
@@ -1095,7 +1285,7 @@ WHERE table_name.namespace_id = $1;
         let rec = sqlx::query_as::<_, Column>(
             r#"
 SELECT * FROM column_name
-WHERE table_id = $1;
+WHERE table_id = $1 AND deleted_at IS NULL;
             "#,
         )
         .bind(table_id)
@@ -1107,14 +1297,84 @@ WHERE table_id = $1;
     }
 
     async fn list(&mut self) -> Result<Vec<Column>> {
-        let rec = sqlx::query_as::<_, Column>("SELECT * FROM column_name;")
-            .fetch_all(&mut self.inner)
-            .await
-            .map_err(|e| Error::SqlxError { source: e })?;
+        let rec = sqlx::query_as::<_, Column>(
+            r#"
+SELECT column_name.* FROM column_name
+INNER JOIN table_name on table_name.id = column_name.table_id
+WHERE table_name.deleted_at IS NULL AND column_name.deleted_at IS NULL;
+            "#,
+        )
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })?;
 
         Ok(rec)
     }
 
+    async fn soft_delete(&mut self, table_id: TableId, name: &str) -> Result<Column> {
+        let flagged_at = Timestamp::from(self.time_provider.now());
+
+        // The checks for the time column and partition sort keys are part of the UPDATE to avoid
+        // racing with a concurrent sort key change.
+        //
+        // note that there is a uniqueness constraint on (table_id, name), so the deleted column
+        // is renamed to release its name
+        let rec = sqlx::query_as::<_, Column>(
+            r#"
+UPDATE column_name
+SET deleted_at = $1, name = name || $2 || id
+WHERE table_id = $3 AND name = $4 AND deleted_at IS NULL
+  AND column_type != $5
+  AND NOT EXISTS (
+    SELECT 1 FROM partition
+    WHERE partition.table_id = $3 AND column_name.id = ANY(partition.sort_key_ids)
+  )
+RETURNING *;
+            "#,
+        )
+        .bind(flagged_at) // $1
+        .bind(SOFT_DELETED_NAME_SEPARATOR) // $2
+        .bind(table_id) // $3
+        .bind(name) // $4
+        .bind(ColumnType::Time) // $5
+        .fetch_one(&mut self.inner)
+        .await;
+
+        match rec {
+            Ok(column) => Ok(column),
+            Err(sqlx::Error::RowNotFound) => {
+                // figure out why nothing was deleted
+                let column = sqlx::query_as::<_, Column>(
+                    r#"
+SELECT * FROM column_name
+WHERE table_id = $1 AND name = $2 AND deleted_at IS NULL;
+                    "#,
+                )
+                .bind(table_id) // $1
+                .bind(name) // $2
+                .fetch_optional(&mut self.inner)
+                .await
+                .map_err(|e| Error::SqlxError { source: e })?
+                .ok_or_else(|| Error::ColumnNotFoundByName {
+                    name: name.to_string(),
+                    table_id,
+                })?;
+
+                let reason = if column.column_type == ColumnType::Time {
+                    DELETE_TIME_COLUMN_REASON
+                } else {
+                    DELETE_SORT_KEY_COLUMN_REASON
+                };
+                Err(Error::ColumnDeleteNotAllowed {
+                    name: name.to_string(),
+                    table_id,
+                    reason,
+                })
+            }
+            Err(e) => Err(Error::SqlxError { source: e }),
+        }
+    }
+
     async fn create_or_get_many_unchecked(
         &mut self,
         table_id: TableId,
@@ -1301,6 +1561,27 @@ WHERE table_id = $1;
         .map_err(|e| Error::SqlxError { source: e })
     }
 
+    async fn list_by_table_ids_and_key(
+        &mut self,
+        table_ids: &[TableId],
+        key: &PartitionKey,
+    ) -> Result<Vec<Partition>> {
+        let ids: Vec<_> = table_ids.iter().map(|t| t.get()).collect();
+
+        sqlx::query_as::<_, Partition>(
+            r#"
+SELECT id, hash_id, table_id, partition_key, sort_key, sort_key_ids, new_file_at
+FROM partition
+WHERE table_id = ANY($1) AND partition_key = $2;
+            "#,
+        )
+        .bind(&ids[..]) // $1
+        .bind(key) // $2
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
     async fn list_ids(&mut self) -> Result<Vec<PartitionId>> {
         sqlx::query_as(
             r#"
@@ -1615,6 +1896,67 @@ RETURNING id;
         Ok(flagged)
     }
 
+    async fn flag_for_delete_by_deleted_table(&mut self) -> Result<Vec<ParquetFileId>> {
+        let flagged_at = Timestamp::from(self.time_provider.now());
+        let flagged = sqlx::query(
+            r#"
+WITH parquet_file_ids as (
+    SELECT parquet_file.id
+    FROM table_name, parquet_file
+    WHERE table_name.deleted_at IS NOT NULL
+    AND parquet_file.to_delete IS NULL
+    AND table_name.id = parquet_file.table_id
+    LIMIT $2
+)
+UPDATE parquet_file
+SET to_delete = $1
+WHERE id IN (SELECT id FROM parquet_file_ids)
+RETURNING id;
+            "#,
+        )
+        .bind(flagged_at) // $1
+        .bind(MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION) // $2
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })?;
+
+        let flagged = flagged.into_iter().map(|row| row.get("id")).collect();
+        Ok(flagged)
+    }
+
+    async fn flag_for_delete_by_deleted_namespace(
+        &mut self,
+        deleted_before: Timestamp,
+    ) -> Result<Vec<ParquetFileId>> {
+        let flagged_at = Timestamp::from(self.time_provider.now());
+        let flagged = sqlx::query(
+            r#"
+WITH parquet_file_ids as (
+    SELECT parquet_file.id
+    FROM namespace, parquet_file
+    WHERE namespace.deleted_at IS NOT NULL
+    AND namespace.deleted_at < $3
+    AND parquet_file.to_delete IS NULL
+    AND namespace.id = parquet_file.namespace_id
+    LIMIT $2
+)
+UPDATE parquet_file
+SET to_delete = $1
+WHERE id IN (SELECT id FROM parquet_file_ids)
+RETURNING id;
+            "#,
+        )
+        .bind(flagged_at) // $1
+        .bind(MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION) // $2
+        .bind(deleted_before) // $3
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })?;
+
+        let flagged = flagged.into_iter().map(|row| row.get("id")).collect();
+        Ok(flagged)
+    }
+
     async fn list_by_namespace_not_to_delete(
         &mut self,
         namespace_id: NamespaceId,
@@ -1805,6 +2147,56 @@ WHERE object_store_id = ANY($1);
     }
 }
 
+#[async_trait]
+impl TombstoneRepo for PostgresTxn {
+    async fn create(
+        &mut self,
+        table_id: TableId,
+        min_time: Timestamp,
+        max_time: Timestamp,
+        serialized_predicate: &str,
+    ) -> Result<Tombstone> {
+        let created_at = Timestamp::from(self.time_provider.now());
+
+        sqlx::query_as::<_, Tombstone>(
+            r#"
+INSERT INTO tombstone ( table_id, min_time, max_time, serialized_predicate, created_at )
+VALUES ( $1, $2, $3, $4, $5 )
+RETURNING *;
+        "#,
+        )
+        .bind(table_id) // $1
+        .bind(min_time) // $2
+        .bind(max_time) // $3
+        .bind(serialized_predicate) // $4
+        .bind(created_at) // $5
+        .fetch_one(&mut self.inner)
+        .await
+        .map_err(|e| {
+            if is_fk_violation(&e) {
+                Error::TableNotFound { id: table_id }
+            } else {
+                Error::SqlxError { source: e }
+            }
+        })
+    }
+
+    async fn list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Tombstone>> {
+        sqlx::query_as::<_, Tombstone>(
+            r#"
+SELECT *
+FROM tombstone
+WHERE table_id = $1
+ORDER BY id;
+        "#,
+        )
+        .bind(table_id) // $1
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+}
+
 // The following three functions are helpers to the create_upgrade_delete method.
 // They are also used by the respective create/flag_for_delete/update_compaction_level methods.
 async fn create_parquet_file<'q, E>(
@@ -2651,7 +3043,7 @@ INSERT INTO namespace (
 )
 VALUES ( $1, $2, $3, $4, NULL )
 RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-          partition_template;
+          partition_template, max_concurrent_queries, max_query_memory_bytes, query_timeout_ms;
             "#,
         )
         .bind(namespace_name) // $1
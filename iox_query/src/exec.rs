@@ -2,12 +2,16 @@
 //! plans. This is currently implemented using DataFusion, and this
 //! interface abstracts away many of the details
 pub(crate) mod context;
+pub mod data_manager;
 pub mod field;
 pub mod fieldlist;
 pub mod gapfill;
 mod metrics;
 mod non_null_checker;
+mod query_limits;
+mod query_stats;
 pub mod query_tracing;
+pub mod query_writer;
 mod schema_pivot;
 pub mod seriesset;
 pub(crate) mod split;
@@ -33,6 +37,8 @@ use datafusion::{
 };
 
 pub use context::{IOxSessionConfig, IOxSessionContext, SessionContextIOxExt};
+pub use query_limits::{QueryCancelledError, QueryTimeoutError};
+pub use query_stats::QueryStats;
 use schema_pivot::SchemaPivotNode;
 
 use crate::exec::metrics::DataFusionMemoryPoolMetricsBridge;
@@ -5,15 +5,19 @@ use super::{
     cross_rt_stream::CrossRtStream,
     gapfill::{plan_gap_fill, GapFill},
     non_null_checker::NonNullCheckerNode,
+    query_limits::{QueryCancellation, QueryDeadline, QueryMemoryPool},
+    query_stats::QueryStats,
     seriesset::{series::Either, SeriesSet},
     split::StreamSplitNode,
 };
 use crate::{
     config::IoxConfigExt,
     exec::{
+        data_manager::DataManager,
         fieldlist::{FieldList, IntoFieldList},
         non_null_checker::NonNullCheckerExec,
         query_tracing::TracedStream,
+        query_writer::QueryWriter,
         schema_pivot::{SchemaPivotExec, SchemaPivotNode},
         seriesset::{
             converter::{GroupGenerator, SeriesSetConverter},
@@ -26,11 +30,15 @@ use crate::{
     physical_optimizer::register_iox_physical_optimizers,
     plan::{
         fieldlist::FieldListPlan,
+        series_cardinality::SeriesCardinalityPlan,
         seriesset::{SeriesSetPlan, SeriesSetPlans},
         stringset::StringSetPlan,
     },
 };
-use arrow::record_batch::RecordBatch;
+use arrow::{
+    array::{Array, Int64Array},
+    record_batch::RecordBatch,
+};
 use async_trait::async_trait;
 use datafusion::{
     catalog::CatalogProvider,
@@ -52,7 +60,8 @@ use executor::DedicatedExecutor;
 use futures::{Stream, StreamExt, TryStreamExt};
 use observability_deps::tracing::{debug, warn};
 use query_functions::{register_scalar_functions, selectors::register_selector_aggregates};
-use std::{fmt, num::NonZeroUsize, sync::Arc};
+use std::{fmt, num::NonZeroUsize, sync::Arc, time::Duration};
+use tokio_util::sync::CancellationToken;
 use trace::{
     ctx::SpanContext,
     span::{MetaValue, Span, SpanExt, SpanRecorder},
@@ -176,6 +185,24 @@ pub struct IOxSessionConfig {
 
     /// Span context from which to create spans for this query
     span_ctx: Option<SpanContext>,
+
+    /// Destination of data written by this query
+    query_writer: Option<Arc<dyn QueryWriter>>,
+
+    /// Handler of data management statements
+    data_manager: Option<Arc<dyn DataManager>>,
+
+    /// Maximum number of bytes the query may reserve
+    memory_limit: Option<usize>,
+
+    /// Maximum wall-clock time the query may run, measured from the creation of the context
+    timeout: Option<Duration>,
+
+    /// Statistics about the query, updated while it runs
+    query_stats: Option<Arc<QueryStats>>,
+
+    /// Token used to cancel the query
+    cancellation_token: Option<CancellationToken>,
 }
 
 impl fmt::Debug for IOxSessionConfig {
@@ -198,6 +225,12 @@ impl IOxSessionConfig {
             runtime,
             default_catalog: None,
             span_ctx: None,
+            query_writer: None,
+            data_manager: None,
+            memory_limit: None,
+            timeout: None,
+            query_stats: None,
+            cancellation_token: None,
         }
     }
 
@@ -222,6 +255,59 @@ impl IOxSessionConfig {
         Self { span_ctx, ..self }
     }
 
+    /// Set the destination of data written by the query, such as the
+    /// results of an InfluxQL `SELECT ... INTO` statement.
+    pub fn with_query_writer(self, query_writer: Arc<dyn QueryWriter>) -> Self {
+        Self {
+            query_writer: Some(query_writer),
+            ..self
+        }
+    }
+
+    /// Set the handler of data management statements, such as the InfluxQL
+    /// `DELETE` statement.
+    pub fn with_data_manager(self, data_manager: Arc<dyn DataManager>) -> Self {
+        Self {
+            data_manager: Some(data_manager),
+            ..self
+        }
+    }
+
+    /// Limit the memory reserved by the query to `limit` bytes.
+    ///
+    /// Reservations still count against the memory pool of the executor.
+    pub fn with_memory_limit(self, limit: usize) -> Self {
+        Self {
+            memory_limit: Some(limit),
+            ..self
+        }
+    }
+
+    /// Fail the query if it runs longer than `timeout`, measured from the creation of the
+    /// context.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Record statistics about the query into `query_stats`.
+    pub fn with_query_stats(self, query_stats: Arc<QueryStats>) -> Self {
+        Self {
+            query_stats: Some(query_stats),
+            ..self
+        }
+    }
+
+    /// Abort the query once `token` is cancelled.
+    pub fn with_cancellation_token(self, token: CancellationToken) -> Self {
+        Self {
+            cancellation_token: Some(token),
+            ..self
+        }
+    }
+
     /// Set DataFusion [config option].
     ///
     /// May be used to set [IOx-specific] option as well.
@@ -250,9 +336,31 @@ impl IOxSessionConfig {
         // attach span to DataFusion session
         let session_config = self
             .session_config
-            .with_extension(Arc::new(recorder.span().cloned()));
+            .with_extension(Arc::new(recorder.span().cloned()))
+            .with_extension(Arc::new(self.query_writer))
+            .with_extension(Arc::new(self.data_manager))
+            .with_extension(Arc::new(self.timeout.map(QueryDeadline::new)))
+            .with_extension(Arc::new(
+                self.cancellation_token.map(QueryCancellation::new),
+            ))
+            .with_extension(Arc::new(self.query_stats.clone()));
+
+        // track the memory of this query separately if it is limited or observed
+        let runtime = if self.memory_limit.is_some() || self.query_stats.is_some() {
+            let memory_pool: Arc<dyn MemoryPool> = Arc::new(QueryMemoryPool::new(
+                Arc::clone(&self.runtime.memory_pool),
+                self.memory_limit,
+                self.query_stats,
+            ));
+            Arc::new(RuntimeEnv {
+                memory_pool,
+                ..self.runtime.as_ref().clone()
+            })
+        } else {
+            self.runtime
+        };
 
-        let state = SessionState::with_config_rt(session_config, self.runtime)
+        let state = SessionState::with_config_rt(session_config, runtime)
             .with_query_planner(Arc::new(IOxQueryPlanner {}));
         let state = register_iox_physical_optimizers(state);
         let state = register_iox_logical_optimizers(state);
@@ -293,6 +401,9 @@ pub struct IOxSessionContext {
 
     /// Span context from which to create spans for this query
     recorder: SpanRecorder,
+
+    /// Authorization of the caller, forwarded with data written by the query
+    authorization: Option<Arc<str>>,
 }
 
 impl fmt::Debug for IOxSessionContext {
@@ -315,6 +426,7 @@ impl IOxSessionContext {
             inner: SessionContext::default(),
             exec: DedicatedExecutor::new_testing(),
             recorder: SpanRecorder::default(),
+            authorization: None,
         }
     }
 
@@ -328,21 +440,85 @@ impl IOxSessionContext {
             inner,
             exec,
             recorder,
+            authorization: None,
+        }
+    }
+
+    /// Set the authorization of the caller (the value of its
+    /// `authorization` header), which is forwarded with any data written
+    /// by the query.
+    pub fn with_authorization(self, authorization: Option<&str>) -> Self {
+        Self {
+            authorization: authorization.map(Into::into),
+            ..self
         }
     }
 
+    /// Returns the authorization of the caller, if any.
+    pub fn authorization(&self) -> Option<&str> {
+        self.authorization.as_deref()
+    }
+
     /// returns a reference to the inner datafusion execution context
     pub fn inner(&self) -> &SessionContext {
         &self.inner
     }
 
+    /// Returns the destination of data written by the query, if any.
+    pub fn query_writer(&self) -> Option<Arc<dyn QueryWriter>> {
+        self.inner
+            .state()
+            .config()
+            .get_extension::<Option<Arc<dyn QueryWriter>>>()
+            .and_then(|writer| writer.as_ref().clone())
+    }
+
+    /// Returns the handler of data management statements, if any.
+    pub fn data_manager(&self) -> Option<Arc<dyn DataManager>> {
+        self.inner
+            .state()
+            .config()
+            .get_extension::<Option<Arc<dyn DataManager>>>()
+            .and_then(|manager| manager.as_ref().clone())
+    }
+
+    /// Returns the deadline of the query, if any.
+    fn deadline(&self) -> Option<QueryDeadline> {
+        self.inner
+            .state()
+            .config()
+            .get_extension::<Option<QueryDeadline>>()
+            .and_then(|deadline| *deadline)
+    }
+
+    /// Returns the cancellation of the query, if any.
+    fn cancellation(&self) -> Option<QueryCancellation> {
+        self.inner
+            .state()
+            .config()
+            .get_extension::<Option<QueryCancellation>>()
+            .and_then(|cancellation| cancellation.as_ref().clone())
+    }
+
+    /// Returns the token that cancels the query, if any.
+    pub fn cancellation_token(&self) -> Option<CancellationToken> {
+        self.cancellation()
+            .map(|cancellation| cancellation.token().clone())
+    }
+
+    /// Returns the statistics recorded for the query, if any.
+    pub fn query_stats(&self) -> Option<Arc<QueryStats>> {
+        self.inner.state().query_stats()
+    }
+
     /// Plan a SQL statement. This assumes that any tables referenced
     /// in the SQL have been registered with this context. Use
     /// `create_physical_plan` to actually execute the query.
     pub async fn sql_to_logical_plan(&self, sql: &str) -> Result<LogicalPlan> {
         let ctx = self.child_ctx("sql_to_logical_plan");
         debug!(text=%sql, "planning SQL query");
-        let plan = ctx.inner.state().create_logical_plan(sql).await?;
+        let state = ctx.inner.state();
+        let plan = ctx.limit_planning(state.create_logical_plan(sql)).await?;
         // ensure the plan does not contain unwanted statements
         let verifier = SQLOptions::new()
             .with_allow_ddl(false) // no CREATE ...
@@ -376,7 +552,12 @@ impl IOxSessionContext {
     ) -> Result<Arc<dyn ExecutionPlan>> {
         let mut ctx = self.child_ctx("create_physical_plan");
         debug!(text=%logical_plan.display_indent_schema(), "create_physical_plan: initial plan");
-        let physical_plan = ctx.inner.state().create_physical_plan(logical_plan).await?;
+        // Physical planning resolves the chunks of each table, which may
+        // query the ingesters, so it is subject to the limits of the query.
+        let state = ctx.inner.state();
+        let physical_plan = ctx
+            .limit_planning(state.create_physical_plan(logical_plan))
+            .await?;
 
         ctx.recorder.event("physical plan");
         debug!(text=%displayable(physical_plan.as_ref()).indent(false), "create_physical_plan: plan to run");
@@ -440,11 +621,12 @@ impl IOxSessionContext {
             .map(|span| span.child("execute_stream_partitioned"));
 
         let task_context = Arc::new(TaskContext::from(self.inner()));
+        let query_stats = self.query_stats();
 
         let stream = self
             .run(async move {
                 let stream = physical_plan.execute(partition, task_context)?;
-                Ok(TracedStream::new(stream, span, physical_plan))
+                Ok(TracedStream::new(stream, span, physical_plan).with_query_stats(query_stats))
             })
             .await?;
         // Wrap the resulting stream into `CrossRtStream`. This is required because polling the DataFusion result stream
@@ -453,8 +635,15 @@ impl IOxSessionContext {
         // requests timetouts (either for new requests, metrics or even for HTTP2 pings on the active connection).
         let schema = stream.schema();
         let stream = CrossRtStream::new_with_df_error_stream(stream, self.exec.clone());
-        let stream = RecordBatchStreamAdapter::new(schema, stream);
-        Ok(Box::pin(stream))
+        let stream = Box::pin(RecordBatchStreamAdapter::new(schema, stream));
+        let stream = match self.deadline() {
+            Some(deadline) => deadline.wrap_stream(stream),
+            None => stream,
+        };
+        match self.cancellation() {
+            Some(cancellation) => Ok(cancellation.wrap_stream(stream)),
+            None => Ok(stream),
+        }
     }
 
     /// Executes the SeriesSetPlans on the query executor, in
@@ -613,6 +802,32 @@ impl IOxSessionContext {
         }
     }
 
+    /// Executes this plan on the query pool, and returns the resulting
+    /// number of series
+    pub async fn to_series_cardinality(&self, plan: SeriesCardinalityPlan) -> Result<u64> {
+        let SeriesCardinalityPlan { known_count, plans } = plan;
+
+        let ctx = self.child_ctx("to_series_cardinality");
+        let batches = ctx.run_logical_plans(plans).await?;
+
+        let mut count = known_count;
+        for batch in batches {
+            let column = batch
+                .columns()
+                .first()
+                .and_then(|c| c.as_any().downcast_ref::<Int64Array>())
+                .ok_or_else(|| {
+                    DataFusionError::Internal(format!(
+                        "Series cardinality plan must produce a single Int64 column, got {:?}",
+                        batch.schema()
+                    ))
+                })?;
+            count += column.iter().flatten().map(|v| v as u64).sum::<u64>();
+        }
+
+        Ok(count)
+    }
+
     /// plans and runs the plans in parallel and collects the results
     /// run each plan in parallel and collect the results
     async fn run_logical_plans(&self, plans: Vec<LogicalPlan>) -> Result<Vec<RecordBatch>> {
@@ -638,13 +853,35 @@ impl IOxSessionContext {
         Ok(results)
     }
 
+    /// Runs the planning future `fut`, failing once the query is cancelled
+    /// or its deadline has passed.
+    ///
+    /// Unlike [`Self::run`], `fut` is run on the current runtime.
+    async fn limit_planning<Fut, T>(&self, fut: Fut) -> Result<T>
+    where
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        match (self.deadline(), self.cancellation()) {
+            (Some(deadline), Some(cancellation)) => cancellation.run(deadline.run(fut)).await,
+            (Some(deadline), None) => deadline.run(fut).await,
+            (None, Some(cancellation)) => cancellation.run(fut).await,
+            (None, None) => fut.await,
+        }
+    }
+
     /// Runs the provided future using this execution context
     pub async fn run<Fut, T>(&self, fut: Fut) -> Result<T>
     where
         Fut: std::future::Future<Output = Result<T>> + Send + 'static,
         T: Send + 'static,
     {
-        Self::run_inner(self.exec.clone(), fut).await
+        let fut = Self::run_inner(self.exec.clone(), fut);
+        match (self.deadline(), self.cancellation()) {
+            (Some(deadline), Some(cancellation)) => cancellation.run(deadline.run(fut)).await,
+            (Some(deadline), None) => deadline.run(fut).await,
+            (None, Some(cancellation)) => cancellation.run(fut).await,
+            (None, None) => fut.await,
+        }
     }
 
     async fn run_inner<Fut, T>(exec: DedicatedExecutor, fut: Fut) -> Result<T>
@@ -667,6 +904,7 @@ impl IOxSessionContext {
             self.exec.clone(),
             self.recorder.child(name),
         )
+        .with_authorization(self.authorization())
     }
 
     /// Record an event on the span recorder
@@ -702,6 +940,9 @@ pub trait SessionContextIOxExt {
 
     /// Get span context
     fn span_ctx(&self) -> Option<SpanContext>;
+
+    /// Get the statistics recorded for the query, if any.
+    fn query_stats(&self) -> Option<Arc<QueryStats>>;
 }
 
 impl SessionContextIOxExt for SessionState {
@@ -716,4 +957,10 @@ impl SessionContextIOxExt for SessionState {
             .get_extension::<Option<Span>>()
             .and_then(|span| span.as_ref().as_ref().map(|span| span.ctx.clone()))
     }
+
+    fn query_stats(&self) -> Option<Arc<QueryStats>> {
+        self.config()
+            .get_extension::<Option<Arc<QueryStats>>>()
+            .and_then(|stats| stats.as_ref().clone())
+    }
 }
@@ -0,0 +1,28 @@
+//! Support for statements that manage the data of a namespace, such as the
+//! InfluxQL `DELETE` and `DROP MEASUREMENT` statements.
+
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use data_types::DeletePredicate;
+use datafusion::error::Result;
+
+/// Applies data management operations to the namespace being queried.
+///
+/// A [`DataManager`] is attached to an [`IOxSessionContext`] using
+/// [`IOxSessionConfig::with_data_manager`].
+///
+/// [`IOxSessionContext`]: super::IOxSessionContext
+/// [`IOxSessionConfig::with_data_manager`]: super::IOxSessionConfig::with_data_manager
+#[async_trait]
+pub trait DataManager: Debug + Send + Sync {
+    /// Delete the rows of the table `table_name` that match `predicate`.
+    ///
+    /// Deleting from a table that does not exist is not an error.
+    async fn delete(&self, table_name: &str, predicate: &DeletePredicate) -> Result<()>;
+
+    /// Drop the table `table_name`, along with all of its data.
+    ///
+    /// Dropping a table that does not exist is not an error.
+    async fn drop_table(&self, table_name: &str) -> Result<()>;
+}
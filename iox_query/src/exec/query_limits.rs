@@ -0,0 +1,360 @@
+//! Limits on the resources a single query may use, and query cancellation.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use arrow::{datatypes::SchemaRef, record_batch::RecordBatch};
+use datafusion::{
+    error::{DataFusionError, Result},
+    execution::memory_pool::{MemoryConsumer, MemoryPool, MemoryReservation},
+    physical_plan::{RecordBatchStream, SendableRecordBatchStream},
+};
+use futures::{future::BoxFuture, FutureExt, Stream, StreamExt};
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+use super::query_stats::QueryStats;
+
+/// Error returned when a query runs longer than its timeout.
+///
+/// This is wrapped into [`DataFusionError::External`] and can be used to tell timeouts apart
+/// from other execution errors.
+#[derive(Debug, Clone, Copy)]
+pub struct QueryTimeoutError {
+    timeout: Duration,
+}
+
+impl QueryTimeoutError {
+    /// Create an error for a query that exceeded `timeout`.
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+
+    /// The timeout that was exceeded.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+impl std::fmt::Display for QueryTimeoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "query exceeded its timeout of {:?}", self.timeout)
+    }
+}
+
+impl std::error::Error for QueryTimeoutError {}
+
+/// Error returned when a query was cancelled.
+///
+/// This is wrapped into [`DataFusionError::External`] and can be used to tell cancellations apart
+/// from other execution errors.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QueryCancelledError;
+
+impl std::fmt::Display for QueryCancelledError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "query was cancelled")
+    }
+}
+
+impl std::error::Error for QueryCancelledError {}
+
+/// Point in time by which a query must have completed.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct QueryDeadline {
+    deadline: Instant,
+    timeout: Duration,
+}
+
+impl QueryDeadline {
+    /// Deadline `timeout` from now.
+    pub(crate) fn new(timeout: Duration) -> Self {
+        Self {
+            deadline: Instant::now() + timeout,
+            timeout,
+        }
+    }
+
+    pub(crate) fn error(&self) -> DataFusionError {
+        DataFusionError::External(Box::new(QueryTimeoutError::new(self.timeout)))
+    }
+
+    /// Run `fut`, failing with a [`QueryTimeoutError`] if it does not complete before the
+    /// deadline.
+    pub(crate) async fn run<Fut, T>(&self, fut: Fut) -> Result<T>
+    where
+        Fut: Future<Output = Result<T>>,
+    {
+        tokio::time::timeout_at(self.deadline, fut)
+            .await
+            .unwrap_or_else(|_| Err(self.error()))
+    }
+
+    /// Wrap `inner` so that it ends with a [`QueryTimeoutError`] once the deadline has passed.
+    pub(crate) fn wrap_stream(
+        &self,
+        inner: SendableRecordBatchStream,
+    ) -> SendableRecordBatchStream {
+        let this = *self;
+        Box::pin(AbortStream {
+            inner,
+            abort: async move {
+                tokio::time::sleep_until(this.deadline).await;
+                this.error()
+            }
+            .boxed(),
+            done: false,
+        })
+    }
+}
+
+/// Cancellation of a query, triggered through a [`CancellationToken`].
+#[derive(Debug, Clone)]
+pub(crate) struct QueryCancellation {
+    token: CancellationToken,
+}
+
+impl QueryCancellation {
+    pub(crate) fn new(token: CancellationToken) -> Self {
+        Self { token }
+    }
+
+    pub(crate) fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    pub(crate) fn error() -> DataFusionError {
+        DataFusionError::External(Box::new(QueryCancelledError))
+    }
+
+    /// Run `fut`, failing with a [`QueryCancelledError`] if the query is cancelled before it
+    /// completes. `fut` is dropped on cancellation.
+    pub(crate) async fn run<Fut, T>(&self, fut: Fut) -> Result<T>
+    where
+        Fut: Future<Output = Result<T>>,
+    {
+        tokio::select! {
+            biased;
+            _ = self.token.cancelled() => Err(Self::error()),
+            res = fut => res,
+        }
+    }
+
+    /// Wrap `inner` so that it ends with a [`QueryCancelledError`] once the query is cancelled.
+    pub(crate) fn wrap_stream(
+        &self,
+        inner: SendableRecordBatchStream,
+    ) -> SendableRecordBatchStream {
+        let token = self.token.clone();
+        Box::pin(AbortStream {
+            inner,
+            abort: async move {
+                token.cancelled().await;
+                Self::error()
+            }
+            .boxed(),
+            done: false,
+        })
+    }
+}
+
+/// Stream that ends with an error once `abort` resolves.
+struct AbortStream {
+    inner: SendableRecordBatchStream,
+    abort: BoxFuture<'static, DataFusionError>,
+    done: bool,
+}
+
+impl Stream for AbortStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+
+        if let Poll::Ready(e) = self.abort.poll_unpin(cx) {
+            self.done = true;
+            return Poll::Ready(Some(Err(e)));
+        }
+
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+impl RecordBatchStream for AbortStream {
+    fn schema(&self) -> SchemaRef {
+        self.inner.schema()
+    }
+}
+
+/// [`MemoryPool`] limiting the memory reserved by a single query, while still accounting all
+/// reservations against the shared pool of the executor.
+///
+/// The peak reservation is recorded into the [`QueryStats`] of the query, if any.
+#[derive(Debug)]
+pub(crate) struct QueryMemoryPool {
+    inner: Arc<dyn MemoryPool>,
+    limit: usize,
+    reserved: AtomicUsize,
+    stats: Option<Arc<QueryStats>>,
+}
+
+impl QueryMemoryPool {
+    pub(crate) fn new(
+        inner: Arc<dyn MemoryPool>,
+        limit: Option<usize>,
+        stats: Option<Arc<QueryStats>>,
+    ) -> Self {
+        Self {
+            inner,
+            limit: limit.unwrap_or(usize::MAX),
+            reserved: AtomicUsize::new(0),
+            stats,
+        }
+    }
+
+    fn record_reserved(&self, reserved: usize) {
+        if let Some(stats) = &self.stats {
+            stats.record_memory_reserved(reserved as u64);
+        }
+    }
+}
+
+impl MemoryPool for QueryMemoryPool {
+    fn register(&self, consumer: &MemoryConsumer) {
+        self.inner.register(consumer)
+    }
+
+    fn unregister(&self, consumer: &MemoryConsumer) {
+        self.inner.unregister(consumer)
+    }
+
+    fn grow(&self, reservation: &MemoryReservation, additional: usize) {
+        self.inner.grow(reservation, additional);
+        let reserved = self.reserved.fetch_add(additional, Ordering::Relaxed) + additional;
+        self.record_reserved(reserved);
+    }
+
+    fn shrink(&self, reservation: &MemoryReservation, shrink: usize) {
+        self.inner.shrink(reservation, shrink);
+        self.reserved.fetch_sub(shrink, Ordering::Relaxed);
+    }
+
+    fn try_grow(&self, reservation: &MemoryReservation, additional: usize) -> Result<()> {
+        let reserved = self
+            .reserved
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |reserved| {
+                let new_reserved = reserved.checked_add(additional)?;
+                (new_reserved <= self.limit).then_some(new_reserved)
+            })
+            .map_err(|reserved| {
+                DataFusionError::ResourcesExhausted(format!(
+                    "Failed to allocate additional {additional} bytes with {reserved} bytes \
+                     already allocated for this query - query memory limit of {} bytes reached",
+                    self.limit,
+                ))
+            })?;
+
+        self.inner.try_grow(reservation, additional).map_err(|e| {
+            self.reserved.fetch_sub(additional, Ordering::Relaxed);
+            e
+        })?;
+
+        self.record_reserved(reserved + additional);
+        Ok(())
+    }
+
+    fn reserved(&self) -> usize {
+        self.reserved.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use datafusion::execution::memory_pool::GreedyMemoryPool;
+
+    use super::*;
+
+    #[test]
+    fn test_memory_limit() {
+        let shared: Arc<dyn MemoryPool> = Arc::new(GreedyMemoryPool::new(100));
+        let stats = Arc::new(QueryStats::default());
+        let pool: Arc<dyn MemoryPool> = Arc::new(QueryMemoryPool::new(
+            Arc::clone(&shared),
+            Some(50),
+            Some(Arc::clone(&stats)),
+        ));
+
+        let mut r1 = MemoryConsumer::new("r1").register(&pool);
+        r1.try_grow(40).unwrap();
+        assert_eq!(pool.reserved(), 40);
+        assert_eq!(shared.reserved(), 40);
+
+        // exceeds the per-query limit but not the shared pool
+        let err = r1.try_grow(20).unwrap_err();
+        assert_matches!(err, DataFusionError::ResourcesExhausted(_));
+        assert_eq!(pool.reserved(), 40);
+        assert_eq!(shared.reserved(), 40);
+
+        r1.shrink(30);
+        r1.try_grow(20).unwrap();
+        assert_eq!(pool.reserved(), 30);
+
+        drop(r1);
+        assert_eq!(pool.reserved(), 0);
+        assert_eq!(shared.reserved(), 0);
+        assert_eq!(stats.peak_memory_bytes(), 40);
+    }
+
+    #[test]
+    fn test_memory_limit_shared_pool_exhausted() {
+        let shared: Arc<dyn MemoryPool> = Arc::new(GreedyMemoryPool::new(10));
+        let pool: Arc<dyn MemoryPool> =
+            Arc::new(QueryMemoryPool::new(Arc::clone(&shared), Some(50), None));
+
+        let mut r1 = MemoryConsumer::new("r1").register(&pool);
+        r1.try_grow(20).unwrap_err();
+        assert_eq!(pool.reserved(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_deadline() {
+        let deadline = QueryDeadline::new(Duration::from_millis(10));
+
+        let err = deadline
+            .run(futures::future::pending::<Result<()>>())
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            DataFusionError::External(e) if e.downcast_ref::<QueryTimeoutError>().is_some()
+        );
+
+        assert_eq!(deadline.run(async { Ok(1) }).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cancellation() {
+        let token = CancellationToken::new();
+        let cancellation = QueryCancellation::new(token.clone());
+
+        assert_eq!(cancellation.run(async { Ok(1) }).await.unwrap(), 1);
+
+        token.cancel();
+        let err = cancellation.run(async { Ok(1) }).await.unwrap_err();
+        assert_matches!(
+            err,
+            DataFusionError::External(e) if e.downcast_ref::<QueryCancelledError>().is_some()
+        );
+    }
+}
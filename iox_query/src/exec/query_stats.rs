@@ -0,0 +1,184 @@
+//! Statistics about the execution of a single query.
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use datafusion::physical_plan::{metrics::MetricValue, ExecutionPlan};
+use parquet_file::bloom_filter::BLOOM_FILTER_ROW_GROUPS_PRUNED_METRIC;
+
+/// Name of the DataFusion metric counting the bytes read from parquet files.
+const BYTES_SCANNED_METRIC: &str = "bytes_scanned";
+
+/// Name of the DataFusion metric counting the parquet row groups skipped using their statistics.
+const ROW_GROUPS_PRUNED_METRIC: &str = "row_groups_pruned";
+
+/// Statistics collected while a query is planned and executed.
+///
+/// The statistics are shared between the components taking part in the query and are updated
+/// while the query is running, so they can be inspected before it completes.
+#[derive(Debug, Default)]
+pub struct QueryStats {
+    peak_memory_bytes: AtomicU64,
+    rows_scanned: AtomicU64,
+    bytes_scanned: AtomicU64,
+    partitions_read: AtomicU64,
+    partitions_pruned: AtomicU64,
+    parquet_files_read: AtomicU64,
+    parquet_files_pruned: AtomicU64,
+    row_groups_pruned: AtomicU64,
+    ingester_latency_nanos: AtomicU64,
+}
+
+impl QueryStats {
+    /// Highest number of bytes reserved by the query at any point in time.
+    pub fn peak_memory_bytes(&self) -> u64 {
+        self.peak_memory_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Number of rows read from the leaves of the executed plans.
+    pub fn rows_scanned(&self) -> u64 {
+        self.rows_scanned.load(Ordering::Relaxed)
+    }
+
+    /// Number of bytes read from parquet files.
+    pub fn bytes_scanned(&self) -> u64 {
+        self.bytes_scanned.load(Ordering::Relaxed)
+    }
+
+    /// Number of partitions that were considered by the query after pruning.
+    pub fn partitions_read(&self) -> u64 {
+        self.partitions_read.load(Ordering::Relaxed)
+    }
+
+    /// Number of partitions that were pruned.
+    pub fn partitions_pruned(&self) -> u64 {
+        self.partitions_pruned.load(Ordering::Relaxed)
+    }
+
+    /// Number of parquet files that were considered by the query after pruning.
+    pub fn parquet_files_read(&self) -> u64 {
+        self.parquet_files_read.load(Ordering::Relaxed)
+    }
+
+    /// Number of parquet files that were pruned.
+    pub fn parquet_files_pruned(&self) -> u64 {
+        self.parquet_files_pruned.load(Ordering::Relaxed)
+    }
+
+    /// Number of parquet row groups skipped while scanning, using either their statistics or the
+    /// bloom filters written for tag columns.
+    pub fn row_groups_pruned(&self) -> u64 {
+        self.row_groups_pruned.load(Ordering::Relaxed)
+    }
+
+    /// Duration of the slowest ingester request, if the ingesters were queried.
+    pub fn ingester_latency(&self) -> Option<Duration> {
+        match self.ingester_latency_nanos.load(Ordering::Relaxed) {
+            0 => None,
+            nanos => Some(Duration::from_nanos(nanos)),
+        }
+    }
+
+    /// Record that the query currently reserves `bytes` of memory.
+    pub fn record_memory_reserved(&self, bytes: u64) {
+        self.peak_memory_bytes.fetch_max(bytes, Ordering::Relaxed);
+    }
+
+    /// Record the outcome of partition pruning.
+    pub fn record_partitions(&self, read: u64, pruned: u64) {
+        self.partitions_read.fetch_add(read, Ordering::Relaxed);
+        self.partitions_pruned.fetch_add(pruned, Ordering::Relaxed);
+    }
+
+    /// Record the outcome of parquet file pruning.
+    pub fn record_parquet_files(&self, read: u64, pruned: u64) {
+        self.parquet_files_read.fetch_add(read, Ordering::Relaxed);
+        self.parquet_files_pruned
+            .fetch_add(pruned, Ordering::Relaxed);
+    }
+
+    /// Record the duration of a request to the ingesters.
+    pub fn record_ingester_latency(&self, latency: Duration) {
+        // a zero latency is the "not queried" sentinel, so round up
+        let nanos = (latency.as_nanos() as u64).max(1);
+        self.ingester_latency_nanos
+            .fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    /// Record the rows and bytes scanned, and the row groups pruned, by the leaves of
+    /// `physical_plan`.
+    ///
+    /// This reads a snapshot of the DataFusion metrics, so it should only be called once the plan
+    /// has been executed.
+    pub(crate) fn record_plan(&self, physical_plan: &dyn ExecutionPlan) {
+        let children = physical_plan.children();
+        if !children.is_empty() {
+            for child in children {
+                self.record_plan(child.as_ref());
+            }
+            return;
+        }
+
+        let Some(metrics) = physical_plan.metrics() else {
+            return;
+        };
+
+        if let Some(rows) = metrics.output_rows() {
+            self.rows_scanned.fetch_add(rows as u64, Ordering::Relaxed);
+        }
+
+        let count = |metric_name: &str| {
+            metrics
+                .iter()
+                .filter_map(|metric| match metric.value() {
+                    MetricValue::Count { name, count } if name == metric_name => {
+                        Some(count.value() as u64)
+                    }
+                    _ => None,
+                })
+                .sum::<u64>()
+        };
+        self.bytes_scanned
+            .fetch_add(count(BYTES_SCANNED_METRIC), Ordering::Relaxed);
+        self.row_groups_pruned
+            .fetch_add(count(ROW_GROUPS_PRUNED_METRIC), Ordering::Relaxed);
+        self.row_groups_pruned.fetch_add(
+            count(BLOOM_FILTER_ROW_GROUPS_PRUNED_METRIC),
+            Ordering::Relaxed,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ingester_latency() {
+        let stats = QueryStats::default();
+        assert_eq!(stats.ingester_latency(), None);
+
+        stats.record_ingester_latency(Duration::from_millis(20));
+        stats.record_ingester_latency(Duration::from_millis(10));
+        assert_eq!(stats.ingester_latency(), Some(Duration::from_millis(20)));
+
+        let stats = QueryStats::default();
+        stats.record_ingester_latency(Duration::ZERO);
+        assert_eq!(stats.ingester_latency(), Some(Duration::from_nanos(1)));
+    }
+
+    #[test]
+    fn test_pruning() {
+        let stats = QueryStats::default();
+        stats.record_partitions(2, 1);
+        stats.record_partitions(3, 0);
+        stats.record_parquet_files(4, 5);
+
+        assert_eq!(stats.partitions_read(), 5);
+        assert_eq!(stats.partitions_pruned(), 1);
+        assert_eq!(stats.parquet_files_read(), 4);
+        assert_eq!(stats.parquet_files_pruned(), 5);
+    }
+}
@@ -14,6 +14,8 @@ use observability_deps::tracing::debug;
 use std::{fmt, sync::Arc};
 use trace::span::{Span, SpanRecorder};
 
+use super::query_stats::QueryStats;
+
 const PER_PARTITION_TRACING_ENABLE_ENV: &str = "INFLUXDB_IOX_PER_PARTITION_TRACING";
 fn per_partition_tracing() -> bool {
     use std::sync::atomic::{AtomicU8, Ordering};
@@ -35,11 +37,12 @@ fn per_partition_tracing() -> bool {
 }
 
 /// Stream wrapper that records DataFusion `MetricSets` into IOx
-/// [`Span`]s when it is dropped.
+/// [`Span`]s and [`QueryStats`] when it is dropped.
 pub(crate) struct TracedStream {
     inner: SendableRecordBatchStream,
     span_recorder: SpanRecorder,
     physical_plan: Arc<dyn ExecutionPlan>,
+    query_stats: Option<Arc<QueryStats>>,
 }
 
 impl TracedStream {
@@ -54,6 +57,15 @@ impl TracedStream {
             inner,
             span_recorder: SpanRecorder::new(span),
             physical_plan,
+            query_stats: None,
+        }
+    }
+
+    /// Also record the rows and bytes scanned by `physical_plan` into `query_stats` when dropped.
+    pub(crate) fn with_query_stats(self, query_stats: Option<Arc<QueryStats>>) -> Self {
+        Self {
+            query_stats,
+            ..self
         }
     }
 }
@@ -77,6 +89,10 @@ impl futures::Stream for TracedStream {
 
 impl Drop for TracedStream {
     fn drop(&mut self) {
+        if let Some(query_stats) = &self.query_stats {
+            query_stats.record_plan(self.physical_plan.as_ref());
+        }
+
         if let Some(span) = self.span_recorder.span() {
             let default_end_time = Utc::now();
             let per_partition_tracing = per_partition_tracing();
@@ -0,0 +1,37 @@
+//! Support for queries that write their results, such as the InfluxQL
+//! `SELECT ... INTO` statement.
+
+use std::fmt::Debug;
+
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use datafusion::error::Result;
+
+/// A sink for data written by a query.
+///
+/// A [`QueryWriter`] is attached to an [`IOxSessionContext`] for a single
+/// namespace, using [`IOxSessionConfig::with_query_writer`].
+///
+/// [`IOxSessionContext`]: super::IOxSessionContext
+/// [`IOxSessionConfig::with_query_writer`]: super::IOxSessionConfig::with_query_writer
+#[async_trait]
+pub trait QueryWriter: Debug + Send + Sync {
+    /// The name of the namespace being queried, which is the default
+    /// namespace for writes.
+    fn namespace_name(&self) -> &str;
+
+    /// Write `batches` to the table `table_name` within `namespace`.
+    ///
+    /// The schema of each batch must be a valid IOx schema, which determines
+    /// whether a column is written as a tag, field or the timestamp.
+    ///
+    /// The `authorization` of the caller, if any, is forwarded with the
+    /// write, so that it is authorized as the caller.
+    async fn write(
+        &self,
+        namespace: &str,
+        table_name: &str,
+        batches: Vec<RecordBatch>,
+        authorization: Option<&str>,
+    ) -> Result<()>;
+}
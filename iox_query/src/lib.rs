@@ -24,7 +24,7 @@ use arrow::{
     record_batch::RecordBatch,
 };
 use async_trait::async_trait;
-use data_types::{ChunkId, ChunkOrder, TransitionPartitionId};
+use data_types::{ChunkId, ChunkOrder, DeletePredicate, TransitionPartitionId};
 use datafusion::{
     error::DataFusionError,
     physical_plan::{SendableRecordBatchStream, Statistics},
@@ -88,6 +88,14 @@ pub trait QueryChunk: Debug + Send + Sync + 'static {
     /// The engine assume that minimal work shall be performed to gather the `QueryChunkData`.
     fn data(&self) -> QueryChunkData;
 
+    /// Delete predicates that apply to this chunk.
+    ///
+    /// Rows matching any of these predicates are removed from the output of
+    /// the chunk scan, before de-duplication.
+    fn delete_predicates(&self) -> &[Arc<DeletePredicate>] {
+        &[]
+    }
+
     /// Returns chunk type. Useful in tests and debug logs.
     fn chunk_type(&self) -> &str;
 
@@ -180,13 +188,17 @@ pub trait QueryNamespace: Debug + Send + Sync {
     /// Returns `None` if now retention policy was defined.
     fn retention_time_ns(&self) -> Option<i64>;
 
-    /// Record that particular type of query was run / planned
+    /// Record that particular type of query was run / planned using `ctx`
+    ///
+    /// Returns an error if the query is rejected, e.g. because too many queries are already
+    /// running against this namespace. Rejected queries are recorded as failed.
     fn record_query(
         &self,
+        ctx: &IOxSessionContext,
         span_ctx: Option<&SpanContext>,
         query_type: &'static str,
         query_text: QueryText,
-    ) -> QueryCompletedToken;
+    ) -> Result<QueryCompletedToken, DataFusionError>;
 
     /// Returns a new execution context suitable for running queries
     fn new_query_context(&self, span_ctx: Option<SpanContext>) -> IOxSessionContext;
@@ -268,6 +280,10 @@ where
         self.as_ref().data()
     }
 
+    fn delete_predicates(&self) -> &[Arc<DeletePredicate>] {
+        self.as_ref().delete_predicates()
+    }
+
     fn chunk_type(&self) -> &str {
         self.as_ref().chunk_type()
     }
@@ -311,6 +327,10 @@ impl QueryChunk for Arc<dyn QueryChunk> {
         self.as_ref().data()
     }
 
+    fn delete_predicates(&self) -> &[Arc<DeletePredicate>] {
+        self.as_ref().delete_predicates()
+    }
+
     fn chunk_type(&self) -> &str {
         self.as_ref().chunk_type()
     }
@@ -0,0 +1,267 @@
+use std::sync::Arc;
+
+use datafusion::{
+    common::tree_node::{Transformed, TreeNode},
+    config::ConfigOptions,
+    datasource::physical_plan::ParquetExec,
+    error::Result,
+    physical_optimizer::PhysicalOptimizerRule,
+    physical_plan::ExecutionPlan,
+};
+use object_store::DynObjectStore;
+use parquet_file::bloom_filter::BloomFilterReaderFactory;
+
+use crate::{provider::PartitionedFileExt, QueryChunkData};
+
+/// Prune the row groups of parquet files using the bloom filters written for
+/// their tag columns, see [`BloomFilterReaderFactory`].
+///
+/// This must run after all rules that re-create [`ParquetExec`] nodes, as the
+/// reader factory is not carried over to them.
+#[derive(Debug, Default)]
+pub struct BloomFilterPruning;
+
+impl PhysicalOptimizerRule for BloomFilterPruning {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        _config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        plan.transform_up(&|plan| {
+            let Some(parquet_exec) = plan.as_any().downcast_ref::<ParquetExec>() else {
+                return Ok(Transformed::No(plan));
+            };
+            let Some(predicate) = parquet_exec.predicate() else {
+                return Ok(Transformed::No(plan));
+            };
+            let Some(factory) = object_store(parquet_exec)
+                .and_then(|store| BloomFilterReaderFactory::try_new(store, predicate))
+            else {
+                return Ok(Transformed::No(plan));
+            };
+
+            // Clone the exec to keep its metadata size hint and options.
+            let new_exec = parquet_exec
+                .clone()
+                .with_parquet_file_reader_factory(Arc::new(factory));
+            Ok(Transformed::Yes(Arc::new(new_exec)))
+        })
+    }
+
+    fn name(&self) -> &str {
+        "bloom_filter_pruning"
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+/// Returns the object store holding the files scanned by `parquet_exec`.
+fn object_store(parquet_exec: &ParquetExec) -> Option<Arc<DynObjectStore>> {
+    parquet_exec
+        .base_config()
+        .file_groups
+        .iter()
+        .flatten()
+        .find_map(|file| {
+            let ext = file
+                .extensions
+                .as_ref()
+                .and_then(|any| any.downcast_ref::<PartitionedFileExt>())?;
+            match ext.chunk.data() {
+                QueryChunkData::Parquet(input) => Some(input.object_store),
+                QueryChunkData::RecordBatches(_) => None,
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::{
+        array::{ArrayRef, DictionaryArray},
+        datatypes::{Int32Type, SchemaRef},
+        record_batch::RecordBatch,
+    };
+    use data_types::{CompactionLevel, NamespaceId, TableId};
+    use datafusion::{
+        execution::context::SessionContext,
+        logical_expr::Operator,
+        physical_plan::{
+            collect,
+            expressions::{BinaryExpr, Column, Literal},
+            PhysicalExpr,
+        },
+        scalar::ScalarValue,
+    };
+    use iox_time::Time;
+    use parquet::{
+        arrow::ArrowWriter,
+        file::{metadata::KeyValue, properties::WriterProperties},
+        schema::types::ColumnPath,
+    };
+    use parquet_file::{
+        bloom_filter::BLOOM_FILTER_ROW_GROUPS_PRUNED_METRIC,
+        metadata::{IoxMetadata, METADATA_KEY},
+    };
+
+    use super::*;
+    use crate::{
+        physical_optimizer::test_util::OptimizationTest, provider::chunks_to_physical_nodes,
+        test::TestChunk,
+    };
+
+    #[test]
+    fn test_plan_unchanged() {
+        let chunk = TestChunk::new("table")
+            .with_tag_column("tag")
+            .with_dummy_parquet_file();
+        let schema = chunk.schema().as_arrow();
+        let plan = chunks_to_physical_nodes(&schema, None, vec![Arc::new(chunk)], 1);
+        let plan = with_predicate(plan, predicate(&schema, Operator::Eq, "foo"));
+
+        let opt = BloomFilterPruning;
+        insta::assert_yaml_snapshot!(
+            OptimizationTest::new(plan, opt),
+            @r###"
+        ---
+        input:
+          - " UnionExec"
+          - "   ParquetExec: file_groups={1 group: [[0.parquet]]}, predicate=tag@0 = foo, pruning_predicate=tag_min@0 <= foo AND foo <= tag_max@1"
+        output:
+          Ok:
+            - " UnionExec"
+            - "   ParquetExec: file_groups={1 group: [[0.parquet]]}, predicate=tag@0 = foo, pruning_predicate=tag_min@0 <= foo AND foo <= tag_max@1"
+        "###
+        );
+    }
+
+    #[test]
+    fn test_factory_requires_tag_guarantee() {
+        let chunk = TestChunk::new("table")
+            .with_tag_column("tag")
+            .with_dummy_parquet_file();
+        let schema = chunk.schema().as_arrow();
+        let plan = chunks_to_physical_nodes(&schema, None, vec![Arc::new(chunk)], 1);
+        let parquet_exec = plan.children().remove(0);
+        let parquet_exec = parquet_exec.as_any().downcast_ref::<ParquetExec>().unwrap();
+        let store = object_store(parquet_exec).expect("chunk has an object store");
+
+        let eq = predicate(&schema, Operator::Eq, "foo");
+        assert!(BloomFilterReaderFactory::try_new(Arc::clone(&store), &eq).is_some());
+
+        let not_eq = predicate(&schema, Operator::NotEq, "foo");
+        assert!(BloomFilterReaderFactory::try_new(store, &not_eq).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prune_row_groups() {
+        // The row group without "b" is skipped.
+        assert_eq!(scan(true, Operator::Eq).await, (2, 1));
+    }
+
+    #[tokio::test]
+    async fn test_no_pruning_without_metadata_flag() {
+        assert_eq!(scan(false, Operator::Eq).await, (4, 0));
+    }
+
+    #[tokio::test]
+    async fn test_no_pruning_without_tag_guarantee() {
+        assert_eq!(scan(true, Operator::NotEq).await, (4, 0));
+    }
+
+    /// Scan a file of two row groups, with tag values `a, c` and `b, d`,
+    /// filtered by `tag <op> 'b'` after running the rule, returning the
+    /// number of rows read and row groups pruned.
+    async fn scan(bloom_filters: bool, op: Operator) -> (usize, usize) {
+        let meta = IoxMetadata {
+            object_store_id: Default::default(),
+            creation_timestamp: Time::from_timestamp_nanos(42),
+            namespace_id: NamespaceId::new(1),
+            namespace_name: "bananas".into(),
+            table_id: TableId::new(3),
+            table_name: "table".into(),
+            partition_key: "potato".into(),
+            compaction_level: CompactionLevel::FileNonOverlapped,
+            sort_key: None,
+            max_l0_created_at: Time::from_timestamp_nanos(42),
+            bloom_filters,
+        };
+
+        // Bloom filters are always written, only the metadata flag differs.
+        let props = WriterProperties::builder()
+            .set_key_value_metadata(Some(vec![KeyValue {
+                key: METADATA_KEY.to_string(),
+                value: Some(meta.to_base64().unwrap()),
+            }]))
+            .set_max_row_group_size(2)
+            .set_column_bloom_filter_enabled(ColumnPath::from("tag"), true)
+            .set_column_bloom_filter_ndv(ColumnPath::from("tag"), 100)
+            .build();
+
+        let chunk = TestChunk::new("table").with_tag_column("tag");
+        let schema = chunk.schema().as_arrow();
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![Arc::new(
+                vec!["a", "c", "b", "d"]
+                    .into_iter()
+                    .collect::<DictionaryArray<Int32Type>>(),
+            ) as ArrayRef],
+        )
+        .unwrap();
+
+        let mut buf = vec![];
+        let mut writer = ArrowWriter::try_new(&mut buf, Arc::clone(&schema), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let chunk = chunk.with_parquet_file(buf).await;
+
+        let plan = chunks_to_physical_nodes(&schema, None, vec![Arc::new(chunk)], 1);
+        let plan = with_predicate(plan, predicate(&schema, op, "b"));
+        let plan = BloomFilterPruning
+            .optimize(plan, &ConfigOptions::default())
+            .unwrap();
+
+        let ctx = SessionContext::new();
+        let batches = collect(Arc::clone(&plan), ctx.task_ctx()).await.unwrap();
+        let rows = batches.iter().map(|b| b.num_rows()).sum();
+        let pruned = plan
+            .children()
+            .remove(0)
+            .metrics()
+            .unwrap()
+            .sum_by_name(BLOOM_FILTER_ROW_GROUPS_PRUNED_METRIC)
+            .map(|v| v.as_usize())
+            .unwrap_or_default();
+
+        (rows, pruned)
+    }
+
+    /// Attach `predicate` to the [`ParquetExec`] nodes of `plan`, as the
+    /// predicate pushdown does.
+    fn with_predicate(
+        plan: Arc<dyn ExecutionPlan>,
+        predicate: Arc<dyn PhysicalExpr>,
+    ) -> Arc<dyn ExecutionPlan> {
+        plan.transform_down(&|plan| {
+            let Some(parquet_exec) = plan.as_any().downcast_ref::<ParquetExec>() else {
+                return Ok(Transformed::No(plan));
+            };
+            Ok(Transformed::Yes(Arc::new(ParquetExec::new(
+                parquet_exec.base_config().clone(),
+                Some(Arc::clone(&predicate)),
+                None,
+            ))))
+        })
+        .unwrap()
+    }
+
+    fn predicate(schema: &SchemaRef, op: Operator, value: &str) -> Arc<dyn PhysicalExpr> {
+        Arc::new(BinaryExpr::new(
+            Arc::new(Column::new_with_schema("tag", schema).unwrap()),
+            op,
+            Arc::new(Literal::new(ScalarValue::from(value))),
+        ))
+    }
+}
@@ -13,7 +13,7 @@ use observability_deps::tracing::debug;
 use schema::sort::SortKey;
 
 use crate::{
-    provider::{PartitionedFileExt, RecordBatchesExec},
+    provider::{DeletesExec, PartitionedFileExt, RecordBatchesExec},
     QueryChunk,
 };
 
@@ -141,6 +141,16 @@ impl ExecutionPlanVisitor for ExtractChunksVisitor {
                     self.add_chunk(Arc::clone(&ext.chunk));
                 }
             }
+        } else if let Some(deletes_exec) = plan_any.downcast_ref::<DeletesExec>() {
+            self.add_schema_from_exec(deletes_exec).map_err(|e| {
+                DataFusionError::Context("add schema from DeletesExec".to_owned(), Box::new(e))
+            })?;
+
+            self.add_sort_key(deletes_exec.output_sort_key_memo())?;
+            self.add_chunk(Arc::clone(deletes_exec.chunk()));
+
+            // the scan below is recreated from the chunk
+            return Ok(false);
         } else if let Some(empty_exec) = plan_any.downcast_ref::<EmptyExec>() {
             // should not produce dummy data
             if empty_exec.produce_one_row() {
@@ -169,7 +179,7 @@ impl ExecutionPlanVisitor for ExtractChunksVisitor {
 mod tests {
     use crate::{provider::chunks_to_physical_nodes, test::TestChunk, util::df_physical_expr};
     use arrow::datatypes::{DataType, Field, Schema as ArrowSchema};
-    use data_types::ChunkId;
+    use data_types::{ChunkId, DeletePredicate, TimestampRange};
     use datafusion::{
         common::tree_node::{Transformed, TreeNode},
         physical_plan::{expressions::Literal, filter::FilterExec},
@@ -221,6 +231,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_roundtrip_deletes() {
+        let chunk1 = chunk(1).with_dummy_parquet_file();
+        let chunk2 = chunk(2)
+            .with_dummy_parquet_file()
+            .with_delete_predicate(DeletePredicate {
+                range: TimestampRange::new(0, 100),
+                exprs: vec![],
+            });
+        let sort_key = Some(sort_key());
+        assert_roundtrip(
+            chunk1.schema().as_arrow(),
+            vec![Arc::new(chunk1), Arc::new(chunk2)],
+            sort_key,
+        );
+    }
+
     #[test]
     fn test_different_schemas() {
         let some_chunk = chunk(1);
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use datafusion::{execution::context::SessionState, physical_optimizer::PhysicalOptimizerRule};
 
 use self::{
+    bloom_filter_pruning::BloomFilterPruning,
     combine_chunks::CombineChunks,
     dedup::{
         dedup_null_columns::DedupNullColumns, dedup_sort_order::DedupSortOrder,
@@ -14,6 +15,7 @@ use self::{
     union::{nested_union::NestedUnion, one_union::OneUnion},
 };
 
+mod bloom_filter_pruning;
 mod chunk_extraction;
 mod combine_chunks;
 mod dedup;
@@ -41,6 +43,7 @@ pub fn register_iox_physical_optimizers(state: SessionState) -> SessionState {
         Arc::new(ParquetSortness) as _,
         Arc::new(NestedUnion),
         Arc::new(OneUnion),
+        Arc::new(BloomFilterPruning),
     ];
     optimizers.append(&mut state.physical_optimizers().to_vec());
 
@@ -1,3 +1,4 @@
 pub mod fieldlist;
+pub mod series_cardinality;
 pub mod seriesset;
 pub mod stringset;
@@ -0,0 +1,39 @@
+use datafusion::logical_expr::LogicalPlan;
+
+/// A plan which produces the number of distinct series (measurement and tag
+/// set) in a namespace.
+///
+/// known_count is the number of series found at plan time, which is added
+/// to the count produced by each of the plans. Each plan must produce a
+/// single Int64 column, the sum of which is its number of series.
+#[derive(Debug, Default)]
+pub struct SeriesCardinalityPlan {
+    /// Number of series known at plan time
+    pub known_count: u64,
+    /// General plans
+    pub plans: Vec<LogicalPlan>,
+}
+
+impl From<LogicalPlan> for SeriesCardinalityPlan {
+    /// Create a plan from a single DataFusion LogicalPlan node, which must
+    /// produce the count in the correct format
+    fn from(plan: LogicalPlan) -> Self {
+        Self {
+            known_count: 0,
+            plans: vec![plan],
+        }
+    }
+}
+
+impl SeriesCardinalityPlan {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append the other plan to ourselves
+    pub fn append_other(mut self, other: Self) -> Self {
+        self.known_count += other.known_count;
+        self.plans.extend(other.plans);
+        self
+    }
+}
@@ -33,6 +33,7 @@ use snafu::{ResultExt, Snafu};
 
 mod adapter;
 mod deduplicate;
+mod deletes_exec;
 pub mod overlap;
 mod physical;
 mod record_batch_exec;
@@ -40,6 +41,7 @@ pub use self::overlap::group_potential_duplicates;
 pub use deduplicate::{DeduplicateExec, RecordBatchDeduplicator};
 pub(crate) use physical::{chunks_to_physical_nodes, PartitionedFileExt};
 
+pub(crate) use deletes_exec::DeletesExec;
 pub(crate) use record_batch_exec::RecordBatchesExec;
 
 #[derive(Debug, Snafu)]
@@ -0,0 +1,251 @@
+//! Implementation of a DataFusion PhysicalPlan node that removes deleted rows from a single chunk.
+
+use std::{fmt, sync::Arc};
+
+use arrow::{
+    datatypes::{Schema as ArrowSchema, SchemaRef},
+    record_batch::RecordBatch,
+};
+use datafusion::{
+    error::DataFusionError,
+    execution::context::TaskContext,
+    physical_plan::{
+        expressions::{Column, PhysicalSortExpr},
+        metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet},
+        stream::RecordBatchStreamAdapter,
+        DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning, SendableRecordBatchStream,
+        Statistics,
+    },
+};
+use futures::StreamExt;
+use observability_deps::tracing::trace;
+use predicate::delete_predicate::filter_deleted_rows;
+use schema::{sort::SortKey, TIME_COLUMN_NAME};
+
+use crate::QueryChunk;
+
+/// Removes the rows matching the [delete predicates] of a single chunk from the output of the chunk scan.
+///
+/// The chunk scan (`input`) may contain additional columns that are needed to evaluate the delete predicates but
+/// are not part of the output schema. These are stripped after the predicates have been applied.
+///
+///
+/// [delete predicates]: QueryChunk::delete_predicates
+#[derive(Debug)]
+pub(crate) struct DeletesExec {
+    /// The chunk the delete predicates are taken from.
+    chunk: Arc<dyn QueryChunk>,
+
+    /// Scan of the chunk.
+    input: Arc<dyn ExecutionPlan>,
+
+    /// Output schema, a prefix of the input schema.
+    schema: SchemaRef,
+
+    /// Sort key that was passed to [`chunks_to_physical_nodes`].
+    ///
+    /// This is NOT used to set the output ordering. It is only here to recover this information later.
+    ///
+    ///
+    /// [`chunks_to_physical_nodes`]: super::physical::chunks_to_physical_nodes
+    output_sort_key_memo: Option<SortKey>,
+
+    /// Output ordering, derived from the input ordering.
+    output_ordering: Option<Vec<PhysicalSortExpr>>,
+
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl DeletesExec {
+    pub(crate) fn new(
+        chunk: Arc<dyn QueryChunk>,
+        input: Arc<dyn ExecutionPlan>,
+        schema: SchemaRef,
+        output_sort_key_memo: Option<SortKey>,
+    ) -> Self {
+        let output_ordering = output_ordering(input.as_ref(), &schema);
+
+        Self {
+            chunk,
+            input,
+            schema,
+            output_sort_key_memo,
+            output_ordering,
+            metrics: ExecutionPlanMetricsSet::new(),
+        }
+    }
+
+    /// The chunk this node removes deleted rows from.
+    pub(crate) fn chunk(&self) -> &Arc<dyn QueryChunk> {
+        &self.chunk
+    }
+
+    /// Sort key that was passed to [`chunks_to_physical_nodes`].
+    ///
+    ///
+    /// [`chunks_to_physical_nodes`]: super::physical::chunks_to_physical_nodes
+    pub(crate) fn output_sort_key_memo(&self) -> Option<&SortKey> {
+        self.output_sort_key_memo.as_ref()
+    }
+}
+
+/// Compute the schema needed to scan `chunk` in order to evaluate its delete predicates and produce `schema`.
+///
+/// The additional columns are appended to `schema`, keeping the output columns at the same positions.
+pub(crate) fn delete_scan_schema(schema: &SchemaRef, chunk: &dyn QueryChunk) -> SchemaRef {
+    let chunk_schema = chunk.schema().as_arrow();
+
+    let mut fields = schema.fields().to_vec();
+    let needed = chunk
+        .delete_predicates()
+        .iter()
+        .flat_map(|p| p.exprs.iter().map(|e| e.column.as_str()))
+        .chain(std::iter::once(TIME_COLUMN_NAME));
+
+    for name in needed {
+        if fields.iter().any(|f| f.name() == name) {
+            continue;
+        }
+        if let Ok(field) = chunk_schema.field_with_name(name) {
+            fields.push(Arc::new(field.clone()));
+        }
+    }
+
+    if fields.len() == schema.fields().len() {
+        return Arc::clone(schema);
+    }
+
+    Arc::new(ArrowSchema::new_with_metadata(
+        fields,
+        schema.metadata().clone(),
+    ))
+}
+
+/// Retain the prefix of the input ordering that only refers to columns of the output `schema`.
+fn output_ordering(input: &dyn ExecutionPlan, schema: &SchemaRef) -> Option<Vec<PhysicalSortExpr>> {
+    let ordering = input
+        .output_ordering()?
+        .iter()
+        .take_while(|sort_expr| {
+            sort_expr
+                .expr
+                .as_any()
+                .downcast_ref::<Column>()
+                .map(|c| c.index() < schema.fields().len())
+                .unwrap_or(false)
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    (!ordering.is_empty()).then_some(ordering)
+}
+
+impl ExecutionPlan for DeletesExec {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(self.input.output_partitioning().partition_count())
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        self.output_ordering.as_deref()
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![Arc::clone(&self.input)]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> datafusion::error::Result<Arc<dyn ExecutionPlan>> {
+        assert_eq!(children.len(), 1);
+
+        Ok(Arc::new(Self::new(
+            Arc::clone(&self.chunk),
+            Arc::clone(&children[0]),
+            Arc::clone(&self.schema),
+            self.output_sort_key_memo.clone(),
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> datafusion::error::Result<SendableRecordBatchStream> {
+        trace!(partition, "Start DeletesExec::execute");
+
+        let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
+        let schema = self.schema();
+        let chunk = Arc::clone(&self.chunk);
+        let num_output_columns = schema.fields().len();
+
+        let stream = self.input.execute(partition, context)?.map({
+            let schema = Arc::clone(&schema);
+            move |batch| {
+                let _timer = baseline_metrics.elapsed_compute().timer();
+
+                let mut batch = batch?;
+                for predicate in chunk.delete_predicates() {
+                    batch = filter_deleted_rows(predicate, &batch)?;
+                }
+
+                // Strip the columns that were only needed to evaluate the delete predicates.
+                let batch = if batch.num_columns() == num_output_columns {
+                    batch
+                } else {
+                    RecordBatch::try_new(
+                        Arc::clone(&schema),
+                        batch.columns()[..num_output_columns].to_vec(),
+                    )
+                    .map_err(DataFusionError::ArrowError)?
+                };
+
+                baseline_metrics.record_output(batch.num_rows());
+                Ok(batch)
+            }
+        });
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(schema, stream)))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Statistics {
+        // The number of deleted rows is unknown until the data is read.
+        let input = self.input.statistics();
+        Statistics {
+            num_rows: input.num_rows,
+            total_byte_size: None,
+            column_statistics: input.column_statistics.map(|mut stats| {
+                stats.truncate(self.schema.fields().len());
+                stats
+            }),
+            is_exact: false,
+        }
+    }
+}
+
+impl DisplayAs for DeletesExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "DeletesExec: predicates={}",
+                    self.chunk.delete_predicates().len()
+                )
+            }
+        }
+    }
+}
@@ -1,8 +1,12 @@
 //! Implementation of a DataFusion PhysicalPlan node across partition chunks
 
 use crate::{
-    provider::record_batch_exec::RecordBatchesExec, util::arrow_sort_key_exprs, QueryChunk,
-    QueryChunkData, CHUNK_ORDER_COLUMN_NAME,
+    provider::{
+        deletes_exec::{delete_scan_schema, DeletesExec},
+        record_batch_exec::RecordBatchesExec,
+    },
+    util::arrow_sort_key_exprs,
+    QueryChunk, QueryChunkData, CHUNK_ORDER_COLUMN_NAME,
 };
 use arrow::datatypes::{DataType, Fields, Schema as ArrowSchema, SchemaRef};
 use datafusion::{
@@ -136,8 +140,12 @@ fn combine_sort_key(
 ///
 /// # Predicates
 /// The give `predicate` will only be applied to [`ParquetExec`] nodes since they are the only node type benifiting from
-/// pushdown ([`RecordBatchesExec`] has NO builtin filter function). Delete predicates are NOT applied at all. The
-/// caller is responsible for wrapping the output node into appropriate filter nodes.
+/// pushdown ([`RecordBatchesExec`] has NO builtin filter function).
+///
+/// # Deletes
+/// Chunks with [delete predicates](QueryChunk::delete_predicates) are scanned individually and wrapped into a
+/// [`DeletesExec`] that removes the deleted rows, so that they are filtered before being combined with the output of
+/// other chunks.
 pub fn chunks_to_physical_nodes(
     schema: &SchemaRef,
     output_sort_key: Option<&SortKey>,
@@ -148,6 +156,41 @@ pub fn chunks_to_physical_nodes(
         return Arc::new(EmptyExec::new(false, Arc::clone(schema)));
     }
 
+    let (deleted_chunks, chunks): (Vec<_>, Vec<_>) = chunks
+        .into_iter()
+        .partition(|chunk| !chunk.delete_predicates().is_empty());
+
+    let mut output_nodes = scan_nodes(schema, output_sort_key, chunks, target_partitions);
+
+    for chunk in deleted_chunks {
+        let scan_schema = delete_scan_schema(schema, chunk.as_ref());
+        let mut scan = scan_nodes(
+            &scan_schema,
+            output_sort_key,
+            vec![Arc::clone(&chunk)],
+            target_partitions,
+        );
+        assert_eq!(scan.len(), 1, "single chunk produces single scan node");
+
+        output_nodes.push(Arc::new(DeletesExec::new(
+            chunk,
+            scan.remove(0),
+            Arc::clone(schema),
+            output_sort_key.cloned(),
+        )));
+    }
+
+    assert!(!output_nodes.is_empty());
+    Arc::new(UnionExec::new(output_nodes))
+}
+
+/// Create the scan nodes for `chunks`, ignoring their delete predicates.
+fn scan_nodes(
+    schema: &SchemaRef,
+    output_sort_key: Option<&SortKey>,
+    chunks: Vec<Arc<dyn QueryChunk>>,
+    target_partitions: usize,
+) -> Vec<Arc<dyn ExecutionPlan>> {
     let mut record_batch_chunks: Vec<Arc<dyn QueryChunk>> = vec![];
     let mut parquet_chunks: HashMap<String, ParquetChunkList> = HashMap::new();
 
@@ -323,8 +366,7 @@ pub fn chunks_to_physical_nodes(
         output_nodes.push(Arc::new(parquet_exec));
     }
 
-    assert!(!output_nodes.is_empty());
-    Arc::new(UnionExec::new(output_nodes))
+    output_nodes
 }
 
 /// Distribute items from the given iterator into `n` containers.
@@ -350,10 +392,13 @@ where
 
 #[cfg(test)]
 mod tests {
+    use arrow_util::assert_batches_eq;
+    use data_types::{DeleteExpr, DeletePredicate, Op, Scalar, TimestampRange};
     use schema::{sort::SortKeyBuilder, SchemaBuilder, TIME_COLUMN_NAME};
 
     use crate::{
         chunk_order_field,
+        exec::IOxSessionContext,
         test::{format_execution_plan, TestChunk},
     };
 
@@ -580,4 +625,71 @@ mod tests {
         "###
         );
     }
+
+    #[test]
+    fn test_chunks_to_physical_nodes_deletes() {
+        let chunk1 = TestChunk::new("table").with_id(0).with_tag_column("tag");
+        let chunk2 = TestChunk::new("table")
+            .with_id(1)
+            .with_tag_column("tag")
+            .with_delete_predicate(DeletePredicate {
+                range: TimestampRange::new(0, 100),
+                exprs: vec![],
+            });
+        let schema = chunk1.schema().as_arrow();
+        let plan =
+            chunks_to_physical_nodes(&schema, None, vec![Arc::new(chunk1), Arc::new(chunk2)], 2);
+        insta::assert_yaml_snapshot!(
+            format_execution_plan(&plan),
+            @r###"
+        ---
+        - " UnionExec"
+        - "   RecordBatchesExec: chunks=1"
+        - "   DeletesExec: predicates=1"
+        - "     RecordBatchesExec: chunks=1"
+        "###
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chunks_to_physical_nodes_deletes_projected() {
+        let chunk = TestChunk::new("table")
+            .with_tag_column("tag1")
+            .with_i64_field_column("field")
+            .with_time_column()
+            .with_three_rows_of_data()
+            .with_delete_predicate(DeletePredicate {
+                range: TimestampRange::new(0, 15000),
+                exprs: vec![DeleteExpr::new(
+                    "tag1".to_string(),
+                    Op::Eq,
+                    Scalar::String("WA".to_string()),
+                )],
+            });
+
+        // the predicate columns are not part of the output
+        let schema = chunk
+            .schema()
+            .select_by_names(&["field"])
+            .unwrap()
+            .as_arrow();
+        let plan = chunks_to_physical_nodes(&schema, None, vec![Arc::new(chunk)], 2);
+        assert_eq!(plan.schema(), schema);
+
+        let batches = IOxSessionContext::with_testing()
+            .collect(plan)
+            .await
+            .unwrap();
+        assert_batches_eq!(
+            &[
+                "+-------+",
+                "| field |",
+                "+-------+",
+                "| 10    |",
+                "| 70    |",
+                "+-------+",
+            ],
+            &batches
+        );
+    }
 }
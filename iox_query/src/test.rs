@@ -20,7 +20,9 @@ use arrow::{
     record_batch::RecordBatch,
 };
 use async_trait::async_trait;
-use data_types::{ChunkId, ChunkOrder, PartitionKey, TableId, TransitionPartitionId};
+use data_types::{
+    ChunkId, ChunkOrder, DeletePredicate, PartitionKey, TableId, TransitionPartitionId,
+};
 use datafusion::error::DataFusionError;
 use datafusion::execution::context::SessionState;
 use datafusion::logical_expr::Expr;
@@ -34,7 +36,7 @@ use datafusion::{
 };
 use datafusion_util::config::DEFAULT_SCHEMA;
 use itertools::Itertools;
-use object_store::{path::Path, ObjectMeta};
+use object_store::{memory::InMemory, path::Path, DynObjectStore, ObjectMeta, ObjectStore};
 use parking_lot::Mutex;
 use parquet_file::storage::ParquetExecInput;
 use schema::{
@@ -162,11 +164,12 @@ impl QueryNamespace for TestDatabase {
 
     fn record_query(
         &self,
+        _ctx: &IOxSessionContext,
         _span_ctx: Option<&SpanContext>,
         _query_type: &'static str,
         _query_text: QueryText,
-    ) -> QueryCompletedToken {
-        QueryCompletedToken::new(|_| {})
+    ) -> Result<QueryCompletedToken, DataFusionError> {
+        Ok(QueryCompletedToken::new(|_| {}))
     }
 
     fn new_query_context(&self, span_ctx: Option<SpanContext>) -> IOxSessionContext {
@@ -321,6 +324,9 @@ pub struct TestChunk {
 
     /// Suppress output
     quiet: bool,
+
+    /// Delete predicates applied to this chunk
+    delete_predicates: Vec<Arc<DeletePredicate>>,
 }
 
 /// Implements a method for adding a column with default stats
@@ -384,6 +390,7 @@ impl TestChunk {
             sort_key: None,
             partition_id: TransitionPartitionId::arbitrary_for_testing(),
             quiet: false,
+            delete_predicates: vec![],
         }
     }
 
@@ -408,6 +415,27 @@ impl TestChunk {
     }
 
     pub fn with_dummy_parquet_file_and_store(self, store: &str) -> Self {
+        let location = Self::parquet_location(self.id);
+        self.with_parquet_input(store, location, 1, Arc::new(InMemory::default()))
+    }
+
+    /// Attach a parquet file with the given encoded `data` to the chunk.
+    pub async fn with_parquet_file(self, data: Vec<u8>) -> Self {
+        let location = Self::parquet_location(self.id);
+        let size = data.len();
+        let object_store: Arc<DynObjectStore> = Arc::new(InMemory::default());
+        object_store.put(&location, data.into()).await.unwrap();
+
+        self.with_parquet_input("iox://store", location, size, object_store)
+    }
+
+    fn with_parquet_input(
+        self,
+        store: &str,
+        location: Path,
+        size: usize,
+        object_store: Arc<DynObjectStore>,
+    ) -> Self {
         match self.table_data {
             TestChunkData::RecordBatches(batches) => {
                 assert!(batches.is_empty(), "chunk already has record batches");
@@ -419,11 +447,12 @@ impl TestChunk {
             table_data: TestChunkData::Parquet(ParquetExecInput {
                 object_store_url: ObjectStoreUrl::parse(store).unwrap(),
                 object_meta: ObjectMeta {
-                    location: Self::parquet_location(self.id),
+                    location,
                     last_modified: Default::default(),
-                    size: 1,
+                    size,
                     e_tag: None,
                 },
+                object_store,
             }),
             ..self
         }
@@ -1031,6 +1060,11 @@ impl TestChunk {
         }
     }
 
+    pub fn with_delete_predicate(mut self, predicate: DeletePredicate) -> Self {
+        self.delete_predicates.push(Arc::new(predicate));
+        self
+    }
+
     pub fn table_name(&self) -> &str {
         &self.table_name
     }
@@ -1100,6 +1134,10 @@ impl QueryChunk for TestChunk {
         self.order
     }
 
+    fn delete_predicates(&self) -> &[Arc<DeletePredicate>] {
+        &self.delete_predicates
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -6,8 +6,26 @@ use datafusion::logical_expr::{
 use once_cell::sync::Lazy;
 use std::sync::Arc;
 
+mod integral;
 mod percentile;
 
+pub(crate) use query_functions::aggregates::{MODE, SPREAD};
+
+/// Definition of the `INTEGRAL` user-defined aggregate function.
+pub(crate) static INTEGRAL: Lazy<Arc<AggregateUDF>> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(integral::return_type);
+    let accumulator: AccumulatorFactoryFunction = Arc::new(integral::accumulator);
+    let state_type: StateTypeFunction = Arc::new(integral::state_type);
+
+    Arc::new(AggregateUDF::new(
+        integral::NAME,
+        &integral::SIGNATURE,
+        &return_type,
+        &accumulator,
+        &state_type,
+    ))
+});
+
 /// Definition of the `PERCENTILE` user-defined aggregate function.
 pub(crate) static PERCENTILE: Lazy<Arc<AggregateUDF>> = Lazy::new(|| {
     let return_type: ReturnTypeFunction = Arc::new(percentile::return_type);
@@ -0,0 +1,327 @@
+use crate::error;
+use arrow::array::{as_list_array, Array, ArrayRef, Float64Array, Int64Array};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, IntervalUnit, TimeUnit};
+use datafusion::common::{downcast_value, DataFusionError, Result, ScalarValue};
+use datafusion::logical_expr::{Accumulator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// The name of the integral aggregate function.
+pub(super) const NAME: &str = "integral";
+
+/// Valid signatures for the integral aggregate function.
+///
+/// The integral of a `GROUP BY time` window is called with additional
+/// arguments describing the window, and the points adjacent to the input
+/// in the same series:
+///
+/// ```text
+/// integral(value, time, unit, stride, origin, prev_time, prev_value, next_time, next_value)
+/// ```
+///
+/// where `stride` and `origin` are the duration and offset of the window in
+/// nanoseconds.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    let time = DataType::Timestamp(TimeUnit::Nanosecond, None);
+    let interval = DataType::Interval(IntervalUnit::MonthDayNano);
+    Signature::one_of(
+        crate::NUMERICS
+            .iter()
+            .flat_map(|dt| {
+                [
+                    TypeSignature::Exact(vec![dt.clone(), time.clone(), interval.clone()]),
+                    TypeSignature::Exact(vec![
+                        dt.clone(),
+                        time.clone(),
+                        interval.clone(),
+                        DataType::Int64,
+                        DataType::Int64,
+                        time.clone(),
+                        dt.clone(),
+                        time.clone(),
+                        dt.clone(),
+                    ]),
+                ]
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature. Integral
+/// always returns a float.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Float64))
+}
+
+/// Create a new accumulator for the data type.
+pub(super) fn accumulator(_: &DataType) -> Result<Box<dyn Accumulator>> {
+    Ok(Box::<IntegralAccumulator>::default())
+}
+
+/// Calculate the intermediate merge state for the aggregator.
+pub(super) fn state_type(_: &DataType) -> Result<Arc<Vec<DataType>>> {
+    Ok(Arc::new(vec![
+        DataType::List(Arc::new(Field::new("item", DataType::Int64, true))),
+        DataType::List(Arc::new(Field::new("item", DataType::Float64, true))),
+        DataType::Interval(IntervalUnit::MonthDayNano),
+        DataType::Int64,
+        DataType::Int64,
+        DataType::Int64,
+        DataType::Float64,
+        DataType::Int64,
+        DataType::Float64,
+    ]))
+}
+
+/// Accumulator which collects the points of the input, to calculate
+/// the area under the curve using the trapezium rule, once all points
+/// are known and may be ordered by time.
+///
+/// When the input is a `GROUP BY time` window, the points adjacent to the
+/// window are used to interpolate the value at its boundaries, as InfluxQL
+/// OG does.
+#[derive(Debug, Default)]
+struct IntegralAccumulator {
+    /// The `(time, value)` pairs of the input.
+    points: Vec<(i64, f64)>,
+    /// The unit duration of the result.
+    unit: Option<i128>,
+    /// The `(stride, origin)` of the `GROUP BY time` window of the input.
+    window: Option<(i64, i64)>,
+    /// The earliest point preceding a point of the input.
+    prev: Option<(i64, f64)>,
+    /// The latest point following a point of the input.
+    next: Option<(i64, f64)>,
+}
+
+impl IntegralAccumulator {
+    fn update(&mut self, times: &Int64Array, values: &Float64Array) {
+        self.points.reserve(values.len() - values.null_count());
+        for idx in 0..values.len() {
+            if times.is_valid(idx) && values.is_valid(idx) {
+                self.points.push((times.value(idx), values.value(idx)));
+            }
+        }
+    }
+
+    /// Record the points adjacent to the input points.
+    ///
+    /// The point preceding the earliest input point has the earliest time of
+    /// all preceding points, as all others are input points themselves.
+    /// Likewise for the point following the latest input point.
+    fn update_adjacent(
+        &mut self,
+        prev_times: &Int64Array,
+        prev_values: &Float64Array,
+        next_times: &Int64Array,
+        next_values: &Float64Array,
+    ) {
+        for idx in 0..prev_times.len() {
+            if prev_times.is_valid(idx) && prev_values.is_valid(idx) {
+                let p = (prev_times.value(idx), prev_values.value(idx));
+                if self.prev.map_or(true, |v| p.0 < v.0) {
+                    self.prev = Some(p);
+                }
+            }
+            if next_times.is_valid(idx) && next_values.is_valid(idx) {
+                let p = (next_times.value(idx), next_values.value(idx));
+                if self.next.map_or(true, |v| p.0 > v.0) {
+                    self.next = Some(p);
+                }
+            }
+        }
+    }
+
+    fn set_unit(&mut self, array: &ArrayRef) -> Result<()> {
+        if self.unit.is_some() {
+            return Ok(());
+        }
+        if let Some(idx) = (0..array.len()).find(|idx| array.is_valid(*idx)) {
+            self.unit = match ScalarValue::try_from_array(array, idx)? {
+                ScalarValue::IntervalMonthDayNano(Some(v)) => Some(v),
+                v => {
+                    return error::internal(format!(
+                        "invalid value ({v}) for INTEGRAL unit argument"
+                    ))
+                }
+            };
+        }
+        Ok(())
+    }
+
+    fn set_window(&mut self, strides: &Int64Array, origins: &Int64Array) {
+        if self.window.is_some() {
+            return;
+        }
+        self.window = (0..strides.len())
+            .find(|idx| strides.is_valid(*idx) && origins.is_valid(*idx))
+            .map(|idx| (strides.value(idx), origins.value(idx)))
+            .filter(|(stride, _)| *stride > 0);
+    }
+
+    /// Return the exclusive end time of the window containing `time`.
+    fn window_end(stride: i64, origin: i64, time: i64) -> i64 {
+        time - (time - origin).rem_euclid(stride) + stride
+    }
+}
+
+/// Linearly interpolate the value at `time` between the points `a` and `b`.
+fn interpolate(time: i64, a: (i64, f64), b: (i64, f64)) -> f64 {
+    a.1 + (b.1 - a.1) * ((time - a.0) as f64 / (b.0 - a.0) as f64)
+}
+
+/// Cast `array` to an [`Int64Array`].
+fn as_int64_array(array: &ArrayRef) -> Result<Int64Array> {
+    let array = cast(array, &DataType::Int64)?;
+    Ok(downcast_value!(array, Int64Array).clone())
+}
+
+/// Cast `array` to a [`Float64Array`].
+fn as_float64_array(array: &ArrayRef) -> Result<Float64Array> {
+    let array = cast(array, &DataType::Float64)?;
+    Ok(downcast_value!(array, Float64Array).clone())
+}
+
+impl Accumulator for IntegralAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        assert!(values.len() == 3 || values.len() == 9);
+
+        self.set_unit(&values[2])?;
+
+        self.update(&as_int64_array(&values[1])?, &as_float64_array(&values[0])?);
+
+        if values.len() == 9 {
+            self.set_window(&as_int64_array(&values[3])?, &as_int64_array(&values[4])?);
+            self.update_adjacent(
+                &as_int64_array(&values[5])?,
+                &as_float64_array(&values[6])?,
+                &as_int64_array(&values[7])?,
+                &as_float64_array(&values[8])?,
+            );
+        }
+        Ok(())
+    }
+
+    /// Calculate the integral of the points, following the behaviour of
+    /// InfluxQL OG, where a point with the same timestamp as the
+    /// previous point replaces it.
+    ///
+    /// For a `GROUP BY time` window, the value at the end of the window is
+    /// interpolated from the last point and the point following it, and the
+    /// area up to the end of the window included. Likewise, the area from the
+    /// end of the window containing the preceding point to the first point
+    /// is included. A window that contains only a single point at its start
+    /// time, with no following point, has no area and returns NULL.
+    ///
+    /// See: <https://github.com/influxdata/influxdb/blob/75a8bcfae2af7b0043933be9f96b98c0741ceee3/influxql/query/functions.go>
+    fn evaluate(&self) -> Result<ScalarValue> {
+        let Some(unit) = self.unit else {
+            return Ok(ScalarValue::Float64(None));
+        };
+
+        let mut points = self.points.clone();
+        points.sort_by_key(|(time, _)| *time);
+
+        let (Some(&first), Some(&last)) = (points.first(), points.last()) else {
+            return Ok(ScalarValue::Float64(None));
+        };
+
+        let area =
+            |a: (i64, f64), b: (i64, f64)| 0.5 * (a.1 + b.1) * ((b.0 - a.0) as f64 / unit as f64);
+
+        let mut sum = 0.0;
+
+        // Include the area from the end of the window containing the
+        // preceding point, to the first point.
+        let window = self.window;
+        if let (Some((stride, origin)), Some(prev)) = (window, self.prev) {
+            if prev.0 < first.0 {
+                let end = Self::window_end(stride, origin, prev.0);
+                sum += area((end, interpolate(end, prev, first)), first);
+            }
+        }
+
+        let mut prev = first;
+        for (time, value) in points.into_iter().skip(1) {
+            if time != prev.0 {
+                sum += area(prev, (time, value));
+            }
+            prev = (time, value);
+        }
+
+        if let Some((stride, origin)) = window {
+            match self.next {
+                // Include the area from the last point to the end of the
+                // window, interpolating the value at the end of the window.
+                Some(next) if next.0 > last.0 => {
+                    let end = Self::window_end(stride, origin, last.0);
+                    sum += area(prev, (end, interpolate(end, prev, next)));
+                }
+                // A window with no following point, where the last point
+                // is at the start of the window, has no area.
+                _ if first.0 == last.0
+                    && Self::window_end(stride, origin, last.0) - stride == last.0 =>
+                {
+                    return Ok(ScalarValue::Float64(None));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(ScalarValue::Float64(Some(sum)))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.points.capacity() * std::mem::size_of::<(i64, f64)>()
+    }
+
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        let (times, values): (Vec<_>, Vec<_>) = self
+            .points
+            .iter()
+            .map(|(t, v)| (ScalarValue::Int64(Some(*t)), ScalarValue::Float64(Some(*v))))
+            .unzip();
+
+        Ok(vec![
+            ScalarValue::new_list(Some(times), DataType::Int64),
+            ScalarValue::new_list(Some(values), DataType::Float64),
+            ScalarValue::IntervalMonthDayNano(self.unit),
+            ScalarValue::Int64(self.window.map(|(stride, _)| stride)),
+            ScalarValue::Int64(self.window.map(|(_, origin)| origin)),
+            ScalarValue::Int64(self.prev.map(|(t, _)| t)),
+            ScalarValue::Float64(self.prev.map(|(_, v)| v)),
+            ScalarValue::Int64(self.next.map(|(t, _)| t)),
+            ScalarValue::Float64(self.next.map(|(_, v)| v)),
+        ])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        assert_eq!(states.len(), 9);
+
+        self.set_unit(&states[2])?;
+        self.set_window(
+            downcast_value!(states[3], Int64Array),
+            downcast_value!(states[4], Int64Array),
+        );
+        self.update_adjacent(
+            downcast_value!(states[5], Int64Array),
+            downcast_value!(states[6], Float64Array),
+            downcast_value!(states[7], Int64Array),
+            downcast_value!(states[8], Float64Array),
+        );
+
+        let times = as_list_array(&states[0]);
+        let values = as_list_array(&states[1]);
+        for idx in 0..times.len() {
+            let t = times.value(idx);
+            let v = values.value(idx);
+            self.update(
+                downcast_value!(t, Int64Array),
+                downcast_value!(v, Float64Array),
+            );
+        }
+        Ok(())
+    }
+}
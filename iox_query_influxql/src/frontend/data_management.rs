@@ -0,0 +1,542 @@
+//! Execution of the InfluxQL data management statements, `DELETE`,
+//! `DROP MEASUREMENT` and `DROP SERIES`.
+
+use std::any::Any;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::fmt::Debug;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Field, Schema as ArrowSchema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use data_types::{DeleteExpr, DeletePredicate, Op, Scalar, TimestampRange};
+use datafusion::catalog::schema::SchemaProvider;
+use datafusion::common::Statistics;
+use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::context::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning, SendableRecordBatchStream,
+};
+use futures::stream;
+use generated_types::influxdata::iox::querier::v1::InfluxQlMetadata;
+use influxdb_influxql_parser::common::{MeasurementName, WhereClause};
+use influxdb_influxql_parser::delete::DeleteStatement;
+use influxdb_influxql_parser::expression::{
+    ConditionalExpression, ConditionalOperator, Expr as IQLExpr,
+};
+use influxdb_influxql_parser::literal::Literal;
+use influxdb_influxql_parser::simple_from_clause::DeleteFromClause;
+use influxdb_influxql_parser::statement::Statement;
+use influxdb_influxql_parser::time_range::{split_cond, ReduceContext};
+use influxdb_influxql_parser::timestamp::Timestamp;
+use iox_query::exec::data_manager::DataManager;
+use iox_query::exec::IOxSessionContext;
+use schema::{InfluxColumnType, Schema, INFLUXQL_MEASUREMENT_COLUMN_NAME, INFLUXQL_METADATA_KEY};
+
+use crate::error;
+use crate::plan::parse_regex;
+
+/// Returns `true` if `statement` manages the data of the namespace, rather
+/// than querying it.
+pub(super) fn is_data_management(statement: &Statement) -> bool {
+    matches!(
+        statement,
+        Statement::Delete(_) | Statement::DropMeasurement(_) | Statement::DropSeries(_)
+    )
+}
+
+/// Create a physical plan that applies the data management `statement`
+/// to the tables of `schema`.
+pub(super) async fn statement_to_physical_plan(
+    statement: Statement,
+    ctx: &IOxSessionContext,
+    schema: Arc<dyn SchemaProvider>,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    let manager = ctx.data_manager().ok_or_else(|| {
+        error::map::not_implemented(format!(
+            "{} is not supported by this server",
+            statement_name(&statement)
+        ))
+    })?;
+
+    let now = Timestamp::from(
+        ctx.inner()
+            .state()
+            .execution_props()
+            .query_execution_start_time,
+    );
+
+    let operations = match statement {
+        Statement::DropMeasurement(drop) => vec![Operation::DropTable(drop.name.deref().clone())],
+        Statement::Delete(delete) => {
+            let (from, condition) = match *delete {
+                DeleteStatement::FromWhere { from, condition } => (Some(from), condition),
+                DeleteStatement::Where(condition) => (None, Some(condition)),
+            };
+            delete_operations(schema, from.as_ref(), condition.as_ref(), now, true).await?
+        }
+        Statement::DropSeries(drop) => {
+            delete_operations(
+                schema,
+                drop.from.as_ref(),
+                drop.condition.as_ref(),
+                now,
+                false,
+            )
+            .await?
+        }
+        _ => return error::internal("expected a data management statement"),
+    };
+
+    Ok(Arc::new(DataManagementExec::try_new(operations, manager)?))
+}
+
+fn statement_name(statement: &Statement) -> &'static str {
+    match statement {
+        Statement::Delete(_) => "DELETE",
+        Statement::DropMeasurement(_) => "DROP MEASUREMENT",
+        Statement::DropSeries(_) => "DROP SERIES",
+        _ => "statement",
+    }
+}
+
+/// A single operation of a data management statement.
+#[derive(Debug, Clone, PartialEq)]
+enum Operation {
+    /// Delete the rows of a table that match a predicate.
+    Delete {
+        table_name: String,
+        predicate: DeletePredicate,
+    },
+    /// Drop a table and all of its data.
+    DropTable(String),
+}
+
+/// Create the delete operations for the measurements of the `FROM` clause,
+/// or all measurements if there is no `FROM` clause.
+///
+/// A time range in the `WHERE` clause is only permitted if `allow_time` is
+/// `true`, as `DROP SERIES` always applies to all time.
+async fn delete_operations(
+    schema: Arc<dyn SchemaProvider>,
+    from: Option<&DeleteFromClause>,
+    condition: Option<&WhereClause>,
+    now: Timestamp,
+    allow_time: bool,
+) -> Result<Vec<Operation>> {
+    let (range, exprs) = delete_condition(condition, now, allow_time)?;
+
+    let mut operations = vec![];
+    for table_name in resolve_measurements(from, &schema.table_names())? {
+        let Some(table) = schema.table(&table_name).await else {
+            continue;
+        };
+        let table_schema = Schema::try_from(table.schema()).map_err(|err| {
+            error::map::internal(format!(
+                "unable to convert DataFusion schema for measurement {table_name} to IOx schema: {err}"
+            ))
+        })?;
+
+        if let Some(predicate) = table_predicate(range, &exprs, &table_schema)? {
+            operations.push(Operation::Delete {
+                table_name,
+                predicate,
+            });
+        }
+    }
+
+    Ok(operations)
+}
+
+/// Resolve the measurement names and regular expressions of `from` to
+/// the matching `tables`, or all `tables` if `from` is `None`.
+fn resolve_measurements(from: Option<&DeleteFromClause>, tables: &[String]) -> Result<Vec<String>> {
+    let Some(from) = from else {
+        return Ok(tables.to_vec());
+    };
+
+    let mut names = BTreeSet::new();
+    for mn in from.iter() {
+        match mn {
+            MeasurementName::Name(name) => {
+                let name = name.deref();
+                if tables.contains(name) {
+                    names.insert(name.clone());
+                }
+            }
+            MeasurementName::Regex(re) => {
+                let re = parse_regex(re)?;
+                names.extend(tables.iter().filter(|t| re.is_match(t)).cloned());
+            }
+        }
+    }
+
+    Ok(names.into_iter().collect())
+}
+
+/// A tag comparison of a delete predicate.
+type TagExpr = (String, Op, String);
+
+/// Split the `WHERE` clause of a delete into the time range and the
+/// conjunction of tag comparisons.
+fn delete_condition(
+    condition: Option<&WhereClause>,
+    now: Timestamp,
+    allow_time: bool,
+) -> Result<(TimestampRange, Vec<TagExpr>)> {
+    let Some(condition) = condition else {
+        return Ok((TimestampRange::new(i64::MIN, i64::MAX), vec![]));
+    };
+
+    let rc = ReduceContext {
+        now: Some(now),
+        tz: None,
+    };
+    let (cond, time_range) = split_cond(&rc, condition).map_err(error::map::expr_error)?;
+
+    if !allow_time && !time_range.is_unbounded() {
+        return error::query("DROP SERIES doesn't support time in WHERE clause");
+    }
+
+    // The time range is inclusive, whereas the upper bound of the delete
+    // predicate is exclusive.
+    let range = TimestampRange::new(
+        time_range.lower.unwrap_or(i64::MIN),
+        time_range
+            .upper
+            .map(|upper| upper.saturating_add(1))
+            .unwrap_or(i64::MAX),
+    );
+
+    let mut exprs = vec![];
+    if let Some(cond) = cond {
+        tag_exprs(&cond, &mut exprs)?;
+    }
+
+    Ok((range, exprs))
+}
+
+/// Collect the tag comparisons of `cond`, which must be a conjunction of
+/// `tag = 'value'` or `tag != 'value'` expressions.
+fn tag_exprs(cond: &ConditionalExpression, exprs: &mut Vec<TagExpr>) -> Result<()> {
+    use ConditionalExpression as CE;
+    use ConditionalOperator::*;
+
+    match cond {
+        CE::Grouped(cond) => tag_exprs(cond, exprs),
+        CE::Binary(b) if b.op == And => {
+            tag_exprs(&b.lhs, exprs)?;
+            tag_exprs(&b.rhs, exprs)
+        }
+        CE::Binary(b) if matches!(b.op, Eq | NotEq) => {
+            let op = if b.op == Eq { Op::Eq } else { Op::Ne };
+            match (b.lhs.expr(), b.rhs.expr()) {
+                (Some(IQLExpr::VarRef(var)), Some(IQLExpr::Literal(Literal::String(value))))
+                | (Some(IQLExpr::Literal(Literal::String(value))), Some(IQLExpr::VarRef(var))) => {
+                    exprs.push((var.name.deref().clone(), op, value.clone()));
+                    Ok(())
+                }
+                _ => error::not_implemented(format!("delete predicate: {cond}")),
+            }
+        }
+        _ => error::not_implemented(format!("delete predicate: {cond}")),
+    }
+}
+
+/// Create the delete predicate for the table with `schema`, returning `None`
+/// if no rows of the table can match.
+fn table_predicate(
+    range: TimestampRange,
+    exprs: &[TagExpr],
+    schema: &Schema,
+) -> Result<Option<DeletePredicate>> {
+    let mut predicate = DeletePredicate {
+        range,
+        exprs: vec![],
+    };
+
+    for (column, op, value) in exprs {
+        match schema.field_by_name(column) {
+            Some((InfluxColumnType::Tag, _)) => predicate.exprs.push(DeleteExpr::new(
+                column.clone(),
+                *op,
+                Scalar::String(value.clone()),
+            )),
+            Some(_) => {
+                return error::query(format!(
+                    "delete predicate may only reference tags, {column} is not a tag"
+                ))
+            }
+            // A tag that does not exist has an empty value, which never
+            // equals the value of the comparison.
+            None if *op == Op::Eq => return Ok(None),
+            None => {}
+        }
+    }
+
+    Ok(Some(predicate))
+}
+
+/// A physical operator that applies the operations of a data management
+/// statement using a [`DataManager`].
+///
+/// Like InfluxDB 1.x, the statement produces no rows.
+pub(super) struct DataManagementExec {
+    operations: Vec<Operation>,
+    manager: Arc<dyn DataManager>,
+    schema: SchemaRef,
+}
+
+impl DataManagementExec {
+    fn try_new(operations: Vec<Operation>, manager: Arc<dyn DataManager>) -> Result<Self> {
+        let metadata = serde_json::to_string(&InfluxQlMetadata {
+            measurement_column_index: 0,
+            tag_key_columns: vec![],
+        })
+        .map_err(|err| {
+            error::map::internal(format!("error serializing InfluxQL metadata: {err}"))
+        })?;
+
+        let schema = Arc::new(ArrowSchema::new_with_metadata(
+            vec![Field::new(
+                INFLUXQL_MEASUREMENT_COLUMN_NAME,
+                DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+                false,
+            )],
+            HashMap::from([(INFLUXQL_METADATA_KEY.to_owned(), metadata)]),
+        ));
+
+        Ok(Self {
+            operations,
+            manager,
+            schema,
+        })
+    }
+}
+
+impl Debug for DataManagementExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_as(DisplayFormatType::Default, f)
+    }
+}
+
+impl ExecutionPlan for DataManagementExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Internal(format!(
+                "DataManagementExec invalid partition {partition}"
+            )));
+        }
+
+        let operations = self.operations.clone();
+        let manager = Arc::clone(&self.manager);
+        let schema = self.schema();
+
+        let fut = {
+            let schema = Arc::clone(&schema);
+            async move {
+                for op in operations {
+                    match op {
+                        Operation::Delete {
+                            table_name,
+                            predicate,
+                        } => manager.delete(&table_name, &predicate).await?,
+                        Operation::DropTable(table_name) => manager.drop_table(&table_name).await?,
+                    }
+                }
+
+                Ok(RecordBatch::new_empty(schema))
+            }
+        };
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            schema,
+            stream::once(fut),
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+impl DisplayAs for DataManagementExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "DataManagementExec: operations={}",
+                    self.operations.len()
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use influxdb_influxql_parser::parse_statements;
+    use schema::{InfluxFieldType, SchemaBuilder};
+
+    /// Parse a `DELETE FROM` statement, returning the `FROM` and `WHERE` clauses.
+    fn parse_delete(s: &str) -> (DeleteFromClause, Option<WhereClause>) {
+        let mut statements = parse_statements(s).unwrap();
+        match statements.pop() {
+            Some(Statement::Delete(delete)) => match *delete {
+                DeleteStatement::FromWhere { from, condition } => (from, condition),
+                _ => panic!("expected DELETE FROM statement"),
+            },
+            _ => panic!("expected DELETE statement"),
+        }
+    }
+
+    fn delete_condition_str(s: &str, allow_time: bool) -> Result<(TimestampRange, Vec<TagExpr>)> {
+        let (_, condition) = parse_delete(&format!("DELETE FROM cpu WHERE {s}"));
+        let now = Timestamp::from(chrono::DateTime::<chrono::Utc>::default());
+        delete_condition(condition.as_ref(), now, allow_time)
+    }
+
+    #[test]
+    fn test_delete_condition() {
+        let (_, condition) = parse_delete("DELETE FROM cpu");
+        let now = Timestamp::from(chrono::DateTime::<chrono::Utc>::default());
+        let (range, exprs) = delete_condition(condition.as_ref(), now, true).unwrap();
+        assert_eq!(range, TimestampRange::new(i64::MIN, i64::MAX));
+        assert!(exprs.is_empty());
+
+        let (range, exprs) = delete_condition_str(
+            "time >= 10 AND time < 20 AND host = 'a' AND 'b' != region",
+            true,
+        )
+        .unwrap();
+        assert_eq!(range, TimestampRange::new(10, 20));
+        assert_eq!(
+            exprs,
+            vec![
+                ("host".to_owned(), Op::Eq, "a".to_owned()),
+                ("region".to_owned(), Op::Ne, "b".to_owned()),
+            ]
+        );
+
+        // Fallible cases
+        assert_eq!(
+            delete_condition_str("time > 10", false)
+                .unwrap_err()
+                .to_string(),
+            "Error during planning: DROP SERIES doesn't support time in WHERE clause"
+        );
+        assert_eq!(
+            delete_condition_str("host = 'a' OR host = 'b'", true)
+                .unwrap_err()
+                .to_string(),
+            "This feature is not implemented: delete predicate: host = 'a' OR host = 'b'"
+        );
+        assert_eq!(
+            delete_condition_str("host =~ /a/", true)
+                .unwrap_err()
+                .to_string(),
+            "This feature is not implemented: delete predicate: host =~ /a/"
+        );
+    }
+
+    #[test]
+    fn test_resolve_measurements() {
+        let tables = vec!["cpu".to_owned(), "disk".to_owned(), "diskio".to_owned()];
+
+        assert_eq!(resolve_measurements(None, &tables).unwrap(), tables);
+
+        let (from, _) = parse_delete("DELETE FROM cpu, /^disk/, mem");
+        assert_eq!(
+            resolve_measurements(Some(&from), &tables).unwrap(),
+            vec!["cpu", "disk", "diskio"]
+        );
+    }
+
+    #[test]
+    fn test_table_predicate() {
+        let schema = SchemaBuilder::new()
+            .tag("host")
+            .influx_field("usage", InfluxFieldType::Float)
+            .timestamp()
+            .build()
+            .unwrap();
+        let range = TimestampRange::new(10, 20);
+
+        let got = table_predicate(
+            range,
+            &[("host".to_owned(), Op::Eq, "a".to_owned())],
+            &schema,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(got.range, range);
+        assert_eq!(got.expr_sql_string(), r#""host"='a'"#);
+
+        // A missing tag never equals a value
+        let got = table_predicate(
+            range,
+            &[("region".to_owned(), Op::Eq, "a".to_owned())],
+            &schema,
+        )
+        .unwrap();
+        assert!(got.is_none());
+
+        // and always differs from a value
+        let got = table_predicate(
+            range,
+            &[("region".to_owned(), Op::Ne, "a".to_owned())],
+            &schema,
+        )
+        .unwrap()
+        .unwrap();
+        assert!(got.exprs.is_empty());
+
+        // Fallible cases
+        assert_eq!(
+            table_predicate(
+                range,
+                &[("usage".to_owned(), Op::Eq, "a".to_owned())],
+                &schema,
+            )
+            .unwrap_err()
+            .to_string(),
+            "Error during planning: delete predicate may only reference tags, usage is not a tag"
+        );
+    }
+}
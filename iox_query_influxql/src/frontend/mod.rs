@@ -1 +1,5 @@
+mod data_management;
 pub mod planner;
+mod select_into;
+
+pub use select_into::into_namespace_name;
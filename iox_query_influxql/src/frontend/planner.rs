@@ -11,7 +11,10 @@ use std::fmt::Debug;
 use std::ops::Deref;
 use std::sync::Arc;
 
+use super::data_management::{self, is_data_management};
+use super::select_into::SelectIntoExec;
 use crate::plan::{parse_regex, InfluxQLToLogicalPlan, SchemaProvider};
+use datafusion::catalog::schema::SchemaProvider as CatalogSchemaProvider;
 use datafusion::common::Statistics;
 use datafusion::datasource::provider_as_source;
 use datafusion::execution::context::{SessionState, TaskContext};
@@ -153,7 +156,34 @@ impl InfluxQLQueryPlanner {
     ) -> Result<Arc<dyn ExecutionPlan>> {
         debug!(text=%query, "planning InfluxQL query");
 
-        let statement = self.query_to_statement(query)?;
+        let mut statement = self.query_to_statement(query)?;
+
+        // Statements that delete data are executed directly, rather than
+        // being planned as a query.
+        if is_data_management(&statement) {
+            return data_management::statement_to_physical_plan(
+                statement,
+                ctx,
+                default_schema(ctx)?,
+            )
+            .await;
+        }
+
+        // The INTO clause is handled by the physical plan, which writes
+        // the results of the SELECT statement.
+        let into = match &mut statement {
+            Statement::Select(select) => select.into.take(),
+            _ => None,
+        };
+        let writer = match &into {
+            Some(_) => Some(ctx.query_writer().ok_or_else(|| {
+                DataFusionError::NotImplemented(
+                    "SELECT INTO is not supported by this server".to_string(),
+                )
+            })?),
+            None => None,
+        };
+
         let logical_plan = self.statement_to_plan(statement, ctx).await?;
 
         let input = ctx.create_physical_plan(&logical_plan).await?;
@@ -169,7 +199,17 @@ impl InfluxQLQueryPlanner {
             md,
         ));
 
-        Ok(Arc::new(SchemaExec { input, schema }))
+        let plan = Arc::new(SchemaExec { input, schema });
+
+        match (into, writer) {
+            (Some(into), Some(writer)) => Ok(Arc::new(SelectIntoExec::try_new(
+                plan,
+                &into,
+                writer,
+                ctx.authorization(),
+            )?)),
+            _ => Ok(plan),
+        }
     }
 
     async fn statement_to_plan(
@@ -179,24 +219,7 @@ impl InfluxQLQueryPlanner {
     ) -> Result<LogicalPlan> {
         use std::collections::hash_map::Entry;
 
-        let session_cfg = ctx.inner().copied_config();
-        let cfg = session_cfg.options();
-        let schema = ctx
-            .inner()
-            .catalog(&cfg.catalog.default_catalog)
-            .ok_or_else(|| {
-                DataFusionError::Plan(format!(
-                    "failed to resolve catalog: {}",
-                    cfg.catalog.default_catalog
-                ))
-            })?
-            .schema(&cfg.catalog.default_schema)
-            .ok_or_else(|| {
-                DataFusionError::Plan(format!(
-                    "failed to resolve schema: {}",
-                    cfg.catalog.default_schema
-                ))
-            })?;
+        let schema = default_schema(ctx)?;
         let names = schema.table_names();
         let query_tables = find_all_measurements(&statement, &names)?;
 
@@ -237,6 +260,27 @@ impl InfluxQLQueryPlanner {
     }
 }
 
+/// Resolve the default schema of the default catalog of `ctx`.
+fn default_schema(ctx: &IOxSessionContext) -> Result<Arc<dyn CatalogSchemaProvider>> {
+    let session_cfg = ctx.inner().copied_config();
+    let cfg = session_cfg.options();
+    ctx.inner()
+        .catalog(&cfg.catalog.default_catalog)
+        .ok_or_else(|| {
+            DataFusionError::Plan(format!(
+                "failed to resolve catalog: {}",
+                cfg.catalog.default_catalog
+            ))
+        })?
+        .schema(&cfg.catalog.default_schema)
+        .ok_or_else(|| {
+            DataFusionError::Plan(format!(
+                "failed to resolve schema: {}",
+                cfg.catalog.default_schema
+            ))
+        })
+}
+
 fn find_all_measurements(stmt: &Statement, tables: &[String]) -> Result<HashSet<String>> {
     struct Matcher<'a>(&'a mut HashSet<String>, &'a [String]);
     impl<'a> Visitor for Matcher<'a> {
@@ -0,0 +1,723 @@
+//! Physical operator for the InfluxQL `SELECT ... INTO` statement.
+
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fmt::Debug;
+use std::ops::Range;
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, ArrayRef, BooleanArray, DictionaryArray, Int32Array, Int64Array, StringArray,
+    TimestampNanosecondArray,
+};
+use arrow::compute::kernels::boolean::{and, or};
+use arrow::compute::{cast, filter_record_batch, is_not_null};
+use arrow::datatypes::{DataType, Field, Int32Type, Schema as ArrowSchema, SchemaRef, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use datafusion::common::Statistics;
+use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::context::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning, SendableRecordBatchStream,
+};
+use futures::{stream, StreamExt};
+use generated_types::influxdata::iox::querier::v1::InfluxQlMetadata;
+use influxdb_influxql_parser::select::{IntoClause, IntoMeasurementName};
+use iox_query::exec::query_writer::QueryWriter;
+use schema::{
+    InfluxFieldType, SchemaBuilder, INFLUXQL_MEASUREMENT_COLUMN_NAME, INFLUXQL_METADATA_KEY,
+    TIME_COLUMN_NAME,
+};
+
+/// The name of the column reporting the number of points written.
+const WRITTEN_COLUMN_NAME: &str = "written";
+
+/// The destination of the rows written by a `SELECT ... INTO` statement.
+#[derive(Debug, Clone)]
+struct IntoTarget {
+    /// The namespace the rows are written to.
+    namespace: String,
+    /// The name of the measurement the rows are written to, or `None`
+    /// to write each row to the measurement it was selected from.
+    measurement: Option<String>,
+}
+
+impl IntoTarget {
+    /// Resolve the target of `into`, where the database defaults to the
+    /// namespace of `writer`.
+    fn new(into: &IntoClause, writer: &dyn QueryWriter) -> Self {
+        let namespace = into_namespace_name(into, writer.namespace_name());
+
+        let measurement = match &into.name {
+            IntoMeasurementName::Name(name) => Some(name.to_string()),
+            IntoMeasurementName::BackReference => None,
+        };
+
+        Self {
+            namespace,
+            measurement,
+        }
+    }
+}
+
+/// Returns the name of the namespace written by `into`, where the database
+/// defaults to `default_namespace`.
+///
+/// Namespaces for a named retention policy follow the naming used by the
+/// v1 write API.
+pub fn into_namespace_name(into: &IntoClause, default_namespace: &str) -> String {
+    let database = into
+        .database
+        .as_ref()
+        .map(|db| db.as_str())
+        .unwrap_or(default_namespace);
+
+    match into.retention_policy.as_ref().map(|rp| rp.as_str()) {
+        None | Some("") | Some("autogen") | Some("default") => database.to_owned(),
+        Some(rp) => format!("{database}/{rp}"),
+    }
+}
+
+/// A physical operator that writes the rows of its input to a
+/// [`QueryWriter`], and produces a single row with the number of
+/// points written.
+pub(super) struct SelectIntoExec {
+    input: Arc<dyn ExecutionPlan>,
+    target: IntoTarget,
+    writer: Arc<dyn QueryWriter>,
+    authorization: Option<Arc<str>>,
+    schema: SchemaRef,
+}
+
+impl SelectIntoExec {
+    pub(super) fn try_new(
+        input: Arc<dyn ExecutionPlan>,
+        into: &IntoClause,
+        writer: Arc<dyn QueryWriter>,
+        authorization: Option<&str>,
+    ) -> Result<Self> {
+        let input: Arc<dyn ExecutionPlan> = if input.output_partitioning().partition_count() > 1 {
+            Arc::new(CoalescePartitionsExec::new(input))
+        } else {
+            input
+        };
+
+        let metadata = serde_json::to_string(&InfluxQlMetadata {
+            measurement_column_index: 0,
+            tag_key_columns: vec![],
+        })
+        .map_err(|err| {
+            DataFusionError::Internal(format!("error serializing InfluxQL metadata: {err}"))
+        })?;
+
+        let schema = Arc::new(ArrowSchema::new_with_metadata(
+            vec![
+                Field::new(
+                    INFLUXQL_MEASUREMENT_COLUMN_NAME,
+                    DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+                    false,
+                ),
+                Field::new(
+                    TIME_COLUMN_NAME,
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    false,
+                ),
+                Field::new(WRITTEN_COLUMN_NAME, DataType::Int64, false),
+            ],
+            HashMap::from([(INFLUXQL_METADATA_KEY.to_owned(), metadata)]),
+        ));
+
+        Ok(Self {
+            target: IntoTarget::new(into, writer.as_ref()),
+            input,
+            writer,
+            authorization: authorization.map(Into::into),
+            schema,
+        })
+    }
+}
+
+impl Debug for SelectIntoExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_as(DisplayFormatType::Default, f)
+    }
+}
+
+impl ExecutionPlan for SelectIntoExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![Arc::clone(&self.input)]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        assert_eq!(children.len(), 1);
+        Ok(Arc::new(Self {
+            input: Arc::clone(&children[0]),
+            target: self.target.clone(),
+            writer: Arc::clone(&self.writer),
+            authorization: self.authorization.clone(),
+            schema: Arc::clone(&self.schema),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Internal(format!(
+                "SelectIntoExec invalid partition {partition}"
+            )));
+        }
+
+        let mut input = self.input.execute(0, context)?;
+        let input_schema = self.input.schema();
+        let target = self.target.clone();
+        let writer = Arc::clone(&self.writer);
+        let authorization = self.authorization.clone();
+        let schema = self.schema();
+
+        let fut = {
+            let schema = Arc::clone(&schema);
+            async move {
+                let columns = InputColumns::try_new(&input_schema)?;
+
+                let mut written = 0;
+                while let Some(batch) = input.next().await {
+                    written += write_batch(
+                        &columns,
+                        &target,
+                        writer.as_ref(),
+                        authorization.as_deref(),
+                        batch?,
+                    )
+                    .await?;
+                }
+
+                let measurement = DictionaryArray::<Int32Type>::try_new(
+                    Int32Array::from(vec![0]),
+                    Arc::new(StringArray::from(vec!["result"])),
+                )?;
+                RecordBatch::try_new(
+                    schema,
+                    vec![
+                        Arc::new(measurement),
+                        Arc::new(TimestampNanosecondArray::from(vec![0])),
+                        Arc::new(Int64Array::from(vec![written as i64])),
+                    ],
+                )
+                .map_err(Into::into)
+            }
+        };
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            schema,
+            stream::once(fut),
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+impl DisplayAs for SelectIntoExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "SelectIntoExec: namespace={}", self.target.namespace)?;
+                match &self.target.measurement {
+                    Some(name) => write!(f, ", measurement={name}"),
+                    None => write!(f, ", measurement=:MEASUREMENT"),
+                }
+            }
+        }
+    }
+}
+
+/// The roles of the columns of the input to a [`SelectIntoExec`],
+/// determined from the InfluxQL metadata of the input schema.
+#[derive(Debug)]
+struct InputColumns {
+    measurement_index: usize,
+    time_index: usize,
+    tag_indices: HashSet<usize>,
+}
+
+impl InputColumns {
+    fn try_new(schema: &ArrowSchema) -> Result<Self> {
+        let md = schema
+            .metadata()
+            .get(INFLUXQL_METADATA_KEY)
+            .ok_or_else(|| {
+                DataFusionError::Internal(
+                    "SELECT INTO input is missing InfluxQL metadata".to_owned(),
+                )
+            })?;
+        let md: InfluxQlMetadata = serde_json::from_str(md).map_err(|err| {
+            DataFusionError::Internal(format!("error deserializing InfluxQL metadata: {err}"))
+        })?;
+
+        let time_index = schema.index_of(TIME_COLUMN_NAME)?;
+
+        Ok(Self {
+            measurement_index: md.measurement_column_index as usize,
+            time_index,
+            tag_indices: md
+                .tag_key_columns
+                .iter()
+                .map(|tk| tk.column_index as usize)
+                .collect(),
+        })
+    }
+}
+
+/// Write the rows of `batch` to `target`, returning the number of points
+/// written.
+async fn write_batch(
+    columns: &InputColumns,
+    target: &IntoTarget,
+    writer: &dyn QueryWriter,
+    authorization: Option<&str>,
+    batch: RecordBatch,
+) -> Result<usize> {
+    let runs = match &target.measurement {
+        Some(name) => vec![(name.clone(), 0..batch.num_rows())],
+        None => measurement_runs(batch.column(columns.measurement_index))?,
+    };
+
+    let mut written = 0;
+    for (measurement, range) in runs {
+        let batch = to_iox_batch(columns, &batch.slice(range.start, range.len()))?;
+        if batch.num_rows() == 0 {
+            continue;
+        }
+
+        written += batch.num_rows();
+        writer
+            .write(&target.namespace, &measurement, vec![batch], authorization)
+            .await?;
+    }
+
+    Ok(written)
+}
+
+/// Split the measurement column into runs of consecutive rows for the
+/// same measurement.
+fn measurement_runs(array: &ArrayRef) -> Result<Vec<(String, Range<usize>)>> {
+    let array = cast(array, &DataType::Utf8)?;
+    let array = array
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .expect("cast to Utf8");
+
+    let mut runs: Vec<(String, Range<usize>)> = vec![];
+    for (i, name) in array.iter().enumerate() {
+        let name = name.ok_or_else(|| {
+            DataFusionError::Internal("SELECT INTO measurement name is NULL".to_owned())
+        })?;
+        match runs.last_mut() {
+            Some((last, range)) if last == name => range.end = i + 1,
+            _ => runs.push((name.to_owned(), i..i + 1)),
+        }
+    }
+
+    Ok(runs)
+}
+
+/// Convert the rows of `batch` to a [`RecordBatch`] with an IOx schema,
+/// where the `GROUP BY` tags are written as tags, and all other columns
+/// as fields. Rows without any field values are removed, as there is
+/// no point to write.
+fn to_iox_batch(columns: &InputColumns, batch: &RecordBatch) -> Result<RecordBatch> {
+    let input_schema = batch.schema();
+    let mut builder = SchemaBuilder::new();
+    let mut arrays = vec![];
+    let mut has_field = None;
+
+    for (i, field) in input_schema.fields().iter().enumerate() {
+        let array = batch.column(i);
+        if i == columns.measurement_index {
+            continue;
+        } else if i == columns.time_index {
+            builder.timestamp();
+            arrays.push(cast(
+                array,
+                &DataType::Timestamp(TimeUnit::Nanosecond, None),
+            )?);
+        } else if columns.tag_indices.contains(&i) {
+            builder.tag(field.name());
+            arrays.push(cast(
+                array,
+                &DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            )?);
+        } else {
+            let (field_type, array) = match field.data_type() {
+                DataType::Float64 => (InfluxFieldType::Float, Arc::clone(array)),
+                DataType::Int64 => (InfluxFieldType::Integer, Arc::clone(array)),
+                DataType::UInt64 => (InfluxFieldType::UInteger, Arc::clone(array)),
+                DataType::Boolean => (InfluxFieldType::Boolean, Arc::clone(array)),
+                DataType::Utf8 | DataType::Dictionary(_, _) => {
+                    (InfluxFieldType::String, cast(array, &DataType::Utf8)?)
+                }
+                dt => {
+                    return Err(DataFusionError::NotImplemented(format!(
+                        "SELECT INTO for column {} of type {dt}",
+                        field.name()
+                    )))
+                }
+            };
+
+            let valid = is_not_null(&array)?;
+            has_field = Some(match has_field {
+                Some(prev) => or(&prev, &valid)?,
+                None => valid,
+            });
+
+            builder.influx_field(field.name(), field_type);
+            arrays.push(array);
+        }
+    }
+
+    let schema = builder
+        .build()
+        .map_err(|err| DataFusionError::External(Box::new(err)))?;
+    let batch = RecordBatch::try_new(schema.as_arrow(), arrays)?;
+
+    let time_valid =
+        is_not_null(batch.column(schema.find_index_of(TIME_COLUMN_NAME).expect("time column")))?;
+    let predicate: BooleanArray = match has_field {
+        Some(has_field) => and(&has_field, &time_valid)?,
+        None => BooleanArray::from(vec![false; batch.num_rows()]),
+    };
+
+    Ok(filter_record_batch(&batch, &predicate)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow::array::Float64Array;
+    use assert_matches::assert_matches;
+    use async_trait::async_trait;
+    use datafusion::assert_batches_eq;
+    use datafusion::physical_plan::collect;
+    use datafusion::physical_plan::memory::MemoryExec;
+    use datafusion::prelude::SessionContext;
+    use generated_types::influxdata::iox::querier::v1::influx_ql_metadata::TagKeyColumn;
+    use influxdb_influxql_parser::parse_statements;
+    use influxdb_influxql_parser::statement::Statement;
+    use schema::{InfluxColumnType, Schema};
+    use std::sync::Mutex;
+
+    /// Parse the INTO clause `into`.
+    fn into_clause(into: &str) -> IntoClause {
+        match parse_statements(&format!("SELECT * {into} FROM cpu"))
+            .unwrap()
+            .pop()
+        {
+            Some(Statement::Select(select)) => select.into.unwrap(),
+            s => panic!("unexpected statement: {s:?}"),
+        }
+    }
+
+    /// A `cpu,host=a` and `mem,host=b` series, with a row without any
+    /// field values for `cpu`.
+    fn input_batch() -> RecordBatch {
+        let md = serde_json::to_string(&InfluxQlMetadata {
+            measurement_column_index: 0,
+            tag_key_columns: vec![TagKeyColumn {
+                tag_key: "host".to_owned(),
+                column_index: 2,
+                is_projected: false,
+            }],
+        })
+        .unwrap();
+        let dict = |values: Vec<&str>| -> ArrayRef {
+            Arc::new(values.into_iter().collect::<DictionaryArray<Int32Type>>())
+        };
+        let schema = Arc::new(ArrowSchema::new_with_metadata(
+            vec![
+                Field::new(
+                    INFLUXQL_MEASUREMENT_COLUMN_NAME,
+                    DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+                    false,
+                ),
+                Field::new(
+                    TIME_COLUMN_NAME,
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    false,
+                ),
+                Field::new(
+                    "host",
+                    DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+                    true,
+                ),
+                Field::new("usage", DataType::Float64, true),
+                Field::new("count", DataType::Int64, true),
+            ],
+            HashMap::from([(INFLUXQL_METADATA_KEY.to_owned(), md)]),
+        ));
+        RecordBatch::try_new(
+            schema,
+            vec![
+                dict(vec!["cpu", "cpu", "cpu", "mem"]),
+                Arc::new(TimestampNanosecondArray::from(vec![10, 20, 30, 10])),
+                dict(vec!["a", "a", "a", "b"]),
+                Arc::new(Float64Array::from(vec![Some(1.5), None, None, Some(4.0)])),
+                Arc::new(Int64Array::from(vec![Some(1), None, Some(3), None])),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_into_namespace_name() {
+        let name = |into: &str| into_namespace_name(&into_clause(into), "bananas");
+
+        assert_eq!(name("INTO foo"), "bananas");
+        assert_eq!(name("INTO autogen.foo"), "bananas");
+        assert_eq!(name("INTO rp.foo"), "bananas/rp");
+        assert_eq!(name("INTO db..foo"), "db");
+        assert_eq!(name("INTO db.default.foo"), "db");
+        assert_eq!(name("INTO db.rp.:MEASUREMENT"), "db/rp");
+    }
+
+    #[test]
+    fn test_measurement_runs() {
+        let array: ArrayRef = Arc::new(
+            vec!["cpu", "cpu", "mem", "cpu"]
+                .into_iter()
+                .collect::<DictionaryArray<Int32Type>>(),
+        );
+        assert_eq!(
+            measurement_runs(&array).unwrap(),
+            vec![
+                ("cpu".to_owned(), 0..2),
+                ("mem".to_owned(), 2..3),
+                ("cpu".to_owned(), 3..4),
+            ]
+        );
+
+        let array: ArrayRef = Arc::new(StringArray::from(Vec::<&str>::new()));
+        assert_eq!(measurement_runs(&array).unwrap(), vec![]);
+
+        let array: ArrayRef = Arc::new(StringArray::from(vec![Some("cpu"), None]));
+        assert_matches!(
+            measurement_runs(&array),
+            Err(DataFusionError::Internal(msg)) if msg == "SELECT INTO measurement name is NULL"
+        );
+    }
+
+    #[test]
+    fn test_to_iox_batch() {
+        let batch = input_batch();
+        let columns = InputColumns::try_new(&batch.schema()).unwrap();
+
+        let got = to_iox_batch(&columns, &batch).unwrap();
+
+        // the measurement column is removed, the GROUP BY tags are written
+        // as tags, and the row without any field values is removed
+        let schema = Schema::try_from(got.schema()).unwrap();
+        assert_eq!(
+            schema
+                .iter()
+                .map(|(t, f)| (t, f.name().as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (InfluxColumnType::Timestamp, "time"),
+                (InfluxColumnType::Tag, "host"),
+                (InfluxColumnType::Field(InfluxFieldType::Float), "usage"),
+                (InfluxColumnType::Field(InfluxFieldType::Integer), "count"),
+            ]
+        );
+        assert_batches_eq!(
+            [
+                "+--------------------------------+------+-------+-------+",
+                "| time                           | host | usage | count |",
+                "+--------------------------------+------+-------+-------+",
+                "| 1970-01-01T00:00:00.000000010Z | a    | 1.5   | 1     |",
+                "| 1970-01-01T00:00:00.000000030Z | a    |       | 3     |",
+                "| 1970-01-01T00:00:00.000000010Z | b    | 4.0   |       |",
+                "+--------------------------------+------+-------+-------+",
+            ],
+            &[got]
+        );
+    }
+
+    #[test]
+    fn test_to_iox_batch_unsupported_type() {
+        let schema = Arc::new(ArrowSchema::new_with_metadata(
+            vec![
+                Field::new(INFLUXQL_MEASUREMENT_COLUMN_NAME, DataType::Utf8, false),
+                Field::new(
+                    TIME_COLUMN_NAME,
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    false,
+                ),
+                Field::new("val", DataType::Int32, true),
+            ],
+            input_batch().schema().metadata().clone(),
+        ));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(vec!["cpu"])),
+                Arc::new(TimestampNanosecondArray::from(vec![10])),
+                Arc::new(Int32Array::from(vec![1])),
+            ],
+        )
+        .unwrap();
+        let columns = InputColumns {
+            measurement_index: 0,
+            time_index: 1,
+            tag_indices: HashSet::new(),
+        };
+
+        assert_matches!(
+            to_iox_batch(&columns, &batch),
+            Err(DataFusionError::NotImplemented(msg)) if msg == "SELECT INTO for column val of type Int32"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_select_into_exec() {
+        let batch = input_batch();
+        let input =
+            Arc::new(MemoryExec::try_new(&[vec![batch.clone()]], batch.schema(), None).unwrap());
+        let writer = Arc::new(MockWriter::default());
+
+        // writes each row to the measurement it was selected from
+        let exec = SelectIntoExec::try_new(
+            Arc::clone(&input) as _,
+            &into_clause("INTO db.rp.:MEASUREMENT"),
+            Arc::clone(&writer) as _,
+            Some("Token t1"),
+        )
+        .unwrap();
+        assert_eq!(
+            format!("{exec:?}"),
+            "SelectIntoExec: namespace=db/rp, measurement=:MEASUREMENT"
+        );
+        let got = collect(Arc::new(exec), SessionContext::new().task_ctx())
+            .await
+            .unwrap();
+        assert_batches_eq!(
+            [
+                "+------------------+----------------------+---------+",
+                "| iox::measurement | time                 | written |",
+                "+------------------+----------------------+---------+",
+                "| result           | 1970-01-01T00:00:00Z | 3       |",
+                "+------------------+----------------------+---------+",
+            ],
+            &got
+        );
+        assert_eq!(
+            writer.summary(),
+            vec![
+                (
+                    "db/rp".to_owned(),
+                    "cpu".to_owned(),
+                    2,
+                    Some("Token t1".to_owned())
+                ),
+                (
+                    "db/rp".to_owned(),
+                    "mem".to_owned(),
+                    1,
+                    Some("Token t1".to_owned())
+                ),
+            ]
+        );
+
+        // writes all rows to the named measurement
+        let writer = Arc::new(MockWriter::default());
+        let exec = SelectIntoExec::try_new(
+            input,
+            &into_clause("INTO foo"),
+            Arc::clone(&writer) as _,
+            None,
+        )
+        .unwrap();
+        collect(Arc::new(exec), SessionContext::new().task_ctx())
+            .await
+            .unwrap();
+        assert_eq!(
+            writer.summary(),
+            vec![("bananas".to_owned(), "foo".to_owned(), 3, None)],
+        );
+    }
+
+    /// A [`QueryWriter`] for the namespace "bananas", which records the
+    /// writes it receives.
+    #[derive(Debug, Default)]
+    struct MockWriter {
+        writes: Mutex<Vec<(String, String, Vec<RecordBatch>, Option<String>)>>,
+    }
+
+    impl MockWriter {
+        /// The namespace, table, number of rows and authorization of each
+        /// write.
+        fn summary(&self) -> Vec<(String, String, usize, Option<String>)> {
+            self.writes
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(namespace, table, batches, authorization)| {
+                    (
+                        namespace.clone(),
+                        table.clone(),
+                        batches.iter().map(|b| b.num_rows()).sum(),
+                        authorization.clone(),
+                    )
+                })
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl QueryWriter for MockWriter {
+        fn namespace_name(&self) -> &str {
+            "bananas"
+        }
+
+        async fn write(
+            &self,
+            namespace: &str,
+            table_name: &str,
+            batches: Vec<RecordBatch>,
+            authorization: Option<&str>,
+        ) -> Result<()> {
+            self.writes.lock().unwrap().push((
+                namespace.to_owned(),
+                table_name.to_owned(),
+                batches,
+                authorization.map(ToOwned::to_owned),
+            ));
+            Ok(())
+        }
+    }
+}
@@ -188,7 +188,8 @@ impl<'a> TypeEvaluator<'a> {
             "mean" => Some(VarRefDataType::Float),
             "count" => Some(VarRefDataType::Integer),
             // These functions return the same type as their first argument
-            "min" | "max" | "sum" | "first" | "last" | "distinct" => match arg_types.first() {
+            "min" | "max" | "sum" | "first" | "last" | "distinct" | "mode" | "spread"
+            | "sample" => match arg_types.first() {
                 Some(v) => *v,
                 None => None,
             },
@@ -1,6 +1,6 @@
 mod select;
 
-use crate::aggregate::PERCENTILE;
+use crate::aggregate::{INTEGRAL, MODE, PERCENTILE, SPREAD};
 use crate::error;
 use crate::plan::ir::{DataSource, Field, Interval, Select, SelectQuery};
 use crate::plan::planner::select::{
@@ -10,15 +10,22 @@ use crate::plan::planner::select::{
 use crate::plan::planner_time_range_expression::time_range_to_df_expr;
 use crate::plan::rewriter::{find_table_names, rewrite_statement, ProjectionType};
 use crate::plan::udf::{
-    cumulative_sum, derivative, difference, find_window_udfs, moving_average,
-    non_negative_derivative, non_negative_difference,
+    chande_momentum_oscillator, cumulative_sum, derivative, difference,
+    double_exponential_moving_average, elapsed, exponential_moving_average, find_window_udfs,
+    holt_winters, holt_winters_with_fit, kaufmans_adaptive_moving_average,
+    kaufmans_efficiency_ratio, moving_average, non_negative_derivative, non_negative_difference,
+    relative_strength_index, triple_exponential_derivative, triple_exponential_moving_average,
 };
 use crate::plan::util::{binary_operator_to_df_operator, rebase_expr, IQLSchema};
 use crate::plan::var_ref::var_ref_data_type_to_data_type;
 use crate::plan::{planner_rewrite_expression, udf};
 use crate::window::{
-    CUMULATIVE_SUM, DERIVATIVE, DIFFERENCE, MOVING_AVERAGE, NON_NEGATIVE_DERIVATIVE,
-    NON_NEGATIVE_DIFFERENCE, PERCENT_ROW_NUMBER,
+    CHANDE_MOMENTUM_OSCILLATOR, CUMULATIVE_SUM, DERIVATIVE, DIFFERENCE,
+    DOUBLE_EXPONENTIAL_MOVING_AVERAGE, ELAPSED, EXPONENTIAL_MOVING_AVERAGE, HOLT_WINTERS,
+    HOLT_WINTERS_WITH_FIT, KAUFMANS_ADAPTIVE_MOVING_AVERAGE, KAUFMANS_EFFICIENCY_RATIO,
+    MOVING_AVERAGE, NON_NEGATIVE_DERIVATIVE, NON_NEGATIVE_DIFFERENCE, PERCENT_ROW_NUMBER,
+    RELATIVE_STRENGTH_INDEX, SAMPLE, TRIPLE_EXPONENTIAL_DERIVATIVE,
+    TRIPLE_EXPONENTIAL_MOVING_AVERAGE,
 };
 use arrow::array::{
     BooleanArray, DictionaryArray, Int32Array, Int64Array, StringArray, StringBuilder,
@@ -58,7 +65,7 @@ use influxdb_influxql_parser::expression::{
 use influxdb_influxql_parser::functions::{
     is_aggregate_function, is_now_function, is_scalar_math_function,
 };
-use influxdb_influxql_parser::select::{FillClause, GroupByClause};
+use influxdb_influxql_parser::select::{FillClause, GroupByClause, SLimitClause, SOffsetClause};
 use influxdb_influxql_parser::show_field_keys::ShowFieldKeysStatement;
 use influxdb_influxql_parser::show_measurements::{
     ShowMeasurementsStatement, WithMeasurementClause,
@@ -446,6 +453,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             Statement::CreateDatabase(_) => error::not_implemented("CREATE DATABASE"),
             Statement::Delete(_) => error::not_implemented("DELETE"),
             Statement::DropMeasurement(_) => error::not_implemented("DROP MEASUREMENT"),
+            Statement::DropSeries(_) => error::not_implemented("DROP SERIES"),
             Statement::Explain(explain) => self.explain_statement_to_plan(*explain),
             Statement::Select(select) => {
                 self.select_query_to_plan(&self.rewrite_select_statement(*select)?)
@@ -646,6 +654,16 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             &projection_tag_set,
         )?;
 
+        let plan = self.series_limit(
+            plan,
+            select.series_offset,
+            select.series_limit,
+            vec![time_sort_expr.clone()],
+            sort_by_measurement,
+            &group_by_tag_set,
+            &projection_tag_set,
+        )?;
+
         self.limit(
             plan,
             select.offset,
@@ -694,6 +712,16 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             &projection_tag_set,
         )?;
 
+        let plan = self.series_limit(
+            plan,
+            select.series_offset,
+            select.series_limit,
+            vec![time_sort_expr.clone()],
+            false,
+            &group_by_tag_set,
+            &projection_tag_set,
+        )?;
+
         Ok(Some(self.limit(
             plan,
             select.offset,
@@ -945,8 +973,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
     /// type. These a queries that include a single FIRST, LAST, MAX, MIN,
     /// PERCENTILE, or SAMPLE function call, possibly requesting additional
     /// tags or fields.
-    ///
-    /// N.B SAMPLE is not yet implemented.
     fn project_select_selector(
         &self,
         ctx: &Context<'_>,
@@ -1010,8 +1036,27 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
                 (idx, field_key, plan)
             }
-            (_, Selector::Sample { field_key: _, n: _ }) => {
-                return error::not_implemented("sample selector function")
+            (idx, Selector::Sample { field_key, n }) => {
+                let window_sample = Expr::WindowFunction(WindowFunction::new(
+                    SAMPLE.clone(),
+                    vec![lit(n)],
+                    window_partition_by(ctx, input.schema(), group_by_tag_set),
+                    vec![ctx.time_sort_expr()],
+                    WindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                    },
+                ));
+                let sample_column_name = window_sample.display_name()?;
+
+                let plan = LogicalPlanBuilder::from(input)
+                    .filter(field_key.as_expr().is_not_null())?
+                    .window(vec![window_sample.alias(sample_column_name.clone())])?
+                    .filter(col(sample_column_name))?
+                    .build()?;
+
+                (idx, field_key, plan)
             }
 
             (_, s) => {
@@ -1208,6 +1253,19 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             }
         }
 
+        // The integral of a `GROUP BY time` window is interpolated at the window
+        // boundaries, using the points adjacent to the window.
+        let input = match ctx.interval {
+            Some(interval) => integral_adjacent_points(
+                input,
+                interval,
+                group_by_tag_set,
+                &mut aggr_exprs,
+                &mut select_exprs,
+            )?,
+            None => input,
+        };
+
         // This block identifies the time column index and updates the time expression
         // based on the semantics of the projection.
         let time_column = {
@@ -1376,9 +1434,54 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             .map(|e| Self::udf_to_expr(ctx, e, partition_by.clone(), order_by.clone()))
             .collect::<Result<Vec<_>>>()?;
 
-        let plan = LogicalPlanBuilder::from(input)
-            .window(window_func_exprs)?
-            .build()?;
+        // HOLT_WINTERS produces a different number of rows to its input, so
+        // the forecast points are returned as a list from the last row of each
+        // partition and expanded.
+        let holt_winters = udfs.iter().find_map(|e| match e {
+            Expr::ScalarUDF(expr::ScalarUDF { fun, .. }) => matches!(
+                udf::WindowFunction::try_from_scalar_udf(Arc::clone(fun)),
+                Some(udf::WindowFunction::HoltWinters | udf::WindowFunction::HoltWintersWithFit)
+            )
+            .then(|| e.display_name()),
+            _ => None,
+        });
+
+        let plan = match holt_winters {
+            Some(alias) => {
+                if udfs.len() > 1 {
+                    return error::not_implemented(
+                        "holt_winters combined with other window functions",
+                    );
+                }
+                let alias = alias?;
+
+                let plan = LogicalPlanBuilder::from(input)
+                    .window(window_func_exprs)?
+                    .unnest_column(Column::from_name(&alias))?
+                    .build()?;
+
+                let point = Expr::Column(Column::from_name(&alias));
+                let exprs = plan
+                    .schema()
+                    .fields()
+                    .iter()
+                    .map(|f| {
+                        if f.name() == &alias {
+                            point.clone().field("value").alias(&alias)
+                        } else if f.name() == "time" {
+                            point.clone().field("time").alias("time")
+                        } else {
+                            Expr::Column(f.qualified_column())
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                LogicalPlanBuilder::from(plan).project(exprs)?.build()?
+            }
+            None => LogicalPlanBuilder::from(input)
+                .window(window_func_exprs)?
+                .build()?,
+        };
 
         // Rewrite the window columns from the projection, so that the expressions
         // refer to the columns from the window projection.
@@ -1469,6 +1572,18 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             }
         }
 
+        fn elapsed_unit(args: &[Expr]) -> Result<ScalarValue> {
+            if args.len() > 1 {
+                if let Expr::Literal(v) = &args[1] {
+                    Ok(v.clone())
+                } else {
+                    error::internal(format!("udf_to_expr: unexpected expression: {}", args[1]))
+                }
+            } else {
+                Ok(ScalarValue::new_interval_mdn(0, 0, 1)) // 1ns
+            }
+        }
+
         match udf::WindowFunction::try_from_scalar_udf(Arc::clone(&fun)) {
             Some(udf::WindowFunction::MovingAverage) => Ok(Expr::WindowFunction(WindowFunction {
                 fun: MOVING_AVERAGE.clone(),
@@ -1554,6 +1669,116 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                 },
             })
             .alias(alias)),
+            Some(udf::WindowFunction::Elapsed) => Ok(Expr::WindowFunction(WindowFunction {
+                fun: ELAPSED.clone(),
+                args: vec![args[0].clone(), lit(elapsed_unit(&args)?), "time".as_expr()],
+                partition_by,
+                order_by,
+                window_frame: WindowFrame {
+                    units: WindowFrameUnits::Rows,
+                    start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                    end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                },
+            })
+            .alias(alias)),
+            Some(
+                func @ (udf::WindowFunction::ExponentialMovingAverage
+                | udf::WindowFunction::DoubleExponentialMovingAverage
+                | udf::WindowFunction::TripleExponentialMovingAverage
+                | udf::WindowFunction::RelativeStrengthIndex
+                | udf::WindowFunction::TripleExponentialDerivative
+                | udf::WindowFunction::ChandeMomentumOscillator
+                | udf::WindowFunction::KaufmansEfficiencyRatio
+                | udf::WindowFunction::KaufmansAdaptiveMovingAverage),
+            ) => {
+                // The default warmup type matches InfluxQL OG, and the Kaufman
+                // functions do not accept a warmup type.
+                let (fun, warmup) = match func {
+                    udf::WindowFunction::ExponentialMovingAverage => {
+                        (EXPONENTIAL_MOVING_AVERAGE.clone(), Some("exponential"))
+                    }
+                    udf::WindowFunction::DoubleExponentialMovingAverage => (
+                        DOUBLE_EXPONENTIAL_MOVING_AVERAGE.clone(),
+                        Some("exponential"),
+                    ),
+                    udf::WindowFunction::TripleExponentialMovingAverage => (
+                        TRIPLE_EXPONENTIAL_MOVING_AVERAGE.clone(),
+                        Some("exponential"),
+                    ),
+                    udf::WindowFunction::RelativeStrengthIndex => {
+                        (RELATIVE_STRENGTH_INDEX.clone(), Some("exponential"))
+                    }
+                    udf::WindowFunction::TripleExponentialDerivative => {
+                        (TRIPLE_EXPONENTIAL_DERIVATIVE.clone(), Some("exponential"))
+                    }
+                    udf::WindowFunction::ChandeMomentumOscillator => {
+                        (CHANDE_MOMENTUM_OSCILLATOR.clone(), Some("none"))
+                    }
+                    udf::WindowFunction::KaufmansEfficiencyRatio => {
+                        (KAUFMANS_EFFICIENCY_RATIO.clone(), None)
+                    }
+                    _ => (KAUFMANS_ADAPTIVE_MOVING_AVERAGE.clone(), None),
+                };
+
+                let mut args = args;
+                if args.len() < 3 {
+                    // A hold period of -1 uses the warmup period of the indicator.
+                    args.push(lit(-1_i64));
+                }
+                if let Some(warmup) = warmup {
+                    if args.len() < 4 {
+                        args.push(lit(warmup));
+                    }
+                }
+
+                Ok(Expr::WindowFunction(WindowFunction {
+                    fun,
+                    args,
+                    partition_by,
+                    order_by,
+                    window_frame: WindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                    },
+                })
+                .alias(alias))
+            }
+            Some(
+                func @ (udf::WindowFunction::HoltWinters | udf::WindowFunction::HoltWintersWithFit),
+            ) => {
+                let Some(interval) = ctx.interval else {
+                    return error::query(format!(
+                        "{} aggregate requires a GROUP BY interval",
+                        fun.name
+                    ));
+                };
+
+                let fun = if matches!(func, udf::WindowFunction::HoltWinters) {
+                    HOLT_WINTERS.clone()
+                } else {
+                    HOLT_WINTERS_WITH_FIT.clone()
+                };
+
+                Ok(Expr::WindowFunction(WindowFunction {
+                    fun,
+                    args: vec![
+                        args[0].clone(),
+                        "time".as_expr(),
+                        args[1].clone(),
+                        args[2].clone(),
+                        lit(ScalarValue::new_interval_mdn(0, 0, interval.duration)),
+                    ],
+                    partition_by,
+                    order_by,
+                    window_frame: WindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                    },
+                })
+                .alias(alias))
+            }
             None => error::internal(format!(
                 "unexpected user-defined window function: {}",
                 fun.name
@@ -1658,34 +1883,9 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                 .transpose()
                 .map_err(|_| error::map::query("offset out of range".to_owned()))?;
 
-            // a reference to the ROW_NUMBER column.
-            let row_alias = IOX_ROW_ALIAS.as_expr();
-
-            let row_filter_expr = match (limit, offset) {
-                // WHERE "iox::row" BETWEEN OFFSET + 1 AND OFFSET + LIMIT
-                (Some(limit), Some(offset)) => {
-                    let low = offset + 1;
-                    let high = offset + limit;
-
-                    Expr::Between(Between {
-                        expr: Box::new(row_alias),
-                        negated: false,
-                        low: Box::new(lit(low)),
-                        high: Box::new(lit(high)),
-                    })
-                }
-
-                // WHERE "iox::row" <= LIMIT
-                (Some(limit), None) => row_alias.lt_eq(lit(limit)),
-
-                // WHERE "iox::row" > OFFSET
-                (None, Some(offset)) => row_alias.gt(lit(offset)),
-                (None, None) => unreachable!("limit and offset cannot not be None"),
-            };
-
             let plan = LogicalPlanBuilder::from(plan)
                 // Filter by the LIMIT and OFFSET clause
-                .filter(row_filter_expr)?
+                .filter(window_filter_expr(IOX_ROW_ALIAS.as_expr(), limit, offset))?
                 // Project the output without the IOX_ROW_ALIAS column
                 .project(proj_exprs)?
                 .build()?;
@@ -1702,6 +1902,122 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         }
     }
 
+    /// Generate a plan that restricts the input data to a window of series, first omitting
+    /// a specified number of series, followed by restricting the quantity of series
+    /// returned for each measurement.
+    ///
+    /// A series is identified by the unique values of the tags from the `GROUP BY` clause,
+    /// and series are ranked by their tag values, in lexicographically ascending order.
+    /// As InfluxDB 1.x treats a missing tag value as an empty string, series without a
+    /// value for a tag are ranked first. Series are limited before any `LIMIT` or `OFFSET`
+    /// clause is applied to the rows of each series.
+    ///
+    /// ## Arguments
+    ///
+    /// - `input`: The plan to apply the series limit to.
+    /// - `soffset`: The number of series to skip.
+    /// - `slimit`: The maximum number of series to return in the output plan per measurement.
+    /// - `sort_exprs`: The `Expr::Sort` expressions ordering the rows of each series in the
+    ///   output plan.
+    /// - `sort_by_measurement`: `true` if the `input` must be sorted by the measurement column.
+    /// - `group_by_tag_set`: Tag columns from the `input` plan that identify each series.
+    /// - `projection_tag_set`: Additional tag columns that should be used to sort the `output`
+    ///   plan.
+    #[allow(clippy::too_many_arguments)]
+    fn series_limit(
+        &self,
+        input: LogicalPlan,
+        soffset: Option<SOffsetClause>,
+        slimit: Option<SLimitClause>,
+        sort_exprs: Vec<Expr>,
+        sort_by_measurement: bool,
+        group_by_tag_set: &[&str],
+        projection_tag_set: &[&str],
+    ) -> Result<LogicalPlan> {
+        if soffset.is_none() && slimit.is_none() {
+            return Ok(input);
+        }
+
+        // The name of the DENSE_RANK window expression
+        const IOX_SERIES_ALIAS: &str = "iox::series";
+
+        // Construct a DENSE_RANK window expression, such that each row of a series is
+        // assigned the same rank:
+        //
+        // DENSE_RANK() OVER (
+        //   PARTITION BY [iox::measurement]
+        //   ORDER BY [group_by_tag_set] ASC NULLS FIRST
+        //   RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+        // ) AS iox::series
+        //
+        // When the query has no GROUP BY tags, each measurement is a single series, and
+        // all rows are assigned a rank of 1.
+        let partition_by = if sort_by_measurement {
+            vec![INFLUXQL_MEASUREMENT_COLUMN_NAME.as_expr()]
+        } else {
+            vec![]
+        };
+
+        let order_by = fields_to_exprs_no_nulls(input.schema(), group_by_tag_set)
+            .map(|e| e.sort(true, true))
+            .collect::<Vec<_>>();
+
+        let window_func_exprs = vec![Expr::WindowFunction(WindowFunction {
+            fun: window_function::WindowFunction::BuiltInWindowFunction(
+                BuiltInWindowFunction::DenseRank,
+            ),
+            args: vec![],
+            partition_by,
+            order_by,
+            window_frame: WindowFrame {
+                units: WindowFrameUnits::Range,
+                start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                end_bound: WindowFrameBound::CurrentRow,
+            },
+        })
+        .alias(IOX_SERIES_ALIAS)];
+
+        // Prepare new projection.
+        let proj_exprs = input
+            .schema()
+            .fields()
+            .iter()
+            .map(|expr| Expr::Column(expr.unqualified_column()))
+            .collect::<Vec<_>>();
+
+        let plan = LogicalPlanBuilder::from(input)
+            .window(window_func_exprs)?
+            .build()?;
+
+        let slimit = slimit
+            .map(|v| <u64 as TryInto<i64>>::try_into(*v))
+            .transpose()
+            .map_err(|_| error::map::query("slimit out of range"))?;
+        let soffset = soffset
+            .map(|v| <u64 as TryInto<i64>>::try_into(*v))
+            .transpose()
+            .map_err(|_| error::map::query("soffset out of range"))?;
+
+        let plan = LogicalPlanBuilder::from(plan)
+            // Filter by the SLIMIT and SOFFSET clause
+            .filter(window_filter_expr(
+                IOX_SERIES_ALIAS.as_expr(),
+                slimit,
+                soffset,
+            ))?
+            // Project the output without the IOX_SERIES_ALIAS column
+            .project(proj_exprs)?
+            .build()?;
+
+        plan_with_sort(
+            plan,
+            sort_exprs,
+            sort_by_measurement,
+            group_by_tag_set,
+            projection_tag_set,
+        )
+    }
+
     /// Map the InfluxQL `SELECT` projection list into a list of DataFusion expressions.
     fn field_list_to_exprs(
         &self,
@@ -1996,6 +2312,56 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     None,
                 )))
             }
+            "spread" => {
+                let expr = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                check_arg_count(name, args, 1)?;
+                Ok(Expr::AggregateUDF(expr::AggregateUDF::new(
+                    SPREAD.clone(),
+                    vec![expr],
+                    None,
+                    None,
+                )))
+            }
+            "mode" => {
+                let expr = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                check_arg_count(name, args, 1)?;
+                Ok(Expr::AggregateUDF(expr::AggregateUDF::new(
+                    MODE.clone(),
+                    vec![expr, "time".as_expr()],
+                    None,
+                    None,
+                )))
+            }
+            "integral" => {
+                check_arg_count_range(name, args, 1, 2)?;
+
+                let expr = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                // The unit of the integral defaults to 1s
+                let unit = if args.len() > 1 {
+                    self.expr_to_df_expr(scope, &args[1], schema)?
+                } else {
+                    lit(ScalarValue::new_interval_mdn(0, 0, 1_000_000_000))
+                };
+
+                Ok(Expr::AggregateUDF(expr::AggregateUDF::new(
+                    INTEGRAL.clone(),
+                    vec![expr, "time".as_expr(), unit],
+                    None,
+                    None,
+                )))
+            }
             name @ ("first" | "last" | "min" | "max") => {
                 let expr = self.expr_to_df_expr(scope, &args[0], schema)?;
                 if let Expr::Literal(ScalarValue::Null) = expr {
@@ -2091,6 +2457,59 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
                 Ok(non_negative_derivative(eargs))
             }
+            "elapsed" => {
+                check_arg_count_range(name, args, 1, 2)?;
+
+                // arg0 should be a column or function
+                let arg0 = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = arg0 {
+                    return Ok(arg0);
+                }
+                let mut eargs = vec![arg0];
+                if args.len() > 1 {
+                    let arg1 = self.expr_to_df_expr(scope, &args[1], schema)?;
+                    eargs.push(arg1);
+                }
+
+                Ok(elapsed(eargs))
+            }
+            "exponential_moving_average"
+            | "double_exponential_moving_average"
+            | "triple_exponential_moving_average"
+            | "relative_strength_index"
+            | "triple_exponential_derivative"
+            | "chande_momentum_oscillator"
+            | "kaufmans_efficiency_ratio"
+            | "kaufmans_adaptive_moving_average"
+            | "holt_winters"
+            | "holt_winters_with_fit" => {
+                check_arg_count_range(name, args, 2, 4)?;
+
+                // arg0 should be a column or function
+                let arg0 = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = arg0 {
+                    return Ok(arg0);
+                }
+
+                // The remaining arguments are validated as literals by the rewriter.
+                let mut eargs = vec![arg0];
+                for arg in &args[1..] {
+                    eargs.push(self.expr_to_df_expr(scope, arg, schema)?);
+                }
+
+                Ok(match name {
+                    "exponential_moving_average" => exponential_moving_average(eargs),
+                    "double_exponential_moving_average" => double_exponential_moving_average(eargs),
+                    "triple_exponential_moving_average" => triple_exponential_moving_average(eargs),
+                    "relative_strength_index" => relative_strength_index(eargs),
+                    "triple_exponential_derivative" => triple_exponential_derivative(eargs),
+                    "chande_momentum_oscillator" => chande_momentum_oscillator(eargs),
+                    "kaufmans_efficiency_ratio" => kaufmans_efficiency_ratio(eargs),
+                    "kaufmans_adaptive_moving_average" => kaufmans_adaptive_moving_average(eargs),
+                    "holt_winters" => holt_winters(eargs),
+                    _ => holt_winters_with_fit(eargs),
+                })
+            }
             "cumulative_sum" => {
                 check_arg_count(name, args, 1)?;
 
@@ -3053,6 +3472,32 @@ fn build_gap_fill_node(
     }))
 }
 
+/// Returns a predicate which selects the rows where `expr`, a 1-based row number or rank,
+/// is within the window specified by `limit` and `offset`.
+fn window_filter_expr(expr: Expr, limit: Option<i64>, offset: Option<i64>) -> Expr {
+    match (limit, offset) {
+        // WHERE expr BETWEEN OFFSET + 1 AND OFFSET + LIMIT
+        (Some(limit), Some(offset)) => {
+            let low = offset + 1;
+            let high = offset + limit;
+
+            Expr::Between(Between {
+                expr: Box::new(expr),
+                negated: false,
+                low: Box::new(lit(low)),
+                high: Box::new(lit(high)),
+            })
+        }
+
+        // WHERE expr <= LIMIT
+        (Some(limit), None) => expr.lt_eq(lit(limit)),
+
+        // WHERE expr > OFFSET
+        (None, Some(offset)) => expr.gt(lit(offset)),
+        (None, None) => unreachable!("limit and offset cannot not be None"),
+    }
+}
+
 /// Adds [`InfluxQlMetadata`] to the `plan`.
 fn plan_with_metadata(plan: LogicalPlan, metadata: &InfluxQlMetadata) -> Result<LogicalPlan> {
     fn make_schema(schema: DFSchemaRef, metadata: &InfluxQlMetadata) -> Result<DFSchemaRef> {
@@ -3295,6 +3740,96 @@ fn find_var_refs(select: &Select) -> BTreeSet<&VarRef> {
     var_refs
 }
 
+/// Rewrite the `INTEGRAL` aggregates of a `GROUP BY time` query, to include
+/// the window of the aggregate and the points of the same series adjacent
+/// to each row of the input, so that the values at the boundaries of the
+/// window may be interpolated.
+///
+/// The adjacent points are projected by a window node over `input`, which
+/// is returned.
+fn integral_adjacent_points(
+    input: LogicalPlan,
+    interval: Interval,
+    group_by_tags: &[&str],
+    aggr_exprs: &mut [Expr],
+    select_exprs: &mut [Expr],
+) -> Result<LogicalPlan> {
+    let mut window_exprs = Vec::new();
+
+    for aggr in aggr_exprs.iter_mut() {
+        let args = match aggr {
+            Expr::AggregateUDF(udf) if udf.fun.name == INTEGRAL.name && udf.args.len() == 3 => {
+                udf.args.clone()
+            }
+            _ => continue,
+        };
+        let value = args[0].clone();
+
+        // Rows where the value is NULL are not points of the series, so are
+        // placed in a separate partition.
+        let mut partition_by =
+            fields_to_exprs_no_nulls(input.schema(), group_by_tags).collect::<Vec<_>>();
+        partition_by.push(value.clone().is_null());
+
+        let mut adjacent = |fun, arg: &Expr| -> Result<Expr> {
+            let expr = Expr::WindowFunction(WindowFunction::new(
+                window_function::WindowFunction::BuiltInWindowFunction(fun),
+                vec![arg.clone()],
+                partition_by.clone(),
+                vec![args[1].clone().sort(true, false)],
+                WindowFrame {
+                    units: WindowFrameUnits::Rows,
+                    start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                    end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                },
+            ));
+            let name = expr.display_name()?;
+            let expr = expr.alias(&name);
+            if !window_exprs.contains(&expr) {
+                window_exprs.push(expr);
+            }
+            Ok(Expr::Column(Column::from_name(name)))
+        };
+
+        let time = args[1].clone();
+        let integral = Expr::AggregateUDF(expr::AggregateUDF::new(
+            INTEGRAL.clone(),
+            vec![
+                value.clone(),
+                time.clone(),
+                args[2].clone(),
+                lit(interval.duration),
+                lit(interval.offset.unwrap_or_default()),
+                adjacent(window_function::BuiltInWindowFunction::Lag, &time)?,
+                adjacent(window_function::BuiltInWindowFunction::Lag, &value)?,
+                adjacent(window_function::BuiltInWindowFunction::Lead, &time)?,
+                adjacent(window_function::BuiltInWindowFunction::Lead, &value)?,
+            ],
+            None,
+            None,
+        ));
+
+        let original = std::mem::replace(aggr, integral.clone());
+        for expr in select_exprs.iter_mut() {
+            *expr = expr.clone().transform_up(&|e| {
+                Ok(if e == original {
+                    Transformed::Yes(integral.clone())
+                } else {
+                    Transformed::No(e)
+                })
+            })?;
+        }
+    }
+
+    if window_exprs.is_empty() {
+        return Ok(input);
+    }
+
+    LogicalPlanBuilder::from(input)
+        .window(window_exprs)?
+        .build()
+}
+
 /// Calculate the partitioning for window functions.
 fn window_partition_by(
     ctx: &Context<'_>,
@@ -3458,11 +3993,14 @@ mod test {
     /// Verify the list of unsupported statements.
     ///
     /// It is expected certain statements will be unsupported, indefinitely.
+    /// The data management statements are executed by the frontend, rather
+    /// than planned as a query.
     #[test]
     fn test_unsupported_statements() {
         assert_snapshot!(plan("CREATE DATABASE foo"), @"This feature is not implemented: CREATE DATABASE");
         assert_snapshot!(plan("DELETE FROM foo"), @"This feature is not implemented: DELETE");
         assert_snapshot!(plan("DROP MEASUREMENT foo"), @"This feature is not implemented: DROP MEASUREMENT");
+        assert_snapshot!(plan("DROP SERIES FROM foo"), @"This feature is not implemented: DROP SERIES");
         assert_snapshot!(plan("SHOW DATABASES"), @"This feature is not implemented: SHOW DATABASES");
     }
 
@@ -4177,9 +4715,76 @@ mod test {
                 "###);
             }
 
+            #[test]
+            fn test_elapsed() {
+                // default unit
+                assert_snapshot!(plan("SELECT ELAPSED(usage_idle) FROM cpu"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, elapsed [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                    Filter: NOT elapsed IS NULL [time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                      Projection: cpu.time AS time, elapsed(cpu.usage_idle) AS elapsed [time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                        WindowAggr: windowExpr=[[elapsed(cpu.usage_idle, IntervalMonthDayNano("1"), cpu.time) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS elapsed(cpu.usage_idle)]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, elapsed(cpu.usage_idle):Int64;N]
+                          TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+
+                // explicit unit
+                assert_snapshot!(plan("SELECT ELAPSED(usage_idle, 1s) FROM cpu"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, elapsed [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                    Filter: NOT elapsed IS NULL [time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                      Projection: cpu.time AS time, elapsed(cpu.usage_idle,IntervalMonthDayNano("1000000000")) AS elapsed [time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                        WindowAggr: windowExpr=[[elapsed(cpu.usage_idle, IntervalMonthDayNano("1000000000"), cpu.time) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS elapsed(cpu.usage_idle,IntervalMonthDayNano("1000000000"))]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, elapsed(cpu.usage_idle,IntervalMonthDayNano("1000000000")):Int64;N]
+                          TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+            }
+
+            #[test]
+            fn test_exponential_moving_average() {
+                // default hold period and warmup type
+                assert_snapshot!(plan("SELECT EXPONENTIAL_MOVING_AVERAGE(usage_idle, 3) FROM cpu"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), exponential_moving_average:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, exponential_moving_average [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), exponential_moving_average:Float64;N]
+                    Filter: NOT exponential_moving_average IS NULL [time:Timestamp(Nanosecond, None), exponential_moving_average:Float64;N]
+                      Projection: cpu.time AS time, exponential_moving_average(cpu.usage_idle,Int64(3)) AS exponential_moving_average [time:Timestamp(Nanosecond, None), exponential_moving_average:Float64;N]
+                        WindowAggr: windowExpr=[[exponential_moving_average(cpu.usage_idle, Int64(3), Int64(-1), Utf8("exponential")) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS exponential_moving_average(cpu.usage_idle,Int64(3))]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, exponential_moving_average(cpu.usage_idle,Int64(3)):Float64;N]
+                          TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+
+                // aggregate, with explicit hold period and warmup type
+                assert_snapshot!(plan("SELECT EXPONENTIAL_MOVING_AVERAGE(MEAN(usage_idle), 3, 0, 'simple') FROM cpu GROUP BY TIME(10s)"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, exponential_moving_average:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, exponential_moving_average [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, exponential_moving_average:Float64;N]
+                    Filter: NOT exponential_moving_average IS NULL [time:Timestamp(Nanosecond, None);N, exponential_moving_average:Float64;N]
+                      Projection: time, exponential_moving_average(AVG(cpu.usage_idle),Int64(3),Int64(0),Utf8("simple")) AS exponential_moving_average [time:Timestamp(Nanosecond, None);N, exponential_moving_average:Float64;N]
+                        WindowAggr: windowExpr=[[exponential_moving_average(AVG(cpu.usage_idle), Int64(3), Int64(0), Utf8("simple")) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS exponential_moving_average(AVG(cpu.usage_idle),Int64(3),Int64(0),Utf8("simple"))]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, exponential_moving_average(AVG(cpu.usage_idle),Int64(3),Int64(0),Utf8("simple")):Float64;N]
+                          GapFill: groupBy=[time], aggr=[[AVG(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("10000000000"), range=Unbounded..Included(Literal(TimestampNanosecond(1672531200000000000, None))) [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                            Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                              Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                                TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+            }
+
+            #[test]
+            fn test_holt_winters() {
+                assert_snapshot!(plan("SELECT HOLT_WINTERS(MEAN(usage_idle), 3, 0) FROM cpu GROUP BY TIME(10s)"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), holt_winters:Float64]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, holt_winters [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), holt_winters:Float64]
+                    Filter: NOT holt_winters IS NULL [time:Timestamp(Nanosecond, None), holt_winters:Float64]
+                      Projection: time, holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(0)) AS holt_winters [time:Timestamp(Nanosecond, None), holt_winters:Float64]
+                        Projection: (holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(0)))[time] AS time, AVG(cpu.usage_idle), (holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(0)))[value] AS holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(0)) [time:Timestamp(Nanosecond, None), AVG(cpu.usage_idle):Float64;N, holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(0)):Float64]
+                          Unnest: holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(0)) [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(0)):Struct([Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "value", data_type: Float64, nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                            WindowAggr: windowExpr=[[holt_winters(AVG(cpu.usage_idle), time, Int64(3), Int64(0), IntervalMonthDayNano("10000000000")) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(0))]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(0)):List(Field { name: "item", data_type: Struct([Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "value", data_type: Float64, nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }]), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} });N]
+                              GapFill: groupBy=[time], aggr=[[AVG(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("10000000000"), range=Unbounded..Included(Literal(TimestampNanosecond(1672531200000000000, None))) [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                                Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                                  Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                                    TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+            }
+
             #[test]
             fn test_not_implemented() {
                 assert_snapshot!(plan("SELECT DIFFERENCE(MEAN(usage_idle)), MEAN(usage_idle) FROM cpu GROUP BY TIME(10s)"), @"This feature is not implemented: mixed window-aggregate and aggregate columns, such as DIFFERENCE(MEAN(col)), MEAN(col)");
+                assert_snapshot!(plan("SELECT HOLT_WINTERS(MEAN(usage_idle), 2, 1), DIFFERENCE(MEAN(usage_idle)) FROM cpu GROUP BY TIME(10s)"), @"This feature is not implemented: holt_winters combined with other window functions");
             }
         }
 
@@ -4414,6 +5019,68 @@ mod test {
             "###);
         }
 
+        #[test]
+        fn test_spread() {
+            assert_snapshot!(plan("SELECT spread(usage_idle) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), spread:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, TimestampNanosecond(0, None) AS time, spread(cpu.usage_idle) AS spread [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), spread:Float64;N]
+                Aggregate: groupBy=[[]], aggr=[[spread(cpu.usage_idle)]] [spread(cpu.usage_idle):Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+        }
+
+        #[test]
+        fn test_mode() {
+            assert_snapshot!(plan("SELECT mode(usage_idle) FROM cpu GROUP BY cpu"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, mode:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, TimestampNanosecond(0, None) AS time, cpu.cpu AS cpu, mode(cpu.usage_idle,cpu.time) AS mode [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, mode:Float64;N]
+                Aggregate: groupBy=[[cpu.cpu]], aggr=[[mode(cpu.usage_idle, cpu.time)]] [cpu:Dictionary(Int32, Utf8);N, mode(cpu.usage_idle,cpu.time):Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+        }
+
+        #[test]
+        fn test_integral() {
+            // default unit
+            assert_snapshot!(plan("SELECT integral(usage_idle) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), integral:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, TimestampNanosecond(0, None) AS time, integral(cpu.usage_idle,cpu.time,IntervalMonthDayNano("1000000000")) AS integral [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), integral:Float64;N]
+                Aggregate: groupBy=[[]], aggr=[[integral(cpu.usage_idle, cpu.time, IntervalMonthDayNano("1000000000"))]] [integral(cpu.usage_idle,cpu.time,IntervalMonthDayNano("1000000000")):Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // explicit unit
+            assert_snapshot!(plan("SELECT integral(usage_idle, 1m) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), integral:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, TimestampNanosecond(0, None) AS time, integral(cpu.usage_idle,cpu.time,IntervalMonthDayNano("60000000000")) AS integral [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), integral:Float64;N]
+                Aggregate: groupBy=[[]], aggr=[[integral(cpu.usage_idle, cpu.time, IntervalMonthDayNano("60000000000"))]] [integral(cpu.usage_idle,cpu.time,IntervalMonthDayNano("60000000000")):Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // interpolated at the GROUP BY time window boundaries
+            assert_snapshot!(plan("SELECT integral(usage_idle) FROM cpu GROUP BY TIME(10s)"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, integral:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, integral(cpu.usage_idle,cpu.time,IntervalMonthDayNano("1000000000"),Int64(10000000000),Int64(0),LAG(cpu.time) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING,LAG(cpu.usage_idle) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING,LEAD(cpu.time) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING,LEAD(cpu.usage_idle) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING) AS integral [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, integral:Float64;N]
+                GapFill: groupBy=[time], aggr=[[integral(cpu.usage_idle,cpu.time,IntervalMonthDayNano("1000000000"),Int64(10000000000),Int64(0),LAG(cpu.time) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING,LAG(cpu.usage_idle) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING,LEAD(cpu.time) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING,LEAD(cpu.usage_idle) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING)]], time_column=time, stride=IntervalMonthDayNano("10000000000"), range=Unbounded..Included(Literal(TimestampNanosecond(1672531200000000000, None))) [time:Timestamp(Nanosecond, None);N, integral(cpu.usage_idle,cpu.time,IntervalMonthDayNano("1000000000"),Int64(10000000000),Int64(0),LAG(cpu.time) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING,LAG(cpu.usage_idle) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING,LEAD(cpu.time) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING,LEAD(cpu.usage_idle) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING):Float64;N]
+                  Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[integral(cpu.usage_idle, cpu.time, IntervalMonthDayNano("1000000000"), Int64(10000000000), Int64(0), LAG(cpu.time) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING, LAG(cpu.usage_idle) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING, LEAD(cpu.time) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING, LEAD(cpu.usage_idle) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING)]] [time:Timestamp(Nanosecond, None);N, integral(cpu.usage_idle,cpu.time,IntervalMonthDayNano("1000000000"),Int64(10000000000),Int64(0),LAG(cpu.time) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING,LAG(cpu.usage_idle) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING,LEAD(cpu.time) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING,LEAD(cpu.usage_idle) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING):Float64;N]
+                    WindowAggr: windowExpr=[[LAG(cpu.time) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS LAG(cpu.time) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING, LAG(cpu.usage_idle) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS LAG(cpu.usage_idle) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING, LEAD(cpu.time) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS LEAD(cpu.time) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING, LEAD(cpu.usage_idle) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS LEAD(cpu.usage_idle) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, LAG(cpu.time) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING:Timestamp(Nanosecond, None);N, LAG(cpu.usage_idle) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING:Float64;N, LEAD(cpu.time) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING:Timestamp(Nanosecond, None);N, LEAD(cpu.usage_idle) PARTITION BY [cpu.usage_idle IS NULL] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING:Float64;N]
+                      Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                        TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+        }
+
+        #[test]
+        fn test_sample() {
+            assert_snapshot!(plan("SELECT sample(usage_idle, 2) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), sample:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS sample [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), sample:Float64;N]
+                Filter: sample(Int64(2)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, sample(Int64(2)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING:Boolean;N]
+                  WindowAggr: windowExpr=[[sample(Int64(2)) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS sample(Int64(2)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, sample(Int64(2)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING:Boolean;N]
+                    Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+        }
+
         #[test]
         fn test_top() {
             assert_snapshot!(plan("SELECT top(usage_idle,10) FROM cpu"), @r###"
@@ -5499,6 +6166,55 @@ mod test {
             "###);
         }
 
+        #[test]
+        fn test_select_group_by_slimit_soffset() {
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu SLIMIT 1"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::series <= Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                  WindowAggr: windowExpr=[[DENSE_RANK() ORDER BY [cpu ASC NULLS FIRST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                    Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                        TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu SOFFSET 1"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::series > Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                  WindowAggr: windowExpr=[[DENSE_RANK() ORDER BY [cpu ASC NULLS FIRST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                    Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                        TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu SLIMIT 1 SOFFSET 1"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::series BETWEEN Int64(2) AND Int64(2) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                  WindowAggr: windowExpr=[[DENSE_RANK() ORDER BY [cpu ASC NULLS FIRST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                    Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                        TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu LIMIT 1 SLIMIT 1"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::row <= Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::row:UInt64;N]
+                  WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [cpu] ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::row]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::row:UInt64;N]
+                    Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                        Filter: iox::series <= Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                          WindowAggr: windowExpr=[[DENSE_RANK() ORDER BY [cpu ASC NULLS FIRST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                                TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // returns an error if SLIMIT or SOFFSET values exceed i64::MAX
+            let max = (i64::MAX as u64) + 1;
+            assert_snapshot!(plan(format!("SELECT usage_idle FROM cpu GROUP BY cpu SLIMIT {max}")), @"Error during planning: slimit out of range");
+            assert_snapshot!(plan(format!("SELECT usage_idle FROM cpu GROUP BY cpu SOFFSET {max}")), @"Error during planning: soffset out of range");
+        }
+
         #[test]
         fn test_select_function_tag_column() {
             assert_snapshot!(plan("SELECT last(foo) as foo, first(usage_idle) from cpu group by foo"), @r###"
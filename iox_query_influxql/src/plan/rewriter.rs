@@ -68,24 +68,6 @@ fn rewrite_select(s: &dyn SchemaProvider, stmt: &SelectStatement) -> Result<Sele
     rw.rewrite(s, stmt)
 }
 
-/// Asserts that the `SELECT` statement does not use any unimplemented features.
-///
-/// The list of unimplemented or unsupported features are listed below.
-///
-/// # `SLIMIT` and `SOFFSET`
-///
-/// * `SLIMIT` and `SOFFSET` don't work as expected per issue [#7571]
-/// * This issue [is noted](https://docs.influxdata.com/influxdb/v1.8/query_language/explore-data/#the-slimit-clause) in our official documentation
-///
-/// [#7571]: https://github.com/influxdata/influxdb/issues/7571
-fn check_features(stmt: &SelectStatement) -> Result<()> {
-    if stmt.series_limit.is_some() || stmt.series_offset.is_some() {
-        return error::not_implemented("SLIMIT or SOFFSET");
-    }
-
-    Ok(())
-}
-
 #[derive(Default)]
 struct RewriteSelect {
     /// The depth of the `SELECT` statement currently processed by the rewriter.
@@ -96,8 +78,6 @@ impl RewriteSelect {
     /// Transform a `SelectStatement` to a `Select`, which is an intermediate representation used by
     /// the InfluxQL planner. Transformations include expanding wildcards.
     fn rewrite(&self, s: &dyn SchemaProvider, stmt: &SelectStatement) -> Result<Select> {
-        check_features(stmt)?;
-
         let from = self.expand_from(s, stmt)?;
         let tag_set = from_tag_set(s, &from);
         let (fields, group_by) = self.expand_projection(s, stmt, &from, &tag_set)?;
@@ -160,6 +140,8 @@ impl RewriteSelect {
             order_by: stmt.order_by,
             limit: stmt.limit,
             offset: stmt.offset,
+            series_limit: stmt.series_limit,
+            series_offset: stmt.series_offset,
             timezone: stmt.timezone.map(|v| *v),
         })
     }
@@ -172,6 +154,10 @@ impl RewriteSelect {
 
     /// Rewrite the `SELECT` statement by applying specific rules for subqueries.
     fn rewrite_subquery(&self, s: &dyn SchemaProvider, stmt: &SelectStatement) -> Result<Select> {
+        if stmt.into.is_some() {
+            return error::query("INTO clause is not allowed in a subquery");
+        }
+
         let rw = Self {
             depth: self.depth + 1,
         };
@@ -1492,7 +1478,7 @@ impl FieldChecker {
     }
 
     fn check_holt_winters(&mut self, name: &str, args: &[Expr]) -> Result<()> {
-        self.inc_aggregate_count();
+        self.inc_window_count();
         check_exp_args!(name, 3, args);
 
         let v = lit_integer!(name, args, 1);
@@ -1766,6 +1752,12 @@ mod test {
         .unwrap();
         assert_matches!(info.projection_type, ProjectionType::WindowAggregateMixed);
 
+        let info = select_statement_info(&parse_select(
+            "SELECT holt_winters(mean(foo), 2, 3) FROM cpu GROUP BY TIME(10s)",
+        ))
+        .unwrap();
+        assert_matches!(info.projection_type, ProjectionType::WindowAggregate);
+
         let info = select_statement_info(&parse_select("SELECT top(foo, 3) FROM cpu")).unwrap();
         assert_matches!(info.projection_type, ProjectionType::TopBottomSelector);
     }
@@ -2451,18 +2443,11 @@ mod test {
                 "Error during planning: unable to use tag as wildcard in count()"
             );
 
-            let stmt = parse_select("SELECT usage_idle FROM cpu SLIMIT 1");
-            let err = rewrite_select_statement(&namespace, &stmt).unwrap_err();
-            assert_eq!(
-                err.to_string(),
-                "This feature is not implemented: SLIMIT or SOFFSET"
-            );
-
-            let stmt = parse_select("SELECT usage_idle FROM cpu SOFFSET 1");
+            let stmt = parse_select("SELECT usage_idle FROM (SELECT usage_idle INTO foo FROM cpu)");
             let err = rewrite_select_statement(&namespace, &stmt).unwrap_err();
             assert_eq!(
                 err.to_string(),
-                "This feature is not implemented: SLIMIT or SOFFSET"
+                "Error during planning: INTO clause is not allowed in a subquery"
             );
         }
 
@@ -6,12 +6,38 @@ use datafusion::logical_expr::{
 use once_cell::sync::Lazy;
 use std::sync::Arc;
 
+mod chande_momentum_oscillator;
 mod cumulative_sum;
 mod derivative;
 mod difference;
+mod elapsed;
+mod ema;
+mod holt_winters;
+mod indicator;
+mod kaufmans;
 mod moving_average;
 mod non_negative;
 mod percent_row_number;
+mod sample;
+
+const CHANDE_MOMENTUM_OSCILLATOR_NAME: &str = "chande_momentum_oscillator";
+
+/// Definition of the `CHANDE_MOMENTUM_OSCILLATOR` user-defined window function.
+pub(crate) static CHANDE_MOMENTUM_OSCILLATOR: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(indicator::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory = Arc::new(|| {
+        Ok(indicator::partition_evaluator(
+            chande_momentum_oscillator::chande_momentum_oscillator,
+        ))
+    });
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        CHANDE_MOMENTUM_OSCILLATOR_NAME,
+        &indicator::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
 
 /// Definition of the `CUMULATIVE_SUM` user-defined window function.
 pub(crate) static CUMULATIVE_SUM: Lazy<WindowFunction> = Lazy::new(|| {
@@ -55,6 +81,128 @@ pub(crate) static DIFFERENCE: Lazy<WindowFunction> = Lazy::new(|| {
     )))
 });
 
+const DOUBLE_EXPONENTIAL_MOVING_AVERAGE_NAME: &str = "double_exponential_moving_average";
+
+/// Definition of the `DOUBLE_EXPONENTIAL_MOVING_AVERAGE` user-defined window function.
+pub(crate) static DOUBLE_EXPONENTIAL_MOVING_AVERAGE: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(indicator::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory = Arc::new(|| {
+        Ok(indicator::partition_evaluator(
+            ema::double_exponential_moving_average,
+        ))
+    });
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        DOUBLE_EXPONENTIAL_MOVING_AVERAGE_NAME,
+        &indicator::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+/// Definition of the `ELAPSED` user-defined window function.
+pub(crate) static ELAPSED: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(elapsed::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(elapsed::partition_evaluator_factory);
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        elapsed::NAME,
+        &elapsed::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+const EXPONENTIAL_MOVING_AVERAGE_NAME: &str = "exponential_moving_average";
+
+/// Definition of the `EXPONENTIAL_MOVING_AVERAGE` user-defined window function.
+pub(crate) static EXPONENTIAL_MOVING_AVERAGE: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(indicator::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory = Arc::new(|| {
+        Ok(indicator::partition_evaluator(
+            ema::exponential_moving_average,
+        ))
+    });
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        EXPONENTIAL_MOVING_AVERAGE_NAME,
+        &indicator::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+const HOLT_WINTERS_NAME: &str = "holt_winters";
+
+/// Definition of the `HOLT_WINTERS` user-defined window function.
+pub(crate) static HOLT_WINTERS: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(holt_winters::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(|| holt_winters::partition_evaluator_factory(false));
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        HOLT_WINTERS_NAME,
+        &holt_winters::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+const HOLT_WINTERS_WITH_FIT_NAME: &str = "holt_winters_with_fit";
+
+/// Definition of the `HOLT_WINTERS_WITH_FIT` user-defined window function.
+pub(crate) static HOLT_WINTERS_WITH_FIT: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(holt_winters::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(|| holt_winters::partition_evaluator_factory(true));
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        HOLT_WINTERS_WITH_FIT_NAME,
+        &holt_winters::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+const KAUFMANS_ADAPTIVE_MOVING_AVERAGE_NAME: &str = "kaufmans_adaptive_moving_average";
+
+/// Definition of the `KAUFMANS_ADAPTIVE_MOVING_AVERAGE` user-defined window function.
+pub(crate) static KAUFMANS_ADAPTIVE_MOVING_AVERAGE: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(indicator::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory = Arc::new(|| {
+        Ok(indicator::partition_evaluator(
+            kaufmans::kaufmans_adaptive_moving_average,
+        ))
+    });
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        KAUFMANS_ADAPTIVE_MOVING_AVERAGE_NAME,
+        &kaufmans::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+const KAUFMANS_EFFICIENCY_RATIO_NAME: &str = "kaufmans_efficiency_ratio";
+
+/// Definition of the `KAUFMANS_EFFICIENCY_RATIO` user-defined window function.
+pub(crate) static KAUFMANS_EFFICIENCY_RATIO: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(indicator::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory = Arc::new(|| {
+        Ok(indicator::partition_evaluator(
+            kaufmans::kaufmans_efficiency_ratio,
+        ))
+    });
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        KAUFMANS_EFFICIENCY_RATIO_NAME,
+        &kaufmans::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
 /// Definition of the `MOVING_AVERAGE` user-defined window function.
 pub(crate) static MOVING_AVERAGE: Lazy<WindowFunction> = Lazy::new(|| {
     let return_type: ReturnTypeFunction = Arc::new(moving_average::return_type);
@@ -120,3 +268,71 @@ pub(crate) static PERCENT_ROW_NUMBER: Lazy<WindowFunction> = Lazy::new(|| {
         &partition_evaluator_factory,
     )))
 });
+
+const RELATIVE_STRENGTH_INDEX_NAME: &str = "relative_strength_index";
+
+/// Definition of the `RELATIVE_STRENGTH_INDEX` user-defined window function.
+pub(crate) static RELATIVE_STRENGTH_INDEX: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(indicator::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(|| Ok(indicator::partition_evaluator(ema::relative_strength_index)));
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        RELATIVE_STRENGTH_INDEX_NAME,
+        &indicator::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+/// Definition of the `SAMPLE` user-defined window function.
+pub(crate) static SAMPLE: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(sample::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(sample::partition_evaluator_factory);
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        sample::NAME,
+        &sample::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+const TRIPLE_EXPONENTIAL_DERIVATIVE_NAME: &str = "triple_exponential_derivative";
+
+/// Definition of the `TRIPLE_EXPONENTIAL_DERIVATIVE` user-defined window function.
+pub(crate) static TRIPLE_EXPONENTIAL_DERIVATIVE: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(indicator::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory = Arc::new(|| {
+        Ok(indicator::partition_evaluator(
+            ema::triple_exponential_derivative,
+        ))
+    });
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        TRIPLE_EXPONENTIAL_DERIVATIVE_NAME,
+        &indicator::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+const TRIPLE_EXPONENTIAL_MOVING_AVERAGE_NAME: &str = "triple_exponential_moving_average";
+
+/// Definition of the `TRIPLE_EXPONENTIAL_MOVING_AVERAGE` user-defined window function.
+pub(crate) static TRIPLE_EXPONENTIAL_MOVING_AVERAGE: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(indicator::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory = Arc::new(|| {
+        Ok(indicator::partition_evaluator(
+            ema::triple_exponential_moving_average,
+        ))
+    });
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        TRIPLE_EXPONENTIAL_MOVING_AVERAGE_NAME,
+        &indicator::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
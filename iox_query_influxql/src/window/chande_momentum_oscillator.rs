@@ -0,0 +1,159 @@
+use super::ema::Ema;
+use super::indicator::{Indicator, WarmupType};
+
+/// Create a `CHANDE_MOMENTUM_OSCILLATOR` indicator. A warmup type of
+/// [`WarmupType::None`] uses the original definition of the oscillator,
+/// otherwise the smoothed version, as used by TA-Lib, is used.
+pub(super) fn chande_momentum_oscillator(period: usize, warmup: WarmupType) -> Box<dyn Indicator> {
+    match warmup {
+        WarmupType::None => Box::new(Cmo {
+            points: vec![CmoPoint::default(); period],
+            sum_up: 0.0,
+            sum_down: 0.0,
+            count: 0,
+            idx: 0,
+        }),
+        _ => Box::new(Cmos {
+            ema_up: Ema::new_with_alpha(period + 1, 1.0 / period as f64, warmup),
+            ema_down: Ema::new_with_alpha(period + 1, 1.0 / period as f64, warmup),
+            last: 0.0,
+        }),
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct CmoPoint {
+    price: f64,
+    diff: f64,
+}
+
+/// Chande momentum oscillator, calculated from the sum of the upward and
+/// downward changes over the period.
+#[derive(Debug)]
+struct Cmo {
+    /// Ring buffer of the last `period` points.
+    points: Vec<CmoPoint>,
+    sum_up: f64,
+    sum_down: f64,
+    count: usize,
+    /// The index of the newest point.
+    idx: usize,
+}
+
+impl Indicator for Cmo {
+    fn add(&mut self, v: f64) -> f64 {
+        let idx_oldest = (self.idx + 1) % self.points.len();
+
+        let mut diff = 0.0;
+        if self.count != 0 {
+            diff = v - self.points[self.idx].price;
+            if diff > 0.0 {
+                self.sum_up += diff;
+            } else if diff < 0.0 {
+                self.sum_down -= diff;
+            }
+        }
+
+        let out = if self.sum_up != 0.0 || self.sum_down != 0.0 {
+            100.0 * ((self.sum_up - self.sum_down) / (self.sum_up + self.sum_down))
+        } else {
+            0.0
+        };
+
+        let oldest = self.points[idx_oldest];
+        if oldest.diff > 0.0 {
+            self.sum_up -= oldest.diff;
+        } else if oldest.diff < 0.0 {
+            self.sum_down += oldest.diff;
+        }
+
+        self.points[idx_oldest] = CmoPoint { price: v, diff };
+        self.idx = idx_oldest;
+
+        if self.count < self.points.len() {
+            self.count += 1;
+        }
+
+        out
+    }
+
+    fn warm_count(&self) -> usize {
+        self.points.len()
+    }
+}
+
+/// Smoothed Chande momentum oscillator, using exponential moving averages
+/// of the upward and downward changes.
+#[derive(Debug)]
+struct Cmos {
+    ema_up: Ema,
+    ema_down: Ema,
+    last: f64,
+}
+
+impl Indicator for Cmos {
+    fn add(&mut self, v: f64) -> f64 {
+        let (up, down) = if v > self.last {
+            (v - self.last, 0.0)
+        } else if v < self.last {
+            (0.0, self.last - v)
+        } else {
+            (0.0, 0.0)
+        };
+        self.ema_up.add(up);
+        self.ema_down.add(down);
+        self.last = v;
+
+        let up = self.ema_up.last();
+        let down = self.ema_down.last();
+        100.0 * ((up - down) / (up + down))
+    }
+
+    fn warm_count(&self) -> usize {
+        self.ema_up.warm_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::window::indicator::assert_indicator;
+
+    #[test]
+    fn test_chande_momentum_oscillator() {
+        assert_indicator(
+            chande_momentum_oscillator(3, WarmupType::None),
+            &[
+                0.0,
+                100.0,
+                33.33333333333333,
+                66.66666666666666,
+                75.0,
+                42.857142857142854,
+                25.0,
+                33.33333333333333,
+                40.0,
+                27.27272727272727,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_smoothed_chande_momentum_oscillator() {
+        assert_indicator(
+            chande_momentum_oscillator(3, WarmupType::Exponential),
+            &[
+                100.0,
+                100.0,
+                77.77777777777779,
+                84.61538461538463,
+                87.75510204081631,
+                62.83185840707964,
+                16.45569620253165,
+                54.94880546075085,
+                70.3287440292217,
+                26.979472140762468,
+            ],
+        );
+    }
+}
@@ -0,0 +1,456 @@
+//! Indicators derived from an exponential moving average.
+
+use super::indicator::{Indicator, WarmupType};
+
+/// Create an `EXPONENTIAL_MOVING_AVERAGE` indicator.
+pub(super) fn exponential_moving_average(period: usize, warmup: WarmupType) -> Box<dyn Indicator> {
+    Box::new(Ema::new(period, warmup))
+}
+
+/// Create a `DOUBLE_EXPONENTIAL_MOVING_AVERAGE` indicator.
+pub(super) fn double_exponential_moving_average(
+    period: usize,
+    warmup: WarmupType,
+) -> Box<dyn Indicator> {
+    Box::new(Dema {
+        ema1: Ema::new(period, warmup),
+        ema2: Ema::new(period, warmup),
+    })
+}
+
+/// Create a `TRIPLE_EXPONENTIAL_MOVING_AVERAGE` indicator.
+pub(super) fn triple_exponential_moving_average(
+    period: usize,
+    warmup: WarmupType,
+) -> Box<dyn Indicator> {
+    Box::new(Tema {
+        ema1: Ema::new(period, warmup),
+        ema2: Ema::new(period, warmup),
+        ema3: Ema::new(period, warmup),
+    })
+}
+
+/// Create a `TRIPLE_EXPONENTIAL_DERIVATIVE` indicator.
+pub(super) fn triple_exponential_derivative(
+    period: usize,
+    warmup: WarmupType,
+) -> Box<dyn Indicator> {
+    Box::new(Trix {
+        ema1: Ema::new(period, warmup),
+        ema2: Ema::new(period, warmup),
+        ema3: Ema::new(period, warmup),
+        last: 0.0,
+    })
+}
+
+/// Create a `RELATIVE_STRENGTH_INDEX` indicator.
+pub(super) fn relative_strength_index(period: usize, warmup: WarmupType) -> Box<dyn Indicator> {
+    Box::new(Rsi {
+        ema_up: Ema::new_with_alpha(period + 1, 1.0 / period as f64, warmup),
+        ema_down: Ema::new_with_alpha(period + 1, 1.0 / period as f64, warmup),
+        last: 0.0,
+    })
+}
+
+/// Exponential moving average.
+///
+/// When warmed with [`WarmupType::Simple`] the first `period` samples
+/// produce a simple average. When warmed with [`WarmupType::Exponential`]
+/// the alpha value is scaled during the warmup period, to prevent
+/// excessively weighting the result towards the first value.
+#[derive(Debug, Clone)]
+pub(super) struct Ema {
+    period: usize,
+    alpha: f64,
+    warmup: WarmupType,
+    last: f64,
+    count: usize,
+}
+
+impl Ema {
+    fn new(period: usize, warmup: WarmupType) -> Self {
+        Self::new_with_alpha(period, 2.0 / (period + 1) as f64, warmup)
+    }
+
+    pub(super) fn new_with_alpha(period: usize, alpha: f64, warmup: WarmupType) -> Self {
+        Self {
+            period,
+            alpha,
+            warmup,
+            last: 0.0,
+            count: 0,
+        }
+    }
+
+    pub(super) fn last(&self) -> f64 {
+        self.last
+    }
+
+    fn warmed(&self) -> bool {
+        self.count == self.period
+    }
+
+    /// Returns `true` if a derived indicator should pass the output of
+    /// this average to the next stage.
+    fn feeds_next(&self) -> bool {
+        self.warmed() || self.warmup == WarmupType::Exponential
+    }
+}
+
+impl Indicator for Ema {
+    fn add(&mut self, v: f64) -> f64 {
+        let avg = if self.count == 0 {
+            v
+        } else if !self.warmed() {
+            match self.warmup {
+                WarmupType::Simple => (self.last * self.count as f64 + v) / (self.count + 1) as f64,
+                _ => {
+                    let alpha = 2.0 / (self.count + 2) as f64;
+                    (v - self.last) * alpha + self.last
+                }
+            }
+        } else {
+            (v - self.last) * self.alpha + self.last
+        };
+
+        self.last = avg;
+        if self.count < self.period {
+            self.count += 1;
+        }
+        avg
+    }
+
+    fn warm_count(&self) -> usize {
+        self.period - 1
+    }
+}
+
+/// Double exponential moving average.
+#[derive(Debug)]
+struct Dema {
+    ema1: Ema,
+    ema2: Ema,
+}
+
+impl Indicator for Dema {
+    fn add(&mut self, v: f64) -> f64 {
+        let avg1 = self.ema1.add(v);
+        let avg2 = if self.ema1.feeds_next() {
+            self.ema2.add(avg1)
+        } else {
+            avg1
+        };
+        2.0 * avg1 - avg2
+    }
+
+    fn warm_count(&self) -> usize {
+        if self.ema1.warmup == WarmupType::Exponential {
+            self.ema1.warm_count()
+        } else {
+            self.ema1.warm_count() + self.ema2.warm_count()
+        }
+    }
+}
+
+/// Triple exponential moving average.
+#[derive(Debug)]
+struct Tema {
+    ema1: Ema,
+    ema2: Ema,
+    ema3: Ema,
+}
+
+impl Indicator for Tema {
+    fn add(&mut self, v: f64) -> f64 {
+        let avg1 = self.ema1.add(v);
+        let (avg2, avg3) = if self.ema1.feeds_next() {
+            let avg2 = self.ema2.add(avg1);
+            let avg3 = if self.ema2.feeds_next() {
+                self.ema3.add(avg2)
+            } else {
+                avg2
+            };
+            (avg2, avg3)
+        } else {
+            (avg1, avg1)
+        };
+        3.0 * avg1 - 3.0 * avg2 + avg3
+    }
+
+    fn warm_count(&self) -> usize {
+        if self.ema1.warmup == WarmupType::Exponential {
+            self.ema1.warm_count()
+        } else {
+            self.ema1.warm_count() + self.ema2.warm_count() + self.ema3.warm_count()
+        }
+    }
+}
+
+/// Triple exponential derivative, the rate of change of a triple
+/// exponential moving average, as a percentage.
+#[derive(Debug)]
+struct Trix {
+    ema1: Ema,
+    ema2: Ema,
+    ema3: Ema,
+    last: f64,
+}
+
+impl Indicator for Trix {
+    fn add(&mut self, v: f64) -> f64 {
+        let mut cur = self.ema1.add(v);
+        if self.ema1.feeds_next() {
+            cur = self.ema2.add(cur);
+            if self.ema2.feeds_next() {
+                cur = self.ema3.add(cur);
+            }
+        }
+
+        let rate = ((cur / self.last) - 1.0) * 100.0;
+        self.last = cur;
+        rate
+    }
+
+    fn warm_count(&self) -> usize {
+        if self.ema1.warmup == WarmupType::Exponential {
+            self.ema1.warm_count() + 1
+        } else {
+            self.ema1.warm_count() * 3 + 1
+        }
+    }
+}
+
+/// Relative strength index, using exponential moving averages of the
+/// upward and downward changes.
+#[derive(Debug)]
+struct Rsi {
+    ema_up: Ema,
+    ema_down: Ema,
+    last: f64,
+}
+
+impl Indicator for Rsi {
+    fn add(&mut self, v: f64) -> f64 {
+        let (up, down) = if v > self.last {
+            (v - self.last, 0.0)
+        } else if v < self.last {
+            (0.0, self.last - v)
+        } else {
+            (0.0, 0.0)
+        };
+        self.ema_up.add(up);
+        self.ema_down.add(down);
+        self.last = v;
+        100.0 - (100.0 / (1.0 + self.ema_up.last() / self.ema_down.last()))
+    }
+
+    fn warm_count(&self) -> usize {
+        self.ema_up.warm_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::window::indicator::assert_indicator;
+
+    #[test]
+    fn test_exponential_moving_average() {
+        assert_indicator(
+            exponential_moving_average(3, WarmupType::Simple),
+            &[
+                20.0, 21.0, 21.0, 22.5, 24.25, 24.625, 23.8125, 25.40625, 27.703125, 27.8515625,
+            ],
+        );
+        let exponential = [
+            20.0,
+            21.333333333333332,
+            21.166666666666664,
+            22.583333333333332,
+            24.291666666666664,
+            24.645833333333332,
+            23.822916666666664,
+            25.411458333333332,
+            27.705729166666664,
+            27.852864583333332,
+        ];
+        assert_indicator(
+            exponential_moving_average(3, WarmupType::Exponential),
+            &exponential,
+        );
+        assert_indicator(
+            exponential_moving_average(3, WarmupType::None),
+            &exponential,
+        );
+    }
+
+    #[test]
+    fn test_double_exponential_moving_average() {
+        assert_indicator(
+            double_exponential_moving_average(3, WarmupType::Simple),
+            &[
+                20.0,
+                21.0,
+                21.0,
+                23.25,
+                25.916666666666668,
+                25.645833333333336,
+                23.916666666666668,
+                26.255208333333336,
+                29.276041666666668,
+                28.712239583333336,
+            ],
+        );
+        assert_indicator(
+            double_exponential_moving_average(3, WarmupType::Exponential),
+            &[
+                20.0,
+                21.777777777777775,
+                21.30555555555555,
+                23.361111111111107,
+                25.534722222222218,
+                25.444444444444443,
+                23.810763888888886,
+                26.19965277777778,
+                29.246961805555554,
+                28.69704861111111,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_triple_exponential_moving_average() {
+        assert_indicator(
+            triple_exponential_moving_average(3, WarmupType::Simple),
+            &[
+                20.0,
+                21.0,
+                21.0,
+                24.0,
+                27.583333333333332,
+                26.15625,
+                23.61111111111111,
+                26.474826388888886,
+                29.747829861111107,
+                28.592013888888886,
+            ],
+        );
+        assert_indicator(
+            triple_exponential_moving_average(3, WarmupType::Exponential),
+            &[
+                20.0,
+                21.92592592592592,
+                21.22685185185184,
+                23.6412037037037,
+                25.907407407407412,
+                25.408564814814824,
+                23.387442129629633,
+                26.388165509259267,
+                29.71773726851852,
+                28.583912037037045,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_triple_exponential_derivative() {
+        // The first value has no previous value, and is always within
+        // the hold period.
+        assert_indicator(
+            triple_exponential_derivative(3, WarmupType::Simple),
+            &[
+                f64::INFINITY,
+                5.000000000000004,
+                0.0,
+                3.571428571428581,
+                3.8314176245210607,
+                2.2601476014760147,
+                0.887084648924974,
+                2.701192250372575,
+                4.601850172319977,
+                3.919052491026065,
+            ],
+        );
+        assert_indicator(
+            triple_exponential_derivative(3, WarmupType::Exponential),
+            &[
+                f64::INFINITY,
+                2.9629629629629672,
+                1.0566546762589946,
+                2.391546162402669,
+                4.084736556219437,
+                3.7626552551925707,
+                1.786702207916302,
+                2.5601274813780828,
+                4.455859946642882,
+                3.814932257134629,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_relative_strength_index() {
+        assert_indicator(
+            relative_strength_index(3, WarmupType::Exponential),
+            &[
+                100.0,
+                100.0,
+                88.88888888888889,
+                92.3076923076923,
+                93.87755102040816,
+                81.41592920353983,
+                58.227848101265835,
+                77.47440273037543,
+                85.16437201461085,
+                63.489736070381234,
+            ],
+        );
+        assert_indicator(
+            relative_strength_index(3, WarmupType::Simple),
+            &[
+                100.0,
+                100.0,
+                95.65217391304348,
+                96.15384615384616,
+                96.66666666666667,
+                87.87878787878788,
+                69.04761904761907,
+                81.15942028985508,
+                86.91823899371069,
+                66.57032755298653,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_warm_count() {
+        assert_eq!(
+            exponential_moving_average(5, WarmupType::Simple).warm_count(),
+            4
+        );
+        assert_eq!(
+            double_exponential_moving_average(5, WarmupType::Simple).warm_count(),
+            8
+        );
+        assert_eq!(
+            double_exponential_moving_average(5, WarmupType::Exponential).warm_count(),
+            4
+        );
+        assert_eq!(
+            triple_exponential_moving_average(5, WarmupType::Simple).warm_count(),
+            12
+        );
+        assert_eq!(
+            triple_exponential_derivative(5, WarmupType::Simple).warm_count(),
+            13
+        );
+        assert_eq!(
+            triple_exponential_derivative(5, WarmupType::Exponential).warm_count(),
+            5
+        );
+        assert_eq!(
+            relative_strength_index(5, WarmupType::Exponential).warm_count(),
+            5
+        );
+    }
+}
@@ -0,0 +1,622 @@
+use crate::{error, NUMERICS};
+use arrow::array::{
+    Array, ArrayRef, Float64Array, ListArray, StructArray, TimestampNanosecondArray,
+};
+use arrow::buffer::OffsetBuffer;
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, Fields, IntervalUnit, TimeUnit};
+use datafusion::common::{downcast_value, DataFusionError, Result, ScalarValue};
+use datafusion::logical_expr::{PartitionEvaluator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// Valid signatures for the holt_winters window functions.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        NUMERICS
+            .iter()
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt.clone(),
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    DataType::Int64,
+                    DataType::Int64,
+                    DataType::Interval(IntervalUnit::MonthDayNano),
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// The fields of each point produced by the holt_winters window functions.
+static POINT_FIELDS: Lazy<Fields> = Lazy::new(|| {
+    Fields::from(vec![
+        Field::new(
+            "time",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            false,
+        ),
+        Field::new("value", DataType::Float64, false),
+    ])
+});
+
+/// Calculate the return type given the function signature.
+///
+/// As the holt_winters functions produce points that are not present in
+/// the input, the result is a list of `time` and `value` structs. All of
+/// the points of a partition are returned in the list of the last row,
+/// and the lists of the other rows are empty.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::List(Arc::new(Field::new(
+        "item",
+        DataType::Struct(POINT_FIELDS.clone()),
+        true,
+    )))))
+}
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory(
+    include_fit_data: bool,
+) -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(HoltWintersPartitionEvaluator { include_fit_data }))
+}
+
+/// PartitionEvaluator which forecasts the values of the partition using
+/// the Holt-Winters method.
+#[derive(Debug)]
+struct HoltWintersPartitionEvaluator {
+    /// `true` if the fitted values of the input should be returned in
+    /// addition to the forecast values.
+    include_fit_data: bool,
+}
+
+impl PartitionEvaluator for HoltWintersPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert_eq!(values.len(), 5);
+
+        let array = cast(&values[0], &DataType::Float64)?;
+        let array = downcast_value!(array, Float64Array);
+        let times = downcast_value!(values[1], TimestampNanosecondArray);
+
+        // INVARIANT:
+        // The planner and rewriter guarantee that the N, S and interval
+        // arguments are always constants.
+        //
+        // See: FieldChecker::check_holt_winters
+        let h = match ScalarValue::try_from_array(&values[2], 0)? {
+            ScalarValue::Int64(Some(v)) if v >= 0 => v as usize,
+            v => {
+                return error::internal(format!("invalid value ({v}) for HOLT_WINTERS N argument"))
+            }
+        };
+        let m = match ScalarValue::try_from_array(&values[3], 0)? {
+            ScalarValue::Int64(Some(v)) if v >= 0 => v as usize,
+            v => {
+                return error::internal(format!("invalid value ({v}) for HOLT_WINTERS S argument"))
+            }
+        };
+        let interval = match ScalarValue::try_from_array(&values[4], 0)? {
+            ScalarValue::IntervalMonthDayNano(Some(v)) if v > 0 => v as i64,
+            v => {
+                return error::internal(format!(
+                    "invalid value ({v}) for HOLT_WINTERS interval argument"
+                ))
+            }
+        };
+
+        let points = (0..array.len())
+            .filter(|&idx| array.is_valid(idx) && times.is_valid(idx))
+            .map(|idx| (times.value(idx), array.value(idx)))
+            .collect::<Vec<_>>();
+
+        let (times, values): (Vec<_>, Vec<_>) =
+            HoltWinters::new(h, m, self.include_fit_data, interval)
+                .emit(&points)
+                .into_iter()
+                .unzip();
+
+        let mut offsets = vec![0_i32; num_rows + 1];
+        if let Some(last) = offsets.last_mut() {
+            *last = values.len() as i32;
+        }
+
+        let points = StructArray::new(
+            POINT_FIELDS.clone(),
+            vec![
+                Arc::new(TimestampNanosecondArray::from(times)),
+                Arc::new(Float64Array::from(values)),
+            ],
+            None,
+        );
+        Ok(Arc::new(ListArray::new(
+            Arc::new(Field::new(
+                "item",
+                DataType::Struct(POINT_FIELDS.clone()),
+                true,
+            )),
+            OffsetBuffer::new(offsets.into()),
+            Arc::new(points),
+            None,
+        )))
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
+
+/// Arbitrary weight for initializing some initial guesses.
+const HW_WEIGHT: f64 = 0.5;
+/// Epsilon value for the minimization process.
+const HW_DEFAULT_EPSILON: f64 = 1.0e-4;
+/// The lower bound of the grid of initial guesses for the parameters.
+const HW_GUESS_LOWER: f64 = 0.3;
+/// The upper bound of the grid of initial guesses for the parameters.
+const HW_GUESS_UPPER: f64 = 1.0;
+/// The step between the initial guesses for the parameters.
+const HW_GUESS_STEP: f64 = 0.4;
+
+/// An implementation of the Holt-Winters forecasting method, following the
+/// behaviour of InfluxQL OG.
+///
+/// See: <https://github.com/influxdata/influxdb/blob/75a8bcfae2af7b0043933be9f96b98c0741ceee3/influxql/query/functions.go>
+#[derive(Debug)]
+struct HoltWinters {
+    /// The number of points to forecast.
+    h: usize,
+    /// The season period.
+    m: usize,
+    seasonal: bool,
+    /// The interval between points.
+    interval: i64,
+    /// Whether to include the fitted values, or only the forecast values.
+    include_fit_data: bool,
+    /// The input values, with NaN for missing values.
+    y: Vec<f64>,
+}
+
+impl HoltWinters {
+    fn new(h: usize, m: usize, include_fit_data: bool, interval: i64) -> Self {
+        Self {
+            h,
+            m,
+            seasonal: m >= 2,
+            interval,
+            include_fit_data,
+            y: vec![],
+        }
+    }
+
+    fn round_time(&self, t: i64) -> i64 {
+        let remainder = t % self.interval;
+        if remainder > self.interval / 2 {
+            (t / self.interval + 1) * self.interval
+        } else {
+            (t / self.interval) * self.interval
+        }
+    }
+
+    /// Return the points produced by the Holt-Winters method for the
+    /// time-ordered `points`.
+    fn emit(mut self, points: &[(i64, f64)]) -> Vec<(i64, f64)> {
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            return vec![];
+        };
+        if points.len() < 2 || (self.seasonal && points.len() < self.m) || self.h == 0 {
+            return vec![];
+        }
+
+        // Fill in y with values and NaNs for missing values
+        let start = self.round_time(first.0);
+        let stop = self.round_time(last.0);
+        if stop <= start {
+            return vec![];
+        }
+        self.y.push(first.1);
+        let mut t = start;
+        for &(time, value) in &points[1..] {
+            let rounded = self.round_time(time);
+            if rounded <= t {
+                // Drop values that occur for the same time bucket
+                continue;
+            }
+            t += self.interval;
+            // Add any missing values before the next point
+            while rounded != t {
+                self.y.push(f64::NAN);
+                t += self.interval;
+            }
+            self.y.push(value);
+        }
+
+        let m = self.m;
+        if self.seasonal && self.y.len() < m {
+            return vec![];
+        }
+
+        // Starting guesses
+        //
+        // Since these values are guesses, missing values are skipped.
+        let l0 = if self.seasonal {
+            self.y[..m]
+                .iter()
+                .filter(|v| !v.is_nan())
+                .map(|v| v / m as f64)
+                .sum()
+        } else {
+            HW_WEIGHT * self.y[0]
+        };
+
+        let b0 = if self.seasonal {
+            (0..m)
+                .filter(|i| m + i < self.y.len())
+                .filter(|&i| !self.y[i].is_nan() && !self.y[m + i].is_nan())
+                .map(|i| (self.y[m + i] - self.y[i]) / (m * m) as f64)
+                .sum()
+        } else if !self.y[1].is_nan() {
+            HW_WEIGHT * (self.y[1] - self.y[0])
+        } else {
+            0.0
+        };
+
+        let mut parameters = vec![0.0; 6];
+        parameters[4] = l0;
+        parameters[5] = b0;
+        if self.seasonal {
+            parameters.extend(
+                self.y[..m]
+                    .iter()
+                    .map(|v| if v.is_nan() { 0.0 } else { v / l0 }),
+            );
+        }
+
+        // Determine the best fit for the various parameters
+        let mut min_sse = f64::INFINITY;
+        let mut best_params: Option<Vec<f64>> = None;
+        let guesses = || {
+            std::iter::successors(Some(HW_GUESS_LOWER), |v| Some(v + HW_GUESS_STEP))
+                .take_while(|v| *v < HW_GUESS_UPPER)
+        };
+        for alpha in guesses() {
+            for beta in guesses() {
+                for gamma in guesses() {
+                    for phi in guesses() {
+                        parameters[0] = alpha;
+                        parameters[1] = beta;
+                        parameters[2] = gamma;
+                        parameters[3] = phi;
+                        let (sse, params) = nelder_mead::optimize(
+                            |params| self.sse(params),
+                            &parameters,
+                            HW_DEFAULT_EPSILON,
+                            1.0,
+                        );
+                        if sse < min_sse || best_params.is_none() {
+                            min_sse = sse;
+                            best_params = Some(params);
+                        }
+                    }
+                }
+            }
+        }
+        let Some(mut best_params) = best_params else {
+            return vec![];
+        };
+
+        let forecasted = self.forecast(self.h, &mut best_params);
+        if self.include_fit_data {
+            forecasted
+                .into_iter()
+                .enumerate()
+                .filter(|(_, v)| !v.is_nan())
+                .map(|(i, v)| (first.0 + self.interval * i as i64, v))
+                .collect()
+        } else {
+            forecasted[self.y.len()..]
+                .iter()
+                .enumerate()
+                .filter(|(_, v)| !v.is_nan())
+                .map(|(i, v)| (last.0 + self.interval * (i as i64 + 1), *v))
+                .collect()
+        }
+    }
+
+    /// Forecast the data `h` points into the future. The `params` are
+    /// constrained, and the seasonal parameters updated, in place.
+    fn forecast(&self, h: usize, params: &mut [f64]) -> Vec<f64> {
+        constrain(params);
+
+        let (alpha, beta, gamma, phi) = (params[0], params[1], params[2], params[3]);
+        let mut phi_h = phi;
+        let mut y_t = self.y[0];
+        let mut l_t = params[4];
+        let mut b_t = params[5];
+
+        // The seasonal parameters are used as a ring buffer of past values
+        let m = params.len() - 6;
+        let mut so = m.saturating_sub(1);
+
+        let l = self.y.len();
+        let mut forecasted = Vec::with_capacity(l + h);
+        forecasted.push(y_t);
+        let (mut s_tm, mut s_tmh) = (1.0, 1.0);
+        for t in 1..l + h {
+            if self.seasonal {
+                let hm = t % m;
+                s_tm = params[6 + (t + so - m) % m];
+                s_tmh = params[6 + (t + so + hm - m) % m];
+            }
+
+            let l_tp = l_t;
+            let b_tp = b_t;
+            l_t = alpha * (y_t / s_tm) + (1.0 - alpha) * (l_tp + phi * b_tp);
+            b_t = beta * (l_t - l_tp) + (1.0 - beta) * phi * b_tp;
+            let s_t = gamma * (y_t / (l_tp + phi * b_tp)) + (1.0 - gamma) * s_tm;
+            y_t = (l_t + phi_h * b_t) * s_tmh;
+
+            phi_h += phi.powi(t as i32);
+
+            if self.seasonal {
+                params[6 + (t + so) % m] = s_t;
+                so += 1;
+            }
+
+            forecasted.push(y_t);
+        }
+        forecasted
+    }
+
+    /// Compute the sum squared error for the given parameters.
+    fn sse(&self, params: &mut [f64]) -> f64 {
+        let forecasted = self.forecast(0, params);
+        let mut sse = 0.0;
+        for (f, y) in forecasted.iter().zip(&self.y) {
+            // Skip missing values since they cannot be used to compute an error.
+            if y.is_nan() {
+                continue;
+            }
+            if f.is_nan() {
+                // Penalize forecasted NaNs
+                return f64::INFINITY;
+            }
+            let diff = f - y;
+            sse += diff * diff;
+        }
+        sse
+    }
+}
+
+/// Constrain alpha, beta, gamma and phi to the range [0, 1].
+fn constrain(params: &mut [f64]) {
+    for v in &mut params[..4] {
+        *v = v.clamp(0.0, 1.0);
+    }
+}
+
+/// An implementation of the Nelder-Mead optimization method, following
+/// the behaviour of InfluxQL OG.
+///
+/// See: <https://github.com/influxdata/influxdb/blob/75a8bcfae2af7b0043933be9f96b98c0741ceee3/influxql/query/neldermead/neldermead.go>
+mod nelder_mead {
+    const MAX_ITERATIONS: usize = 1000;
+    /// Reflection coefficient.
+    const ALPHA: f64 = 1.0;
+    /// Contraction coefficient.
+    const BETA: f64 = 0.5;
+    /// Expansion coefficient.
+    const GAMMA: f64 = 2.0;
+
+    /// Find the parameters that minimize `objfunc`, starting from `start`,
+    /// returning the minimum value and the parameters that produced it.
+    pub(super) fn optimize(
+        mut objfunc: impl FnMut(&mut [f64]) -> f64,
+        start: &[f64],
+        epsilon: f64,
+        scale: f64,
+    ) -> (f64, Vec<f64>) {
+        let n = start.len();
+        let nf = n as f64;
+
+        // Create the initial simplex, with one vertex at the start
+        let pn = scale * ((nf + 1.0).sqrt() - 1.0 + nf) / (nf * 2_f64.sqrt());
+        let qn = scale * ((nf + 1.0).sqrt() - 1.0) / (nf * 2_f64.sqrt());
+        let mut v = vec![start.to_vec()];
+        for i in 1..=n {
+            v.push(
+                start
+                    .iter()
+                    .enumerate()
+                    .map(|(j, s)| if i - 1 == j { pn + s } else { qn + s })
+                    .collect(),
+            );
+        }
+
+        // The value of the function at each vertex
+        let mut f = v.iter_mut().map(|v| objfunc(v)).collect::<Vec<_>>();
+
+        let mut vm = vec![0.0; n];
+        for _ in 0..MAX_ITERATIONS {
+            // Find the indexes of the largest and smallest values
+            let mut vg = 0;
+            let mut vs = 0;
+            for (i, &fi) in f.iter().enumerate() {
+                if fi > f[vg] {
+                    vg = i;
+                }
+                if fi < f[vs] {
+                    vs = i;
+                }
+            }
+            // Find the index of the second largest value
+            let mut vh = vs;
+            for (i, &fi) in f.iter().enumerate() {
+                if fi > f[vh] && fi < f[vg] {
+                    vh = i;
+                }
+            }
+
+            // Calculate the centroid
+            for (i, c) in vm.iter_mut().enumerate() {
+                *c = (0..=n).filter(|&m| m != vg).map(|m| v[m][i]).sum::<f64>() / nf;
+            }
+
+            // Reflect vg to a new vertex vr
+            let mut vr = (0..n)
+                .map(|i| vm[i] + ALPHA * (vm[i] - v[vg][i]))
+                .collect::<Vec<_>>();
+            let fr = objfunc(&mut vr);
+
+            if fr < f[vh] && fr >= f[vs] {
+                v[vg].copy_from_slice(&vr);
+                f[vg] = fr;
+            }
+
+            // Investigate a step further in this direction
+            if fr < f[vs] {
+                let mut ve = (0..n)
+                    .map(|i| vm[i] + GAMMA * (vr[i] - vm[i]))
+                    .collect::<Vec<_>>();
+                let fe = objfunc(&mut ve);
+
+                if fe < fr {
+                    v[vg].copy_from_slice(&ve);
+                    f[vg] = fe;
+                } else {
+                    v[vg].copy_from_slice(&vr);
+                    f[vg] = fr;
+                }
+            }
+
+            // Check to see if a contraction is necessary
+            if fr >= f[vh] {
+                let mut vc = if fr < f[vg] && fr >= f[vh] {
+                    // Perform outside contraction
+                    (0..n)
+                        .map(|i| vm[i] + BETA * (vr[i] - vm[i]))
+                        .collect::<Vec<_>>()
+                } else {
+                    // Perform inside contraction
+                    (0..n)
+                        .map(|i| vm[i] - BETA * (vm[i] - v[vg][i]))
+                        .collect::<Vec<_>>()
+                };
+                let fc = objfunc(&mut vc);
+
+                if fc < f[vg] {
+                    v[vg].copy_from_slice(&vc);
+                    f[vg] = fc;
+                } else {
+                    // The contraction was not successful, so halve the
+                    // distance from vs to all the vertices of the simplex.
+                    let best = v[vs].clone();
+                    for (row, vertex) in v.iter_mut().enumerate() {
+                        if row != vs {
+                            for (x, b) in vertex.iter_mut().zip(&best) {
+                                *x = b + (*x - b) / 2.0;
+                            }
+                        }
+                    }
+                    f[vg] = objfunc(&mut v[vg]);
+                    f[vh] = objfunc(&mut v[vh]);
+                }
+            }
+
+            // Test for convergence
+            let favg = f.iter().sum::<f64>() / (nf + 1.0);
+            let s = (f.iter().map(|f| (f - favg).powi(2) / nf).sum::<f64>()).sqrt();
+            if s < epsilon {
+                break;
+            }
+        }
+
+        // Find the index of the smallest value
+        let mut vs = 0;
+        for (i, &fi) in f.iter().enumerate() {
+            if fi < f[vs] {
+                vs = i;
+            }
+        }
+
+        let parameters = v[vs].clone();
+        let min = objfunc(&mut v[vs]);
+        (min, parameters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int64Array, IntervalMonthDayNanoArray};
+
+    const INTERVAL: i64 = 10_000_000_000;
+
+    /// Evaluate holt_winters for `values` at 10s intervals, asserting all
+    /// values produced are finite, and returning the time of each point
+    /// produced, as a number of intervals.
+    fn evaluate(values: &[Option<f64>], h: i64, m: i64, include_fit_data: bool) -> Vec<i64> {
+        let n = values.len();
+        let args: Vec<ArrayRef> = vec![
+            Arc::new(Float64Array::from(values.to_vec())),
+            Arc::new(TimestampNanosecondArray::from_iter_values(
+                (0..n as i64).map(|i| i * INTERVAL),
+            )),
+            Arc::new(Int64Array::from(vec![h; n])),
+            Arc::new(Int64Array::from(vec![m; n])),
+            Arc::new(IntervalMonthDayNanoArray::from(vec![INTERVAL as i128; n])),
+        ];
+        let got = partition_evaluator_factory(include_fit_data)
+            .unwrap()
+            .evaluate_all(&args, n)
+            .unwrap();
+        let got = got.as_any().downcast_ref::<ListArray>().unwrap();
+        assert_eq!(got.len(), n);
+
+        // All points are returned in the list of the last row.
+        for idx in 0..n - 1 {
+            assert!(got.value(idx).is_empty());
+        }
+        let points = got.value(n - 1);
+        let points = points.as_any().downcast_ref::<StructArray>().unwrap();
+        let times = points
+            .column(0)
+            .as_any()
+            .downcast_ref::<TimestampNanosecondArray>()
+            .unwrap();
+        let values = points
+            .column(1)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert!(values.iter().all(|v| v.unwrap().is_finite()));
+        times.values().iter().map(|t| t / INTERVAL).collect()
+    }
+
+    #[test]
+    fn test_forecast() {
+        let values = [1.0, 2.0, 3.0, 4.0].map(Some);
+        assert_eq!(evaluate(&values, 3, 0, false), [4, 5, 6]);
+        assert_eq!(evaluate(&values, 3, 0, true), [0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(evaluate(&values, 2, 2, false), [4, 5]);
+    }
+
+    #[test]
+    fn test_null_values() {
+        // Missing values are skipped when fitting, but are forecast.
+        let values = [Some(1.0), Some(2.0), None, Some(4.0)];
+        assert_eq!(evaluate(&values, 3, 0, false), [4, 5, 6]);
+        assert_eq!(evaluate(&values, 3, 0, true), [0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_insufficient_points() {
+        assert!(evaluate(&[Some(1.0)], 3, 0, false).is_empty());
+        assert!(evaluate(&[Some(1.0), None, None], 3, 0, false).is_empty());
+        // Fewer points than the season
+        assert!(evaluate(&[1.0, 2.0, 3.0].map(Some), 3, 4, false).is_empty());
+    }
+}
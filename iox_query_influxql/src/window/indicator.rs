@@ -0,0 +1,247 @@
+//! Common implementation of the technical analysis window functions,
+//! such as `EXPONENTIAL_MOVING_AVERAGE` and `RELATIVE_STRENGTH_INDEX`.
+//!
+//! The algorithms are ports of the `gota` package used by InfluxQL OG.
+//!
+//! See: <https://github.com/influxdata/influxdb/tree/75a8bcfae2af7b0043933be9f96b98c0741ceee3/influxql/query/internal/gota>
+
+use crate::{error, NUMERICS};
+use arrow::array::{Array, ArrayRef, Float64Array};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use datafusion::common::{downcast_value, DataFusionError, Result, ScalarValue};
+use datafusion::logical_expr::{PartitionEvaluator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// Valid signatures for the technical analysis window functions that
+/// accept a period, hold period and warmup type.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        NUMERICS
+            .iter()
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt.clone(),
+                    DataType::Int64,
+                    DataType::Int64,
+                    DataType::Utf8,
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature. The technical
+/// analysis functions always return a Float64.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Float64))
+}
+
+/// The method used to calculate values before an indicator has received
+/// enough samples to be fully "warmed".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum WarmupType {
+    /// Use an exponential moving average, scaling the weight of the
+    /// samples seen so far.
+    Exponential,
+    /// Use a simple average of the samples seen so far.
+    Simple,
+    /// Calculate the indicator without any warmup.
+    None,
+}
+
+impl WarmupType {
+    fn try_from_str(s: &str) -> Result<Self> {
+        match s {
+            "exponential" => Ok(Self::Exponential),
+            "simple" => Ok(Self::Simple),
+            "none" => Ok(Self::None),
+            _ => error::internal(format!("invalid warmup type '{s}'")),
+        }
+    }
+}
+
+/// A technical analysis indicator, which computes a new value for each
+/// sample that is added.
+pub(super) trait Indicator: std::fmt::Debug + Send {
+    /// Add a sample to the indicator, returning the computed value.
+    fn add(&mut self, v: f64) -> f64;
+
+    /// The number of samples that must be added before the indicator
+    /// is fully "warmed".
+    fn warm_count(&self) -> usize;
+}
+
+/// A function to create a new [`Indicator`] with the specified period and
+/// warmup type.
+pub(super) type IndicatorFactory = fn(usize, WarmupType) -> Box<dyn Indicator>;
+
+/// Create a new [`PartitionEvaluator`] that computes the indicator
+/// created by `factory`.
+pub(super) fn partition_evaluator(factory: IndicatorFactory) -> Box<dyn PartitionEvaluator> {
+    Box::new(IndicatorPartitionEvaluator { factory })
+}
+
+/// PartitionEvaluator which computes an [`Indicator`] for each non-null
+/// row of the partition. As with InfluxQL OG, a value is only produced
+/// once more samples than the hold period have been added.
+#[derive(Debug)]
+struct IndicatorPartitionEvaluator {
+    factory: IndicatorFactory,
+}
+
+impl PartitionEvaluator for IndicatorPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], _num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert!(values.len() == 3 || values.len() == 4);
+
+        // INVARIANT:
+        // The planner and rewriter guarantee that the period, hold period
+        // and warmup type arguments are always constants.
+        //
+        // See: FieldChecker::check_exponential_moving_average
+        let period = match ScalarValue::try_from_array(&values[1], 0)? {
+            ScalarValue::Int64(Some(v)) if v > 0 => v as usize,
+            v => return error::internal(format!("invalid value ({v}) for period argument")),
+        };
+        let warmup = match values.get(3) {
+            Some(array) => match ScalarValue::try_from_array(array, 0)? {
+                ScalarValue::Utf8(Some(v)) => WarmupType::try_from_str(&v)?,
+                v => return error::internal(format!("invalid value ({v}) for warmup argument")),
+            },
+            None => WarmupType::None,
+        };
+
+        let mut indicator = (self.factory)(period, warmup);
+
+        // A hold period of -1 defers to the warmup period of the indicator.
+        let hold_period = match ScalarValue::try_from_array(&values[2], 0)? {
+            ScalarValue::Int64(Some(-1)) => indicator.warm_count(),
+            ScalarValue::Int64(Some(v)) if v >= 0 => v as usize,
+            v => return error::internal(format!("invalid value ({v}) for hold period argument")),
+        };
+
+        let array = cast(&values[0], &DataType::Float64)?;
+        let array = downcast_value!(array, Float64Array);
+
+        let mut count = 0;
+        Ok(Arc::new(
+            array
+                .iter()
+                .map(|v| {
+                    v.and_then(|v| {
+                        let v = indicator.add(v);
+                        count += 1;
+                        (count > hold_period).then_some(v)
+                    })
+                })
+                .collect::<Float64Array>(),
+        ))
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
+
+/// Prices used to test the [`Indicator`] implementations.
+#[cfg(test)]
+pub(super) const TEST_PRICES: [f64; 10] =
+    [20.0, 22.0, 21.0, 24.0, 26.0, 25.0, 23.0, 27.0, 30.0, 28.0];
+
+/// Assert that adding [`TEST_PRICES`] to `indicator` produces the
+/// `expected` values, as calculated by the `gota` implementation used by
+/// InfluxQL OG.
+#[cfg(test)]
+pub(super) fn assert_indicator(mut indicator: Box<dyn Indicator>, expected: &[f64]) {
+    let got = TEST_PRICES
+        .iter()
+        .map(|v| indicator.add(*v))
+        .collect::<Vec<_>>();
+    assert_eq!(got.len(), expected.len());
+    for (idx, (got_v, want)) in got.iter().zip(expected).enumerate() {
+        assert!(
+            got_v == want || (got_v - want).abs() <= 1e-9 * want.abs().max(1.0),
+            "value {idx}: got {got_v}, want {want}, all values: {got:?}"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::window::ema::exponential_moving_average;
+    use arrow::array::{Int64Array, StringArray};
+
+    fn evaluate(values: &[Option<f64>], hold_period: i64, warmup: &str) -> Vec<Option<f64>> {
+        let n = values.len();
+        let args: Vec<ArrayRef> = vec![
+            Arc::new(Float64Array::from(values.to_vec())),
+            Arc::new(Int64Array::from(vec![3; n])),
+            Arc::new(Int64Array::from(vec![hold_period; n])),
+            Arc::new(StringArray::from(vec![warmup; n])),
+        ];
+        let got = partition_evaluator(exponential_moving_average)
+            .evaluate_all(&args, n)
+            .unwrap();
+        got.as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap()
+            .iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_hold_period() {
+        let values = [20.0, 22.0, 21.0, 24.0, 26.0].map(Some);
+
+        // A hold period of -1 is the warmup period of the indicator.
+        assert_eq!(
+            evaluate(&values, -1, "simple"),
+            [None, None, Some(21.0), Some(22.5), Some(24.25)]
+        );
+        assert_eq!(
+            evaluate(&values, 0, "simple"),
+            [Some(20.0), Some(21.0), Some(21.0), Some(22.5), Some(24.25)]
+        );
+        assert_eq!(
+            evaluate(&values, 4, "exponential"),
+            [None, None, None, None, Some(24.291666666666664)]
+        );
+    }
+
+    #[test]
+    fn test_null_values() {
+        // NULL values are skipped, and do not count towards the hold period.
+        let values = [
+            Some(20.0),
+            None,
+            Some(22.0),
+            Some(21.0),
+            None,
+            Some(24.0),
+            Some(26.0),
+        ];
+        assert_eq!(
+            evaluate(&values, -1, "simple"),
+            [None, None, None, Some(21.0), None, Some(22.5), Some(24.25)]
+        );
+        assert_eq!(
+            evaluate(&values, 0, "exponential"),
+            [
+                Some(20.0),
+                None,
+                Some(21.333333333333332),
+                Some(21.166666666666664),
+                None,
+                Some(22.583333333333332),
+                Some(24.291666666666664)
+            ]
+        );
+    }
+}
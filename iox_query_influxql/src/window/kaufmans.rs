@@ -0,0 +1,165 @@
+use super::indicator::{Indicator, WarmupType};
+use crate::NUMERICS;
+use arrow::datatypes::DataType;
+use datafusion::logical_expr::{Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+
+/// Valid signatures for the Kaufman window functions, which accept a
+/// period and hold period.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        NUMERICS
+            .iter()
+            .map(|dt| TypeSignature::Exact(vec![dt.clone(), DataType::Int64, DataType::Int64]))
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Create a `KAUFMANS_EFFICIENCY_RATIO` indicator.
+pub(super) fn kaufmans_efficiency_ratio(period: usize, _: WarmupType) -> Box<dyn Indicator> {
+    Box::new(Ker::new(period))
+}
+
+/// Create a `KAUFMANS_ADAPTIVE_MOVING_AVERAGE` indicator.
+pub(super) fn kaufmans_adaptive_moving_average(period: usize, _: WarmupType) -> Box<dyn Indicator> {
+    Box::new(Kama {
+        ker: Ker::new(period),
+        last: 0.0,
+    })
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct KerPoint {
+    price: f64,
+    diff: f64,
+}
+
+/// Kaufman's efficiency ratio, the ratio of the change in price over the
+/// period to the sum of the individual changes.
+#[derive(Debug)]
+struct Ker {
+    /// Ring buffer of the last `period` points.
+    points: Vec<KerPoint>,
+    noise: f64,
+    count: usize,
+    /// The index of the newest point.
+    idx: usize,
+}
+
+impl Ker {
+    fn new(period: usize) -> Self {
+        Self {
+            points: vec![KerPoint::default(); period],
+            noise: 0.0,
+            count: 0,
+            idx: 0,
+        }
+    }
+
+    fn warmed(&self) -> bool {
+        self.count == self.points.len() + 1
+    }
+}
+
+impl Indicator for Ker {
+    fn add(&mut self, v: f64) -> f64 {
+        let idx_oldest = (self.idx + 1) % self.points.len();
+
+        let signal = (v - self.points[idx_oldest].price).abs();
+        let point = KerPoint {
+            price: v,
+            diff: (v - self.points[self.idx].price).abs(),
+        };
+        self.noise -= self.points[idx_oldest].diff;
+        self.noise += point.diff;
+
+        self.idx = idx_oldest;
+        self.points[idx_oldest] = point;
+        if !self.warmed() {
+            self.count += 1;
+        }
+
+        if signal == 0.0 || self.noise == 0.0 {
+            0.0
+        } else {
+            signal / self.noise
+        }
+    }
+
+    fn warm_count(&self) -> usize {
+        self.points.len()
+    }
+}
+
+/// Kaufman's adaptive moving average, which adjusts the smoothing of the
+/// average using the efficiency ratio.
+#[derive(Debug)]
+struct Kama {
+    ker: Ker,
+    last: f64,
+}
+
+impl Indicator for Kama {
+    fn add(&mut self, v: f64) -> f64 {
+        if !self.ker.warmed() {
+            // initialize with the last value
+            self.last = self.ker.points[self.ker.idx].price;
+        }
+
+        let er = self.ker.add(v);
+        let sc = (er * (2.0 / (2.0 + 1.0) - 2.0 / (30.0 + 1.0)) + 2.0 / (30.0 + 1.0)).powi(2);
+
+        self.last += sc * (v - self.last);
+        self.last
+    }
+
+    fn warm_count(&self) -> usize {
+        self.ker.warm_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::window::indicator::assert_indicator;
+
+    #[test]
+    fn test_kaufmans_efficiency_ratio() {
+        assert_indicator(
+            kaufmans_efficiency_ratio(3, WarmupType::None),
+            &[
+                1.0,
+                1.0,
+                0.9130434782608695,
+                0.6666666666666666,
+                0.6666666666666666,
+                0.6666666666666666,
+                0.2,
+                0.14285714285714285,
+                0.5555555555555556,
+                0.5555555555555556,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_kaufmans_adaptive_moving_average() {
+        // The values within the hold period are initialised from a zero price.
+        assert_indicator(
+            kaufmans_adaptive_moving_average(3, WarmupType::None),
+            &[
+                8.888888888888886,
+                20.88888888888889,
+                21.62262844508615,
+                21.65132770647859,
+                22.59546462353402,
+                23.117511460834095,
+                23.113491968340956,
+                23.20156637858551,
+                24.284123644280275,
+                24.87582596444955,
+            ],
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use crate::error;
+use arrow::array::{Array, ArrayRef, BooleanArray};
+use arrow::datatypes::DataType;
+use datafusion::common::{Result, ScalarValue};
+use datafusion::logical_expr::{PartitionEvaluator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use rand::seq::index;
+use std::sync::Arc;
+
+/// The name of the sample window function.
+pub(super) const NAME: &str = "sample";
+
+/// Valid signatures for the sample window function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        vec![TypeSignature::Exact(vec![DataType::Int64])],
+        Volatility::Volatile,
+    )
+});
+
+/// Calculate the return type given the function signature. Sample
+/// always returns a Boolean.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Boolean))
+}
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(SamplePartitionEvaluator {}))
+}
+
+/// PartitionEvaluator which returns `true` for a random selection of
+/// at most n rows of the partition.
+///
+/// This evaluator samples rows from the entire partition, any data that
+/// should not be included must be filtered out before evaluating the
+/// window function.
+#[derive(Debug)]
+struct SamplePartitionEvaluator {}
+
+impl PartitionEvaluator for SamplePartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert_eq!(values.len(), 1);
+
+        let n = match ScalarValue::try_from_array(&values[0], 0)? {
+            ScalarValue::Int64(Some(n)) if n > 0 => n as usize,
+            v => return error::internal(format!("invalid value ({v}) for SAMPLE n argument")),
+        };
+
+        let mut selected = vec![false; num_rows];
+        for idx in index::sample(&mut rand::thread_rng(), num_rows, n.min(num_rows)).into_iter() {
+            selected[idx] = true;
+        }
+        Ok(Arc::new(BooleanArray::from(selected)))
+    }
+
+    fn supports_bounded_execution(&self) -> bool {
+        false
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
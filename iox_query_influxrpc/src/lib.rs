@@ -23,7 +23,8 @@ use datafusion::{
     common::DFSchemaRef,
     error::DataFusionError,
     logical_expr::{utils::exprlist_to_columns, ExprSchemable, LogicalPlan, LogicalPlanBuilder},
-    prelude::{when, Column, Expr},
+    prelude::{count, lit, when, Column, Expr},
+    scalar::ScalarValue,
 };
 use datafusion_util::{
     config::{DEFAULT_CATALOG, DEFAULT_SCHEMA},
@@ -38,6 +39,7 @@ use iox_query::{
     },
     plan::{
         fieldlist::FieldListPlan,
+        series_cardinality::SeriesCardinalityPlan,
         seriesset::{SeriesSetPlan, SeriesSetPlans},
         stringset::{Error as StringSetError, StringSetPlan, StringSetPlanBuilder},
     },
@@ -653,6 +655,84 @@ impl InfluxRpcPlanner {
         Ok(field_list_plan)
     }
 
+    /// Returns a plan that produces the number of distinct series (measurement and tag set) in
+    /// this namespace which have at least one row that passes the conditions specified by
+    /// `predicate`.
+    ///
+    /// The count is exact: the chunks of all the partitions of a table are counted together, so
+    /// that a series written to several partitions or chunks is only counted once, and series of
+    /// different tables are always distinct.
+    pub async fn series_cardinality(
+        &self,
+        namespace: Arc<dyn QueryNamespace>,
+        rpc_predicate: InfluxRpcPredicate,
+    ) -> Result<SeriesCardinalityPlan> {
+        let ctx = self.ctx.child_ctx("series_cardinality planning");
+        debug!(?rpc_predicate, "planning series_cardinality");
+
+        // Special case predicates that span the entire valid timestamp range
+        let rpc_predicate = rpc_predicate.clear_timestamp_if_max_range();
+
+        // The basic algorithm is:
+        //
+        // 1. Find all the potential tables in the chunks
+        //
+        // 2. For each table, use the chunk statistics to figure out if all its rows belong to a
+        //    single series. Otherwise run a plan that counts the distinct tag sets.
+
+        let table_predicates = rpc_predicate
+            .table_predicates(self.meta.as_ref())
+            .context(CreatingPredicatesSnafu)?;
+
+        let tables: Vec<_> = table_chunk_stream(
+            Arc::clone(&namespace),
+            false,
+            &table_predicates,
+            &ctx,
+            &self.meta,
+        )
+        .try_filter_map(|(table_name, table_schema, predicate, chunks)| async move {
+            let chunks = prune_chunks(&table_schema, chunks, &predicate);
+            if chunks.is_empty() {
+                return Ok(None);
+            }
+
+            let known_count = if predicate.is_empty() {
+                known_series_count(&table_schema, &chunks)
+            } else {
+                None
+            };
+
+            Ok(Some((table_name, predicate, chunks, known_count)))
+        })
+        .try_collect()
+        .await?;
+
+        let mut plan = SeriesCardinalityPlan::new();
+        for (table_name, predicate, chunks, known_count) in tables {
+            if let Some(known_count) = known_count {
+                debug!(%table_name, known_count, "series cardinality found from metadata");
+                plan.known_count += known_count;
+                continue;
+            }
+
+            let schema = self
+                .meta
+                .table_schema(table_name)
+                .context(TableRemovedSnafu {
+                    table_name: table_name.as_ref(),
+                })?;
+
+            if let Some(table_plan) =
+                Self::series_cardinality_plan(Arc::clone(table_name), &schema, &predicate, chunks)?
+            {
+                plan = plan.append_other(table_plan.into());
+            }
+        }
+
+        Ok(plan)
+    }
+
     /// Returns a plan that finds all rows which pass the
     /// conditions specified by `predicate` in the form of logical
     /// time series.
@@ -989,6 +1069,81 @@ impl InfluxRpcPlanner {
         Ok(plan)
     }
 
+    /// Creates a DataFusion LogicalPlan that returns the number of
+    /// distinct tag sets in a specified table that have a non null
+    /// value for any of the fields which pass the predicate.
+    ///
+    /// returns `None` if no field of the table passes the predicate.
+    ///
+    /// The created plan looks like:
+    ///
+    /// ```text
+    ///  Aggregate (count)
+    ///    Aggregate (group by tags) or Limit (1, if there are no tags)
+    ///      Filter (any field not null)
+    ///        Projection (select tags and fields)
+    ///          Filter(predicate) [optional]
+    ///            Scan
+    /// ```
+    fn series_cardinality_plan(
+        table_name: Arc<str>,
+        schema: &Schema,
+        predicate: &Predicate,
+        chunks: Vec<Arc<dyn QueryChunk>>,
+    ) -> Result<Option<LogicalPlan>> {
+        debug!(%table_name, "Creating series_cardinality full plan");
+        let scan_and_filter = ScanPlanBuilder::new(Arc::clone(&table_name), schema)
+            .with_predicate(predicate)
+            .with_chunks(chunks)
+            .build()?;
+
+        let tag_exprs: Vec<_> = scan_and_filter
+            .schema()
+            .tags_iter()
+            .map(|field| field.name().as_expr())
+            .collect();
+        let field_exprs: Vec<_> = filtered_fields_iter(scan_and_filter.schema(), predicate)
+            .map(|field| (field.name.as_expr(), field.expr))
+            .collect();
+
+        // A row only belongs to a series if it has a value for a field
+        let Some(any_field_not_null) = field_exprs
+            .iter()
+            .map(|(name, _expr)| name.clone().is_not_null())
+            .reduce(|a, b| a.or(b))
+        else {
+            return Ok(None);
+        };
+
+        let select_exprs = tag_exprs
+            .iter()
+            .cloned()
+            .chain(field_exprs.into_iter().map(|(_name, expr)| expr))
+            .collect::<Vec<_>>();
+
+        let plan_builder = scan_and_filter
+            .plan_builder
+            .project(select_exprs)
+            .context(BuildingPlanSnafu)?
+            .filter(any_field_not_null)
+            .context(BuildingPlanSnafu)?;
+
+        let plan_builder = if tag_exprs.is_empty() {
+            plan_builder.limit(0, Some(1))
+        } else {
+            plan_builder.aggregate(tag_exprs, Vec::<Expr>::new())
+        }
+        .context(BuildingPlanSnafu)?;
+
+        let plan = plan_builder
+            .aggregate(Vec::<Expr>::new(), vec![count(lit(1))])
+            .context(BuildingPlanSnafu)?
+            .build()
+            .context(BuildingPlanSnafu)?;
+
+        Ok(Some(plan))
+    }
+
     /// Creates a plan for computing series sets for a given table,
     /// returning None if the predicate rules out matching any rows in
     /// the table
@@ -1608,9 +1763,14 @@ impl AggExprs {
         predicate: &Predicate,
     ) -> Result<Self> {
         match agg {
-            Aggregate::Sum | Aggregate::Count | Aggregate::Mean => {
-                Self::agg_for_read_group(agg, schema, predicate)
-            }
+            Aggregate::Sum
+            | Aggregate::Count
+            | Aggregate::Mean
+            | Aggregate::Median
+            | Aggregate::Stddev
+            | Aggregate::Spread
+            | Aggregate::Quantile(_)
+            | Aggregate::Mode => Self::agg_for_read_group(agg, schema, predicate),
             Aggregate::First | Aggregate::Last | Aggregate::Min | Aggregate::Max => {
                 Self::selector_aggregates(agg, schema, predicate)
             }
@@ -1626,9 +1786,14 @@ impl AggExprs {
         predicate: &Predicate,
     ) -> Result<Self> {
         match agg {
-            Aggregate::Sum | Aggregate::Count | Aggregate::Mean => {
-                Self::agg_for_read_window_aggregate(agg, schema, predicate)
-            }
+            Aggregate::Sum
+            | Aggregate::Count
+            | Aggregate::Mean
+            | Aggregate::Median
+            | Aggregate::Stddev
+            | Aggregate::Spread
+            | Aggregate::Quantile(_)
+            | Aggregate::Mode => Self::agg_for_read_window_aggregate(agg, schema, predicate),
             Aggregate::First | Aggregate::Last | Aggregate::Min | Aggregate::Max => {
                 Self::selector_aggregates(agg, schema, predicate)
             }
@@ -1815,6 +1980,59 @@ fn prune_chunks(
         .collect()
 }
 
+/// Returns the number of series in `chunks` if it can be determined from
+/// the chunk statistics alone, i.e. if every tag column has at most one
+/// value (or is entirely null) in all chunks.
+fn known_series_count(table_schema: &Schema, chunks: &[Arc<dyn QueryChunk>]) -> Option<u64> {
+    let mut tag_values: BTreeMap<&str, Option<ScalarValue>> = BTreeMap::new();
+    let mut has_rows = false;
+
+    for chunk in chunks {
+        // deleted rows are not reflected in the statistics
+        if !chunk.delete_predicates().is_empty() {
+            return None;
+        }
+
+        let stats = chunk.stats();
+        let num_rows = stats.num_rows?;
+        if num_rows == 0 {
+            continue;
+        }
+        has_rows = true;
+
+        let schema = chunk.schema();
+        let column_stats = stats.column_statistics.as_ref()?;
+        for field in table_schema.tags_iter() {
+            let value = match schema.find_index_of(field.name()) {
+                Some(idx) => {
+                    let col_stats = column_stats.get(idx)?;
+                    match col_stats.null_count? {
+                        n if n == num_rows => None,
+                        0 => match (&col_stats.min_value, &col_stats.max_value) {
+                            (Some(min), Some(max)) if min == max && !min.is_null() => {
+                                Some(min.clone())
+                            }
+                            _ => return None,
+                        },
+                        _ => return None,
+                    }
+                }
+                None => None,
+            };
+
+            match tag_values.get(field.name().as_str()) {
+                Some(existing) if existing != &value => return None,
+                Some(_) => {}
+                None => {
+                    tag_values.insert(field.name().as_str(), value);
+                }
+            }
+        }
+    }
+
+    Some(u64::from(has_rows))
+}
+
 fn chunk_column_names(
     chunk: &dyn QueryChunk,
     predicate: &Predicate,
@@ -2461,6 +2679,145 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_predicate_rewrite_series_cardinality() {
+        run_test(|test_db, rpc_predicate| {
+            async move {
+                InfluxRpcPlanner::new(test_db.new_query_context(None))
+                    .await
+                    .series_cardinality(test_db, rpc_predicate)
+                    .await
+                    .expect("creating plan");
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_series_cardinality_from_stats() {
+        maybe_start_logging();
+
+        let chunk0 = Arc::new(
+            TestChunk::new("h2o")
+                .with_id(0)
+                .with_tag_column_with_full_stats("state", Some("MA"), Some("MA"), 2, None)
+                .with_i64_field_column("i64_field")
+                .with_time_column(),
+        );
+        let chunk1 = Arc::new(
+            TestChunk::new("h2o")
+                .with_id(1)
+                .with_tag_column_with_full_stats("state", Some("MA"), Some("MA"), 3, None)
+                .with_i64_field_column("i64_field")
+                .with_time_column(),
+        );
+
+        let executor = Arc::new(Executor::new_testing());
+        let test_db = Arc::new(TestDatabase::new(Arc::clone(&executor)));
+        test_db.add_chunk("my_partition_key", chunk0);
+        test_db.add_chunk("my_partition_key", chunk1);
+
+        let plan = InfluxRpcPlanner::new(test_db.new_query_context(None))
+            .await
+            .series_cardinality(Arc::clone(&test_db) as _, InfluxRpcPredicate::default())
+            .await
+            .expect("creating plan");
+
+        assert_eq!(plan.known_count, 1);
+        assert!(plan.plans.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_series_cardinality_full_plan() {
+        maybe_start_logging();
+
+        let chunk0 = Arc::new(
+            TestChunk::new("h2o")
+                .with_id(0)
+                .with_tag_column("tag1")
+                .with_tag_column("tag2")
+                .with_i64_field_column("i64_field")
+                .with_time_column()
+                .with_three_rows_of_data(),
+        );
+        let chunk1 = Arc::new(
+            TestChunk::new("o2")
+                .with_id(1)
+                .with_tag_column("tag1")
+                .with_i64_field_column("i64_field")
+                .with_time_column()
+                .with_three_rows_of_data(),
+        );
+
+        let executor = Arc::new(Executor::new_testing());
+        let test_db = Arc::new(TestDatabase::new(Arc::clone(&executor)));
+        test_db.add_chunk("my_partition_key", chunk0);
+        test_db.add_chunk("my_partition_key", chunk1);
+
+        let ctx = test_db.new_query_context(None);
+        let plan = InfluxRpcPlanner::new(ctx.child_ctx("planner"))
+            .await
+            .series_cardinality(Arc::clone(&test_db) as _, InfluxRpcPredicate::default())
+            .await
+            .expect("creating plan");
+        assert_eq!(plan.known_count, 0);
+        assert_eq!(plan.plans.len(), 2);
+
+        let count = ctx.to_series_cardinality(plan).await.expect("running plan");
+        assert_eq!(count, 6);
+    }
+
+    #[tokio::test]
+    async fn test_series_cardinality_across_partitions() {
+        maybe_start_logging();
+
+        let executor = Arc::new(Executor::new_testing());
+        let test_db = Arc::new(TestDatabase::new(Arc::clone(&executor)));
+
+        // The same series in two partitions, known from the statistics.
+        for (id, partition_key) in [(0, "2021-01-01"), (1, "2021-01-02")] {
+            test_db.add_chunk(
+                partition_key,
+                Arc::new(
+                    TestChunk::new("h2o")
+                        .with_id(id)
+                        .with_tag_column_with_full_stats("state", Some("MA"), Some("MA"), 2, None)
+                        .with_i64_field_column("i64_field")
+                        .with_time_column(),
+                ),
+            );
+        }
+
+        // The same three series in two partitions, counted by a plan.
+        for (id, partition_key) in [(2, "2021-01-01"), (3, "2021-01-02")] {
+            test_db.add_chunk(
+                partition_key,
+                Arc::new(
+                    TestChunk::new("o2")
+                        .with_id(id)
+                        .with_tag_column("tag1")
+                        .with_tag_column("tag2")
+                        .with_i64_field_column("i64_field")
+                        .with_time_column()
+                        .with_three_rows_of_data(),
+                ),
+            );
+        }
+
+        let ctx = test_db.new_query_context(None);
+        let plan = InfluxRpcPlanner::new(ctx.child_ctx("planner"))
+            .await
+            .series_cardinality(Arc::clone(&test_db) as _, InfluxRpcPredicate::default())
+            .await
+            .expect("creating plan");
+        assert_eq!(plan.known_count, 1);
+        assert_eq!(plan.plans.len(), 1);
+
+        let count = ctx.to_series_cardinality(plan).await.expect("running plan");
+        assert_eq!(count, 4);
+    }
+
     #[tokio::test]
     async fn test_issue_7848() {
         maybe_start_logging();
@@ -538,6 +538,7 @@ impl TestPartition {
             compaction_level: CompactionLevel::Initial,
             sort_key: Some(sort_key.clone()),
             max_l0_created_at: Time::from_timestamp_nanos(max_l0_created_at),
+            bloom_filters: false,
         };
         let real_file_size_bytes = create_parquet_file(
             ParquetStorage::new(
@@ -11,6 +11,10 @@
     unused_crate_dependencies
 )]
 mod scheduler_config;
+mod scheduler_server;
+pub use scheduler_server::{
+    create_compaction_scheduler_server_type, CompactionSchedulerServerType,
+};
 
 // Workaround for "unused crate" lint false positives.
 use workspace_hack as _;
@@ -33,7 +37,7 @@ use ioxd_common::{
 };
 use metric::Registry;
 use observability_deps::tracing::{info, warn};
-use parquet_file::storage::ParquetStorage;
+use parquet_file::storage::{BloomFilterTables, ParquetStorage};
 use std::{
     fmt::{Debug, Display},
     fs,
@@ -208,8 +212,13 @@ pub async fn create_compactor_server_type(
         catalog,
         scheduler_config: convert_scheduler_config(
             compactor_config.compactor_scheduler_config.clone(),
+            compactor_config.compaction_partition_concurrency.get(),
+        ),
+        parquet_store_real: parquet_store_real.with_bloom_filter_tables(
+            BloomFilterTables::from_iter(
+                compactor_config.parquet_bloom_filter_tables.iter().cloned(),
+            ),
         ),
-        parquet_store_real,
         parquet_store_scratchpad,
         exec,
         time_provider,
@@ -1,15 +1,16 @@
 use std::time::Duration;
 
 use clap_blocks::compactor_scheduler::{
-    CompactorSchedulerConfig, CompactorSchedulerType, PartitionSourceConfigForLocalScheduler,
-    ShardConfigForLocalScheduler,
+    ClientConfigForRemoteScheduler, CompactorSchedulerConfig, CompactorSchedulerType,
+    PartitionSourceConfigForLocalScheduler, ShardConfigForLocalScheduler,
 };
 use compactor_scheduler::{
-    LocalSchedulerConfig, PartitionsSourceConfig, SchedulerConfig, ShardConfig,
+    LocalSchedulerConfig, PartitionsSourceConfig, RemoteSchedulerConfig, SchedulerConfig,
+    ShardConfig,
 };
 use data_types::PartitionId;
 
-fn convert_partitions_source_config(
+pub(crate) fn convert_partitions_source_config(
     config: PartitionSourceConfigForLocalScheduler,
 ) -> PartitionsSourceConfig {
     let PartitionSourceConfigForLocalScheduler {
@@ -64,7 +65,31 @@ fn convert_shard_config(config: ShardConfigForLocalScheduler) -> Option<ShardCon
     }
 }
 
-pub(crate) fn convert_scheduler_config(config: CompactorSchedulerConfig) -> SchedulerConfig {
+/// Create a new [`RemoteSchedulerConfig`] from a [`ClientConfigForRemoteScheduler`].
+///
+/// The compactor is identified by its host name, if known.
+fn convert_remote_config(
+    config: ClientConfigForRemoteScheduler,
+    hostname: Option<String>,
+    max_jobs: usize,
+) -> RemoteSchedulerConfig {
+    RemoteSchedulerConfig {
+        address: config
+            .scheduler_address
+            .expect("remote scheduler requires a scheduler address"),
+        compactor_id: hostname.unwrap_or_else(|| "unknown".to_owned()),
+        max_jobs,
+        heartbeat_interval: Duration::from_secs(config.heartbeat_interval_secs),
+    }
+}
+
+/// Create a new [`SchedulerConfig`] from a [`CompactorSchedulerConfig`].
+///
+/// A remote scheduler leases at most `max_jobs` jobs at a time.
+pub(crate) fn convert_scheduler_config(
+    config: CompactorSchedulerConfig,
+    max_jobs: usize,
+) -> SchedulerConfig {
     match config.compactor_scheduler_type {
         CompactorSchedulerType::Local => SchedulerConfig::Local(LocalSchedulerConfig {
             commit_wrapper: None,
@@ -76,7 +101,11 @@ pub(crate) fn convert_scheduler_config(config: CompactorSchedulerConfig) -> Sche
                 .partition_source_config
                 .ignore_partition_skip_marker,
         }),
-        CompactorSchedulerType::Remote => unimplemented!("Remote scheduler not implemented"),
+        CompactorSchedulerType::Remote => SchedulerConfig::Remote(convert_remote_config(
+            config.remote_config,
+            config.shard_config.hostname,
+            max_jobs,
+        )),
     }
 }
 
@@ -127,6 +156,30 @@ mod tests {
         assert_eq!(partitions_source_config, PartitionsSourceConfig::CatalogAll,);
     }
 
+    #[test]
+    fn remote_scheduler() {
+        let config = CompactorSchedulerConfig {
+            compactor_scheduler_type: CompactorSchedulerType::Remote,
+            shard_config: ShardConfigForLocalScheduler {
+                hostname: Some("iox-shared-compactor-7".into()),
+                ..Default::default()
+            },
+            remote_config: ClientConfigForRemoteScheduler {
+                scheduler_address: Some("http://scheduler:8082".into()),
+                heartbeat_interval_secs: 5,
+            },
+            ..Default::default()
+        };
+
+        let SchedulerConfig::Remote(remote_config) = convert_scheduler_config(config, 10) else {
+            panic!("expected remote scheduler config");
+        };
+        assert_eq!(remote_config.address, "http://scheduler:8082");
+        assert_eq!(remote_config.compactor_id, "iox-shared-compactor-7");
+        assert_eq!(remote_config.max_jobs, 10);
+        assert_eq!(remote_config.heartbeat_interval, Duration::from_secs(5));
+    }
+
     #[test]
     fn normal_compaction() {
         let config = PartitionSourceConfigForLocalScheduler {
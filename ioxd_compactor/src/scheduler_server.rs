@@ -0,0 +1,129 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use clap_blocks::compactor_scheduler::CompactionSchedulerServerConfig;
+use compactor_scheduler::{
+    create_scheduler, LocalSchedulerConfig, SchedulerConfig, SchedulerService,
+};
+use hyper::{Body, Request, Response};
+use iox_catalog::interface::Catalog;
+use iox_time::TimeProvider;
+use ioxd_common::{
+    add_service,
+    http::error::HttpApiErrorSource,
+    rpc::RpcBuilderInput,
+    serve_builder,
+    server_type::{CommonServerState, RpcError, ServerType},
+    setup_builder,
+};
+use metric::Registry;
+use observability_deps::tracing::info;
+use tokio_util::sync::CancellationToken;
+use trace::TraceCollector;
+
+use crate::{scheduler_config::convert_partitions_source_config, IoxHttpError};
+
+/// Server type of the standalone compaction scheduler, leasing compaction jobs to remote
+/// compactors.
+pub struct CompactionSchedulerServerType {
+    service: Arc<SchedulerService>,
+    metric_registry: Arc<Registry>,
+    trace_collector: Option<Arc<dyn TraceCollector>>,
+    shutdown: CancellationToken,
+}
+
+impl std::fmt::Debug for CompactionSchedulerServerType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CompactionScheduler")
+    }
+}
+
+#[async_trait]
+impl ServerType for CompactionSchedulerServerType {
+    /// Human name for this server type
+    fn name(&self) -> &str {
+        "compaction_scheduler"
+    }
+
+    /// Return the [`metric::Registry`] used by the compaction scheduler.
+    fn metric_registry(&self) -> Arc<Registry> {
+        Arc::clone(&self.metric_registry)
+    }
+
+    /// Returns the trace collector for compaction scheduler traces.
+    fn trace_collector(&self) -> Option<Arc<dyn TraceCollector>> {
+        self.trace_collector.as_ref().map(Arc::clone)
+    }
+
+    /// Just return "not found".
+    async fn route_http_request(
+        &self,
+        _req: Request<Body>,
+    ) -> Result<Response<Body>, Box<dyn HttpApiErrorSource>> {
+        Err(Box::new(IoxHttpError::NotFound))
+    }
+
+    /// Configure the gRPC services.
+    async fn server_grpc(self: Arc<Self>, builder_input: RpcBuilderInput) -> Result<(), RpcError> {
+        let builder = setup_builder!(builder_input, self);
+
+        add_service!(builder, Arc::clone(&self.service).into_server());
+
+        serve_builder!(builder);
+
+        Ok(())
+    }
+
+    async fn join(self: Arc<Self>) {
+        self.shutdown.cancelled().await;
+    }
+
+    fn shutdown(&self, frontend: CancellationToken) {
+        frontend.cancel();
+        self.shutdown.cancel();
+    }
+}
+
+/// Instantiate a compaction scheduler server
+pub fn create_compaction_scheduler_server_type(
+    common_state: &CommonServerState,
+    metric_registry: Arc<metric::Registry>,
+    catalog: Arc<dyn Catalog>,
+    time_provider: Arc<dyn TimeProvider>,
+    config: CompactionSchedulerServerConfig,
+) -> Arc<dyn ServerType> {
+    let lease_duration = Duration::from_secs(config.lease_duration_secs);
+    info!(?lease_duration, "starting compaction scheduler");
+
+    // Partitions are handed out via leases, so sharding is not needed.
+    let scheduler = create_scheduler(
+        SchedulerConfig::Local(LocalSchedulerConfig {
+            commit_wrapper: None,
+            partitions_source_config: convert_partitions_source_config(
+                config.partition_source_config.clone(),
+            ),
+            shard_config: None,
+            ignore_partition_skip_marker: config
+                .partition_source_config
+                .ignore_partition_skip_marker,
+        }),
+        catalog,
+        Arc::clone(&time_provider),
+        Arc::clone(&metric_registry),
+        config.shadow_mode,
+    );
+
+    let service = Arc::new(SchedulerService::new(
+        scheduler,
+        time_provider,
+        lease_duration,
+        &metric_registry,
+    ));
+
+    Arc::new(CompactionSchedulerServerType {
+        service,
+        metric_registry,
+        trace_collector: common_state.trace_collector(),
+        shutdown: CancellationToken::new(),
+    })
+}
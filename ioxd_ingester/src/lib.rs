@@ -20,12 +20,16 @@ use clap_blocks::ingester::IngesterConfig;
 use futures::FutureExt;
 use generated_types::influxdata::iox::{
     catalog::v1::catalog_service_server::CatalogServiceServer,
+    delete::v1::delete_service_server::DeleteServiceServer,
     ingester::v1::{
         persist_service_server::PersistServiceServer, write_service_server::WriteServiceServer,
     },
 };
 use hyper::{Body, Request, Response};
-use ingester::{GossipConfig, IngesterGuard, IngesterRpcInterface};
+use ingester::{
+    BufferMemoryLimit, GossipConfig, IngesterGuard, IngesterRpcInterface, WalArchiveConfig,
+    WalReplayMode,
+};
 use iox_catalog::interface::Catalog;
 use iox_query::exec::Executor;
 use ioxd_common::{
@@ -37,7 +41,7 @@ use ioxd_common::{
     setup_builder,
 };
 use metric::Registry;
-use parquet_file::storage::ParquetStorage;
+use parquet_file::storage::{BloomFilterTables, ParquetStorage};
 use std::{
     fmt::{Debug, Display},
     num::NonZeroUsize,
@@ -136,6 +140,10 @@ impl<I: IngesterRpcInterface + Sync + Send + Debug + 'static> ServerType for Ing
                 .max_decoding_message_size(self.max_incoming_msg_bytes)
                 .max_encoding_message_size(MAX_OUTGOING_MSG_BYTES)
         );
+        add_service!(
+            builder,
+            DeleteServiceServer::new(self.server.rpc().delete_service())
+        );
         add_service!(
             builder,
             PersistServiceServer::new(self.server.rpc().persist_service())
@@ -225,11 +233,28 @@ pub async fn create_ingester_server_type(
         PERSIST_BACKGROUND_FETCH_TIME,
         ingester_config.wal_directory.clone(),
         Duration::from_secs(ingester_config.wal_rotation_period_seconds),
+        match ingester_config.wal_replay_mode {
+            clap_blocks::ingester::WalReplayMode::Strict => WalReplayMode::Strict,
+            clap_blocks::ingester::WalReplayMode::Recover => WalReplayMode::Recover,
+        },
+        ingester_config.wal_archive_retention.map(|retention| {
+            WalArchiveConfig::new(ingester_config.wal_archive_prefix.clone(), retention)
+        }),
         exec,
         ingester_config.persist_max_parallelism,
         ingester_config.persist_queue_depth,
         ingester_config.persist_hot_partition_cost,
-        object_store,
+        ingester_config.buffer_memory_limit_bytes.map(|soft_limit| {
+            let soft_limit = soft_limit.bytes();
+            let hard_limit = (soft_limit as u128
+                * ingester_config.buffer_memory_hard_limit_percent as u128
+                / 100) as usize;
+            BufferMemoryLimit::new(soft_limit, hard_limit)
+                .with_check_interval(ingester_config.buffer_memory_check_interval)
+        }),
+        object_store.with_bloom_filter_tables(BloomFilterTables::from_iter(
+            ingester_config.parquet_bloom_filter_tables.iter().cloned(),
+        )),
         gossip,
         ingester_config
             .max_partitions_per_namespace
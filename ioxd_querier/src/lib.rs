@@ -12,7 +12,7 @@
 )]
 
 use generated_types::influxdata::iox::{
-    catalog::v1::catalog_service_server::CatalogServiceServer,
+    catalog::v1::catalog_service_server::CatalogServiceServer, gossip::Topic,
     object_store::v1::object_store_service_server::ObjectStoreServiceServer,
     schema::v1::schema_service_server::SchemaServiceServer,
 };
@@ -25,7 +25,8 @@ use workspace_hack as _;
 use async_trait::async_trait;
 use authz::{Authorizer, IoxAuthorizer};
 use clap_blocks::querier::QuerierConfig;
-use datafusion_util::config::register_iox_object_store;
+use datafusion_util::config::{register_iox_object_store, PARQUET_PAGE_PRUNING_OPTION};
+use gossip::{GossipHandle, TopicInterests};
 use hyper::{Body, Request, Response};
 use iox_catalog::interface::Catalog;
 use iox_query::exec::{Executor, ExecutorType};
@@ -40,7 +41,11 @@ use ioxd_common::{
 };
 use metric::Registry;
 use object_store::{DynObjectStore, ObjectStore};
-use querier::{create_ingester_connections, QuerierCatalogCache, QuerierDatabase, QuerierServer};
+use querier::{
+    create_ingester_connections, CatalogCacheGossip, CatalogCacheGossipRx, QuerierCatalogCache,
+    QuerierDatabase, QuerierDiskCacheConfig, QuerierDiskCacheDirError, QuerierServer,
+    RouterConnection,
+};
 use std::{
     fmt::{Debug, Display},
     sync::Arc,
@@ -60,6 +65,9 @@ pub struct QuerierServerType {
     object_store: Arc<dyn ObjectStore>,
     trace_collector: Option<Arc<dyn TraceCollector>>,
     authz: Option<Arc<dyn Authorizer>>,
+
+    /// Keeps the gossip subsystem running, if enabled.
+    _gossip: Option<GossipHandle<Topic>>,
 }
 
 impl std::fmt::Debug for QuerierServerType {
@@ -111,6 +119,13 @@ impl ServerType for QuerierServerType {
             builder,
             rpc::namespace::namespace_service(Arc::clone(&self.database))
         );
+        add_service!(
+            builder,
+            rpc::query_service::query_service(
+                Arc::clone(&self.database),
+                self.authz.as_ref().map(Arc::clone)
+            )
+        );
         add_service!(
             builder,
             SchemaServiceServer::new(SchemaService::new(Arc::clone(&self.catalog)))
@@ -193,12 +208,26 @@ pub enum Error {
         source: Box<dyn std::error::Error>,
         addr: String,
     },
+
+    /// An error binding the UDP socket for gossip communication.
+    #[error("failed to bind udp gossip socket: {0}")]
+    GossipBind(std::io::Error),
+
+    #[error("disk cache error: {0}")]
+    DiskCache(#[from] QuerierDiskCacheDirError),
 }
 
 /// Instantiate a querier server
 pub async fn create_querier_server_type(
     args: QuerierServerTypeArgs<'_>,
 ) -> Result<Arc<dyn ServerType>, Error> {
+    let disk_cache = args
+        .querier_config
+        .disk_cache_dir
+        .clone()
+        .map(|dir| QuerierDiskCacheConfig::open(dir, args.querier_config.disk_cache_bytes))
+        .transpose()?;
+
     let catalog_cache = Arc::new(QuerierCatalogCache::new(
         Arc::clone(&args.catalog),
         args.time_provider,
@@ -206,6 +235,7 @@ pub async fn create_querier_server_type(
         Arc::clone(&args.object_store),
         args.querier_config.ram_pool_metadata_bytes.bytes(),
         args.querier_config.ram_pool_data_bytes.bytes(),
+        disk_cache,
         &Handle::current(),
     ));
 
@@ -223,6 +253,33 @@ pub async fn create_querier_server_type(
     );
     assert!(existing.is_none());
 
+    // Optionally subscribe to parquet file and compaction notifications to
+    // keep the catalog cache fresh.
+    let gossip = match args.querier_config.gossip_config.gossip_bind_address {
+        Some(bind_addr) => {
+            let handler = Arc::new(CatalogCacheGossip::new(Arc::clone(&catalog_cache)));
+            let dispatcher = CatalogCacheGossipRx::new(handler, 100);
+
+            let handle = gossip::Builder::<_, Topic>::new(
+                args.querier_config.gossip_config.seed_list.clone(),
+                dispatcher,
+                Arc::clone(&args.metric_registry),
+            )
+            // The querier only listens, it never sends any messages.
+            .with_topic_filter(
+                TopicInterests::default()
+                    .with_topic(Topic::NewParquetFiles)
+                    .with_topic(Topic::CompactionEvents),
+            )
+            .bind(*bind_addr)
+            .await
+            .map_err(Error::GossipBind)?;
+
+            Some(handle)
+        }
+        None => None,
+    };
+
     let authz = match &args.querier_config.authz_address {
         Some(addr) => {
             let authz = IoxAuthorizer::connect_lazy(addr.clone())
@@ -255,14 +312,28 @@ pub async fn create_querier_server_type(
         ))
     };
 
+    let router_connection = args
+        .querier_config
+        .router_address
+        .as_ref()
+        .map(|addr| Arc::new(RouterConnection::new(addr.as_str())));
+
+    // An option set explicitly with `--datafusion-config` takes precedence
+    // over `--parquet-page-pruning`.
+    let mut datafusion_config = args.querier_config.datafusion_config;
+    datafusion_config
+        .entry(PARQUET_PAGE_PRUNING_OPTION.to_string())
+        .or_insert_with(|| args.querier_config.parquet_page_pruning.to_string());
+
     let database = Arc::new(
         QuerierDatabase::new(
             catalog_cache,
             Arc::clone(&args.metric_registry),
             args.exec,
             ingester_connections,
+            router_connection,
             args.querier_config.max_concurrent_queries,
-            Arc::new(args.querier_config.datafusion_config),
+            Arc::new(datafusion_config),
         )
         .await?,
     );
@@ -276,5 +347,6 @@ pub async fn create_querier_server_type(
         object_store: args.object_store,
         trace_collector: args.common_state.trace_collector(),
         authz,
+        _gossip: gossip,
     }))
 }
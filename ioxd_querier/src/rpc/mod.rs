@@ -1,2 +1,3 @@
 pub(crate) mod namespace;
 pub(crate) mod query;
+pub(crate) mod query_service;
@@ -39,6 +39,15 @@ fn namespace_to_proto(namespace: Namespace) -> proto::Namespace {
         max_tables: namespace.max_tables.get(),
         max_columns_per_table: namespace.max_columns_per_table.get(),
         partition_template: namespace.partition_template.as_proto().cloned(),
+        max_concurrent_queries: namespace
+            .query_limits
+            .max_concurrent_queries
+            .map(|v| v.get()),
+        max_query_memory_bytes: namespace
+            .query_limits
+            .max_query_memory_bytes
+            .map(|v| v.get()),
+        query_timeout_ms: namespace.query_limits.query_timeout_ms.map(|v| v.get()),
     }
 }
 
@@ -128,6 +137,7 @@ mod tests {
                 catalog.metric_registry(),
                 catalog.exec(),
                 Some(create_ingester_connection_for_testing()),
+                None,
                 QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX,
                 Arc::new(HashMap::default()),
             )
@@ -161,6 +171,7 @@ mod tests {
                 catalog.metric_registry(),
                 catalog.exec(),
                 Some(create_ingester_connection_for_testing()),
+                None,
                 QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX,
                 Arc::new(HashMap::default()),
             )
@@ -184,6 +195,9 @@ mod tests {
                         max_tables: MaxTables::default().get(),
                         max_columns_per_table: MaxColumnsPerTable::default().get(),
                         partition_template: None,
+                        max_concurrent_queries: None,
+                        max_query_memory_bytes: None,
+                        query_timeout_ms: None,
                     },
                     proto::Namespace {
                         id: 2,
@@ -192,6 +206,9 @@ mod tests {
                         max_tables: MaxTables::default().get(),
                         max_columns_per_table: MaxColumnsPerTable::default().get(),
                         partition_template: None,
+                        max_concurrent_queries: None,
+                        max_query_memory_bytes: None,
+                        query_timeout_ms: None,
                     },
                 ]
             }
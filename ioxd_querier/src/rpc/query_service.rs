@@ -0,0 +1,229 @@
+//! QueryService gRPC implementation
+
+use authz::{extract_token, Authorizer};
+use generated_types::influxdata::iox::querier::v1 as proto;
+use querier::{CancelOutcome, QuerierDatabase};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Acquire a [`QueryService`](proto::query_service_server::QueryService) gRPC service implementation.
+///
+/// When `authz` is set, cancelling a query requires the permission to read
+/// the namespace it runs against.
+pub fn query_service(
+    server: Arc<QuerierDatabase>,
+    authz: Option<Arc<dyn Authorizer>>,
+) -> proto::query_service_server::QueryServiceServer<impl proto::query_service_server::QueryService>
+{
+    proto::query_service_server::QueryServiceServer::new(QueryServiceImpl::new(server, authz))
+}
+
+#[derive(Debug)]
+struct QueryServiceImpl {
+    server: Arc<QuerierDatabase>,
+    authz: Option<Arc<dyn Authorizer>>,
+}
+
+impl QueryServiceImpl {
+    pub fn new(server: Arc<QuerierDatabase>, authz: Option<Arc<dyn Authorizer>>) -> Self {
+        Self { server, authz }
+    }
+}
+
+#[tonic::async_trait]
+impl proto::query_service_server::QueryService for QueryServiceImpl {
+    async fn cancel_query(
+        &self,
+        request: tonic::Request<proto::CancelQueryRequest>,
+    ) -> Result<tonic::Response<proto::CancelQueryResponse>, tonic::Status> {
+        let authz_token = extract_token(request.metadata().get("authorization"));
+        let proto::CancelQueryRequest { query_id } = request.into_inner();
+        let id = Uuid::parse_str(&query_id).map_err(|e| {
+            tonic::Status::invalid_argument(format!("invalid query ID '{query_id}': {e}"))
+        })?;
+
+        let query = self
+            .server
+            .query(id)
+            .ok_or_else(|| tonic::Status::not_found(format!("query {id} not found")))?;
+
+        let perms = [authz::Permission::ResourceAction(
+            authz::Resource::Database(query.namespace_name.to_string()),
+            authz::Action::Read,
+        )];
+        self.authz
+            .permissions(authz_token, &perms)
+            .await
+            .map_err(|e| match e {
+                authz::Error::Forbidden | authz::Error::InvalidToken => {
+                    tonic::Status::permission_denied("Permission denied")
+                }
+                authz::Error::NoToken => tonic::Status::unauthenticated("Unauthenticated"),
+                e => tonic::Status::internal(e.to_string()),
+            })?;
+
+        match query.cancel() {
+            CancelOutcome::Cancelled => Ok(tonic::Response::new(proto::CancelQueryResponse {})),
+            CancelOutcome::AlreadyCompleted => Err(tonic::Status::failed_precondition(format!(
+                "query {id} has already completed"
+            ))),
+            CancelOutcome::NotFound => {
+                Err(tonic::Status::not_found(format!("query {id} not found")))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use async_trait::async_trait;
+    use authz::Permission;
+    use generated_types::influxdata::iox::querier::v1::query_service_server::QueryService;
+    use iox_query::{exec::IOxSessionContext, QueryCompletedToken, QueryNamespace};
+    use iox_tests::TestCatalog;
+    use querier::{create_ingester_connection_for_testing, QuerierCatalogCache};
+    use tokio::runtime::Handle;
+
+    #[tokio::test]
+    async fn test_cancel_query() {
+        let catalog = TestCatalog::new();
+        let db = querier_database(&catalog).await;
+        let (ctx, token) = running_query(&catalog, &db).await;
+        let query_id = db.query_log().back().unwrap().id.to_string();
+
+        let service = QueryServiceImpl::new(Arc::clone(&db), None);
+
+        let status = cancel_query(&service, "not a uuid", None)
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        let status = cancel_query(&service, &Uuid::new_v4().to_string(), None)
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+
+        cancel_query(&service, &query_id, None).await.unwrap();
+        assert!(ctx.cancellation_token().unwrap().is_cancelled());
+
+        drop(token);
+        let status = cancel_query(&service, &query_id, None).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_query_authz() {
+        let catalog = TestCatalog::new();
+        let db = querier_database(&catalog).await;
+        let (ctx, _token) = running_query(&catalog, &db).await;
+        let query_id = db.query_log().back().unwrap().id.to_string();
+
+        let service = QueryServiceImpl::new(Arc::clone(&db), Some(Arc::new(MockAuthorizer {})));
+
+        let status = cancel_query(&service, &query_id, None).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+
+        let status = cancel_query(&service, &query_id, Some("Bearer BAD"))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+
+        let status = cancel_query(&service, &query_id, Some("Bearer UGLY"))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Internal);
+        assert!(!ctx.cancellation_token().unwrap().is_cancelled());
+
+        cancel_query(&service, &query_id, Some("Bearer GOOD"))
+            .await
+            .unwrap();
+        assert!(ctx.cancellation_token().unwrap().is_cancelled());
+    }
+
+    async fn querier_database(catalog: &TestCatalog) -> Arc<QuerierDatabase> {
+        let catalog_cache = Arc::new(QuerierCatalogCache::new_testing(
+            catalog.catalog(),
+            catalog.time_provider(),
+            catalog.metric_registry(),
+            catalog.object_store(),
+            &Handle::current(),
+        ));
+        Arc::new(
+            QuerierDatabase::new(
+                catalog_cache,
+                catalog.metric_registry(),
+                catalog.exec(),
+                Some(create_ingester_connection_for_testing()),
+                None,
+                QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX,
+                Arc::new(HashMap::default()),
+            )
+            .await
+            .unwrap(),
+        )
+    }
+
+    /// Record a running query against the namespace "namespace", returning
+    /// its context and the token that completes it when dropped.
+    async fn running_query(
+        catalog: &TestCatalog,
+        db: &QuerierDatabase,
+    ) -> (IOxSessionContext, QueryCompletedToken) {
+        catalog.create_namespace_1hr_retention("namespace").await;
+        let ns = db.namespace("namespace", None, true).await.unwrap();
+        let ctx = ns.new_query_context(None);
+        let token = ns
+            .record_query(&ctx, None, "sql", Box::new("SELECT 1"))
+            .unwrap();
+        (ctx, token)
+    }
+
+    async fn cancel_query(
+        service: &QueryServiceImpl,
+        query_id: &str,
+        authorization: Option<&'static str>,
+    ) -> Result<proto::CancelQueryResponse, tonic::Status> {
+        let mut request = tonic::Request::new(proto::CancelQueryRequest {
+            query_id: query_id.to_string(),
+        });
+        if let Some(authorization) = authorization {
+            request
+                .metadata_mut()
+                .insert("authorization", authorization.parse().unwrap());
+        }
+        service
+            .cancel_query(request)
+            .await
+            .map(|response| response.into_inner())
+    }
+
+    #[derive(Debug)]
+    struct MockAuthorizer {}
+
+    #[async_trait]
+    impl Authorizer for MockAuthorizer {
+        async fn permissions(
+            &self,
+            token: Option<Vec<u8>>,
+            perms: &[Permission],
+        ) -> Result<Vec<Permission>, authz::Error> {
+            assert_eq!(
+                perms,
+                [Permission::ResourceAction(
+                    authz::Resource::Database("namespace".to_string()),
+                    authz::Action::Read,
+                )]
+            );
+            match token.as_deref() {
+                Some(b"GOOD") => Ok(perms.to_vec()),
+                Some(b"BAD") => Err(authz::Error::Forbidden),
+                Some(b"UGLY") => Err(authz::Error::verification("test", "test error")),
+                Some(_) => panic!("unexpected token"),
+                None => Err(authz::Error::NoToken),
+            }
+        }
+    }
+}
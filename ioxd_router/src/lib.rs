@@ -35,7 +35,7 @@ use ioxd_common::{
     http::error::{HttpApiError, HttpApiErrorSource},
     reexport::{
         generated_types::influxdata::iox::{
-            catalog::v1::catalog_service_server, gossip::Topic,
+            catalog::v1::catalog_service_server, delete::v1::delete_service_server, gossip::Topic,
             namespace::v1::namespace_service_server, object_store::v1::object_store_service_server,
             schema::v1::schema_service_server, table::v1::table_service_server,
         },
@@ -51,13 +51,15 @@ use mutable_batch::MutableBatch;
 use object_store::DynObjectStore;
 use router::{
     dml_handlers::{
-        lazy_connector::LazyConnector, DmlHandler, DmlHandlerChainExt, FanOutAdaptor,
-        InstrumentationDecorator, Partitioner, RetentionValidator, RpcWrite,
+        deletion_observer::IngesterDeletionObserver, lazy_connector::LazyConnector, DmlHandler,
+        DmlHandlerChainExt, FanOutAdaptor, InstrumentationDecorator, Partitioner,
+        RetentionValidator, RpcWrite, TombstoneWriter,
     },
     gossip::{
         anti_entropy::mst::{
             actor::AntiEntropyActor, handle::AntiEntropyHandle, merkle::MerkleTree,
         },
+        deletion_observer::SchemaDeletionObserver,
         namespace_cache::NamespaceSchemaGossip,
         schema_change_observer::SchemaChangeObserver,
     },
@@ -70,6 +72,8 @@ use router::{
     },
     schema_validator::SchemaValidator,
     server::{
+        delete::DeleteDelegate,
+        flight::FlightWriteDelegate,
         grpc::RpcWriteGrpcDelegate,
         http::{
             write::{
@@ -192,6 +196,11 @@ where
             builder,
             table_service_server::TableServiceServer::new(self.server.grpc().table_service())
         );
+        add_service!(builder, self.server.grpc().flight_service());
+        add_service!(
+            builder,
+            delete_service_server::DeleteServiceServer::new(self.server.grpc().delete_service())
+        );
         serve_builder!(builder);
 
         Ok(())
@@ -253,13 +262,20 @@ pub async fn create_router_server_type(
     });
 
     // Initialise the DML handler that sends writes to the ingester using the RPC write path.
-    let rpc_writer = RpcWrite::new(
+    //
+    // The writer is shared with the deletion observer, which instructs the
+    // ingesters to drop the buffered values of deleted columns.
+    let rpc_write = Arc::new(RpcWrite::new(
         ingester_connections,
         router_config.rpc_write_replicas,
         &metrics,
         router_config.rpc_write_health_num_probes,
-    );
-    let rpc_writer = InstrumentationDecorator::new("rpc_writer", &metrics, rpc_writer);
+    ));
+    let rpc_writer = InstrumentationDecorator::new("rpc_writer", &metrics, Arc::clone(&rpc_write));
+
+    // Deletes applied to the ingesters are recorded in the catalog as
+    // tombstones, filtering the deleted rows out of persisted data.
+    let rpc_writer = TombstoneWriter::new(rpc_writer, Arc::clone(&catalog));
 
     // # Namespace cache
     //
@@ -329,7 +345,10 @@ pub async fn create_router_server_type(
     // drive catalog queries themselves (defeating the point of the gossiping!).
     // If a local node has to perform a catalog lookup, it gossips the result to
     // other peers, helping converge them.
-    let ns_cache = match gossip_config.gossip_bind_address {
+    //
+    // The gossip transmit handle is shared with the SchemaDeletionObserver
+    // below, which gossips table & column deletions.
+    let (ns_cache, schema_tx) = match gossip_config.gossip_bind_address {
         Some(bind_addr) => {
             let ns_cache = Arc::new(ns_cache);
 
@@ -356,17 +375,27 @@ pub async fn create_router_server_type(
             // local changes made to the cache content.
             //
             // This sits above / wraps the NamespaceSchemaGossip layer.
-            let ns_cache = SchemaChangeObserver::new(ns_cache, SchemaTx::new(handle));
+            let schema_tx = Arc::new(SchemaTx::new(handle));
+            let ns_cache = SchemaChangeObserver::new(ns_cache, Arc::clone(&schema_tx));
 
-            MaybeLayer::With(ns_cache)
+            (MaybeLayer::With(ns_cache), Some(schema_tx))
         }
-        None => MaybeLayer::Without(ns_cache),
+        None => (MaybeLayer::Without(ns_cache), None),
     };
 
     // Wrap the NamespaceCache in a read-through layer that queries the catalog
     // for cache misses, and populates the local cache with the result.
     let ns_cache = Arc::new(ReadThroughCache::new(ns_cache, Arc::clone(&catalog)));
 
+    // Tables and columns deleted through the table gRPC service, and
+    // namespaces renamed or restored through the namespace gRPC service, are
+    // evicted from the local cache, and the change gossiped to peers (if
+    // enabled).
+    let deletion_observer = Arc::new(SchemaDeletionObserver::new(
+        Arc::clone(&ns_cache),
+        schema_tx,
+    ));
+
     // # Schema validator
     //
     // Initialise and instrument the schema validator
@@ -435,10 +464,18 @@ pub async fn create_router_server_type(
         ));
 
     // Record the overall request handling latency
-    let handler_stack = InstrumentationDecorator::new("request", &metrics, handler_stack);
+    //
+    // The handler stack and namespace resolver are shared between the HTTP
+    // API and the Flight write service.
+    let handler_stack = Arc::new(InstrumentationDecorator::new(
+        "request",
+        &metrics,
+        handler_stack,
+    ));
+    let namespace_resolver = Arc::new(namespace_resolver);
 
-    // Initialize the HTTP API delegate
-    let write_request_unifier: Result<Box<dyn WriteRequestUnifier>> = match (
+    // Initialise the authorizer used by single tenant deployments
+    let authz = match (
         router_config.single_tenant_deployment,
         &router_config.authz_address,
     ) {
@@ -453,7 +490,7 @@ pub async fn create_router_server_type(
                 })?;
             authz.probe().await.expect("Authz connection test failed.");
 
-            Ok(Box::new(SingleTenantRequestUnifier::new(authz)))
+            Some(authz)
         }
         (true, None) => {
             // Single tenancy was requested, but no auth was provided - the
@@ -462,26 +499,65 @@ pub async fn create_router_server_type(
             // never reach here.
             unreachable!("INFLUXDB_IOX_SINGLE_TENANCY is set, but could not create an authz service. Check the INFLUXDB_IOX_AUTHZ_ADDR")
         }
-        (false, None) => Ok(Box::<MultiTenantRequestUnifier>::default()),
+        (false, None) => None,
         (false, Some(_)) => {
             // As above, this combination should be prevented by the
             // router's clap flag parse configuration.
             unreachable!("INFLUXDB_IOX_AUTHZ_ADDR is set, but authz only exists for single_tenancy. Check the INFLUXDB_IOX_SINGLE_TENANCY")
         }
     };
+
+    // Initialize the HTTP API delegate
+    let write_request_unifier: Box<dyn WriteRequestUnifier> = match &authz {
+        Some(authz) => Box::new(SingleTenantRequestUnifier::new(Arc::clone(authz))),
+        None => Box::<MultiTenantRequestUnifier>::default(),
+    };
     let http = HttpDelegate::new(
         common_state.run_config().max_http_request_size,
         router_config.http_request_limit,
+        Arc::clone(&namespace_resolver),
+        Arc::clone(&handler_stack),
+        &metrics,
+        write_request_unifier,
+    )
+    .with_partial_writes(router_config.partial_writes);
+
+    // Initialize the Flight write delegate, passing Arrow data written to
+    // the router through the same handler stack as HTTP writes, subject to
+    // the same request size and concurrency limits.
+    let flight_writer = Arc::new(FlightWriteDelegate::new(
+        Arc::clone(&namespace_resolver),
+        Arc::clone(&handler_stack),
+    ));
+
+    // Initialize the gRPC delete delegate, passing predicate deletes through
+    // the same handler stack as deletes made through the HTTP API.
+    let deleter = Arc::new(DeleteDelegate::new(
+        Arc::clone(&catalog),
         namespace_resolver,
         handler_stack,
-        &metrics,
-        write_request_unifier?,
-    );
+    ));
 
     // Initialize the gRPC API delegate that creates the services relevant to the RPC
     // write router path and use it to create the relevant `RpcWriteRouterServer` and
     // `RpcWriteRouterServerType`.
-    let grpc = RpcWriteGrpcDelegate::new(catalog, object_store);
+    let grpc = RpcWriteGrpcDelegate::new(
+        catalog,
+        object_store,
+        Arc::new(IngesterDeletionObserver::new(
+            Arc::clone(&deletion_observer),
+            rpc_write,
+        )),
+        deletion_observer,
+        router_config
+            .namespace_delete_config
+            .namespace_delete_grace_period,
+        flight_writer,
+        deleter,
+        common_state.run_config().max_http_request_size,
+        http.request_limiter(),
+        authz,
+    );
 
     let router_server =
         RpcWriteRouterServer::new(http, grpc, metrics, common_state.trace_collector());
@@ -205,6 +205,18 @@ impl MutableBatch {
         Ok(&self.columns[*idx])
     }
 
+    /// Remove the column named `column` from this batch, returning it if it
+    /// existed.
+    pub fn remove_column(&mut self, column: &str) -> Option<Column> {
+        let idx = self.column_names.remove(column)?;
+        for v in self.column_names.values_mut() {
+            if *v > idx {
+                *v -= 1;
+            }
+        }
+        Some(self.columns.remove(idx))
+    }
+
     /// Return the approximate memory size of the batch, in bytes.
     ///
     /// This includes `Self`.
@@ -298,4 +310,22 @@ mod tests {
         assert_eq!(batch.size_data(), 124);
         assert_eq!(batch.columns().len(), 5);
     }
+
+    #[test]
+    fn remove_column() {
+        let batches = lines_to_batches("cpu,t1=hello,t2=world f1=1.1,f2=2i 1234", 0).unwrap();
+        let mut batch = batches.get("cpu").unwrap().clone();
+
+        assert!(batch.remove_column("t1").is_some());
+        assert!(batch.remove_column("t1").is_none());
+        assert!(batch.remove_column("bananas").is_none());
+
+        assert_eq!(
+            batch.column_names().into_iter().collect::<Vec<_>>(),
+            ["f1", "f2", "t2", "time"]
+        );
+        let rb = batch.to_arrow(schema::Projection::All).unwrap();
+        assert_eq!(rb.num_columns(), 4);
+        assert_eq!(rb.num_rows(), 1);
+    }
 }
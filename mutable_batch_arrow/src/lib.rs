@@ -0,0 +1,358 @@
+//! Code to convert Arrow [`RecordBatch`] to [`mutable_batch::MutableBatch`]
+
+#![deny(rustdoc::broken_intra_doc_links, rustdoc::bare_urls, rust_2018_idioms)]
+#![warn(
+    missing_copy_implementations,
+    missing_debug_implementations,
+    missing_docs,
+    clippy::explicit_iter_loop,
+    // See https://github.com/influxdata/influxdb_iox/pull/1671
+    clippy::future_not_send,
+    clippy::use_self,
+    clippy::clone_on_ref_ptr,
+    clippy::todo,
+    clippy::dbg_macro,
+    unused_crate_dependencies
+)]
+
+// Workaround for "unused crate" lint false positives.
+#[cfg(test)]
+use assert_matches as _;
+#[cfg(test)]
+use mutable_batch_lp as _;
+use workspace_hack as _;
+
+use arrow::{
+    array::{Array, AsArray},
+    compute::cast,
+    datatypes::{DataType, Float64Type, Int64Type, TimeUnit, TimestampNanosecondType, UInt64Type},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use arrow_util::bitset::BitSet;
+use hashbrown::HashSet;
+use mutable_batch::{writer::Writer, MutableBatch};
+use schema::{InfluxColumnType, InfluxFieldType, Schema, TIME_COLUMN_NAME};
+use snafu::{ensure, ResultExt, Snafu};
+
+/// Error type for Arrow conversion
+#[derive(Debug, Snafu)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[snafu(display("error writing column {}: {}", column, source))]
+    Write {
+        source: mutable_batch::writer::Error,
+        column: String,
+    },
+
+    #[snafu(display("error converting column {}: {}", column, source))]
+    Cast { source: ArrowError, column: String },
+
+    #[snafu(display("duplicate column name: {}", column))]
+    DuplicateColumnName { column: String },
+
+    #[snafu(display("record batch must contain time column"))]
+    MissingTime,
+
+    #[snafu(display("time column must not contain nulls"))]
+    NullTime,
+
+    #[snafu(display("cannot infer type for column {} with data type {}", column, data_type))]
+    InvalidType { column: String, data_type: DataType },
+}
+
+/// Result type for Arrow conversion
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Converts the provided [`RecordBatch`] into a new [`MutableBatch`].
+///
+/// See [`write_record_batch`] for the conversion rules.
+pub fn record_batch_to_mutable_batch(batch: &RecordBatch) -> Result<MutableBatch> {
+    let mut mb = MutableBatch::new();
+    write_record_batch(&mut mb, batch)?;
+    Ok(mb)
+}
+
+/// Writes the provided [`RecordBatch`] to a [`MutableBatch`], on error any
+/// changes made to `mb` are reverted.
+///
+/// If the schema of `batch` is a valid IOx [`Schema`], carrying the IOx
+/// column type metadata for every field, the column types are taken from it.
+/// Otherwise they are inferred from the Arrow data types:
+///
+/// * A `Timestamp(Nanosecond, _)` column named [`TIME_COLUMN_NAME`] is the
+///   timestamp column.
+/// * Dictionary encoded string columns are tags.
+/// * `Float64`, `Int64`, `UInt64`, `Utf8` and `Boolean` columns are fields.
+///
+/// The timestamp column is required, and must not contain nulls.
+pub fn write_record_batch(mb: &mut MutableBatch, batch: &RecordBatch) -> Result<()> {
+    let to_insert = batch.num_rows();
+    if to_insert == 0 {
+        return Ok(());
+    }
+
+    let arrow_schema = batch.schema();
+
+    // Verify columns are unique
+    let mut columns = HashSet::with_capacity(arrow_schema.fields().len());
+    for field in arrow_schema.fields() {
+        ensure!(
+            columns.insert(field.name().as_str()),
+            DuplicateColumnNameSnafu {
+                column: field.name()
+            }
+        );
+    }
+
+    let column_types = match Schema::try_from(arrow_schema) {
+        Ok(schema) => schema.iter().map(|(t, _)| t).collect::<Vec<_>>(),
+        Err(_) => batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| infer_column_type(f.name(), f.data_type()))
+            .collect::<Result<Vec<_>>>()?,
+    };
+
+    // Batch must contain a time column
+    ensure!(
+        column_types
+            .iter()
+            .zip(batch.schema().fields())
+            .any(|(t, f)| *t == InfluxColumnType::Timestamp && f.name() == TIME_COLUMN_NAME),
+        MissingTimeSnafu
+    );
+
+    let mut writer = Writer::new(mb, to_insert);
+    for ((influx_type, field), array) in column_types
+        .into_iter()
+        .zip(batch.schema().fields())
+        .zip(batch.columns())
+    {
+        let name = field.name().as_str();
+        let valid_mask = valid_mask(array.as_ref());
+        let valid_mask = valid_mask.as_ref().map(|v| v.bytes());
+
+        match influx_type {
+            InfluxColumnType::Field(InfluxFieldType::Float) => writer.write_f64(
+                name,
+                valid_mask,
+                array.as_primitive::<Float64Type>().iter().flatten(),
+            ),
+            InfluxColumnType::Field(InfluxFieldType::Integer) => writer.write_i64(
+                name,
+                valid_mask,
+                array.as_primitive::<Int64Type>().iter().flatten(),
+            ),
+            InfluxColumnType::Field(InfluxFieldType::UInteger) => writer.write_u64(
+                name,
+                valid_mask,
+                array.as_primitive::<UInt64Type>().iter().flatten(),
+            ),
+            InfluxColumnType::Field(InfluxFieldType::Boolean) => {
+                writer.write_bool(name, valid_mask, array.as_boolean().iter().flatten())
+            }
+            InfluxColumnType::Field(InfluxFieldType::String) => {
+                writer.write_string(name, valid_mask, array.as_string::<i32>().iter().flatten())
+            }
+            InfluxColumnType::Tag => {
+                // Tags may be dictionary encoded with any key type - decode
+                // them to plain strings to be interned by the writer.
+                let values = cast(array, &DataType::Utf8).context(CastSnafu { column: name })?;
+                writer.write_tag(name, valid_mask, values.as_string::<i32>().iter().flatten())
+            }
+            InfluxColumnType::Timestamp => {
+                ensure!(valid_mask.is_none(), NullTimeSnafu);
+                writer.write_time(
+                    name,
+                    array
+                        .as_primitive::<TimestampNanosecondType>()
+                        .values()
+                        .iter()
+                        .copied(),
+                )
+            }
+        }
+        .context(WriteSnafu { column: name })?;
+    }
+
+    writer.commit();
+
+    Ok(())
+}
+
+/// Infer the [`InfluxColumnType`] of a column from its Arrow data type.
+fn infer_column_type(name: &str, data_type: &DataType) -> Result<InfluxColumnType> {
+    match data_type {
+        DataType::Timestamp(TimeUnit::Nanosecond, _) if name == TIME_COLUMN_NAME => {
+            Ok(InfluxColumnType::Timestamp)
+        }
+        DataType::Dictionary(_, v) if v.as_ref() == &DataType::Utf8 => Ok(InfluxColumnType::Tag),
+        _ => InfluxFieldType::try_from(data_type.clone())
+            .map(InfluxColumnType::Field)
+            .map_err(|_| Error::InvalidType {
+                column: name.to_string(),
+                data_type: data_type.clone(),
+            }),
+    }
+}
+
+/// Returns the validity mask of `array` in the form expected by [`Writer`],
+/// or [`None`] if it contains no nulls.
+fn valid_mask(array: &dyn Array) -> Option<BitSet> {
+    if array.null_count() == 0 {
+        return None;
+    }
+
+    let mut mask = BitSet::with_size(array.len());
+    for idx in (0..array.len()).filter(|idx| array.is_valid(*idx)) {
+        mask.set(idx);
+    }
+    Some(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{
+            ArrayRef, BooleanArray, DictionaryArray, Float64Array, Int64Array,
+            TimestampNanosecondArray,
+        },
+        datatypes::{Field, Int32Type, Schema as ArrowSchema},
+    };
+    use assert_matches::assert_matches;
+    use mutable_batch_lp::test_helpers::lp_to_mutable_batch;
+    use schema::Projection;
+
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let (_, want) = lp_to_mutable_batch(
+            r#"cpu,host=a,region=west usage=1.5,count=3i,ucount=4u,up=true,msg="hello" 10
+            cpu,host=b usage=2.5,count=4i 20
+            cpu,region=east ucount=5u,up=false,msg="world" 30"#,
+        );
+        let batch = want.to_arrow(Projection::All).unwrap();
+
+        let got = record_batch_to_mutable_batch(&batch).unwrap();
+
+        // Project the columns in a fixed order, as the column order of
+        // Projection::All is arbitrary.
+        let columns = [
+            "count", "host", "msg", "region", "time", "ucount", "up", "usage",
+        ];
+        assert_eq!(
+            got.to_arrow(Projection::Some(&columns)).unwrap(),
+            want.to_arrow(Projection::Some(&columns)).unwrap()
+        );
+        assert_eq!(got.rows(), 3);
+    }
+
+    #[test]
+    fn test_inferred_types() {
+        let tags: DictionaryArray<Int32Type> =
+            vec![Some("a"), None, Some("a")].into_iter().collect();
+        let batch = RecordBatch::try_from_iter(vec![
+            ("host", Arc::new(tags) as ArrayRef),
+            (
+                "usage",
+                Arc::new(Float64Array::from(vec![Some(1.0), Some(2.0), None])) as ArrayRef,
+            ),
+            (
+                "up",
+                Arc::new(BooleanArray::from(vec![true, false, true])) as ArrayRef,
+            ),
+            (
+                "time",
+                Arc::new(TimestampNanosecondArray::from(vec![1, 2, 3])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+
+        let got = record_batch_to_mutable_batch(&batch).unwrap();
+        let schema = got.schema(Projection::All).unwrap();
+
+        assert_eq!(
+            schema.field_by_name("host").unwrap().0,
+            InfluxColumnType::Tag
+        );
+        assert_eq!(
+            schema.field_by_name("usage").unwrap().0,
+            InfluxColumnType::Field(InfluxFieldType::Float)
+        );
+        assert_eq!(
+            schema.field_by_name("up").unwrap().0,
+            InfluxColumnType::Field(InfluxFieldType::Boolean)
+        );
+        assert_eq!(
+            schema.field_by_name("time").unwrap().0,
+            InfluxColumnType::Timestamp
+        );
+
+        let tags = got.to_arrow(Projection::Some(&["host"])).unwrap();
+        let tags = cast(tags.column(0), &DataType::Utf8).unwrap();
+        assert_eq!(
+            tags.as_string::<i32>().iter().collect::<Vec<_>>(),
+            vec![Some("a"), None, Some("a")]
+        );
+        assert_eq!(got.rows(), 3);
+    }
+
+    #[test]
+    fn test_missing_time() {
+        let batch = RecordBatch::try_from_iter(vec![(
+            "count",
+            Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef,
+        )])
+        .unwrap();
+
+        let mut mb = MutableBatch::new();
+        assert_matches!(write_record_batch(&mut mb, &batch), Err(Error::MissingTime));
+        assert_eq!(mb.rows(), 0);
+    }
+
+    #[test]
+    fn test_null_time() {
+        let batch = RecordBatch::try_from_iter(vec![(
+            "time",
+            Arc::new(TimestampNanosecondArray::from(vec![Some(1), None])) as ArrayRef,
+        )])
+        .unwrap();
+
+        assert_matches!(record_batch_to_mutable_batch(&batch), Err(Error::NullTime));
+    }
+
+    #[test]
+    fn test_invalid_type() {
+        let schema = Arc::new(ArrowSchema::new(vec![
+            Field::new("ratio", DataType::Float32, true),
+            Field::new(
+                "time",
+                DataType::Timestamp(TimeUnit::Nanosecond, None),
+                false,
+            ),
+        ]));
+        let batch = RecordBatch::new_empty(Arc::clone(&schema));
+        // Empty batches are a no-op, regardless of schema.
+        assert_eq!(record_batch_to_mutable_batch(&batch).unwrap().rows(), 0);
+
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(arrow::array::Float32Array::from(vec![1.0])),
+                Arc::new(TimestampNanosecondArray::from(vec![1])),
+            ],
+        )
+        .unwrap();
+        assert_matches!(
+            record_batch_to_mutable_batch(&batch),
+            Err(Error::InvalidType { column, .. }) => {
+                assert_eq!(column, "ratio");
+            }
+        );
+    }
+}
@@ -24,7 +24,8 @@ use hashbrown::{hash_map::Entry, HashMap, HashSet};
 use influxdb_line_protocol::{parse_lines, FieldValue, ParsedLine};
 use mutable_batch::writer::Writer;
 use mutable_batch::MutableBatch;
-use snafu::{ResultExt, Snafu};
+use schema::{InfluxColumnType, InfluxFieldType};
+use snafu::{OptionExt, ResultExt, Snafu};
 
 /// Error type for line protocol conversion
 #[derive(Debug, Snafu)]
@@ -42,8 +43,8 @@ pub enum Error {
     #[snafu(display("empty write payload"))]
     EmptyPayload,
 
-    #[snafu(display("timestamp overflows i64"))]
-    TimestampOverflow,
+    #[snafu(display("error writing line {}: timestamp overflows i64", line))]
+    TimestampOverflow { line: usize },
 }
 
 /// Result type for line protocol conversion
@@ -108,31 +109,153 @@ impl LinesConverter {
     ///
     pub fn write_lp(&mut self, lines: &str) -> Result<()> {
         for (line_idx, maybe_line) in parse_lines(lines).enumerate() {
-            let mut line = maybe_line.context(LineProtocolSnafu { line: line_idx + 1 })?;
+            self.write_parsed_line(line_idx + 1, maybe_line, None)?;
+        }
+        Ok(())
+    }
+
+    /// Write some line protocol data, skipping the lines that cannot be
+    /// written rather than failing the whole payload.
+    ///
+    /// A line is rejected if it fails to parse, breaks the semantics
+    /// described in [`Self::write_lp()`], has a column with a different type
+    /// to the existing column in `schema`, or would exceed the table or
+    /// column limits of `schema` together with the lines written so far.
+    ///
+    /// Returns the error for each rejected line, in line order.
+    pub fn write_lp_partial(&mut self, lines: &str, schema: &dyn PartialWriteSchema) -> Vec<Error> {
+        parse_lines(lines)
+            .enumerate()
+            .filter_map(|(line_idx, maybe_line)| {
+                self.write_parsed_line(line_idx + 1, maybe_line, Some(schema))
+                    .err()
+            })
+            .collect()
+    }
+
+    /// Write a single line with the 1-based `line_number`, leaving the
+    /// batches unchanged if it cannot be written.
+    fn write_parsed_line(
+        &mut self,
+        line_number: usize,
+        maybe_line: Result<ParsedLine<'_>, influxdb_line_protocol::Error>,
+        schema: Option<&dyn PartialWriteSchema>,
+    ) -> Result<()> {
+        let mut line = maybe_line.context(LineProtocolSnafu { line: line_number })?;
+
+        if let Some(t) = line.timestamp.as_mut() {
+            *t = t
+                .checked_mul(self.timestamp_base)
+                .context(TimestampOverflowSnafu { line: line_number })?;
+        }
+
+        let measurement = line.series.measurement.as_str();
+
+        if let Some(schema) = schema {
+            check_column_types(&line, |column| schema.column_type(measurement, column))
+                .and_then(|_| self.check_limits(measurement, &line, schema))
+                .context(WriteSnafu { line: line_number })?;
+        }
 
-            if let Some(t) = line.timestamp.as_mut() {
-                *t = t
-                    .checked_mul(self.timestamp_base)
-                    .ok_or(Error::TimestampOverflow)?;
+        let (_, batch) = self
+            .batches
+            .raw_entry_mut()
+            .from_key(measurement)
+            .or_insert_with(|| (measurement.to_string(), MutableBatch::new()));
+
+        // TODO: Reuse writer
+        let mut writer = Writer::new(batch, 1);
+        if let Err(source) = write_line(&mut writer, &line, self.default_time) {
+            // Dropping the uncommitted writer rolls back the line, which may
+            // leave an empty batch for a new measurement.
+            drop(writer);
+            if batch.rows() == 0 {
+                self.batches.remove(measurement);
             }
+            return Err(Error::Write {
+                source,
+                line: line_number,
+            });
+        }
+        writer.commit();
 
-            self.stats.num_lines += 1;
-            self.stats.num_fields += line.field_set.len();
+        self.stats.num_lines += 1;
+        self.stats.num_fields += line.field_set.len();
 
-            let measurement = line.series.measurement.as_str();
+        Ok(())
+    }
 
-            let (_, batch) = self
-                .batches
-                .raw_entry_mut()
-                .from_key(measurement)
-                .or_insert_with(|| (measurement.to_string(), MutableBatch::new()));
+    /// Checks that writing the [`ParsedLine`] to `table`, in addition to the
+    /// lines written so far, does not exceed the table and column limits of
+    /// the `schema`.
+    ///
+    /// As for the limits enforced on whole writes, a line is only rejected if
+    /// it creates a new table or column.
+    fn check_limits(
+        &self,
+        table: &str,
+        line: &ParsedLine<'_>,
+        schema: &dyn PartialWriteSchema,
+    ) -> Result<(), LineWriteError> {
+        let batch = self.batches.get(table);
+
+        let existing_column_count = match schema.column_count(table) {
+            Some(n) => n,
+            None => {
+                if batch.is_none() {
+                    // The table is neither in the schema nor created by the
+                    // lines written so far.
+                    let created_tables = self
+                        .batches
+                        .keys()
+                        .filter(|t| schema.column_count(t).is_none())
+                        .count();
+                    let table_count = schema.table_count() + created_tables + 1;
+                    if table_count > schema.max_tables() {
+                        return Err(LineWriteError::TableLimit {
+                            table: table.to_string(),
+                            table_count,
+                            max_tables: schema.max_tables(),
+                        });
+                    }
+                }
+                0
+            }
+        };
 
-            // TODO: Reuse writer
-            let mut writer = Writer::new(batch, 1);
-            write_line(&mut writer, &line, self.default_time)
-                .context(WriteSnafu { line: line_idx + 1 })?;
-            writer.commit();
+        let is_new = |column: &str| {
+            schema.column_type(table, column).is_none()
+                && batch.map_or(true, |b| b.column(column).is_err())
+        };
+        let columns = || {
+            line.series
+                .tag_set
+                .iter()
+                .flatten()
+                .map(|(k, _)| k.as_str())
+                .chain(line.field_set.iter().map(|(k, _)| k.as_str()))
+                .chain(std::iter::once("time"))
+        };
+        if !columns().any(is_new) {
+            return Ok(());
         }
+
+        // Columns may be repeated within a line.
+        let new_columns = columns().filter(|c| is_new(c)).collect::<HashSet<_>>();
+        let created_columns = batch.map_or(0, |b| {
+            b.columns()
+                .filter(|(c, _)| schema.column_type(table, c).is_none())
+                .count()
+        });
+        let column_count = existing_column_count + created_columns + new_columns.len();
+        if column_count > schema.max_columns_per_table() {
+            return Err(LineWriteError::ColumnLimit {
+                table: table.to_string(),
+                column_count,
+                max_columns_per_table: schema.max_columns_per_table(),
+            });
+        }
+
         Ok(())
     }
 
@@ -163,6 +286,27 @@ pub fn lines_to_batches_stats(
     converter.finish()
 }
 
+/// The existing schema of the tables written by
+/// [`LinesConverter::write_lp_partial()`], which each line is validated
+/// against.
+pub trait PartialWriteSchema {
+    /// Returns the type of the existing `column` of `table`, if any.
+    fn column_type(&self, table: &str, column: &str) -> Option<InfluxColumnType>;
+
+    /// Returns the number of columns of the existing `table`, or [`None`] if
+    /// the table does not exist.
+    fn column_count(&self, table: &str) -> Option<usize>;
+
+    /// Returns the number of existing tables.
+    fn table_count(&self) -> usize;
+
+    /// The maximum number of columns of a table.
+    fn max_columns_per_table(&self) -> usize;
+
+    /// The maximum number of tables.
+    fn max_tables(&self) -> usize;
+}
+
 /// An error applying an already-parsed line protocol line ([`ParsedLine`]) to a
 /// [`MutableBatch`].
 #[allow(missing_copy_implementations)]
@@ -196,6 +340,74 @@ pub enum LineWriteError {
         /// The duplicated field name.
         name: String,
     },
+
+    /// Writing the line would exceed the maximum number of columns of its
+    /// table.
+    #[snafu(display(
+        "couldn't create columns in table `{}`; applying this line would result in {} columns, limit is {}",
+        table,
+        column_count,
+        max_columns_per_table
+    ))]
+    ColumnLimit {
+        /// The table of the line.
+        table: String,
+        /// The number of columns of the table after applying the line.
+        column_count: usize,
+        /// The configured limit.
+        max_columns_per_table: usize,
+    },
+
+    /// Writing the line would exceed the maximum number of tables.
+    #[snafu(display(
+        "couldn't create table `{}`; applying this line would result in {} tables, limit is {}",
+        table,
+        table_count,
+        max_tables
+    ))]
+    TableLimit {
+        /// The table of the line.
+        table: String,
+        /// The number of tables after applying the line.
+        table_count: usize,
+        /// The configured limit.
+        max_tables: usize,
+    },
+}
+
+/// Checks the type of each column of the [`ParsedLine`] against the existing
+/// type of the column, if any, returned by `column_type`.
+fn check_column_types(
+    line: &ParsedLine<'_>,
+    column_type: impl Fn(&str) -> Option<InfluxColumnType>,
+) -> Result<(), LineWriteError> {
+    let check = |column: &str, inserted: InfluxColumnType| match column_type(column) {
+        Some(existing) if existing != inserted => Err(LineWriteError::MutableBatch {
+            source: mutable_batch::writer::Error::TypeMismatch {
+                column: column.to_string(),
+                existing,
+                inserted,
+            },
+        }),
+        _ => Ok(()),
+    };
+
+    for (tag_key, _) in line.series.tag_set.iter().flatten() {
+        check(tag_key.as_str(), InfluxColumnType::Tag)?;
+    }
+
+    for (field_key, field_value) in &line.field_set {
+        let field_type = match field_value {
+            FieldValue::I64(_) => InfluxFieldType::Integer,
+            FieldValue::U64(_) => InfluxFieldType::UInteger,
+            FieldValue::F64(_) => InfluxFieldType::Float,
+            FieldValue::String(_) => InfluxFieldType::String,
+            FieldValue::Boolean(_) => InfluxFieldType::Boolean,
+        };
+        check(field_key.as_str(), InfluxColumnType::Field(field_type))?;
+    }
+
+    check("time", InfluxColumnType::Timestamp)
 }
 
 /// Writes the [`ParsedLine`] to the [`MutableBatch`], respecting the edge case
@@ -376,6 +588,176 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_partial() {
+        let lp = [
+            "cpu,tag1=v1 val=2i 0",
+            "cpu,tag1=v2 val=2.0 1",
+            "bad line",
+            "mem,host=a,host=b v=1 2",
+            "disk,host=a free=1i 3",
+            "cpu,tag1=v3 val=3i 4",
+        ]
+        .join("\n");
+
+        let schema = TestSchema {
+            columns: vec![(
+                "disk",
+                "free",
+                InfluxColumnType::Field(InfluxFieldType::Float),
+            )],
+            max_columns_per_table: 10,
+            max_tables: 10,
+        };
+
+        let mut converter = LinesConverter::new(5);
+        let rejected = converter.write_lp_partial(&lp, &schema);
+
+        assert_matches!(
+            rejected.as_slice(),
+            [
+                Error::Write {
+                    line: 2,
+                    source: LineWriteError::MutableBatch { .. }
+                },
+                Error::LineProtocol { line: 3, .. },
+                Error::Write {
+                    line: 4,
+                    source: LineWriteError::DuplicateTag { .. }
+                },
+                Error::Write {
+                    line: 5,
+                    source: LineWriteError::MutableBatch { .. }
+                },
+            ]
+        );
+
+        let (batches, stats) = converter.finish().unwrap();
+        assert_eq!(stats.num_lines, 2);
+        assert_eq!(batches.len(), 1);
+
+        assert_batches_eq!(
+            &[
+                "+------+--------------------------------+-----+",
+                "| tag1 | time                           | val |",
+                "+------+--------------------------------+-----+",
+                "| v1   | 1970-01-01T00:00:00Z           | 2   |",
+                "| v3   | 1970-01-01T00:00:00.000000004Z | 3   |",
+                "+------+--------------------------------+-----+",
+            ],
+            &[batches["cpu"].to_arrow(Projection::All).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_partial_limits() {
+        let lp = [
+            // Two new columns in the existing table, at the limit of 4.
+            "cpu,tag1=v1 val=1i,new=1i 0",
+            // A third new column exceeds the limit.
+            "cpu,tag1=v2 val=2i,other=2i 1",
+            // Existing and created columns only.
+            "cpu,tag1=v3 new=3i 2",
+            // A new table, at the limit of 2.
+            "mem v=1 3",
+            // Another new table exceeds the limit.
+            "disk v=1 4",
+            // Too many columns for a new table.
+            "mem,a=a,b=b,c=c v=1 5",
+        ]
+        .join("\n");
+
+        let schema = TestSchema {
+            columns: vec![
+                ("cpu", "tag1", InfluxColumnType::Tag),
+                (
+                    "cpu",
+                    "val",
+                    InfluxColumnType::Field(InfluxFieldType::Integer),
+                ),
+            ],
+            max_columns_per_table: 4,
+            max_tables: 2,
+        };
+
+        let mut converter = LinesConverter::new(5);
+        let rejected = converter.write_lp_partial(&lp, &schema);
+
+        assert_matches!(
+            rejected.as_slice(),
+            [
+                Error::Write {
+                    line: 2,
+                    source: LineWriteError::ColumnLimit {
+                        column_count: 5,
+                        max_columns_per_table: 4,
+                        ..
+                    }
+                },
+                Error::Write {
+                    line: 5,
+                    source: LineWriteError::TableLimit {
+                        table_count: 3,
+                        max_tables: 2,
+                        ..
+                    }
+                },
+                Error::Write {
+                    line: 6,
+                    source: LineWriteError::ColumnLimit {
+                        column_count: 5,
+                        max_columns_per_table: 4,
+                        ..
+                    }
+                },
+            ]
+        );
+
+        let (batches, stats) = converter.finish().unwrap();
+        assert_eq!(stats.num_lines, 3);
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches["cpu"].rows(), 2);
+        assert_eq!(batches["mem"].rows(), 1);
+    }
+
+    /// A [`PartialWriteSchema`] of the given `(table, column, type)` columns.
+    #[derive(Debug)]
+    struct TestSchema {
+        columns: Vec<(&'static str, &'static str, InfluxColumnType)>,
+        max_columns_per_table: usize,
+        max_tables: usize,
+    }
+
+    impl PartialWriteSchema for TestSchema {
+        fn column_type(&self, table: &str, column: &str) -> Option<InfluxColumnType> {
+            self.columns
+                .iter()
+                .find(|(t, c, _)| *t == table && *c == column)
+                .map(|(_, _, column_type)| *column_type)
+        }
+
+        fn column_count(&self, table: &str) -> Option<usize> {
+            let n = self.columns.iter().filter(|(t, _, _)| *t == table).count();
+            (n > 0).then_some(n)
+        }
+
+        fn table_count(&self) -> usize {
+            self.columns
+                .iter()
+                .map(|(t, _, _)| t)
+                .collect::<HashSet<_>>()
+                .len()
+        }
+
+        fn max_columns_per_table(&self) -> usize {
+            self.max_columns_per_table
+        }
+
+        fn max_tables(&self) -> usize {
+            self.max_tables
+        }
+    }
+
     #[test]
     fn test_nulls_string_and_float() {
         let lp = r#"m f0="cat" 1639612800000000000
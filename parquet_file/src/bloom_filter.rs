@@ -0,0 +1,543 @@
+//! Parquet row group pruning using the bloom filters written for tag columns.
+//!
+//! Files are only pruned if their [`IoxMetadata::bloom_filters`] flag is set,
+//! so files written without bloom filters incur no additional reads.
+
+use std::{ops::Range, sync::Arc};
+
+use bytes::{Buf, Bytes};
+use datafusion::{
+    datasource::physical_plan::{
+        parquet::DefaultParquetFileReaderFactory, FileMeta, ParquetFileReaderFactory,
+    },
+    error::Result as DataFusionResult,
+    logical_expr::Operator,
+    physical_expr::split_conjunction,
+    physical_plan::{
+        expressions::{BinaryExpr, Column, InListExpr, Literal},
+        metrics::{Count, ExecutionPlanMetricsSet, MetricBuilder},
+        PhysicalExpr,
+    },
+    scalar::ScalarValue,
+};
+use futures::{future::BoxFuture, FutureExt};
+use object_store::DynObjectStore;
+use observability_deps::tracing::*;
+use parquet::{
+    arrow::async_reader::AsyncFileReader,
+    bloom_filter::Sbbf,
+    data_type::ByteArray,
+    errors::{ParquetError, Result as ParquetResult},
+    file::{
+        metadata::{ColumnChunkMetaData, ParquetMetaData, RowGroupMetaData},
+        reader::{ChunkReader, Length},
+    },
+    format::BloomFilterHeader,
+};
+use thrift::protocol::{TCompactInputProtocol, TSerializable};
+
+use crate::metadata::{IoxMetadata, METADATA_KEY};
+
+/// Name of the DataFusion metric counting the parquet row groups skipped
+/// using the bloom filters of their tag columns.
+pub const BLOOM_FILTER_ROW_GROUPS_PRUNED_METRIC: &str = "bloom_filter_row_groups_pruned";
+
+/// The number of bytes read to decode a bloom filter header, an upper bound
+/// of its encoded size.
+const BLOOM_FILTER_HEADER_SIZE_ESTIMATE: usize = 20;
+
+/// A column that must contain one of the values for a row to match a
+/// predicate.
+#[derive(Debug)]
+struct TagGuarantee {
+    column: String,
+    values: Vec<String>,
+}
+
+/// A [`ParquetFileReaderFactory`] for readers that remove the row groups that
+/// cannot match a scan predicate from the parquet metadata, using the bloom
+/// filters written for tag columns.
+///
+/// Row groups are pruned for the `tag = 'value'` and `tag IN (...)`
+/// conjuncts of the predicate. The number of row groups pruned is reported
+/// by the [`BLOOM_FILTER_ROW_GROUPS_PRUNED_METRIC`] metric of the scan.
+#[derive(Debug)]
+pub struct BloomFilterReaderFactory {
+    inner: DefaultParquetFileReaderFactory,
+    guarantees: Arc<[TagGuarantee]>,
+}
+
+impl BloomFilterReaderFactory {
+    /// Initialise a new [`BloomFilterReaderFactory`] reading files from
+    /// `object_store` and pruning row groups for `predicate`.
+    ///
+    /// Returns [`None`] if `predicate` cannot be used to prune row groups
+    /// with bloom filters.
+    pub fn try_new(
+        object_store: Arc<DynObjectStore>,
+        predicate: &Arc<dyn PhysicalExpr>,
+    ) -> Option<Self> {
+        let guarantees = tag_guarantees(predicate);
+        if guarantees.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            inner: DefaultParquetFileReaderFactory::new(object_store),
+            guarantees: guarantees.into(),
+        })
+    }
+}
+
+impl ParquetFileReaderFactory for BloomFilterReaderFactory {
+    fn create_reader(
+        &self,
+        partition_index: usize,
+        file_meta: FileMeta,
+        metadata_size_hint: Option<usize>,
+        metrics: &ExecutionPlanMetricsSet,
+    ) -> DataFusionResult<Box<dyn AsyncFileReader + Send>> {
+        let pruned = MetricBuilder::new(metrics)
+            .counter(BLOOM_FILTER_ROW_GROUPS_PRUNED_METRIC, partition_index);
+        let inner =
+            self.inner
+                .create_reader(partition_index, file_meta, metadata_size_hint, metrics)?;
+
+        Ok(Box::new(BloomFilterReader {
+            inner,
+            guarantees: Arc::clone(&self.guarantees),
+            pruned,
+        }))
+    }
+}
+
+/// An [`AsyncFileReader`] returning the metadata of the row groups of a file
+/// that may match the [`TagGuarantee`]s only.
+struct BloomFilterReader {
+    inner: Box<dyn AsyncFileReader + Send>,
+    guarantees: Arc<[TagGuarantee]>,
+    pruned: Count,
+}
+
+impl AsyncFileReader for BloomFilterReader {
+    fn get_bytes(&mut self, range: Range<usize>) -> BoxFuture<'_, ParquetResult<Bytes>> {
+        self.inner.get_bytes(range)
+    }
+
+    fn get_byte_ranges(
+        &mut self,
+        ranges: Vec<Range<usize>>,
+    ) -> BoxFuture<'_, ParquetResult<Vec<Bytes>>> {
+        self.inner.get_byte_ranges(ranges)
+    }
+
+    fn get_metadata(&mut self) -> BoxFuture<'_, ParquetResult<Arc<ParquetMetaData>>> {
+        async move {
+            let metadata = self.inner.get_metadata().await?;
+            if !has_bloom_filters(&metadata) {
+                return Ok(metadata);
+            }
+
+            let mut keep = Vec::with_capacity(metadata.num_row_groups());
+            for row_group in metadata.row_groups() {
+                keep.push(self.may_match(row_group).await?);
+            }
+
+            let n_pruned = keep.iter().filter(|keep| !**keep).count();
+            if n_pruned == 0 {
+                return Ok(metadata);
+            }
+            self.pruned.add(n_pruned);
+
+            Ok(Arc::new(retain_row_groups(&metadata, &keep)))
+        }
+        .boxed()
+    }
+}
+
+impl BloomFilterReader {
+    /// Returns false if the bloom filters of `row_group` show it contains no
+    /// rows matching the [`TagGuarantee`]s.
+    async fn may_match(&mut self, row_group: &RowGroupMetaData) -> ParquetResult<bool> {
+        let guarantees = Arc::clone(&self.guarantees);
+        for guarantee in guarantees.iter() {
+            let Some(column) = row_group
+                .columns()
+                .iter()
+                .find(|c| c.column_descr().name() == guarantee.column)
+            else {
+                continue;
+            };
+            let Some(filter) = self.read_bloom_filter(column).await? else {
+                continue;
+            };
+
+            if !guarantee
+                .values
+                .iter()
+                .any(|v| filter.check(&ByteArray::from(v.as_str())))
+            {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Read the bloom filter of `column`, if it has one.
+    async fn read_bloom_filter(
+        &mut self,
+        column: &ColumnChunkMetaData,
+    ) -> ParquetResult<Option<Sbbf>> {
+        let Some(offset) = column.bloom_filter_offset() else {
+            return Ok(None);
+        };
+        let offset = usize::try_from(offset)
+            .map_err(|e| ParquetError::General(format!("invalid bloom filter offset: {e}")))?;
+
+        // Decode the header to learn the size of the filter bitset that
+        // follows it.
+        let header_bytes = self
+            .inner
+            .get_bytes(offset..offset + BLOOM_FILTER_HEADER_SIZE_ESTIMATE)
+            .await?;
+        let mut remaining = header_bytes.as_ref();
+        let header = {
+            let mut protocol = TCompactInputProtocol::new(&mut remaining);
+            BloomFilterHeader::read_from_in_protocol(&mut protocol)
+        }
+        .map_err(|e| ParquetError::General(format!("invalid bloom filter header: {e}")))?;
+        let header_len = header_bytes.len() - remaining.len();
+        let bitset_len = usize::try_from(header.num_bytes)
+            .map_err(|e| ParquetError::General(format!("invalid bloom filter size: {e}")))?;
+
+        let data = self
+            .inner
+            .get_bytes(offset..offset + header_len + bitset_len)
+            .await?;
+
+        Sbbf::read_from_column_chunk(
+            column,
+            Arc::new(BloomFilterBytes {
+                offset: offset as u64,
+                data,
+            }),
+        )
+    }
+}
+
+/// Returns true if the [`IoxMetadata`] of the file described by `metadata`
+/// records that bloom filters were written for its tag columns.
+fn has_bloom_filters(metadata: &ParquetMetaData) -> bool {
+    let Some(value) = metadata
+        .file_metadata()
+        .key_value_metadata()
+        .and_then(|kv| kv.iter().find(|kv| kv.key == METADATA_KEY))
+        .and_then(|kv| kv.value.as_ref())
+    else {
+        return false;
+    };
+
+    match IoxMetadata::from_base64(value.as_bytes()) {
+        Ok(meta) => meta.bloom_filters,
+        Err(e) => {
+            warn!(error=%e, "invalid iox metadata, skipping bloom filter pruning");
+            false
+        }
+    }
+}
+
+/// Returns a copy of `metadata` with the row groups for which `keep` is false
+/// removed.
+fn retain_row_groups(metadata: &ParquetMetaData, keep: &[bool]) -> ParquetMetaData {
+    fn retain<T: Clone>(values: &[T], keep: &[bool]) -> Vec<T> {
+        values
+            .iter()
+            .zip(keep)
+            .filter(|(_, keep)| **keep)
+            .map(|(v, _)| v.clone())
+            .collect()
+    }
+
+    ParquetMetaData::new_with_page_index(
+        metadata.file_metadata().clone(),
+        retain(metadata.row_groups(), keep),
+        metadata.column_index().map(|index| retain(index, keep)),
+        metadata.offset_index().map(|index| retain(index, keep)),
+    )
+}
+
+/// Extract the columns that must contain one of a set of string values for a
+/// row to match `predicate`.
+fn tag_guarantees(predicate: &Arc<dyn PhysicalExpr>) -> Vec<TagGuarantee> {
+    split_conjunction(predicate)
+        .into_iter()
+        .filter_map(|expr| {
+            let expr_any = expr.as_any();
+
+            if let Some(binary) = expr_any.downcast_ref::<BinaryExpr>() {
+                if *binary.op() != Operator::Eq {
+                    return None;
+                }
+                let (column, value) = column_and_value(binary.left(), binary.right())
+                    .or_else(|| column_and_value(binary.right(), binary.left()))?;
+                Some(TagGuarantee {
+                    column,
+                    values: vec![value],
+                })
+            } else if let Some(in_list) = expr_any.downcast_ref::<InListExpr>() {
+                if in_list.negated() {
+                    return None;
+                }
+                let column = in_list.expr().as_any().downcast_ref::<Column>()?;
+                let values = in_list
+                    .list()
+                    .iter()
+                    .map(string_literal)
+                    .collect::<Option<Vec<_>>>()?;
+                Some(TagGuarantee {
+                    column: column.name().to_owned(),
+                    values,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn column_and_value(
+    column: &Arc<dyn PhysicalExpr>,
+    value: &Arc<dyn PhysicalExpr>,
+) -> Option<(String, String)> {
+    let column = column.as_any().downcast_ref::<Column>()?;
+    Some((column.name().to_owned(), string_literal(value)?))
+}
+
+fn string_literal(expr: &Arc<dyn PhysicalExpr>) -> Option<String> {
+    let mut value = expr.as_any().downcast_ref::<Literal>()?.value();
+    if let ScalarValue::Dictionary(_, inner) = value {
+        value = inner.as_ref();
+    }
+
+    match value {
+        ScalarValue::Utf8(Some(v)) | ScalarValue::LargeUtf8(Some(v)) => Some(v.clone()),
+        _ => None,
+    }
+}
+
+/// The bytes of a bloom filter starting at `offset` in a parquet file,
+/// addressed by their offset in the file.
+struct BloomFilterBytes {
+    offset: u64,
+    data: Bytes,
+}
+
+impl Length for BloomFilterBytes {
+    fn len(&self) -> u64 {
+        self.offset + self.data.len() as u64
+    }
+}
+
+impl ChunkReader for BloomFilterBytes {
+    type T = bytes::buf::Reader<Bytes>;
+
+    fn get_read(&self, start: u64) -> ParquetResult<Self::T> {
+        let start = start
+            .checked_sub(self.offset)
+            .filter(|start| *start <= self.data.len() as u64)
+            .ok_or_else(|| ParquetError::EOF(format!("read at {start} outside bloom filter")))?;
+        Ok(self.data.slice(start as usize..).reader())
+    }
+
+    fn get_bytes(&self, start: u64, length: usize) -> ParquetResult<Bytes> {
+        let range = start
+            .checked_sub(self.offset)
+            .map(|start| start as usize..start as usize + length)
+            .filter(|range| range.end <= self.data.len())
+            .ok_or_else(|| {
+                ParquetError::EOF(format!(
+                    "read of {length} bytes at {start} outside bloom filter"
+                ))
+            })?;
+        Ok(self.data.slice(range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::{
+        array::{ArrayRef, StringArray},
+        record_batch::RecordBatch,
+    };
+    use data_types::{CompactionLevel, NamespaceId, TableId};
+    use datafusion::{
+        datasource::{
+            listing::PartitionedFile,
+            object_store::ObjectStoreUrl,
+            physical_plan::{FileScanConfig, ParquetExec},
+        },
+        execution::context::SessionContext,
+        physical_plan::{
+            expressions::{binary, col, lit},
+            ExecutionPlan, Statistics,
+        },
+    };
+    use iox_time::Time;
+    use object_store::{memory::InMemory, path::Path};
+    use parquet::{
+        arrow::ArrowWriter,
+        file::{metadata::KeyValue, properties::WriterProperties},
+        schema::types::ColumnPath,
+    };
+
+    use super::*;
+
+    /// Scan a file of two row groups, with tag values `a, c` and `b, d`, for
+    /// the rows with tag `b`, returning the number of rows read and row
+    /// groups pruned.
+    async fn scan(bloom_filters: bool) -> (usize, usize) {
+        let meta = IoxMetadata {
+            object_store_id: Default::default(),
+            creation_timestamp: Time::from_timestamp_nanos(42),
+            namespace_id: NamespaceId::new(1),
+            namespace_name: "bananas".into(),
+            table_id: TableId::new(3),
+            table_name: "platanos".into(),
+            partition_key: "potato".into(),
+            compaction_level: CompactionLevel::FileNonOverlapped,
+            sort_key: None,
+            max_l0_created_at: Time::from_timestamp_nanos(42),
+            bloom_filters,
+        };
+
+        // Bloom filters are always written, only the metadata flag differs.
+        let props = WriterProperties::builder()
+            .set_key_value_metadata(Some(vec![KeyValue {
+                key: METADATA_KEY.to_string(),
+                value: Some(meta.to_base64().unwrap()),
+            }]))
+            .set_max_row_group_size(2)
+            .set_column_bloom_filter_enabled(ColumnPath::from("tag"), true)
+            .set_column_bloom_filter_ndv(ColumnPath::from("tag"), 100)
+            .build();
+
+        let batch = RecordBatch::try_from_iter([(
+            "tag",
+            Arc::new(StringArray::from(vec!["a", "c", "b", "d"])) as ArrayRef,
+        )])
+        .unwrap();
+        let schema = batch.schema();
+
+        let mut buf = vec![];
+        let mut writer = ArrowWriter::try_new(&mut buf, Arc::clone(&schema), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let object_store: Arc<DynObjectStore> = Arc::new(InMemory::default());
+        let size = buf.len();
+        object_store
+            .put(&Path::from("file.parquet"), Bytes::from(buf))
+            .await
+            .unwrap();
+
+        // The statistics of both row groups include "b".
+        let predicate = binary(
+            col("tag", &schema).unwrap(),
+            Operator::Eq,
+            lit("b"),
+            &schema,
+        )
+        .unwrap();
+        let factory = BloomFilterReaderFactory::try_new(object_store, &predicate).unwrap();
+
+        let base_config = FileScanConfig {
+            object_store_url: ObjectStoreUrl::parse("iox://store/").unwrap(),
+            file_schema: schema,
+            file_groups: vec![vec![PartitionedFile::new(
+                "file.parquet".to_owned(),
+                size as u64,
+            )]],
+            statistics: Statistics::default(),
+            projection: None,
+            limit: None,
+            table_partition_cols: vec![],
+            output_ordering: vec![],
+            infinite_source: false,
+        };
+        let exec: Arc<dyn ExecutionPlan> = Arc::new(
+            ParquetExec::new(base_config, Some(predicate), None)
+                .with_parquet_file_reader_factory(Arc::new(factory)),
+        );
+
+        let ctx = SessionContext::new();
+        let batches = datafusion::physical_plan::collect(Arc::clone(&exec), ctx.task_ctx())
+            .await
+            .unwrap();
+        let rows = batches.iter().map(|b| b.num_rows()).sum();
+        let pruned = exec
+            .metrics()
+            .unwrap()
+            .sum_by_name(BLOOM_FILTER_ROW_GROUPS_PRUNED_METRIC)
+            .map(|v| v.as_usize())
+            .unwrap_or_default();
+
+        (rows, pruned)
+    }
+
+    #[tokio::test]
+    async fn test_prune_row_groups() {
+        // The row group without "b" is skipped.
+        assert_eq!(scan(true).await, (2, 1));
+    }
+
+    #[tokio::test]
+    async fn test_no_pruning_without_metadata_flag() {
+        assert_eq!(scan(false).await, (4, 0));
+    }
+
+    #[test]
+    fn test_tag_guarantees() {
+        let batch = RecordBatch::try_from_iter([
+            ("tag", Arc::new(StringArray::from(vec!["a"])) as ArrayRef),
+            ("field", Arc::new(StringArray::from(vec!["a"])) as ArrayRef),
+        ])
+        .unwrap();
+        let schema = batch.schema();
+
+        let predicate = binary(
+            binary(
+                lit("a"),
+                Operator::Eq,
+                col("tag", &schema).unwrap(),
+                &schema,
+            )
+            .unwrap(),
+            Operator::And,
+            binary(
+                col("field", &schema).unwrap(),
+                Operator::NotEq,
+                lit("b"),
+                &schema,
+            )
+            .unwrap(),
+            &schema,
+        )
+        .unwrap();
+
+        let got = tag_guarantees(&predicate)
+            .into_iter()
+            .map(|g| (g.column, g.values))
+            .collect::<Vec<_>>();
+        assert_eq!(got, [("tag".to_owned(), vec!["a".to_owned()])]);
+
+        let predicate = binary(
+            col("field", &schema).unwrap(),
+            Operator::NotEq,
+            lit("b"),
+            &schema,
+        )
+        .unwrap();
+        assert!(tag_guarantees(&predicate).is_empty());
+    }
+}
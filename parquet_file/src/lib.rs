@@ -20,6 +20,7 @@
 // Workaround for "unused crate" lint false positives.
 use workspace_hack as _;
 
+pub mod bloom_filter;
 pub mod chunk;
 pub mod metadata;
 pub mod serialize;
@@ -294,6 +294,11 @@ pub struct IoxMetadata {
     /// If this metadata is for an L1/L2 file, this value will be the max of all L0 files
     ///  that are compacted into this file
     pub max_l0_created_at: Time,
+
+    /// When true, bloom filters are written for the tag columns of the
+    /// file, allowing readers to skip row groups that cannot contain a
+    /// tag value.
+    pub bloom_filters: bool,
 }
 
 impl IoxMetadata {
@@ -336,6 +341,7 @@ impl IoxMetadata {
             sort_key,
             compaction_level: self.compaction_level as i32,
             max_l0_created_at: Some(self.max_l0_created_at.date_time().into()),
+            bloom_filters: self.bloom_filters,
         };
 
         let mut buf = Vec::new();
@@ -390,6 +396,7 @@ impl IoxMetadata {
                 },
             )?,
             max_l0_created_at,
+            bloom_filters: proto_msg.bloom_filters,
         })
     }
 
@@ -409,6 +416,7 @@ impl IoxMetadata {
             compaction_level: CompactionLevel::Initial,
             sort_key: None,
             max_l0_created_at: Time::from_timestamp_nanos(creation_timestamp_ns),
+            bloom_filters: false,
         }
     }
 
@@ -1006,6 +1014,7 @@ mod tests {
             compaction_level: CompactionLevel::Initial,
             sort_key: Some(sort_key),
             max_l0_created_at: create_time,
+            bloom_filters: true,
         };
 
         let proto = iox_metadata.to_protobuf().unwrap();
@@ -1028,6 +1037,7 @@ mod tests {
             compaction_level: CompactionLevel::FileNonOverlapped,
             sort_key: None,
             max_l0_created_at: Time::from_timestamp_nanos(42),
+            bloom_filters: false,
         };
 
         let array = StringArray::from_iter([Some("bananas")]);
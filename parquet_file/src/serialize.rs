@@ -4,6 +4,7 @@
 
 use std::{io::Write, sync::Arc};
 
+use arrow::datatypes::SchemaRef;
 use datafusion::{
     error::DataFusionError, execution::memory_pool::MemoryPool,
     physical_plan::SendableRecordBatchStream,
@@ -14,8 +15,13 @@ use observability_deps::tracing::{debug, trace, warn};
 use parquet::{
     basic::Compression,
     errors::ParquetError,
-    file::{metadata::KeyValue, properties::WriterProperties},
+    file::{
+        metadata::KeyValue,
+        properties::{EnabledStatistics, WriterProperties},
+    },
+    schema::types::ColumnPath,
 };
+use schema::{Schema, TIME_COLUMN_NAME};
 use thiserror::Error;
 
 use crate::{
@@ -31,6 +37,25 @@ pub const ROW_GROUP_WRITE_SIZE: usize = 1024 * 1024;
 #[allow(clippy::assertions_on_constants)]
 const _: () = assert!(ROW_GROUP_WRITE_SIZE % BATCH_SIZE == 0);
 
+/// The false positive probability of the bloom filters written for tag
+/// columns.
+pub const BLOOM_FILTER_FPP: f64 = 0.01;
+
+/// The number of distinct values per row group the tag column bloom filters
+/// are sized for.
+///
+/// A filter sized for [`BLOOM_FILTER_FPP`] at this NDV occupies 128KiB per
+/// tag column, per row group. Row groups with more distinct tag values
+/// than this see a higher false positive rate.
+pub const BLOOM_FILTER_NDV: u64 = 100_000;
+
+/// The maximum number of rows in a data page.
+///
+/// The page index written for the time and tag columns records the min/max
+/// of every page, so smaller pages let readers skip more of a row group at
+/// the cost of more page headers and index entries.
+pub const DATA_PAGE_ROW_COUNT_LIMIT: usize = 20_000;
+
 /// [`RecordBatch`] to Parquet serialisation errors.
 ///
 /// [`RecordBatch`]: arrow::record_batch::RecordBatch
@@ -102,6 +127,9 @@ impl From<crate::writer::Error> for CodecError {
 /// [`METADATA_KEY`], with a base64-wrapped, protobuf serialized
 /// [`proto::IoxMetadata`] structure.
 ///
+/// If [`IoxMetadata::bloom_filters`] is set, a bloom filter is written for
+/// each tag column in the schema.
+///
 /// Returns the serialized [`FileMetaData`] for the encoded parquet file, from
 /// which an [`IoxParquetMetaData`] can be derived.
 ///
@@ -135,7 +163,7 @@ where
     pin_mut!(stream);
 
     // Serialize the IoxMetadata to the protobuf bytes.
-    let props = writer_props(meta)?;
+    let props = writer_props(meta, &schema)?;
     let write_batch_size = props.write_batch_size();
     let max_row_group_size = props.max_row_group_size();
 
@@ -192,31 +220,74 @@ pub async fn to_parquet_bytes(
 /// Helper to construct [`WriterProperties`] , serialising the given
 /// [`IoxMetadata`] and embedding it as a key=value property keyed by
 /// [`METADATA_KEY`].
-fn writer_props(meta: &IoxMetadata) -> Result<WriterProperties, prost::EncodeError> {
-    let builder = WriterProperties::builder()
+///
+/// Page level statistics are written for the time and tag columns of
+/// `schema`, producing the column and offset indexes readers use to skip
+/// pages. If [`IoxMetadata::bloom_filters`] is set, bloom filters are
+/// enabled for the tag columns too.
+fn writer_props(
+    meta: &IoxMetadata,
+    schema: &SchemaRef,
+) -> Result<WriterProperties, prost::EncodeError> {
+    let mut builder = WriterProperties::builder()
         .set_key_value_metadata(Some(vec![KeyValue {
             key: METADATA_KEY.to_string(),
             value: Some(meta.to_base64()?),
         }]))
         .set_compression(Compression::ZSTD(Default::default()))
-        .set_max_row_group_size(ROW_GROUP_WRITE_SIZE);
+        .set_max_row_group_size(ROW_GROUP_WRITE_SIZE)
+        .set_data_page_row_count_limit(DATA_PAGE_ROW_COUNT_LIMIT)
+        .set_column_statistics_enabled(ColumnPath::from(TIME_COLUMN_NAME), EnabledStatistics::Page);
+
+    let tags = tag_columns(schema);
+    for tag in &tags {
+        builder = builder
+            .set_column_statistics_enabled(ColumnPath::from(tag.as_str()), EnabledStatistics::Page);
+    }
+
+    if meta.bloom_filters {
+        for tag in tags {
+            let col = ColumnPath::from(tag);
+            builder = builder
+                .set_column_bloom_filter_enabled(col.clone(), true)
+                .set_column_bloom_filter_fpp(col.clone(), BLOOM_FILTER_FPP)
+                .set_column_bloom_filter_ndv(col, BLOOM_FILTER_NDV);
+        }
+    }
 
     Ok(builder.build())
 }
 
+/// Returns the names of the tag columns in `schema`.
+///
+/// Returns no columns if `schema` is not a valid IOx schema.
+fn tag_columns(schema: &SchemaRef) -> Vec<String> {
+    match Schema::try_from(Arc::clone(schema)) {
+        Ok(schema) => schema.tags_iter().map(|f| f.name().clone()).collect(),
+        Err(e) => {
+            warn!(error=%e, "not an iox schema, skipping tag column indexes");
+            vec![]
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::metadata::IoxParquetMetaData;
     use arrow::{
-        array::{ArrayRef, StringArray},
+        array::{ArrayRef, DictionaryArray, Float64Array, StringArray, TimestampNanosecondArray},
+        datatypes::Int32Type,
         record_batch::RecordBatch,
     };
     use bytes::Bytes;
     use data_types::{CompactionLevel, NamespaceId, TableId};
-    use datafusion::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use datafusion::parquet::arrow::arrow_reader::{
+        ArrowReaderOptions, ParquetRecordBatchReaderBuilder,
+    };
     use datafusion_util::{unbounded_memory_pool, MemoryStream};
     use iox_time::Time;
+    use schema::{builder::SchemaBuilder, InfluxFieldType};
     use std::sync::Arc;
 
     #[tokio::test]
@@ -232,6 +303,7 @@ mod tests {
             compaction_level: CompactionLevel::FileNonOverlapped,
             sort_key: None,
             max_l0_created_at: Time::from_timestamp_nanos(42),
+            bloom_filters: false,
         };
 
         let batch = RecordBatch::try_from_iter([("a", to_string_array(&["value"]))]).unwrap();
@@ -270,6 +342,131 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_encode_bloom_filters() {
+        let meta = IoxMetadata {
+            object_store_id: Default::default(),
+            creation_timestamp: Time::from_timestamp_nanos(42),
+            namespace_id: NamespaceId::new(1),
+            namespace_name: "bananas".into(),
+            table_id: TableId::new(3),
+            table_name: "platanos".into(),
+            partition_key: "potato".into(),
+            compaction_level: CompactionLevel::FileNonOverlapped,
+            sort_key: None,
+            max_l0_created_at: Time::from_timestamp_nanos(42),
+            bloom_filters: true,
+        };
+
+        let schema = SchemaBuilder::new()
+            .tag("host")
+            .influx_field("temp", InfluxFieldType::Float)
+            .timestamp()
+            .build()
+            .unwrap();
+        let batch = RecordBatch::try_new(
+            schema.as_arrow(),
+            vec![
+                Arc::new(DictionaryArray::<Int32Type>::from_iter(["a", "b"])),
+                Arc::new(Float64Array::from(vec![1.0, 2.0])),
+                Arc::new(TimestampNanosecondArray::from(vec![1, 2])),
+            ],
+        )
+        .unwrap();
+        let stream = Box::pin(MemoryStream::new(vec![batch]));
+
+        let (_bytes, file_meta) = to_parquet_bytes(stream, &meta, unbounded_memory_pool())
+            .await
+            .expect("should serialize");
+
+        // Only the tag column has a bloom filter.
+        let row_group = &file_meta.row_groups[0];
+        let has_filter = schema
+            .iter()
+            .zip(&row_group.columns)
+            .map(|((_, field), col)| {
+                let filter = col.meta_data.as_ref().unwrap().bloom_filter_offset;
+                (field.name().as_str(), filter.is_some())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            has_filter,
+            [("host", true), ("temp", false), ("time", false)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_encode_page_index() {
+        let meta = IoxMetadata {
+            object_store_id: Default::default(),
+            creation_timestamp: Time::from_timestamp_nanos(42),
+            namespace_id: NamespaceId::new(1),
+            namespace_name: "bananas".into(),
+            table_id: TableId::new(3),
+            table_name: "platanos".into(),
+            partition_key: "potato".into(),
+            compaction_level: CompactionLevel::FileNonOverlapped,
+            sort_key: None,
+            max_l0_created_at: Time::from_timestamp_nanos(42),
+            bloom_filters: false,
+        };
+
+        let schema = SchemaBuilder::new()
+            .tag("host")
+            .influx_field("temp", InfluxFieldType::Float)
+            .timestamp()
+            .build()
+            .unwrap();
+        let n_rows = DATA_PAGE_ROW_COUNT_LIMIT * 2 + 1;
+        let batch = RecordBatch::try_new(
+            schema.as_arrow(),
+            vec![
+                Arc::new(DictionaryArray::<Int32Type>::from_iter(
+                    (0..n_rows).map(|i| if i % 2 == 0 { "a" } else { "b" }),
+                )),
+                Arc::new(Float64Array::from_iter_values(
+                    (0..n_rows).map(|i| i as f64),
+                )),
+                Arc::new(TimestampNanosecondArray::from_iter_values(
+                    (0..n_rows).map(|i| i as i64),
+                )),
+            ],
+        )
+        .unwrap();
+        let stream = Box::pin(MemoryStream::new(vec![batch]));
+
+        let (bytes, file_meta) = to_parquet_bytes(stream, &meta, unbounded_memory_pool())
+            .await
+            .expect("should serialize");
+
+        // The tag and time columns have both a column and an offset index.
+        let row_group = &file_meta.row_groups[0];
+        for ((_, field), col) in schema.iter().zip(&row_group.columns) {
+            if field.name() == "temp" {
+                continue;
+            }
+            assert!(
+                col.column_index_offset.is_some(),
+                "no column index for {}",
+                field.name()
+            );
+            assert!(
+                col.offset_index_offset.is_some(),
+                "no offset index for {}",
+                field.name()
+            );
+        }
+
+        // And the data pages are split at (approximately) the row count limit.
+        let reader = ParquetRecordBatchReaderBuilder::try_new_with_options(
+            Bytes::from(bytes),
+            ArrowReaderOptions::new().with_page_index(true),
+        )
+        .unwrap();
+        let offset_index = reader.metadata().offset_index().expect("offset index");
+        assert!(offset_index[0][2].len() > 1);
+    }
+
     fn to_string_array(strs: &[&str]) -> ArrayRef {
         let array: StringArray = strs.iter().map(|s| Some(*s)).collect();
         Arc::new(array)
@@ -28,6 +28,7 @@ use object_store::{DynObjectStore, ObjectMeta};
 use observability_deps::tracing::*;
 use schema::Projection;
 use std::{
+    collections::HashSet,
     fmt::Display,
     sync::Arc,
     time::{Duration, Instant},
@@ -102,6 +103,10 @@ pub struct ParquetExecInput {
 
     /// Object metadata.
     pub object_meta: ObjectMeta,
+
+    /// The object store holding the file, for readers that bypass the
+    /// store registered for [`object_store_url`](Self::object_store_url).
+    pub object_store: Arc<DynObjectStore>,
 }
 
 impl ParquetExecInput {
@@ -153,6 +158,51 @@ impl ParquetExecInput {
     }
 }
 
+/// The set of tables that parquet bloom filters are written for.
+///
+/// See [`IoxMetadata::bloom_filters`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum BloomFilterTables {
+    /// No bloom filters are written.
+    #[default]
+    None,
+
+    /// Bloom filters are written for all tables.
+    All,
+
+    /// Bloom filters are written for the named tables only.
+    Only(HashSet<String>),
+}
+
+impl BloomFilterTables {
+    /// Returns true if bloom filters should be written for `table_name`.
+    pub fn contains(&self, table_name: &str) -> bool {
+        match self {
+            Self::None => false,
+            Self::All => true,
+            Self::Only(tables) => tables.contains(table_name),
+        }
+    }
+}
+
+/// Parses a list of table names, where an empty list selects no tables and a
+/// list containing `*` selects all tables.
+impl<T> FromIterator<T> for BloomFilterTables
+where
+    T: Into<String>,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let tables = iter.into_iter().map(Into::into).collect::<HashSet<_>>();
+        if tables.is_empty() {
+            Self::None
+        } else if tables.contains("*") {
+            Self::All
+        } else {
+            Self::Only(tables)
+        }
+    }
+}
+
 /// The [`ParquetStorage`] type encapsulates [`RecordBatch`] persistence to an
 /// underlying [`ObjectStore`].
 ///
@@ -171,6 +221,9 @@ pub struct ParquetStorage {
 
     /// Storage ID to hook it into DataFusion.
     id: StorageId,
+
+    /// Tables for which bloom filters are written.
+    bloom_filter_tables: Arc<BloomFilterTables>,
 }
 
 impl Display for ParquetStorage {
@@ -187,7 +240,29 @@ impl ParquetStorage {
     /// Initialise a new [`ParquetStorage`] using `object_store` as the
     /// persistence layer.
     pub fn new(object_store: Arc<DynObjectStore>, id: StorageId) -> Self {
-        Self { object_store, id }
+        Self {
+            object_store,
+            id,
+            bloom_filter_tables: Default::default(),
+        }
+    }
+
+    /// Write parquet bloom filters for the tag columns of `tables`.
+    ///
+    /// This is a hint for writers constructing an [`IoxMetadata`] for an
+    /// upload, see [`Self::bloom_filters_enabled()`].
+    pub fn with_bloom_filter_tables(self, tables: BloomFilterTables) -> Self {
+        Self {
+            bloom_filter_tables: Arc::new(tables),
+            ..self
+        }
+    }
+
+    /// Returns true if bloom filters should be written for the tag columns
+    /// of `table_name`, the value [`IoxMetadata::bloom_filters`] should be set
+    /// to.
+    pub fn bloom_filters_enabled(&self, table_name: &str) -> bool {
+        self.bloom_filter_tables.contains(table_name)
     }
 
     /// Get underlying object store.
@@ -299,6 +374,7 @@ impl ParquetStorage {
                 size: file_size,
                 e_tag: None,
             },
+            object_store: Arc::clone(&self.object_store),
         }
     }
 }
@@ -335,6 +411,26 @@ mod tests {
     use iox_time::Time;
     use std::collections::HashMap;
 
+    #[test]
+    fn test_bloom_filter_tables() {
+        let store = ParquetStorage::new(
+            Arc::new(object_store::memory::InMemory::default()),
+            StorageId::from("iox"),
+        );
+        assert!(!store.bloom_filters_enabled("bananas"));
+
+        let tables = BloomFilterTables::from_iter(Vec::<String>::new());
+        assert_eq!(tables, BloomFilterTables::None);
+
+        let store = store.with_bloom_filter_tables(BloomFilterTables::from_iter(["bananas"]));
+        assert!(store.bloom_filters_enabled("bananas"));
+        assert!(!store.bloom_filters_enabled("platanos"));
+
+        let store = store.with_bloom_filter_tables(BloomFilterTables::from_iter(["bananas", "*"]));
+        assert!(store.bloom_filters_enabled("bananas"));
+        assert!(store.bloom_filters_enabled("platanos"));
+    }
+
     #[tokio::test]
     async fn test_upload_metadata() {
         let object_store: Arc<DynObjectStore> = Arc::new(object_store::memory::InMemory::default());
@@ -608,6 +704,7 @@ mod tests {
                 compaction_level: CompactionLevel::FileNonOverlapped,
                 sort_key: None,
                 max_l0_created_at: Time::from_timestamp_nanos(42),
+                bloom_filters: false,
             },
         )
     }
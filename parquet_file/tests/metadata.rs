@@ -62,6 +62,7 @@ async fn test_decoded_iox_metadata() {
         compaction_level: CompactionLevel::FileNonOverlapped,
         sort_key: None,
         max_l0_created_at: Time::from_timestamp_nanos(42),
+        bloom_filters: false,
     };
 
     let mut schema_builder = SchemaBuilder::new();
@@ -203,6 +204,7 @@ async fn test_empty_parquet_file_panic() {
         compaction_level: CompactionLevel::FileNonOverlapped,
         sort_key: None,
         max_l0_created_at: Time::from_timestamp_nanos(42),
+        bloom_filters: false,
     };
 
     let batch = RecordBatch::try_from_iter(data).unwrap();
@@ -295,6 +297,7 @@ async fn test_decoded_many_columns_with_null_cols_iox_metadata() {
         compaction_level: CompactionLevel::FileNonOverlapped,
         sort_key: Some(sort_key),
         max_l0_created_at: Time::from_timestamp_nanos(42),
+        bloom_filters: false,
     };
 
     let mut schema_builder = SchemaBuilder::new();
@@ -384,6 +387,7 @@ async fn test_derive_parquet_file_params() {
         compaction_level: CompactionLevel::FileNonOverlapped,
         sort_key: None,
         max_l0_created_at: Time::from_timestamp_nanos(1234),
+        bloom_filters: false,
     };
 
     // Build a schema that contains the IOx metadata, ensuring it is correctly
@@ -1,8 +1,19 @@
 use crate::delete_expr::{df_to_expr, expr_to_df};
+use arrow::{
+    array::BooleanArray, compute::filter_record_batch, datatypes::SchemaRef,
+    record_batch::RecordBatch,
+};
 use chrono::DateTime;
-use data_types::{DeleteExpr, DeletePredicate, TimestampRange};
+use data_types::{
+    DeleteExpr, DeletePredicate, ParquetFile, TimestampRange, Tombstone, TombstoneId,
+};
 use datafusion::{
+    common::{cast::as_boolean_array, ToDFSchema},
+    error::DataFusionError,
+    execution::context::ExecutionProps,
     logical_expr::Operator,
+    optimizer::simplify_expressions::{ExprSimplifier, SimplifyContext},
+    physical_expr::create_physical_expr,
     prelude::{binary_expr, lit, Column, Expr},
 };
 use snafu::Snafu;
@@ -11,6 +22,7 @@ use sqlparser::{
     dialect::GenericDialect,
     parser::Parser,
 };
+use std::sync::Arc;
 
 /// Parse Delete Predicates
 /// Parse Error
@@ -51,6 +63,141 @@ impl From<DeletePredicate> for crate::Predicate {
     }
 }
 
+/// Returns a DataFusion [`Expr`] that evaluates to true for all rows that are
+/// NOT deleted by `pred`, or [`None`] if `pred` can never match a row with the
+/// given `schema`.
+///
+/// A row for which the delete predicate evaluates to NULL is retained - this
+/// is the case for rows that have no value for a column referenced by the
+/// predicate. Likewise a predicate that references a column not present in
+/// `schema` deletes nothing.
+pub fn delete_predicate_retain_expr(pred: &DeletePredicate, schema: &SchemaRef) -> Option<Expr> {
+    if pred
+        .exprs
+        .iter()
+        .any(|e| schema.column_with_name(&e.column).is_none())
+    {
+        return None;
+    }
+
+    crate::Predicate::from(pred.clone())
+        .filter_expr()
+        .map(|expr| expr.is_not_true())
+}
+
+/// Evaluate `pred` against `batch`, returning a mask that is true for each row
+/// NOT deleted by `pred`.
+///
+/// Returns [`None`] if `pred` matches no rows in `batch`.
+pub fn delete_predicate_retain_mask(
+    pred: &DeletePredicate,
+    batch: &RecordBatch,
+) -> Result<Option<BooleanArray>, DataFusionError> {
+    let schema = batch.schema();
+    let expr = match delete_predicate_retain_expr(pred, &schema) {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+
+    // tag columns may be dictionary encoded, so coerce the literals to the column types
+    let props = ExecutionProps::new();
+    let df_schema = Arc::clone(&schema).to_dfschema_ref()?;
+    let simplifier =
+        ExprSimplifier::new(SimplifyContext::new(&props).with_schema(Arc::clone(&df_schema)));
+    let expr = simplifier.coerce(expr, Arc::clone(&df_schema))?;
+
+    let expr = create_physical_expr(&expr, df_schema.as_ref(), schema.as_ref(), &props)?;
+    let mask = expr.evaluate(batch)?.into_array(batch.num_rows());
+    let mask = as_boolean_array(&mask)?;
+
+    if mask.true_count() == batch.num_rows() {
+        return Ok(None);
+    }
+
+    Ok(Some(mask.clone()))
+}
+
+/// Return a copy of `batch` with all the rows matched by `pred` removed.
+pub fn filter_deleted_rows(
+    pred: &DeletePredicate,
+    batch: &RecordBatch,
+) -> Result<RecordBatch, DataFusionError> {
+    match delete_predicate_retain_mask(pred, batch)? {
+        Some(mask) => filter_record_batch(batch, &mask).map_err(DataFusionError::ArrowError),
+        None => Ok(batch.clone()),
+    }
+}
+
+/// Error parsing the predicate of a [`Tombstone`].
+#[derive(Debug, Snafu)]
+#[snafu(display("Cannot parse the predicate of tombstone {}: {}", tombstone_id, source))]
+pub struct TombstoneError {
+    tombstone_id: TombstoneId,
+    source: Error,
+}
+
+/// The tombstones of a table, together with their parsed predicates.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TombstonePredicates {
+    tombstones: Vec<(Tombstone, Arc<DeletePredicate>)>,
+}
+
+impl TombstonePredicates {
+    /// Parse the predicates of `tombstones`.
+    ///
+    /// Fails if any predicate cannot be parsed. Predicates are validated when a tombstone is
+    /// created, so such a tombstone is never silently ignored (resurrecting deleted data) nor
+    /// applied to its whole time range (hiding data that was not deleted).
+    pub fn try_new(tombstones: Vec<Tombstone>) -> Result<Self, TombstoneError> {
+        let tombstones = tombstones
+            .into_iter()
+            .map(|t| {
+                let predicate = parse_delete_predicate(
+                    &t.min_time.get().to_string(),
+                    &t.max_time.get().to_string(),
+                    &t.serialized_predicate,
+                )
+                .map_err(|source| TombstoneError {
+                    tombstone_id: t.id,
+                    source,
+                })?;
+                Ok((t, Arc::new(predicate)))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { tombstones })
+    }
+
+    /// Delete predicates that must be applied to the rows of `file`.
+    pub fn predicates_for(&self, file: &ParquetFile) -> Vec<Arc<DeletePredicate>> {
+        self.tombstones
+            .iter()
+            .filter(|(t, _)| t.applies_to(file))
+            .map(|(_, p)| Arc::clone(p))
+            .collect()
+    }
+
+    /// Number of tombstones.
+    pub fn len(&self) -> usize {
+        self.tombstones.len()
+    }
+
+    /// Returns true if there are no tombstones.
+    pub fn is_empty(&self) -> bool {
+        self.tombstones.is_empty()
+    }
+
+    /// Estimate the memory consumption of this object and its contents
+    pub fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self
+                .tombstones
+                .iter()
+                .map(|(t, p)| t.size() + std::mem::size_of::<Arc<DeletePredicate>>() + p.size())
+                .sum::<usize>()
+    }
+}
+
 /// Parse and convert the delete grpc API into ParseDeletePredicate to send to server
 pub fn parse_delete_predicate(
     start_time: &str,
@@ -402,6 +549,54 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_filter_deleted_rows() {
+        use arrow::array::{ArrayRef, Int64Array, StringArray, TimestampNanosecondArray};
+
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "host",
+                Arc::new(StringArray::from(vec![
+                    Some("a"),
+                    Some("b"),
+                    None,
+                    Some("a"),
+                ])) as ArrayRef,
+            ),
+            (
+                "v",
+                Arc::new(Int64Array::from(vec![1, 2, 3, 4])) as ArrayRef,
+            ),
+            (
+                "time",
+                Arc::new(TimestampNanosecondArray::from(vec![10, 20, 30, 40])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+
+        // Delete host=a in [0, 40) - the last row is outside the range, and
+        // the row with a NULL host is retained.
+        let pred = parse_delete_predicate("0", "40", "host=a").unwrap();
+        let got = filter_deleted_rows(&pred, &batch).unwrap();
+        let v = got
+            .column_by_name("v")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(v.values(), &[2, 3, 4]);
+
+        // A predicate on a column that does not exist deletes nothing.
+        let pred = parse_delete_predicate("0", "100", "region=west").unwrap();
+        let got = filter_deleted_rows(&pred, &batch).unwrap();
+        assert_eq!(got.num_rows(), 4);
+
+        // An empty predicate deletes everything in the time range.
+        let pred = parse_delete_predicate("0", "100", "").unwrap();
+        let got = filter_deleted_rows(&pred, &batch).unwrap();
+        assert_eq!(got.num_rows(), 0);
+    }
+
     #[test]
     fn test_full_delete_pred() {
         let start = r#"1970-01-01T00:00:00Z"#; // this is nano 0
@@ -439,4 +634,41 @@ mod tests {
         let result = parse_delete_predicate(start, stop, pred);
         assert!(result.is_err());
     }
+
+    fn tombstone(id: i64, serialized_predicate: &str) -> Tombstone {
+        Tombstone {
+            id: TombstoneId::new(id),
+            table_id: data_types::TableId::new(1),
+            min_time: data_types::Timestamp::new(100),
+            max_time: data_types::Timestamp::new(200),
+            serialized_predicate: serialized_predicate.to_string(),
+            created_at: data_types::Timestamp::new(300),
+        }
+    }
+
+    #[test]
+    fn test_tombstone_predicates() {
+        let tombstones =
+            TombstonePredicates::try_new(vec![tombstone(1, "cost != 100"), tombstone(2, "")])
+                .unwrap();
+        assert_eq!(tombstones.len(), 2);
+        assert_eq!(
+            tombstones.tombstones[0].1.as_ref(),
+            &parse_delete_predicate("100", "200", "cost != 100").unwrap()
+        );
+        assert!(tombstones.tombstones[1].1.exprs.is_empty());
+    }
+
+    #[test]
+    fn test_tombstone_predicates_invalid_pred() {
+        let err = TombstonePredicates::try_new(vec![
+            tombstone(1, "cost != 100"),
+            tombstone(2, "cost > 100"),
+        ])
+        .unwrap_err();
+        assert_eq!(err.tombstone_id, TombstoneId::new(2));
+        assert!(err
+            .to_string()
+            .starts_with("Cannot parse the predicate of tombstone 2"));
+    }
 }
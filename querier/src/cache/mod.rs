@@ -9,8 +9,13 @@ use std::sync::Arc;
 use tokio::runtime::Handle;
 
 use self::{
-    namespace::NamespaceCache, object_store::ObjectStoreCache, parquet_file::ParquetFileCache,
-    partition::PartitionCache, projected_schema::ProjectedSchemaCache, ram::RamSize,
+    namespace::NamespaceCache,
+    object_store::{DiskCacheConfig, ObjectStoreCache},
+    parquet_file::ParquetFileCache,
+    partition::PartitionCache,
+    projected_schema::ProjectedSchemaCache,
+    ram::RamSize,
+    tombstone::TombstoneCache,
 };
 
 pub mod namespace;
@@ -19,6 +24,7 @@ pub mod parquet_file;
 pub mod partition;
 pub mod projected_schema;
 mod ram;
+pub mod tombstone;
 
 #[cfg(test)]
 pub(crate) mod test_util;
@@ -38,6 +44,9 @@ pub struct CatalogCache {
     /// Parquet file cache
     parquet_file_cache: ParquetFileCache,
 
+    /// Tombstone cache
+    tombstone_cache: TombstoneCache,
+
     /// Projected schema cache.
     projected_schema_cache: ProjectedSchemaCache,
 
@@ -53,6 +62,7 @@ pub struct CatalogCache {
 
 impl CatalogCache {
     /// Create empty cache.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         catalog: Arc<dyn Catalog>,
         time_provider: Arc<dyn TimeProvider>,
@@ -60,6 +70,7 @@ impl CatalogCache {
         object_store: Arc<dyn ObjectStore>,
         ram_pool_metadata_bytes: usize,
         ram_pool_data_bytes: usize,
+        disk_cache: Option<DiskCacheConfig>,
         handle: &Handle,
     ) -> Self {
         Self::new_internal(
@@ -69,6 +80,7 @@ impl CatalogCache {
             object_store,
             ram_pool_metadata_bytes,
             ram_pool_data_bytes,
+            disk_cache,
             handle,
             false,
         )
@@ -91,6 +103,7 @@ impl CatalogCache {
             object_store,
             usize::MAX,
             usize::MAX,
+            None,
             handle,
             true,
         )
@@ -104,6 +117,7 @@ impl CatalogCache {
         object_store: Arc<dyn ObjectStore>,
         ram_pool_metadata_bytes: usize,
         ram_pool_data_bytes: usize,
+        disk_cache: Option<DiskCacheConfig>,
         handle: &Handle,
         testing: bool,
     ) -> Self {
@@ -147,6 +161,14 @@ impl CatalogCache {
             Arc::clone(&ram_pool_metadata),
             testing,
         );
+        let tombstone_cache = TombstoneCache::new(
+            Arc::clone(&catalog),
+            backoff_config.clone(),
+            Arc::clone(&time_provider),
+            &metric_registry,
+            Arc::clone(&ram_pool_metadata),
+            testing,
+        );
         let projected_schema_cache = ProjectedSchemaCache::new(
             Arc::clone(&time_provider),
             &metric_registry,
@@ -159,6 +181,7 @@ impl CatalogCache {
             Arc::clone(&time_provider),
             &metric_registry,
             Arc::clone(&ram_pool_data),
+            disk_cache,
             handle,
             testing,
         );
@@ -168,6 +191,7 @@ impl CatalogCache {
             partition_cache,
             namespace_cache,
             parquet_file_cache,
+            tombstone_cache,
             projected_schema_cache,
             object_store_cache,
             metric_registry,
@@ -205,6 +229,11 @@ impl CatalogCache {
         &self.parquet_file_cache
     }
 
+    /// Tombstone cache.
+    pub(crate) fn tombstone(&self) -> &TombstoneCache {
+        &self.tombstone_cache
+    }
+
     /// Projected schema cache.
     pub(crate) fn projected_schema(&self) -> &ProjectedSchemaCache {
         &self.projected_schema_cache
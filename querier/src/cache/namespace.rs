@@ -15,7 +15,7 @@ use cache_system::{
 };
 use data_types::{
     partition_template::TablePartitionTemplateOverride, Column, ColumnId, Namespace, NamespaceId,
-    Table, TableId,
+    NamespaceQueryLimits, Table, TableId,
 };
 use iox_catalog::interface::{Catalog, SoftDeletedRows};
 use iox_time::TimeProvider;
@@ -307,6 +307,7 @@ impl CachedTable {
 pub struct CachedNamespace {
     pub id: NamespaceId,
     pub retention_period: Option<Duration>,
+    pub query_limits: NamespaceQueryLimits,
     pub tables: HashMap<Arc<str>, Arc<CachedTable>>,
 }
 
@@ -339,6 +340,7 @@ impl CachedNamespace {
         Self {
             id: namespace.id,
             retention_period,
+            query_limits: namespace.query_limits,
             tables,
         }
     }
@@ -418,6 +420,7 @@ mod tests {
         let expected_ns_1 = CachedNamespace {
             id: ns1.namespace.id,
             retention_period,
+            query_limits: ns1.namespace.query_limits,
             tables: HashMap::from([
                 (
                     Arc::from("table1"),
@@ -482,6 +485,7 @@ mod tests {
         let expected_ns_2 = CachedNamespace {
             id: ns2.namespace.id,
             retention_period,
+            query_limits: ns2.namespace.query_limits,
             tables: HashMap::from([(
                 Arc::from("table1"),
                 Arc::new(CachedTable {
@@ -0,0 +1,934 @@
+//! Local-disk tier of the [object store cache](super::ObjectStoreCache).
+//!
+//! Objects are stored as one file per object underneath the `objects` subdirectory of the cache
+//! directory, using the object store path as the relative file path. Each file starts with a small
+//! header:
+//!
+//! ```text
+//! magic (4 bytes) | version (1) | CRC32 of the payload (4) | payload size (8)
+//!   | last modified in nanoseconds (8) | e-tag length (4, u32::MAX for none) | e-tag | payload
+//! ```
+//!
+//! All integers are big-endian. The payload checksum is verified on every read. Files are first
+//! written to a temporary directory and then moved into place, so a crash never leaves a partially
+//! written file behind.
+//!
+//! The cache directory survives restarts: on startup it is re-indexed in the background and the
+//! existing files are added to the LRU accounting again. A marker file identifies the directory as
+//! a cache directory, so a misconfigured directory holding other data is never cleaned up.
+use std::{
+    io::Read,
+    ops::{Add, Sub},
+    path::{Component, PathBuf},
+    sync::Arc,
+};
+
+use backoff::BackoffConfig;
+use bytes::Bytes;
+use cache_system::{
+    backend::policy::{
+        lru::{LruPolicy, ResourcePool},
+        remove_if::{RemoveIfHandle, RemoveIfPolicy},
+        ChangeRequest, PolicyBackend, Subscriber,
+    },
+    cache::{driver::CacheDriver, metrics::CacheWithMetrics, Cache},
+    loader::{metrics::MetricsLoader, FunctionLoader},
+    resource_consumption::{FunctionEstimator, Resource},
+};
+use chrono::{TimeZone, Utc};
+use iox_time::{Time, TimeProvider};
+use object_store::{path::Path, ObjectMeta, ObjectStore};
+use observability_deps::tracing::{info, warn};
+use snafu::{ensure, ResultExt, Snafu};
+use tokio::{io::AsyncWriteExt, runtime::Handle};
+use trace::span::Span;
+use uuid::Uuid;
+
+use super::{load_from_store, CachedRead};
+
+const CACHE_ID: &str = "object_store_disk";
+
+/// Magic bytes at the start of every cache file.
+const MAGIC: &[u8; 4] = b"IOXC";
+
+/// Version of the cache file format.
+const VERSION: u8 = 1;
+
+/// Length of the fixed-size part of the header.
+const FIXED_HEADER_LEN: usize = 4 + 1 + 4 + 8 + 8 + 4;
+
+/// Marker file identifying a directory as a disk cache directory.
+const MARKER_FILE: &str = "IOX_DISK_CACHE";
+
+/// Directory (within the cache directory) for the cached objects.
+const OBJECTS_DIR: &str = "objects";
+
+/// Directory (within the cache directory) for files that are being written.
+const TMP_DIR: &str = "tmp";
+
+#[derive(Debug, Snafu)]
+#[allow(missing_docs)]
+enum Error {
+    #[snafu(display("I/O error for {}: {}", path.display(), source))]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("invalid cache file header: {}", reason))]
+    InvalidHeader { reason: &'static str },
+
+    #[snafu(display("payload size mismatch: expected {} bytes, got {}", expected, actual))]
+    SizeMismatch { expected: u64, actual: u64 },
+
+    #[snafu(display("checksum mismatch: expected {:#010x}, got {:#010x}", expected, actual))]
+    ChecksumMismatch { expected: u32, actual: u32 },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Error opening the directory of the local-disk tier of the object store cache.
+#[derive(Debug, Snafu)]
+#[allow(missing_docs)]
+pub enum DiskCacheDirError {
+    #[snafu(display("cannot prepare disk cache directory {}: {}", path.display(), source))]
+    Prepare {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display(
+        "refusing to use {} as disk cache directory: it is not empty and has no {} marker file",
+        path.display(),
+        MARKER_FILE,
+    ))]
+    NotACacheDir { path: PathBuf },
+}
+
+/// Configuration of the local-disk tier of the object store cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskCacheConfig {
+    /// Directory that holds the cached objects.
+    dir: PathBuf,
+
+    /// Maximum number of bytes stored in `dir`.
+    size_bytes: u64,
+}
+
+impl DiskCacheConfig {
+    /// Use `dir` for the disk cache, storing at most `size_bytes`.
+    ///
+    /// The directory is created if it does not exist, and marked as a cache directory if it is
+    /// empty. Files from previous runs are reused. A non-empty directory that was not marked as a
+    /// cache directory is rejected, as the cache removes the files it does not recognise.
+    pub fn open(dir: PathBuf, size_bytes: u64) -> Result<Self, DiskCacheDirError> {
+        std::fs::create_dir_all(&dir).context(PrepareSnafu { path: &dir })?;
+
+        let marker = dir.join(MARKER_FILE);
+        if !marker.exists() {
+            let is_empty = std::fs::read_dir(&dir)
+                .context(PrepareSnafu { path: &dir })?
+                .next()
+                .is_none();
+            ensure!(is_empty, NotACacheDirSnafu { path: &dir });
+
+            std::fs::write(&marker, b"").context(PrepareSnafu { path: &marker })?;
+        }
+
+        Ok(Self { dir, size_bytes })
+    }
+}
+
+/// Disk space used by the cache, in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DiskSize(pub u64);
+
+impl Resource for DiskSize {
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    fn unit() -> &'static str {
+        "bytes"
+    }
+}
+
+impl From<DiskSize> for u64 {
+    fn from(s: DiskSize) -> Self {
+        s.0
+    }
+}
+
+impl Add for DiskSize {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0.checked_add(rhs.0).expect("overflow"))
+    }
+}
+
+impl Sub for DiskSize {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0.checked_sub(rhs.0).expect("underflow"))
+    }
+}
+
+/// Result of looking up an object in the disk tier.
+#[derive(Debug, Clone)]
+enum DiskRead {
+    /// The object does not exist in the object store.
+    NotFound,
+
+    /// The object is stored on disk.
+    Stored(Arc<StoredObject>),
+
+    /// The object was fetched but could not be written to disk.
+    ///
+    /// The data is handed to the caller, who removes this entry again right away.
+    NotStored(CachedRead),
+}
+
+/// Index entry for an object stored on disk.
+#[derive(Debug, PartialEq, Eq)]
+struct StoredObject {
+    /// Size of the file, including the header.
+    file_size: u64,
+
+    /// CRC32 of the payload.
+    checksum: u32,
+}
+
+type CacheT = Arc<
+    dyn Cache<
+        K = Path,
+        V = DiskRead,
+        GetExtra = ((), Option<Span>),
+        PeekExtra = ((), Option<Span>),
+    >,
+>;
+
+/// Bounded local-disk cache for immutable objects.
+#[derive(Debug)]
+pub(super) struct DiskCache {
+    cache: CacheT,
+    remove_if_handle: RemoveIfHandle<Path, DiskRead>,
+    dir: Arc<CacheDir>,
+    backoff_config: BackoffConfig,
+    object_store: Arc<dyn ObjectStore>,
+}
+
+impl DiskCache {
+    /// Create new disk cache and start re-indexing the files left by previous runs in the
+    /// background.
+    pub(super) fn new(
+        config: DiskCacheConfig,
+        backoff_config: BackoffConfig,
+        object_store: Arc<dyn ObjectStore>,
+        time_provider: Arc<dyn TimeProvider>,
+        metric_registry: &Arc<metric::Registry>,
+        handle: &Handle,
+        testing: bool,
+    ) -> Self {
+        let dir = Arc::new(CacheDir {
+            root: config.dir.clone(),
+        });
+
+        let dir_captured = Arc::clone(&dir);
+        let backoff_config_captured = backoff_config.clone();
+        let object_store_captured = Arc::clone(&object_store);
+        let loader = FunctionLoader::new(move |key: Path, _extra: ()| {
+            let dir = Arc::clone(&dir_captured);
+            let backoff_config = backoff_config_captured.clone();
+            let object_store = Arc::clone(&object_store_captured);
+
+            async move {
+                let Some(read) =
+                    load_from_store(&backoff_config, object_store.as_ref(), &key).await
+                else {
+                    return DiskRead::NotFound;
+                };
+
+                match dir.write(&key, &read).await {
+                    Ok(stored) => DiskRead::Stored(Arc::new(stored)),
+                    Err(e) => {
+                        warn!(location=%key, error=%e, "cannot write object to disk cache");
+                        DiskRead::NotStored(read)
+                    }
+                }
+            }
+        });
+        let loader = Arc::new(MetricsLoader::new(
+            loader,
+            CACHE_ID,
+            Arc::clone(&time_provider),
+            metric_registry,
+            testing,
+        ));
+
+        let pool = Arc::new(ResourcePool::new(
+            "disk_data",
+            DiskSize(config.size_bytes),
+            Arc::clone(metric_registry),
+            handle,
+        ));
+
+        let mut backend = PolicyBackend::hashmap_backed(Arc::clone(&time_provider));
+        let (policy_constructor, remove_if_handle) =
+            RemoveIfPolicy::create_constructor_and_handle(CACHE_ID, metric_registry);
+        backend.add_policy(policy_constructor);
+        backend.add_policy(LruPolicy::new(
+            pool,
+            CACHE_ID,
+            Arc::new(FunctionEstimator::new(|_k: &Path, v: &DiskRead| match v {
+                DiskRead::Stored(stored) => DiskSize(stored.file_size),
+                DiskRead::NotFound | DiskRead::NotStored(_) => DiskSize(0),
+            })),
+        ));
+        let dir_captured = Arc::clone(&dir);
+        let handle_captured = handle.clone();
+        backend.add_policy(move |_callback_handle| FileRemover {
+            dir: dir_captured,
+            handle: handle_captured,
+        });
+
+        let cache = CacheDriver::new(loader, backend);
+        let cache: CacheT = Arc::new(CacheWithMetrics::new(
+            cache,
+            CACHE_ID,
+            time_provider,
+            metric_registry,
+        ));
+
+        handle.spawn(reindex(Arc::clone(&dir), Arc::clone(&cache)));
+
+        Self {
+            cache,
+            remove_if_handle,
+            dir,
+            backoff_config,
+            object_store,
+        }
+    }
+
+    /// Get object, either from disk or from the underlying object store.
+    ///
+    /// Returns `None` if the object does not exist.
+    pub(super) async fn get(&self, location: &Path) -> Option<CachedRead> {
+        match self.cache.get(location.clone(), ((), None)).await {
+            DiskRead::NotFound => None,
+            DiskRead::Stored(stored) => match self.dir.read(location, &stored).await {
+                Ok(read) => Some(read),
+                Err(e) => {
+                    warn!(
+                        %location,
+                        error=%e,
+                        "cannot read object from disk cache, falling back to object store",
+                    );
+                    // Only drop the entry that failed, it may have been reloaded in the meantime.
+                    self.remove_if_handle.remove_if(
+                        location,
+                        |v| matches!(v, DiskRead::Stored(v) if Arc::ptr_eq(&v, &stored)),
+                    );
+                    load_from_store(&self.backoff_config, self.object_store.as_ref(), location)
+                        .await
+                }
+            },
+            DiskRead::NotStored(read) => {
+                self.remove_if_handle
+                    .remove_if(location, |v| matches!(v, DiskRead::NotStored(_)));
+                Some(read)
+            }
+        }
+    }
+}
+
+/// Re-add files from previous runs to the cache.
+async fn reindex(dir: Arc<CacheDir>, cache: CacheT) {
+    let dir_captured = Arc::clone(&dir);
+    let entries = match tokio::task::spawn_blocking(move || dir_captured.scan()).await {
+        Ok(Ok(entries)) => entries,
+        Ok(Err(e)) => {
+            warn!(error=%e, "cannot re-index disk cache");
+            return;
+        }
+        Err(e) => {
+            warn!(error=%e, "disk cache re-index task failed");
+            return;
+        }
+    };
+
+    let n_files = entries.len();
+    for (location, stored) in entries {
+        cache
+            .set(location, DiskRead::Stored(Arc::new(stored)))
+            .await;
+    }
+
+    info!(root=%dir.root.display(), n_files, "re-indexed disk cache");
+}
+
+/// Removes files from disk once they are removed from the cache index.
+///
+/// The files are removed in the background, so the cache backend is not blocked on disk I/O. If
+/// the object is reloaded before its old file is removed, the new file may be removed instead,
+/// in which case reading it fails and the object is fetched from the object store again.
+#[derive(Debug)]
+struct FileRemover {
+    dir: Arc<CacheDir>,
+    handle: Handle,
+}
+
+impl Subscriber for FileRemover {
+    type K = Path;
+    type V = DiskRead;
+
+    fn remove(&mut self, k: &Path, _now: Time) -> Vec<ChangeRequest<'static, Path, DiskRead>> {
+        let path = self.dir.file_path(k);
+        self.handle.spawn(async move {
+            match tokio::fs::remove_file(&path).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    warn!(path=%path.display(), error=%e, "cannot remove file from disk cache");
+                }
+            }
+        });
+
+        vec![]
+    }
+}
+
+/// The directory that holds the cached files.
+#[derive(Debug)]
+struct CacheDir {
+    root: PathBuf,
+}
+
+impl CacheDir {
+    /// File that stores the object at `location`.
+    fn file_path(&self, location: &Path) -> PathBuf {
+        self.objects_dir().join(location.as_ref())
+    }
+
+    fn objects_dir(&self) -> PathBuf {
+        self.root.join(OBJECTS_DIR)
+    }
+
+    fn tmp_dir(&self) -> PathBuf {
+        self.root.join(TMP_DIR)
+    }
+
+    /// Write object to disk.
+    async fn write(&self, location: &Path, read: &CachedRead) -> Result<StoredObject> {
+        let checksum = crc32fast::hash(&read.bytes);
+        let header = encode_header(&read.meta, checksum, read.bytes.len() as u64);
+
+        let tmp_dir = self.tmp_dir();
+        tokio::fs::create_dir_all(&tmp_dir)
+            .await
+            .context(IoSnafu { path: &tmp_dir })?;
+        let tmp_path = tmp_dir.join(Uuid::new_v4().to_string());
+
+        let res = async {
+            let mut file = tokio::fs::File::create(&tmp_path)
+                .await
+                .context(IoSnafu { path: &tmp_path })?;
+            file.write_all(&header)
+                .await
+                .context(IoSnafu { path: &tmp_path })?;
+            file.write_all(&read.bytes)
+                .await
+                .context(IoSnafu { path: &tmp_path })?;
+            file.sync_data()
+                .await
+                .context(IoSnafu { path: &tmp_path })?;
+
+            let path = self.file_path(location);
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .context(IoSnafu { path: parent })?;
+            }
+            tokio::fs::rename(&tmp_path, &path)
+                .await
+                .context(IoSnafu { path: &path })
+        }
+        .await;
+
+        if res.is_err() {
+            // best effort cleanup
+            tokio::fs::remove_file(&tmp_path).await.ok();
+        }
+        res?;
+
+        Ok(StoredObject {
+            file_size: (header.len() + read.bytes.len()) as u64,
+            checksum,
+        })
+    }
+
+    /// Read object from disk, verifying its checksum.
+    async fn read(&self, location: &Path, stored: &StoredObject) -> Result<CachedRead> {
+        let path = self.file_path(location);
+        let data = Bytes::from(
+            tokio::fs::read(&path)
+                .await
+                .context(IoSnafu { path: &path })?,
+        );
+
+        let header = decode_header(&mut data.as_ref())?;
+        let payload = data.slice(header.len..);
+        ensure!(
+            payload.len() as u64 == header.size,
+            SizeMismatchSnafu {
+                expected: header.size,
+                actual: payload.len() as u64,
+            }
+        );
+
+        let actual = crc32fast::hash(&payload);
+        for expected in [header.checksum, stored.checksum] {
+            ensure!(
+                actual == expected,
+                ChecksumMismatchSnafu { expected, actual }
+            );
+        }
+
+        Ok(CachedRead {
+            meta: ObjectMeta {
+                location: location.clone(),
+                last_modified: Utc.timestamp_nanos(header.last_modified),
+                size: payload.len(),
+                e_tag: header.e_tag,
+            },
+            bytes: payload,
+        })
+    }
+
+    /// Find all complete cache files left by previous runs.
+    ///
+    /// Partially written and unreadable files are removed. Only the objects and temporary
+    /// directories are touched.
+    fn scan(&self) -> Result<Vec<(Path, StoredObject)>> {
+        // leftovers from interrupted writes
+        let tmp_dir = self.tmp_dir();
+        match std::fs::remove_dir_all(&tmp_dir) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e).context(IoSnafu { path: tmp_dir }),
+        }
+
+        let objects_dir = self.objects_dir();
+        std::fs::create_dir_all(&objects_dir).context(IoSnafu { path: &objects_dir })?;
+
+        let mut entries = vec![];
+        let mut dirs = vec![objects_dir];
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(&dir).context(IoSnafu { path: &dir })? {
+                let entry = entry.context(IoSnafu { path: &dir })?;
+                let path = entry.path();
+                let file_type = entry.file_type().context(IoSnafu { path: &path })?;
+
+                if file_type.is_dir() {
+                    dirs.push(path);
+                    continue;
+                }
+
+                match self.scan_file(&path) {
+                    Ok(v) => entries.push(v),
+                    Err(e) => {
+                        warn!(path=%path.display(), error=%e, "removing invalid disk cache file");
+                        std::fs::remove_file(&path).ok();
+                    }
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// Read index entry for a single file without reading its payload.
+    fn scan_file(&self, path: &std::path::Path) -> Result<(Path, StoredObject)> {
+        let location = path
+            .strip_prefix(self.objects_dir())
+            .ok()
+            .and_then(|rel| {
+                rel.components()
+                    .map(|c| match c {
+                        Component::Normal(s) => s.to_str(),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()
+            })
+            .and_then(|parts| Path::parse(parts.join("/")).ok())
+            .ok_or(Error::InvalidHeader {
+                reason: "file path is not a valid object store path",
+            })?;
+
+        let mut file = std::fs::File::open(path).context(IoSnafu { path })?;
+        let file_size = file.metadata().context(IoSnafu { path })?.len();
+
+        let mut fixed = [0u8; FIXED_HEADER_LEN];
+        file.read_exact(&mut fixed).context(IoSnafu { path })?;
+        let e_tag_len = u32::from_be_bytes(fixed[FIXED_HEADER_LEN - 4..].try_into().unwrap());
+        let mut e_tag = vec![
+            0u8;
+            if e_tag_len == u32::MAX {
+                0
+            } else {
+                e_tag_len as usize
+            }
+        ];
+        file.read_exact(&mut e_tag).context(IoSnafu { path })?;
+
+        let mut buf = fixed.to_vec();
+        buf.extend(e_tag);
+        let header = decode_header(&mut buf.as_slice())?;
+
+        let actual = file_size - header.len as u64;
+        ensure!(
+            actual == header.size,
+            SizeMismatchSnafu {
+                expected: header.size,
+                actual,
+            }
+        );
+
+        Ok((
+            location,
+            StoredObject {
+                file_size,
+                checksum: header.checksum,
+            },
+        ))
+    }
+}
+
+/// Decoded cache file header.
+#[derive(Debug, PartialEq, Eq)]
+struct Header {
+    /// Length of the header in bytes.
+    len: usize,
+    checksum: u32,
+    size: u64,
+    last_modified: i64,
+    e_tag: Option<String>,
+}
+
+fn encode_header(meta: &ObjectMeta, checksum: u32, size: u64) -> Vec<u8> {
+    let e_tag = meta.e_tag.as_deref();
+    let mut buf = Vec::with_capacity(FIXED_HEADER_LEN + e_tag.map(|e| e.len()).unwrap_or_default());
+
+    buf.extend_from_slice(MAGIC);
+    buf.push(VERSION);
+    buf.extend_from_slice(&checksum.to_be_bytes());
+    buf.extend_from_slice(&size.to_be_bytes());
+    buf.extend_from_slice(
+        &meta
+            .last_modified
+            .timestamp_nanos_opt()
+            .unwrap_or_default()
+            .to_be_bytes(),
+    );
+    match e_tag {
+        Some(e_tag) => {
+            buf.extend_from_slice(&(e_tag.len() as u32).to_be_bytes());
+            buf.extend_from_slice(e_tag.as_bytes());
+        }
+        None => buf.extend_from_slice(&u32::MAX.to_be_bytes()),
+    }
+
+    buf
+}
+
+fn decode_header(buf: &mut &[u8]) -> Result<Header> {
+    let start_len = buf.len();
+
+    ensure!(
+        take(buf, 4)? == MAGIC,
+        InvalidHeaderSnafu {
+            reason: "bad magic bytes"
+        }
+    );
+    ensure!(
+        take(buf, 1)?[0] == VERSION,
+        InvalidHeaderSnafu {
+            reason: "unsupported version"
+        }
+    );
+    let checksum = u32::from_be_bytes(take(buf, 4)?.try_into().unwrap());
+    let size = u64::from_be_bytes(take(buf, 8)?.try_into().unwrap());
+    let last_modified = i64::from_be_bytes(take(buf, 8)?.try_into().unwrap());
+    let e_tag = match u32::from_be_bytes(take(buf, 4)?.try_into().unwrap()) {
+        u32::MAX => None,
+        len => Some(
+            String::from_utf8(take(buf, len as usize)?.to_vec()).map_err(|_| {
+                Error::InvalidHeader {
+                    reason: "e-tag is not valid UTF-8",
+                }
+            })?,
+        ),
+    };
+
+    Ok(Header {
+        len: start_len - buf.len(),
+        checksum,
+        size,
+        last_modified,
+        e_tag,
+    })
+}
+
+/// Take `n` bytes from the front of `buf`.
+fn take<'a>(buf: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+    ensure!(
+        buf.len() >= n,
+        InvalidHeaderSnafu {
+            reason: "truncated header"
+        }
+    );
+    let (head, tail) = buf.split_at(n);
+    *buf = tail;
+    Ok(head)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use iox_time::SystemProvider;
+    use metric::{Attributes, DurationHistogram, Metric, U64Counter};
+    use object_store::memory::InMemory;
+    use test_helpers::timeout::FutureTimeout;
+
+    use super::*;
+
+    #[test]
+    fn test_header_roundtrip() {
+        for e_tag in [None, Some(String::new()), Some(String::from("foo"))] {
+            let meta = ObjectMeta {
+                location: Path::from("foo"),
+                last_modified: Utc.timestamp_nanos(1_234_567_890),
+                size: 42,
+                e_tag: e_tag.clone(),
+            };
+            let encoded = encode_header(&meta, 0xdeadbeef, 42);
+
+            let mut buf = encoded.as_slice();
+            let header = decode_header(&mut buf).unwrap();
+            assert!(buf.is_empty());
+            assert_eq!(
+                header,
+                Header {
+                    len: encoded.len(),
+                    checksum: 0xdeadbeef,
+                    size: 42,
+                    last_modified: 1_234_567_890,
+                    e_tag,
+                }
+            );
+
+            // truncated headers are rejected
+            let mut buf = &encoded[..encoded.len() - 1];
+            assert!(decode_header(&mut buf).is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_persist_and_reindex() {
+        let dir = test_helpers::tmp_dir().unwrap();
+        let inner = Arc::new(InMemory::new());
+        let path = Path::from("ns/table/file.parquet");
+        let bytes = Bytes::from_static(b"parquet data");
+        inner.put(&path, bytes.clone()).await.unwrap();
+
+        // first "run" fetches from the object store and writes to disk
+        let (cache, metric_registry) = make_cache(dir.path().into(), Arc::clone(&inner), 1024);
+        assert_eq!(cache.get(&path).await.unwrap().bytes, bytes);
+        assert!(cache.get(&Path::from("missing")).await.is_none());
+        assert_eq!(load_count(&metric_registry), 2);
+        assert!(dir.path().join("objects/ns/table/file.parquet").exists());
+        drop(cache);
+
+        // the object store is no longer needed after a restart
+        inner.delete(&path).await.unwrap();
+        let (cache, metric_registry) = make_cache(dir.path().into(), Arc::clone(&inner), 1024);
+        wait_for_reindex(&cache, &path).await;
+        let read = cache.get(&path).await.unwrap();
+        assert_eq!(read.bytes, bytes);
+        assert_eq!(read.meta.location, path);
+        assert_eq!(read.meta.size, bytes.len());
+        assert_eq!(load_count(&metric_registry), 0);
+    }
+
+    #[tokio::test]
+    async fn test_checksum_mismatch() {
+        let dir = test_helpers::tmp_dir().unwrap();
+        let inner = Arc::new(InMemory::new());
+        let path = Path::from("file.parquet");
+        let bytes = Bytes::from_static(b"parquet data");
+        inner.put(&path, bytes.clone()).await.unwrap();
+
+        let (cache, _metric_registry) = make_cache(dir.path().into(), Arc::clone(&inner), 1024);
+        assert_eq!(cache.get(&path).await.unwrap().bytes, bytes);
+
+        // corrupt the payload
+        let file_path = dir.path().join("objects/file.parquet");
+        let mut data = std::fs::read(&file_path).unwrap();
+        *data.last_mut().unwrap() ^= 0xff;
+        std::fs::write(&file_path, data).unwrap();
+
+        // served from the object store, the corrupt file is removed
+        assert_eq!(cache.get(&path).await.unwrap().bytes, bytes);
+        wait_until(|| !file_path.exists()).await;
+
+        // and written again on the next access
+        assert_eq!(cache.get(&path).await.unwrap().bytes, bytes);
+        assert!(file_path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_eviction_removes_files() {
+        let dir = test_helpers::tmp_dir().unwrap();
+        let inner = Arc::new(InMemory::new());
+        let path_1 = Path::from("file_1.parquet");
+        let path_2 = Path::from("file_2.parquet");
+        let bytes = Bytes::from(vec![0u8; 100]);
+        inner.put(&path_1, bytes.clone()).await.unwrap();
+        inner.put(&path_2, bytes.clone()).await.unwrap();
+
+        // room for a single file only
+        let (cache, metric_registry) = make_cache(dir.path().into(), Arc::clone(&inner), 150);
+        cache.get(&path_1).await.unwrap();
+        assert!(dir.path().join("objects/file_1.parquet").exists());
+
+        cache.get(&path_2).await.unwrap();
+        wait_until(|| !dir.path().join("objects/file_1.parquet").exists()).await;
+        assert!(dir.path().join("objects/file_2.parquet").exists());
+
+        let evicted = metric_registry
+            .get_instrument::<Metric<U64Counter>>("cache_lru_member_evicted")
+            .unwrap()
+            .get_observer(&Attributes::from(&[
+                ("pool", "disk_data"),
+                ("member", CACHE_ID),
+            ]))
+            .unwrap()
+            .fetch();
+        assert_eq!(evicted, 1);
+    }
+
+    #[tokio::test]
+    async fn test_reindex_removes_invalid_files() {
+        let dir = test_helpers::tmp_dir().unwrap();
+        std::fs::create_dir_all(dir.path().join(TMP_DIR)).unwrap();
+        std::fs::write(dir.path().join(TMP_DIR).join("partial"), b"IOXC").unwrap();
+        std::fs::create_dir_all(dir.path().join(OBJECTS_DIR)).unwrap();
+        std::fs::write(
+            dir.path().join(OBJECTS_DIR).join("garbage"),
+            b"not a cache file",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("other"), b"not a cache file").unwrap();
+
+        let cache_dir = CacheDir {
+            root: dir.path().into(),
+        };
+        assert!(cache_dir.scan().unwrap().is_empty());
+        assert!(!dir.path().join(TMP_DIR).exists());
+        assert!(!dir.path().join(OBJECTS_DIR).join("garbage").exists());
+
+        // files outside of the cache subdirectories are left alone
+        assert!(dir.path().join("other").exists());
+    }
+
+    #[test]
+    fn test_open_dir() {
+        let dir = test_helpers::tmp_dir().unwrap();
+
+        // a new directory is created and marked
+        let cache_dir = dir.path().join("cache");
+        DiskCacheConfig::open(cache_dir.clone(), 1024).unwrap();
+        assert!(cache_dir.join(MARKER_FILE).exists());
+
+        // a marked directory is reused
+        std::fs::create_dir_all(cache_dir.join(OBJECTS_DIR)).unwrap();
+        DiskCacheConfig::open(cache_dir, 1024).unwrap();
+
+        // an existing empty directory is marked
+        let empty_dir = dir.path().join("empty");
+        std::fs::create_dir_all(&empty_dir).unwrap();
+        DiskCacheConfig::open(empty_dir.clone(), 1024).unwrap();
+        assert!(empty_dir.join(MARKER_FILE).exists());
+    }
+
+    #[test]
+    fn test_open_dir_refuses_foreign_dir() {
+        let dir = test_helpers::tmp_dir().unwrap();
+        std::fs::write(dir.path().join("important"), b"not a cache file").unwrap();
+
+        let err = DiskCacheConfig::open(dir.path().into(), 1024).unwrap_err();
+        assert!(
+            matches!(err, DiskCacheDirError::NotACacheDir { .. }),
+            "unexpected error: {err}"
+        );
+        assert!(!dir.path().join(MARKER_FILE).exists());
+        assert!(dir.path().join("important").exists());
+    }
+
+    fn make_cache(
+        dir: PathBuf,
+        object_store: Arc<InMemory>,
+        size_bytes: u64,
+    ) -> (DiskCache, Arc<metric::Registry>) {
+        let metric_registry = Arc::new(metric::Registry::new());
+        let cache = DiskCache::new(
+            DiskCacheConfig::open(dir, size_bytes).unwrap(),
+            BackoffConfig::default(),
+            object_store,
+            Arc::new(SystemProvider::new()),
+            &metric_registry,
+            &Handle::current(),
+            true,
+        );
+        (cache, metric_registry)
+    }
+
+    async fn wait_for_reindex(cache: &DiskCache, location: &Path) {
+        async {
+            while cache
+                .cache
+                .peek(location.clone(), ((), None))
+                .await
+                .is_none()
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }
+        .with_timeout_panic(Duration::from_secs(5))
+        .await;
+    }
+
+    /// Eviction happens in the background, so wait for its effect.
+    async fn wait_until(f: impl Fn() -> bool) {
+        async {
+            while !f() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }
+        .with_timeout_panic(Duration::from_secs(5))
+        .await;
+    }
+
+    fn load_count(metric_registry: &metric::Registry) -> u64 {
+        metric_registry
+            .get_instrument::<Metric<DurationHistogram>>("cache_load_function_duration")
+            .unwrap()
+            .get_observer(&Attributes::from(&[("name", CACHE_ID)]))
+            .unwrap()
+            .fetch()
+            .sample_count()
+    }
+}
@@ -25,6 +25,11 @@ use trace::span::Span;
 
 use super::ram::RamSize;
 
+use self::disk::DiskCache;
+pub use self::disk::{DiskCacheConfig, DiskCacheDirError};
+
+mod disk;
+
 const CACHE_ID: &str = "object_store";
 
 #[derive(Debug, Clone)]
@@ -78,6 +83,20 @@ async fn read_from_store(
     Ok(Some(CachedRead { bytes, meta }))
 }
 
+/// Read object from the store, retrying all errors.
+async fn load_from_store(
+    backoff_config: &BackoffConfig,
+    store: &dyn ObjectStore,
+    path: &Path,
+) -> Option<CachedRead> {
+    Backoff::new(backoff_config)
+        .retry_all_errors::<_, _, _, ObjectStoreError>("get object from object store", || {
+            read_from_store(store, path)
+        })
+        .await
+        .expect("retry forever")
+}
+
 type CacheT = Arc<
     dyn Cache<
         K = Path,
@@ -102,32 +121,43 @@ pub struct ObjectStoreCache {
 
 impl ObjectStoreCache {
     /// Create new empty cache.
+    ///
+    /// If `disk_cache` is set, objects evicted from RAM are served from a local-disk tier before
+    /// they are fetched from the object store again.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         backoff_config: BackoffConfig,
         object_store: Arc<dyn ObjectStore>,
         time_provider: Arc<dyn TimeProvider>,
-        metric_registry: &metric::Registry,
+        metric_registry: &Arc<metric::Registry>,
         ram_pool: Arc<ResourcePool<RamSize>>,
+        disk_cache: Option<DiskCacheConfig>,
         handle: &Handle,
         testing: bool,
     ) -> Self {
+        let disk_cache = disk_cache.map(|config| {
+            Arc::new(DiskCache::new(
+                config,
+                backoff_config.clone(),
+                Arc::clone(&object_store),
+                Arc::clone(&time_provider),
+                metric_registry,
+                handle,
+                testing,
+            ))
+        });
+
         let object_store_captured = Arc::clone(&object_store);
         let loader = FunctionLoader::new(move |key: Path, _extra: ()| {
             let backoff_config = backoff_config.clone();
             let object_store = Arc::clone(&object_store_captured);
+            let disk_cache = disk_cache.as_ref().map(Arc::clone);
 
             async move {
-                Backoff::new(&backoff_config)
-                    .retry_all_errors::<_, _, _, ObjectStoreError>(
-                        "get object from object store",
-                        || async {
-                            let data = read_from_store(object_store.as_ref(), &key).await?;
-
-                            Ok(data)
-                        },
-                    )
-                    .await
-                    .expect("retry forever")
+                match disk_cache {
+                    Some(disk_cache) => disk_cache.get(&key).await,
+                    None => load_from_store(&backoff_config, object_store.as_ref(), &key).await,
+                }
             }
         });
         let loader = Arc::new(MetricsLoader::new(
@@ -387,7 +417,7 @@ mod tests {
         let bytes_4 = Bytes::from(b"data_baz" as &'static [u8]);
 
         // set up cache
-        let metric_registry = metric::Registry::new();
+        let metric_registry = Arc::new(metric::Registry::new());
         let time_provider = Arc::new(SystemProvider::new());
         let instrumented_store = ObjectStoreMetrics::new(
             Arc::clone(&inner) as _,
@@ -400,6 +430,7 @@ mod tests {
             time_provider,
             &metric_registry,
             test_ram_pool(),
+            None,
             &Handle::current(),
             true,
         );
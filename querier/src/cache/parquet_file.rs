@@ -12,11 +12,16 @@ use cache_system::{
     loader::{metrics::MetricsLoader, FunctionLoader},
     resource_consumption::FunctionEstimator,
 };
-use data_types::{ParquetFile, TableId};
+use data_types::{CompactionLevel, ParquetFile, ParquetFileId, TableId};
 use iox_catalog::interface::Catalog;
 use iox_time::TimeProvider;
 use snafu::{ResultExt, Snafu};
-use std::{collections::HashMap, mem, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+    sync::Arc,
+    time::Duration,
+};
 use trace::span::Span;
 use uuid::Uuid;
 
@@ -68,6 +73,39 @@ impl CachedParquetFiles {
         }
     }
 
+    /// Build a new set of files by applying `changes` to this one.
+    ///
+    /// The ingester file counts are carried over unchanged.
+    fn with_changes(&self, changes: &ParquetFileChanges) -> Self {
+        let existing = self.files.iter().map(|f| f.id).collect::<HashSet<_>>();
+
+        let files = self
+            .files
+            .iter()
+            .filter(|f| !changes.deleted.contains(&f.id))
+            .map(|f| match changes.upgraded_target_level {
+                Some(level) if changes.upgraded.contains(&f.id) => {
+                    let mut f = ParquetFile::clone(f);
+                    f.compaction_level = level;
+                    Arc::new(f)
+                }
+                _ => Arc::clone(f),
+            })
+            .chain(
+                changes
+                    .created
+                    .iter()
+                    .filter(|f| !existing.contains(&f.id))
+                    .map(|f| Arc::new(f.clone())),
+            )
+            .collect();
+
+        Self {
+            files,
+            persisted_file_counts_from_ingesters: self.persisted_file_counts_from_ingesters.clone(),
+        }
+    }
+
     /// return the underlying files as a new Vec
     #[cfg(test)]
     fn vec(&self) -> Vec<Arc<ParquetFile>> {
@@ -98,6 +136,25 @@ impl CachedParquetFiles {
     }
 }
 
+/// A set of changes to the parquet files of a single table that the querier
+/// learned about without asking the catalog, e.g. via gossip.
+#[derive(Debug, Default)]
+pub struct ParquetFileChanges {
+    /// Files that were newly created.
+    pub created: Vec<ParquetFile>,
+
+    /// Files that were deleted.
+    pub deleted: HashSet<ParquetFileId>,
+
+    /// Files that were moved to `upgraded_target_level`.
+    pub upgraded: HashSet<ParquetFileId>,
+
+    /// The level the `upgraded` files were moved to.
+    ///
+    /// Must be set if `upgraded` is non-empty.
+    pub upgraded_target_level: Option<CompactionLevel>,
+}
+
 type CacheT = Box<
     dyn Cache<
         K = TableId,
@@ -257,8 +314,24 @@ impl ParquetFileCache {
             .await
     }
 
+    /// Apply `changes` to the cached entry for `table_id`.
+    ///
+    /// If the table is not cached, nothing happens: the next [`get`](Self::get) loads the
+    /// current state from the catalog anyway.
+    ///
+    /// This is best effort. A concurrent catalog load that started before the changes were
+    /// committed may overwrite the updated entry with an older view, in which case the usual
+    /// expiration rules apply.
+    pub async fn apply_changes(&self, table_id: TableId, changes: ParquetFileChanges) {
+        let Some(cached) = self.cache.peek(table_id, ((), None)).await else {
+            return;
+        };
+
+        let updated = Arc::new(cached.with_changes(&changes));
+        self.cache.set(table_id, updated).await;
+    }
+
     /// Mark the entry for table_id as expired (and needs a refresh)
-    #[cfg(test)]
     pub fn expire(&self, table_id: TableId) {
         self.remove_if_handle.remove_if(&table_id, |_| true);
     }
@@ -282,7 +355,7 @@ mod tests {
     use std::{collections::HashSet, time::Duration};
 
     use super::*;
-    use data_types::{ColumnType, ParquetFileId};
+    use data_types::ColumnType;
     use iox_tests::{TestCatalog, TestNamespace, TestParquetFileBuilder, TestPartition, TestTable};
 
     use crate::cache::{
@@ -477,6 +550,88 @@ mod tests {
         assert_eq!(cached_files[2].as_ref(), &tfile4.parquet_file);
     }
 
+    #[tokio::test]
+    async fn test_apply_changes() {
+        let (catalog, table, partition) = make_catalog().await;
+        let builder = TestParquetFileBuilder::default().with_line_protocol(TABLE1_LINE_PROTOCOL);
+        let tfile1 = partition.create_parquet_file(builder).await;
+        let builder = TestParquetFileBuilder::default().with_line_protocol(TABLE1_LINE_PROTOCOL2);
+        let tfile2 = partition.create_parquet_file(builder).await;
+        let table_id = table.table.id;
+
+        let cache = make_cache(&catalog);
+
+        // changes to uncached tables are ignored
+        let builder = TestParquetFileBuilder::default().with_line_protocol(TABLE1_LINE_PROTOCOL3);
+        let tfile3 = partition.create_parquet_file(builder).await;
+        cache
+            .apply_changes(
+                table_id,
+                ParquetFileChanges {
+                    created: vec![tfile3.parquet_file.clone()],
+                    ..Default::default()
+                },
+            )
+            .await;
+        let cached_files = cache.get(table_id, None, None).await;
+        assert_eq!(
+            cached_files.ids(),
+            ids(&[
+                &tfile1.parquet_file,
+                &tfile2.parquet_file,
+                &tfile3.parquet_file
+            ]),
+        );
+        assert_catalog_access_metric_count(&catalog.metric_registry, METRIC_NAME, 1);
+
+        // a compaction round: file 1 and 2 replaced by file 4, file 3 upgraded
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol(TABLE1_LINE_PROTOCOL)
+            .with_compaction_level(CompactionLevel::FileNonOverlapped);
+        let tfile4 = partition.create_parquet_file(builder).await;
+        cache
+            .apply_changes(
+                table_id,
+                ParquetFileChanges {
+                    created: vec![tfile4.parquet_file.clone()],
+                    deleted: [tfile1.parquet_file.id, tfile2.parquet_file.id].into(),
+                    upgraded: [tfile3.parquet_file.id].into(),
+                    upgraded_target_level: Some(CompactionLevel::FileNonOverlapped),
+                },
+            )
+            .await;
+
+        // served from the cache, no catalog request
+        let cached_files = cache.get(table_id, None, None).await;
+        assert_catalog_access_metric_count(&catalog.metric_registry, METRIC_NAME, 1);
+        assert_eq!(
+            cached_files.ids(),
+            ids(&[&tfile3.parquet_file, &tfile4.parquet_file]),
+        );
+        assert!(cached_files
+            .files
+            .iter()
+            .all(|f| f.compaction_level == CompactionLevel::FileNonOverlapped));
+
+        // re-applying a known file does not duplicate it
+        cache
+            .apply_changes(
+                table_id,
+                ParquetFileChanges {
+                    created: vec![tfile4.parquet_file.clone()],
+                    ..Default::default()
+                },
+            )
+            .await;
+        let cached_files = cache.get(table_id, None, None).await;
+        assert_eq!(cached_files.files.len(), 2);
+        assert_catalog_access_metric_count(&catalog.metric_registry, METRIC_NAME, 1);
+    }
+
+    fn ids(files: &[&ParquetFile]) -> HashSet<ParquetFileId> {
+        files.iter().map(|f| f.id).collect()
+    }
+
     /// Extracts parquet ids from various objects
     trait ParquetIds {
         fn ids(&self) -> HashSet<ParquetFileId>;
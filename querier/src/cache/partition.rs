@@ -236,6 +236,21 @@ impl PartitionCache {
 
         out
     }
+
+    /// Expire the cached entry for `partition_id` if it is known to be outdated once new files were
+    /// written to the partition.
+    ///
+    /// This is the case if the partition was cached as unknown (it was created after it was
+    /// cached) or without a sort key (the first persist sets the sort key). Other entries stay as
+    /// they are: their sort keys are checked against the files on every [`get`](Self::get).
+    pub fn expire_if_outdated(&self, partition_id: &TransitionPartitionId) -> bool {
+        self.remove_if_handle
+            .remove_if(partition_id, |cached_partition| {
+                cached_partition
+                    .map(|p| p.sort_key.is_none())
+                    .unwrap_or(true)
+            })
+    }
 }
 
 /// Request for [`PartitionCache::get`].
@@ -1213,6 +1228,92 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_expire_if_outdated() {
+        let catalog = TestCatalog::new();
+
+        let ns = catalog.create_namespace_1hr_retention("ns").await;
+        let t = ns.create_table("table").await;
+        let c1 = t.create_column("foo", ColumnType::Tag).await;
+        let c2 = t.create_column("time", ColumnType::Time).await;
+        let p = t.create_partition("k1").await;
+        let p_id = p.partition.transition_partition_id();
+        let cached_table = Arc::new(CachedTable {
+            id: t.table.id,
+            schema: schema(),
+            column_id_map: HashMap::from([
+                (c1.column.id, Arc::from(c1.column.name.clone())),
+                (c2.column.id, Arc::from(c2.column.name.clone())),
+            ]),
+            column_id_map_rev: HashMap::from([
+                (Arc::from(c1.column.name.clone()), c1.column.id),
+                (Arc::from(c2.column.name.clone()), c2.column.id),
+            ]),
+            primary_key_column_ids: [c1.column.id, c2.column.id].into(),
+            partition_template: TablePartitionTemplateOverride::default(),
+        });
+
+        let cache = PartitionCache::new(
+            catalog.catalog(),
+            BackoffConfig::default(),
+            catalog.time_provider(),
+            &catalog.metric_registry(),
+            test_ram_pool(),
+            true,
+        );
+
+        // uncached partitions are not touched
+        assert!(!cache.expire_if_outdated(&p_id));
+
+        // cached without a sort key => expire
+        let sort_key = cache
+            .get_one(Arc::clone(&cached_table), &p_id, &[], None)
+            .await
+            .unwrap()
+            .sort_key
+            .clone();
+        assert_eq!(sort_key, None);
+        assert_catalog_access_metric_count(
+            &catalog.metric_registry,
+            "partition_get_by_hash_id_batch",
+            1,
+        );
+        assert!(cache.expire_if_outdated(&p_id));
+
+        // set sort key
+        p.update_sort_key(
+            SortKey::from_columns([c1.column.name.as_str(), c2.column.name.as_str()]),
+            &SortedColumnSet::from([c1.column.id.get(), c2.column.id.get()]),
+        )
+        .await;
+
+        // re-fetched with the sort key
+        let sort_key = cache
+            .get_one(Arc::clone(&cached_table), &p_id, &[], None)
+            .await
+            .unwrap()
+            .sort_key
+            .clone();
+        assert!(sort_key.is_some());
+        assert_catalog_access_metric_count(
+            &catalog.metric_registry,
+            "partition_get_by_hash_id_batch",
+            2,
+        );
+
+        // cached with a sort key => keep
+        assert!(!cache.expire_if_outdated(&p_id));
+        cache
+            .get_one(Arc::clone(&cached_table), &p_id, &[], None)
+            .await
+            .unwrap();
+        assert_catalog_access_metric_count(
+            &catalog.metric_registry,
+            "partition_get_by_hash_id_batch",
+            2,
+        );
+    }
+
     #[tokio::test]
     async fn test_multi_get() {
         let catalog = TestCatalog::new();
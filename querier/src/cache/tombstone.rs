@@ -0,0 +1,278 @@
+//! Tombstone cache
+
+use backoff::{Backoff, BackoffConfig};
+use cache_system::{
+    backend::policy::{
+        lru::{LruPolicy, ResourcePool},
+        ttl::{ConstantValueTtlProvider, TtlPolicy},
+        PolicyBackend,
+    },
+    cache::{driver::CacheDriver, metrics::CacheWithMetrics, Cache},
+    loader::{metrics::MetricsLoader, FunctionLoader},
+    resource_consumption::FunctionEstimator,
+};
+use data_types::{TableId, Tombstone};
+use iox_catalog::interface::Catalog;
+use iox_time::TimeProvider;
+use observability_deps::tracing::warn;
+use predicate::delete_predicate::{TombstoneError, TombstonePredicates};
+use snafu::{ResultExt, Snafu};
+use std::{mem, sync::Arc, time::Duration};
+use trace::span::Span;
+
+use super::ram::RamSize;
+
+/// Duration to keep cached view.
+///
+/// Deletes are only visible to queries against persisted data once the cached view was refreshed, so this is kept
+/// short.
+///
+/// This is currently `10s`.
+pub const TTL: Duration = Duration::from_secs(10);
+
+const CACHE_ID: &str = "tombstone";
+
+#[derive(Debug, Snafu)]
+#[allow(missing_copy_implementations, missing_docs)]
+pub enum Error {
+    #[snafu(display("CatalogError refreshing tombstone cache: {}", source))]
+    Catalog {
+        source: iox_catalog::interface::Error,
+    },
+}
+
+/// Holds the tombstones of a table, together with their parsed predicates.
+#[derive(Debug)]
+pub struct CachedTombstones {
+    tombstones: Result<TombstonePredicates, Arc<TombstoneError>>,
+}
+
+impl CachedTombstones {
+    fn new(tombstones: Vec<Tombstone>) -> Self {
+        let tombstones = TombstonePredicates::try_new(tombstones).map_err(|e| {
+            warn!(%e, "cannot parse tombstone predicate");
+            Arc::new(e)
+        });
+
+        Self { tombstones }
+    }
+
+    /// The parsed tombstones of the table.
+    ///
+    /// Fails if the predicate of any of them cannot be parsed, as the rows deleted from the
+    /// table are then unknown.
+    pub fn predicates(&self) -> Result<&TombstonePredicates, Arc<TombstoneError>> {
+        self.tombstones.as_ref().map_err(Arc::clone)
+    }
+
+    /// Estimate the memory consumption of this object and its contents
+    fn size(&self) -> usize {
+        mem::size_of_val(self)
+            + self
+                .tombstones
+                .as_ref()
+                .map(|t| t.size())
+                .unwrap_or_default()
+    }
+}
+
+type CacheT = Box<
+    dyn Cache<
+        K = TableId,
+        V = Arc<CachedTombstones>,
+        GetExtra = ((), Option<Span>),
+        PeekExtra = ((), Option<Span>),
+    >,
+>;
+
+/// Cache for the tombstones of a table.
+#[derive(Debug)]
+pub struct TombstoneCache {
+    cache: CacheT,
+}
+
+impl TombstoneCache {
+    /// Create new empty cache.
+    pub fn new(
+        catalog: Arc<dyn Catalog>,
+        backoff_config: BackoffConfig,
+        time_provider: Arc<dyn TimeProvider>,
+        metric_registry: &metric::Registry,
+        ram_pool: Arc<ResourcePool<RamSize>>,
+        testing: bool,
+    ) -> Self {
+        let loader = FunctionLoader::new(move |table_id: TableId, _extra: ()| {
+            let catalog = Arc::clone(&catalog);
+            let backoff_config = backoff_config.clone();
+
+            async move {
+                Backoff::new(&backoff_config)
+                    .retry_all_errors("get tombstones", || async {
+                        let tombstones = catalog
+                            .repositories()
+                            .await
+                            .tombstones()
+                            .list_by_table_id(table_id)
+                            .await
+                            .context(CatalogSnafu)?;
+
+                        Ok(Arc::new(CachedTombstones::new(tombstones)))
+                            as std::result::Result<_, Error>
+                    })
+                    .await
+                    .expect("retry forever")
+            }
+        });
+        let loader = Arc::new(MetricsLoader::new(
+            loader,
+            CACHE_ID,
+            Arc::clone(&time_provider),
+            metric_registry,
+            testing,
+        ));
+
+        let mut backend = PolicyBackend::hashmap_backed(Arc::clone(&time_provider));
+        backend.add_policy(LruPolicy::new(
+            Arc::clone(&ram_pool),
+            CACHE_ID,
+            Arc::new(FunctionEstimator::new(
+                |k: &TableId, v: &Arc<CachedTombstones>| {
+                    RamSize(mem::size_of_val(k) + mem::size_of_val(v) + v.size())
+                },
+            )),
+        ));
+        backend.add_policy(TtlPolicy::new(
+            Arc::new(ConstantValueTtlProvider::new(Some(TTL))),
+            CACHE_ID,
+            metric_registry,
+        ));
+
+        let cache = CacheDriver::new(loader, backend);
+        let cache = Box::new(CacheWithMetrics::new(
+            cache,
+            CACHE_ID,
+            time_provider,
+            metric_registry,
+        ));
+
+        Self { cache }
+    }
+
+    /// Get the tombstones of the given table.
+    pub async fn get(&self, table_id: TableId, span: Option<Span>) -> Arc<CachedTombstones> {
+        self.cache.get(table_id, ((), span)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_types::{ColumnType, Timestamp};
+    use iox_tests::{TestCatalog, TestParquetFileBuilder};
+
+    use crate::cache::{
+        ram::test_util::test_ram_pool, test_util::assert_catalog_access_metric_count,
+    };
+
+    const METRIC_NAME: &str = "tombstone_list_by_table_id";
+
+    #[tokio::test]
+    async fn test_tombstones() {
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_1hr_retention("ns").await;
+        let table = ns.create_table("table1").await;
+        table.create_column("tag", ColumnType::Tag).await;
+        table.create_column("foo", ColumnType::F64).await;
+        table.create_column("time", ColumnType::Time).await;
+        let partition = table.create_partition("k").await;
+
+        let builder = TestParquetFileBuilder::default().with_line_protocol("table1,tag=A foo=1 11");
+        let file = partition.create_parquet_file(builder).await.parquet_file;
+
+        // the tombstone is created after the file
+        catalog.mock_time_provider().inc(Duration::from_secs(1));
+
+        catalog
+            .catalog()
+            .repositories()
+            .await
+            .tombstones()
+            .create(
+                table.table.id,
+                Timestamp::new(10),
+                Timestamp::new(20),
+                r#""tag"='A'"#,
+            )
+            .await
+            .unwrap();
+
+        let cache = TombstoneCache::new(
+            catalog.catalog(),
+            BackoffConfig::default(),
+            catalog.time_provider(),
+            &catalog.metric_registry(),
+            test_ram_pool(),
+            true,
+        );
+
+        let cached = cache.get(table.table.id, None).await;
+        let tombstones = cached.predicates().unwrap();
+        assert_eq!(tombstones.len(), 1);
+        let predicates = tombstones.predicates_for(&file);
+        assert_eq!(predicates.len(), 1);
+        assert_eq!(predicates[0].range.start(), 10);
+        assert_eq!(predicates[0].range.end(), 20);
+        assert_eq!(predicates[0].expr_sql_string(), r#""tag"='A'"#);
+
+        // validate a second request doesn't result in a catalog request
+        assert_catalog_access_metric_count(&catalog.metric_registry, METRIC_NAME, 1);
+        cache.get(table.table.id, None).await;
+        assert_catalog_access_metric_count(&catalog.metric_registry, METRIC_NAME, 1);
+
+        // the cache is refreshed after the TTL
+        catalog.mock_time_provider().inc(TTL);
+        cache.get(table.table.id, None).await;
+        assert_catalog_access_metric_count(&catalog.metric_registry, METRIC_NAME, 2);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_predicate() {
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_1hr_retention("ns").await;
+        let table = ns.create_table("table1").await;
+        table.create_column("tag", ColumnType::Tag).await;
+        table.create_column("time", ColumnType::Time).await;
+
+        let tombstone = catalog
+            .catalog()
+            .repositories()
+            .await
+            .tombstones()
+            .create(
+                table.table.id,
+                Timestamp::new(10),
+                Timestamp::new(20),
+                r#""tag" LIKE 'A%' OR"#,
+            )
+            .await
+            .unwrap();
+
+        let cache = TombstoneCache::new(
+            catalog.catalog(),
+            BackoffConfig::default(),
+            catalog.time_provider(),
+            &catalog.metric_registry(),
+            test_ram_pool(),
+            true,
+        );
+
+        // The tombstone is neither ignored nor applied to its whole time range, reading the
+        // table fails instead.
+        let cached = cache.get(table.table.id, None).await;
+        let err = cached.predicates().unwrap_err();
+        assert!(
+            err.to_string().contains(&tombstone.id.to_string()),
+            "unexpected error: {err}"
+        );
+    }
+}
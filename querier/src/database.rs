@@ -5,25 +5,29 @@ use crate::{
     ingester::IngesterConnection,
     namespace::{QuerierNamespace, QuerierNamespaceArgs},
     parquet::ChunkAdapter,
-    query_log::QueryLog,
+    query_log::{CancelOutcome, QueryLog},
+    router::RouterConnection,
     table::PruneMetrics,
     QueryLogEntry,
 };
 use async_trait::async_trait;
 use backoff::{Backoff, BackoffConfig};
-use data_types::Namespace;
+use data_types::{MaxConcurrentQueries, Namespace, NamespaceId, NamespaceQueryLimits};
 use iox_catalog::interface::SoftDeletedRows;
 use iox_query::exec::Executor;
+use parking_lot::Mutex;
 use service_common::QueryNamespaceProvider;
 use snafu::Snafu;
 use std::{
     collections::{HashMap, VecDeque},
     sync::Arc,
 };
+use tokio::sync::Semaphore;
 use trace::span::{Span, SpanRecorder};
 use tracker::{
     AsyncSemaphoreMetrics, InstrumentedAsyncOwnedSemaphorePermit, InstrumentedAsyncSemaphore,
 };
+use uuid::Uuid;
 
 /// The number of entries to store in the circular query buffer log.
 ///
@@ -59,6 +63,9 @@ pub struct QuerierDatabase {
     /// Connection to ingester(s)
     ingester_connection: Option<Arc<dyn IngesterConnection>>,
 
+    /// Connection to the router, used to write the results of queries.
+    router_connection: Option<Arc<RouterConnection>>,
+
     /// Query log.
     query_log: Arc<QueryLog>,
 
@@ -74,6 +81,12 @@ pub struct QuerierDatabase {
 
     /// DataFusion config.
     datafusion_config: Arc<HashMap<String, String>>,
+
+    /// Semaphores enforcing the per-namespace concurrent query limits, keyed by namespace.
+    ///
+    /// The semaphore of a namespace is replaced when its limit changes. Queries holding a permit
+    /// of the old semaphore keep running but no longer count against the new limit.
+    namespace_query_semaphores: Mutex<HashMap<NamespaceId, (MaxConcurrentQueries, Arc<Semaphore>)>>,
 }
 
 #[async_trait]
@@ -111,6 +124,7 @@ impl QuerierDatabase {
         metric_registry: Arc<metric::Registry>,
         exec: Arc<Executor>,
         ingester_connection: Option<Arc<dyn IngesterConnection>>,
+        router_connection: Option<Arc<RouterConnection>>,
         max_concurrent_queries: usize,
         datafusion_config: Arc<HashMap<String, String>>,
     ) -> Result<Self, Error> {
@@ -143,10 +157,12 @@ impl QuerierDatabase {
             chunk_adapter,
             exec,
             ingester_connection,
+            router_connection,
             query_log,
             query_execution_semaphore,
             prune_metrics,
             datafusion_config,
+            namespace_query_semaphores: Default::default(),
         })
     }
 
@@ -172,19 +188,47 @@ impl QuerierDatabase {
                 span_recorder.child_span("cache GET namespace schema"),
             )
             .await?;
+        let query_semaphore = self.namespace_query_semaphore(ns.id, &ns.query_limits);
         Some(Arc::new(QuerierNamespace::new(QuerierNamespaceArgs {
             chunk_adapter: Arc::clone(&self.chunk_adapter),
             ns,
             name,
             exec: Arc::clone(&self.exec),
             ingester_connection: self.ingester_connection.clone(),
+            router_connection: self.router_connection.clone(),
             query_log: Arc::clone(&self.query_log),
             prune_metrics: Arc::clone(&self.prune_metrics),
             datafusion_config: Arc::clone(&self.datafusion_config),
             include_debug_info_tables,
+            query_semaphore,
         })))
     }
 
+    /// Get the semaphore limiting concurrent queries against the given namespace, if it has a
+    /// limit.
+    fn namespace_query_semaphore(
+        &self,
+        id: NamespaceId,
+        limits: &NamespaceQueryLimits,
+    ) -> Option<Arc<Semaphore>> {
+        let mut semaphores = self.namespace_query_semaphores.lock();
+
+        let Some(max) = limits.max_concurrent_queries else {
+            semaphores.remove(&id);
+            return None;
+        };
+
+        let (current_max, semaphore) = semaphores
+            .entry(id)
+            .or_insert_with(|| (max, Arc::new(Semaphore::new(max.get() as usize))));
+        if *current_max != max {
+            *current_max = max;
+            *semaphore = Arc::new(Semaphore::new(max.get() as usize));
+        }
+
+        Some(Arc::clone(semaphore))
+    }
+
     /// Return all namespaces this querier knows about
     pub async fn namespaces(&self) -> Vec<Namespace> {
         let catalog = &self.catalog_cache.catalog();
@@ -210,12 +254,28 @@ impl QuerierDatabase {
     pub fn query_log(&self) -> VecDeque<Arc<QueryLogEntry>> {
         self.query_log.entries()
     }
+
+    /// Returns the query with the given ID, if it is still in the query log.
+    pub fn query(&self, id: Uuid) -> Option<Arc<QueryLogEntry>> {
+        self.query_log.get(id)
+    }
+
+    /// Cancel the running query with the given ID.
+    ///
+    /// This aborts the execution of the query, including any outstanding requests to the
+    /// ingesters.
+    pub fn cancel_query(&self, id: Uuid) -> CancelOutcome {
+        self.query_log.cancel(id)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::create_ingester_connection_for_testing;
+    use assert_matches::assert_matches;
+    use datafusion::error::DataFusionError;
+    use iox_query::{exec::QueryCancelledError, QueryNamespace};
     use iox_tests::TestCatalog;
     use tokio::runtime::Handle;
 
@@ -238,6 +298,7 @@ mod tests {
             catalog.metric_registry(),
             catalog.exec(),
             Some(create_ingester_connection_for_testing()),
+            None,
             QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX.saturating_add(1),
             Arc::new(HashMap::default()),
         )
@@ -271,6 +332,76 @@ mod tests {
         assert_eq!(namespaces[1].name, "ns2");
     }
 
+    #[tokio::test]
+    async fn test_namespace_concurrent_query_limit() {
+        let catalog = TestCatalog::new();
+        let db = new_db(&catalog).await;
+
+        catalog.create_namespace_1hr_retention("ns1").await;
+        catalog
+            .catalog()
+            .repositories()
+            .await
+            .namespaces()
+            .update_concurrent_query_limit("ns1", MaxConcurrentQueries::new(1))
+            .await
+            .unwrap();
+
+        let ns_a = db.namespace("ns1", None, true).await.unwrap();
+        let ns_b = db.namespace("ns1", None, true).await.unwrap();
+
+        // the limit is shared by all instances of the namespace
+        let ctx_a = ns_a.new_query_context(None);
+        let ctx_b = ns_b.new_query_context(None);
+        let token = ns_a
+            .record_query(&ctx_a, None, "sql", Box::new("SELECT 1"))
+            .unwrap();
+        let err = ns_b
+            .record_query(&ctx_b, None, "sql", Box::new("SELECT 2"))
+            .unwrap_err();
+        assert_matches!(err, DataFusionError::ResourcesExhausted(_));
+
+        drop(token);
+        ns_b.record_query(&ctx_b, None, "sql", Box::new("SELECT 3"))
+            .unwrap();
+
+        let log = db.query_log();
+        assert_eq!(log.len(), 3);
+        assert!(!log[1].success());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_query() {
+        let catalog = TestCatalog::new();
+        let db = new_db(&catalog).await;
+
+        catalog.create_namespace_1hr_retention("ns1").await;
+        let ns = db.namespace("ns1", None, true).await.unwrap();
+
+        let ctx = ns.new_query_context(None);
+        let token = ns
+            .record_query(&ctx, None, "sql", Box::new("SELECT 1"))
+            .unwrap();
+        let id = db.query_log().back().unwrap().id;
+
+        assert_eq!(db.cancel_query(Uuid::new_v4()), CancelOutcome::NotFound);
+        assert_eq!(db.cancel_query(id), CancelOutcome::Cancelled);
+        assert!(db.query_log().back().unwrap().cancelled());
+
+        // the running query is aborted
+        let err = ctx
+            .run(futures::future::pending::<Result<(), DataFusionError>>())
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            DataFusionError::External(e) if e.downcast_ref::<QueryCancelledError>().is_some()
+        );
+
+        drop(token);
+        assert_eq!(db.cancel_query(id), CancelOutcome::AlreadyCompleted);
+    }
+
     async fn new_db(catalog: &Arc<TestCatalog>) -> QuerierDatabase {
         let catalog_cache = Arc::new(CatalogCache::new_testing(
             catalog.catalog(),
@@ -284,6 +415,7 @@ mod tests {
             catalog.metric_registry(),
             catalog.exec(),
             Some(create_ingester_connection_for_testing()),
+            None,
             QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX,
             Arc::new(HashMap::default()),
         )
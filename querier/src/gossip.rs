@@ -0,0 +1,269 @@
+//! [`gossip`] integration to keep the [`CatalogCache`] fresh.
+//!
+//! Ingesters broadcast every parquet file they persist, and compactors broadcast the outcome of
+//! every compaction round. Applying these to the cache lets the querier see new files right after
+//! they were created instead of waiting for the cache entries to expire, and saves the catalog
+//! requests that a refresh would need.
+//!
+//! Gossip delivery is best effort: messages may be lost or reordered. The usual expiration logic
+//! of the caches stays in place and eventually corrects anything missed here.
+
+use std::{collections::HashSet, sync::Arc};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use data_types::{CompactionLevel, ParquetFile, ParquetFileId, TableId};
+use generated_types::influxdata::iox::{
+    catalog::v1 as proto,
+    gossip::{v1::CompactionEvent, Topic},
+};
+use gossip::{Dispatcher, Identity};
+use gossip_compaction::rx::{CompactionEventHandler, CompactionEventRx};
+use gossip_parquet_file::rx::{ParquetFileEventHandler, ParquetFileRx};
+use observability_deps::tracing::{debug, warn};
+
+use crate::cache::{parquet_file::ParquetFileChanges, CatalogCache};
+
+/// Applies parquet file and compaction notifications received via gossip to
+/// the [`CatalogCache`].
+#[derive(Debug)]
+pub struct CatalogCacheGossip {
+    cache: Arc<CatalogCache>,
+}
+
+impl CatalogCacheGossip {
+    /// Apply incoming notifications to `cache`.
+    pub fn new(cache: Arc<CatalogCache>) -> Self {
+        Self { cache }
+    }
+
+    /// Apply `changes` to the caches.
+    ///
+    /// All files must belong to `table_id`.
+    async fn apply(&self, table_id: TableId, changes: ParquetFileChanges) {
+        for file in &changes.created {
+            if self
+                .cache
+                .partition()
+                .expire_if_outdated(&file.partition_id)
+            {
+                debug!(partition_id=%file.partition_id, "expired partition cache entry via gossip");
+            }
+        }
+
+        self.cache
+            .parquet_file()
+            .apply_changes(table_id, changes)
+            .await;
+    }
+}
+
+#[async_trait]
+impl ParquetFileEventHandler for CatalogCacheGossip {
+    async fn handle(&self, event: proto::ParquetFile) {
+        let file = match ParquetFile::try_from(event) {
+            Ok(v) => v,
+            Err(e) => {
+                warn!(error=%e, "invalid parquet file gossip message");
+                return;
+            }
+        };
+
+        debug!(
+            parquet_file_id=%file.id,
+            table_id=%file.table_id,
+            partition_id=%file.partition_id,
+            "received parquet file via gossip"
+        );
+
+        let table_id = file.table_id;
+        self.apply(
+            table_id,
+            ParquetFileChanges {
+                created: vec![file],
+                ..Default::default()
+            },
+        )
+        .await;
+    }
+}
+
+#[async_trait]
+impl CompactionEventHandler for CatalogCacheGossip {
+    async fn handle(&self, event: CompactionEvent) {
+        let created = match event
+            .new_files
+            .into_iter()
+            .map(ParquetFile::try_from)
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(v) => v,
+            Err(e) => {
+                warn!(error=%e, "invalid compaction gossip message");
+                return;
+            }
+        };
+
+        // Deleted and upgraded files are only addressed by their ID, so the
+        // table is derived from the created files. A compaction round only
+        // ever touches a single partition.
+        let Some(table_id) = created.first().map(|f| f.table_id) else {
+            debug!("ignoring compaction gossip message without created files");
+            return;
+        };
+
+        let upgraded = event
+            .updated_file_ids
+            .into_iter()
+            .map(ParquetFileId::new)
+            .collect::<HashSet<_>>();
+        let upgraded_target_level = if upgraded.is_empty() {
+            None
+        } else {
+            match CompactionLevel::try_from(event.upgraded_target_level) {
+                Ok(v) => Some(v),
+                Err(e) => {
+                    warn!(
+                        error=%e,
+                        %table_id,
+                        "invalid compaction gossip message, expiring parquet file cache entry"
+                    );
+                    self.cache.parquet_file().expire(table_id);
+                    return;
+                }
+            }
+        };
+
+        debug!(
+            %table_id,
+            n_created=created.len(),
+            n_deleted=event.deleted_file_ids.len(),
+            n_upgraded=upgraded.len(),
+            "received compaction event via gossip"
+        );
+
+        self.apply(
+            table_id,
+            ParquetFileChanges {
+                created,
+                deleted: event
+                    .deleted_file_ids
+                    .into_iter()
+                    .map(ParquetFileId::new)
+                    .collect(),
+                upgraded,
+                upgraded_target_level,
+            },
+        )
+        .await;
+    }
+}
+
+/// A gossip [`Dispatcher`] passing [`Topic::NewParquetFiles`] and
+/// [`Topic::CompactionEvents`] messages to a [`CatalogCacheGossip`].
+#[derive(Debug)]
+pub struct CatalogCacheGossipRx {
+    parquet_files: ParquetFileRx,
+    compaction_events: CompactionEventRx,
+}
+
+impl CatalogCacheGossipRx {
+    /// Dispatch messages to `handler`, buffering up to `buffer` events per
+    /// topic.
+    pub fn new(handler: Arc<CatalogCacheGossip>, buffer: usize) -> Self {
+        Self {
+            parquet_files: ParquetFileRx::new(Arc::clone(&handler), buffer),
+            compaction_events: CompactionEventRx::new(handler, buffer),
+        }
+    }
+}
+
+#[async_trait]
+impl Dispatcher<Topic> for CatalogCacheGossipRx {
+    async fn dispatch(&self, topic: Topic, payload: Bytes, identity: Identity) {
+        // Both dispatchers ignore topics they are not responsible for.
+        self.parquet_files
+            .dispatch(topic, payload.clone(), identity.clone())
+            .await;
+        self.compaction_events
+            .dispatch(topic, payload, identity)
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::ColumnType;
+    use iox_tests::{TestCatalog, TestParquetFileBuilder};
+    use tokio::runtime::Handle;
+
+    use super::*;
+    use crate::cache::test_util::assert_catalog_access_metric_count;
+
+    #[tokio::test]
+    async fn test_apply_gossip() {
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_1hr_retention("ns").await;
+        let table = ns.create_table("table").await;
+        table.create_column("foo", ColumnType::F64).await;
+        table.create_column("time", ColumnType::Time).await;
+        let partition = table.create_partition("k").await;
+        let table_id = table.table.id;
+
+        let builder = TestParquetFileBuilder::default().with_line_protocol("table foo=1 11");
+        let file1 = partition.create_parquet_file(builder).await.parquet_file;
+
+        let cache = Arc::new(CatalogCache::new_testing(
+            catalog.catalog(),
+            catalog.time_provider(),
+            catalog.metric_registry(),
+            catalog.object_store(),
+            &Handle::current(),
+        ));
+        let handler = CatalogCacheGossip::new(Arc::clone(&cache));
+
+        let cached_ids = || async {
+            let mut ids = cache
+                .parquet_file()
+                .get(table_id, None, None)
+                .await
+                .files
+                .iter()
+                .map(|f| f.id)
+                .collect::<Vec<_>>();
+            ids.sort();
+            ids
+        };
+        assert_eq!(cached_ids().await, vec![file1.id]);
+
+        // an ingester persisted a new file
+        let builder = TestParquetFileBuilder::default().with_line_protocol("table foo=2 22");
+        let file2 = partition.create_parquet_file(builder).await.parquet_file;
+        ParquetFileEventHandler::handle(&handler, file2.clone().into()).await;
+        assert_eq!(cached_ids().await, vec![file1.id, file2.id]);
+
+        // the compactor replaced both with a new file
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("table foo=2 22")
+            .with_compaction_level(CompactionLevel::FileNonOverlapped);
+        let file3 = partition.create_parquet_file(builder).await.parquet_file;
+        CompactionEventHandler::handle(
+            &handler,
+            CompactionEvent {
+                deleted_file_ids: vec![file1.id.get(), file2.id.get()],
+                upgraded_target_level: 0,
+                updated_file_ids: vec![],
+                new_files: vec![file3.clone().into()],
+            },
+        )
+        .await;
+        assert_eq!(cached_ids().await, vec![file3.id]);
+
+        // everything was served from the cache after the initial load
+        assert_catalog_access_metric_count(
+            &catalog.metric_registry,
+            "parquet_list_by_table_not_to_delete",
+            1,
+        );
+    }
+}
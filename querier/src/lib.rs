@@ -18,10 +18,12 @@ use workspace_hack as _;
 
 mod cache;
 mod database;
+mod gossip;
 mod ingester;
 mod namespace;
 mod parquet;
 mod query_log;
+mod router;
 mod server;
 mod system_tables;
 mod table;
@@ -31,9 +33,16 @@ mod table;
 /// This is mostly to fetch per-partition data concurrently.
 const CONCURRENT_CHUNK_CREATION_JOBS: usize = 100;
 
-pub use cache::CatalogCache as QuerierCatalogCache;
+pub use crate::gossip::{CatalogCacheGossip, CatalogCacheGossipRx};
+pub use cache::{
+    object_store::{
+        DiskCacheConfig as QuerierDiskCacheConfig, DiskCacheDirError as QuerierDiskCacheDirError,
+    },
+    CatalogCache as QuerierCatalogCache,
+};
 pub use database::{Error as QuerierDatabaseError, QuerierDatabase};
 pub use ingester::{create_ingester_connection_for_testing, create_ingester_connections};
 pub use namespace::QuerierNamespace;
-pub use query_log::QueryLogEntry;
+pub use query_log::{CancelOutcome, QueryLogEntry};
+pub use router::{Error as RouterError, RouterConnection};
 pub use server::QuerierServer;
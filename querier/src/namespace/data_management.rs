@@ -0,0 +1,59 @@
+//! Deletes and drops data of a namespace, for the InfluxQL data management
+//! statements.
+
+use crate::router::RouterConnection;
+use async_trait::async_trait;
+use data_types::{DeletePredicate, NamespaceId};
+use datafusion::error::{DataFusionError, Result};
+use iox_query::exec::data_manager::DataManager;
+use observability_deps::tracing::debug;
+use std::sync::Arc;
+
+/// A [`DataManager`] for a single namespace, which forwards all operations
+/// to the router.
+///
+/// The router applies predicate deletes to the data buffered by the
+/// ingesters and records them as tombstones for the persisted data, and
+/// notifies its deletion observers of dropped tables, exactly as it does for
+/// the same requests made to it directly.
+#[derive(Debug)]
+pub(crate) struct QuerierDataManager {
+    namespace_id: NamespaceId,
+    namespace_name: Arc<str>,
+    connection: Arc<RouterConnection>,
+}
+
+impl QuerierDataManager {
+    pub(crate) fn new(
+        namespace_id: NamespaceId,
+        namespace_name: Arc<str>,
+        connection: Arc<RouterConnection>,
+    ) -> Self {
+        Self {
+            namespace_id,
+            namespace_name,
+            connection,
+        }
+    }
+}
+
+#[async_trait]
+impl DataManager for QuerierDataManager {
+    async fn delete(&self, table_name: &str, predicate: &DeletePredicate) -> Result<()> {
+        debug!(namespace_id=%self.namespace_id, %table_name, "Deleting data");
+
+        self.connection
+            .delete(self.namespace_id, table_name, predicate)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))
+    }
+
+    async fn drop_table(&self, table_name: &str) -> Result<()> {
+        debug!(namespace=%self.namespace_name, %table_name, "Dropping table");
+
+        self.connection
+            .drop_table(&self.namespace_name, table_name)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))
+    }
+}
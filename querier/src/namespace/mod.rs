@@ -5,12 +5,15 @@ use crate::{
     ingester::IngesterConnection,
     parquet::ChunkAdapter,
     query_log::QueryLog,
+    router::RouterConnection,
     table::{PruneMetrics, QuerierTable, QuerierTableArgs},
 };
-use data_types::NamespaceId;
+use data_types::{NamespaceId, NamespaceQueryLimits};
 use iox_query::exec::Executor;
 use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::sync::Semaphore;
 
+mod data_management;
 mod query_access;
 
 #[cfg(test)]
@@ -24,10 +27,12 @@ pub struct QuerierNamespaceArgs {
     pub name: Arc<str>,
     pub exec: Arc<Executor>,
     pub ingester_connection: Option<Arc<dyn IngesterConnection>>,
+    pub router_connection: Option<Arc<RouterConnection>>,
     pub query_log: Arc<QueryLog>,
     pub prune_metrics: Arc<PruneMetrics>,
     pub datafusion_config: Arc<HashMap<String, String>>,
     pub include_debug_info_tables: bool,
+    pub query_semaphore: Option<Arc<Semaphore>>,
 }
 
 /// Maps a catalog namespace to all the in-memory resources and sync-state that the querier needs.
@@ -59,6 +64,10 @@ pub struct QuerierNamespace {
     /// Query log.
     query_log: Arc<QueryLog>,
 
+    /// Connection to the router, used to write the results of queries and
+    /// to delete data.
+    router_connection: Option<Arc<RouterConnection>>,
+
     /// DataFusion config.
     datafusion_config: Arc<HashMap<String, String>>,
 
@@ -67,6 +76,15 @@ pub struct QuerierNamespace {
 
     /// Retention period.
     retention_period: Option<Duration>,
+
+    /// Per-namespace query limits.
+    query_limits: NamespaceQueryLimits,
+
+    /// Semaphore limiting the number of queries running concurrently against this namespace.
+    ///
+    /// This is shared by all [`QuerierNamespace`] instances of the same namespace and only set if
+    /// the namespace has a concurrency limit.
+    query_semaphore: Option<Arc<Semaphore>>,
 }
 
 impl QuerierNamespace {
@@ -78,10 +96,12 @@ impl QuerierNamespace {
             name,
             exec,
             ingester_connection,
+            router_connection,
             query_log,
             prune_metrics,
             datafusion_config,
             include_debug_info_tables,
+            query_semaphore,
         } = args;
 
         let tables: HashMap<_, _> = ns
@@ -113,9 +133,12 @@ impl QuerierNamespace {
             exec,
             catalog_cache: Arc::clone(chunk_adapter.catalog_cache()),
             query_log,
+            router_connection,
             datafusion_config,
             include_debug_info_tables,
             retention_period: ns.retention_period,
+            query_limits: ns.query_limits,
+            query_semaphore,
         }
     }
 
@@ -132,6 +155,10 @@ impl QuerierNamespace {
         let chunk_adapter = Arc::new(ChunkAdapter::new(catalog_cache, metric_registry));
         let query_log = Arc::new(QueryLog::new(10, time_provider));
         let prune_metrics = Arc::new(PruneMetrics::new(&chunk_adapter.metric_registry()));
+        let query_semaphore = ns
+            .query_limits
+            .max_concurrent_queries
+            .map(|max| Arc::new(Semaphore::new(max.get() as usize)));
 
         Self::new(QuerierNamespaceArgs {
             chunk_adapter,
@@ -139,10 +166,12 @@ impl QuerierNamespace {
             name,
             exec,
             ingester_connection,
+            router_connection: None,
             query_log,
             prune_metrics,
             datafusion_config: Default::default(),
             include_debug_info_tables: true,
+            query_semaphore,
         })
     }
 
@@ -1,8 +1,9 @@
 //! This module contains implementations of [`iox_query`] interfaces for [QuerierNamespace].
 
 use crate::{
-    namespace::QuerierNamespace,
+    namespace::{data_management::QuerierDataManager, QuerierNamespace},
     query_log::QueryLog,
+    router::RouterWriter,
     system_tables::{SystemSchemaProvider, SYSTEM_SCHEMA},
     table::QuerierTable,
 };
@@ -15,12 +16,14 @@ use datafusion::{
     prelude::Expr,
 };
 use datafusion_util::config::DEFAULT_SCHEMA;
+use iox_catalog::interface::Catalog;
 use iox_query::{
-    exec::{ExecutorType, IOxSessionContext},
+    exec::{ExecutorType, IOxSessionContext, QueryStats},
     QueryChunk, QueryCompletedToken, QueryNamespace, QueryText,
 };
 use observability_deps::tracing::{debug, trace};
 use std::{any::Any, collections::HashMap, sync::Arc};
+use tokio_util::sync::CancellationToken;
 use trace::ctx::SpanContext;
 
 #[async_trait]
@@ -48,6 +51,7 @@ impl QueryNamespace for QuerierNamespace {
                 filters,
                 ctx.child_span("QuerierNamespace chunks"),
                 projection,
+                ctx.query_stats().as_deref(),
             )
             .await?;
 
@@ -62,16 +66,48 @@ impl QueryNamespace for QuerierNamespace {
 
     fn record_query(
         &self,
+        ctx: &IOxSessionContext,
         span_ctx: Option<&SpanContext>,
         query_type: &'static str,
         query_text: QueryText,
-    ) -> QueryCompletedToken {
+    ) -> Result<QueryCompletedToken, DataFusionError> {
         // When the query token is dropped the query entry's completion time
         // will be set.
         let query_log = Arc::clone(&self.query_log);
         let trace_id = span_ctx.map(|ctx| ctx.trace_id);
-        let entry = query_log.push(self.id, query_type, query_text, trace_id);
-        QueryCompletedToken::new(move |success| query_log.set_completed(entry, success))
+        let entry = query_log.push(
+            self.id,
+            Arc::clone(&self.name),
+            query_type,
+            query_text,
+            trace_id,
+            ctx.query_stats().unwrap_or_default(),
+            ctx.cancellation_token().unwrap_or_default(),
+        );
+
+        // The permit is held until the query token is dropped.
+        let permit = match &self.query_semaphore {
+            Some(semaphore) => match Arc::clone(semaphore).try_acquire_owned() {
+                Ok(permit) => Some(permit),
+                Err(_) => {
+                    query_log.set_completed(entry, false);
+                    return Err(DataFusionError::ResourcesExhausted(format!(
+                        "too many concurrent queries against namespace '{}' (limit: {})",
+                        self.name,
+                        self.query_limits
+                            .max_concurrent_queries
+                            .map(|max| max.get())
+                            .unwrap_or_default(),
+                    )));
+                }
+            },
+            None => None,
+        };
+
+        Ok(QueryCompletedToken::new(move |success| {
+            drop(permit);
+            query_log.set_completed(entry, success)
+        }))
     }
 
     fn new_query_context(&self, span_ctx: Option<SpanContext>) -> IOxSessionContext {
@@ -79,12 +115,35 @@ impl QueryNamespace for QuerierNamespace {
             .exec
             .new_execution_config(ExecutorType::Query)
             .with_default_catalog(Arc::new(QuerierCatalogProvider::from_namespace(self)) as _)
-            .with_span_context(span_ctx);
+            .with_span_context(span_ctx)
+            .with_query_stats(Arc::new(QueryStats::default()))
+            .with_cancellation_token(CancellationToken::new());
 
         for (k, v) in self.datafusion_config.as_ref() {
             cfg = cfg.with_config_option(k, v);
         }
 
+        if let Some(router_connection) = &self.router_connection {
+            cfg = cfg
+                .with_query_writer(Arc::new(RouterWriter::new(
+                    Arc::clone(&self.name),
+                    Arc::clone(router_connection),
+                )))
+                .with_data_manager(Arc::new(QuerierDataManager::new(
+                    self.id,
+                    Arc::clone(&self.name),
+                    Arc::clone(router_connection),
+                )));
+        }
+
+        if let Some(max) = self.query_limits.max_query_memory_bytes {
+            cfg = cfg.with_memory_limit(max.get() as usize);
+        }
+
+        if let Some(timeout) = self.query_limits.query_timeout_ms {
+            cfg = cfg.with_timeout(timeout.as_duration());
+        }
+
         cfg.build()
     }
 }
@@ -96,6 +155,9 @@ pub struct QuerierCatalogProvider {
     /// A snapshot of all tables.
     tables: Arc<HashMap<Arc<str>, Arc<QuerierTable>>>,
 
+    /// Catalog, used by the catalog-backed system tables.
+    catalog: Arc<dyn Catalog>,
+
     /// Query log.
     query_log: Arc<QueryLog>,
 
@@ -108,6 +170,7 @@ impl QuerierCatalogProvider {
         Self {
             namespace_id: namespace.id,
             tables: Arc::clone(&namespace.tables),
+            catalog: namespace.catalog_cache.catalog(),
             query_log: Arc::clone(&namespace.query_log),
             include_debug_info_tables: namespace.include_debug_info_tables,
         }
@@ -129,6 +192,7 @@ impl CatalogProvider for QuerierCatalogProvider {
                 tables: Arc::clone(&self.tables),
             })),
             SYSTEM_SCHEMA => Some(Arc::new(SystemSchemaProvider::new(
+                Arc::clone(&self.catalog),
                 Arc::clone(&self.query_log),
                 self.namespace_id,
                 self.include_debug_info_tables,
@@ -168,17 +232,26 @@ impl SchemaProvider for UserSchemaProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::namespace::test_util::{clear_parquet_cache, querier_namespace};
+    use crate::{
+        cache::namespace::CachedTable,
+        ingester::{DynError, IngesterConnection, IngesterPartition},
+        namespace::test_util::{
+            clear_parquet_cache, querier_namespace, querier_namespace_with_ingester,
+        },
+    };
     use arrow::record_batch::RecordBatch;
     use arrow_util::test_util::{batches_to_sorted_lines, Normalizer};
-    use data_types::ColumnType;
+    use data_types::{ColumnType, QueryTimeout};
     use datafusion::common::DataFusionError;
     use iox_query::frontend::sql::SqlQueryPlanner;
     use iox_tests::{TestCatalog, TestParquetFileBuilder};
     use iox_time::Time;
     use metric::{Observation, RawReporter};
     use snafu::{ResultExt, Snafu};
-    use trace::{span::SpanStatus, RingBufferTraceCollector};
+    use trace::{
+        span::{Span, SpanStatus},
+        RingBufferTraceCollector,
+    };
 
     #[tokio::test]
     async fn test_query() {
@@ -529,6 +602,247 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_catalog_system_tables() {
+        test_helpers::maybe_start_logging();
+
+        let catalog = TestCatalog::new();
+
+        let ns = catalog.create_namespace_with_retention("ns", None).await;
+
+        let table_cpu = ns.create_table("cpu").await;
+        let table_mem = ns.create_table("mem").await;
+
+        table_cpu.create_column("host", ColumnType::Tag).await;
+        table_cpu.create_column("time", ColumnType::Time).await;
+        table_cpu.create_column("load", ColumnType::F64).await;
+        table_mem.create_column("host", ColumnType::Tag).await;
+        table_mem.create_column("time", ColumnType::Time).await;
+        table_mem.create_column("perc", ColumnType::F64).await;
+
+        let partition_cpu_a = table_cpu.create_partition("a").await;
+        let partition_cpu_b = table_cpu.create_partition("b").await;
+        let partition_mem_c = table_mem.create_partition("c").await;
+
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("cpu,host=a load=1 11")
+            .with_min_time(11)
+            .with_max_time(11);
+        partition_cpu_a.create_parquet_file(builder).await;
+
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("cpu,host=a load=2 22")
+            .with_min_time(22)
+            .with_max_time(22);
+        partition_cpu_a
+            .create_parquet_file(builder)
+            .await
+            .flag_for_delete() // not listed because of soft delete
+            .await;
+
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("mem,host=c perc=50 11")
+            .with_min_time(11)
+            .with_max_time(11);
+        partition_mem_c.create_parquet_file(builder).await;
+
+        catalog
+            .add_to_skipped_compaction(partition_cpu_b.partition.id, "too many files")
+            .await;
+
+        let querier_namespace = Arc::new(querier_namespace(&ns).await);
+
+        insta::assert_yaml_snapshot!(
+            format_query(&querier_namespace, "SELECT table_name FROM system.tables").await,
+            @r###"
+        ---
+        - +------------+
+        - "| table_name |"
+        - +------------+
+        - "| cpu        |"
+        - "| mem        |"
+        - +------------+
+        "###
+        );
+
+        insta::assert_yaml_snapshot!(
+            format_query(
+                &querier_namespace,
+                "SELECT column_name, column_type FROM system.columns WHERE table_name = 'cpu'",
+            ).await,
+            @r###"
+        ---
+        - +-------------+-------------+
+        - "| column_name | column_type |"
+        - +-------------+-------------+
+        - "| host        | tag         |"
+        - "| load        | f64         |"
+        - "| time        | time        |"
+        - +-------------+-------------+
+        "###
+        );
+
+        insta::assert_yaml_snapshot!(
+            format_query(
+                &querier_namespace,
+                "SELECT table_name, partition_key FROM system.partitions WHERE partition_key = 'a'",
+            ).await,
+            @r###"
+        ---
+        - +------------+---------------+
+        - "| table_name | partition_key |"
+        - +------------+---------------+
+        - "| cpu        | a             |"
+        - +------------+---------------+
+        "###
+        );
+
+        insta::assert_yaml_snapshot!(
+            format_query(
+                &querier_namespace,
+                "SELECT table_name, partition_key, row_count, compaction_level FROM system.parquet_files",
+            ).await,
+            @r###"
+        ---
+        - +------------+---------------+-----------+------------------+
+        - "| table_name | partition_key | row_count | compaction_level |"
+        - +------------+---------------+-----------+------------------+
+        - "| cpu        | a             | 1         | 0                |"
+        - "| mem        | c             | 1         | 0                |"
+        - +------------+---------------+-----------+------------------+
+        "###
+        );
+
+        insta::assert_yaml_snapshot!(
+            format_query(
+                &querier_namespace,
+                "SELECT table_name, partition_key FROM system.parquet_files WHERE partition_key = 'c'",
+            ).await,
+            @r###"
+        ---
+        - +------------+---------------+
+        - "| table_name | partition_key |"
+        - +------------+---------------+
+        - "| mem        | c             |"
+        - +------------+---------------+
+        "###
+        );
+
+        insta::assert_yaml_snapshot!(
+            format_query(
+                &querier_namespace,
+                "SELECT table_name, partition_key, reason FROM system.compactions_skipped",
+            ).await,
+            @r###"
+        ---
+        - +------------+---------------+----------------+
+        - "| table_name | partition_key | reason         |"
+        - +------------+---------------+----------------+
+        - "| cpu        | b             | too many files |"
+        - +------------+---------------+----------------+
+        "###
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_timeout_stalled_ingester() {
+        test_helpers::maybe_start_logging();
+
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_with_retention("ns", None).await;
+        let table = ns.create_table("cpu").await;
+        table.create_column("host", ColumnType::Tag).await;
+        table.create_column("time", ColumnType::Time).await;
+        table.create_column("load", ColumnType::F64).await;
+
+        let mut querier_namespace =
+            querier_namespace_with_ingester(&ns, Arc::new(StalledIngesterConnection)).await;
+        querier_namespace.query_limits.query_timeout_ms = Some(QueryTimeout::new(100));
+        let querier_namespace = Arc::new(querier_namespace);
+
+        // The ingester never responds while the query is planned, so the
+        // query fails once its timeout elapses.
+        let err = tokio::time::timeout(
+            std::time::Duration::from_secs(10),
+            run_res(&querier_namespace, "SELECT * FROM cpu", None),
+        )
+        .await
+        .expect("query should time out")
+        .unwrap_err();
+
+        let RunError::Build { source } = err else {
+            panic!("query should fail while planning: {err}");
+        };
+        assert_eq!(
+            service_common::datafusion_error_to_tonic_code(&source),
+            tonic::Code::DeadlineExceeded,
+            "unexpected error: {source}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_cancel_stalled_ingester() {
+        test_helpers::maybe_start_logging();
+
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_with_retention("ns", None).await;
+        let table = ns.create_table("cpu").await;
+        table.create_column("host", ColumnType::Tag).await;
+        table.create_column("time", ColumnType::Time).await;
+        table.create_column("load", ColumnType::F64).await;
+
+        let querier_namespace = Arc::new(
+            querier_namespace_with_ingester(&ns, Arc::new(StalledIngesterConnection)).await,
+        );
+
+        // The ingester never responds while the query is planned, so the
+        // query only ends once it is cancelled.
+        let ctx = querier_namespace.new_query_context(None);
+        let token = ctx
+            .cancellation_token()
+            .expect("query should be cancellable");
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            token.cancel();
+        });
+
+        let err = tokio::time::timeout(
+            std::time::Duration::from_secs(10),
+            SqlQueryPlanner::default().query("SELECT * FROM cpu", &ctx),
+        )
+        .await
+        .expect("query should be cancelled")
+        .unwrap_err();
+
+        assert_eq!(
+            service_common::datafusion_error_to_tonic_code(&err),
+            tonic::Code::Cancelled,
+            "unexpected error: {err}"
+        );
+    }
+
+    /// An [`IngesterConnection`] that never responds.
+    #[derive(Debug)]
+    struct StalledIngesterConnection;
+
+    #[async_trait]
+    impl IngesterConnection for StalledIngesterConnection {
+        async fn partitions(
+            &self,
+            _namespace_id: NamespaceId,
+            _cached_table: Arc<CachedTable>,
+            _columns: Vec<String>,
+            _filters: &[Expr],
+            _span: Option<Span>,
+        ) -> Result<Vec<IngesterPartition>, DynError> {
+            futures::future::pending().await
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
     async fn format_query(querier_namespace: &Arc<QuerierNamespace>, sql: &str) -> Vec<String> {
         format_query_with_span_ctx(querier_namespace, sql, None).await
     }
@@ -1,6 +1,7 @@
 use super::QuerierNamespace;
 use crate::{
-    cache::namespace::CachedNamespace, create_ingester_connection_for_testing, QuerierCatalogCache,
+    cache::namespace::CachedNamespace, create_ingester_connection_for_testing,
+    ingester::IngesterConnection, QuerierCatalogCache,
 };
 use data_types::TableId;
 use datafusion_util::config::register_iox_object_store;
@@ -11,6 +12,15 @@ use tokio::runtime::Handle;
 
 /// Create [`QuerierNamespace`] for testing.
 pub async fn querier_namespace(ns: &Arc<TestNamespace>) -> QuerierNamespace {
+    querier_namespace_with_ingester(ns, create_ingester_connection_for_testing()).await
+}
+
+/// Create [`QuerierNamespace`] for testing, which queries the ingesters
+/// through `ingester_connection`.
+pub async fn querier_namespace_with_ingester(
+    ns: &Arc<TestNamespace>,
+    ingester_connection: Arc<dyn IngesterConnection>,
+) -> QuerierNamespace {
     let mut repos = ns.catalog.catalog.repositories().await;
     let tables = repos
         .tables()
@@ -52,7 +62,7 @@ pub async fn querier_namespace(ns: &Arc<TestNamespace>) -> QuerierNamespace {
         ns.namespace.name.clone().into(),
         cached_ns,
         ns.catalog.exec(),
-        Some(create_ingester_connection_for_testing()),
+        Some(ingester_connection),
     )
 }
 
@@ -5,6 +5,7 @@ use futures::StreamExt;
 use hashbrown::HashSet;
 use iox_catalog::interface::Catalog;
 use parquet_file::chunk::ParquetChunk;
+use predicate::delete_predicate::TombstonePredicates;
 use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
 use schema::{sort::SortKeyBuilder, Schema};
 use trace::span::{Span, SpanRecorder};
@@ -57,6 +58,7 @@ impl ChunkAdapter {
         cached_table: Arc<CachedTable>,
         files: impl IntoIterator<Item = Arc<ParquetFile>> + Send,
         cached_partitions: &HashMap<TransitionPartitionId, Arc<CachedPartition>>,
+        tombstones: &TombstonePredicates,
         span: Option<Span>,
     ) -> Vec<QuerierParquetChunk> {
         let span_recorder = SpanRecorder::new(span);
@@ -124,7 +126,7 @@ impl ChunkAdapter {
                     let cached_partition = cached_partitions
                         .get(&file.file.partition_id)
                         .expect("filter files down to existing partitions");
-                    self.new_chunk(cached_table, file, schema, cached_partition)
+                    self.new_chunk(cached_table, file, schema, cached_partition, tombstones)
                 })
                 .collect()
         }
@@ -136,6 +138,7 @@ impl ChunkAdapter {
         parquet_file: PreparedParquetFile,
         schema: Schema,
         cached_partition: &CachedPartition,
+        tombstones: &TombstonePredicates,
     ) -> QuerierParquetChunk {
         // NOTE: Because we've looked up the sort key AFTER the namespace schema, it may contain columns for which we
         //       don't have any schema information yet. This is OK because we've ensured that all file columns are known
@@ -180,6 +183,8 @@ impl ChunkAdapter {
             partition_id: parquet_file.file.partition_id.clone(),
         });
 
+        let delete_predicates = tombstones.predicates_for(&parquet_file.file);
+
         let parquet_chunk = Arc::new(ParquetChunk::new(
             parquet_file.file,
             schema,
@@ -190,6 +195,7 @@ impl ChunkAdapter {
             parquet_chunk,
             meta,
             Arc::clone(&cached_partition.column_ranges),
+            delete_predicates,
         )
     }
 }
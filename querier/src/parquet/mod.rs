@@ -1,6 +1,6 @@
 //! Querier Chunks
 
-use data_types::{ChunkId, ChunkOrder, TransitionPartitionId};
+use data_types::{ChunkId, ChunkOrder, DeletePredicate, TransitionPartitionId};
 use datafusion::physical_plan::Statistics;
 use iox_query::chunk_statistics::{create_chunk_statistics, ColumnRanges};
 use parquet_file::chunk::ParquetChunk;
@@ -55,6 +55,9 @@ pub struct QuerierParquetChunk {
 
     /// Stats
     stats: Arc<Statistics>,
+
+    /// Delete predicates of the tombstones that apply to this chunk.
+    delete_predicates: Vec<Arc<DeletePredicate>>,
 }
 
 impl QuerierParquetChunk {
@@ -63,6 +66,7 @@ impl QuerierParquetChunk {
         parquet_chunk: Arc<ParquetChunk>,
         meta: Arc<QuerierParquetChunkMeta>,
         column_ranges: ColumnRanges,
+        delete_predicates: Vec<Arc<DeletePredicate>>,
     ) -> Self {
         let stats = Arc::new(create_chunk_statistics(
             parquet_chunk.rows() as u64,
@@ -75,6 +79,7 @@ impl QuerierParquetChunk {
             meta,
             parquet_chunk,
             stats,
+            delete_predicates,
         }
     }
 
@@ -257,11 +262,19 @@ pub mod tests {
                 .unwrap();
             let cached_partitions =
                 HashMap::from([(self.parquet_file.partition_id.clone(), cached_partition)]);
+            let tombstones = self
+                .adapter
+                .catalog_cache()
+                .tombstone()
+                .get(self.cached_table.id, None)
+                .await;
+            let tombstones = tombstones.predicates().unwrap();
             self.adapter
                 .new_chunks(
                     Arc::clone(&self.cached_table),
                     [Arc::clone(&self.parquet_file)],
                     &cached_partitions,
+                    tombstones,
                     None,
                 )
                 .await
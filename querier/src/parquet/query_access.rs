@@ -1,5 +1,5 @@
 use crate::parquet::QuerierParquetChunk;
-use data_types::{ChunkId, ChunkOrder, TransitionPartitionId};
+use data_types::{ChunkId, ChunkOrder, DeletePredicate, TransitionPartitionId};
 use datafusion::physical_plan::Statistics;
 use iox_query::{QueryChunk, QueryChunkData};
 use schema::{sort::SortKey, Schema};
@@ -42,6 +42,10 @@ impl QueryChunk for QuerierParquetChunk {
         self.meta().order()
     }
 
+    fn delete_predicates(&self) -> &[Arc<DeletePredicate>] {
+        &self.delete_predicates
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
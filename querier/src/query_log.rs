@@ -1,7 +1,7 @@
 //! Ring buffer of queries that have been run with some brief information
 
 use data_types::NamespaceId;
-use iox_query::QueryText;
+use iox_query::{exec::QueryStats, QueryText};
 use iox_time::{Time, TimeProvider};
 use observability_deps::tracing::warn;
 use parking_lot::Mutex;
@@ -10,16 +10,37 @@ use std::{
     sync::{atomic, Arc},
     time::Duration,
 };
+use tokio_util::sync::CancellationToken;
 use trace::ctx::TraceId;
+use uuid::Uuid;
 
 /// The query duration used for queries still running.
 const UNCOMPLETED_DURATION: i64 = -1;
 
+/// Outcome of [`QueryLog::cancel`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancelOutcome {
+    /// The query was running and has been cancelled.
+    Cancelled,
+
+    /// The query had already completed.
+    AlreadyCompleted,
+
+    /// There is no query with the given ID in the log.
+    NotFound,
+}
+
 /// Information about a single query that was executed
 pub struct QueryLogEntry {
+    /// Unique ID of the query, used to cancel it.
+    pub id: Uuid,
+
     /// Namespace ID.
     pub namespace_id: NamespaceId,
 
+    /// Namespace name.
+    pub namespace_name: Arc<str>,
+
     /// The type of query
     pub query_type: &'static str,
 
@@ -38,16 +59,26 @@ pub struct QueryLogEntry {
 
     /// If the query completed successfully
     pub success: atomic::AtomicBool,
+
+    /// Statistics about the query, updated while it runs.
+    pub stats: Arc<QueryStats>,
+
+    /// Token used to cancel the query.
+    cancellation_token: CancellationToken,
 }
 
 impl std::fmt::Debug for QueryLogEntry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("QueryLogEntry")
+            .field("id", &self.id)
+            .field("namespace_name", &self.namespace_name)
             .field("query_type", &self.query_type)
             .field("query_text", &self.query_text.to_string())
             .field("issue_time", &self.issue_time)
             .field("query_completed_duration", &self.query_completed_duration)
             .field("success", &self.success)
+            .field("stats", &self.stats)
+            .field("cancelled", &self.cancelled())
             .finish()
     }
 }
@@ -56,19 +87,26 @@ impl QueryLogEntry {
     /// Creates a new QueryLogEntry -- use `QueryLog::push` to add new entries to the log
     fn new(
         namespace_id: NamespaceId,
+        namespace_name: Arc<str>,
         query_type: &'static str,
         query_text: QueryText,
         trace_id: Option<TraceId>,
         issue_time: Time,
+        stats: Arc<QueryStats>,
+        cancellation_token: CancellationToken,
     ) -> Self {
         Self {
+            id: Uuid::new_v4(),
             namespace_id,
+            namespace_name,
             query_type,
             query_text,
             trace_id,
             issue_time,
             query_completed_duration: UNCOMPLETED_DURATION.into(),
             success: atomic::AtomicBool::new(false),
+            stats,
+            cancellation_token,
         }
     }
 
@@ -89,6 +127,21 @@ impl QueryLogEntry {
         self.success.load(atomic::Ordering::SeqCst)
     }
 
+    /// Returns true if the query was cancelled.
+    pub fn cancelled(&self) -> bool {
+        self.cancellation_token.is_cancelled()
+    }
+
+    /// Cancel the query, unless it has already completed.
+    pub fn cancel(&self) -> CancelOutcome {
+        if self.query_completed_duration().is_some() {
+            return CancelOutcome::AlreadyCompleted;
+        }
+
+        self.cancellation_token.cancel();
+        CancelOutcome::Cancelled
+    }
+
     /// Mark this entry complete as of `now`. `success` records if the
     /// entry is successful or not.
     pub fn set_completed(&self, now: Time, success: bool) {
@@ -128,16 +181,22 @@ impl QueryLog {
     pub fn push(
         &self,
         namespace_id: NamespaceId,
+        namespace_name: Arc<str>,
         query_type: &'static str,
         query_text: QueryText,
         trace_id: Option<TraceId>,
+        stats: Arc<QueryStats>,
+        cancellation_token: CancellationToken,
     ) -> Arc<QueryLogEntry> {
         let entry = Arc::new(QueryLogEntry::new(
             namespace_id,
+            namespace_name,
             query_type,
             query_text,
             trace_id,
             self.time_provider.now(),
+            stats,
+            cancellation_token,
         ));
 
         if self.max_size == 0 {
@@ -165,6 +224,19 @@ impl QueryLog {
     pub fn set_completed(&self, entry: Arc<QueryLogEntry>, success: bool) {
         entry.set_completed(self.time_provider.now(), success)
     }
+
+    /// Returns the query with the given ID, if it is still in the log.
+    pub fn get(&self, id: Uuid) -> Option<Arc<QueryLogEntry>> {
+        self.log.lock().iter().find(|entry| entry.id == id).cloned()
+    }
+
+    /// Cancel the query with the given ID.
+    pub fn cancel(&self, id: Uuid) -> CancelOutcome {
+        match self.get(id) {
+            Some(entry) => entry.cancel(),
+            None => CancelOutcome::NotFound,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -179,10 +251,13 @@ mod test_super {
 
         let entry = Arc::new(QueryLogEntry::new(
             NamespaceId::new(1),
+            "ns".into(),
             "sql",
             Box::new("SELECT 1"),
             None,
             time_provider.now(),
+            Default::default(),
+            CancellationToken::new(),
         ));
         // query has not completed
         assert_eq!(entry.query_completed_duration(), None);
@@ -205,4 +280,43 @@ mod test_super {
         );
         assert!(!entry.success());
     }
+
+    #[test]
+    fn test_query_log_cancel() {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_millis(100).unwrap()));
+        let query_log = QueryLog::new(10, Arc::clone(&time_provider) as _);
+
+        let token = CancellationToken::new();
+        let running = query_log.push(
+            NamespaceId::new(1),
+            "ns".into(),
+            "sql",
+            Box::new("SELECT 1"),
+            None,
+            Default::default(),
+            token.clone(),
+        );
+        let completed = query_log.push(
+            NamespaceId::new(1),
+            "ns".into(),
+            "sql",
+            Box::new("SELECT 2"),
+            None,
+            Default::default(),
+            CancellationToken::new(),
+        );
+        query_log.set_completed(Arc::clone(&completed), true);
+
+        assert_eq!(query_log.cancel(Uuid::new_v4()), CancelOutcome::NotFound);
+        assert_eq!(
+            query_log.cancel(completed.id),
+            CancelOutcome::AlreadyCompleted
+        );
+        assert!(!completed.cancelled());
+
+        assert!(!token.is_cancelled());
+        assert_eq!(query_log.cancel(running.id), CancelOutcome::Cancelled);
+        assert!(token.is_cancelled());
+        assert!(running.cancelled());
+    }
 }
@@ -0,0 +1,625 @@
+//! Writes data produced by queries, such as the InfluxQL `SELECT ... INTO`
+//! statement, to a router, and forwards data management operations, such as
+//! the InfluxQL `DELETE` statement, to it.
+
+use arrow::record_batch::RecordBatch;
+use arrow_flight::{encode::FlightDataEncoderBuilder, error::FlightError, FlightDescriptor};
+use async_trait::async_trait;
+use client_util::connection::{self, Connection};
+use data_types::{DeletePredicate, NamespaceId};
+use datafusion::error::DataFusionError;
+use futures::{stream, StreamExt, TryStreamExt};
+use generated_types::{
+    arrow::flight::protocol::sql::{
+        command_statement_ingest::{
+            table_definition_options::{TableExistsOption, TableNotExistOption},
+            TableDefinitionOptions,
+        },
+        CommandStatementIngest, COMMAND_STATEMENT_INGEST,
+    },
+    google::protobuf::Any,
+    protobuf_type_url,
+};
+use iox_query::exec::query_writer::QueryWriter;
+use observability_deps::tracing::debug;
+use prost::Message;
+use snafu::{ResultExt, Snafu};
+use std::sync::Arc;
+
+#[derive(Debug, Snafu)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[snafu(display("Failed to connect to router '{router_address}': {source}"))]
+    Connecting {
+        router_address: String,
+        source: connection::Error,
+    },
+
+    #[snafu(display(
+        "Failed to write to table '{table_name}' of namespace '{namespace}': {source}"
+    ))]
+    Write {
+        namespace: String,
+        table_name: String,
+        source: FlightError,
+    },
+
+    #[snafu(display(
+        "Failed to delete from table '{table_name}' of namespace {namespace_id}: {source}"
+    ))]
+    Delete {
+        namespace_id: NamespaceId,
+        table_name: String,
+        source: influxdb_iox_client::error::Error,
+    },
+
+    #[snafu(display("Failed to drop table '{table_name}' of namespace '{namespace}': {source}"))]
+    DropTable {
+        namespace: String,
+        table_name: String,
+        source: influxdb_iox_client::error::Error,
+    },
+}
+
+/// A connection to the router, which is established on first use.
+#[derive(Debug)]
+pub struct RouterConnection {
+    router_address: Arc<str>,
+    maybe_connection: tokio::sync::Mutex<Option<Connection>>,
+}
+
+impl RouterConnection {
+    /// Create a new connection to the router gRPC API at `router_address`
+    /// (e.g. "http://router:8081").
+    pub fn new(router_address: impl Into<Arc<str>>) -> Self {
+        Self {
+            router_address: router_address.into(),
+            maybe_connection: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// Return the underlying connection, creating it if needed.
+    async fn connect(&self) -> Result<Connection, Error> {
+        let mut maybe_connection = self.maybe_connection.lock().await;
+
+        let router_address = self.router_address.as_ref();
+
+        if let Some(connection) = maybe_connection.as_ref() {
+            return Ok(connection.clone());
+        }
+
+        debug!(%router_address, "Connecting to router");
+        let connection = connection::Builder::new()
+            .build(router_address)
+            .await
+            .context(ConnectingSnafu { router_address })?;
+
+        *maybe_connection = Some(connection.clone());
+        Ok(connection)
+    }
+
+    /// Write `batches` to `table_name` within `namespace` using a Flight
+    /// `DoPut` request, sending `authorization` as the `authorization`
+    /// header if set.
+    async fn write(
+        &self,
+        namespace: &str,
+        table_name: &str,
+        batches: Vec<RecordBatch>,
+        authorization: Option<&str>,
+    ) -> Result<(), Error> {
+        let connection = self.connect().await?;
+        let mut client = influxdb_iox_client::flight::Client::new(connection).into_inner();
+        client
+            .add_header("database", namespace)
+            .context(WriteSnafu {
+                namespace,
+                table_name,
+            })?;
+        if let Some(authorization) = authorization {
+            client
+                .add_header("authorization", authorization)
+                .context(WriteSnafu {
+                    namespace,
+                    table_name,
+                })?;
+        }
+
+        let mut data: Vec<_> = FlightDataEncoderBuilder::new()
+            .build(stream::iter(batches.into_iter().map(Ok)))
+            .try_collect()
+            .await
+            .context(WriteSnafu {
+                namespace,
+                table_name,
+            })?;
+
+        // The write is a FlightSQL bulk ingestion into the table, whose
+        // command is passed in the descriptor of the first message. Tables
+        // and columns are created by the router as needed.
+        if let Some(first) = data.first_mut() {
+            let cmd = CommandStatementIngest {
+                table_definition_options: Some(TableDefinitionOptions {
+                    if_not_exist: TableNotExistOption::Create as i32,
+                    if_exists: TableExistsOption::Append as i32,
+                }),
+                table: table_name.to_string(),
+                ..Default::default()
+            };
+            let cmd = Any {
+                type_url: protobuf_type_url(COMMAND_STATEMENT_INGEST),
+                value: cmd.encode_to_vec().into(),
+            };
+            first.flight_descriptor = Some(FlightDescriptor::new_cmd(cmd.encode_to_vec()));
+        }
+
+        let mut results = client
+            .do_put(stream::iter(data.into_iter().map(Ok)))
+            .await
+            .context(WriteSnafu {
+                namespace,
+                table_name,
+            })?;
+        while let Some(result) = results.next().await {
+            result.context(WriteSnafu {
+                namespace,
+                table_name,
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Delete the rows matching `predicate` from `table_name` within the
+    /// namespace `namespace_id`.
+    ///
+    /// The router applies the delete to the ingesters and records a
+    /// tombstone for it, as for deletes made through its HTTP API.
+    pub(crate) async fn delete(
+        &self,
+        namespace_id: NamespaceId,
+        table_name: &str,
+        predicate: &DeletePredicate,
+    ) -> Result<(), Error> {
+        let connection = self.connect().await?;
+        influxdb_iox_client::delete::Client::new(connection)
+            .delete(namespace_id.get(), table_name, predicate.into())
+            .await
+            .context(DeleteSnafu {
+                namespace_id,
+                table_name,
+            })
+    }
+
+    /// Soft-delete the table `table_name` within `namespace`.
+    ///
+    /// The router notifies its deletion observers, invalidating the cached
+    /// schemas and dropping the data buffered by the ingesters. Dropping a
+    /// table that does not exist is not an error.
+    pub(crate) async fn drop_table(&self, namespace: &str, table_name: &str) -> Result<(), Error> {
+        let connection = self.connect().await?;
+        match influxdb_iox_client::table::Client::new(connection)
+            .delete_table(namespace, table_name)
+            .await
+        {
+            Ok(()) | Err(influxdb_iox_client::error::Error::NotFound(_)) => Ok(()),
+            Err(source) => Err(Error::DropTable {
+                namespace: namespace.to_string(),
+                table_name: table_name.to_string(),
+                source,
+            }),
+        }
+    }
+}
+
+/// A [`QueryWriter`] for queries of a single namespace, which writes to the
+/// router.
+#[derive(Debug)]
+pub(crate) struct RouterWriter {
+    namespace_name: Arc<str>,
+    connection: Arc<RouterConnection>,
+}
+
+impl RouterWriter {
+    pub(crate) fn new(namespace_name: Arc<str>, connection: Arc<RouterConnection>) -> Self {
+        Self {
+            namespace_name,
+            connection,
+        }
+    }
+}
+
+#[async_trait]
+impl QueryWriter for RouterWriter {
+    fn namespace_name(&self) -> &str {
+        &self.namespace_name
+    }
+
+    async fn write(
+        &self,
+        namespace: &str,
+        table_name: &str,
+        batches: Vec<RecordBatch>,
+        authorization: Option<&str>,
+    ) -> Result<(), DataFusionError> {
+        debug!(%namespace, %table_name, "Writing query results to router");
+
+        self.connection
+            .write(namespace, table_name, batches, authorization)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{ArrayRef, Float64Array};
+    use arrow_flight::{
+        decode::FlightRecordBatchStream,
+        flight_service_server::{FlightService, FlightServiceServer},
+        Action, ActionType, Criteria, Empty, FlightData, FlightInfo, HandshakeRequest,
+        HandshakeResponse, PutResult, SchemaResult, Ticket,
+    };
+    use arrow_util::assert_batches_eq;
+    use assert_matches::assert_matches;
+    use data_types::TimestampRange;
+    use futures::stream::BoxStream;
+    use generated_types::{
+        influxdata::iox::{
+            delete::v1::{
+                delete_service_server::{DeleteService, DeleteServiceServer},
+                DeleteRequest, DeleteResponse,
+            },
+            table::v1::{
+                table_service_server::{TableService, TableServiceServer},
+                CreateTableRequest, CreateTableResponse, DeleteColumnRequest, DeleteColumnResponse,
+                DeleteTableRequest, DeleteTableResponse, GetTablesRequest, GetTablesResponse,
+            },
+        },
+        protobuf_type_url_eq,
+    };
+    use parking_lot::Mutex;
+    use tokio::net::TcpListener;
+    use tonic::{
+        transport::{server::TcpIncoming, Server},
+        Request, Response, Status, Streaming,
+    };
+
+    #[tokio::test]
+    async fn test_write() {
+        let router = MockRouter::start().await;
+        let writer = RouterWriter::new(
+            Arc::from("bananas"),
+            Arc::new(RouterConnection::new(router.address.clone())),
+        );
+        assert_eq!(writer.namespace_name(), "bananas");
+
+        writer
+            .write(
+                "platanos",
+                "cpu",
+                vec![batch(&[1.0, 2.0])],
+                Some("Token t1"),
+            )
+            .await
+            .unwrap();
+        writer
+            .write("bananas", "mem", vec![batch(&[3.0])], None)
+            .await
+            .unwrap();
+
+        let writes = router.writes.lock();
+        assert_eq!(writes.len(), 2);
+
+        assert_eq!(writes[0].namespace, "platanos");
+        assert_eq!(writes[0].table, "cpu");
+        assert_eq!(writes[0].authorization.as_deref(), Some("Token t1"));
+        assert_batches_eq!(
+            ["+-----+", "| val |", "+-----+", "| 1.0 |", "| 2.0 |", "+-----+",],
+            &writes[0].batches
+        );
+
+        assert_eq!(writes[1].namespace, "bananas");
+        assert_eq!(writes[1].table, "mem");
+        assert_eq!(writes[1].authorization, None);
+        assert_batches_eq!(
+            ["+-----+", "| val |", "+-----+", "| 3.0 |", "+-----+"],
+            &writes[1].batches
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_rejected() {
+        let router = MockRouter::start().await;
+        let writer = RouterWriter::new(
+            Arc::from("bananas"),
+            Arc::new(RouterConnection::new(router.address.clone())),
+        );
+
+        let err = writer
+            .write("bananas", "rejected", vec![batch(&[1.0])], None)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Failed to write to table 'rejected' of namespace 'bananas'"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete() {
+        let router = MockRouter::start().await;
+        let connection = RouterConnection::new(router.address.clone());
+
+        let predicate = DeletePredicate {
+            range: TimestampRange::new(1, 2),
+            exprs: vec![],
+        };
+        connection
+            .delete(NamespaceId::new(42), "cpu", &predicate)
+            .await
+            .unwrap();
+
+        let deletes = router.deletes.lock();
+        assert_matches!(deletes.as_slice(), [DeleteRequest { payload: Some(p) }] => {
+            assert_eq!(p.database_id, 42);
+            assert_eq!(p.table_name, "cpu");
+            assert_eq!(
+                DeletePredicate::try_from(p.predicate.clone().unwrap()).unwrap(),
+                predicate
+            );
+        });
+    }
+
+    #[tokio::test]
+    async fn test_drop_table() {
+        let router = MockRouter::start().await;
+        let connection = RouterConnection::new(router.address.clone());
+
+        connection.drop_table("bananas", "cpu").await.unwrap();
+        // Dropping a table that does not exist succeeds.
+        connection.drop_table("bananas", "missing").await.unwrap();
+
+        let err = connection
+            .drop_table("bananas", "rejected")
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Failed to drop table 'rejected' of namespace 'bananas'"),
+            "unexpected error: {err}"
+        );
+
+        assert_eq!(
+            *router.dropped_tables.lock(),
+            vec![
+                ("bananas".to_string(), "cpu".to_string()),
+                ("bananas".to_string(), "missing".to_string()),
+                ("bananas".to_string(), "rejected".to_string()),
+            ]
+        );
+    }
+
+    fn batch(values: &[f64]) -> RecordBatch {
+        RecordBatch::try_from_iter([(
+            "val",
+            Arc::new(Float64Array::from(values.to_vec())) as ArrayRef,
+        )])
+        .unwrap()
+    }
+
+    /// A `DoPut` request received by a [`MockRouter`].
+    #[derive(Debug)]
+    struct Write {
+        namespace: String,
+        table: String,
+        authorization: Option<String>,
+        batches: Vec<RecordBatch>,
+    }
+
+    /// A router recording the Flight `DoPut`, delete and drop table requests
+    /// it receives, which rejects requests for the table "rejected", and
+    /// reports the table "missing" as not found.
+    #[derive(Debug, Clone, Default)]
+    struct MockRouter {
+        address: String,
+        writes: Arc<Mutex<Vec<Write>>>,
+        deletes: Arc<Mutex<Vec<DeleteRequest>>>,
+        dropped_tables: Arc<Mutex<Vec<(String, String)>>>,
+    }
+
+    impl MockRouter {
+        async fn start() -> Self {
+            let listener = TcpListener::bind("localhost:0").await.unwrap();
+            let address = format!("http://{}", listener.local_addr().unwrap());
+            let incoming = TcpIncoming::from_listener(listener, false, None).unwrap();
+
+            let router = Self {
+                address,
+                ..Default::default()
+            };
+            tokio::spawn(
+                Server::builder()
+                    .add_service(FlightServiceServer::new(router.clone()))
+                    .add_service(DeleteServiceServer::new(router.clone()))
+                    .add_service(TableServiceServer::new(router.clone()))
+                    .serve_with_incoming(incoming),
+            );
+            router
+        }
+    }
+
+    type MockStream<T> = BoxStream<'static, Result<T, Status>>;
+
+    #[async_trait]
+    impl DeleteService for MockRouter {
+        async fn delete(
+            &self,
+            request: Request<DeleteRequest>,
+        ) -> Result<Response<DeleteResponse>, Status> {
+            self.deletes.lock().push(request.into_inner());
+            Ok(Response::new(DeleteResponse {}))
+        }
+    }
+
+    #[async_trait]
+    impl TableService for MockRouter {
+        async fn get_tables(
+            &self,
+            _request: Request<GetTablesRequest>,
+        ) -> Result<Response<GetTablesResponse>, Status> {
+            Err(Status::unimplemented("get_tables"))
+        }
+
+        async fn create_table(
+            &self,
+            _request: Request<CreateTableRequest>,
+        ) -> Result<Response<CreateTableResponse>, Status> {
+            Err(Status::unimplemented("create_table"))
+        }
+
+        async fn delete_table(
+            &self,
+            request: Request<DeleteTableRequest>,
+        ) -> Result<Response<DeleteTableResponse>, Status> {
+            let DeleteTableRequest {
+                namespace_name,
+                table_name,
+            } = request.into_inner();
+            self.dropped_tables
+                .lock()
+                .push((namespace_name, table_name.clone()));
+
+            match table_name.as_str() {
+                "missing" => Err(Status::not_found("missing")),
+                "rejected" => Err(Status::internal("rejected")),
+                _ => Ok(Response::new(DeleteTableResponse {})),
+            }
+        }
+
+        async fn delete_column(
+            &self,
+            _request: Request<DeleteColumnRequest>,
+        ) -> Result<Response<DeleteColumnResponse>, Status> {
+            Err(Status::unimplemented("delete_column"))
+        }
+    }
+
+    #[async_trait]
+    impl FlightService for MockRouter {
+        type HandshakeStream = MockStream<HandshakeResponse>;
+        type ListFlightsStream = MockStream<FlightInfo>;
+        type DoGetStream = MockStream<FlightData>;
+        type DoPutStream = MockStream<PutResult>;
+        type DoActionStream = MockStream<arrow_flight::Result>;
+        type ListActionsStream = MockStream<ActionType>;
+        type DoExchangeStream = MockStream<FlightData>;
+
+        async fn do_put(
+            &self,
+            request: Request<Streaming<FlightData>>,
+        ) -> Result<Response<Self::DoPutStream>, Status> {
+            let header = |key: &str| {
+                request
+                    .metadata()
+                    .get(key)
+                    .map(|v| v.to_str().unwrap().to_owned())
+            };
+            let namespace = header("database").expect("no database header");
+            let authorization = header("authorization");
+            let data: Vec<FlightData> = request.into_inner().try_collect().await?;
+
+            let descriptor = data
+                .first()
+                .and_then(|d| d.flight_descriptor.clone())
+                .expect("no flight descriptor");
+            let cmd = Any::decode(descriptor.cmd).unwrap();
+            assert!(protobuf_type_url_eq(
+                &cmd.type_url,
+                COMMAND_STATEMENT_INGEST
+            ));
+            let cmd = CommandStatementIngest::decode(cmd.value).unwrap();
+            let options = cmd.table_definition_options.unwrap();
+            assert_eq!(options.if_not_exist(), TableNotExistOption::Create);
+            assert_eq!(options.if_exists(), TableExistsOption::Append);
+            if cmd.table == "rejected" {
+                return Err(Status::invalid_argument("rejected"));
+            }
+
+            let batches = FlightRecordBatchStream::new_from_flight_data(stream::iter(
+                data.into_iter().map(Ok),
+            ))
+            .try_collect()
+            .await?;
+
+            self.writes.lock().push(Write {
+                namespace,
+                table: cmd.table,
+                authorization,
+                batches,
+            });
+
+            Ok(Response::new(Box::pin(stream::iter([Ok(
+                PutResult::default(),
+            )]))))
+        }
+
+        async fn handshake(
+            &self,
+            _request: Request<Streaming<HandshakeRequest>>,
+        ) -> Result<Response<Self::HandshakeStream>, Status> {
+            Err(Status::unimplemented("handshake"))
+        }
+
+        async fn list_flights(
+            &self,
+            _request: Request<Criteria>,
+        ) -> Result<Response<Self::ListFlightsStream>, Status> {
+            Err(Status::unimplemented("list_flights"))
+        }
+
+        async fn get_flight_info(
+            &self,
+            _request: Request<FlightDescriptor>,
+        ) -> Result<Response<FlightInfo>, Status> {
+            Err(Status::unimplemented("get_flight_info"))
+        }
+
+        async fn get_schema(
+            &self,
+            _request: Request<FlightDescriptor>,
+        ) -> Result<Response<SchemaResult>, Status> {
+            Err(Status::unimplemented("get_schema"))
+        }
+
+        async fn do_get(
+            &self,
+            _request: Request<Ticket>,
+        ) -> Result<Response<Self::DoGetStream>, Status> {
+            Err(Status::unimplemented("do_get"))
+        }
+
+        async fn do_action(
+            &self,
+            _request: Request<Action>,
+        ) -> Result<Response<Self::DoActionStream>, Status> {
+            Err(Status::unimplemented("do_action"))
+        }
+
+        async fn list_actions(
+            &self,
+            _request: Request<Empty>,
+        ) -> Result<Response<Self::ListActionsStream>, Status> {
+            Err(Status::unimplemented("list_actions"))
+        }
+
+        async fn do_exchange(
+            &self,
+            _request: Request<Streaming<FlightData>>,
+        ) -> Result<Response<Self::DoExchangeStream>, Status> {
+            Err(Status::unimplemented("do_exchange"))
+        }
+    }
+}
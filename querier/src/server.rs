@@ -106,6 +106,7 @@ mod tests {
                     Arc::clone(&metric_registry),
                     exec,
                     Some(create_ingester_connection_for_testing()),
+                    None,
                     QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX,
                     Arc::new(HashMap::default()),
                 )
@@ -0,0 +1,1296 @@
+//! System tables that expose the catalog state of a namespace.
+
+use arrow::{
+    array::{ArrayRef, Int16Array, Int64Array, StringArray, TimestampNanosecondArray},
+    datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit},
+    record_batch::RecordBatch,
+};
+use async_trait::async_trait;
+use data_types::{
+    NamespaceId, ParquetFile, Partition, PartitionKey, Table, TableId, TransitionPartitionId,
+};
+use datafusion::{
+    datasource::TableProvider,
+    error::{DataFusionError, Result as DataFusionResult},
+    execution::context::SessionState,
+    logical_expr::{BinaryExpr, Operator, TableProviderFilterPushDown, TableType},
+    physical_plan::{memory::MemoryExec, ExecutionPlan},
+    prelude::Expr,
+    scalar::ScalarValue,
+};
+use iox_catalog::interface::{Catalog, RepoCollection};
+use std::{any::Any, collections::HashMap, sync::Arc};
+
+use super::{
+    COLUMNS_TABLE, COMPACTIONS_SKIPPED_TABLE, PARQUET_FILES_TABLE, PARTITIONS_TABLE, TABLES_TABLE,
+};
+
+/// Column holding the table name; equality filters on it are pushed down into the catalog.
+const TABLE_NAME_COLUMN: &str = "table_name";
+
+/// Column holding the partition key; equality filters on it are pushed down into the catalog.
+const PARTITION_KEY_COLUMN: &str = "partition_key";
+
+/// Restrictions extracted from the filters of a scan.
+///
+/// Only equality comparisons between a column and a string literal are recognized. Everything
+/// else is left to DataFusion, which also re-applies the recognized filters, so a filter that is
+/// ignored here never produces wrong results.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct CatalogFilters {
+    table_name: Option<String>,
+    partition_key: Option<String>,
+}
+
+impl CatalogFilters {
+    fn new(filters: &[Expr]) -> Self {
+        let mut this = Self::default();
+
+        for (column, value) in filters.iter().filter_map(column_equals_literal) {
+            match column {
+                TABLE_NAME_COLUMN => {
+                    this.table_name.get_or_insert_with(|| value.to_owned());
+                }
+                PARTITION_KEY_COLUMN => {
+                    this.partition_key.get_or_insert_with(|| value.to_owned());
+                }
+                _ => {}
+            }
+        }
+
+        this
+    }
+}
+
+/// Returns the column name and value if `expr` is `<column> = '<value>'` or `'<value>' = <column>`.
+fn column_equals_literal(expr: &Expr) -> Option<(&str, &str)> {
+    let Expr::BinaryExpr(BinaryExpr {
+        left,
+        op: Operator::Eq,
+        right,
+    }) = expr
+    else {
+        return None;
+    };
+
+    match (left.as_ref(), right.as_ref()) {
+        (Expr::Column(column), Expr::Literal(ScalarValue::Utf8(Some(value))))
+        | (Expr::Literal(ScalarValue::Utf8(Some(value))), Expr::Column(column)) => {
+            Some((column.name.as_str(), value.as_str()))
+        }
+        _ => None,
+    }
+}
+
+/// A system table whose contents are read from the catalog when it is scanned.
+#[async_trait]
+trait CatalogSystemTable: Send + Sync {
+    /// Produce the schema from this system table.
+    fn schema(&self) -> SchemaRef;
+
+    /// Columns for which equality filters are pushed down into [`scan`](Self::scan).
+    fn filter_columns(&self) -> &'static [&'static str];
+
+    /// Read the contents of the system table for the given namespace.
+    async fn scan(
+        &self,
+        repos: &mut dyn RepoCollection,
+        namespace_id: NamespaceId,
+        filters: &CatalogFilters,
+    ) -> DataFusionResult<RecordBatch>;
+}
+
+/// Adapter that makes any [`CatalogSystemTable`] a DataFusion [`TableProvider`].
+struct CatalogSystemTableProvider<T> {
+    table: T,
+    catalog: Arc<dyn Catalog>,
+    namespace_id: NamespaceId,
+}
+
+#[async_trait]
+impl<T> TableProvider for CatalogSystemTableProvider<T>
+where
+    T: CatalogSystemTable + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.table.schema()
+    }
+
+    async fn scan(
+        &self,
+        _ctx: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        let filters = CatalogFilters::new(filters);
+
+        let mut repos = self.catalog.repositories().await;
+        let batch = self
+            .table
+            .scan(repos.as_mut(), self.namespace_id, &filters)
+            .await?;
+
+        Ok(Arc::new(MemoryExec::try_new(
+            &[vec![batch]],
+            self.table.schema(),
+            projection.cloned(),
+        )?))
+    }
+
+    fn supports_filter_pushdown(
+        &self,
+        filter: &Expr,
+    ) -> DataFusionResult<TableProviderFilterPushDown> {
+        match column_equals_literal(filter) {
+            Some((column, _)) if self.table.filter_columns().contains(&column) => {
+                Ok(TableProviderFilterPushDown::Inexact)
+            }
+            _ => Ok(TableProviderFilterPushDown::Unsupported),
+        }
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+}
+
+/// Create the providers for all catalog-backed system tables, keyed by table name.
+pub(super) fn catalog_tables(
+    catalog: Arc<dyn Catalog>,
+    namespace_id: NamespaceId,
+) -> Vec<(&'static str, Arc<dyn TableProvider>)> {
+    vec![
+        (
+            TABLES_TABLE,
+            provider(TablesTable::new(), &catalog, namespace_id),
+        ),
+        (
+            COLUMNS_TABLE,
+            provider(ColumnsTable::new(), &catalog, namespace_id),
+        ),
+        (
+            PARTITIONS_TABLE,
+            provider(PartitionsTable::new(), &catalog, namespace_id),
+        ),
+        (
+            PARQUET_FILES_TABLE,
+            provider(ParquetFilesTable::new(), &catalog, namespace_id),
+        ),
+        (
+            COMPACTIONS_SKIPPED_TABLE,
+            provider(CompactionsSkippedTable::new(), &catalog, namespace_id),
+        ),
+    ]
+}
+
+fn provider<T>(
+    table: T,
+    catalog: &Arc<dyn Catalog>,
+    namespace_id: NamespaceId,
+) -> Arc<dyn TableProvider>
+where
+    T: CatalogSystemTable + 'static,
+{
+    Arc::new(CatalogSystemTableProvider {
+        table,
+        catalog: Arc::clone(catalog),
+        namespace_id,
+    })
+}
+
+fn catalog_error(e: iox_catalog::interface::Error) -> DataFusionError {
+    DataFusionError::External(Box::new(e))
+}
+
+fn timestamp_type() -> DataType {
+    DataType::Timestamp(TimeUnit::Nanosecond, None)
+}
+
+/// List the tables of the namespace that match `filters`, sorted by name.
+async fn list_tables(
+    repos: &mut dyn RepoCollection,
+    namespace_id: NamespaceId,
+    filters: &CatalogFilters,
+) -> DataFusionResult<Vec<Table>> {
+    let mut tables = match &filters.table_name {
+        Some(name) => repos
+            .tables()
+            .get_by_namespace_and_name(namespace_id, name)
+            .await
+            .map_err(catalog_error)?
+            .into_iter()
+            .collect(),
+        None => repos
+            .tables()
+            .list_by_namespace_id(namespace_id)
+            .await
+            .map_err(catalog_error)?,
+    };
+    tables.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(tables)
+}
+
+/// List the partitions of `tables` that match `filters`, sorted by table and partition key.
+async fn list_partitions<'a>(
+    repos: &mut dyn RepoCollection,
+    tables: &'a [Table],
+    filters: &CatalogFilters,
+) -> DataFusionResult<Vec<(&'a Table, Partition)>> {
+    let mut partitions = vec![];
+
+    match &filters.partition_key {
+        Some(key) => {
+            let table_ids = tables.iter().map(|t| t.id).collect::<Vec<_>>();
+            let tables_by_id: HashMap<TableId, &Table> = tables.iter().map(|t| (t.id, t)).collect();
+
+            let key_partitions = if table_ids.is_empty() {
+                vec![]
+            } else {
+                repos
+                    .partitions()
+                    .list_by_table_ids_and_key(&table_ids, &PartitionKey::from(key.as_str()))
+                    .await
+                    .map_err(catalog_error)?
+            };
+
+            partitions.extend(
+                key_partitions
+                    .into_iter()
+                    .filter_map(|p| Some((*tables_by_id.get(&p.table_id)?, p))),
+            );
+            // tables are sorted by name, and each has at most one partition with the key
+            partitions.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+        }
+        None => {
+            for table in tables {
+                let mut table_partitions = repos
+                    .partitions()
+                    .list_by_table_id(table.id)
+                    .await
+                    .map_err(catalog_error)?;
+                table_partitions.sort_by(|a, b| a.partition_key.cmp(&b.partition_key));
+
+                partitions.extend(table_partitions.into_iter().map(|p| (table, p)));
+            }
+        }
+    }
+
+    Ok(partitions)
+}
+
+/// Implementation of system.tables table
+#[derive(Debug)]
+struct TablesTable {
+    schema: SchemaRef,
+}
+
+impl TablesTable {
+    fn new() -> Self {
+        Self {
+            schema: Arc::new(Schema::new(vec![
+                Field::new("table_id", DataType::Int64, false),
+                Field::new(TABLE_NAME_COLUMN, DataType::Utf8, false),
+            ])),
+        }
+    }
+}
+
+#[async_trait]
+impl CatalogSystemTable for TablesTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn filter_columns(&self) -> &'static [&'static str] {
+        &[TABLE_NAME_COLUMN]
+    }
+
+    async fn scan(
+        &self,
+        repos: &mut dyn RepoCollection,
+        namespace_id: NamespaceId,
+        filters: &CatalogFilters,
+    ) -> DataFusionResult<RecordBatch> {
+        let tables = list_tables(repos, namespace_id, filters).await?;
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(
+                tables
+                    .iter()
+                    .map(|t| Some(t.id.get()))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                tables
+                    .iter()
+                    .map(|t| Some(t.name.as_str()))
+                    .collect::<StringArray>(),
+            ),
+        ];
+
+        Ok(RecordBatch::try_new(self.schema(), columns)?)
+    }
+}
+
+/// Implementation of system.columns table
+#[derive(Debug)]
+struct ColumnsTable {
+    schema: SchemaRef,
+}
+
+impl ColumnsTable {
+    fn new() -> Self {
+        Self {
+            schema: Arc::new(Schema::new(vec![
+                Field::new("table_id", DataType::Int64, false),
+                Field::new(TABLE_NAME_COLUMN, DataType::Utf8, false),
+                Field::new("column_id", DataType::Int64, false),
+                Field::new("column_name", DataType::Utf8, false),
+                Field::new("column_type", DataType::Utf8, false),
+            ])),
+        }
+    }
+}
+
+#[async_trait]
+impl CatalogSystemTable for ColumnsTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn filter_columns(&self) -> &'static [&'static str] {
+        &[TABLE_NAME_COLUMN]
+    }
+
+    async fn scan(
+        &self,
+        repos: &mut dyn RepoCollection,
+        namespace_id: NamespaceId,
+        filters: &CatalogFilters,
+    ) -> DataFusionResult<RecordBatch> {
+        let tables = list_tables(repos, namespace_id, filters).await?;
+
+        let mut columns = vec![];
+        for table in &tables {
+            let mut table_columns = repos
+                .columns()
+                .list_by_table_id(table.id)
+                .await
+                .map_err(catalog_error)?;
+            table_columns.sort_by(|a, b| a.name.cmp(&b.name));
+
+            columns.extend(table_columns.into_iter().map(|c| (table, c)));
+        }
+
+        let arrays: Vec<ArrayRef> = vec![
+            Arc::new(
+                columns
+                    .iter()
+                    .map(|(t, _)| Some(t.id.get()))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                columns
+                    .iter()
+                    .map(|(t, _)| Some(t.name.as_str()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                columns
+                    .iter()
+                    .map(|(_, c)| Some(c.id.get()))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                columns
+                    .iter()
+                    .map(|(_, c)| Some(c.name.as_str()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                columns
+                    .iter()
+                    .map(|(_, c)| Some(c.column_type.as_str()))
+                    .collect::<StringArray>(),
+            ),
+        ];
+
+        Ok(RecordBatch::try_new(self.schema(), arrays)?)
+    }
+}
+
+/// Implementation of system.partitions table
+#[derive(Debug)]
+struct PartitionsTable {
+    schema: SchemaRef,
+}
+
+impl PartitionsTable {
+    fn new() -> Self {
+        Self {
+            schema: Arc::new(Schema::new(vec![
+                Field::new(TABLE_NAME_COLUMN, DataType::Utf8, false),
+                Field::new(PARTITION_KEY_COLUMN, DataType::Utf8, false),
+                Field::new("partition_id", DataType::Int64, false),
+                Field::new("partition_hash_id", DataType::Utf8, true),
+                Field::new("sort_key", DataType::Utf8, true),
+                Field::new("new_file_at", timestamp_type(), true),
+            ])),
+        }
+    }
+}
+
+#[async_trait]
+impl CatalogSystemTable for PartitionsTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn filter_columns(&self) -> &'static [&'static str] {
+        &[TABLE_NAME_COLUMN, PARTITION_KEY_COLUMN]
+    }
+
+    async fn scan(
+        &self,
+        repos: &mut dyn RepoCollection,
+        namespace_id: NamespaceId,
+        filters: &CatalogFilters,
+    ) -> DataFusionResult<RecordBatch> {
+        let tables = list_tables(repos, namespace_id, filters).await?;
+        let partitions = list_partitions(repos, &tables, filters).await?;
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(
+                partitions
+                    .iter()
+                    .map(|(t, _)| Some(t.name.as_str()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                partitions
+                    .iter()
+                    .map(|(_, p)| Some(p.partition_key.inner()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                partitions
+                    .iter()
+                    .map(|(_, p)| Some(p.id.get()))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                partitions
+                    .iter()
+                    .map(|(_, p)| p.hash_id().map(|id| id.to_string()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                partitions
+                    .iter()
+                    .map(|(_, p)| p.sort_key.as_ref().map(|key| key.join(",")))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                partitions
+                    .iter()
+                    .map(|(_, p)| p.new_file_at.map(|ts| ts.get()))
+                    .collect::<TimestampNanosecondArray>(),
+            ),
+        ];
+
+        Ok(RecordBatch::try_new(self.schema(), columns)?)
+    }
+}
+
+/// Implementation of system.parquet_files table
+#[derive(Debug)]
+struct ParquetFilesTable {
+    schema: SchemaRef,
+}
+
+impl ParquetFilesTable {
+    fn new() -> Self {
+        Self {
+            schema: Arc::new(Schema::new(vec![
+                Field::new(TABLE_NAME_COLUMN, DataType::Utf8, false),
+                Field::new(PARTITION_KEY_COLUMN, DataType::Utf8, false),
+                Field::new("partition_id", DataType::Utf8, false),
+                Field::new("parquet_file_id", DataType::Int64, false),
+                Field::new("object_store_id", DataType::Utf8, false),
+                Field::new("compaction_level", DataType::Int16, false),
+                Field::new("min_time", timestamp_type(), false),
+                Field::new("max_time", timestamp_type(), false),
+                Field::new("row_count", DataType::Int64, false),
+                Field::new("file_size_bytes", DataType::Int64, false),
+                Field::new("created_at", timestamp_type(), false),
+                Field::new("max_l0_created_at", timestamp_type(), false),
+            ])),
+        }
+    }
+}
+
+#[async_trait]
+impl CatalogSystemTable for ParquetFilesTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn filter_columns(&self) -> &'static [&'static str] {
+        &[TABLE_NAME_COLUMN, PARTITION_KEY_COLUMN]
+    }
+
+    async fn scan(
+        &self,
+        repos: &mut dyn RepoCollection,
+        namespace_id: NamespaceId,
+        filters: &CatalogFilters,
+    ) -> DataFusionResult<RecordBatch> {
+        let tables = list_tables(repos, namespace_id, filters).await?;
+        let partitions = list_partitions(repos, &tables, filters).await?;
+
+        let mut files: Vec<(&Table, &Partition, ParquetFile)> = vec![];
+        if filters.partition_key.is_some() {
+            for (table, partition) in &partitions {
+                let mut partition_files = repos
+                    .parquet_files()
+                    .list_by_partition_not_to_delete(&partition.transition_partition_id())
+                    .await
+                    .map_err(catalog_error)?;
+                partition_files.sort_by_key(|f| f.id);
+
+                files.extend(partition_files.into_iter().map(|f| (*table, partition, f)));
+            }
+        } else {
+            let partitions_by_id: HashMap<TransitionPartitionId, &Partition> = partitions
+                .iter()
+                .map(|(_, p)| (p.transition_partition_id(), p))
+                .collect();
+
+            for table in &tables {
+                let mut table_files = repos
+                    .parquet_files()
+                    .list_by_table_not_to_delete(table.id)
+                    .await
+                    .map_err(catalog_error)?;
+                table_files.sort_by(|a, b| {
+                    let key_a = partitions_by_id
+                        .get(&a.partition_id)
+                        .map(|p| &p.partition_key);
+                    let key_b = partitions_by_id
+                        .get(&b.partition_id)
+                        .map(|p| &p.partition_key);
+                    key_a.cmp(&key_b).then(a.id.cmp(&b.id))
+                });
+
+                files.extend(table_files.into_iter().filter_map(|f| {
+                    let partition = partitions_by_id.get(&f.partition_id)?;
+                    Some((table, *partition, f))
+                }));
+            }
+        }
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(t, _, _)| Some(t.name.as_str()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, p, _)| Some(p.partition_key.inner()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, _, f)| Some(f.partition_id.to_string()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, _, f)| Some(f.id.get()))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, _, f)| Some(f.object_store_id.to_string()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, _, f)| Some(f.compaction_level as i16))
+                    .collect::<Int16Array>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, _, f)| Some(f.min_time.get()))
+                    .collect::<TimestampNanosecondArray>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, _, f)| Some(f.max_time.get()))
+                    .collect::<TimestampNanosecondArray>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, _, f)| Some(f.row_count))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, _, f)| Some(f.file_size_bytes))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, _, f)| Some(f.created_at.get()))
+                    .collect::<TimestampNanosecondArray>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, _, f)| Some(f.max_l0_created_at.get()))
+                    .collect::<TimestampNanosecondArray>(),
+            ),
+        ];
+
+        Ok(RecordBatch::try_new(self.schema(), columns)?)
+    }
+}
+
+/// Implementation of system.compactions_skipped table
+#[derive(Debug)]
+struct CompactionsSkippedTable {
+    schema: SchemaRef,
+}
+
+impl CompactionsSkippedTable {
+    fn new() -> Self {
+        Self {
+            schema: Arc::new(Schema::new(vec![
+                Field::new(TABLE_NAME_COLUMN, DataType::Utf8, false),
+                Field::new(PARTITION_KEY_COLUMN, DataType::Utf8, false),
+                Field::new("partition_id", DataType::Int64, false),
+                Field::new("reason", DataType::Utf8, false),
+                Field::new("skipped_at", timestamp_type(), false),
+                Field::new("estimated_bytes", DataType::Int64, false),
+                Field::new("limit_bytes", DataType::Int64, false),
+                Field::new("num_files", DataType::Int64, false),
+                Field::new("limit_num_files", DataType::Int64, false),
+                Field::new("limit_num_files_first_in_partition", DataType::Int64, false),
+            ])),
+        }
+    }
+}
+
+#[async_trait]
+impl CatalogSystemTable for CompactionsSkippedTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn filter_columns(&self) -> &'static [&'static str] {
+        &[TABLE_NAME_COLUMN, PARTITION_KEY_COLUMN]
+    }
+
+    async fn scan(
+        &self,
+        repos: &mut dyn RepoCollection,
+        namespace_id: NamespaceId,
+        filters: &CatalogFilters,
+    ) -> DataFusionResult<RecordBatch> {
+        let tables = list_tables(repos, namespace_id, filters).await?;
+        let partitions = list_partitions(repos, &tables, filters).await?;
+
+        let partition_ids = partitions.iter().map(|(_, p)| p.id).collect::<Vec<_>>();
+        let skipped = if partition_ids.is_empty() {
+            vec![]
+        } else {
+            repos
+                .partitions()
+                .get_in_skipped_compactions(&partition_ids)
+                .await
+                .map_err(catalog_error)?
+        };
+        let skipped_by_id: HashMap<_, _> =
+            skipped.into_iter().map(|s| (s.partition_id, s)).collect();
+
+        // keep the order of the partition listing
+        let skipped = partitions
+            .iter()
+            .filter_map(|(t, p)| Some((*t, p, skipped_by_id.get(&p.id)?)))
+            .collect::<Vec<_>>();
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(
+                skipped
+                    .iter()
+                    .map(|(t, _, _)| Some(t.name.as_str()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                skipped
+                    .iter()
+                    .map(|(_, p, _)| Some(p.partition_key.inner()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                skipped
+                    .iter()
+                    .map(|(_, p, _)| Some(p.id.get()))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                skipped
+                    .iter()
+                    .map(|(_, _, s)| Some(s.reason.as_str()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                skipped
+                    .iter()
+                    .map(|(_, _, s)| Some(s.skipped_at.get()))
+                    .collect::<TimestampNanosecondArray>(),
+            ),
+            Arc::new(
+                skipped
+                    .iter()
+                    .map(|(_, _, s)| Some(s.estimated_bytes))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                skipped
+                    .iter()
+                    .map(|(_, _, s)| Some(s.limit_bytes))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                skipped
+                    .iter()
+                    .map(|(_, _, s)| Some(s.num_files))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                skipped
+                    .iter()
+                    .map(|(_, _, s)| Some(s.limit_num_files))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                skipped
+                    .iter()
+                    .map(|(_, _, s)| Some(s.limit_num_files_first_in_partition))
+                    .collect::<Int64Array>(),
+            ),
+        ];
+
+        Ok(RecordBatch::try_new(self.schema(), columns)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_util::assert_batches_eq;
+    use data_types::{ColumnType, CompactionLevel};
+    use datafusion::{
+        physical_plan::collect,
+        prelude::{col, lit, SessionContext},
+    };
+    use iox_tests::{TestCatalog, TestParquetFileBuilder};
+
+    #[test]
+    fn test_catalog_filters() {
+        let filters = CatalogFilters::new(&[
+            col("table_name").eq(lit("cpu")),
+            lit("2023-01-01").eq(col("partition_key")),
+            col("table_name").eq(lit("mem")),
+            col("column_name").eq(lit("host")),
+        ]);
+        assert_eq!(
+            filters,
+            CatalogFilters {
+                table_name: Some("cpu".to_owned()),
+                partition_key: Some("2023-01-01".to_owned()),
+            }
+        );
+
+        let filters = CatalogFilters::new(&[
+            col("table_name").not_eq(lit("cpu")),
+            col("partition_key").eq(col("table_name")),
+            col("table_name").eq(lit(1)),
+        ]);
+        assert_eq!(filters, CatalogFilters::default());
+    }
+
+    /// A namespace with the tables "cpu" and "mem", where partition "2023-01-01" exists in both
+    /// tables, and "2023-01-02" in "cpu" only.
+    async fn fixture() -> (Arc<TestCatalog>, NamespaceId) {
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_1hr_retention("ns").await;
+
+        let cpu = ns.create_table("cpu").await;
+        cpu.create_column("host", ColumnType::Tag).await;
+        cpu.create_column("usage", ColumnType::F64).await;
+        cpu.create_column("time", ColumnType::Time).await;
+        let mem = ns.create_table("mem").await;
+        mem.create_column("free", ColumnType::I64).await;
+        mem.create_column("time", ColumnType::Time).await;
+
+        let cpu_1 = cpu
+            .create_partition_with_sort_key("2023-01-01", &["host", "time"], &[1, 3])
+            .await;
+        let cpu_2 = cpu.create_partition("2023-01-02").await;
+        let mem_1 = mem.create_partition("2023-01-01").await;
+
+        let file = |min_time: i64, max_time: i64, row_count: usize| {
+            TestParquetFileBuilder::default()
+                .with_min_time(min_time)
+                .with_max_time(max_time)
+                .with_row_count(row_count)
+                .with_file_size_bytes(100 * row_count as u64)
+        };
+        cpu_1
+            .create_parquet_file_catalog_record(file(10, 20, 3))
+            .await;
+        cpu_1
+            .create_parquet_file_catalog_record(file(10, 20, 3).with_to_delete(true))
+            .await;
+        cpu_2
+            .create_parquet_file_catalog_record(
+                file(30, 40, 5).with_compaction_level(CompactionLevel::FileNonOverlapped),
+            )
+            .await;
+        mem_1
+            .create_parquet_file_catalog_record(file(10, 10, 1))
+            .await;
+
+        catalog
+            .add_to_skipped_compaction(cpu_2.partition.id, "over memory budget")
+            .await;
+
+        (catalog, ns.namespace.id)
+    }
+
+    /// Scan the system table `name` with `filters`, returning the columns in `projection`.
+    async fn scan(
+        catalog: &TestCatalog,
+        namespace_id: NamespaceId,
+        name: &str,
+        projection: &[&str],
+        filters: &[Expr],
+    ) -> Vec<RecordBatch> {
+        let provider = provider_for(catalog, namespace_id, name);
+
+        let schema = provider.schema();
+        let projection = projection
+            .iter()
+            .map(|c| schema.index_of(c).unwrap())
+            .collect::<Vec<_>>();
+
+        let ctx = SessionContext::new();
+        let plan = provider
+            .scan(&ctx.state(), Some(&projection), filters, None)
+            .await
+            .unwrap();
+        collect(plan, ctx.task_ctx()).await.unwrap()
+    }
+
+    fn provider_for(
+        catalog: &TestCatalog,
+        namespace_id: NamespaceId,
+        name: &str,
+    ) -> Arc<dyn TableProvider> {
+        catalog_tables(catalog.catalog(), namespace_id)
+            .into_iter()
+            .find_map(|(n, provider)| (n == name).then_some(provider))
+            .unwrap()
+    }
+
+    /// The `name: type` of the columns of the system table `name`.
+    fn schema_of(catalog: &TestCatalog, name: &str) -> Vec<String> {
+        provider_for(catalog, NamespaceId::new(1), name)
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| format!("{}: {}", f.name(), f.data_type()))
+            .collect()
+    }
+
+    /// The columns for which equality filters are pushed down into the system table `name`.
+    fn pushed_down(catalog: &TestCatalog, name: &str) -> Vec<&'static str> {
+        let provider = provider_for(catalog, NamespaceId::new(1), name);
+        [TABLE_NAME_COLUMN, PARTITION_KEY_COLUMN, "other"]
+            .into_iter()
+            .filter(|column| {
+                matches!(
+                    provider
+                        .supports_filter_pushdown(&col(*column).eq(lit("x")))
+                        .unwrap(),
+                    TableProviderFilterPushDown::Inexact
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_tables() {
+        let (catalog, namespace_id) = fixture().await;
+
+        assert_eq!(
+            schema_of(&catalog, TABLES_TABLE),
+            ["table_id: Int64", "table_name: Utf8"]
+        );
+        assert_eq!(pushed_down(&catalog, TABLES_TABLE), [TABLE_NAME_COLUMN]);
+
+        let columns = ["table_id", "table_name"];
+        assert_batches_eq!(
+            [
+                "+----------+------------+",
+                "| table_id | table_name |",
+                "+----------+------------+",
+                "| 1        | cpu        |",
+                "| 2        | mem        |",
+                "+----------+------------+",
+            ],
+            &scan(&catalog, namespace_id, TABLES_TABLE, &columns, &[]).await
+        );
+        assert_batches_eq!(
+            [
+                "+----------+------------+",
+                "| table_id | table_name |",
+                "+----------+------------+",
+                "| 2        | mem        |",
+                "+----------+------------+",
+            ],
+            &scan(
+                &catalog,
+                namespace_id,
+                TABLES_TABLE,
+                &columns,
+                &[col("table_name").eq(lit("mem"))]
+            )
+            .await
+        );
+        assert_batches_eq!(
+            [
+                "+----------+------------+",
+                "| table_id | table_name |",
+                "+----------+------------+",
+                "+----------+------------+",
+            ],
+            &scan(
+                &catalog,
+                namespace_id,
+                TABLES_TABLE,
+                &columns,
+                &[col("table_name").eq(lit("missing"))]
+            )
+            .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_columns() {
+        let (catalog, namespace_id) = fixture().await;
+
+        assert_eq!(
+            schema_of(&catalog, COLUMNS_TABLE),
+            [
+                "table_id: Int64",
+                "table_name: Utf8",
+                "column_id: Int64",
+                "column_name: Utf8",
+                "column_type: Utf8"
+            ]
+        );
+        assert_eq!(pushed_down(&catalog, COLUMNS_TABLE), [TABLE_NAME_COLUMN]);
+
+        let columns = [
+            "table_id",
+            "table_name",
+            "column_id",
+            "column_name",
+            "column_type",
+        ];
+        assert_batches_eq!(
+            [
+                "+----------+------------+-----------+-------------+-------------+",
+                "| table_id | table_name | column_id | column_name | column_type |",
+                "+----------+------------+-----------+-------------+-------------+",
+                "| 1        | cpu        | 1         | host        | tag         |",
+                "| 1        | cpu        | 3         | time        | time        |",
+                "| 1        | cpu        | 2         | usage       | f64         |",
+                "| 2        | mem        | 4         | free        | i64         |",
+                "| 2        | mem        | 5         | time        | time        |",
+                "+----------+------------+-----------+-------------+-------------+",
+            ],
+            &scan(&catalog, namespace_id, COLUMNS_TABLE, &columns, &[]).await
+        );
+        // the partition key is not a column of this table
+        assert_batches_eq!(
+            [
+                "+----------+------------+-----------+-------------+-------------+",
+                "| table_id | table_name | column_id | column_name | column_type |",
+                "+----------+------------+-----------+-------------+-------------+",
+                "| 2        | mem        | 4         | free        | i64         |",
+                "| 2        | mem        | 5         | time        | time        |",
+                "+----------+------------+-----------+-------------+-------------+",
+            ],
+            &scan(
+                &catalog,
+                namespace_id,
+                COLUMNS_TABLE,
+                &columns,
+                &[
+                    lit("mem").eq(col("table_name")),
+                    col("partition_key").eq(lit("2023-01-02"))
+                ]
+            )
+            .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_partitions() {
+        let (catalog, namespace_id) = fixture().await;
+
+        assert_eq!(
+            schema_of(&catalog, PARTITIONS_TABLE),
+            [
+                "table_name: Utf8",
+                "partition_key: Utf8",
+                "partition_id: Int64",
+                "partition_hash_id: Utf8",
+                "sort_key: Utf8",
+                "new_file_at: Timestamp(Nanosecond, None)"
+            ]
+        );
+        assert_eq!(
+            pushed_down(&catalog, PARTITIONS_TABLE),
+            [TABLE_NAME_COLUMN, PARTITION_KEY_COLUMN]
+        );
+
+        let columns = ["table_name", "partition_key", "partition_id", "sort_key"];
+        assert_batches_eq!(
+            [
+                "+------------+---------------+--------------+-----------+",
+                "| table_name | partition_key | partition_id | sort_key  |",
+                "+------------+---------------+--------------+-----------+",
+                "| cpu        | 2023-01-01    | 1            | host,time |",
+                "| cpu        | 2023-01-02    | 2            |           |",
+                "| mem        | 2023-01-01    | 3            |           |",
+                "+------------+---------------+--------------+-----------+",
+            ],
+            &scan(&catalog, namespace_id, PARTITIONS_TABLE, &columns, &[]).await
+        );
+        assert_batches_eq!(
+            [
+                "+------------+---------------+--------------+-----------+",
+                "| table_name | partition_key | partition_id | sort_key  |",
+                "+------------+---------------+--------------+-----------+",
+                "| cpu        | 2023-01-01    | 1            | host,time |",
+                "| mem        | 2023-01-01    | 3            |           |",
+                "+------------+---------------+--------------+-----------+",
+            ],
+            &scan(
+                &catalog,
+                namespace_id,
+                PARTITIONS_TABLE,
+                &columns,
+                &[col("partition_key").eq(lit("2023-01-01"))]
+            )
+            .await
+        );
+        assert_batches_eq!(
+            [
+                "+------------+---------------+--------------+----------+",
+                "| table_name | partition_key | partition_id | sort_key |",
+                "+------------+---------------+--------------+----------+",
+                "+------------+---------------+--------------+----------+",
+            ],
+            &scan(
+                &catalog,
+                namespace_id,
+                PARTITIONS_TABLE,
+                &columns,
+                &[
+                    col("table_name").eq(lit("mem")),
+                    col("partition_key").eq(lit("2023-01-02"))
+                ]
+            )
+            .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parquet_files() {
+        let (catalog, namespace_id) = fixture().await;
+
+        assert_eq!(
+            schema_of(&catalog, PARQUET_FILES_TABLE),
+            [
+                "table_name: Utf8",
+                "partition_key: Utf8",
+                "partition_id: Utf8",
+                "parquet_file_id: Int64",
+                "object_store_id: Utf8",
+                "compaction_level: Int16",
+                "min_time: Timestamp(Nanosecond, None)",
+                "max_time: Timestamp(Nanosecond, None)",
+                "row_count: Int64",
+                "file_size_bytes: Int64",
+                "created_at: Timestamp(Nanosecond, None)",
+                "max_l0_created_at: Timestamp(Nanosecond, None)"
+            ]
+        );
+        assert_eq!(
+            pushed_down(&catalog, PARQUET_FILES_TABLE),
+            [TABLE_NAME_COLUMN, PARTITION_KEY_COLUMN]
+        );
+
+        // files flagged for deletion are not listed
+        let columns = [
+            "table_name",
+            "partition_key",
+            "parquet_file_id",
+            "compaction_level",
+            "min_time",
+            "max_time",
+            "row_count",
+            "file_size_bytes",
+        ];
+        assert_batches_eq!(
+            [
+                "+------------+---------------+-----------------+------------------+--------------------------------+--------------------------------+-----------+-----------------+",
+                "| table_name | partition_key | parquet_file_id | compaction_level | min_time                       | max_time                       | row_count | file_size_bytes |",
+                "+------------+---------------+-----------------+------------------+--------------------------------+--------------------------------+-----------+-----------------+",
+                "| cpu        | 2023-01-01    | 1               | 0                | 1970-01-01T00:00:00.000000010Z | 1970-01-01T00:00:00.000000020Z | 3         | 300             |",
+                "| cpu        | 2023-01-02    | 3               | 1                | 1970-01-01T00:00:00.000000030Z | 1970-01-01T00:00:00.000000040Z | 5         | 500             |",
+                "| mem        | 2023-01-01    | 4               | 0                | 1970-01-01T00:00:00.000000010Z | 1970-01-01T00:00:00.000000010Z | 1         | 100             |",
+                "+------------+---------------+-----------------+------------------+--------------------------------+--------------------------------+-----------+-----------------+",
+            ],
+            &scan(&catalog, namespace_id, PARQUET_FILES_TABLE, &columns, &[]).await
+        );
+
+        let columns = ["table_name", "partition_key", "parquet_file_id"];
+        assert_batches_eq!(
+            [
+                "+------------+---------------+-----------------+",
+                "| table_name | partition_key | parquet_file_id |",
+                "+------------+---------------+-----------------+",
+                "| cpu        | 2023-01-01    | 1               |",
+                "| mem        | 2023-01-01    | 4               |",
+                "+------------+---------------+-----------------+",
+            ],
+            &scan(
+                &catalog,
+                namespace_id,
+                PARQUET_FILES_TABLE,
+                &columns,
+                &[col("partition_key").eq(lit("2023-01-01"))]
+            )
+            .await
+        );
+        assert_batches_eq!(
+            [
+                "+------------+---------------+-----------------+",
+                "| table_name | partition_key | parquet_file_id |",
+                "+------------+---------------+-----------------+",
+                "| cpu        | 2023-01-01    | 1               |",
+                "| cpu        | 2023-01-02    | 3               |",
+                "+------------+---------------+-----------------+",
+            ],
+            &scan(
+                &catalog,
+                namespace_id,
+                PARQUET_FILES_TABLE,
+                &columns,
+                &[col("table_name").eq(lit("cpu"))]
+            )
+            .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compactions_skipped() {
+        let (catalog, namespace_id) = fixture().await;
+
+        assert_eq!(
+            schema_of(&catalog, COMPACTIONS_SKIPPED_TABLE),
+            [
+                "table_name: Utf8",
+                "partition_key: Utf8",
+                "partition_id: Int64",
+                "reason: Utf8",
+                "skipped_at: Timestamp(Nanosecond, None)",
+                "estimated_bytes: Int64",
+                "limit_bytes: Int64",
+                "num_files: Int64",
+                "limit_num_files: Int64",
+                "limit_num_files_first_in_partition: Int64"
+            ]
+        );
+        assert_eq!(
+            pushed_down(&catalog, COMPACTIONS_SKIPPED_TABLE),
+            [TABLE_NAME_COLUMN, PARTITION_KEY_COLUMN]
+        );
+
+        let columns = [
+            "table_name",
+            "partition_key",
+            "partition_id",
+            "reason",
+            "num_files",
+        ];
+        assert_batches_eq!(
+            [
+                "+------------+---------------+--------------+--------------------+-----------+",
+                "| table_name | partition_key | partition_id | reason             | num_files |",
+                "+------------+---------------+--------------+--------------------+-----------+",
+                "| cpu        | 2023-01-02    | 2            | over memory budget | 0         |",
+                "+------------+---------------+--------------+--------------------+-----------+",
+            ],
+            &scan(
+                &catalog,
+                namespace_id,
+                COMPACTIONS_SKIPPED_TABLE,
+                &columns,
+                &[col("partition_key").eq(lit("2023-01-02"))]
+            )
+            .await
+        );
+        assert_batches_eq!(
+            [
+                "+------------+---------------+--------------+--------+-----------+",
+                "| table_name | partition_key | partition_id | reason | num_files |",
+                "+------------+---------------+--------------+--------+-----------+",
+                "+------------+---------------+--------------+--------+-----------+",
+            ],
+            &scan(
+                &catalog,
+                namespace_id,
+                COMPACTIONS_SKIPPED_TABLE,
+                &columns,
+                &[col("partition_key").eq(lit("2023-01-01"))]
+            )
+            .await
+        );
+    }
+}
@@ -16,6 +16,7 @@ use datafusion::{
     },
     prelude::Expr,
 };
+use iox_catalog::interface::Catalog;
 use std::collections::HashMap;
 use std::{
     any::Any,
@@ -24,11 +25,17 @@ use std::{
     task::{Context, Poll},
 };
 
+mod catalog;
 mod queries;
 
 pub const SYSTEM_SCHEMA: &str = "system";
 
 const QUERIES_TABLE: &str = "queries";
+const TABLES_TABLE: &str = "tables";
+const COLUMNS_TABLE: &str = "columns";
+const PARTITIONS_TABLE: &str = "partitions";
+const PARQUET_FILES_TABLE: &str = "parquet_files";
+const COMPACTIONS_SKIPPED_TABLE: &str = "compactions_skipped";
 
 pub struct SystemSchemaProvider {
     tables: HashMap<&'static str, Arc<dyn TableProvider>>,
@@ -36,6 +43,7 @@ pub struct SystemSchemaProvider {
 
 impl SystemSchemaProvider {
     pub fn new(
+        catalog: Arc<dyn Catalog>,
         query_log: Arc<QueryLog>,
         namespace_id: NamespaceId,
         include_debug_info: bool,
@@ -47,6 +55,8 @@ impl SystemSchemaProvider {
                 table: Arc::new(queries::QueriesTable::new(query_log, Some(namespace_id))),
             });
             tables.insert(QUERIES_TABLE, queries);
+
+            tables.extend(catalog::catalog_tables(catalog, namespace_id));
         }
 
         Self { tables }
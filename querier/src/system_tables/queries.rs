@@ -5,13 +5,14 @@ use crate::{
 use arrow::{
     array::{
         ArrayRef, BooleanArray, DurationNanosecondArray, Int64Array, StringArray,
-        TimestampNanosecondArray,
+        TimestampNanosecondArray, UInt64Array,
     },
     datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit},
     error::Result,
     record_batch::RecordBatch,
 };
 use data_types::NamespaceId;
+use iox_query::exec::QueryStats;
 use observability_deps::tracing::error;
 use std::{collections::VecDeque, sync::Arc};
 
@@ -80,6 +81,7 @@ fn queries_schema(include_namespace_id: bool) -> SchemaRef {
         columns.push(Field::new("namespace_id", DataType::Int64, false));
     }
     columns.append(&mut vec![
+        Field::new("query_id", DataType::Utf8, false),
         Field::new(
             "issue_time",
             DataType::Timestamp(TimeUnit::Nanosecond, None),
@@ -94,6 +96,20 @@ fn queries_schema(include_namespace_id: bool) -> SchemaRef {
         ),
         Field::new("success", DataType::Boolean, false),
         Field::new("trace_id", DataType::Utf8, true),
+        Field::new("cancelled", DataType::Boolean, false),
+        Field::new("peak_memory_bytes", DataType::UInt64, false),
+        Field::new("rows_scanned", DataType::UInt64, false),
+        Field::new("bytes_scanned", DataType::UInt64, false),
+        Field::new("partitions_read", DataType::UInt64, false),
+        Field::new("partitions_pruned", DataType::UInt64, false),
+        Field::new("parquet_files_read", DataType::UInt64, false),
+        Field::new("parquet_files_pruned", DataType::UInt64, false),
+        Field::new("row_groups_pruned", DataType::UInt64, false),
+        Field::new(
+            "ingester_latency",
+            DataType::Duration(TimeUnit::Nanosecond),
+            true,
+        ),
     ]);
 
     Arc::new(Schema::new(columns))
@@ -119,6 +135,15 @@ fn from_query_log_entries(
         ));
     }
 
+    columns.push(Arc::new(
+        entries
+            .iter()
+            .skip(offset)
+            .take(len)
+            .map(|e| Some(e.id.to_string()))
+            .collect::<StringArray>(),
+    ));
+
     columns.push(Arc::new(
         entries
             .iter()
@@ -174,6 +199,45 @@ fn from_query_log_entries(
             .collect::<StringArray>(),
     ));
 
+    columns.push(Arc::new(
+        entries
+            .iter()
+            .skip(offset)
+            .take(len)
+            .map(|e| Some(e.cancelled()))
+            .collect::<BooleanArray>(),
+    ));
+
+    let stats_getters: [fn(&QueryStats) -> u64; 8] = [
+        QueryStats::peak_memory_bytes,
+        QueryStats::rows_scanned,
+        QueryStats::bytes_scanned,
+        QueryStats::partitions_read,
+        QueryStats::partitions_pruned,
+        QueryStats::parquet_files_read,
+        QueryStats::parquet_files_pruned,
+        QueryStats::row_groups_pruned,
+    ];
+    for get in stats_getters {
+        columns.push(Arc::new(
+            entries
+                .iter()
+                .skip(offset)
+                .take(len)
+                .map(|e| Some(get(&e.stats)))
+                .collect::<UInt64Array>(),
+        ));
+    }
+
+    columns.push(Arc::new(
+        entries
+            .iter()
+            .skip(offset)
+            .take(len)
+            .map(|e| e.stats.ingester_latency().map(|d| d.as_nanos() as i64))
+            .collect::<DurationNanosecondArray>(),
+    ));
+
     RecordBatch::try_new(schema, columns)
 }
 
@@ -182,7 +246,9 @@ mod tests {
     use super::*;
     use arrow_util::assert_batches_eq;
     use iox_time::{Time, TimeProvider};
+    use tokio_util::sync::CancellationToken;
     use trace::ctx::TraceId;
+    use uuid::Uuid;
 
     #[test]
     fn test_from_query_log() {
@@ -196,33 +262,62 @@ mod tests {
             10,
             Arc::clone(&time_provider) as Arc<dyn TimeProvider>,
         ));
-        query_log.push(id1, "sql", Box::new("select * from foo"), None);
+        let sql1_entry = query_log.push(
+            id1,
+            "ns".into(),
+            "sql",
+            Box::new("select * from foo"),
+            None,
+            Default::default(),
+            CancellationToken::new(),
+        );
         time_provider.inc(std::time::Duration::from_secs(24 * 60 * 60));
-        let sql2_entry = query_log.push(id1, "sql", Box::new("select * from bar"), None);
+        let sql2_entry = query_log.push(
+            id1,
+            "ns".into(),
+            "sql",
+            Box::new("select * from bar"),
+            None,
+            Default::default(),
+            CancellationToken::new(),
+        );
+        let read_filter_stats = Arc::new(QueryStats::default());
+        read_filter_stats.record_memory_reserved(1024);
+        read_filter_stats.record_partitions(3, 1);
+        read_filter_stats.record_parquet_files(5, 2);
+        read_filter_stats.record_ingester_latency(std::time::Duration::from_millis(2));
         let read_filter_entry = query_log.push(
             id2,
+            "ns".into(),
             "read_filter",
             Box::new("json goop"),
             Some(TraceId::new(0x45fe).unwrap()),
+            read_filter_stats,
+            CancellationToken::new(),
         );
 
         let table = QueriesTable::new(Arc::clone(&query_log), None);
 
         let expected = vec![
-            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+",
-            "| namespace_id | issue_time           | query_type  | query_text        | completed_duration | success | trace_id |",
-            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+",
-            "| 1            | 1996-12-19T16:39:57Z | sql         | select * from foo |                    | false   |          |",
-            "| 1            | 1996-12-20T16:39:57Z | sql         | select * from bar |                    | false   |          |",
-            "| 2            | 1996-12-20T16:39:57Z | read_filter | json goop         |                    | false   | 45fe     |",
-            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+",
+            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+-----------+-------------------+--------------+---------------+-----------------+-------------------+--------------------+----------------------+-------------------+------------------+",
+            "| namespace_id | issue_time           | query_type  | query_text        | completed_duration | success | trace_id | cancelled | peak_memory_bytes | rows_scanned | bytes_scanned | partitions_read | partitions_pruned | parquet_files_read | parquet_files_pruned | row_groups_pruned | ingester_latency |",
+            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+-----------+-------------------+--------------+---------------+-----------------+-------------------+--------------------+----------------------+-------------------+------------------+",
+            "| 1            | 1996-12-19T16:39:57Z | sql         | select * from foo |                    | false   |          | false     | 0                 | 0            | 0             | 0               | 0                 | 0                  | 0                    | 0                 |                  |",
+            "| 1            | 1996-12-20T16:39:57Z | sql         | select * from bar |                    | false   |          | false     | 0                 | 0            | 0             | 0               | 0                 | 0                  | 0                    | 0                 |                  |",
+            "| 2            | 1996-12-20T16:39:57Z | read_filter | json goop         |                    | false   | 45fe     | false     | 1024              | 0            | 0             | 3               | 1                 | 5                  | 2                    | 0                 | 2ms              |",
+            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+-----------+-------------------+--------------+---------------+-----------------+-------------------+--------------------+----------------------+-------------------+------------------+",
         ];
 
         let entries = table.scan(3).unwrap().collect::<Result<Vec<_>>>().unwrap();
         assert_eq!(entries.len(), 1);
-        assert_batches_eq!(&expected, &entries);
+        assert_eq!(
+            query_ids(&entries),
+            vec![sql1_entry.id, sql2_entry.id, read_filter_entry.id]
+        );
+        assert_batches_eq!(&expected, &without_query_id(&entries));
 
-        // mark the sql query completed after 4s unsuccessfully
+        // cancel the sql query, which then completes after 4s unsuccessfully
+        query_log.cancel(sql2_entry.id);
         let now = Time::from_rfc3339("1996-12-20T16:40:01+00:00").unwrap();
         sql2_entry.set_completed(now, false);
 
@@ -230,33 +325,65 @@ mod tests {
         read_filter_entry.set_completed(now, true);
 
         let expected = vec![
-            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+",
-            "| namespace_id | issue_time           | query_type  | query_text        | completed_duration | success | trace_id |",
-            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+",
-            "| 1            | 1996-12-19T16:39:57Z | sql         | select * from foo |                    | false   |          |",
-            "| 1            | 1996-12-20T16:39:57Z | sql         | select * from bar | 4s                 | false   |          |",
-            "| 2            | 1996-12-20T16:39:57Z | read_filter | json goop         | 4s                 | true    | 45fe     |",
-            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+",
+            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+-----------+-------------------+--------------+---------------+-----------------+-------------------+--------------------+----------------------+-------------------+------------------+",
+            "| namespace_id | issue_time           | query_type  | query_text        | completed_duration | success | trace_id | cancelled | peak_memory_bytes | rows_scanned | bytes_scanned | partitions_read | partitions_pruned | parquet_files_read | parquet_files_pruned | row_groups_pruned | ingester_latency |",
+            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+-----------+-------------------+--------------+---------------+-----------------+-------------------+--------------------+----------------------+-------------------+------------------+",
+            "| 1            | 1996-12-19T16:39:57Z | sql         | select * from foo |                    | false   |          | false     | 0                 | 0            | 0             | 0               | 0                 | 0                  | 0                    | 0                 |                  |",
+            "| 1            | 1996-12-20T16:39:57Z | sql         | select * from bar | 4s                 | false   |          | true      | 0                 | 0            | 0             | 0               | 0                 | 0                  | 0                    | 0                 |                  |",
+            "| 2            | 1996-12-20T16:39:57Z | read_filter | json goop         | 4s                 | true    | 45fe     | false     | 1024              | 0            | 0             | 3               | 1                 | 5                  | 2                    | 0                 | 2ms              |",
+            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+-----------+-------------------+--------------+---------------+-----------------+-------------------+--------------------+----------------------+-------------------+------------------+",
         ];
 
         let entries = table.scan(2).unwrap().collect::<Result<Vec<_>>>().unwrap();
         assert_eq!(entries.len(), 2);
-        assert_batches_eq!(&expected, &entries);
+        assert_batches_eq!(&expected, &without_query_id(&entries));
 
         // test namespace scoping
         let table = QueriesTable::new(Arc::clone(&query_log), Some(id1));
 
         let expected = vec![
-            "+----------------------+------------+-------------------+--------------------+---------+----------+",
-            "| issue_time           | query_type | query_text        | completed_duration | success | trace_id |",
-            "+----------------------+------------+-------------------+--------------------+---------+----------+",
-            "| 1996-12-19T16:39:57Z | sql        | select * from foo |                    | false   |          |",
-            "| 1996-12-20T16:39:57Z | sql        | select * from bar | 4s                 | false   |          |",
-            "+----------------------+------------+-------------------+--------------------+---------+----------+",
+            "+----------------------+------------+-------------------+--------------------+---------+----------+-----------+-------------------+--------------+---------------+-----------------+-------------------+--------------------+----------------------+-------------------+------------------+",
+            "| issue_time           | query_type | query_text        | completed_duration | success | trace_id | cancelled | peak_memory_bytes | rows_scanned | bytes_scanned | partitions_read | partitions_pruned | parquet_files_read | parquet_files_pruned | row_groups_pruned | ingester_latency |",
+            "+----------------------+------------+-------------------+--------------------+---------+----------+-----------+-------------------+--------------+---------------+-----------------+-------------------+--------------------+----------------------+-------------------+------------------+",
+            "| 1996-12-19T16:39:57Z | sql        | select * from foo |                    | false   |          | false     | 0                 | 0            | 0             | 0               | 0                 | 0                  | 0                    | 0                 |                  |",
+            "| 1996-12-20T16:39:57Z | sql        | select * from bar | 4s                 | false   |          | true      | 0                 | 0            | 0             | 0               | 0                 | 0                  | 0                    | 0                 |                  |",
+            "+----------------------+------------+-------------------+--------------------+---------+----------+-----------+-------------------+--------------+---------------+-----------------+-------------------+--------------------+----------------------+-------------------+------------------+",
         ];
 
         let entries = table.scan(3).unwrap().collect::<Result<Vec<_>>>().unwrap();
         assert_eq!(entries.len(), 1);
-        assert_batches_eq!(&expected, &entries);
+        assert_eq!(query_ids(&entries), vec![sql1_entry.id, sql2_entry.id]);
+        assert_batches_eq!(&expected, &without_query_id(&entries));
+    }
+
+    /// Query IDs are random, so they are checked separately.
+    fn query_ids(batches: &[RecordBatch]) -> Vec<Uuid> {
+        batches
+            .iter()
+            .flat_map(|batch| {
+                let idx = batch.schema().index_of("query_id").unwrap();
+                batch
+                    .column(idx)
+                    .as_any()
+                    .downcast_ref::<StringArray>()
+                    .unwrap()
+                    .iter()
+                    .map(|id| Uuid::parse_str(id.unwrap()).unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn without_query_id(batches: &[RecordBatch]) -> Vec<RecordBatch> {
+        batches
+            .iter()
+            .map(|batch| {
+                let idx = batch.schema().index_of("query_id").unwrap();
+                let projection = (0..batch.num_columns())
+                    .filter(|i| *i != idx)
+                    .collect::<Vec<_>>();
+                batch.project(&projection).unwrap()
+            })
+            .collect()
     }
 }
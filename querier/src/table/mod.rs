@@ -15,11 +15,13 @@ use datafusion::{error::DataFusionError, prelude::Expr};
 use futures::{join, StreamExt};
 use iox_query::{
     chunk_statistics::create_chunk_statistics,
+    exec::QueryStats,
     provider,
     pruning::{prune_chunks, prune_summaries},
     QueryChunk,
 };
 use observability_deps::tracing::debug;
+use predicate::delete_predicate::TombstoneError;
 use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
 use schema::{Schema, TIME_COLUMN_NAME};
 use snafu::{ResultExt, Snafu};
@@ -48,6 +50,9 @@ pub enum Error {
 
     #[snafu(display("Chunk pruning failed: {}", source))]
     ChunkPruning { source: provider::Error },
+
+    #[snafu(display("Cannot read tombstones: {}", source))]
+    Tombstones { source: Arc<TombstoneError> },
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -146,14 +151,20 @@ impl QuerierTable {
     }
 
     /// Query all chunks within this table.
+    ///
+    /// Pruning results and ingester latency are recorded into `query_stats`, if provided.
     pub async fn chunks(
         &self,
         filters: &[Expr],
         span: Option<Span>,
         projection: Option<&Vec<usize>>,
+        query_stats: Option<&QueryStats>,
     ) -> Result<Vec<Arc<dyn QueryChunk>>> {
         let mut span_recorder = SpanRecorder::new(span);
-        match self.chunks_inner(filters, &span_recorder, projection).await {
+        match self
+            .chunks_inner(filters, &span_recorder, projection, query_stats)
+            .await
+        {
             Ok(chunks) => {
                 span_recorder.ok("got chunks");
                 Ok(chunks)
@@ -170,6 +181,7 @@ impl QuerierTable {
         filters: &[Expr],
         span_recorder: &SpanRecorder,
         projection: Option<&Vec<usize>>,
+        query_stats: Option<&QueryStats>,
     ) -> Result<Vec<Arc<dyn QueryChunk>>> {
         debug!(
             ?filters,
@@ -194,6 +206,7 @@ impl QuerierTable {
                         filters,
                         span_recorder.child_span("ingester partitions"),
                         projection,
+                        query_stats,
                     )
                     .await;
                 ingester_ready.cancel();
@@ -274,6 +287,7 @@ impl QuerierTable {
             .await;
 
         // prune partitons
+        let num_initial_partitions = cached_partitions.len();
         let cached_partitions = self
             .prune_partitions(
                 cached_partitions,
@@ -282,6 +296,7 @@ impl QuerierTable {
                 span_recorder.child_span("prune partitions"),
             )
             .await;
+        let num_initial_parquet_files = parquet_files.files.len();
         let parquet_files = parquet_files
             .files
             .iter()
@@ -296,6 +311,13 @@ impl QuerierTable {
             })
             .cloned();
 
+        // tombstones are applied to the parquet files they cover
+        let tombstones = catalog_cache
+            .tombstone()
+            .get(self.id(), span_recorder.child_span("cache GET tombstone"))
+            .await;
+        let tombstones = tombstones.predicates().context(TombstonesSnafu)?;
+
         // create parquet files
         let parquet_files = self
             .chunk_adapter
@@ -303,6 +325,7 @@ impl QuerierTable {
                 Arc::clone(cached_table),
                 parquet_files,
                 &cached_partitions,
+                tombstones,
                 span_recorder.child_span("new_chunks"),
             )
             .await;
@@ -323,6 +346,17 @@ impl QuerierTable {
         );
         let num_final_parquet_file_chunks = pruned_parquet_file_chunks.len();
 
+        if let Some(query_stats) = query_stats {
+            query_stats.record_partitions(
+                cached_partitions.len() as u64,
+                (num_initial_partitions - cached_partitions.len()) as u64,
+            );
+            query_stats.record_parquet_files(
+                num_final_parquet_file_chunks as u64,
+                (num_initial_parquet_files - num_final_parquet_file_chunks) as u64,
+            );
+        }
+
         // build final chunk list from ingester chunks + pruned parquet file chunks
         let chunks: Vec<_> = partitions
             .into_iter()
@@ -508,19 +542,30 @@ impl QuerierTable {
         filters: &[Expr],
         span: Option<Span>,
         projection: Option<&Vec<usize>>,
+        query_stats: Option<&QueryStats>,
     ) -> Result<Vec<IngesterPartition>> {
         let mut span_recorder = SpanRecorder::new(span);
 
         if let Some(ingester_connection) = &self.ingester_connection {
-            match self
+            let time_provider = self.chunk_adapter.catalog_cache().time_provider();
+            let start = time_provider.now();
+            let res = self
                 .ingester_partitions_inner(
                     Arc::clone(ingester_connection),
                     filters,
                     &span_recorder,
                     projection,
                 )
-                .await
-            {
+                .await;
+
+            if let (Some(query_stats), Some(latency)) = (
+                query_stats,
+                time_provider.now().checked_duration_since(start),
+            ) {
+                query_stats.record_ingester_latency(latency);
+            }
+
+            match res {
                 Ok(partitions) => {
                     span_recorder.ok("Got partitions");
                     Ok(partitions)
@@ -653,8 +698,9 @@ mod tests {
     };
     use arrow::datatypes::DataType;
     use arrow_util::assert_batches_eq;
+    use assert_matches::assert_matches;
     use chrono::{Datelike, TimeZone, Utc};
-    use data_types::{ChunkId, ColumnType};
+    use data_types::{ChunkId, ColumnType, Timestamp};
     use datafusion::{
         prelude::{col, lit},
         scalar::ScalarValue,
@@ -921,6 +967,88 @@ mod tests {
         assert_eq!(chunks.len(), 3);
     }
 
+    #[tokio::test]
+    async fn test_tombstones() {
+        maybe_start_logging();
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_1hr_retention("ns").await;
+        let table = ns.create_table("table1").await;
+        let partition = table.create_partition("k").await;
+        table.create_column("tag", ColumnType::Tag).await;
+        table.create_column("foo", ColumnType::F64).await;
+        table.create_column("time", ColumnType::Time).await;
+
+        let pf_builder =
+            TestParquetFileBuilder::default().with_line_protocol("table1,tag=A foo=1 11");
+        partition.create_parquet_file(pf_builder).await;
+
+        catalog.mock_time_provider().inc(Duration::from_secs(1));
+        catalog
+            .catalog()
+            .repositories()
+            .await
+            .tombstones()
+            .create(
+                table.table.id,
+                Timestamp::new(0),
+                Timestamp::new(100),
+                r#""tag"='A'"#,
+            )
+            .await
+            .unwrap();
+
+        // a file persisted after the delete is not affected by the tombstone
+        let pf_builder = TestParquetFileBuilder::default()
+            .with_line_protocol("table1,tag=A foo=2 22")
+            .with_max_l0_created_at(catalog.time_provider().now() + Duration::from_secs(1));
+        partition.create_parquet_file(pf_builder).await;
+
+        let querier_table = TestQuerierTable::new(&catalog, &table).await;
+
+        let mut chunks = querier_table.chunks().await.unwrap();
+        chunks.sort_by_key(|c| c.order());
+        let num_predicates = chunks
+            .iter()
+            .map(|c| c.delete_predicates().len())
+            .collect::<Vec<_>>();
+        assert_eq!(num_predicates, vec![1, 0]);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_tombstone() {
+        maybe_start_logging();
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_1hr_retention("ns").await;
+        let table = ns.create_table("table1").await;
+        let partition = table.create_partition("k").await;
+        table.create_column("tag", ColumnType::Tag).await;
+        table.create_column("foo", ColumnType::F64).await;
+        table.create_column("time", ColumnType::Time).await;
+
+        let pf_builder =
+            TestParquetFileBuilder::default().with_line_protocol("table1,tag=A foo=1 11");
+        partition.create_parquet_file(pf_builder).await;
+
+        catalog
+            .catalog()
+            .repositories()
+            .await
+            .tombstones()
+            .create(
+                table.table.id,
+                Timestamp::new(0),
+                Timestamp::new(100),
+                r#""tag" LIKE 'A%' OR"#,
+            )
+            .await
+            .unwrap();
+
+        let querier_table = TestQuerierTable::new(&catalog, &table).await;
+
+        let err = querier_table.chunks().await.unwrap_err();
+        assert_matches!(err, Error::Tombstones { .. });
+    }
+
     #[tokio::test]
     async fn test_custom_partitioning() {
         maybe_start_logging();
@@ -1283,7 +1411,9 @@ mod tests {
                 .next_response(Ok(self.ingester_partitions.clone()));
 
             let span = Some(Span::root("root", Arc::clone(&self.traces) as _));
-            self.querier_table.chunks(filters, span, projection).await
+            self.querier_table
+                .chunks(filters, span, projection, None)
+                .await
         }
     }
 }
@@ -61,7 +61,12 @@ impl TableProvider for QuerierTable {
         };
 
         let chunks = self
-            .chunks(&filters, ctx.child_span("QuerierTable chunks"), projection)
+            .chunks(
+                &filters,
+                ctx.child_span("QuerierTable chunks"),
+                projection,
+                ctx.query_stats().as_deref(),
+            )
             .await?;
 
         for chunk in chunks {
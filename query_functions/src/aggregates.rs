@@ -0,0 +1,51 @@
+//! User defined aggregate functions shared by the InfluxQL and InfluxRPC
+//! frontends.
+
+use arrow::datatypes::DataType;
+use datafusion::logical_expr::{
+    AccumulatorFactoryFunction, AggregateUDF, ReturnTypeFunction, StateTypeFunction,
+};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+mod mode;
+mod spread;
+
+/// Numeric datatypes supported by the aggregate functions.
+static NUMERICS: &[DataType] = &[DataType::Int64, DataType::UInt64, DataType::Float64];
+
+/// Definition of the `MODE` user-defined aggregate function.
+///
+/// Takes the value and the time column as arguments, and returns the most
+/// frequent value. If more than one value occurs the most frequently, the
+/// value that occurred first is returned.
+pub static MODE: Lazy<Arc<AggregateUDF>> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(mode::return_type);
+    let accumulator: AccumulatorFactoryFunction = Arc::new(mode::accumulator);
+    let state_type: StateTypeFunction = Arc::new(mode::state_type);
+
+    Arc::new(AggregateUDF::new(
+        mode::NAME,
+        &mode::SIGNATURE,
+        &return_type,
+        &accumulator,
+        &state_type,
+    ))
+});
+
+/// Definition of the `SPREAD` user-defined aggregate function.
+///
+/// Returns the difference between the maximum and minimum value.
+pub static SPREAD: Lazy<Arc<AggregateUDF>> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(spread::return_type);
+    let accumulator: AccumulatorFactoryFunction = Arc::new(spread::accumulator);
+    let state_type: StateTypeFunction = Arc::new(spread::state_type);
+
+    Arc::new(AggregateUDF::new(
+        spread::NAME,
+        &spread::SIGNATURE,
+        &return_type,
+        &accumulator,
+        &state_type,
+    ))
+});
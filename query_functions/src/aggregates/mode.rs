@@ -0,0 +1,179 @@
+use arrow::array::{
+    as_list_array, Array, ArrayRef, Int64Array, TimestampNanosecondArray, UInt64Array,
+};
+use arrow::datatypes::{DataType, Field, TimeUnit};
+use datafusion::common::{downcast_value, DataFusionError, Result, ScalarValue};
+use datafusion::logical_expr::{Accumulator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The name of the mode aggregate function.
+pub(super) const NAME: &str = "mode";
+
+/// Valid signatures for the mode aggregate function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        super::NUMERICS
+            .iter()
+            .chain(&[DataType::Utf8, DataType::Boolean])
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt.clone(),
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature. Mode
+/// always returns the same type as the input column.
+pub(super) fn return_type(signature: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(signature[0].clone()))
+}
+
+/// Create a new accumulator for the data type.
+pub(super) fn accumulator(dt: &DataType) -> Result<Box<dyn Accumulator>> {
+    Ok(Box::new(ModeAccumulator::new(dt.clone())))
+}
+
+/// Calculate the intermediate merge state for the aggregator.
+pub(super) fn state_type(dt: &DataType) -> Result<Arc<Vec<DataType>>> {
+    Ok(Arc::new(vec![
+        DataType::List(Arc::new(Field::new("item", dt.clone(), true))),
+        DataType::List(Arc::new(Field::new("item", DataType::UInt64, true))),
+        DataType::List(Arc::new(Field::new("item", DataType::Int64, true))),
+    ]))
+}
+
+/// The number of occurrences of a value, and the time it first occurred.
+#[derive(Debug, Clone, Copy)]
+struct Occurrences {
+    count: u64,
+    first_time: i64,
+}
+
+impl Occurrences {
+    fn merge(&mut self, count: u64, first_time: i64) {
+        self.count += count;
+        self.first_time = self.first_time.min(first_time);
+    }
+}
+
+#[derive(Debug)]
+struct ModeAccumulator {
+    data_type: DataType,
+    values: HashMap<ScalarValue, Occurrences>,
+}
+
+impl ModeAccumulator {
+    fn new(data_type: DataType) -> Self {
+        Self {
+            data_type,
+            values: HashMap::new(),
+        }
+    }
+
+    fn update(&mut self, value: ScalarValue, count: u64, first_time: i64) {
+        self.values
+            .entry(value)
+            .and_modify(|o| o.merge(count, first_time))
+            .or_insert(Occurrences { count, first_time });
+    }
+}
+
+impl Accumulator for ModeAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        assert_eq!(values.len(), 2);
+
+        let array = Arc::clone(&values[0]);
+        let times = downcast_value!(values[1], TimestampNanosecondArray);
+        for idx in 0..array.len() {
+            if array.is_valid(idx) && times.is_valid(idx) {
+                self.update(
+                    ScalarValue::try_from_array(&array, idx)?,
+                    1,
+                    times.value(idx),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the most frequent value. As with InfluxQL OG, if more than
+    /// one value occurs the most frequently, the value that occurred
+    /// first is returned.
+    ///
+    /// See: <https://github.com/influxdata/influxdb/blob/75a8bcfae2af7b0043933be9f96b98c0741ceee3/influxql/query/call_iterator.go>
+    fn evaluate(&self) -> Result<ScalarValue> {
+        let mode = self.values.iter().max_by(|(av, ao), (bv, bo)| {
+            ao.count
+                .cmp(&bo.count)
+                .then_with(|| bo.first_time.cmp(&ao.first_time))
+                .then_with(|| bv.partial_cmp(av).unwrap_or(Ordering::Equal))
+        });
+
+        match mode {
+            Some((value, _)) => Ok(value.clone()),
+            None => ScalarValue::try_from(&self.data_type),
+        }
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of::<DataType>()
+            + self
+                .values
+                .keys()
+                .map(|v| v.size() + std::mem::size_of::<Occurrences>())
+                .sum::<usize>()
+    }
+
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        let (values, (counts, times)): (Vec<_>, (Vec<_>, Vec<_>)) = self
+            .values
+            .iter()
+            .map(|(v, o)| {
+                (
+                    v.clone(),
+                    (
+                        ScalarValue::UInt64(Some(o.count)),
+                        ScalarValue::Int64(Some(o.first_time)),
+                    ),
+                )
+            })
+            .unzip();
+
+        Ok(vec![
+            ScalarValue::new_list(Some(values), self.data_type.clone()),
+            ScalarValue::new_list(Some(counts), DataType::UInt64),
+            ScalarValue::new_list(Some(times), DataType::Int64),
+        ])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        assert_eq!(states.len(), 3);
+
+        let values = as_list_array(&states[0]);
+        let counts = as_list_array(&states[1]);
+        let times = as_list_array(&states[2]);
+        for idx in 0..values.len() {
+            let v = values.value(idx);
+            let c = counts.value(idx);
+            let c = downcast_value!(c, UInt64Array);
+            let t = times.value(idx);
+            let t = downcast_value!(t, Int64Array);
+            if v.len() != c.len() || v.len() != t.len() {
+                return Err(DataFusionError::Internal(
+                    "mode state lists must have the same length".to_string(),
+                ));
+            }
+            for i in 0..v.len() {
+                self.update(ScalarValue::try_from_array(&v, i)?, c.value(i), t.value(i));
+            }
+        }
+        Ok(())
+    }
+}
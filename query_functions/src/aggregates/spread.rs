@@ -0,0 +1,89 @@
+use arrow::array::ArrayRef;
+use arrow::datatypes::DataType;
+use datafusion::common::{Result, ScalarValue};
+use datafusion::logical_expr::{Accumulator, Signature, TypeSignature, Volatility};
+use datafusion::physical_plan::expressions::{MaxAccumulator, MinAccumulator};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// The name of the spread aggregate function.
+pub(super) const NAME: &str = "spread";
+
+/// Valid signatures for the spread aggregate function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        super::NUMERICS
+            .iter()
+            .map(|dt| TypeSignature::Exact(vec![dt.clone()]))
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature. Spread
+/// always returns the same type as the input column.
+pub(super) fn return_type(signature: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(signature[0].clone()))
+}
+
+/// Create a new accumulator for the data type.
+pub(super) fn accumulator(dt: &DataType) -> Result<Box<dyn Accumulator>> {
+    Ok(Box::new(SpreadAccumulator::try_new(dt)?))
+}
+
+/// Calculate the intermediate merge state for the aggregator.
+pub(super) fn state_type(dt: &DataType) -> Result<Arc<Vec<DataType>>> {
+    Ok(Arc::new(vec![dt.clone(), dt.clone()]))
+}
+
+/// Accumulator that tracks the minimum and maximum values of the
+/// input, to calculate the difference between them.
+#[derive(Debug)]
+struct SpreadAccumulator {
+    data_type: DataType,
+    min: MinAccumulator,
+    max: MaxAccumulator,
+}
+
+impl SpreadAccumulator {
+    fn try_new(data_type: &DataType) -> Result<Self> {
+        Ok(Self {
+            data_type: data_type.clone(),
+            min: MinAccumulator::try_new(data_type)?,
+            max: MaxAccumulator::try_new(data_type)?,
+        })
+    }
+}
+
+impl Accumulator for SpreadAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        assert_eq!(values.len(), 1);
+
+        self.min.update_batch(values)?;
+        self.max.update_batch(values)
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        let min = self.min.evaluate()?;
+        let max = self.max.evaluate()?;
+        if min.is_null() || max.is_null() {
+            return ScalarValue::try_from(&self.data_type);
+        }
+        max.sub(min)
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of::<DataType>() + self.min.size() + self.max.size()
+    }
+
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        Ok(vec![self.min.evaluate()?, self.max.evaluate()?])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        assert_eq!(states.len(), 2);
+
+        self.min.merge_batch(&states[0..1])?;
+        self.max.merge_batch(&states[1..2])
+    }
+}
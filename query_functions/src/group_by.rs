@@ -2,10 +2,15 @@
 //! and Aggregate functions in IOx, designed to be compatible with
 //! InfluxDB classic
 
-use datafusion::prelude::Expr;
+use arrow::datatypes::DataType;
+use datafusion::prelude::{col, Expr};
+use schema::TIME_COLUMN_NAME;
 use snafu::Snafu;
 
-use crate::window;
+use crate::{
+    aggregates::{MODE, SPREAD},
+    window,
+};
 
 #[allow(missing_docs)]
 #[derive(Debug, Snafu)]
@@ -20,7 +25,7 @@ pub enum Error {
 #[allow(missing_docs)]
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
-#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+#[derive(Debug, Clone, PartialEq, Copy)]
 
 /// TimeSeries specific aggregates or selector functions
 ///
@@ -63,6 +68,25 @@ pub enum Aggregate {
     /// Aggregate: Average (geometric mean) column's value
     Mean,
 
+    /// Aggregate: the median of the column's values
+    Median,
+
+    /// Aggregate: the sample standard deviation of the column's values
+    Stddev,
+
+    /// Aggregate: the difference between the maximum and minimum
+    /// values of the column
+    Spread,
+
+    /// Aggregate: the (approximate) value of the column at the given
+    /// quantile, which is between 0 and 1
+    Quantile(f64),
+
+    /// Aggregate: the most frequent value of the column. If more than
+    /// one value occurs the most frequently, the value that occurred
+    /// first is used
+    Mode,
+
     /// No grouping is applied
     None,
 }
@@ -81,7 +105,9 @@ pub enum WindowDuration {
 impl Aggregate {
     /// Create the appropriate DataFusion expression for this aggregate
     pub fn to_datafusion_expr(self, input: Expr) -> Result<Expr> {
-        use datafusion::prelude::{avg, count, max, min, sum};
+        use datafusion::prelude::{
+            approx_percentile_cont, avg, cast, count, lit, max, median, min, stddev, sum,
+        };
         match self {
             Self::Sum => Ok(sum(input)),
             Self::Count => Ok(count(input)),
@@ -90,6 +116,14 @@ impl Aggregate {
             Self::First => AggregateNotSupportedSnafu { agg: "First" }.fail(),
             Self::Last => AggregateNotSupportedSnafu { agg: "Last" }.fail(),
             Self::Mean => Ok(avg(input)),
+            Self::Median => Ok(median(cast(input, DataType::Float64))),
+            Self::Stddev => Ok(stddev(input)),
+            Self::Spread => Ok(SPREAD.call(vec![input])),
+            Self::Quantile(quantile) => Ok(approx_percentile_cont(
+                cast(input, DataType::Float64),
+                lit(quantile),
+            )),
+            Self::Mode => Ok(MODE.call(vec![input, col(TIME_COLUMN_NAME)])),
             Self::None => AggregateNotSupportedSnafu { agg: "None" }.fail(),
         }
     }
@@ -23,6 +23,8 @@ use datafusion::{
 use group_by::WindowDuration;
 use window::EncodedWindowDuration;
 
+pub mod aggregates;
+
 pub mod coalesce_struct;
 
 /// Grouping by structs
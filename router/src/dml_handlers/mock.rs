@@ -1,7 +1,7 @@
 use std::{collections::VecDeque, fmt::Debug, sync::Arc};
 
 use async_trait::async_trait;
-use data_types::{NamespaceName, NamespaceSchema};
+use data_types::{DeletePredicate, NamespaceName, NamespaceSchema};
 use parking_lot::Mutex;
 use trace::ctx::SpanContext;
 
@@ -16,12 +16,19 @@ pub enum MockDmlHandlerCall<W> {
         namespace_schema: Arc<NamespaceSchema>,
         write_input: W,
     },
+    Delete {
+        namespace: String,
+        namespace_schema: Arc<NamespaceSchema>,
+        table: String,
+        predicate: DeletePredicate,
+    },
 }
 
 #[derive(Debug)]
 struct Inner<W> {
     calls: Vec<MockDmlHandlerCall<W>>,
     write_return: VecDeque<Result<(), DmlError>>,
+    delete_return: VecDeque<Result<(), DmlError>>,
 }
 
 impl<W> Default for Inner<W> {
@@ -29,6 +36,7 @@ impl<W> Default for Inner<W> {
         Self {
             calls: Default::default(),
             write_return: Default::default(),
+            delete_return: Default::default(),
         }
     }
 }
@@ -57,6 +65,11 @@ where
         self
     }
 
+    pub fn with_delete_return(self, ret: impl Into<VecDeque<Result<(), DmlError>>>) -> Self {
+        self.0.lock().delete_return = ret.into();
+        self
+    }
+
     pub fn calls(&self) -> Vec<MockDmlHandlerCall<W>> {
         self.0.lock().calls.clone()
     }
@@ -82,6 +95,7 @@ where
     type WriteError = DmlError;
     type WriteInput = W;
     type WriteOutput = ();
+    type DeleteError = DmlError;
 
     async fn write(
         &self,
@@ -100,4 +114,24 @@ where
             write_return
         )
     }
+
+    async fn delete(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_schema: Arc<NamespaceSchema>,
+        table_name: &str,
+        predicate: &DeletePredicate,
+        _span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError> {
+        record_and_return!(
+            self,
+            MockDmlHandlerCall::Delete {
+                namespace: namespace.into(),
+                namespace_schema,
+                table: table_name.to_owned(),
+                predicate: predicate.clone(),
+            },
+            delete_return
+        )
+    }
 }
@@ -68,5 +68,8 @@ pub use fan_out::*;
 mod rpc_write;
 pub use rpc_write::*;
 
+mod tombstone;
+pub use tombstone::*;
+
 #[cfg(test)]
 pub mod mock;
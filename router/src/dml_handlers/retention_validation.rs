@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use data_types::{NamespaceName, NamespaceSchema};
+use data_types::{DeletePredicate, NamespaceName, NamespaceSchema};
 use hashbrown::HashMap;
 use iox_time::{SystemProvider, TimeProvider};
 use mutable_batch::MutableBatch;
@@ -54,6 +54,7 @@ where
     P: TimeProvider,
 {
     type WriteError = RetentionError;
+    type DeleteError = RetentionError;
 
     type WriteInput = HashMap<String, MutableBatch>;
     type WriteOutput = Self::WriteInput;
@@ -85,6 +86,19 @@ where
 
         Ok(batch)
     }
+
+    /// Deletes are not validated against the retention period - deleting
+    /// data that has already expired is a no-op.
+    async fn delete(
+        &self,
+        _namespace: &NamespaceName<'static>,
+        _namespace_schema: Arc<NamespaceSchema>,
+        _table_name: &str,
+        _predicate: &DeletePredicate,
+        _span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -2,6 +2,7 @@ mod balancer;
 mod circuit_breaker;
 mod circuit_breaking_client;
 pub mod client;
+pub mod deletion_observer;
 pub mod lazy_connector;
 mod upstream_snapshot;
 
@@ -11,10 +12,13 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
-use data_types::{NamespaceName, NamespaceSchema, TableId};
+use data_types::{DeletePredicate, NamespaceId, NamespaceName, NamespaceSchema, TableId};
 use dml::{DmlMeta, DmlWrite};
-use futures::{stream::FuturesUnordered, StreamExt};
-use generated_types::influxdata::iox::ingester::v1::WriteRequest;
+use futures::{stream::FuturesUnordered, StreamExt, TryStreamExt};
+use generated_types::influxdata::iox::{
+    delete::v1::{DeletePayload, DeleteRequest},
+    ingester::v1::WriteRequest,
+};
 use hashbrown::HashMap;
 use mutable_batch::MutableBatch;
 use mutable_batch_pb::encode::encode_write;
@@ -92,8 +96,10 @@ pub enum RpcWriteError {
 ///
 /// # Deletes
 ///
-/// This handler drops delete requests, logging the attempt and returning an
-/// error to the client.
+/// Unlike writes, a delete must be applied to every upstream ingester, as any
+/// of them may be buffering data for the table. A delete request is sent to
+/// all configured upstreams regardless of their health, and succeeds only if
+/// all upstreams acknowledge it.
 ///
 /// [gRPC write service]: client::WriteClient
 #[derive(Debug)]
@@ -166,6 +172,65 @@ impl<T> RpcWrite<T> {
     }
 }
 
+impl<T, C> RpcWrite<T, C>
+where
+    T: WriteClient + 'static,
+    C: CircuitBreakerState + 'static,
+{
+    /// Instruct all upstream ingesters to drop the buffered values of
+    /// `columns`, which have been deleted from the table `table_id`.
+    pub async fn delete_columns(
+        &self,
+        namespace_id: NamespaceId,
+        table_id: TableId,
+        table_name: &str,
+        columns: Vec<String>,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), RpcWriteError> {
+        let req = DeleteRequest {
+            payload: Some(DeletePayload {
+                database_id: namespace_id.get(),
+                table_id: table_id.get(),
+                table_name: table_name.to_string(),
+                predicate: None,
+                column_names: columns,
+            }),
+        };
+
+        self.dispatch_delete(req, span_ctx).await?;
+
+        debug!(
+            %namespace_id,
+            %table_name,
+            %table_id,
+            "dispatched column delete to ingesters"
+        );
+
+        Ok(())
+    }
+
+    /// Send `req` to every upstream ingester, as deletes must be applied to
+    /// all replicas of the affected data.
+    async fn dispatch_delete(
+        &self,
+        req: DeleteRequest,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), RpcWriteError> {
+        self.endpoints
+            .all()
+            .map(|client| {
+                let req = req.clone();
+                let span_ctx = span_ctx.clone();
+                async move { delete_loop(&**client, &req, span_ctx).await }
+            })
+            .collect::<FuturesUnordered<_>>()
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl<T, C> DmlHandler for RpcWrite<T, C>
 where
@@ -176,6 +241,7 @@ where
     type WriteOutput = Vec<DmlMeta>;
 
     type WriteError = RpcWriteError;
+    type DeleteError = RpcWriteError;
 
     async fn write(
         &self,
@@ -299,6 +365,49 @@ where
 
         Ok(vec![op.meta().clone()])
     }
+
+    async fn delete(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_schema: Arc<NamespaceSchema>,
+        table_name: &str,
+        predicate: &DeletePredicate,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), RpcWriteError> {
+        let namespace_id = namespace_schema.id;
+
+        // A table that does not exist cannot contain any data to delete.
+        let table_id = match namespace_schema.tables.get(table_name) {
+            Some(t) => t.id,
+            None => {
+                debug!(%namespace, %namespace_id, %table_name, "ignoring delete for unknown table");
+                return Ok(());
+            }
+        };
+
+        let req = DeleteRequest {
+            payload: Some(DeletePayload {
+                database_id: namespace_id.get(),
+                table_id: table_id.get(),
+                table_name: table_name.to_string(),
+                predicate: Some(predicate.into()),
+                column_names: vec![],
+            }),
+        };
+
+        self.dispatch_delete(req, span_ctx).await?;
+
+        debug!(
+            %namespace,
+            %namespace_id,
+            %table_name,
+            %table_id,
+            ?predicate,
+            "dispatched delete to ingesters"
+        );
+
+        Ok(())
+    }
 }
 
 /// Perform an RPC write with `req` against one of the upstream ingesters in
@@ -372,6 +481,41 @@ where
     })?
 }
 
+/// Perform an RPC delete with `req` against the upstream `client`, retrying
+/// until it succeeds or [`RPC_TIMEOUT`] elapses.
+async fn delete_loop<T>(
+    client: &T,
+    req: &DeleteRequest,
+    span_ctx: Option<SpanContext>,
+) -> Result<(), RpcWriteError>
+where
+    T: WriteClient,
+{
+    // The last error returned from an upstream delete request attempt.
+    let mut last_err = None;
+
+    tokio::time::timeout(RPC_TIMEOUT, async {
+        let mut delay = Duration::from_millis(50);
+        loop {
+            match client.delete(req.clone(), span_ctx.clone()).await {
+                Ok(()) => return,
+                Err(e) => {
+                    warn!(error=%e, "failed ingester rpc delete");
+                    last_err = Some(e);
+                }
+            };
+
+            tokio::time::sleep(delay).await;
+            delay = delay.saturating_mul(2);
+        }
+    })
+    .await
+    .map_err(|e| match last_err {
+        Some(v) => RpcWriteError::Client(v),
+        None => RpcWriteError::Timeout(e),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::{collections::HashSet, iter, sync::Arc};
@@ -509,6 +653,138 @@ mod tests {
         assert_eq!(got_tables, want_tables);
     }
 
+    /// Build a namespace schema containing a single empty table named
+    /// `table_name`.
+    fn namespace_schema_with_table(table_name: &str, table_id: TableId) -> NamespaceSchema {
+        let mut schema = new_empty_namespace_schema(NAMESPACE_ID.get());
+        schema.tables.insert(
+            table_name.to_string(),
+            data_types::TableSchema {
+                id: table_id,
+                partition_template: Default::default(),
+                columns: data_types::ColumnsByName::new([]),
+            },
+        );
+        schema
+    }
+
+    /// Deletes are broadcast to all upstreams, regardless of their health.
+    #[tokio::test]
+    async fn test_delete_all_upstreams() {
+        let predicate = DeletePredicate {
+            range: data_types::TimestampRange::new(1, 2),
+            exprs: vec![],
+        };
+
+        let client1 = Arc::new(MockWriteClient::default());
+        let client2 = Arc::new(MockWriteClient::default());
+        let circuit_1 = Arc::new(MockCircuitBreaker::default());
+        circuit_1.set_healthy(true);
+        let circuit_2 = Arc::new(MockCircuitBreaker::default());
+        circuit_2.set_healthy(false);
+
+        let handler = RpcWrite {
+            endpoints: Balancer::new(
+                [
+                    CircuitBreakingClient::new(Arc::clone(&client1), "client1", 10)
+                        .with_circuit_breaker(circuit_1),
+                    CircuitBreakingClient::new(Arc::clone(&client2), "client2", 10)
+                        .with_circuit_breaker(circuit_2),
+                ],
+                None,
+            ),
+            n_copies: 1,
+        };
+
+        handler
+            .delete(
+                &NamespaceName::new(NAMESPACE_NAME).unwrap(),
+                Arc::new(namespace_schema_with_table("bananas", TableId::new(24))),
+                "bananas",
+                &predicate,
+                None,
+            )
+            .await
+            .expect("delete should succeed");
+
+        for client in [client1, client2] {
+            let calls = client.delete_calls();
+            assert_matches!(calls.as_slice(), [DeleteRequest { payload: Some(p) }] => {
+                assert_eq!(p.database_id, NAMESPACE_ID.get());
+                assert_eq!(p.table_id, 24);
+                assert_eq!(p.table_name, "bananas");
+                let got = DeletePredicate::try_from(p.predicate.clone().unwrap()).unwrap();
+                assert_eq!(got, predicate);
+            });
+        }
+    }
+
+    /// Column deletes are broadcast to all upstreams without a predicate.
+    #[tokio::test]
+    async fn test_delete_columns() {
+        let client1 = Arc::new(MockWriteClient::default());
+        let client2 = Arc::new(MockWriteClient::default());
+        let handler = RpcWrite::new(
+            [
+                (Arc::clone(&client1), "client1"),
+                (Arc::clone(&client2), "client2"),
+            ],
+            1.try_into().unwrap(),
+            &metric::Registry::default(),
+            ARBITRARY_TEST_NUM_PROBES,
+        );
+
+        handler
+            .delete_columns(
+                NAMESPACE_ID,
+                TableId::new(24),
+                "bananas",
+                vec!["platanos".to_string()],
+                None,
+            )
+            .await
+            .expect("delete should succeed");
+
+        for client in [client1, client2] {
+            let calls = client.delete_calls();
+            assert_matches!(calls.as_slice(), [DeleteRequest { payload: Some(p) }] => {
+                assert_eq!(p.database_id, NAMESPACE_ID.get());
+                assert_eq!(p.table_id, 24);
+                assert_eq!(p.table_name, "bananas");
+                assert!(p.predicate.is_none());
+                assert_eq!(p.column_names, ["platanos"]);
+            });
+        }
+    }
+
+    /// A delete against a table that does not exist is a no-op.
+    #[tokio::test]
+    async fn test_delete_unknown_table() {
+        let client = Arc::new(MockWriteClient::default());
+        let handler = RpcWrite::new(
+            [(Arc::clone(&client), "mock client")],
+            1.try_into().unwrap(),
+            &metric::Registry::default(),
+            ARBITRARY_TEST_NUM_PROBES,
+        );
+
+        handler
+            .delete(
+                &NamespaceName::new(NAMESPACE_NAME).unwrap(),
+                Arc::new(new_empty_namespace_schema(NAMESPACE_ID.get())),
+                "bananas",
+                &DeletePredicate {
+                    range: data_types::TimestampRange::new(1, 2),
+                    exprs: vec![],
+                },
+                None,
+            )
+            .await
+            .expect("delete should succeed");
+
+        assert!(client.delete_calls().is_empty());
+    }
+
     /// Ensure all candidates returned by the balancer are tried, aborting after
     /// the first successful request.
     #[tokio::test]
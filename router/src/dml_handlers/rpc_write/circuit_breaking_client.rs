@@ -1,7 +1,7 @@
 use std::{fmt::Debug, sync::Arc};
 
 use async_trait::async_trait;
-use generated_types::influxdata::iox::ingester::v1::WriteRequest;
+use generated_types::influxdata::iox::{delete::v1::DeleteRequest, ingester::v1::WriteRequest};
 use trace::ctx::SpanContext;
 
 use super::{
@@ -112,6 +112,16 @@ where
         self.state.observe(&res);
         res
     }
+
+    async fn delete(
+        &self,
+        op: DeleteRequest,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), RpcWriteClientError> {
+        let res = self.inner.delete(op, span_ctx).await;
+        self.state.observe(&res);
+        res
+    }
 }
 
 #[cfg(test)]
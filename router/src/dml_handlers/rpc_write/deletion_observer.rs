@@ -0,0 +1,150 @@
+//! A [`DeletionObserver`] that instructs the ingesters to drop the buffered
+//! values of deleted columns.
+
+use std::sync::Arc;
+
+use data_types::{ColumnId, NamespaceId, NamespaceName, TableId};
+use observability_deps::tracing::*;
+use service_grpc_table::DeletionObserver;
+
+use super::{client::WriteClient, RpcWrite};
+
+/// A [`DeletionObserver`] decorator that notifies the inner `D`, and then
+/// sends a column delete to all upstream ingesters via [`RpcWrite`].
+///
+/// Ingesters drop the buffered values of a deleted column when the delete is
+/// applied, allowing the column to be immediately re-created with a
+/// different type, and removing it from ingester query responses.
+///
+/// The delete is dispatched in the background once the column has been
+/// deleted from the catalog. Should it fail, the deleted column is dropped
+/// from the buffered data when it is next persisted instead.
+#[derive(Debug)]
+pub struct IngesterDeletionObserver<D, T> {
+    inner: D,
+    rpc_writer: Arc<RpcWrite<T>>,
+}
+
+impl<D, T> IngesterDeletionObserver<D, T> {
+    /// Construct a new [`IngesterDeletionObserver`] notifying `inner` of
+    /// deletions, and sending column deletes to the ingesters of `rpc_writer`.
+    pub fn new(inner: D, rpc_writer: Arc<RpcWrite<T>>) -> Self {
+        Self { inner, rpc_writer }
+    }
+}
+
+impl<D, T> DeletionObserver for IngesterDeletionObserver<D, T>
+where
+    D: DeletionObserver,
+    T: WriteClient + 'static,
+{
+    fn table_deleted(
+        &self,
+        namespace: &NamespaceName<'static>,
+        table_name: &str,
+        table_id: TableId,
+    ) {
+        self.inner.table_deleted(namespace, table_name, table_id);
+    }
+
+    fn column_deleted(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_id: NamespaceId,
+        table_name: &str,
+        table_id: TableId,
+        column_name: &str,
+        column_id: ColumnId,
+    ) {
+        self.inner.column_deleted(
+            namespace,
+            namespace_id,
+            table_name,
+            table_id,
+            column_name,
+            column_id,
+        );
+
+        let rpc_writer = Arc::clone(&self.rpc_writer);
+        let namespace = namespace.clone();
+        let table_name = table_name.to_string();
+        let column_name = column_name.to_string();
+        tokio::spawn(async move {
+            if let Err(e) = rpc_writer
+                .delete_columns(
+                    namespace_id,
+                    table_id,
+                    &table_name,
+                    vec![column_name.clone()],
+                    None,
+                )
+                .await
+            {
+                warn!(
+                    error=%e,
+                    %namespace,
+                    %namespace_id,
+                    %table_name,
+                    %table_id,
+                    %column_name,
+                    "failed to dispatch column delete to ingesters"
+                );
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use assert_matches::assert_matches;
+    use generated_types::influxdata::iox::delete::v1::DeleteRequest;
+    use service_grpc_table::NopDeletionObserver;
+
+    use super::*;
+    use crate::dml_handlers::rpc_write::client::mock::MockWriteClient;
+
+    #[tokio::test]
+    async fn test_column_deleted() {
+        let client = Arc::new(MockWriteClient::default());
+        let rpc_writer = Arc::new(RpcWrite::new(
+            [(Arc::clone(&client), "mock client")],
+            1.try_into().unwrap(),
+            &metric::Registry::default(),
+            10,
+        ));
+
+        let observer = IngesterDeletionObserver::new(NopDeletionObserver, rpc_writer);
+
+        observer.column_deleted(
+            &NamespaceName::try_from("bananas").unwrap(),
+            NamespaceId::new(1),
+            "platanos",
+            TableId::new(2),
+            "c1",
+            ColumnId::new(3),
+        );
+
+        // The delete is dispatched asynchronously.
+        let calls = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let calls = client.delete_calls();
+                if !calls.is_empty() {
+                    return calls;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("timeout waiting for column delete");
+
+        assert_matches!(calls.as_slice(), [DeleteRequest { payload: Some(p) }] => {
+            assert_eq!(p.database_id, 1);
+            assert_eq!(p.table_id, 2);
+            assert_eq!(p.table_name, "platanos");
+            assert!(p.predicate.is_none());
+            assert_eq!(p.column_names, ["c1"]);
+        });
+    }
+}
@@ -9,8 +9,9 @@ use std::{
 };
 
 use async_trait::async_trait;
-use generated_types::influxdata::iox::ingester::v1::{
-    write_service_client::WriteServiceClient, WriteRequest,
+use generated_types::influxdata::iox::{
+    delete::v1::{delete_service_client::DeleteServiceClient, DeleteRequest},
+    ingester::v1::{write_service_client::WriteServiceClient, WriteRequest},
 };
 use observability_deps::tracing::*;
 use parking_lot::Mutex;
@@ -101,36 +102,38 @@ impl LazyConnector {
     }
 }
 
-#[async_trait]
-impl WriteClient for LazyConnector {
-    async fn write(
-        &self,
-        op: WriteRequest,
-        span_ctx: Option<SpanContext>,
-    ) -> Result<(), RpcWriteClientError> {
+impl LazyConnector {
+    /// Construct a [`TracePropagatingWriteClient`] over the current
+    /// connection, if any.
+    fn client(&self) -> Result<TracePropagatingWriteClient<'_>, RpcWriteClientError> {
         let conn = self.connection.lock().clone();
         let conn = conn.ok_or_else(|| {
             RpcWriteClientError::UpstreamNotConnected(self.addr.uri().to_string())
         })?;
 
-        match TracePropagatingWriteClient::new(
-            WriteServiceClient::new(conn)
+        Ok(TracePropagatingWriteClient::new(
+            WriteServiceClient::new(conn.clone())
+                .max_encoding_message_size(self.max_outgoing_msg_bytes)
+                .max_decoding_message_size(MAX_INCOMING_MSG_BYTES),
+            DeleteServiceClient::new(conn)
                 .max_encoding_message_size(self.max_outgoing_msg_bytes)
                 .max_decoding_message_size(MAX_INCOMING_MSG_BYTES),
             &self.trace_context_header_name,
-        )
-        .write(op, span_ctx)
-        .await
-        {
+        ))
+    }
+
+    /// Track the connection health from the result of an RPC request.
+    fn observe(&self, res: Result<(), RpcWriteClientError>) -> Result<(), RpcWriteClientError> {
+        match res {
             Err(e) if is_envoy_unavailable_error(&e) => {
                 warn!(error=%e, "detected envoy proxy upstream network error translation, reconnecting");
                 self.consecutive_errors
                     .store(RECONNECT_ERROR_COUNT + 1, Ordering::Relaxed);
-                return Err(e);
+                Err(e)
             }
             Err(e) => {
                 self.consecutive_errors.fetch_add(1, Ordering::Relaxed);
-                return Err(e);
+                Err(e)
             }
             Ok(_) => {
                 self.consecutive_errors.store(0, Ordering::Relaxed);
@@ -140,6 +143,27 @@ impl WriteClient for LazyConnector {
     }
 }
 
+#[async_trait]
+impl WriteClient for LazyConnector {
+    async fn write(
+        &self,
+        op: WriteRequest,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), RpcWriteClientError> {
+        let res = self.client()?.write(op, span_ctx).await;
+        self.observe(res)
+    }
+
+    async fn delete(
+        &self,
+        op: DeleteRequest,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), RpcWriteClientError> {
+        let res = self.client()?.delete(op, span_ctx).await;
+        self.observe(res)
+    }
+}
+
 /// Returns `true` if `e` is a gRPC error with the status [`Code::Unavailable`],
 /// and a metadata entry indicating the response was generated by an envoy proxy
 /// instance.
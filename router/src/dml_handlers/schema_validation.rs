@@ -2,7 +2,8 @@ use std::{ops::DerefMut, sync::Arc};
 
 use async_trait::async_trait;
 use data_types::{
-    partition_template::TablePartitionTemplateOverride, NamespaceName, NamespaceSchema, TableId,
+    partition_template::TablePartitionTemplateOverride, DeletePredicate, NamespaceName,
+    NamespaceSchema, TableId,
 };
 use hashbrown::HashMap;
 use iox_catalog::{interface::Error as CatalogError, validate_or_insert_schema};
@@ -22,6 +23,7 @@ where
     C: NamespaceCache<ReadError = iox_catalog::interface::Error>, // The handler expects the cache to read from the catalog if necessary.
 {
     type WriteError = SchemaError;
+    type DeleteError = SchemaError;
 
     // Accepts a map of TableName -> MutableBatch
     type WriteInput = HashMap<String, MutableBatch>;
@@ -150,6 +152,19 @@ where
 
         Ok(batches)
     }
+
+    /// Deletes are not schema validated - a delete predicate referencing a
+    /// table or column that does not exist matches no rows.
+    async fn delete(
+        &self,
+        _namespace: &NamespaceName<'static>,
+        _namespace_schema: Arc<NamespaceSchema>,
+        _table_name: &str,
+        _predicate: &DeletePredicate,
+        _span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,235 @@
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use data_types::{DeletePredicate, NamespaceName, NamespaceSchema, Timestamp};
+use iox_catalog::interface::Catalog;
+use observability_deps::tracing::*;
+use trace::ctx::SpanContext;
+
+use super::{DmlError, DmlHandler};
+
+/// A [`DmlHandler`] decorator that records a tombstone in the catalog for
+/// each delete successfully applied by the inner handler.
+///
+/// The inner handler is responsible for removing the matching rows buffered in
+/// the ingesters, while the tombstone causes the matching rows in already
+/// persisted parquet files to be filtered out at query time (and eventually
+/// removed by compaction).
+///
+/// The tombstone MUST be created only after the inner delete has completed -
+/// any parquet file persisted before the delete was applied to the ingester
+/// buffers is then guaranteed to have been created before the tombstone, and
+/// therefore be covered by it.
+///
+/// Writes are passed through to the inner handler unmodified.
+#[derive(Debug)]
+pub struct TombstoneWriter<T> {
+    inner: T,
+    catalog: Arc<dyn Catalog>,
+}
+
+impl<T> TombstoneWriter<T> {
+    /// Construct a new [`TombstoneWriter`] recording tombstones in `catalog`
+    /// for deletes applied by `inner`.
+    pub fn new(inner: T, catalog: Arc<dyn Catalog>) -> Self {
+        Self { inner, catalog }
+    }
+}
+
+#[async_trait]
+impl<T> DmlHandler for TombstoneWriter<T>
+where
+    T: DmlHandler,
+{
+    type WriteInput = T::WriteInput;
+    type WriteOutput = T::WriteOutput;
+    type WriteError = T::WriteError;
+    type DeleteError = DmlError;
+
+    async fn write(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_schema: Arc<NamespaceSchema>,
+        input: Self::WriteInput,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<Self::WriteOutput, Self::WriteError> {
+        self.inner
+            .write(namespace, namespace_schema, input, span_ctx)
+            .await
+    }
+
+    /// Apply the delete using the inner handler, and then record a tombstone
+    /// for it in the catalog.
+    async fn delete(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_schema: Arc<NamespaceSchema>,
+        table_name: &str,
+        predicate: &DeletePredicate,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError> {
+        let table_id = namespace_schema.tables.get(table_name).map(|t| t.id);
+
+        self.inner
+            .delete(
+                namespace,
+                Arc::clone(&namespace_schema),
+                table_name,
+                predicate,
+                span_ctx,
+            )
+            .await
+            .map_err(Into::into)?;
+
+        // A table that does not exist has no persisted data to delete.
+        let table_id = match table_id {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+
+        let tombstone = self
+            .catalog
+            .repositories()
+            .await
+            .tombstones()
+            .create(
+                table_id,
+                Timestamp::new(predicate.range.start()),
+                Timestamp::new(predicate.range.end()),
+                &predicate.expr_sql_string(),
+            )
+            .await
+            .map_err(|e| DmlError::Internal(Box::new(e)))?;
+
+        debug!(
+            %namespace,
+            %table_name,
+            %table_id,
+            tombstone_id=%tombstone.id.get(),
+            "recorded delete tombstone"
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::{DeleteExpr, Op, Scalar, TimestampRange};
+    use iox_catalog::{
+        mem::MemCatalog,
+        test_helpers::{arbitrary_namespace, arbitrary_table},
+    };
+
+    use super::*;
+    use crate::dml_handlers::mock::{MockDmlHandler, MockDmlHandlerCall};
+
+    #[tokio::test]
+    async fn test_delete_records_tombstone() {
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(metrics));
+
+        let (ns, table) = {
+            let mut repos = catalog.repositories().await;
+            let ns = arbitrary_namespace(&mut *repos, "bananas").await;
+            let table = arbitrary_table(&mut *repos, "platanos", &ns).await;
+            (ns, table)
+        };
+
+        let mut schema = crate::test_helpers::new_empty_namespace_schema(ns.id.get());
+        schema.tables.insert(
+            table.name.clone(),
+            data_types::TableSchema::new_empty_from(&table),
+        );
+
+        let inner = Arc::new(MockDmlHandler::<()>::default().with_delete_return([Ok(())]));
+        let handler = TombstoneWriter::new(Arc::clone(&inner), Arc::clone(&catalog));
+
+        let predicate = DeletePredicate {
+            range: TimestampRange::new(1, 2),
+            exprs: vec![DeleteExpr::new(
+                "tag".to_string(),
+                Op::Eq,
+                Scalar::String("A".to_string()),
+            )],
+        };
+
+        handler
+            .delete(
+                &NamespaceName::new("bananas").unwrap(),
+                Arc::new(schema),
+                "platanos",
+                &predicate,
+                None,
+            )
+            .await
+            .expect("delete should succeed");
+
+        assert_matches!(inner.calls().as_slice(), [MockDmlHandlerCall::Delete { table, .. }] => {
+            assert_eq!(table, "platanos");
+        });
+
+        let tombstones = catalog
+            .repositories()
+            .await
+            .tombstones()
+            .list_by_table_id(table.id)
+            .await
+            .unwrap();
+        assert_matches!(tombstones.as_slice(), [t] => {
+            assert_eq!(t.min_time, Timestamp::new(1));
+            assert_eq!(t.max_time, Timestamp::new(2));
+            assert_eq!(t.serialized_predicate, predicate.expr_sql_string());
+        });
+    }
+
+    #[tokio::test]
+    async fn test_delete_inner_error_no_tombstone() {
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(metrics));
+
+        let (ns, table) = {
+            let mut repos = catalog.repositories().await;
+            let ns = arbitrary_namespace(&mut *repos, "bananas").await;
+            let table = arbitrary_table(&mut *repos, "platanos", &ns).await;
+            (ns, table)
+        };
+
+        let mut schema = crate::test_helpers::new_empty_namespace_schema(ns.id.get());
+        schema.tables.insert(
+            table.name.clone(),
+            data_types::TableSchema::new_empty_from(&table),
+        );
+
+        let inner = Arc::new(
+            MockDmlHandler::<()>::default()
+                .with_delete_return([Err(DmlError::NamespaceNotFound("nope".to_owned()))]),
+        );
+        let handler = TombstoneWriter::new(inner, Arc::clone(&catalog));
+
+        let err = handler
+            .delete(
+                &NamespaceName::new("bananas").unwrap(),
+                Arc::new(schema),
+                "platanos",
+                &DeletePredicate {
+                    range: TimestampRange::new(1, 2),
+                    exprs: vec![],
+                },
+                None,
+            )
+            .await
+            .expect_err("delete should fail");
+        assert_matches!(err, DmlError::NamespaceNotFound(_));
+
+        let tombstones = catalog
+            .repositories()
+            .await
+            .tombstones()
+            .list_by_table_id(table.id)
+            .await
+            .unwrap();
+        assert!(tombstones.is_empty());
+    }
+}
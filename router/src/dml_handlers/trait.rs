@@ -1,7 +1,7 @@
 use super::{partitioner::PartitionError, retention_validation::RetentionError, RpcWriteError};
 use crate::schema_validator::SchemaError;
 use async_trait::async_trait;
-use data_types::{NamespaceName, NamespaceSchema};
+use data_types::{DeletePredicate, NamespaceName, NamespaceSchema};
 use std::{error::Error, fmt::Debug, sync::Arc};
 use thiserror::Error;
 use trace::ctx::SpanContext;
@@ -56,6 +56,12 @@ pub trait DmlHandler: Debug + Send + Sync {
     /// All errors must be mappable into the concrete [`DmlError`] type.
     type WriteError: Error + Into<DmlError> + Send;
 
+    /// The type of error a [`DmlHandler`] implementation produces for delete
+    /// requests.
+    ///
+    /// All errors must be mappable into the concrete [`DmlError`] type.
+    type DeleteError: Error + Into<DmlError> + Send;
+
     /// Write `batches` to `namespace`.
     async fn write(
         &self,
@@ -64,6 +70,17 @@ pub trait DmlHandler: Debug + Send + Sync {
         input: Self::WriteInput,
         span_ctx: Option<SpanContext>,
     ) -> Result<Self::WriteOutput, Self::WriteError>;
+
+    /// Delete the data specified in `predicate` from `table_name` in
+    /// `namespace`.
+    async fn delete(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_schema: Arc<NamespaceSchema>,
+        table_name: &str,
+        predicate: &DeletePredicate,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError>;
 }
 
 #[async_trait]
@@ -74,6 +91,7 @@ where
     type WriteInput = T::WriteInput;
     type WriteOutput = T::WriteOutput;
     type WriteError = T::WriteError;
+    type DeleteError = T::DeleteError;
 
     async fn write(
         &self,
@@ -86,4 +104,17 @@ where
             .write(namespace, namespace_schema, input, span_ctx)
             .await
     }
+
+    async fn delete(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_schema: Arc<NamespaceSchema>,
+        table_name: &str,
+        predicate: &DeletePredicate,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError> {
+        (**self)
+            .delete(namespace, namespace_schema, table_name, predicate, span_ctx)
+            .await
+    }
 }
@@ -92,6 +92,15 @@ where
         // And pass through the return value to the caller.
         (schema, diff)
     }
+
+    /// Remove `namespace` from the inner cache.
+    ///
+    /// The MST cannot remove entries, so the content hash for `namespace` is
+    /// left as-is until it is next updated by a call to
+    /// [`NamespaceCache::put_schema()`] when the entry is reloaded.
+    fn remove_schema(&self, namespace: &NamespaceName<'static>) -> Option<Arc<NamespaceSchema>> {
+        self.inner.remove_schema(namespace)
+    }
 }
 
 /// A [`NamespaceSchema`] decorator that produces a content hash covering fields
@@ -0,0 +1,255 @@
+//! A [`DeletionObserver`] and [`NamespaceChangeObserver`] that invalidates
+//! cached schemas of deleted tables and columns, and renamed or restored
+//! namespaces, and gossips the changes to other peers.
+
+use data_types::{ColumnId, NamespaceId, NamespaceName, TableId};
+use generated_types::influxdata::iox::gossip::v1::{
+    schema_message::Event, ColumnDeleted, NamespaceRenamed, TableDeleted,
+};
+use observability_deps::tracing::debug;
+use service_grpc_namespace::NamespaceChangeObserver;
+use service_grpc_table::DeletionObserver;
+
+use crate::namespace_cache::NamespaceCache;
+
+use super::traits::SchemaBroadcast;
+
+/// A [`DeletionObserver`] evicting the namespace containing a deleted table or
+/// column from the local [`NamespaceCache`], and optionally broadcasting the
+/// deletion to gossip peers.
+///
+/// The [`NamespaceCache`] only ever merges schema additions, so the affected
+/// namespace is evicted and lazily reloaded from the catalog on next use,
+/// rather than having the deleted element removed in place.
+///
+/// Peers receiving the gossiped deletion apply it through the
+/// [`NamespaceSchemaGossip`] handler.
+///
+/// As a [`NamespaceChangeObserver`], a renamed namespace is evicted under its
+/// old name and the rename gossiped. A restored namespace is evicted locally
+/// only - peers never cache a deleted namespace they have not already cached,
+/// so they have nothing to invalidate.
+///
+/// [`NamespaceSchemaGossip`]: super::namespace_cache::NamespaceSchemaGossip
+#[derive(Debug)]
+pub struct SchemaDeletionObserver<C, U> {
+    cache: C,
+    tx: Option<U>,
+}
+
+impl<C, U> SchemaDeletionObserver<C, U>
+where
+    C: NamespaceCache,
+    U: SchemaBroadcast,
+{
+    /// Construct a new [`SchemaDeletionObserver`] evicting deleted schema
+    /// elements from `cache`, and gossiping the deletions over `gossip`, if
+    /// any.
+    pub fn new(cache: C, gossip: Option<U>) -> Self {
+        Self { cache, tx: gossip }
+    }
+
+    fn broadcast(&self, event: Event) {
+        if let Some(tx) = &self.tx {
+            tx.broadcast(event);
+        }
+    }
+}
+
+impl<C, U> DeletionObserver for SchemaDeletionObserver<C, U>
+where
+    C: NamespaceCache,
+    U: SchemaBroadcast,
+{
+    fn table_deleted(
+        &self,
+        namespace: &NamespaceName<'static>,
+        table_name: &str,
+        table_id: TableId,
+    ) {
+        debug!(%namespace, %table_name, %table_id, "evicting namespace for deleted table");
+        self.cache.remove_schema(namespace);
+
+        self.broadcast(Event::TableDeleted(TableDeleted {
+            namespace_name: namespace.to_string(),
+            table_name: table_name.to_string(),
+            table_id: table_id.get(),
+        }));
+    }
+
+    fn column_deleted(
+        &self,
+        namespace: &NamespaceName<'static>,
+        _namespace_id: NamespaceId,
+        table_name: &str,
+        table_id: TableId,
+        column_name: &str,
+        column_id: ColumnId,
+    ) {
+        debug!(
+            %namespace,
+            %table_name,
+            %table_id,
+            %column_name,
+            column_id = column_id.get(),
+            "evicting namespace for deleted column"
+        );
+        self.cache.remove_schema(namespace);
+
+        self.broadcast(Event::ColumnDeleted(ColumnDeleted {
+            namespace_name: namespace.to_string(),
+            table_name: table_name.to_string(),
+            table_id: table_id.get(),
+            column_name: column_name.to_string(),
+            column_id: column_id.get(),
+        }));
+    }
+}
+
+impl<C, U> NamespaceChangeObserver for SchemaDeletionObserver<C, U>
+where
+    C: NamespaceCache,
+    U: SchemaBroadcast,
+{
+    fn namespace_renamed(
+        &self,
+        old_name: &NamespaceName<'static>,
+        new_name: &NamespaceName<'static>,
+        namespace_id: NamespaceId,
+    ) {
+        debug!(%old_name, %new_name, %namespace_id, "evicting renamed namespace");
+        self.cache.remove_schema(old_name);
+
+        self.broadcast(Event::NamespaceRenamed(NamespaceRenamed {
+            old_name: old_name.to_string(),
+            new_name: new_name.to_string(),
+            namespace_id: namespace_id.get(),
+        }));
+    }
+
+    fn namespace_undeleted(&self, name: &NamespaceName<'static>, namespace_id: NamespaceId) {
+        debug!(namespace = %name, %namespace_id, "evicting restored namespace");
+        self.cache.remove_schema(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use assert_matches::assert_matches;
+
+    use crate::{
+        gossip::mock_schema_broadcast::MockSchemaBroadcast,
+        namespace_cache::{CacheMissErr, MemoryNamespaceCache},
+        test_helpers::{DEFAULT_NAMESPACE, NAMESPACE_NAME},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_table_deleted() {
+        let cache = Arc::new(MemoryNamespaceCache::default());
+        let gossip = Arc::new(MockSchemaBroadcast::default());
+        let observer = SchemaDeletionObserver::new(Arc::clone(&cache), Some(Arc::clone(&gossip)));
+
+        let name = NamespaceName::try_from(NAMESPACE_NAME).unwrap();
+        cache.put_schema(name.clone(), DEFAULT_NAMESPACE.clone());
+
+        observer.table_deleted(&name, "bananas", TableId::new(42));
+
+        // The namespace was evicted from the cache.
+        assert_matches!(cache.get_schema(&name).await, Err(CacheMissErr { .. }));
+
+        // And the deletion was gossiped.
+        assert_matches!(gossip.messages().as_slice(), [Event::TableDeleted(v)] => {
+            assert_eq!(v.namespace_name, NAMESPACE_NAME);
+            assert_eq!(v.table_name, "bananas");
+            assert_eq!(v.table_id, 42);
+        });
+    }
+
+    #[tokio::test]
+    async fn test_column_deleted() {
+        let cache = Arc::new(MemoryNamespaceCache::default());
+        let gossip = Arc::new(MockSchemaBroadcast::default());
+        let observer = SchemaDeletionObserver::new(Arc::clone(&cache), Some(Arc::clone(&gossip)));
+
+        let name = NamespaceName::try_from(NAMESPACE_NAME).unwrap();
+        cache.put_schema(name.clone(), DEFAULT_NAMESPACE.clone());
+
+        observer.column_deleted(
+            &name,
+            DEFAULT_NAMESPACE.id,
+            "bananas",
+            TableId::new(42),
+            "c1",
+            ColumnId::new(101),
+        );
+
+        // The namespace was evicted from the cache.
+        assert_matches!(cache.get_schema(&name).await, Err(CacheMissErr { .. }));
+
+        // And the deletion was gossiped.
+        assert_matches!(gossip.messages().as_slice(), [Event::ColumnDeleted(v)] => {
+            assert_eq!(v.namespace_name, NAMESPACE_NAME);
+            assert_eq!(v.table_name, "bananas");
+            assert_eq!(v.table_id, 42);
+            assert_eq!(v.column_name, "c1");
+            assert_eq!(v.column_id, 101);
+        });
+    }
+
+    #[tokio::test]
+    async fn test_deleted_without_gossip() {
+        let cache = Arc::new(MemoryNamespaceCache::default());
+        let observer =
+            SchemaDeletionObserver::<_, Arc<MockSchemaBroadcast>>::new(Arc::clone(&cache), None);
+
+        let name = NamespaceName::try_from(NAMESPACE_NAME).unwrap();
+        cache.put_schema(name.clone(), DEFAULT_NAMESPACE.clone());
+
+        observer.table_deleted(&name, "bananas", TableId::new(42));
+
+        assert_matches!(cache.get_schema(&name).await, Err(CacheMissErr { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_namespace_renamed() {
+        let cache = Arc::new(MemoryNamespaceCache::default());
+        let gossip = Arc::new(MockSchemaBroadcast::default());
+        let observer = SchemaDeletionObserver::new(Arc::clone(&cache), Some(Arc::clone(&gossip)));
+
+        let name = NamespaceName::try_from(NAMESPACE_NAME).unwrap();
+        let new_name = NamespaceName::try_from("platanos").unwrap();
+        cache.put_schema(name.clone(), DEFAULT_NAMESPACE.clone());
+
+        observer.namespace_renamed(&name, &new_name, DEFAULT_NAMESPACE.id);
+
+        // The old name was evicted from the cache.
+        assert_matches!(cache.get_schema(&name).await, Err(CacheMissErr { .. }));
+
+        // And the rename was gossiped.
+        assert_matches!(gossip.messages().as_slice(), [Event::NamespaceRenamed(v)] => {
+            assert_eq!(v.old_name, NAMESPACE_NAME);
+            assert_eq!(v.new_name, "platanos");
+            assert_eq!(v.namespace_id, DEFAULT_NAMESPACE.id.get());
+        });
+    }
+
+    #[tokio::test]
+    async fn test_namespace_undeleted() {
+        let cache = Arc::new(MemoryNamespaceCache::default());
+        let gossip = Arc::new(MockSchemaBroadcast::default());
+        let observer = SchemaDeletionObserver::new(Arc::clone(&cache), Some(Arc::clone(&gossip)));
+
+        let name = NamespaceName::try_from(NAMESPACE_NAME).unwrap();
+        cache.put_schema(name.clone(), DEFAULT_NAMESPACE.clone());
+
+        observer.namespace_undeleted(&name, DEFAULT_NAMESPACE.id);
+
+        // The namespace was evicted from the cache, but nothing was gossiped.
+        assert_matches!(cache.get_schema(&name).await, Err(CacheMissErr { .. }));
+        assert!(gossip.messages().is_empty());
+    }
+}
@@ -13,6 +13,11 @@
 //!   received from peers, idempotently applying them to the local cache state
 //!   if necessary.
 //!
+//! * The outgoing [`SchemaDeletionObserver`]: notified of tables and columns
+//!   deleted through the table gRPC service, and namespaces renamed or
+//!   restored through the namespace gRPC service, evicting the affected
+//!   namespace from the local cache and gossiping the change to peers.
+//!
 //! ```text
 //!         ┌────────────────────────────────────────────────────┐
 //!         │                   NamespaceCache                   │
@@ -41,6 +46,7 @@
 //! ```
 //!
 //! [`SchemaChangeObserver`]: schema_change_observer::SchemaChangeObserver
+//! [`SchemaDeletionObserver`]: deletion_observer::SchemaDeletionObserver
 //! [`Event`]:
 //!     generated_types::influxdata::iox::gossip::v1::schema_message::Event
 //! [`NamespaceSchemaGossip`]: namespace_cache::NamespaceSchemaGossip
@@ -48,6 +54,7 @@
 //! [`SchemaTx`]: gossip_schema::handle::SchemaTx
 
 pub mod anti_entropy;
+pub mod deletion_observer;
 pub mod namespace_cache;
 pub mod schema_change_observer;
 pub mod traits;
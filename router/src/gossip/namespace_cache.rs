@@ -9,7 +9,8 @@ use data_types::{
     NamespaceNameError, NamespaceSchema, TableId, TableSchema,
 };
 use generated_types::influxdata::iox::gossip::v1::{
-    schema_message::Event, NamespaceCreated, TableCreated, TableUpdated,
+    schema_message::Event, ColumnDeleted, NamespaceCreated, NamespaceRenamed, TableCreated,
+    TableDeleted, TableUpdated,
 };
 use gossip_schema::dispatcher::SchemaEventHandler;
 use observability_deps::tracing::{debug, error, trace, warn};
@@ -42,6 +43,15 @@ enum Error {
     /// yet to be processed.
     #[error("received update for unknown table {0}")]
     TableNotFound(String),
+
+    /// A gossiped namespace, table or column contradicts the immutable values
+    /// of the locally cached entity with the same name.
+    ///
+    /// This happens when a namespace is renamed, or a table or column is
+    /// deleted, and a new one is created with the same name before all peers
+    /// converge, and causes the cached namespace to be evicted.
+    #[error("gossiped schema conflicts with cached {0}")]
+    Conflict(String),
 }
 
 /// A [`NamespaceCache`] decorator applying incoming schema change notifications
@@ -70,15 +80,28 @@ enum Error {
 /// Certain values are immutable for the lifetime of the associated entity; for
 /// example, the data type of a column must never change.
 ///
-/// If a peer gossips an event that contradicts the local state w.r.t an
-/// immutable value, the handler will panic. This is designed to bring down the
-/// local node and cause it to rebuild the cache state from the source of truth
-/// (the catalog) at startup to converge any differences.
+/// Namespaces can be renamed, and tables and columns deleted, after which
+/// their name can be reused by a new entity with a different ID (and for
+/// columns, a different data type). If a peer gossips an event that
+/// contradicts the local state w.r.t an immutable value, the conflict is
+/// therefore resolved by evicting the cached namespace, causing it to be
+/// reloaded from the source of truth (the catalog) on next use.
+///
+/// # Deletions and Renames
+///
+/// [`Event::TableDeleted`] and [`Event::ColumnDeleted`] cause the cached
+/// namespace to be evicted if it contains the deleted entity (matched by ID).
+/// The [`NamespaceCache`] only ever merges additions, so eviction is the only
+/// way of removing schema elements from it.
+///
+/// [`Event::NamespaceRenamed`] causes the namespace cached under the old name
+/// to be evicted if its ID matches. The new name is resolved from the catalog
+/// on next use.
 ///
 /// This requires trusted peers within the network this node is operating
-/// within. A malicious peer can trivially panic peers by gossiping malicious
-/// schema updates. This lays outside the threat model of the gossip system
-/// which explicitly trusts all gossip peers by design.
+/// within. A malicious peer can trivially evict cached state, or corrupt it,
+/// by gossiping malicious schema updates. This lays outside the threat model
+/// of the gossip system which explicitly trusts all gossip peers by design.
 #[derive(Debug)]
 pub struct NamespaceSchemaGossip<C> {
     inner: C,
@@ -100,6 +123,9 @@ where
             Event::NamespaceCreated(v) => self.handle_namespace_created(v).await,
             Event::TableCreated(v) => self.handle_table_created(v).await,
             Event::TableUpdated(v) => self.handle_updated_table(v).await,
+            Event::TableDeleted(v) => self.handle_table_deleted(v).await,
+            Event::ColumnDeleted(v) => self.handle_column_deleted(v).await,
+            Event::NamespaceRenamed(v) => self.handle_namespace_renamed(v).await,
         };
 
         if let Err(error) = res {
@@ -124,10 +150,8 @@ where
     /// If the local state contains the gossiped namespace, this is a no-op,
     /// otherwise it is inserted into the [`NamespaceCache`].
     ///
-    /// # Panics
-    ///
-    /// This method panics if the gossiped immutable values do not match the
-    /// local state.
+    /// If the gossiped immutable values do not match the local state, the
+    /// cached namespace is evicted.
     async fn handle_namespace_created(&self, note: NamespaceCreated) -> Result<(), Error> {
         // Check if this namespace exists already
         let namespace_name = NamespaceName::try_from(note.namespace_name)?;
@@ -153,9 +177,14 @@ where
                 // It does! This is a no-op.
 
                 // Invariant: name -> ID mappings & partition templates MUST be
-                // immutable and consistent across the cluster.
-                assert_eq!(v.id.get(), note.namespace_id);
-                assert_eq!(v.partition_template, partition_template);
+                // consistent across the cluster for the lifetime of the
+                // namespace.
+                if v.id.get() != note.namespace_id || v.partition_template != partition_template {
+                    return Err(self.evict_on_conflict(
+                        &namespace_name,
+                        Error::Conflict(format!("namespace {namespace_name}")),
+                    ));
+                }
 
                 return Ok(());
             }
@@ -199,10 +228,8 @@ where
     /// The local peer MAY or MAY NOT already know about this table and
     /// namespace. If the peer is unaware of either, this is a no-op.
     ///
-    /// # Panics
-    ///
-    /// This method panics if the gossiped immutable values do not match the
-    /// local state.
+    /// If the gossiped immutable values do not match the local state, the
+    /// cached namespace is evicted.
     async fn handle_updated_table(&self, update: TableUpdated) -> Result<(), Error> {
         let table_name = update.table_name.clone();
 
@@ -223,11 +250,18 @@ where
             .get(&update.table_name)
             .ok_or_else(|| Error::TableNotFound(table_name.clone()))?;
 
-        // Invariant: name -> ID mappings MUST be immutable and consistent
-        // across the cluster.
-        assert_eq!(table.id.get(), update.table_id);
+        // Invariant: name -> ID mappings MUST be consistent across the
+        // cluster for the lifetime of the table.
+        if table.id.get() != update.table_id {
+            return Err(self.evict_on_conflict(
+                &namespace_name,
+                Error::Conflict(format!("table {table_name} ID")),
+            ));
+        }
 
-        if let Some(table) = update_table(table, update)? {
+        let table =
+            update_table(table, update).map_err(|e| self.evict_on_conflict(&namespace_name, e))?;
+        if let Some(table) = table {
             upsert_cached_namespace(&self.inner, &ns, namespace_name, table, table_name);
         }
 
@@ -242,11 +276,8 @@ where
     /// If the local peer does not know of this namespace, this is a no-op.
     ///
     /// If the local peer already knows of this table, the contents are merged,
-    /// and the immutable fields are verified to be identical.
-    ///
-    /// # Panics
-    ///
-    /// This method panics if the immutable table fields differ.
+    /// and the immutable fields are verified to be identical. If they differ,
+    /// the cached namespace is evicted.
     async fn handle_table_created(&self, v: TableCreated) -> Result<(), Error> {
         // Extract the table update from the message.
         let update = v.table.ok_or(Error::MissingTableUpdate)?;
@@ -276,11 +307,15 @@ where
         let table = match ns.tables.get(&update.table_name) {
             Some(v) => {
                 // Invariant: name -> ID mappings & partition templates MUST be
-                // immutable and consistent across the cluster.
-                assert_eq!(v.id, table_id);
-                assert_eq!(v.partition_template, partition_template);
+                // consistent across the cluster for the lifetime of the table.
+                if v.id != table_id || v.partition_template != partition_template {
+                    return Err(self.evict_on_conflict(
+                        &namespace_name,
+                        Error::Conflict(format!("table {table_name}")),
+                    ));
+                }
 
-                update_table(v, update)?
+                update_table(v, update).map_err(|e| self.evict_on_conflict(&namespace_name, e))?
             }
             None => {
                 // Decode the columns within this update
@@ -317,6 +352,109 @@ where
 
         Ok(())
     }
+
+    /// Handle a gossip event for a deleted table.
+    ///
+    /// If the local peer has the deleted table cached, the namespace is
+    /// evicted from the [`NamespaceCache`]. If the peer is unaware of the
+    /// namespace or table, or the cached table has a different ID (it was
+    /// re-created), this is a no-op.
+    async fn handle_table_deleted(&self, note: TableDeleted) -> Result<(), Error> {
+        let namespace_name = NamespaceName::try_from(note.namespace_name)?;
+        let ns = self
+            .inner
+            .get_schema(&namespace_name)
+            .await
+            .map_err(|v| Error::Lookup(Box::from(v)))?;
+
+        match ns.tables.get(&note.table_name) {
+            Some(table) if table.id.get() == note.table_id => {
+                debug!(
+                    %namespace_name,
+                    table_name=%note.table_name,
+                    table_id=note.table_id,
+                    "evicting namespace for table deleted via gossip"
+                );
+                self.inner.remove_schema(&namespace_name);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Handle a gossip event for a deleted column.
+    ///
+    /// If the local peer has the deleted column cached, the namespace is
+    /// evicted from the [`NamespaceCache`]. If the peer is unaware of the
+    /// namespace, table or column, or the cached table or column has a
+    /// different ID (it was re-created), this is a no-op.
+    async fn handle_column_deleted(&self, note: ColumnDeleted) -> Result<(), Error> {
+        let namespace_name = NamespaceName::try_from(note.namespace_name)?;
+        let ns = self
+            .inner
+            .get_schema(&namespace_name)
+            .await
+            .map_err(|v| Error::Lookup(Box::from(v)))?;
+
+        let cached = ns
+            .tables
+            .get(&note.table_name)
+            .filter(|t| t.id.get() == note.table_id)
+            .and_then(|t| t.columns.get(&note.column_name))
+            .filter(|c| c.id.get() == note.column_id);
+
+        if cached.is_some() {
+            debug!(
+                %namespace_name,
+                table_name=%note.table_name,
+                table_id=note.table_id,
+                column_name=%note.column_name,
+                column_id=note.column_id,
+                "evicting namespace for column deleted via gossip"
+            );
+            self.inner.remove_schema(&namespace_name);
+        }
+
+        Ok(())
+    }
+
+    /// Handle a gossip event for a renamed namespace.
+    ///
+    /// If the local peer has the namespace cached under its old name, it is
+    /// evicted from the [`NamespaceCache`]. If the peer is unaware of the old
+    /// name, or the cached namespace has a different ID (the name was reused),
+    /// this is a no-op.
+    async fn handle_namespace_renamed(&self, note: NamespaceRenamed) -> Result<(), Error> {
+        let old_name = NamespaceName::try_from(note.old_name)?;
+        let ns = self
+            .inner
+            .get_schema(&old_name)
+            .await
+            .map_err(|v| Error::Lookup(Box::from(v)))?;
+
+        if ns.id.get() == note.namespace_id {
+            debug!(
+                %old_name,
+                new_name=%note.new_name,
+                namespace_id=note.namespace_id,
+                "evicting namespace renamed via gossip"
+            );
+            self.inner.remove_schema(&old_name);
+        }
+
+        Ok(())
+    }
+
+    /// Evict `namespace` from the cache if `error` is an [`Error::Conflict`],
+    /// returning `error` for logging purposes.
+    fn evict_on_conflict(&self, namespace: &NamespaceName<'static>, error: Error) -> Error {
+        if matches!(error, Error::Conflict(_)) {
+            warn!(%namespace, %error, "evicting cached namespace schema");
+            self.inner.remove_schema(namespace);
+        }
+        error
+    }
 }
 
 /// Apply `update` to `table`, returning an updated copy, if any.
@@ -336,12 +474,14 @@ fn update_table(table: &TableSchema, update: TableUpdated) -> Result<Option<Tabl
         let name = v.name;
 
         if let Some(v) = table.columns.get(&name) {
-            // Invariant: name -> ID mappings MUST be immutable and
-            // consistent across the cluster.
-            assert_eq!(v.id, column.id);
-            // Invariant: name -> data type mappings MUST be
-            // immutable and consistent across the cluster.
-            assert_eq!(v.column_type, column.column_type);
+            // Invariant: name -> ID & data type mappings MUST be consistent
+            // across the cluster for the lifetime of the column.
+            if v.id != column.id || v.column_type != column.column_type {
+                return Err(Error::Conflict(format!(
+                    "column {name} in table {}",
+                    update.table_name
+                )));
+            }
             continue;
         }
 
@@ -898,4 +1038,178 @@ mod tests {
             assert_eq!(*v, DEFAULT_NAMESPACE);
         }
     );
+
+    /// Return [`DEFAULT_NAMESPACE`] containing the table "bananas" (ID 42)
+    /// with the string column "c1" (ID 101).
+    fn namespace_with_table() -> NamespaceSchema {
+        let mut ns = DEFAULT_NAMESPACE.clone();
+
+        let mut table = TableSchema {
+            id: TableId::new(42),
+            partition_template: TablePartitionTemplateOverride::default(),
+            columns: ColumnsByName::new(vec![]),
+        };
+
+        table.add_column_schema(
+            "c1".to_string(),
+            ColumnSchema {
+                id: ColumnId::new(101),
+                column_type: ColumnType::String,
+            },
+        );
+
+        ns.tables.insert("bananas".to_string(), table);
+
+        ns
+    }
+
+    // An update message arrives for a column that was deleted and re-created
+    // with a different type on the sender, evicting the namespace.
+    test_handle_gossip_message_!(
+        table_updated_column_conflict,
+        existing = Some(namespace_with_table()),
+        message = Event::TableUpdated(TableUpdated {
+            table_name: "bananas".to_string(),
+            namespace_name: NAMESPACE_NAME.to_string(),
+            table_id: 42,
+            columns: vec![generated_types::influxdata::iox::gossip::v1::Column {
+                name: "c1".to_string(),
+                column_id: 102,
+                column_type: ColumnType::Tag as _,
+            },],
+        }),
+        want = Err(CacheMissErr { .. })
+    );
+
+    // A create message arrives for a table that was deleted and re-created
+    // with a different ID on the sender, evicting the namespace.
+    test_handle_gossip_message_!(
+        table_created_id_conflict,
+        existing = Some(namespace_with_table()),
+        message = Event::TableCreated(TableCreated {
+            table: Some(TableUpdated {
+                table_name: "bananas".to_string(),
+                namespace_name: NAMESPACE_NAME.to_string(),
+                table_id: 43,
+                columns: vec![],
+            }),
+            partition_template: None,
+        }),
+        want = Err(CacheMissErr { .. })
+    );
+
+    // A table delete message arrives for a cached table, evicting the
+    // namespace.
+    test_handle_gossip_message_!(
+        table_deleted,
+        existing = Some(namespace_with_table()),
+        message = Event::TableDeleted(TableDeleted {
+            namespace_name: NAMESPACE_NAME.to_string(),
+            table_name: "bananas".to_string(),
+            table_id: 42,
+        }),
+        want = Err(CacheMissErr { .. })
+    );
+
+    // A table delete message arrives for a table with the same name, but a
+    // different ID (it has since been re-created).
+    test_handle_gossip_message_!(
+        table_deleted_different_id,
+        existing = Some(namespace_with_table()),
+        message = Event::TableDeleted(TableDeleted {
+            namespace_name: NAMESPACE_NAME.to_string(),
+            table_name: "bananas".to_string(),
+            table_id: 24,
+        }),
+        want = Ok(ns) => {
+            assert_eq!(*ns, namespace_with_table()); // Unmodified
+        }
+    );
+
+    // A table delete message arrives for a table not known locally.
+    test_handle_gossip_message_!(
+        table_deleted_missing_table,
+        existing = Some(DEFAULT_NAMESPACE),
+        message = Event::TableDeleted(TableDeleted {
+            namespace_name: NAMESPACE_NAME.to_string(),
+            table_name: "bananas".to_string(),
+            table_id: 42,
+        }),
+        want = Ok(ns) => {
+            assert_eq!(*ns, DEFAULT_NAMESPACE); // Unmodified
+        }
+    );
+
+    // A column delete message arrives for a cached column, evicting the
+    // namespace.
+    test_handle_gossip_message_!(
+        column_deleted,
+        existing = Some(namespace_with_table()),
+        message = Event::ColumnDeleted(ColumnDeleted {
+            namespace_name: NAMESPACE_NAME.to_string(),
+            table_name: "bananas".to_string(),
+            table_id: 42,
+            column_name: "c1".to_string(),
+            column_id: 101,
+        }),
+        want = Err(CacheMissErr { .. })
+    );
+
+    // A column delete message arrives for a column with the same name, but a
+    // different ID (it has since been re-created).
+    test_handle_gossip_message_!(
+        column_deleted_different_id,
+        existing = Some(namespace_with_table()),
+        message = Event::ColumnDeleted(ColumnDeleted {
+            namespace_name: NAMESPACE_NAME.to_string(),
+            table_name: "bananas".to_string(),
+            table_id: 42,
+            column_name: "c1".to_string(),
+            column_id: 100,
+        }),
+        want = Ok(ns) => {
+            assert_eq!(*ns, namespace_with_table()); // Unmodified
+        }
+    );
+
+    // A create message arrives for a namespace whose name is cached with a
+    // different ID (it was renamed and the name reused on the sender),
+    // evicting the namespace.
+    test_handle_gossip_message_!(
+        namespace_created_id_conflict,
+        existing = Some(DEFAULT_NAMESPACE),
+        message = Event::NamespaceCreated(NamespaceCreated {
+            namespace_id: DEFAULT_NAMESPACE.id.get() + 1,
+            ..namespace_created(NAMESPACE_NAME, &DEFAULT_NAMESPACE)
+        }),
+        want = Err(CacheMissErr { .. })
+    );
+
+    // A rename message arrives for a namespace cached under its old name,
+    // evicting it.
+    test_handle_gossip_message_!(
+        namespace_renamed,
+        existing = Some(DEFAULT_NAMESPACE),
+        message = Event::NamespaceRenamed(NamespaceRenamed {
+            old_name: NAMESPACE_NAME.to_string(),
+            new_name: "platanos".to_string(),
+            namespace_id: DEFAULT_NAMESPACE.id.get(),
+        }),
+        want = Err(CacheMissErr { .. })
+    );
+
+    // A rename message arrives for a namespace with the same name, but a
+    // different ID (the old name has since been reused).
+    test_handle_gossip_message_!(
+        namespace_renamed_different_id,
+        existing = Some(DEFAULT_NAMESPACE),
+        message = Event::NamespaceRenamed(NamespaceRenamed {
+            old_name: NAMESPACE_NAME.to_string(),
+            new_name: "platanos".to_string(),
+            namespace_id: DEFAULT_NAMESPACE.id.get() + 1,
+        }),
+        want = Ok(ns) => {
+            assert_eq!(*ns, DEFAULT_NAMESPACE); // Unmodified
+        }
+    );
 }
@@ -73,6 +73,16 @@ where
 
         (schema, diff)
     }
+
+    /// Pass through remove requests.
+    ///
+    /// Deletions are gossiped by the [`SchemaDeletionObserver`] instead, as
+    /// the cache is unaware of which table or column was deleted.
+    ///
+    /// [`SchemaDeletionObserver`]: super::deletion_observer::SchemaDeletionObserver
+    fn remove_schema(&self, namespace: &NamespaceName<'static>) -> Option<Arc<NamespaceSchema>> {
+        self.inner.remove_schema(namespace)
+    }
 }
 
 impl<T, U> SchemaChangeObserver<T, U>
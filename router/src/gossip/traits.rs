@@ -1,7 +1,7 @@
 //! Abstractions decoupling application schema gossiping from the underlying
 //! transport.
 
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 use generated_types::influxdata::iox::gossip::v1::schema_message::Event;
 use gossip_schema::handle::SchemaTx;
@@ -19,3 +19,9 @@ impl SchemaBroadcast for SchemaTx {
         SchemaTx::broadcast(self, payload)
     }
 }
+
+impl SchemaBroadcast for Arc<SchemaTx> {
+    fn broadcast(&self, payload: Event) {
+        SchemaTx::broadcast(self, payload)
+    }
+}
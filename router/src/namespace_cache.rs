@@ -41,6 +41,12 @@ pub trait NamespaceCache: Debug + Send + Sync {
         namespace: NamespaceName<'static>,
         schema: NamespaceSchema,
     ) -> (Arc<NamespaceSchema>, ChangeStats);
+
+    /// Remove the cached entry for `namespace`, if any, returning it.
+    ///
+    /// As [`NamespaceCache::put_schema()`] only ever adds tables and columns,
+    /// this is the only way to drop deleted schema elements from the cache.
+    fn remove_schema(&self, namespace: &NamespaceName<'static>) -> Option<Arc<NamespaceSchema>>;
 }
 
 #[async_trait]
@@ -64,6 +70,10 @@ where
     ) -> (Arc<NamespaceSchema>, ChangeStats) {
         T::put_schema(self, namespace, schema)
     }
+
+    fn remove_schema(&self, namespace: &NamespaceName<'static>) -> Option<Arc<NamespaceSchema>> {
+        T::remove_schema(self, namespace)
+    }
 }
 
 /// Change statistics describing how the cache entry was modified by the
@@ -123,4 +133,11 @@ where
             MaybeLayer::Without(v) => v.put_schema(namespace, schema),
         }
     }
+
+    fn remove_schema(&self, namespace: &NamespaceName<'static>) -> Option<Arc<NamespaceSchema>> {
+        match self {
+            MaybeLayer::With(v) => v.remove_schema(namespace),
+            MaybeLayer::Without(v) => v.remove_schema(namespace),
+        }
+    }
 }
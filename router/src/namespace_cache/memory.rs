@@ -71,6 +71,10 @@ impl NamespaceCache for MemoryNamespaceCache {
         self.cache.write().insert(namespace, Arc::clone(&ret));
         (ret, change_stats)
     }
+
+    fn remove_schema(&self, namespace: &NamespaceName<'static>) -> Option<Arc<NamespaceSchema>> {
+        self.cache.write().remove(namespace)
+    }
 }
 
 /// Merges into `new_ns` any table or column schema which are
@@ -119,6 +119,24 @@ where
 
         (result, change_stats)
     }
+
+    fn remove_schema(&self, namespace: &NamespaceName<'static>) -> Option<Arc<NamespaceSchema>> {
+        let removed = self.inner.remove_schema(namespace);
+
+        // Adjust the metrics to reflect the removed table and columns
+        if let Some(schema) = &removed {
+            self.table_count.dec(schema.tables.len() as u64);
+            self.column_count.dec(
+                schema
+                    .tables
+                    .values()
+                    .map(|t| t.column_count())
+                    .sum::<usize>() as u64,
+            );
+        }
+
+        removed
+    }
 }
 
 #[cfg(test)]
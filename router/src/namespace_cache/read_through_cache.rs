@@ -93,6 +93,12 @@ where
     ) -> (Arc<NamespaceSchema>, ChangeStats) {
         self.inner_cache.put_schema(namespace, schema)
     }
+
+    /// Remove `namespace` from the inner cache, causing the next
+    /// `self.get_schema()` call to load it from the catalog.
+    fn remove_schema(&self, namespace: &NamespaceName<'static>) -> Option<Arc<NamespaceSchema>> {
+        self.inner_cache.remove_schema(namespace)
+    }
 }
 
 #[cfg(test)]
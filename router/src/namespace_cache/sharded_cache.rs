@@ -43,6 +43,10 @@ where
     ) -> (Arc<NamespaceSchema>, ChangeStats) {
         self.shards.hash(&namespace).put_schema(namespace, schema)
     }
+
+    fn remove_schema(&self, namespace: &NamespaceName<'static>) -> Option<Arc<NamespaceSchema>> {
+        self.shards.hash(namespace).remove_schema(namespace)
+    }
 }
 
 #[cfg(test)]
@@ -230,6 +230,7 @@ mod tests {
                 name: ns.to_string(),
                 max_tables: Default::default(),
                 max_columns_per_table: Default::default(),
+                query_limits: Default::default(),
                 retention_period_ns: TEST_RETENTION_PERIOD_NS,
                 deleted_at: None,
                 partition_template: Default::default(),
@@ -4,6 +4,8 @@ use self::{grpc::RpcWriteGrpcDelegate, http::HttpDelegate};
 use std::sync::Arc;
 use trace::TraceCollector;
 
+pub mod delete;
+pub mod flight;
 pub mod grpc;
 pub mod http;
 
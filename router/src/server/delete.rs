@@ -0,0 +1,287 @@
+//! gRPC predicate delete path for `router`.
+
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use data_types::{DeletePredicate, NamespaceId, NamespaceName};
+use generated_types::influxdata::iox::delete::v1::{
+    self as proto, delete_service_server::DeleteService,
+};
+use hashbrown::HashMap;
+use iox_catalog::interface::{Catalog, SoftDeletedRows};
+use mutable_batch::MutableBatch;
+use observability_deps::tracing::*;
+use tonic::{Request, Response, Status};
+use trace::ctx::SpanContext;
+
+use crate::{
+    dml_handlers::DmlHandler,
+    namespace_resolver::NamespaceResolver,
+    server::flight::{dml_error_to_status, namespace_resolver_error_to_status},
+};
+
+/// Applies predicate deletes received through the gRPC [`DeleteService`].
+#[async_trait]
+pub trait Deleter: Debug + Send + Sync {
+    /// Delete the rows matching `predicate` from the table `table_name`
+    /// within the namespace `namespace_id`, or from all of its tables if
+    /// `table_name` is [`None`].
+    async fn delete(
+        &self,
+        namespace_id: NamespaceId,
+        table_name: Option<&str>,
+        predicate: &DeletePredicate,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), Status>;
+}
+
+/// A [`Deleter`] passing deletes to the `dml_handler`, as for a delete
+/// request to the HTTP API.
+#[derive(Debug)]
+pub struct DeleteDelegate<D, N> {
+    catalog: Arc<dyn Catalog>,
+    namespace_resolver: N,
+    dml_handler: D,
+}
+
+impl<D, N> DeleteDelegate<D, N> {
+    /// Initialise a new [`DeleteDelegate`] passing deletes for namespaces
+    /// resolved by `namespace_resolver` to `dml_handler`.
+    ///
+    /// The name of the namespace deleted from is looked up in `catalog`.
+    pub fn new(catalog: Arc<dyn Catalog>, namespace_resolver: N, dml_handler: D) -> Self {
+        Self {
+            catalog,
+            namespace_resolver,
+            dml_handler,
+        }
+    }
+}
+
+#[async_trait]
+impl<D, N> Deleter for DeleteDelegate<D, N>
+where
+    D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()>,
+    N: NamespaceResolver,
+{
+    async fn delete(
+        &self,
+        namespace_id: NamespaceId,
+        table_name: Option<&str>,
+        predicate: &DeletePredicate,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), Status> {
+        let namespace = self
+            .catalog
+            .repositories()
+            .await
+            .namespaces()
+            .get_by_id(namespace_id, SoftDeletedRows::ExcludeDeleted)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| Status::not_found(format!("namespace {namespace_id} not found")))?;
+        let namespace =
+            NamespaceName::try_from(namespace.name).map_err(|e| Status::internal(e.to_string()))?;
+
+        let namespace_schema = self
+            .namespace_resolver
+            .get_namespace_schema(&namespace)
+            .await
+            .map_err(|e| namespace_resolver_error_to_status(&e))?;
+
+        // As for the HTTP API, a delete that specifies no table applies to
+        // all tables in the namespace.
+        let tables = match table_name {
+            Some(v) => vec![v.to_string()],
+            None => namespace_schema.tables.keys().cloned().collect(),
+        };
+
+        debug!(%namespace, ?tables, ?predicate, "routing rpc delete");
+
+        for table_name in tables {
+            self.dml_handler
+                .delete(
+                    &namespace,
+                    Arc::clone(&namespace_schema),
+                    &table_name,
+                    predicate,
+                    span_ctx.clone(),
+                )
+                .await
+                .map_err(|e| dml_error_to_status(&e.into()))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A gRPC [`DeleteService`] decoding delete requests, and passing them to a
+/// [`Deleter`].
+#[derive(Debug)]
+pub struct RpcDeleteService {
+    deleter: Arc<dyn Deleter>,
+}
+
+impl RpcDeleteService {
+    /// Initialise a new [`RpcDeleteService`] applying the deletes it
+    /// receives using `deleter`.
+    pub fn new(deleter: Arc<dyn Deleter>) -> Self {
+        Self { deleter }
+    }
+}
+
+#[tonic::async_trait]
+impl DeleteService for RpcDeleteService {
+    async fn delete(
+        &self,
+        request: Request<proto::DeleteRequest>,
+    ) -> Result<Response<proto::DeleteResponse>, Status> {
+        let span_ctx: Option<SpanContext> = request.extensions().get().cloned();
+
+        let payload = request
+            .into_inner()
+            .payload
+            .ok_or_else(|| Status::invalid_argument("delete request does not contain a payload"))?;
+        let predicate = payload
+            .predicate
+            .ok_or_else(|| Status::invalid_argument("delete request does not contain a predicate"))
+            .and_then(|p| {
+                DeletePredicate::try_from(p).map_err(|e| Status::invalid_argument(e.to_string()))
+            })?;
+        let table_name = Some(payload.table_name.as_str()).filter(|v| !v.is_empty());
+
+        self.deleter
+            .delete(
+                NamespaceId::new(payload.database_id),
+                table_name,
+                &predicate,
+                span_ctx,
+            )
+            .await?;
+
+        Ok(Response::new(proto::DeleteResponse {}))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::TimestampRange;
+    use iox_catalog::{mem::MemCatalog, test_helpers::arbitrary_namespace};
+    use tonic::Code;
+
+    use super::*;
+    use crate::{
+        dml_handlers::{
+            mock::{MockDmlHandler, MockDmlHandlerCall},
+            DmlError,
+        },
+        namespace_resolver::mock::MockNamespaceResolver,
+        test_helpers::{NAMESPACE_NAME, TABLE_NAME},
+    };
+
+    type Handler = Arc<MockDmlHandler<HashMap<String, MutableBatch>>>;
+
+    async fn service(dml_handler: Handler) -> (NamespaceId, RpcDeleteService) {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let namespace =
+            arbitrary_namespace(&mut *catalog.repositories().await, NAMESPACE_NAME).await;
+
+        let delegate = DeleteDelegate::new(
+            catalog,
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, namespace.id),
+            dml_handler,
+        );
+        (namespace.id, RpcDeleteService::new(Arc::new(delegate)))
+    }
+
+    fn request(namespace_id: NamespaceId, table_name: &str) -> Request<proto::DeleteRequest> {
+        Request::new(proto::DeleteRequest {
+            payload: Some(proto::DeletePayload {
+                database_id: namespace_id.get(),
+                table_name: table_name.to_string(),
+                predicate: Some(
+                    (&DeletePredicate {
+                        range: TimestampRange::new(1, 2),
+                        exprs: vec![],
+                    })
+                        .into(),
+                ),
+                ..Default::default()
+            }),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_delete() {
+        let dml_handler = Arc::new(MockDmlHandler::default().with_delete_return([Ok(())]));
+        let (namespace_id, service) = service(Arc::clone(&dml_handler)).await;
+
+        service
+            .delete(request(namespace_id, TABLE_NAME))
+            .await
+            .expect("delete should succeed");
+
+        assert_matches!(dml_handler.calls().as_slice(), [MockDmlHandlerCall::Delete {
+            namespace,
+            namespace_schema,
+            table,
+            predicate,
+        }] => {
+            assert_eq!(namespace, NAMESPACE_NAME);
+            assert_eq!(namespace_schema.id, namespace_id);
+            assert_eq!(table, TABLE_NAME);
+            assert_eq!(predicate.range, TimestampRange::new(1, 2));
+        });
+    }
+
+    #[tokio::test]
+    async fn test_delete_unknown_namespace() {
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let (namespace_id, service) = service(Arc::clone(&dml_handler)).await;
+
+        let err = service
+            .delete(request(
+                NamespaceId::new(namespace_id.get() + 1),
+                TABLE_NAME,
+            ))
+            .await
+            .expect_err("delete should fail");
+
+        assert_eq!(err.code(), Code::NotFound);
+        assert!(dml_handler.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_no_predicate() {
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let (namespace_id, service) = service(Arc::clone(&dml_handler)).await;
+
+        let mut request = request(namespace_id, TABLE_NAME);
+        request.get_mut().payload.as_mut().unwrap().predicate = None;
+        let err = service
+            .delete(request)
+            .await
+            .expect_err("delete should fail");
+
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(dml_handler.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_dml_error() {
+        let dml_handler = Arc::new(
+            MockDmlHandler::default()
+                .with_delete_return([Err(DmlError::NamespaceNotFound(NAMESPACE_NAME.to_string()))]),
+        );
+        let (namespace_id, service) = service(Arc::clone(&dml_handler)).await;
+
+        let err = service
+            .delete(request(namespace_id, TABLE_NAME))
+            .await
+            .expect_err("delete should fail");
+
+        assert_eq!(err.code(), Code::NotFound);
+    }
+}
@@ -0,0 +1,276 @@
+//! Arrow Flight write path for `router`.
+
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use data_types::NamespaceName;
+use hashbrown::HashMap;
+use mutable_batch::MutableBatch;
+use observability_deps::tracing::*;
+use service_grpc_flight::FlightWriter;
+use tonic::Status;
+use trace::ctx::SpanContext;
+
+use crate::{
+    dml_handlers::{
+        client::RpcWriteClientError, DmlError, DmlHandler, PartitionError, RetentionError,
+        RpcWriteError,
+    },
+    namespace_resolver::{self, NamespaceCreationError, NamespaceResolver},
+    schema_validator::SchemaError,
+};
+
+/// A [`FlightWriter`] converting Arrow data written through Flight `DoPut`
+/// into a [`MutableBatch`], and passing it to the `dml_handler`, as for a
+/// line protocol write to the HTTP API.
+#[derive(Debug)]
+pub struct FlightWriteDelegate<D, N> {
+    namespace_resolver: N,
+    dml_handler: D,
+}
+
+impl<D, N> FlightWriteDelegate<D, N> {
+    /// Initialise a new [`FlightWriteDelegate`] passing writes for namespaces
+    /// resolved by `namespace_resolver` to `dml_handler`.
+    pub fn new(namespace_resolver: N, dml_handler: D) -> Self {
+        Self {
+            namespace_resolver,
+            dml_handler,
+        }
+    }
+}
+
+#[async_trait]
+impl<D, N> FlightWriter for FlightWriteDelegate<D, N>
+where
+    D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()>,
+    N: NamespaceResolver,
+{
+    async fn write(
+        &self,
+        namespace: &NamespaceName<'static>,
+        table_name: &str,
+        batches: Vec<RecordBatch>,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), Status> {
+        let mut batch = MutableBatch::new();
+        for b in &batches {
+            mutable_batch_arrow::write_record_batch(&mut batch, b)
+                .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        }
+
+        if batch.rows() == 0 {
+            debug!(%namespace, %table_name, "nothing to write");
+            return Ok(());
+        }
+
+        trace!(
+            %namespace,
+            %table_name,
+            num_rows = batch.rows(),
+            "processing flight write request"
+        );
+
+        let namespace_schema = self
+            .namespace_resolver
+            .get_namespace_schema(namespace)
+            .await
+            .map_err(|e| namespace_resolver_error_to_status(&e))?;
+
+        self.dml_handler
+            .write(
+                namespace,
+                namespace_schema,
+                HashMap::from([(table_name.to_string(), batch)]),
+                span_ctx,
+            )
+            .await
+            .map_err(|e| dml_error_to_status(&e.into()))
+    }
+}
+
+/// Map a [`NamespaceResolver`] error into a gRPC [`Status`].
+///
+/// Errors are the fault of the caller only when the namespace does not exist
+/// and autocreation is disabled.
+pub(crate) fn namespace_resolver_error_to_status(e: &namespace_resolver::Error) -> Status {
+    match e {
+        namespace_resolver::Error::Create(NamespaceCreationError::Reject(_)) => {
+            Status::not_found(e.to_string())
+        }
+        _ => Status::internal(e.to_string()),
+    }
+}
+
+/// Map a [`DmlError`] into a gRPC [`Status`], mirroring the HTTP status codes
+/// returned for line protocol writes.
+pub(crate) fn dml_error_to_status(e: &DmlError) -> Status {
+    let msg = e.to_string();
+    match e {
+        DmlError::NamespaceNotFound(_) => Status::not_found(msg),
+
+        DmlError::Schema(SchemaError::ServiceLimit(_) | SchemaError::Conflict(_)) => {
+            Status::invalid_argument(msg)
+        }
+        DmlError::Schema(SchemaError::UnexpectedCatalogError(_)) => Status::internal(msg),
+
+        DmlError::Internal(_)
+        | DmlError::Partition(PartitionError::BatchWrite(_) | PartitionError::Partitioner(_)) => {
+            Status::internal(msg)
+        }
+        DmlError::Retention(RetentionError::OutsideRetention { .. }) => {
+            Status::permission_denied(msg)
+        }
+        DmlError::RpcWrite(RpcWriteError::Client(
+            RpcWriteClientError::Upstream(_)
+            | RpcWriteClientError::MisconfiguredMetadataKey(_)
+            | RpcWriteClientError::MisconfiguredMetadataValue(_),
+        )) => Status::internal(msg),
+        DmlError::RpcWrite(RpcWriteError::Client(RpcWriteClientError::UpstreamNotConnected(_))) => {
+            Status::unavailable(msg)
+        }
+        DmlError::RpcWrite(RpcWriteError::Timeout(_)) => Status::deadline_exceeded(msg),
+        DmlError::RpcWrite(
+            RpcWriteError::NoHealthyUpstreams
+            | RpcWriteError::NotEnoughReplicas
+            | RpcWriteError::PartialWrite { .. },
+        ) => Status::unavailable(msg),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{ArrayRef, Float64Array, TimestampNanosecondArray};
+    use assert_matches::assert_matches;
+    use data_types::NamespaceId;
+    use tonic::Code;
+
+    use super::*;
+    use crate::{
+        dml_handlers::mock::{MockDmlHandler, MockDmlHandlerCall},
+        namespace_resolver::mock::MockNamespaceResolver,
+        test_helpers::{NAMESPACE_NAME, TABLE_NAME},
+    };
+
+    fn batch(values: Vec<f64>) -> RecordBatch {
+        let times = (0..values.len() as i64).collect::<Vec<_>>();
+        RecordBatch::try_from_iter(vec![
+            ("usage", Arc::new(Float64Array::from(values)) as ArrayRef),
+            (
+                "time",
+                Arc::new(TimestampNanosecondArray::from(times)) as ArrayRef,
+            ),
+        ])
+        .unwrap()
+    }
+
+    fn delegate(
+        dml_handler: Arc<MockDmlHandler<HashMap<String, MutableBatch>>>,
+    ) -> FlightWriteDelegate<
+        Arc<MockDmlHandler<HashMap<String, MutableBatch>>>,
+        MockNamespaceResolver,
+    > {
+        FlightWriteDelegate::new(
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NamespaceId::new(42)),
+            dml_handler,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_write() {
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let delegate = delegate(Arc::clone(&dml_handler));
+
+        let namespace = NamespaceName::try_from(NAMESPACE_NAME).unwrap();
+        delegate
+            .write(
+                &namespace,
+                TABLE_NAME,
+                vec![batch(vec![1.0, 2.0]), batch(vec![3.0])],
+                None,
+            )
+            .await
+            .expect("write should succeed");
+
+        assert_matches!(dml_handler.calls().as_slice(), [MockDmlHandlerCall::Write {
+            namespace,
+            namespace_schema,
+            write_input,
+        }] => {
+            assert_eq!(namespace, NAMESPACE_NAME);
+            assert_eq!(namespace_schema.id, NamespaceId::new(42));
+            // All the batches are written to the single table.
+            assert_eq!(write_input.len(), 1);
+            assert_eq!(write_input[TABLE_NAME].rows(), 3);
+        });
+    }
+
+    #[tokio::test]
+    async fn test_write_empty() {
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let delegate = delegate(Arc::clone(&dml_handler));
+
+        let namespace = NamespaceName::try_from(NAMESPACE_NAME).unwrap();
+        delegate
+            .write(&namespace, TABLE_NAME, vec![batch(vec![])], None)
+            .await
+            .expect("empty write should succeed");
+
+        assert!(dml_handler.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_write_invalid_batch() {
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let delegate = delegate(Arc::clone(&dml_handler));
+
+        // No time column
+        let invalid = RecordBatch::try_from_iter(vec![(
+            "usage",
+            Arc::new(Float64Array::from(vec![1.0])) as ArrayRef,
+        )])
+        .unwrap();
+
+        let namespace = NamespaceName::try_from(NAMESPACE_NAME).unwrap();
+        let err = delegate
+            .write(&namespace, TABLE_NAME, vec![invalid], None)
+            .await
+            .expect_err("write should fail");
+
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(dml_handler.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_write_unknown_namespace() {
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let delegate = delegate(Arc::clone(&dml_handler));
+
+        let namespace = NamespaceName::try_from("bananas").unwrap();
+        let err = delegate
+            .write(&namespace, TABLE_NAME, vec![batch(vec![1.0])], None)
+            .await
+            .expect_err("write should fail");
+
+        assert_eq!(err.code(), Code::Internal);
+        assert!(dml_handler.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_write_dml_error() {
+        let dml_handler = Arc::new(
+            MockDmlHandler::default()
+                .with_write_return([Err(DmlError::NamespaceNotFound(NAMESPACE_NAME.to_string()))]),
+        );
+        let delegate = delegate(Arc::clone(&dml_handler));
+
+        let namespace = NamespaceName::try_from(NAMESPACE_NAME).unwrap();
+        let err = delegate
+            .write(&namespace, TABLE_NAME, vec![batch(vec![1.0])], None)
+            .await
+            .expect_err("write should fail");
+
+        assert_eq!(err.code(), Code::NotFound);
+    }
+}
@@ -1,30 +1,76 @@
 //! gRPC service implementations for `router`.
 
+use arrow_flight::flight_service_server::{FlightService, FlightServiceServer};
+use authz::Authorizer;
 use generated_types::influxdata::iox::{
-    catalog::v1::*, namespace::v1::*, object_store::v1::*, table::v1::*,
+    catalog::v1::*, delete::v1::*, namespace::v1::*, object_store::v1::*, table::v1::*,
 };
 use iox_catalog::interface::Catalog;
 use object_store::DynObjectStore;
 use service_grpc_catalog::CatalogService;
-use service_grpc_namespace::NamespaceService;
+use service_grpc_flight::FlightWriter;
+use service_grpc_namespace::{NamespaceChangeObserver, NamespaceService};
 use service_grpc_object_store::ObjectStoreService;
 use service_grpc_schema::SchemaService;
-use service_grpc_table::TableService;
-use std::sync::Arc;
+use service_grpc_table::{DeletionObserver, TableService};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::Semaphore;
+
+use super::delete::{Deleter, RpcDeleteService};
 
 /// This type manages all gRPC services exposed by a `router` using the RPC write path.
 #[derive(Debug)]
 pub struct RpcWriteGrpcDelegate {
     catalog: Arc<dyn Catalog>,
     object_store: Arc<DynObjectStore>,
+    deletion_observer: Arc<dyn DeletionObserver>,
+    namespace_observer: Arc<dyn NamespaceChangeObserver>,
+    undelete_grace_period: Duration,
+    flight_writer: Arc<dyn FlightWriter>,
+    deleter: Arc<dyn Deleter>,
+    max_request_bytes: usize,
+    request_sem: Arc<Semaphore>,
+    authz: Option<Arc<dyn Authorizer>>,
 }
 
 impl RpcWriteGrpcDelegate {
-    /// Create a new gRPC handler
-    pub fn new(catalog: Arc<dyn Catalog>, object_store: Arc<DynObjectStore>) -> Self {
+    /// Create a new gRPC handler, notifying `deletion_observer` of tables and
+    /// columns deleted through the [`TableService`], and `namespace_observer`
+    /// of namespaces renamed or restored through the [`NamespaceService`].
+    ///
+    /// Namespaces deleted within `undelete_grace_period` can be restored.
+    ///
+    /// Arrow data written through the Flight service is passed to
+    /// `flight_writer`, once authorised by `authz`, if any. Flight writes are
+    /// limited to `max_request_bytes` in size, and hold a permit from
+    /// `request_sem` while they are serviced.
+    ///
+    /// Predicate deletes received through the gRPC delete service are
+    /// applied by `deleter`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        catalog: Arc<dyn Catalog>,
+        object_store: Arc<DynObjectStore>,
+        deletion_observer: Arc<dyn DeletionObserver>,
+        namespace_observer: Arc<dyn NamespaceChangeObserver>,
+        undelete_grace_period: Duration,
+        flight_writer: Arc<dyn FlightWriter>,
+        deleter: Arc<dyn Deleter>,
+        max_request_bytes: usize,
+        request_sem: Arc<Semaphore>,
+        authz: Option<Arc<dyn Authorizer>>,
+    ) -> Self {
         Self {
             catalog,
             object_store,
+            deletion_observer,
+            namespace_observer,
+            undelete_grace_period,
+            flight_writer,
+            deleter,
+            max_request_bytes,
+            request_sem,
+            authz,
         }
     }
 
@@ -54,6 +100,8 @@ impl RpcWriteGrpcDelegate {
     /// [`NamespaceService`]: generated_types::influxdata::iox::namespace::v1::namespace_service_server::NamespaceService.
     pub fn namespace_service(&self) -> impl namespace_service_server::NamespaceService {
         NamespaceService::new(Arc::clone(&self.catalog))
+            .with_undelete_grace_period(self.undelete_grace_period)
+            .with_change_observer(Arc::clone(&self.namespace_observer))
     }
 
     /// Acquire a [`TableService`] gRPC service implementation.
@@ -61,5 +109,25 @@ impl RpcWriteGrpcDelegate {
     /// [`TableService`]: generated_types::influxdata::iox::table::v1::table_service_server::TableService
     pub fn table_service(&self) -> impl table_service_server::TableService {
         TableService::new(Arc::clone(&self.catalog))
+            .with_deletion_observer(Arc::clone(&self.deletion_observer))
+    }
+
+    /// Acquire an Arrow Flight gRPC service implementation accepting writes
+    /// through `DoPut`.
+    pub fn flight_service(&self) -> FlightServiceServer<impl FlightService> {
+        service_grpc_flight::make_write_server(
+            Arc::clone(&self.flight_writer),
+            self.authz.clone(),
+            self.max_request_bytes,
+            Arc::clone(&self.request_sem),
+        )
+    }
+
+    /// Acquire a [`DeleteService`] gRPC service implementation, applying
+    /// predicate deletes through the DML handler stack.
+    ///
+    /// [`DeleteService`]: generated_types::influxdata::iox::delete::v1::delete_service_server::DeleteService
+    pub fn delete_service(&self) -> impl delete_service_server::DeleteService {
+        RpcDeleteService::new(Arc::clone(&self.deleter))
     }
 }
@@ -2,17 +2,21 @@
 
 pub mod write;
 
-use std::{str::Utf8Error, time::Instant};
+use std::{str::Utf8Error, sync::Arc, time::Instant};
 
 use bytes::{Bytes, BytesMut};
+use data_types::{DeletePredicate, NamespaceSchema, Op, Scalar};
 use futures::StreamExt;
 use hashbrown::HashMap;
 use hyper::{header::CONTENT_ENCODING, Body, Method, Request, Response, StatusCode};
 use iox_time::{SystemProvider, TimeProvider};
 use metric::{DurationHistogram, U64Counter};
 use mutable_batch::MutableBatch;
-use mutable_batch_lp::LinesConverter;
+use mutable_batch_lp::{LinesConverter, PartialWriteSchema};
 use observability_deps::tracing::*;
+use predicate::delete_predicate::parse_delete_predicate;
+use schema::InfluxColumnType;
+use serde::Deserialize;
 use thiserror::Error;
 use tokio::sync::{Semaphore, TryAcquireError};
 use trace::ctx::SpanContext;
@@ -37,9 +41,18 @@ pub enum Error {
     #[error("not found")]
     NoHandler,
 
-    /// A delete request was rejected (not supported).
-    #[error("deletes are not supported")]
-    DeletesUnsupported,
+    /// The delete request body is not a valid JSON delete request.
+    #[error("failed to deserialise delete request: {0}")]
+    ParseHttpDelete(serde_json::Error),
+
+    /// Failure to parse the time range or predicate of a delete request.
+    #[error("failed to parse delete predicate: {0}")]
+    ParseDelete(predicate::delete_predicate::Error),
+
+    /// The delete predicate specifies a `_measurement` that is not a simple
+    /// equality match against a string.
+    #[error("delete predicate must specify the measurement as _measurement=\"name\"")]
+    InvalidDeleteMeasurement,
 
     /// An error parsing a single-tenant HTTP request.
     #[error(transparent)]
@@ -77,6 +90,11 @@ pub enum Error {
     #[error("failed to parse line protocol: {0}")]
     ParseLineProtocol(mutable_batch_lp::Error),
 
+    /// Some lines of a partial write were rejected, and the remaining lines
+    /// were routed.
+    #[error(transparent)]
+    PartialWrite(PartialWriteError),
+
     /// An error returned from the [`DmlHandler`].
     #[error("dml handler error: {0}")]
     DmlHandler(#[from] DmlError),
@@ -108,12 +126,15 @@ impl Error {
     pub fn as_status_code(&self) -> StatusCode {
         match self {
             Error::NoHandler => StatusCode::NOT_FOUND,
-            Error::DeletesUnsupported => StatusCode::NOT_IMPLEMENTED,
+            Error::ParseHttpDelete(_) => StatusCode::BAD_REQUEST,
+            Error::ParseDelete(_) => StatusCode::BAD_REQUEST,
+            Error::InvalidDeleteMeasurement => StatusCode::BAD_REQUEST,
             Error::ClientHangup(_) => StatusCode::BAD_REQUEST,
             Error::InvalidGzip(_) => StatusCode::BAD_REQUEST,
             Error::NonUtf8ContentHeader(_) => StatusCode::BAD_REQUEST,
             Error::NonUtf8Body(_) => StatusCode::BAD_REQUEST,
             Error::ParseLineProtocol(_) => StatusCode::BAD_REQUEST,
+            Error::PartialWrite(_) => StatusCode::BAD_REQUEST,
             Error::RequestSizeExceeded(_) => StatusCode::PAYLOAD_TOO_LARGE,
             Error::InvalidContentEncoding(_) => {
                 // https://www.rfc-editor.org/rfc/rfc7231#section-6.5.13
@@ -134,6 +155,62 @@ impl Error {
     }
 }
 
+/// The lines rejected by a partial write.
+#[derive(Debug, Error)]
+#[error(
+    "partial write error ({written} lines written): errors encountered on line(s):\n{}",
+    format_rejected_lines(.rejected)
+)]
+pub struct PartialWriteError {
+    /// The number of lines routed.
+    pub written: usize,
+    /// The error for each rejected line.
+    pub rejected: Vec<mutable_batch_lp::Error>,
+}
+
+/// The cached [`NamespaceSchema`] that the lines of a partial write are
+/// validated against.
+///
+/// The whole write is validated against the catalog by the [`DmlHandler`]
+/// once converted, which only fails if the schema changed concurrently.
+#[derive(Debug)]
+struct CachedNamespaceSchema<'a>(&'a NamespaceSchema);
+
+impl PartialWriteSchema for CachedNamespaceSchema<'_> {
+    fn column_type(&self, table: &str, column: &str) -> Option<InfluxColumnType> {
+        self.0
+            .tables
+            .get(table)
+            .and_then(|t| t.columns.get(column))
+            .map(|c| c.column_type.into())
+    }
+
+    fn column_count(&self, table: &str) -> Option<usize> {
+        self.0.tables.get(table).map(|t| t.columns.column_count())
+    }
+
+    fn table_count(&self) -> usize {
+        self.0.tables.len()
+    }
+
+    fn max_columns_per_table(&self) -> usize {
+        self.0.max_columns_per_table.get() as usize
+    }
+
+    fn max_tables(&self) -> usize {
+        self.0.max_tables.get() as usize
+    }
+}
+
+/// Render the rejected lines of a partial write, one per line.
+fn format_rejected_lines(rejected: &[mutable_batch_lp::Error]) -> String {
+    rejected
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 impl From<&DmlError> for StatusCode {
     fn from(e: &DmlError) -> Self {
         match e {
@@ -190,6 +267,10 @@ pub struct HttpDelegate<D, N, T = SystemProvider> {
     dml_handler: D,
     write_request_mode_handler: Box<dyn WriteRequestUnifier>,
 
+    // Accept the valid lines of a write that contains invalid lines, rather
+    // than rejecting the whole write.
+    partial_writes: bool,
+
     // A request limiter to restrict the number of simultaneous requests this
     // router services.
     //
@@ -197,13 +278,15 @@ pub struct HttpDelegate<D, N, T = SystemProvider> {
     // unusual flood of requests (i.e. due to peer routers crashing and
     // depleting the available instances in the pool) in order to preserve
     // overall system availability, instead of OOMing or otherwise failing.
-    request_sem: Semaphore,
+    request_sem: Arc<Semaphore>,
 
     write_metric_lines: U64Counter,
+    write_metric_rejected_lines: U64Counter,
     http_line_protocol_parse_duration: DurationHistogram,
     write_metric_fields: U64Counter,
     write_metric_tables: U64Counter,
     write_metric_body_size: U64Counter,
+    delete_metric_body_size: U64Counter,
     request_limit_rejected: U64Counter,
 }
 
@@ -227,6 +310,12 @@ impl<D, N> HttpDelegate<D, N, SystemProvider> {
                 "cumulative number of line protocol lines successfully routed",
             )
             .recorder(&[]);
+        let write_metric_rejected_lines = metrics
+            .register_metric::<U64Counter>(
+                "http_write_rejected_lines",
+                "cumulative number of line protocol lines rejected by partial writes",
+            )
+            .recorder(&[]);
         let write_metric_fields = metrics
             .register_metric::<U64Counter>(
                 "http_write_fields",
@@ -245,6 +334,12 @@ impl<D, N> HttpDelegate<D, N, SystemProvider> {
                 "cumulative byte size of successfully routed (decompressed) line protocol write requests",
             )
             .recorder(&[]);
+        let delete_metric_body_size = metrics
+            .register_metric::<U64Counter>(
+                "http_delete_body_bytes",
+                "cumulative byte size of successfully routed (decompressed) delete requests",
+            )
+            .recorder(&[]);
         let request_limit_rejected = metrics
             .register_metric::<U64Counter>(
                 "http_request_limit_rejected",
@@ -264,17 +359,36 @@ impl<D, N> HttpDelegate<D, N, SystemProvider> {
             namespace_resolver,
             write_request_mode_handler,
             dml_handler,
-            request_sem: Semaphore::new(max_requests),
+            partial_writes: false,
+            request_sem: Arc::new(Semaphore::new(max_requests)),
             write_metric_lines,
+            write_metric_rejected_lines,
             http_line_protocol_parse_duration,
             write_metric_fields,
             write_metric_tables,
             write_metric_body_size,
+            delete_metric_body_size,
             request_limit_rejected,
         }
     }
 }
 
+impl<D, N, T> HttpDelegate<D, N, T> {
+    /// Return the limiter of simultaneous requests serviced by this
+    /// [`HttpDelegate`], allowing other write paths to share the limit.
+    pub fn request_limiter(&self) -> Arc<Semaphore> {
+        Arc::clone(&self.request_sem)
+    }
+
+    /// Accept the valid lines of a line protocol write that contains invalid
+    /// lines when `enabled`, returning [`Error::PartialWrite`] listing the
+    /// rejected lines.
+    pub fn with_partial_writes(mut self, enabled: bool) -> Self {
+        self.partial_writes = enabled;
+        self
+    }
+}
+
 impl<D, N, T> HttpDelegate<D, N, T>
 where
     D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()>,
@@ -311,7 +425,10 @@ where
                 let dml_info = self.write_request_mode_handler.parse_v2(&req).await?;
                 self.write_handler(req, dml_info).await
             }
-            (&Method::POST, "/api/v2/delete") => return Err(Error::DeletesUnsupported),
+            (&Method::POST, "/api/v2/delete") => {
+                let dml_info = self.write_request_mode_handler.parse_v2(&req).await?;
+                self.delete_handler(req, dml_info).await
+            }
             _ => return Err(Error::NoHandler),
         }
         .map(|_summary| {
@@ -345,12 +462,45 @@ where
 
         let mut converter = LinesConverter::new(default_time);
         converter.set_timestamp_base(write_info.precision.timestamp_base());
-        let (batches, stats) = match converter.write_lp(body).and_then(|_| converter.finish()) {
+
+        // A partial write rejects the lines that conflict with the namespace
+        // schema or exceed its limits, and the schema is therefore retrieved
+        // before the conversion.
+        let mut namespace_schema = None;
+        let mut rejected = vec![];
+        let converted = if self.partial_writes {
+            let schema = self
+                .namespace_resolver
+                .get_namespace_schema(&write_info.namespace)
+                .await?;
+            rejected = converter.write_lp_partial(body, &CachedNamespaceSchema(&schema));
+            namespace_schema = Some(schema);
+            converter.finish()
+        } else {
+            converter.write_lp(body).and_then(|_| converter.finish())
+        };
+
+        if !rejected.is_empty() {
+            debug!(
+                rejected_lines = rejected.len(),
+                namespace=%write_info.namespace,
+                "rejected lines of partial write",
+            );
+            self.write_metric_rejected_lines.inc(rejected.len() as _);
+        }
+
+        let (batches, stats) = match converted {
             Ok(v) => v,
-            Err(mutable_batch_lp::Error::EmptyPayload) => {
+            Err(mutable_batch_lp::Error::EmptyPayload) if rejected.is_empty() => {
                 debug!("nothing to write");
                 return Ok(());
             }
+            Err(mutable_batch_lp::Error::EmptyPayload) => {
+                return Err(Error::PartialWrite(PartialWriteError {
+                    written: 0,
+                    rejected,
+                }))
+            }
             Err(e) => return Err(Error::ParseLineProtocol(e)),
         };
 
@@ -369,10 +519,14 @@ where
         );
 
         // Retrieve the namespace schema for this namespace.
-        let namespace_schema = self
-            .namespace_resolver
-            .get_namespace_schema(&write_info.namespace)
-            .await?;
+        let namespace_schema = match namespace_schema {
+            Some(v) => v,
+            None => {
+                self.namespace_resolver
+                    .get_namespace_schema(&write_info.namespace)
+                    .await?
+            }
+        };
 
         self.dml_handler
             .write(&write_info.namespace, namespace_schema, batches, span_ctx)
@@ -384,6 +538,76 @@ where
         self.write_metric_tables.inc(num_tables as _);
         self.write_metric_body_size.inc(body.len() as _);
 
+        if !rejected.is_empty() {
+            return Err(Error::PartialWrite(PartialWriteError {
+                written: stats.num_lines,
+                rejected,
+            }));
+        }
+
+        Ok(())
+    }
+
+    async fn delete_handler(
+        &self,
+        req: Request<Body>,
+        write_info: WriteParams,
+    ) -> Result<(), Error> {
+        let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+
+        trace!(
+            namespace=%write_info.namespace,
+            "processing delete request"
+        );
+
+        // Read the HTTP body and decode the JSON delete request.
+        let body = self.read_body(req).await?;
+        let request: HttpDeleteRequest =
+            serde_json::from_slice(&body).map_err(Error::ParseHttpDelete)?;
+
+        let mut predicate =
+            parse_delete_predicate(&request.start, &request.stop, &request.predicate)
+                .map_err(Error::ParseDelete)?;
+
+        // The table to delete from is specified as part of the predicate.
+        let table_name = take_measurement(&mut predicate)?;
+
+        debug!(
+            namespace=%write_info.namespace,
+            table_name=?table_name,
+            ?predicate,
+            body_size=body.len(),
+            "routing delete",
+        );
+
+        // Retrieve the namespace schema for this namespace.
+        let namespace_schema = self
+            .namespace_resolver
+            .get_namespace_schema(&write_info.namespace)
+            .await?;
+
+        // If no measurement was specified, the delete applies to all tables
+        // in the namespace.
+        let tables = match table_name {
+            Some(v) => vec![v],
+            None => namespace_schema.tables.keys().cloned().collect(),
+        };
+
+        for table_name in tables {
+            self.dml_handler
+                .delete(
+                    &write_info.namespace,
+                    Arc::clone(&namespace_schema),
+                    &table_name,
+                    &predicate,
+                    span_ctx.clone(),
+                )
+                .await
+                .map_err(Into::into)?;
+        }
+
+        self.delete_metric_body_size.inc(body.len() as _);
+
         Ok(())
     }
 
@@ -445,13 +669,47 @@ where
     }
 }
 
+/// The JSON body of an `/api/v2/delete` request.
+#[derive(Debug, Deserialize)]
+struct HttpDeleteRequest {
+    start: String,
+    stop: String,
+    #[serde(default)]
+    predicate: String,
+}
+
+/// The pseudo-column used to specify the measurement (table) a delete
+/// applies to.
+const MEASUREMENT_COLUMN: &str = "_measurement";
+
+/// Remove the `_measurement="name"` expression from `predicate`, returning the
+/// table name it specifies, if any.
+fn take_measurement(predicate: &mut DeletePredicate) -> Result<Option<String>, Error> {
+    let mut table_name = None;
+    for expr in std::mem::take(&mut predicate.exprs) {
+        if expr.column != MEASUREMENT_COLUMN {
+            predicate.exprs.push(expr);
+            continue;
+        }
+
+        match (expr.op, expr.scalar) {
+            (Op::Eq, Scalar::String(name)) if table_name.is_none() => table_name = Some(name),
+            _ => return Err(Error::InvalidDeleteMeasurement),
+        }
+    }
+
+    Ok(table_name)
+}
+
 #[cfg(test)]
 mod tests {
     use std::{io::Write, iter, sync::Arc, time::Duration};
 
     use assert_matches::assert_matches;
     use data_types::{
+        ColumnId, ColumnSchema, ColumnType, ColumnsByName, MaxColumnsPerTable, MaxTables,
         NamespaceId, NamespaceName, NamespaceNameError, OrgBucketMappingError, TableId,
+        TableSchema,
     };
     use flate2::{write::GzEncoder, Compression};
     use hyper::header::HeaderValue;
@@ -584,6 +842,7 @@ mod tests {
                         .with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
                     let dml_handler = Arc::new(MockDmlHandler::default()
                         .with_write_return($dml_write_handler)
+                        .with_delete_return($dml_delete_handler)
                     );
                     let metrics = Arc::new(metric::Registry::default());
                     let delegate = HttpDelegate::new(
@@ -922,6 +1181,63 @@ mod tests {
         want_dml_calls = []
     );
 
+    test_http_handler!(
+        delete_ok,
+        uri = "https://bananas.example/api/v2/delete?org=bananas&bucket=test",
+        body = r#"{"start":"1","stop":"2","predicate":"_measurement=\"platanos\" AND tag=\"A\""}"#.as_bytes(),
+        dml_write_handler = [],
+        dml_delete_handler = [Ok(())],
+        want_result = Ok(_),
+        want_dml_calls = [MockDmlHandlerCall::Delete { namespace, table, predicate, .. }] => {
+            assert_eq!(namespace, NAMESPACE_NAME);
+            assert_eq!(table, "platanos");
+            assert_eq!(predicate.range, data_types::TimestampRange::new(1, 2));
+            assert_matches!(predicate.exprs.as_slice(), [expr] => {
+                assert_eq!(expr.column, "tag");
+            });
+        }
+    );
+
+    test_http_handler!(
+        delete_invalid_json,
+        uri = "https://bananas.example/api/v2/delete?org=bananas&bucket=test",
+        body = "not json".as_bytes(),
+        dml_write_handler = [],
+        dml_delete_handler = [],
+        want_result = Err(Error::ParseHttpDelete(_)),
+        want_dml_calls = []
+    );
+
+    test_http_handler!(
+        delete_invalid_predicate,
+        uri = "https://bananas.example/api/v2/delete?org=bananas&bucket=test",
+        body = r#"{"start":"1","stop":"2","predicate":"tag > 42"}"#.as_bytes(),
+        dml_write_handler = [],
+        dml_delete_handler = [],
+        want_result = Err(Error::ParseDelete(_)),
+        want_dml_calls = []
+    );
+
+    test_http_handler!(
+        delete_invalid_measurement,
+        uri = "https://bananas.example/api/v2/delete?org=bananas&bucket=test",
+        body = r#"{"start":"1","stop":"2","predicate":"_measurement!=\"platanos\""}"#.as_bytes(),
+        dml_write_handler = [],
+        dml_delete_handler = [],
+        want_result = Err(Error::InvalidDeleteMeasurement),
+        want_dml_calls = []
+    );
+
+    test_http_handler!(
+        delete_handler_error,
+        uri = "https://bananas.example/api/v2/delete?org=bananas&bucket=test",
+        body = r#"{"start":"1","stop":"2","predicate":"_measurement=\"platanos\""}"#.as_bytes(),
+        dml_write_handler = [],
+        dml_delete_handler = [Err(DmlError::NamespaceNotFound(NAMESPACE_NAME.to_string()))],
+        want_result = Err(Error::DmlHandler(DmlError::NamespaceNotFound(_))),
+        want_dml_calls = [MockDmlHandlerCall::Delete { .. }]
+    );
+
     // https://github.com/influxdata/influxdb_iox/issues/4326
     mod issue4326 {
         use super::*;
@@ -1203,6 +1519,161 @@ mod tests {
         assert_matches!(got, Err(Error::NoHandler));
     }
 
+    /// Assert a partial write routes the valid lines, and rejects the lines
+    /// that are invalid or conflict with the namespace schema.
+    #[tokio::test]
+    async fn test_partial_write() {
+        let mut table = TableSchema {
+            id: TableId::new(1),
+            partition_template: Default::default(),
+            columns: ColumnsByName::new([]),
+        };
+        table.add_column_schema(
+            "val".to_string(),
+            ColumnSchema {
+                id: ColumnId::new(1),
+                column_type: ColumnType::I64,
+            },
+        );
+        let mut namespace_schema = crate::test_helpers::new_empty_namespace_schema(42);
+        namespace_schema
+            .tables
+            .insert("platanos".to_string(), table);
+
+        let mock_namespace_resolver = MockNamespaceResolver::new(
+            [(
+                NamespaceName::new(NAMESPACE_NAME).unwrap(),
+                Arc::new(namespace_schema),
+            )]
+            .into_iter()
+            .collect(),
+        );
+
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            1,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        )
+        .with_partial_writes(true);
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::from(
+                "platanos,tag1=A val=42i 1\n\
+                platanos,tag1=B val=4.2 2\n\
+                bananas\n\
+                platanos,tag1=C val=24i 3",
+            ))
+            .unwrap();
+
+        let got = delegate.route(request).await;
+        assert_matches!(got, Err(Error::PartialWrite(PartialWriteError { written: 2, rejected })) => {
+            assert_matches!(
+                rejected.as_slice(),
+                [
+                    mutable_batch_lp::Error::Write { line: 2, .. },
+                    mutable_batch_lp::Error::LineProtocol { line: 3, .. },
+                ]
+            );
+        });
+
+        assert_matches!(dml_handler.calls().as_slice(), [MockDmlHandlerCall::Write { write_input, .. }] => {
+            assert_eq!(write_input["platanos"].rows(), 2);
+        });
+        assert_metric_hit(&metrics, "http_write_lines", Some(2));
+        assert_metric_hit(&metrics, "http_write_rejected_lines", Some(2));
+    }
+
+    /// Assert a partial write rejects the lines that exceed the table and
+    /// column limits of the namespace, and routes the remaining lines.
+    #[tokio::test]
+    async fn test_partial_write_limits() {
+        let mut table = TableSchema {
+            id: TableId::new(1),
+            partition_template: Default::default(),
+            columns: ColumnsByName::new([]),
+        };
+        table.add_column_schema(
+            "val".to_string(),
+            ColumnSchema {
+                id: ColumnId::new(1),
+                column_type: ColumnType::I64,
+            },
+        );
+        let mut namespace_schema = crate::test_helpers::new_empty_namespace_schema(42);
+        namespace_schema
+            .tables
+            .insert("platanos".to_string(), table);
+        namespace_schema.max_columns_per_table = MaxColumnsPerTable::new(3);
+        namespace_schema.max_tables = MaxTables::new(2);
+
+        let mock_namespace_resolver = MockNamespaceResolver::new(
+            [(
+                NamespaceName::new(NAMESPACE_NAME).unwrap(),
+                Arc::new(namespace_schema),
+            )]
+            .into_iter()
+            .collect(),
+        );
+
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            1,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        )
+        .with_partial_writes(true);
+
+        // The first line brings "platanos" to the column limit, which the
+        // second line exceeds. The third line brings the namespace to the
+        // table limit, which the fourth line exceeds.
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::from(
+                "platanos,tag1=A val=42i 1\n\
+                platanos,tag1=B,tag2=B val=24i 2\n\
+                bananas val=42i 3\n\
+                apples val=42i 4",
+            ))
+            .unwrap();
+
+        let got = delegate.route(request).await;
+        assert_matches!(got, Err(Error::PartialWrite(PartialWriteError { written: 2, rejected })) => {
+            assert_matches!(
+                rejected.as_slice(),
+                [
+                    mutable_batch_lp::Error::Write {
+                        line: 2,
+                        source: mutable_batch_lp::LineWriteError::ColumnLimit { .. },
+                    },
+                    mutable_batch_lp::Error::Write {
+                        line: 4,
+                        source: mutable_batch_lp::LineWriteError::TableLimit { .. },
+                    },
+                ]
+            );
+        });
+
+        assert_matches!(dml_handler.calls().as_slice(), [MockDmlHandlerCall::Write { write_input, .. }] => {
+            assert_eq!(write_input.len(), 2);
+            assert_eq!(write_input["platanos"].rows(), 1);
+            assert_eq!(write_input["bananas"].rows(), 1);
+        });
+        assert_metric_hit(&metrics, "http_write_lines", Some(2));
+        assert_metric_hit(&metrics, "http_write_rejected_lines", Some(2));
+    }
+
     /// Assert the router delegates request parsing to the
     /// [`WriteRequestUnifier`] implementation.
     ///
@@ -1327,8 +1798,21 @@ mod tests {
         ),
 
         (
-            DeletesUnsupported,
-            "deletes are not supported",
+            ParseHttpDelete(serde_json::from_str::<HttpDeleteRequest>("{}").unwrap_err()),
+            "failed to deserialise delete request: missing field `start` at line 1 column 2",
+        ),
+
+        (
+            ParseDelete(predicate::delete_predicate::Error::InvalidTimeRange {
+                start: "2".to_string(),
+                stop: "1".to_string(),
+            }),
+            "failed to parse delete predicate: Invalid time range: (2, 1)",
+        ),
+
+        (
+            InvalidDeleteMeasurement,
+            "delete predicate must specify the measurement as _measurement=\"name\"",
         ),
 
         (
@@ -1410,8 +1894,29 @@ mod tests {
         ),
 
         (
-            ParseLineProtocol(mutable_batch_lp::Error::TimestampOverflow),
-            "failed to parse line protocol: timestamp overflows i64",
+            ParseLineProtocol(mutable_batch_lp::Error::TimestampOverflow { line: 42 }),
+            "failed to parse line protocol: error writing line 42: timestamp overflows i64",
+        ),
+
+        (
+            PartialWrite(PartialWriteError {
+                written: 2,
+                rejected: vec![
+                    mutable_batch_lp::Error::LineProtocol {
+                        source: influxdb_line_protocol::Error::FieldSetMissing,
+                        line: 3,
+                    },
+                    mutable_batch_lp::Error::Write {
+                        source: mutable_batch_lp::LineWriteError::DuplicateTag {
+                            name: "host".into(),
+                        },
+                        line: 5,
+                    },
+                ],
+            }),
+            "partial write error (2 lines written): errors encountered on line(s):\n\
+            error parsing line 3 (1-based): No fields were provided\n\
+            error writing line 5: the tag 'host' is specified more than once with conflicting values",
         ),
 
         (
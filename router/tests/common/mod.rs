@@ -1,7 +1,8 @@
 use std::{iter, string::String, sync::Arc, time::Duration};
 
 use data_types::TableId;
-use generated_types::influxdata::iox::ingester::v1::WriteRequest;
+use generated_types::influxdata::iox::{delete::v1::DeleteRequest, ingester::v1::WriteRequest};
+use gossip_schema::handle::SchemaTx;
 use hashbrown::HashMap;
 use hyper::{Body, Request, Response};
 use iox_catalog::{
@@ -14,11 +15,15 @@ use router::{
     dml_handlers::{
         client::mock::MockWriteClient, Chain, DmlHandlerChainExt, FanOutAdaptor,
         InstrumentationDecorator, Partitioned, Partitioner, RetentionValidator, RpcWrite,
+        TombstoneWriter,
     },
+    gossip::deletion_observer::SchemaDeletionObserver,
     namespace_cache::{MemoryNamespaceCache, ReadThroughCache, ShardedCache},
     namespace_resolver::{MissingNamespaceAction, NamespaceAutocreation, NamespaceSchemaResolver},
     schema_validator::SchemaValidator,
     server::{
+        delete::DeleteDelegate,
+        flight::FlightWriteDelegate,
         grpc::RpcWriteGrpcDelegate,
         http::{write::multi_tenant::MultiTenantRequestUnifier, HttpDelegate},
     },
@@ -109,24 +114,30 @@ pub struct TestContext {
 // Fortunately the compiler errors are very descriptive and updating this is
 // relatively easy when something changes!
 type HttpDelegateStack = HttpDelegate<
-    InstrumentationDecorator<
-        Chain<
+    Arc<
+        InstrumentationDecorator<
             Chain<
                 Chain<
-                    RetentionValidator,
-                    SchemaValidator<Arc<ReadThroughCache<Arc<ShardedCache<MemoryNamespaceCache>>>>>,
+                    Chain<
+                        RetentionValidator,
+                        SchemaValidator<
+                            Arc<ReadThroughCache<Arc<ShardedCache<MemoryNamespaceCache>>>>,
+                        >,
+                    >,
+                    Partitioner,
+                >,
+                FanOutAdaptor<
+                    TombstoneWriter<RpcWrite<Arc<MockWriteClient>>>,
+                    Vec<Partitioned<HashMap<TableId, (String, MutableBatch)>>>,
                 >,
-                Partitioner,
-            >,
-            FanOutAdaptor<
-                RpcWrite<Arc<MockWriteClient>>,
-                Vec<Partitioned<HashMap<TableId, (String, MutableBatch)>>>,
             >,
         >,
     >,
-    NamespaceAutocreation<
-        Arc<ReadThroughCache<Arc<ShardedCache<MemoryNamespaceCache>>>>,
-        NamespaceSchemaResolver<Arc<ReadThroughCache<Arc<ShardedCache<MemoryNamespaceCache>>>>>,
+    Arc<
+        NamespaceAutocreation<
+            Arc<ReadThroughCache<Arc<ShardedCache<MemoryNamespaceCache>>>>,
+            NamespaceSchemaResolver<Arc<ReadThroughCache<Arc<ShardedCache<MemoryNamespaceCache>>>>>,
+        >,
     >,
 >;
 
@@ -147,6 +158,7 @@ impl TestContext {
             &metrics,
             rpc_write_num_probes,
         );
+        let rpc_writer = TombstoneWriter::new(rpc_writer, Arc::clone(&catalog));
 
         let ns_cache = Arc::new(ReadThroughCache::new(
             Arc::new(ShardedCache::new(
@@ -177,21 +189,48 @@ impl TestContext {
             .and_then(partitioner)
             .and_then(parallel_write);
 
-        let handler_stack = InstrumentationDecorator::new("request", &metrics, handler_stack);
+        let handler_stack = Arc::new(InstrumentationDecorator::new(
+            "request",
+            &metrics,
+            handler_stack,
+        ));
+        let namespace_resolver = Arc::new(namespace_resolver);
 
         let write_request_unifier = Box::<MultiTenantRequestUnifier>::default();
 
         let http_delegate = HttpDelegate::new(
             1024,
             100,
-            namespace_resolver,
-            handler_stack,
+            Arc::clone(&namespace_resolver),
+            Arc::clone(&handler_stack),
             &metrics,
             write_request_unifier,
         );
 
-        let grpc_delegate =
-            RpcWriteGrpcDelegate::new(Arc::clone(&catalog), Arc::new(InMemory::default()));
+        let deletion_observer = Arc::new(SchemaDeletionObserver::<_, SchemaTx>::new(
+            Arc::clone(&ns_cache),
+            None,
+        ));
+
+        let grpc_delegate = RpcWriteGrpcDelegate::new(
+            Arc::clone(&catalog),
+            Arc::new(InMemory::default()),
+            Arc::clone(&deletion_observer) as _,
+            deletion_observer,
+            Duration::from_secs(60 * 60),
+            Arc::new(FlightWriteDelegate::new(
+                Arc::clone(&namespace_resolver),
+                Arc::clone(&handler_stack),
+            )),
+            Arc::new(DeleteDelegate::new(
+                Arc::clone(&catalog),
+                namespace_resolver,
+                handler_stack,
+            )),
+            1024,
+            http_delegate.request_limiter(),
+            None,
+        );
 
         Self {
             client,
@@ -242,6 +281,10 @@ impl TestContext {
         self.client.calls()
     }
 
+    pub fn delete_calls(&self) -> Vec<DeleteRequest> {
+        self.client.delete_calls()
+    }
+
     /// Get a reference to the test context's metrics.
     pub fn metrics(&self) -> &metric::Registry {
         self.metrics.as_ref()
@@ -1,9 +1,10 @@
 use std::time::Duration;
 
 use assert_matches::assert_matches;
-use data_types::{MaxColumnsPerTable, MaxTables, NamespaceId};
+use data_types::{DeletePredicate, MaxColumnsPerTable, MaxTables, NamespaceId, TimestampRange};
 use generated_types::influxdata::{
     iox::{
+        delete::v1::{delete_service_server::DeleteService, DeletePayload, DeleteRequest},
         ingester::v1::WriteRequest,
         namespace::v1::{namespace_service_server::NamespaceService, *},
         partition_template::v1::*,
@@ -12,7 +13,10 @@ use generated_types::influxdata::{
     pbdata::v1::DatabaseBatch,
 };
 use hyper::StatusCode;
-use iox_catalog::interface::{Error as CatalogError, SoftDeletedRows};
+use iox_catalog::{
+    interface::{Error as CatalogError, SoftDeletedRows},
+    test_helpers::{arbitrary_namespace, arbitrary_table},
+};
 use iox_time::{SystemProvider, TimeProvider};
 use router::{
     dml_handlers::{DmlError, RetentionError},
@@ -936,6 +940,76 @@ async fn test_table_create() {
     })
 }
 
+/// Ensure deleting a column through the gRPC TableService evicts the cached
+/// schema, allowing the column to be re-created with a different type.
+#[tokio::test]
+async fn test_column_delete() {
+    // Initialise a TestContext with implicit namespace creation.
+    let ctx = TestContextBuilder::default()
+        .with_autocreate_namespace(None)
+        .build()
+        .await;
+
+    let response = ctx
+        .write_lp(
+            "bananas",
+            "test",
+            "plantains,tag1=A val=42i 1685026200000000000",
+        )
+        .await
+        .expect("write failed");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    // Writing a different type to the column is rejected.
+    let err = ctx
+        .write_lp(
+            "bananas",
+            "test",
+            "plantains,tag1=A val=4.2 1685026200000000000",
+        )
+        .await
+        .expect_err("write should fail");
+    assert_matches!(
+        err,
+        router::server::http::Error::DmlHandler(DmlError::Schema(SchemaError::Conflict(_)))
+    );
+
+    // Delete the column.
+    ctx.grpc_delegate()
+        .table_service()
+        .delete_column(Request::new(DeleteColumnRequest {
+            namespace_name: "bananas_test".to_string(),
+            table_name: "plantains".to_string(),
+            column_name: "val".to_string(),
+        }))
+        .await
+        .expect("must delete");
+
+    // The column can now be re-created with a different type.
+    let response = ctx
+        .write_lp(
+            "bananas",
+            "test",
+            "plantains,tag1=A val=4.2 1685026200000000000",
+        )
+        .await
+        .expect("write failed");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let table_id = ctx.table_id("bananas_test", "plantains").await;
+    let columns = ctx
+        .catalog()
+        .repositories()
+        .await
+        .columns()
+        .list_by_table_id(table_id)
+        .await
+        .unwrap();
+    assert_matches!(columns.iter().find(|c| c.name == "val"), Some(c) => {
+        assert_eq!(c.column_type, data_types::ColumnType::F64);
+    });
+}
+
 #[tokio::test]
 async fn test_invalid_strftime_partition_template() {
     // Initialise a TestContext without a namespace autocreation policy.
@@ -1252,3 +1326,75 @@ async fn test_namespace_without_partition_template_table_with_partition_template
         assert_eq!(partition_key, "B");
     });
 }
+
+/// Ensure a delete made through the gRPC DeleteService is pushed to the
+/// ingester and recorded as a tombstone, as for the HTTP API.
+#[tokio::test]
+async fn test_rpc_delete() {
+    let ctx = TestContextBuilder::default().build().await;
+
+    let ns = arbitrary_namespace(&mut *ctx.catalog().repositories().await, "bananas_test").await;
+    let table = arbitrary_table(&mut *ctx.catalog().repositories().await, "bananas", &ns).await;
+
+    ctx.grpc_delegate()
+        .delete_service()
+        .delete(Request::new(DeleteRequest {
+            payload: Some(DeletePayload {
+                database_id: ns.id.get(),
+                table_name: "bananas".to_string(),
+                predicate: Some(
+                    (&DeletePredicate {
+                        range: TimestampRange::new(0, 42),
+                        exprs: vec![],
+                    })
+                        .into(),
+                ),
+                ..Default::default()
+            }),
+        }))
+        .await
+        .expect("delete request failed");
+
+    // The delete is pushed to the ingester.
+    assert_matches!(ctx.delete_calls().as_slice(), [DeleteRequest { payload: Some(p) }] => {
+        assert_eq!(p.database_id, ns.id.get());
+        assert_eq!(p.table_id, table.id.get());
+        assert_eq!(p.table_name, "bananas");
+    });
+
+    // And recorded as a tombstone in the catalog.
+    let tombstones = ctx
+        .catalog()
+        .repositories()
+        .await
+        .tombstones()
+        .list_by_table_id(table.id)
+        .await
+        .expect("failed to list tombstones");
+    assert_matches!(tombstones.as_slice(), [t] => {
+        assert_eq!(t.min_time.get(), 0);
+        assert_eq!(t.max_time.get(), 42);
+    });
+
+    // Deletes from unknown namespaces are rejected.
+    let err = ctx
+        .grpc_delegate()
+        .delete_service()
+        .delete(Request::new(DeleteRequest {
+            payload: Some(DeletePayload {
+                database_id: ns.id.get() + 1,
+                table_name: "bananas".to_string(),
+                predicate: Some(
+                    (&DeletePredicate {
+                        range: TimestampRange::new(0, 42),
+                        exprs: vec![],
+                    })
+                        .into(),
+                ),
+                ..Default::default()
+            }),
+        }))
+        .await
+        .expect_err("delete from unknown namespace should fail");
+    assert_eq!(err.code(), Code::NotFound);
+}
@@ -2,10 +2,16 @@ use crate::common::{TestContextBuilder, TEST_RETENTION_PERIOD};
 use assert_matches::assert_matches;
 use data_types::{ColumnType, MaxTables};
 use futures::{stream::FuturesUnordered, StreamExt};
-use generated_types::influxdata::{iox::ingester::v1::WriteRequest, pbdata::v1::DatabaseBatch};
+use generated_types::influxdata::{
+    iox::{delete::v1::DeleteRequest, ingester::v1::WriteRequest},
+    pbdata::v1::DatabaseBatch,
+};
 use hashbrown::HashMap;
 use hyper::{Body, Request, StatusCode};
-use iox_catalog::{interface::SoftDeletedRows, test_helpers::arbitrary_namespace};
+use iox_catalog::{
+    interface::SoftDeletedRows,
+    test_helpers::{arbitrary_namespace, arbitrary_table},
+};
 use iox_time::{SystemProvider, TimeProvider};
 use metric::{Attributes, DurationHistogram, Metric, U64Counter};
 use router::{
@@ -338,14 +344,15 @@ async fn test_write_propagate_ids() {
 }
 
 #[tokio::test]
-async fn test_delete_unsupported() {
+async fn test_delete() {
     let ctx = TestContextBuilder::default()
         .with_autocreate_namespace(None)
         .build()
         .await;
 
-    // Create the namespace and a set of tables.
-    ctx.catalog()
+    // Create the namespace and table to delete from.
+    let ns = ctx
+        .catalog()
         .repositories()
         .await
         .namespaces()
@@ -356,30 +363,46 @@ async fn test_delete_unsupported() {
             None,
         )
         .await
-        .expect("failed to update table limit");
+        .expect("failed to create namespace");
+    let table = arbitrary_table(&mut *ctx.catalog().repositories().await, "bananas", &ns).await;
 
     let request = Request::builder()
         .uri("https://bananas.example/api/v2/delete?org=bananas&bucket=test")
         .method("POST")
         .body(Body::from(
             r#"{
-                "predicate": "_measurement=bananas",
+                "predicate": "_measurement=bananas AND tag=\"A\"",
                 "start": "1970-01-01T00:00:00Z",
                 "stop": "2070-01-02T00:00:00Z"
             }"#,
         ))
         .expect("failed to construct HTTP request");
 
-    let err = ctx.http_delegate().route(request).await.unwrap_err();
+    let response = ctx
+        .http_delegate()
+        .route(request)
+        .await
+        .expect("delete request failed");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
 
-    assert_matches!(
-        &err,
-        e @ router::server::http::Error::DeletesUnsupported => {
-            assert_eq!(
-                e.to_string(),
-                "deletes are not supported"
-            );
-        }
-    );
-    assert_eq!(err.as_status_code(), StatusCode::NOT_IMPLEMENTED);
+    // The delete is pushed to the ingester.
+    assert_matches!(ctx.delete_calls().as_slice(), [DeleteRequest { payload: Some(p) }] => {
+        assert_eq!(p.database_id, ns.id.get());
+        assert_eq!(p.table_id, table.id.get());
+        assert_eq!(p.table_name, "bananas");
+    });
+
+    // And recorded as a tombstone in the catalog.
+    let tombstones = ctx
+        .catalog()
+        .repositories()
+        .await
+        .tombstones()
+        .list_by_table_id(table.id)
+        .await
+        .expect("failed to list tombstones");
+    assert_matches!(tombstones.as_slice(), [t] => {
+        assert_eq!(t.min_time.get(), 0);
+        assert_eq!(t.serialized_predicate, r#""tag"='A'"#);
+    });
 }
@@ -1,5 +1,6 @@
 //! Routines for error handling
 use datafusion::error::DataFusionError;
+use iox_query::exec::{QueryCancelledError, QueryTimeoutError};
 
 /// Converts a [`DataFusionError`] into the appropriate [`tonic::Code`]
 ///
@@ -60,6 +61,10 @@ pub fn datafusion_error_to_tonic_code(e: &DataFusionError) -> tonic::Code {
                     executor::JobError::WorkerGone => tonic::Code::Unavailable,
                     executor::JobError::Panic { .. } => tonic::Code::Internal,
                 }
+            } else if e.downcast_ref::<QueryTimeoutError>().is_some() {
+                tonic::Code::DeadlineExceeded
+            } else if e.downcast_ref::<QueryCancelledError>().is_some() {
+                tonic::Code::Cancelled
             } else {
                 // All other, unclassified cases are signalled as "internal error" to the user since they cannot do
                 // anything about it (except for reporting a bug). Note that DataFusion "external" error is only from
@@ -143,6 +148,16 @@ mod test {
             ),
             tonic::Code::Unavailable,
         );
+        do_transl_test(
+            DataFusionError::External(Box::new(QueryTimeoutError::new(
+                std::time::Duration::from_secs(1),
+            ))),
+            tonic::Code::DeadlineExceeded,
+        );
+        do_transl_test(
+            DataFusionError::External(Box::new(QueryCancelledError)),
+            tonic::Code::Cancelled,
+        );
     }
 
     fn do_transl_test(e: DataFusionError, code: tonic::Code) {
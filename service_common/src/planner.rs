@@ -9,7 +9,10 @@ use flightsql::{FlightSQLCommand, FlightSQLPlanner};
 use iox_query::{
     exec::IOxSessionContext,
     frontend::sql::SqlQueryPlanner,
-    plan::{fieldlist::FieldListPlan, seriesset::SeriesSetPlans, stringset::StringSetPlan},
+    plan::{
+        fieldlist::FieldListPlan, series_cardinality::SeriesCardinalityPlan,
+        seriesset::SeriesSetPlans, stringset::StringSetPlan,
+    },
     Aggregate, QueryNamespace, WindowDuration,
 };
 use iox_query_influxrpc::InfluxRpcPlanner;
@@ -220,6 +223,28 @@ impl Planner {
             .await
     }
 
+    /// Creates a plan as described on [`InfluxRpcPlanner::series_cardinality`],
+    /// on a separate threadpool
+    pub async fn series_cardinality<N>(
+        &self,
+        namespace: Arc<N>,
+        predicate: InfluxRpcPredicate,
+    ) -> Result<SeriesCardinalityPlan>
+    where
+        N: QueryNamespace + 'static,
+    {
+        let planner = InfluxRpcPlanner::new(self.ctx.child_ctx("planner series_cardinality")).await;
+
+        self.ctx
+            .run(async move {
+                planner
+                    .series_cardinality(namespace, predicate)
+                    .await
+                    .map_err(|e| e.to_df_error("series_cardinality"))
+            })
+            .await
+    }
+
     /// Creates a plan as described on [`InfluxRpcPlanner::read_filter`], on a
     /// separate threadpool
     pub async fn read_filter<N>(
@@ -22,10 +22,15 @@ use workspace_hack as _;
 
 mod keep_alive;
 mod request;
+mod write;
 
-use arrow::error::ArrowError;
+pub use write::*;
+
+use arrow::{error::ArrowError, record_batch::RecordBatch};
 use arrow_flight::{
+    decode::FlightRecordBatchStream,
     encode::FlightDataEncoderBuilder,
+    error::FlightError,
     flight_descriptor::DescriptorType,
     flight_service_server::{FlightService as Flight, FlightServiceServer as FlightServer},
     Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightEndpoint, FlightInfo,
@@ -34,10 +39,12 @@ use arrow_flight::{
 use authz::{extract_token, Authorizer};
 use data_types::NamespaceNameError;
 use datafusion::{error::DataFusionError, physical_plan::ExecutionPlan};
-use flightsql::FlightSQLCommand;
+use flightsql::{FlightSQLCommand, FlightSQLPlanner};
 use futures::{ready, Stream, StreamExt, TryStreamExt};
 use generated_types::influxdata::iox::querier::v1 as proto;
+use influxdb_influxql_parser::{parse_statements, statement::Statement};
 use iox_query::{exec::IOxSessionContext, QueryCompletedToken, QueryNamespace};
+use iox_query_influxql::frontend::into_namespace_name;
 use observability_deps::tracing::{debug, info, warn};
 use prost::Message;
 use request::{IoxGetRequest, RunQuery};
@@ -73,6 +80,14 @@ const IOX_FLIGHT_SQL_DATABASE_HEADERS: [&str; 4] = [
 /// In which interval should the `DoGet` stream send empty messages as keep alive markers?
 const DO_GET_KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(5);
 
+/// The maximum number of record batches of parameter values accepted by a
+/// `DoPut` binding prepared statement parameters.
+///
+/// A single row of values is bound, which clients send in one batch (possibly
+/// preceded by empty ones), so this only bounds the memory used for
+/// misbehaving clients.
+const MAX_PARAMETER_BATCHES: usize = 16;
+
 #[allow(clippy::enum_variant_names)]
 #[derive(Debug, Snafu)]
 pub enum Error {
@@ -99,6 +114,13 @@ pub enum Error {
         source: DataFusionError,
     },
 
+    #[snafu(display("Query against namespace {} rejected: {}", namespace_name, source))]
+    QueryRejected {
+        namespace_name: String,
+        query: String,
+        source: DataFusionError,
+    },
+
     #[snafu(display(
         "More than one headers are found in request: {:?}. \
     Please include only one of them",
@@ -117,6 +139,21 @@ pub enum Error {
     #[snafu(display("Invalid database name: {}", source))]
     InvalidDatabaseName { source: NamespaceNameError },
 
+    #[snafu(display("Invalid DoPut descriptor: {}", description))]
+    InvalidPutDescriptor { description: String },
+
+    #[snafu(display("DoPut stream exceeds the maximum size of {} bytes", max_request_bytes))]
+    PutSizeExceeded { max_request_bytes: usize },
+
+    #[snafu(display(
+        "DoPut stream exceeds the maximum of {} parameter batches",
+        max_parameter_batches
+    ))]
+    TooManyParameterBatches { max_parameter_batches: usize },
+
+    #[snafu(display("this service is overloaded, please try again later"))]
+    RequestLimit,
+
     #[snafu(display("Failed to optimize record batch: {}", source))]
     Optimize { source: ArrowError },
 
@@ -166,7 +203,11 @@ impl From<Error> for tonic::Status {
             | Error::Unauthenticated { .. }
             | Error::PermissionDenied { .. }
             | Error::InvalidDatabaseName { .. }
-            | Error::Query { .. } => info!(e=%err, %namespace, %query, msg),
+            | Error::InvalidPutDescriptor { .. }
+            | Error::PutSizeExceeded { .. }
+            | Error::TooManyParameterBatches { .. }
+            | Error::Query { .. }
+            | Error::QueryRejected { .. } => info!(e=%err, %namespace, %query, msg),
             Error::Optimize { .. }
             | Error::EncodeSchema { .. }
             | Error::TooManyFlightSQLDatabases { .. }
@@ -177,6 +218,7 @@ impl From<Error> for tonic::Status {
             | Error::InternalCreatingTicket { .. }
             | Error::UnsupportedMessageType { .. }
             | Error::FlightSQL { .. }
+            | Error::RequestLimit
             | Error::Authz { .. } => {
                 warn!(e=%err, %namespace, %query, msg)
             }
@@ -199,13 +241,20 @@ impl Error {
             | Self::TooManyFlightSQLDatabases { .. }
             | Self::NoFlightSQLDatabase
             | Self::InvalidDatabaseHeader { .. }
-            | Self::InvalidDatabaseName { .. } => tonic::Code::InvalidArgument,
-            Self::Planning { source, .. } | Self::Query { source, .. } => {
+            | Self::InvalidDatabaseName { .. }
+            | Self::InvalidPutDescriptor { .. }
+            | Self::TooManyParameterBatches { .. } => tonic::Code::InvalidArgument,
+            Self::Planning { source, .. } => datafusion_error_to_tonic_code(&source),
+            Self::Query { source, .. } | Self::QueryRejected { source, .. } => {
                 datafusion_error_to_tonic_code(&source)
             }
             Self::UnsupportedMessageType { .. } => tonic::Code::Unimplemented,
             Self::FlightSQL { source } => match source {
                 flightsql::Error::InvalidHandle { .. }
+                | flightsql::Error::MalformedHandle { .. }
+                | flightsql::Error::InvalidParameters { .. }
+                | flightsql::Error::MissingParameters { .. }
+                | flightsql::Error::InvalidParameter { .. }
                 | flightsql::Error::Decode { .. }
                 | flightsql::Error::Protocol { .. }
                 | flightsql::Error::UnsupportedMessageType { .. } => tonic::Code::InvalidArgument,
@@ -223,6 +272,8 @@ impl Error {
             | Self::Authz { .. } => tonic::Code::Internal,
             Self::Unauthenticated => tonic::Code::Unauthenticated,
             Self::PermissionDenied => tonic::Code::PermissionDenied,
+            Self::PutSizeExceeded { .. } => tonic::Code::ResourceExhausted,
+            Self::RequestLimit => tonic::Code::Unavailable,
         };
 
         tonic::Status::new(code, msg)
@@ -238,6 +289,10 @@ impl Error {
             | Error::NoFlightSQLDatabase
             | Error::InvalidDatabaseHeader { .. }
             | Error::InvalidDatabaseName { .. }
+            | Error::InvalidPutDescriptor { .. }
+            | Error::PutSizeExceeded { .. }
+            | Error::TooManyParameterBatches { .. }
+            | Error::RequestLimit
             | Error::Optimize { .. }
             | Error::EncodeSchema { .. }
             | Error::FlightSQL { .. }
@@ -248,6 +303,7 @@ impl Error {
             | Error::Authz { .. } => "<unknown>",
             Error::DatabaseNotFound { namespace_name } => namespace_name,
             Error::Query { namespace_name, .. } => namespace_name,
+            Error::QueryRejected { namespace_name, .. } => namespace_name,
             Error::Planning { namespace_name, .. } => namespace_name,
         }
     }
@@ -262,6 +318,10 @@ impl Error {
             | Error::NoFlightSQLDatabase
             | Error::InvalidDatabaseHeader { .. }
             | Error::InvalidDatabaseName { .. }
+            | Error::InvalidPutDescriptor { .. }
+            | Error::PutSizeExceeded { .. }
+            | Error::TooManyParameterBatches { .. }
+            | Error::RequestLimit
             | Error::Optimize { .. }
             | Error::EncodeSchema { .. }
             | Error::FlightSQL { .. }
@@ -272,6 +332,7 @@ impl Error {
             | Error::Authz { .. }
             | Error::DatabaseNotFound { .. } => "NONE",
             Error::Query { query, .. } => query,
+            Error::QueryRejected { query, .. } => query,
             Error::Planning { query, .. } => query,
         }
     }
@@ -411,7 +472,7 @@ type TonicStream<T> = Pin<Box<dyn Stream<Item = Result<T, tonic::Status>> + Send
 ///       ┃                                                  ┃
 /// ```
 ///
-/// ## FlightSQL Prepared Statement (no bind parameters like $1, etc)
+/// ## FlightSQL Prepared Statement
 ///
 /// To run a prepared query, via FlightSQL, the client undertakes a
 /// few more steps:
@@ -461,6 +522,23 @@ type TonicStream<T> = Pin<Box<dyn Stream<Item = Result<T, tonic::Status>> + Send
 ///     7 ┃◀ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ┃
 /// ```
 ///
+/// ## FlightSQL Prepared Statement with bind parameters
+///
+/// Queries may contain positional parameters (`$1`, `$2`, etc). The
+/// `ActionCreatePreparedStatementResponse` then contains the schema of the
+/// parameters, with the type of each parameter inferred from the query.
+///
+/// Before step 4 above, the client binds values to the parameters by calling
+/// `DoPut` with the handle in a `CommandPreparedStatementQuery`, followed by
+/// a single row [`RecordBatch`] containing one column per parameter.
+///
+/// As IOx does not keep any state for prepared statements, the response to
+/// `DoPut` contains a `DoPutPreparedStatementResult` in its `app_metadata`
+/// with an updated handle that includes the bound values, which the client
+/// uses in place of the original handle for the remaining steps. The values
+/// are checked against the parameter types when the query is planned.
+///
+/// [`RecordBatch`]: arrow::record_batch::RecordBatch
 /// [Arrow Flight]: https://arrow.apache.org/docs/format/Flight.html
 /// [Arrow FlightSQL]: https://arrow.apache.org/docs/format/FlightSql.html
 #[derive(Debug)]
@@ -495,6 +573,7 @@ where
         query: RunQuery,
         namespace_name: String,
         is_debug: bool,
+        authorization: Option<String>,
     ) -> Result<Response<TonicStream<FlightData>>, tonic::Status> {
         let db = self
             .server
@@ -508,14 +587,24 @@ where
                 namespace_name: &namespace_name,
             })?;
 
-        let ctx = db.new_query_context(span_ctx);
+        // The authorization is forwarded with any data written by the
+        // query, such as the results of an InfluxQL `SELECT ... INTO`.
+        let ctx = db
+            .new_query_context(span_ctx)
+            .with_authorization(authorization.as_deref());
         let (query_completed_token, physical_plan) = match &query {
             RunQuery::Sql(sql_query) => {
-                let token = db.record_query(
-                    external_span_ctx.as_ref().map(RequestLogContext::ctx),
-                    "sql",
-                    Box::new(sql_query.clone()),
-                );
+                let token = db
+                    .record_query(
+                        &ctx,
+                        external_span_ctx.as_ref().map(RequestLogContext::ctx),
+                        "sql",
+                        Box::new(sql_query.clone()),
+                    )
+                    .context(QueryRejectedSnafu {
+                        namespace_name: &namespace_name,
+                        query: query.to_string(),
+                    })?;
                 let plan = Planner::new(&ctx)
                     .sql(sql_query)
                     .await
@@ -526,11 +615,17 @@ where
                 (token, plan)
             }
             RunQuery::InfluxQL(sql_query) => {
-                let token = db.record_query(
-                    external_span_ctx.as_ref().map(RequestLogContext::ctx),
-                    "influxql",
-                    Box::new(sql_query.clone()),
-                );
+                let token = db
+                    .record_query(
+                        &ctx,
+                        external_span_ctx.as_ref().map(RequestLogContext::ctx),
+                        "influxql",
+                        Box::new(sql_query.clone()),
+                    )
+                    .context(QueryRejectedSnafu {
+                        namespace_name: &namespace_name,
+                        query: query.to_string(),
+                    })?;
                 let plan = Planner::new(&ctx)
                     .influxql(sql_query)
                     .await
@@ -541,11 +636,17 @@ where
                 (token, plan)
             }
             RunQuery::FlightSQL(msg) => {
-                let token = db.record_query(
-                    external_span_ctx.as_ref().map(RequestLogContext::ctx),
-                    "flightsql",
-                    Box::new(msg.to_string()),
-                );
+                let token = db
+                    .record_query(
+                        &ctx,
+                        external_span_ctx.as_ref().map(RequestLogContext::ctx),
+                        "flightsql",
+                        Box::new(msg.to_string()),
+                    )
+                    .context(QueryRejectedSnafu {
+                        namespace_name: &namespace_name,
+                        query: query.to_string(),
+                    })?;
                 let plan = Planner::new(&ctx)
                     .flight_sql_do_get(&namespace_name, db, msg.clone())
                     .await
@@ -615,6 +716,7 @@ where
         let external_span_ctx: Option<RequestLogContext> = request.extensions().get().cloned();
         let span_ctx: Option<SpanContext> = request.extensions().get().cloned();
         let authz_token = get_flight_authz(request.metadata());
+        let authorization = get_authorization_header(request.metadata());
         let mut is_debug = has_debug_header(request.metadata());
         let ticket = request.into_inner();
 
@@ -632,7 +734,8 @@ where
 
         let perms = match query {
             RunQuery::FlightSQL(cmd) => flightsql_permissions(namespace_name, cmd),
-            RunQuery::Sql(_) | RunQuery::InfluxQL(_) => vec![authz::Permission::ResourceAction(
+            RunQuery::InfluxQL(query) => influxql_permissions(namespace_name, query),
+            RunQuery::Sql(_) => vec![authz::Permission::ResourceAction(
                 authz::Resource::Database(namespace_name.to_string()),
                 authz::Action::Read,
             )],
@@ -665,6 +768,7 @@ where
                 query.clone(),
                 namespace_name.to_string(),
                 is_debug,
+                authorization,
             )
             .await;
 
@@ -813,11 +917,69 @@ where
 
     async fn do_put(
         &self,
-        _request: Request<Streaming<FlightData>>,
+        request: Request<Streaming<FlightData>>,
     ) -> Result<Response<Self::DoPutStream>, tonic::Status> {
-        info!("Handling flightsql do_put body");
+        let external_span_ctx: Option<RequestLogContext> = request.extensions().get().cloned();
+        let trace = external_span_ctx.format_jaeger();
 
-        Err(tonic::Status::unimplemented("Not yet implemented: do_put"))
+        let namespace_name = get_flightsql_namespace(request.metadata())?;
+        let authz_token = get_flight_authz(request.metadata());
+        let mut data = request.into_inner();
+
+        // The FlightSQL command is in the descriptor of the first message
+        let first = data
+            .next()
+            .await
+            .transpose()?
+            .ok_or_else(|| Error::InvalidPutDescriptor {
+                description: "empty DoPut stream".to_string(),
+            })?;
+        let flight_descriptor =
+            first
+                .flight_descriptor
+                .clone()
+                .ok_or_else(|| Error::InvalidPutDescriptor {
+                    description: "DoPut stream must start with a flight descriptor".to_string(),
+                })?;
+        let cmd = cmd_from_descriptor(flight_descriptor)?;
+        info!(%namespace_name, %cmd, %trace, "DoPut request");
+
+        // Binding prepared statement parameters is the only use of DoPut
+        // supported by queriers.
+        if !matches!(cmd, FlightSQLCommand::CommandPreparedStatementQuery(_)) {
+            return Err(tonic::Status::unimplemented(
+                "DoPut is only supported for binding prepared statement parameters, \
+                write data to a router instead",
+            ));
+        }
+
+        let perms = flightsql_permissions(&namespace_name, &cmd);
+        self.authz
+            .permissions(authz_token, &perms)
+            .await
+            .map_err(Error::from)?;
+
+        let batches: Vec<RecordBatch> = FlightRecordBatchStream::new_from_flight_data(
+            futures::stream::once(async { Ok(first) })
+                .chain(data)
+                .map_err(FlightError::Tonic),
+        )
+        .take(MAX_PARAMETER_BATCHES + 1)
+        .try_collect()
+        .await
+        .map_err(tonic::Status::from)?;
+        if batches.len() > MAX_PARAMETER_BATCHES {
+            return Err(Error::TooManyParameterBatches {
+                max_parameter_batches: MAX_PARAMETER_BATCHES,
+            }
+            .into());
+        }
+
+        let app_metadata =
+            FlightSQLPlanner::do_put(&namespace_name, cmd, batches).context(FlightSQLSnafu)?;
+
+        let output = futures::stream::iter([Ok(PutResult { app_metadata })]);
+        Ok(Response::new(output.boxed()))
     }
 
     async fn do_action(
@@ -951,6 +1113,14 @@ fn get_flight_authz(metadata: &MetadataMap) -> Option<Vec<u8>> {
     extract_token(metadata.get("authorization"))
 }
 
+/// Retrieve the raw `authorization` header of the request.
+fn get_authorization_header(metadata: &MetadataMap) -> Option<String> {
+    metadata
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .map(ToOwned::to_owned)
+}
+
 fn flightsql_permissions(namespace_name: &str, cmd: &FlightSQLCommand) -> Vec<authz::Permission> {
     let resource = authz::Resource::Database(namespace_name.to_string());
     let action = match cmd {
@@ -972,6 +1142,43 @@ fn flightsql_permissions(namespace_name: &str, cmd: &FlightSQLCommand) -> Vec<au
     vec![authz::Permission::ResourceAction(resource, action)]
 }
 
+/// The permissions required to run an InfluxQL `query`.
+///
+/// Statements that delete data require the delete action, and each
+/// `SELECT ... INTO` statement requires the write action on the namespace it
+/// writes to. A query that cannot be parsed requires the read action, and
+/// fails when planned.
+fn influxql_permissions(namespace_name: &str, query: &str) -> Vec<authz::Permission> {
+    let statements = parse_statements(query).unwrap_or_default();
+
+    let deletes = statements.iter().any(|s| {
+        matches!(
+            s,
+            Statement::Delete(_) | Statement::DropMeasurement(_) | Statement::DropSeries(_)
+        )
+    });
+    let action = if deletes {
+        authz::Action::Delete
+    } else {
+        authz::Action::Read
+    };
+
+    let mut perms = vec![authz::Permission::ResourceAction(
+        authz::Resource::Database(namespace_name.to_string()),
+        action,
+    )];
+    perms.extend(statements.iter().filter_map(|s| match s {
+        Statement::Select(select) => select.into.as_ref().map(|into| {
+            authz::Permission::ResourceAction(
+                authz::Resource::Database(into_namespace_name(into, namespace_name)),
+                authz::Action::Write,
+            )
+        }),
+        _ => None,
+    }));
+    perms
+}
+
 /// Check if request has IOx debug header set.
 fn has_debug_header(metadata: &MetadataMap) -> bool {
     metadata
@@ -1260,6 +1467,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_influxql_permissions() {
+        fn action(query: &str) -> authz::Action {
+            match influxql_permissions("bananas", query).as_slice() {
+                [Permission::ResourceAction(authz::Resource::Database(name), action)] => {
+                    assert_eq!(name, "bananas");
+                    *action
+                }
+                perms => panic!("unexpected permissions: {perms:?}"),
+            }
+        }
+
+        assert_eq!(action("SELECT * FROM cpu"), authz::Action::Read);
+        assert_eq!(action("SHOW MEASUREMENTS"), authz::Action::Read);
+        assert_eq!(action("DELETE FROM cpu"), authz::Action::Delete);
+        assert_eq!(action("DROP MEASUREMENT cpu"), authz::Action::Delete);
+        assert_eq!(action("DROP SERIES FROM cpu"), authz::Action::Delete);
+        assert_eq!(
+            action("SELECT * FROM cpu; DROP MEASUREMENT cpu"),
+            authz::Action::Delete
+        );
+        assert_eq!(action("NOT A QUERY"), authz::Action::Read);
+
+        assert_eq!(
+            influxql_permissions(
+                "bananas",
+                "SELECT * INTO cpu_copy FROM cpu; SELECT * INTO db.rp.:MEASUREMENT FROM cpu"
+            ),
+            vec![
+                Permission::ResourceAction(
+                    authz::Resource::Database("bananas".to_string()),
+                    authz::Action::Read
+                ),
+                Permission::ResourceAction(
+                    authz::Resource::Database("bananas".to_string()),
+                    authz::Action::Write
+                ),
+                Permission::ResourceAction(
+                    authz::Resource::Database("db/rp".to_string()),
+                    authz::Action::Write
+                ),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn do_get_authz() {
         let test_storage = Arc::new(TestDatabaseStore::default());
@@ -0,0 +1,679 @@
+//! Arrow Flight `DoPut` support, writing Arrow IPC streams to a table.
+
+use std::{fmt::Debug, sync::Arc};
+
+use arrow::record_batch::RecordBatch;
+use arrow_flight::{
+    decode::FlightRecordBatchStream,
+    error::FlightError,
+    flight_descriptor::DescriptorType,
+    flight_service_server::{FlightService as Flight, FlightServiceServer as FlightServer},
+    sql::DoPutUpdateResult,
+    Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightInfo,
+    HandshakeRequest, HandshakeResponse, PutResult, SchemaResult, Ticket,
+};
+use authz::Authorizer;
+use data_types::NamespaceName;
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use generated_types::{
+    arrow::flight::protocol::sql::{
+        command_statement_ingest::table_definition_options::{
+            TableExistsOption, TableNotExistOption,
+        },
+        CommandStatementIngest, COMMAND_STATEMENT_INGEST,
+    },
+    google::protobuf::Any,
+    protobuf_type_url_eq,
+};
+use observability_deps::tracing::{debug, error, info};
+use prost::Message;
+use snafu::ResultExt;
+use tokio::sync::{Semaphore, TryAcquireError};
+use tonic::{metadata::MetadataMap, Request, Response, Streaming};
+use trace::ctx::SpanContext;
+
+use crate::{
+    get_flight_authz, get_flightsql_namespace, Error, InvalidDatabaseNameSnafu, Result, TonicStream,
+};
+
+/// A handler of Arrow data written to a table through Flight `DoPut`.
+#[tonic::async_trait]
+pub trait FlightWriter: Debug + Send + Sync {
+    /// Write `batches` to the table `table_name` within `namespace`.
+    ///
+    /// The batches are not required to share a schema, but each one must be
+    /// compatible with the schema of the table.
+    async fn write(
+        &self,
+        namespace: &NamespaceName<'static>,
+        table_name: &str,
+        batches: Vec<RecordBatch>,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), tonic::Status>;
+}
+
+/// An Arrow Flight service accepting writes through `DoPut`, passing the
+/// decoded data to a [`FlightWriter`].
+///
+/// Writes are FlightSQL bulk ingestion requests: the [`FlightDescriptor`] of
+/// the first [`FlightData`] message in the stream MUST be of type `CMD`,
+/// holding a [`CommandStatementIngest`] naming the table written to. As for
+/// other FlightSQL requests, the namespace is specified in the `database`
+/// header, and the `catalog` and `schema` of the command are ignored.
+///
+/// Tables and their columns are created as data is written to them, so the
+/// command must specify to create missing tables and append to existing ones,
+/// and cannot write to temporary tables or within a transaction.
+///
+/// The remainder of the stream is a regular Arrow IPC stream, containing the
+/// schema followed by any number of record batches. A single [`PutResult`],
+/// holding a [`DoPutUpdateResult`] with the number of rows written, is
+/// returned once all the data has been written.
+///
+/// A stream is rejected once the Arrow IPC data received exceeds
+/// `max_request_bytes`, and each `DoPut` holds a permit from `request_sem`
+/// for its duration, failing fast if none are available.
+///
+/// All other Flight methods are unimplemented.
+#[derive(Debug)]
+struct FlightWriteService {
+    writer: Arc<dyn FlightWriter>,
+    authz: Option<Arc<dyn Authorizer>>,
+    max_request_bytes: usize,
+    request_sem: Arc<Semaphore>,
+}
+
+/// Construct a Flight service accepting writes through `DoPut`, see
+/// [`FlightWriter`].
+///
+/// `DoPut` streams are limited to `max_request_bytes` of Arrow IPC data, and
+/// the number of concurrent writes is limited by the permits of
+/// `request_sem`, which may be shared with other write paths.
+pub fn make_write_server(
+    writer: Arc<dyn FlightWriter>,
+    authz: Option<Arc<dyn Authorizer>>,
+    max_request_bytes: usize,
+    request_sem: Arc<Semaphore>,
+) -> FlightServer<impl Flight> {
+    FlightServer::new(FlightWriteService {
+        writer,
+        authz,
+        max_request_bytes,
+        request_sem,
+    })
+}
+
+impl FlightWriteService {
+    /// Write the Arrow IPC stream in `data` to the table specified by its
+    /// descriptor, returning the number of rows written.
+    async fn put<S>(
+        &self,
+        metadata: &MetadataMap,
+        span_ctx: Option<SpanContext>,
+        mut data: S,
+    ) -> Result<usize, tonic::Status>
+    where
+        S: Stream<Item = Result<FlightData, tonic::Status>> + Send + Unpin + 'static,
+    {
+        // Acquire and hold a permit for the duration of this request, or
+        // reject it if the existing requests have already exhausted the
+        // allocation, before any data is read.
+        let _permit = match self.request_sem.try_acquire() {
+            Ok(p) => p,
+            Err(TryAcquireError::NoPermits) => {
+                error!("simultaneous request limit exceeded - dropping DoPut request");
+                return Err(Error::RequestLimit.into());
+            }
+            Err(e) => panic!("request limiter error: {e}"),
+        };
+
+        let first = match data.next().await {
+            Some(v) => v?,
+            None => return Err(invalid_descriptor("empty DoPut stream").into()),
+        };
+
+        let (namespace, table_name) = put_target(first.flight_descriptor.clone(), metadata)?;
+
+        self.authz
+            .permissions(
+                get_flight_authz(metadata),
+                &[authz::Permission::ResourceAction(
+                    authz::Resource::Database(namespace.to_string()),
+                    authz::Action::Write,
+                )],
+            )
+            .await
+            .map_err(Error::from)?;
+
+        // Enforce the size limit as the data arrives, so an oversized stream
+        // is rejected without being buffered in full.
+        let max_request_bytes = self.max_request_bytes;
+        let mut received_bytes = 0;
+        let data = stream::once(async { Ok(first) }).chain(data).map(move |v| {
+            let v = v?;
+            received_bytes += v.data_header.len() + v.data_body.len();
+            if received_bytes > max_request_bytes {
+                return Err(Error::PutSizeExceeded { max_request_bytes }.into());
+            }
+            Ok(v)
+        });
+
+        let batches: Vec<RecordBatch> =
+            FlightRecordBatchStream::new_from_flight_data(data.map_err(FlightError::Tonic))
+                .try_collect()
+                .await
+                .map_err(tonic::Status::from)?;
+
+        let num_rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+        debug!(
+            %namespace,
+            %table_name,
+            num_batches = batches.len(),
+            num_rows,
+            "routing DoPut write",
+        );
+
+        self.writer
+            .write(&namespace, &table_name, batches, span_ctx)
+            .await
+            .map_err(|e| {
+                info!(%namespace, %table_name, %e, "Error handling DoPut write");
+                e
+            })?;
+
+        Ok(num_rows)
+    }
+}
+
+/// Resolve the namespace and table name written to from the `descriptor` of a
+/// `DoPut` stream, and the request `metadata`.
+fn put_target(
+    descriptor: Option<FlightDescriptor>,
+    metadata: &MetadataMap,
+) -> Result<(NamespaceName<'static>, String)> {
+    let descriptor = descriptor
+        .ok_or_else(|| invalid_descriptor("DoPut stream must start with a flight descriptor"))?;
+
+    if descriptor.r#type() != DescriptorType::Cmd {
+        return Err(invalid_descriptor("flight descriptor must be of type CMD"));
+    }
+
+    let cmd = Any::decode(descriptor.cmd)
+        .map_err(|e| invalid_descriptor(format!("invalid command: {e}")))?;
+    if !protobuf_type_url_eq(&cmd.type_url, COMMAND_STATEMENT_INGEST) {
+        return Err(invalid_descriptor(format!(
+            "unsupported command {}, expected {COMMAND_STATEMENT_INGEST}",
+            cmd.type_url
+        )));
+    }
+    let cmd = CommandStatementIngest::decode(cmd.value)
+        .map_err(|e| invalid_descriptor(format!("invalid command: {e}")))?;
+
+    let options = cmd.table_definition_options.unwrap_or_default();
+    if options.if_not_exist() != TableNotExistOption::Create
+        || options.if_exists() != TableExistsOption::Append
+    {
+        return Err(invalid_descriptor(
+            "table definition options must be to create missing tables and append to existing ones",
+        ));
+    }
+    if cmd.temporary {
+        return Err(invalid_descriptor("temporary tables are not supported"));
+    }
+    if cmd.transaction_id.is_some() {
+        return Err(invalid_descriptor("transactions are not supported"));
+    }
+
+    if cmd.table.is_empty() {
+        return Err(invalid_descriptor("table name must not be empty"));
+    }
+
+    let namespace = get_flightsql_namespace(metadata)?;
+    let namespace = NamespaceName::try_from(namespace).context(InvalidDatabaseNameSnafu)?;
+
+    Ok((namespace, cmd.table))
+}
+
+fn invalid_descriptor(description: impl Into<String>) -> Error {
+    Error::InvalidPutDescriptor {
+        description: description.into(),
+    }
+}
+
+#[tonic::async_trait]
+impl Flight for FlightWriteService {
+    type HandshakeStream = TonicStream<HandshakeResponse>;
+    type ListFlightsStream = TonicStream<FlightInfo>;
+    type DoGetStream = TonicStream<FlightData>;
+    type DoPutStream = TonicStream<PutResult>;
+    type DoActionStream = TonicStream<arrow_flight::Result>;
+    type ListActionsStream = TonicStream<ActionType>;
+    type DoExchangeStream = TonicStream<FlightData>;
+
+    async fn handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented(
+            "Not yet implemented: handshake",
+        ))
+    }
+
+    async fn list_flights(
+        &self,
+        _request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented(
+            "Not yet implemented: list_flights",
+        ))
+    }
+
+    async fn get_flight_info(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, tonic::Status> {
+        Err(tonic::Status::unimplemented(
+            "Not yet implemented: get_flight_info",
+        ))
+    }
+
+    async fn get_schema(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, tonic::Status> {
+        Err(tonic::Status::unimplemented(
+            "Not yet implemented: get_schema",
+        ))
+    }
+
+    async fn do_get(
+        &self,
+        _request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented(
+            "DoGet is not supported by this server",
+        ))
+    }
+
+    async fn do_put(
+        &self,
+        request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, tonic::Status> {
+        let span_ctx: Option<SpanContext> = request.extensions().get().cloned();
+        let metadata = request.metadata().clone();
+
+        let record_count = self.put(&metadata, span_ctx, request.into_inner()).await?;
+
+        let result = PutResult {
+            app_metadata: DoPutUpdateResult {
+                record_count: record_count as i64,
+            }
+            .encode_to_vec()
+            .into(),
+        };
+        let output = stream::once(async { Ok(result) });
+        Ok(Response::new(Box::pin(output) as Self::DoPutStream))
+    }
+
+    async fn do_action(
+        &self,
+        _request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented(
+            "Not yet implemented: do_action",
+        ))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented(
+            "Not yet implemented: list_actions",
+        ))
+    }
+
+    async fn do_exchange(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented(
+            "Not yet implemented: do_exchange",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{ArrayRef, Float64Array, TimestampNanosecondArray};
+    use arrow_flight::encode::FlightDataEncoderBuilder;
+    use generated_types::{
+        arrow::flight::protocol::sql::command_statement_ingest::TableDefinitionOptions,
+        protobuf_type_url,
+    };
+    use std::sync::Mutex;
+    use tonic::{
+        metadata::{MetadataKey, MetadataValue},
+        Code,
+    };
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct MockWriter {
+        calls: Mutex<Vec<(String, String, Vec<RecordBatch>)>>,
+    }
+
+    #[tonic::async_trait]
+    impl FlightWriter for MockWriter {
+        async fn write(
+            &self,
+            namespace: &NamespaceName<'static>,
+            table_name: &str,
+            batches: Vec<RecordBatch>,
+            _span_ctx: Option<SpanContext>,
+        ) -> Result<(), tonic::Status> {
+            self.calls.lock().unwrap().push((
+                namespace.to_string(),
+                table_name.to_string(),
+                batches,
+            ));
+            Ok(())
+        }
+    }
+
+    fn batch() -> RecordBatch {
+        RecordBatch::try_from_iter(vec![
+            (
+                "usage",
+                Arc::new(Float64Array::from(vec![1.0, 2.0])) as ArrayRef,
+            ),
+            (
+                "time",
+                Arc::new(TimestampNanosecondArray::from(vec![1, 2])) as ArrayRef,
+            ),
+        ])
+        .unwrap()
+    }
+
+    /// A `CommandStatementIngest` appending to `table`, creating it if
+    /// needed.
+    fn ingest(table: &str) -> CommandStatementIngest {
+        CommandStatementIngest {
+            table_definition_options: Some(TableDefinitionOptions {
+                if_not_exist: TableNotExistOption::Create as i32,
+                if_exists: TableExistsOption::Append as i32,
+            }),
+            table: table.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// A `CMD` descriptor holding `cmd`.
+    fn descriptor(cmd: &CommandStatementIngest) -> FlightDescriptor {
+        FlightDescriptor::new_cmd(
+            Any {
+                type_url: protobuf_type_url(COMMAND_STATEMENT_INGEST),
+                value: cmd.encode_to_vec().into(),
+            }
+            .encode_to_vec(),
+        )
+    }
+
+    /// Encode `batches` as a `DoPut` stream with the given descriptor.
+    async fn encode(
+        descriptor: Option<FlightDescriptor>,
+        batches: Vec<RecordBatch>,
+    ) -> impl Stream<Item = Result<FlightData, tonic::Status>> + Send + Unpin + 'static {
+        let mut data: Vec<FlightData> = FlightDataEncoderBuilder::new()
+            .build(stream::iter(batches.into_iter().map(Ok)))
+            .try_collect()
+            .await
+            .unwrap();
+
+        data[0].flight_descriptor = descriptor;
+
+        stream::iter(data.into_iter().map(Ok))
+    }
+
+    /// Request metadata specifying the `database` to write to.
+    fn metadata(database: &'static str) -> MetadataMap {
+        let mut metadata = MetadataMap::new();
+        metadata.insert(
+            MetadataKey::from_static("database"),
+            MetadataValue::from_static(database),
+        );
+        metadata
+    }
+
+    fn service() -> (FlightWriteService, Arc<MockWriter>) {
+        let writer = Arc::new(MockWriter::default());
+        let svc = FlightWriteService {
+            writer: Arc::clone(&writer) as _,
+            authz: None,
+            max_request_bytes: 1024 * 1024,
+            request_sem: Arc::new(Semaphore::new(1)),
+        };
+        (svc, writer)
+    }
+
+    #[tokio::test]
+    async fn test_put() {
+        let (svc, writer) = service();
+
+        let num_rows = svc
+            .put(
+                &metadata("bananas"),
+                None,
+                encode(Some(descriptor(&ingest("cpu"))), vec![batch(), batch()]).await,
+            )
+            .await
+            .expect("write should succeed");
+        assert_eq!(num_rows, 4);
+
+        let calls = writer.calls.lock().unwrap();
+        let [(namespace, table_name, batches)] = calls.as_slice() else {
+            panic!("expected a single write, got {}", calls.len());
+        };
+        assert_eq!(namespace, "bananas");
+        assert_eq!(table_name, "cpu");
+        assert_eq!(batches, &[batch(), batch()]);
+    }
+
+    #[tokio::test]
+    async fn test_put_ignores_catalog_and_schema() {
+        let (svc, writer) = service();
+
+        let cmd = CommandStatementIngest {
+            catalog: Some("platanos".to_string()),
+            schema: Some("public".to_string()),
+            ..ingest("cpu")
+        };
+        svc.put(
+            &metadata("bananas"),
+            None,
+            encode(Some(descriptor(&cmd)), vec![batch()]).await,
+        )
+        .await
+        .expect("write should succeed");
+
+        let calls = writer.calls.lock().unwrap();
+        let [(namespace, table_name, _)] = calls.as_slice() else {
+            panic!("expected a single write, got {}", calls.len());
+        };
+        assert_eq!(namespace, "bananas");
+        assert_eq!(table_name, "cpu");
+    }
+
+    #[tokio::test]
+    async fn test_put_invalid_descriptor() {
+        let (svc, writer) = service();
+
+        let options = |if_not_exist: TableNotExistOption, if_exists: TableExistsOption| {
+            CommandStatementIngest {
+                table_definition_options: Some(TableDefinitionOptions {
+                    if_not_exist: if_not_exist as i32,
+                    if_exists: if_exists as i32,
+                }),
+                ..ingest("cpu")
+            }
+        };
+
+        for (name, descriptor) in [
+            ("none", None),
+            (
+                "path",
+                Some(FlightDescriptor::new_path(vec![
+                    "bananas".to_string(),
+                    "cpu".to_string(),
+                ])),
+            ),
+            ("not an any", Some(FlightDescriptor::new_cmd(vec![0xff]))),
+            (
+                "wrong command",
+                Some(FlightDescriptor::new_cmd(
+                    Any {
+                        type_url: protobuf_type_url(
+                            "arrow.flight.protocol.sql.CommandStatementQuery",
+                        ),
+                        value: Default::default(),
+                    }
+                    .encode_to_vec(),
+                )),
+            ),
+            ("empty table", Some(descriptor(&ingest("")))),
+            (
+                "no table definition options",
+                Some(descriptor(&CommandStatementIngest {
+                    table_definition_options: None,
+                    ..ingest("cpu")
+                })),
+            ),
+            (
+                "fail if not exists",
+                Some(descriptor(&options(
+                    TableNotExistOption::Fail,
+                    TableExistsOption::Append,
+                ))),
+            ),
+            (
+                "fail if exists",
+                Some(descriptor(&options(
+                    TableNotExistOption::Create,
+                    TableExistsOption::Fail,
+                ))),
+            ),
+            (
+                "replace",
+                Some(descriptor(&options(
+                    TableNotExistOption::Create,
+                    TableExistsOption::Replace,
+                ))),
+            ),
+            (
+                "temporary",
+                Some(descriptor(&CommandStatementIngest {
+                    temporary: true,
+                    ..ingest("cpu")
+                })),
+            ),
+            (
+                "transaction",
+                Some(descriptor(&CommandStatementIngest {
+                    transaction_id: Some(vec![42]),
+                    ..ingest("cpu")
+                })),
+            ),
+        ] {
+            let err = svc
+                .put(
+                    &metadata("bananas"),
+                    None,
+                    encode(descriptor, vec![batch()]).await,
+                )
+                .await
+                .expect_err("write should fail");
+            assert_eq!(err.code(), Code::InvalidArgument, "{name}");
+        }
+
+        assert!(writer.calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_put_invalid_database() {
+        let (svc, writer) = service();
+
+        // No database header
+        let err = svc
+            .put(
+                &MetadataMap::new(),
+                None,
+                encode(Some(descriptor(&ingest("cpu"))), vec![batch()]).await,
+            )
+            .await
+            .expect_err("write should fail");
+        assert_eq!(err.code(), Code::InvalidArgument);
+
+        let err = svc
+            .put(
+                &metadata("bad name!"),
+                None,
+                encode(Some(descriptor(&ingest("cpu"))), vec![batch()]).await,
+            )
+            .await
+            .expect_err("write should fail");
+        assert_eq!(err.code(), Code::InvalidArgument);
+
+        assert!(writer.calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_put_size_exceeded() {
+        let (mut svc, writer) = service();
+        svc.max_request_bytes = 2048;
+
+        // Each encoded batch carries an IPC message header and body, so this
+        // stream exceeds the limit part-way through.
+        let batches = (0..100).map(|_| batch()).collect::<Vec<_>>();
+        let err = svc
+            .put(
+                &metadata("bananas"),
+                None,
+                encode(Some(descriptor(&ingest("cpu"))), batches).await,
+            )
+            .await
+            .expect_err("write should fail");
+        assert_eq!(err.code(), Code::ResourceExhausted);
+
+        assert!(writer.calls.lock().unwrap().is_empty());
+
+        // A stream within the limit succeeds.
+        svc.put(
+            &metadata("bananas"),
+            None,
+            encode(Some(descriptor(&ingest("cpu"))), vec![batch()]).await,
+        )
+        .await
+        .expect("write should succeed");
+    }
+
+    #[tokio::test]
+    async fn test_put_request_limit() {
+        let (svc, writer) = service();
+
+        // Exhaust the request limit.
+        let _permit = svc.request_sem.try_acquire().unwrap();
+
+        let err = svc
+            .put(
+                &metadata("bananas"),
+                None,
+                encode(Some(descriptor(&ingest("cpu"))), vec![batch()]).await,
+            )
+            .await
+            .expect_err("write should fail");
+        assert_eq!(err.code(), Code::Unavailable);
+
+        assert!(writer.calls.lock().unwrap().is_empty());
+    }
+}
@@ -27,7 +27,7 @@ use predicate::{
     rpc_predicate::{InfluxRpcPredicate, FIELD_COLUMN_NAME, MEASUREMENT_COLUMN_NAME},
     Predicate,
 };
-use snafu::{OptionExt, ResultExt, Snafu};
+use snafu::{ensure, OptionExt, ResultExt, Snafu};
 
 #[derive(Debug, Snafu)]
 pub enum Error {
@@ -38,6 +38,12 @@ pub enum Error {
     #[snafu(display("Error creating aggregate: Unknown aggregate type {}", aggregate_type))]
     UnknownAggregate { aggregate_type: i32 },
 
+    #[snafu(display(
+        "Error creating aggregate: quantile must be between 0 and 1, got {}",
+        quantile
+    ))]
+    InvalidQuantile { quantile: f64 },
+
     #[snafu(display("Error creating aggregate: Unknown group type: {}", group_type))]
     UnknownGroup { group_type: i32 },
 
@@ -74,8 +80,14 @@ pub enum Error {
     #[snafu(display("Internal error: incorrect number of nodes: {:?}", num_children))]
     InternalInvalidRegexExprChildren { num_children: usize },
 
-    #[snafu(display("Error creating predicate: StartsWith comparisons not supported"))]
-    StartsWithNotSupported {},
+    #[snafu(display("Error creating predicate: StartsWith prefix must be a string literal"))]
+    StartsWithPrefixInvalid {},
+
+    #[snafu(display(
+        "Error creating predicate: Unsupported number of children in StartsWith comparison: {} (must be 2)",
+        num_children
+    ))]
+    StartsWithUnsupportedNumberOfChildren { num_children: usize },
 
     #[snafu(display(
         "Error creating predicate: Unexpected children for predicate: {:?}",
@@ -126,7 +138,7 @@ pub enum Error {
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
 /// Defines the different ways series can be grouped and aggregated
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum GroupByAndAggregate {
     /// group by a set of (Tag) columns, applying an agg to each field
     ///
@@ -587,7 +599,7 @@ fn build_comparison_node(comparison: i32, inputs: Vec<Expr>) -> Result<Expr> {
     match comparison_enum {
         Some(RPCComparison::Equal) => build_binary_expr(Operator::Eq, inputs),
         Some(RPCComparison::NotEqual) => build_binary_expr(Operator::NotEq, inputs),
-        Some(RPCComparison::StartsWith) => StartsWithNotSupportedSnafu {}.fail(),
+        Some(RPCComparison::StartsWith) => build_starts_with_expr(inputs),
         Some(RPCComparison::Regex) => build_regex_match_expr(true, inputs),
         Some(RPCComparison::NotRegex) => build_regex_match_expr(false, inputs),
         Some(RPCComparison::Lt) => build_binary_expr(Operator::Lt, inputs),
@@ -614,6 +626,51 @@ fn build_binary_expr(op: Operator, inputs: Vec<Expr>) -> Result<Expr> {
     }
 }
 
+/// Creates an expr that matches values which start with a prefix.
+///
+/// The prefix match is expressed as a range, which allows pruning chunks
+/// using the min/max statistics of the column:
+///
+/// ```sql
+/// expr >= 'prefix' AND expr < 'prefiy'
+/// ```
+fn build_starts_with_expr(mut inputs: Vec<Expr>) -> Result<Expr> {
+    let num_children = inputs.len();
+    if num_children != 2 {
+        return StartsWithUnsupportedNumberOfChildrenSnafu { num_children }.fail();
+    }
+
+    let prefix = match inputs.remove(1) {
+        Expr::Literal(ScalarValue::Utf8(Some(prefix))) => prefix,
+        _ => return StartsWithPrefixInvalidSnafu.fail(),
+    };
+    let expr = inputs.remove(0);
+
+    let lower = expr.clone().gt_eq(lit(prefix.clone()));
+    Ok(match prefix_upper_bound(&prefix) {
+        Some(upper) => lower.and(expr.lt(lit(upper))),
+        None => lower,
+    })
+}
+
+/// Returns the smallest string that is greater than all strings starting
+/// with `prefix`, or `None` if there is no such string (e.g. for an empty
+/// prefix).
+///
+/// UTF-8 preserves the order of code points, so this increments the last
+/// character of the prefix that can be incremented.
+fn prefix_upper_bound(prefix: &str) -> Option<String> {
+    let mut chars: Vec<char> = prefix.chars().collect();
+    while let Some(last) = chars.pop() {
+        let next = (last as u32 + 1..=char::MAX as u32).find_map(char::from_u32);
+        if let Some(next) = next {
+            chars.push(next);
+            return Some(chars.into_iter().collect());
+        }
+    }
+    None
+}
+
 // Creates a DataFusion ScalarUDF expression that performs a regex matching
 // operation.
 fn build_regex_match_expr(matches: bool, mut inputs: Vec<Expr>) -> Result<Expr> {
@@ -762,6 +819,18 @@ fn convert_aggregate(aggregate: Option<RPCAggregate>) -> Result<QueryAggregate>
         Some(RPCAggregateType::First) => Ok(QueryAggregate::First),
         Some(RPCAggregateType::Last) => Ok(QueryAggregate::Last),
         Some(RPCAggregateType::Mean) => Ok(QueryAggregate::Mean),
+        Some(RPCAggregateType::Median) => Ok(QueryAggregate::Median),
+        Some(RPCAggregateType::Stddev) => Ok(QueryAggregate::Stddev),
+        Some(RPCAggregateType::Spread) => Ok(QueryAggregate::Spread),
+        Some(RPCAggregateType::Quantile) => {
+            let quantile = aggregate.quantile;
+            ensure!(
+                (0.0..=1.0).contains(&quantile),
+                InvalidQuantileSnafu { quantile }
+            );
+            Ok(QueryAggregate::Quantile(quantile))
+        }
+        Some(RPCAggregateType::Mode) => Ok(QueryAggregate::Mode),
         None => UnknownAggregateSnafu { aggregate_type }.fail(),
     }
 }
@@ -1544,6 +1613,80 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_starts_with() {
+        let expr = build_starts_with_expr(vec![col("host"), lit("ser")]).unwrap();
+        let expected = col("host")
+            .gt_eq(lit("ser"))
+            .and(col("host").lt(lit("ses")));
+        assert_eq!(expr, expected);
+
+        // an empty prefix matches everything
+        let expr = build_starts_with_expr(vec![col("host"), lit("")]).unwrap();
+        assert_eq!(expr, col("host").gt_eq(lit("")));
+
+        let err = build_starts_with_expr(vec![col("host"), lit(1i64)]).unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "StartsWith prefix must be a string literal"
+        );
+
+        let err = build_starts_with_expr(vec![col("host")]).unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "Unsupported number of children in StartsWith comparison: 1"
+        );
+    }
+
+    #[test]
+    fn test_prefix_upper_bound() {
+        assert_eq!(prefix_upper_bound("abc").as_deref(), Some("abd"));
+        assert_eq!(prefix_upper_bound("a\u{10FFFF}").as_deref(), Some("b"));
+        assert_eq!(prefix_upper_bound("\u{D7FF}").as_deref(), Some("\u{E000}"));
+        assert_eq!(prefix_upper_bound("\u{10FFFF}"), None);
+        assert_eq!(prefix_upper_bound(""), None);
+    }
+
+    #[test]
+    fn test_convert_predicate_starts_with() {
+        let mut comparison = make_tag_ref_node(b"host", "ser");
+        comparison.value = Some(RPCValue::Comparison(RPCComparison::StartsWith as i32));
+
+        let rpc_predicate = RPCPredicate {
+            root: Some(comparison),
+        };
+
+        let predicate = InfluxRpcPredicateBuilder::default()
+            .rpc_predicate(Some(rpc_predicate))
+            .expect("successfully converting predicate")
+            .build();
+
+        let predicate = table_predicate(predicate);
+        assert_eq!(predicate.exprs.len(), 1);
+
+        // The upper bound increments the last character that can be
+        // incremented, and does not exist if none can be.
+        for (prefix, upper) in [
+            ("ser", Some("set")),
+            ("", None),
+            ("\u{10FFFF}", None),
+            ("se\u{10FFFF}", Some("sf")),
+            ("seÿ", Some("seĀ")),
+            ("s€", Some("s₭")),
+            // Surrogates are not characters.
+            ("s\u{D7FF}", Some("s\u{E000}")),
+        ] {
+            let got = build_starts_with_expr(vec![col("host"), lit(prefix)]).unwrap();
+
+            let lower = col("host").gt_eq(lit(prefix));
+            let expected = match upper {
+                Some(upper) => lower.and(col("host").lt(lit(upper))),
+                None => lower,
+            };
+            assert_eq!(got, expected, "prefix {prefix:?}");
+        }
+    }
+
     /// make a _f = 'field_name' type node
     fn make_field_ref_node(field_name: impl Into<String>) -> RPCNode {
         make_tag_ref_node(TAG_KEY_FIELD, field_name)
@@ -1687,7 +1830,7 @@ mod tests {
 
         let agg =
             make_read_window_aggregate(vec![make_aggregate(1), make_aggregate(2)], 5, 10, None);
-        let expected = "Error creating aggregate: Exactly one aggregate is supported, but 2 were supplied: [Aggregate { r#type: Sum }, Aggregate { r#type: Count }]";
+        let expected = "Error creating aggregate: Exactly one aggregate is supported, but 2 were supplied: [Aggregate { r#type: Sum, quantile: 0.0 }, Aggregate { r#type: Count, quantile: 0.0 }]";
         assert_eq!(agg.unwrap_err().to_string(), expected);
 
         // now window specified
@@ -1832,6 +1975,39 @@ mod tests {
             convert_aggregate(Some(make_aggregate(7))).unwrap(),
             QueryAggregate::Mean
         );
+        assert_eq!(
+            convert_aggregate(Some(make_aggregate(8))).unwrap(),
+            QueryAggregate::Median
+        );
+        assert_eq!(
+            convert_aggregate(Some(make_aggregate(9))).unwrap(),
+            QueryAggregate::Stddev
+        );
+        assert_eq!(
+            convert_aggregate(Some(make_aggregate(10))).unwrap(),
+            QueryAggregate::Spread
+        );
+        assert_eq!(
+            convert_aggregate(Some(RPCAggregate {
+                r#type: 11,
+                quantile: 0.9,
+            }))
+            .unwrap(),
+            QueryAggregate::Quantile(0.9)
+        );
+        assert_eq!(
+            convert_aggregate(Some(RPCAggregate {
+                r#type: 11,
+                quantile: 1.5,
+            }))
+            .unwrap_err()
+            .to_string(),
+            "Error creating aggregate: quantile must be between 0 and 1, got 1.5"
+        );
+        assert_eq!(
+            convert_aggregate(Some(make_aggregate(12))).unwrap(),
+            QueryAggregate::Mode
+        );
         assert_eq!(
             convert_aggregate(Some(make_aggregate(100)))
                 .unwrap_err()
@@ -1841,7 +2017,10 @@ mod tests {
     }
 
     fn make_aggregate(t: i32) -> RPCAggregate {
-        RPCAggregate { r#type: t }
+        RPCAggregate {
+            r#type: t,
+            quantile: 0.0,
+        }
     }
 
     fn make_rpc_window(
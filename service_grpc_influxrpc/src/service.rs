@@ -46,7 +46,6 @@ use std::{
     fmt::{Display, Formatter, Result as FmtResult},
     sync::Arc,
 };
-use tokio_stream::wrappers::ReceiverStream;
 use tonic::{metadata::MetadataMap, Response, Status};
 use trace::{ctx::SpanContext, span::SpanExt};
 use trace_http::ctx::{RequestLogContext, RequestLogContextExt};
@@ -67,6 +66,12 @@ pub enum Error {
     #[snafu(display("Namespace not found: {}", db_name))]
     NamespaceNotFound { db_name: String },
 
+    #[snafu(display("Query against namespace '{}' rejected: {}", db_name, source))]
+    QueryRejected {
+        db_name: String,
+        source: DataFusionError,
+    },
+
     #[snafu(display("Error listing tables in namespace '{}': {}", db_name, source))]
     ListingTables {
         db_name: String,
@@ -109,6 +114,12 @@ pub enum Error {
         source: DataFusionError,
     },
 
+    #[snafu(display("Error counting series in namespace '{}': {}", db_name, source))]
+    CountingSeries {
+        db_name: String,
+        source: DataFusionError,
+    },
+
     #[snafu(display(
         "Can not retrieve tag values for '{}' in namespace '{}': {}",
         tag_name,
@@ -211,13 +222,15 @@ impl Error {
 
         let code = match self {
             Self::NamespaceNotFound { .. } => tonic::Code::NotFound,
-            Self::ListingTables { source, .. }
+            Self::QueryRejected { source, .. }
+            | Self::ListingTables { source, .. }
             | Self::ListingColumns { source, .. }
             | Self::ListingFields { source, .. }
             | Self::PlanningFilteringSeries { source, .. }
             | Self::PlanningGroupSeries { source, .. }
             | Self::FilteringSeries { source, .. }
             | Self::GroupingSeries { source, .. }
+            | Self::CountingSeries { source, .. }
             | Self::ListingTagValues { source, .. } => datafusion_error_to_tonic_code(&source),
             Self::ConvertingPredicate { source, .. }
             | Self::ConvertingReadGroupType { source, .. }
@@ -394,11 +407,14 @@ where
             .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
 
         let ctx = db.new_query_context(span_ctx);
-        let query_completed_token = db.record_query(
-            external_span_ctx.as_ref().map(RequestLogContext::ctx),
-            "read_filter",
-            defer_json(&req),
-        );
+        let query_completed_token = db
+            .record_query(
+                &ctx,
+                external_span_ctx.as_ref().map(RequestLogContext::ctx),
+                "read_filter",
+                defer_json(&req),
+            )
+            .context(QueryRejectedSnafu { db_name: &db_name })?;
 
         let frames = read_filter_impl(Arc::clone(&db), db_name, req, &ctx)
             .await?
@@ -446,11 +462,14 @@ where
             .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
 
         let ctx = db.new_query_context(span_ctx);
-        let query_completed_token = db.record_query(
-            external_span_ctx.as_ref().map(RequestLogContext::ctx),
-            "read_group",
-            defer_json(&req),
-        );
+        let query_completed_token = db
+            .record_query(
+                &ctx,
+                external_span_ctx.as_ref().map(RequestLogContext::ctx),
+                "read_group",
+                defer_json(&req),
+            )
+            .context(QueryRejectedSnafu { db_name: &db_name })?;
 
         let ReadGroupRequest {
             read_source: _read_source,
@@ -526,11 +545,14 @@ where
             .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
 
         let ctx = db.new_query_context(span_ctx);
-        let query_completed_token = db.record_query(
-            external_span_ctx.as_ref().map(RequestLogContext::ctx),
-            "read_window_aggregate",
-            defer_json(&req),
-        );
+        let query_completed_token = db
+            .record_query(
+                &ctx,
+                external_span_ctx.as_ref().map(RequestLogContext::ctx),
+                "read_window_aggregate",
+                defer_json(&req),
+            )
+            .context(QueryRejectedSnafu { db_name: &db_name })?;
 
         let ReadWindowAggregateRequest {
             read_source: _read_source,
@@ -607,11 +629,14 @@ where
             .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
 
         let ctx = db.new_query_context(span_ctx);
-        let query_completed_token = db.record_query(
-            external_span_ctx.as_ref().map(RequestLogContext::ctx),
-            "tag_keys",
-            defer_json(&req),
-        );
+        let query_completed_token = db
+            .record_query(
+                &ctx,
+                external_span_ctx.as_ref().map(RequestLogContext::ctx),
+                "tag_keys",
+                defer_json(&req),
+            )
+            .context(QueryRejectedSnafu { db_name: &db_name })?;
 
         let TagKeysRequest {
             tags_source: _tag_source,
@@ -679,11 +704,14 @@ where
             .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
 
         let ctx = db.new_query_context(span_ctx);
-        let query_completed_token = db.record_query(
-            external_span_ctx.as_ref().map(RequestLogContext::ctx),
-            "tag_values",
-            defer_json(&req),
-        );
+        let query_completed_token = db
+            .record_query(
+                &ctx,
+                external_span_ctx.as_ref().map(RequestLogContext::ctx),
+                "tag_values",
+                defer_json(&req),
+            )
+            .context(QueryRejectedSnafu { db_name: &db_name })?;
 
         let TagValuesRequest {
             tags_source: _tag_source,
@@ -698,14 +726,6 @@ where
         // measurements
         let response = match tag_key {
             DecodedTagKey::Measurement => {
-                if predicate.is_some() {
-                    return Err(Error::NotYetImplemented {
-                        operation: "tag_value for a measurement, with general predicate"
-                            .to_string(),
-                    }
-                    .into_status());
-                }
-
                 measurement_name_impl(Arc::clone(&db), db_name, range, predicate, &ctx).await
             }
             DecodedTagKey::Field => {
@@ -783,11 +803,14 @@ where
             .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
 
         let ctx = db.new_query_context(span_ctx);
-        let query_completed_token = db.record_query(
-            external_span_ctx.as_ref().map(RequestLogContext::ctx),
-            "tag_values_grouped_by_measurement_and_tag_key",
-            defer_json(&req),
-        );
+        let query_completed_token = db
+            .record_query(
+                &ctx,
+                external_span_ctx.as_ref().map(RequestLogContext::ctx),
+                "tag_values_grouped_by_measurement_and_tag_key",
+                defer_json(&req),
+            )
+            .context(QueryRejectedSnafu { db_name: &db_name })?;
 
         let results =
             tag_values_grouped_by_measurement_and_tag_key_impl(Arc::clone(&db), db_name, req, &ctx)
@@ -804,13 +827,68 @@ where
         )
     }
 
-    type ReadSeriesCardinalityStream = ReceiverStream<Result<Int64ValuesResponse, Status>>;
+    type ReadSeriesCardinalityStream = StreamWithPermit<
+        QueryCompletedTokenStream<
+            BoxStream<'static, Result<Int64ValuesResponse, Status>>,
+            Int64ValuesResponse,
+            Status,
+        >,
+    >;
 
     async fn read_series_cardinality(
         &self,
-        _req: tonic::Request<ReadSeriesCardinalityRequest>,
+        req: tonic::Request<ReadSeriesCardinalityRequest>,
     ) -> Result<Response<Self::ReadSeriesCardinalityStream>, Status> {
-        unimplemented!("read_series_cardinality not yet implemented. https://github.com/influxdata/influxdb_iox/issues/447");
+        let external_span_ctx: Option<RequestLogContext> = req.extensions().get().cloned();
+        let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+
+        let req = req.into_inner();
+        let permit = self
+            .db_store
+            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .await;
+
+        let db_name = get_namespace_name(&req)?;
+        info!(
+            %db_name,
+            ?req.range,
+            predicate=%req.predicate.loggable(),
+            trace=%external_span_ctx.format_jaeger(),
+            "read_series_cardinality",
+        );
+
+        let db = self
+            .db_store
+            .db(&db_name, span_ctx.child_span("get namespace"), false)
+            .await
+            .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
+
+        let ctx = db.new_query_context(span_ctx);
+        let query_completed_token = db
+            .record_query(
+                &ctx,
+                external_span_ctx.as_ref().map(RequestLogContext::ctx),
+                "read_series_cardinality",
+                defer_json(&req),
+            )
+            .context(QueryRejectedSnafu { db_name: &db_name })?;
+
+        let ReadSeriesCardinalityRequest {
+            read_series_cardinality_source: _read_series_cardinality_source,
+            range,
+            predicate,
+        } = req;
+
+        let response =
+            read_series_cardinality_impl(Arc::clone(&db), db_name, range, predicate, &ctx)
+                .await
+                .map_err(|e| e.into_status());
+
+        make_response(
+            futures::stream::once(async move { response }).boxed(),
+            query_completed_token,
+            permit,
+        )
     }
 
     async fn capabilities(
@@ -836,7 +914,8 @@ where
                 vec![
                     "Count", "Sum", // "First"
                     // "Last",
-                    "Min", "Max", "Mean",
+                    "Min", "Max", "Mean", "Median", "Stddev", "Spread", "Quantile",
+                    "Mode",
                     // "Offset"
                 ],
             ),
@@ -892,11 +971,14 @@ where
             .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
 
         let ctx = db.new_query_context(span_ctx);
-        let query_completed_token = db.record_query(
-            external_span_ctx.as_ref().map(RequestLogContext::ctx),
-            "measurement_names",
-            defer_json(&req),
-        );
+        let query_completed_token = db
+            .record_query(
+                &ctx,
+                external_span_ctx.as_ref().map(RequestLogContext::ctx),
+                "measurement_names",
+                defer_json(&req),
+            )
+            .context(QueryRejectedSnafu { db_name: &db_name })?;
 
         let MeasurementNamesRequest {
             source: _source,
@@ -953,11 +1035,14 @@ where
             .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
 
         let ctx = db.new_query_context(span_ctx);
-        let query_completed_token = db.record_query(
-            external_span_ctx.as_ref().map(RequestLogContext::ctx),
-            "measurement_tag_keys",
-            defer_json(&req),
-        );
+        let query_completed_token = db
+            .record_query(
+                &ctx,
+                external_span_ctx.as_ref().map(RequestLogContext::ctx),
+                "measurement_tag_keys",
+                defer_json(&req),
+            )
+            .context(QueryRejectedSnafu { db_name: &db_name })?;
 
         let MeasurementTagKeysRequest {
             source: _source,
@@ -1025,11 +1110,14 @@ where
             .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
 
         let ctx = db.new_query_context(span_ctx);
-        let query_completed_token = db.record_query(
-            external_span_ctx.as_ref().map(RequestLogContext::ctx),
-            "measurement_tag_values",
-            defer_json(&req),
-        );
+        let query_completed_token = db
+            .record_query(
+                &ctx,
+                external_span_ctx.as_ref().map(RequestLogContext::ctx),
+                "measurement_tag_values",
+                defer_json(&req),
+            )
+            .context(QueryRejectedSnafu { db_name: &db_name })?;
 
         let MeasurementTagValuesRequest {
             source: _source,
@@ -1098,11 +1186,14 @@ where
             .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
 
         let ctx = db.new_query_context(span_ctx);
-        let query_completed_token = db.record_query(
-            external_span_ctx.as_ref().map(RequestLogContext::ctx),
-            "measurement_fields",
-            defer_json(&req),
-        );
+        let query_completed_token = db
+            .record_query(
+                &ctx,
+                external_span_ctx.as_ref().map(RequestLogContext::ctx),
+                "measurement_fields",
+                defer_json(&req),
+            )
+            .context(QueryRejectedSnafu { db_name: &db_name })?;
 
         let MeasurementFieldsRequest {
             source: _source,
@@ -1245,6 +1336,45 @@ where
     Ok(StringValuesResponse { values })
 }
 
+/// Return the number of series with optional timestamp and arbitrary
+/// predicates
+async fn read_series_cardinality_impl<N>(
+    db: Arc<N>,
+    db_name: NamespaceName<'static>,
+    range: Option<TimestampRange>,
+    rpc_predicate: Option<Predicate>,
+    ctx: &IOxSessionContext,
+) -> Result<Int64ValuesResponse>
+where
+    N: QueryNamespace + 'static,
+{
+    let rpc_predicate_string = format!("{rpc_predicate:?}");
+    let db_name = db_name.as_str();
+
+    let predicate = InfluxRpcPredicateBuilder::default()
+        .set_range(range)
+        .rpc_predicate(rpc_predicate)
+        .context(ConvertingPredicateSnafu {
+            rpc_predicate_string,
+        })?
+        .build();
+
+    let series_cardinality_plan = Planner::new(ctx)
+        .series_cardinality(db, predicate)
+        .await
+        .context(CountingSeriesSnafu { db_name })?;
+
+    let count = ctx
+        .to_series_cardinality(series_cardinality_plan)
+        .await
+        .context(CountingSeriesSnafu { db_name })?;
+
+    trace!(count, "Series cardinality response");
+    Ok(Int64ValuesResponse {
+        values: vec![count as i64],
+    })
+}
+
 /// Return tag values for tag_name, with optional measurement, timestamp and
 /// arbitratry predicates
 async fn tag_values_impl<N>(
@@ -1818,7 +1948,10 @@ mod tests {
         expected_capabilities.insert("Group".into(), to_str_vec(&["First", "Last", "Min", "Max"]));
         expected_capabilities.insert(
             "WindowAggregate".into(),
-            to_str_vec(&["Count", "Sum", "Min", "Max", "Mean"]),
+            to_str_vec(&[
+                "Count", "Sum", "Min", "Max", "Mean", "Median", "Stddev", "Spread", "Quantile",
+                "Mode",
+            ]),
         );
 
         assert_eq!(
@@ -2000,6 +2133,55 @@ mod tests {
         grpc_request_metric_has_count(&fixture, "TagKeys", "server_error", 1);
     }
 
+    /// test the plumbing of the RPC layer for read_series_cardinality
+    #[tokio::test]
+    async fn test_storage_rpc_read_series_cardinality() {
+        test_helpers::maybe_start_logging();
+        // Start a test gRPC server on a randomally allocated port
+        let mut fixture = Fixture::new().await.expect("Connecting to test server");
+
+        let db_info = org_and_bucket();
+
+        let chunk0 = TestChunk::new("m1")
+            .with_id(0)
+            .with_tag_column("tag1")
+            .with_tag_column("tag2")
+            .with_i64_field_column("i64_field")
+            .with_time_column()
+            .with_three_rows_of_data();
+
+        let chunk1 = TestChunk::new("m2")
+            .with_id(1)
+            .with_tag_column("state")
+            .with_i64_field_column("i64_field")
+            .with_time_column()
+            .with_one_row_of_data();
+
+        fixture
+            .test_storage
+            .db_or_create(db_info.db_name())
+            .await
+            .add_chunk("my_partition_key", Arc::new(chunk0))
+            .add_chunk("my_partition_key", Arc::new(chunk1));
+
+        let source = Some(StorageClient::read_source(&db_info, 1));
+
+        let request = ReadSeriesCardinalityRequest {
+            read_series_cardinality_source: source.clone(),
+            range: Some(make_timestamp_range(0, 15000)),
+            predicate: None,
+        };
+
+        let actual = fixture
+            .storage_client
+            .read_series_cardinality(request)
+            .await
+            .unwrap();
+        assert_eq!(actual, 3);
+
+        grpc_request_metric_has_count(&fixture, "ReadSeriesCardinality", "ok", 1);
+    }
+
     /// test the plumbing of the RPC layer for measurement_tag_keys--
     /// specifically that the right parameters are passed into the Namespace
     /// interface and that the returned values are sent back via gRPC.
@@ -2180,6 +2362,56 @@ mod tests {
         grpc_request_metric_has_count(&fixture, "TagValues", "ok", 1);
     }
 
+    /// tag_key = _measurement with a predicate lists the measurements
+    /// with rows that pass the predicate
+    #[tokio::test]
+    async fn test_storage_rpc_tag_values_with_measurement_and_predicate() {
+        test_helpers::maybe_start_logging();
+        // Start a test gRPC server on a randomally allocated port
+        let mut fixture = Fixture::new().await.expect("Connecting to test server");
+
+        let db_info = org_and_bucket();
+
+        let source = Some(StorageClient::read_source(&db_info, 1));
+
+        let request = TagValuesRequest {
+            tags_source: source.clone(),
+            range: Some(make_timestamp_range(150, 2000)),
+            predicate: Some(make_state_eq_ma_predicate()),
+            tag_key: [0].into(),
+        };
+
+        let chunk0 = TestChunk::new("h2o")
+            .with_id(0)
+            .with_tag_column("state")
+            .with_time_column()
+            .with_i64_field_column("i64_field")
+            .with_one_row_of_data();
+
+        let chunk1 = TestChunk::new("o2")
+            .with_id(1)
+            .with_tag_column("city")
+            .with_time_column()
+            .with_i64_field_column("i64_field")
+            .with_one_row_of_data();
+
+        fixture
+            .test_storage
+            .db_or_create(db_info.db_name())
+            .await
+            .add_chunk("my_partition_key", Arc::new(chunk0))
+            .add_chunk("my_partition_key", Arc::new(chunk1));
+
+        let tag_values = vec!["h2o"];
+        let actual_tag_values = fixture.storage_client.tag_values(request).await.unwrap();
+        assert_eq!(
+            actual_tag_values, tag_values,
+            "unexpected tag values while getting tag values for measurement names"
+        );
+
+        grpc_request_metric_has_count(&fixture, "TagValues", "ok", 1);
+    }
+
     #[tokio::test]
     async fn test_storage_rpc_tag_values_field() {
         test_helpers::maybe_start_logging();
@@ -2837,6 +3069,7 @@ mod tests {
             group,
             aggregate: Some(Aggregate {
                 r#type: aggregate::AggregateType::Sum as i32,
+                quantile: 0.0,
             }),
         };
 
@@ -2882,6 +3115,7 @@ mod tests {
             group,
             aggregate: Some(Aggregate {
                 r#type: aggregate::AggregateType::Sum as i32,
+                quantile: 0.0,
             }),
         };
 
@@ -2931,6 +3165,7 @@ mod tests {
             offset: 15,
             aggregate: vec![Aggregate {
                 r#type: aggregate::AggregateType::Sum as i32,
+                quantile: 0.0,
             }],
             // old skool window definition
             window: None,
@@ -2985,6 +3220,7 @@ mod tests {
             offset: 0,
             aggregate: vec![Aggregate {
                 r#type: aggregate::AggregateType::Sum as i32,
+                quantile: 0.0,
             }],
             // old skool window definition
             window: Some(Window {
@@ -3045,6 +3281,7 @@ mod tests {
             offset: 15,
             aggregate: vec![Aggregate {
                 r#type: aggregate::AggregateType::Sum as i32,
+                quantile: 0.0,
             }],
             // old skool window definition
             window: None,
@@ -3240,6 +3477,7 @@ mod tests {
                         group,
                         aggregate: Some(Aggregate {
                             r#type: aggregate::AggregateType::Sum as i32,
+                            quantile: 0.0,
                         }),
                     };
                     let streaming_resp = service
@@ -3257,6 +3495,7 @@ mod tests {
                         offset: 0,
                         aggregate: vec![Aggregate {
                             r#type: aggregate::AggregateType::Sum as i32,
+                            quantile: 0.0,
                         }],
                         // old skool window definition
                         window: Some(Window {
@@ -3589,6 +3828,7 @@ mod tests {
             offset: 0,
             aggregate: vec![Aggregate {
                 r#type: aggregate::AggregateType::Mean as i32,
+                quantile: 0.0,
             }],
             window: Some(Window {
                 every: Some(Duration {
@@ -16,27 +16,93 @@
 // Workaround for "unused crate" lint false positives.
 use workspace_hack as _;
 
-use std::sync::Arc;
+use std::{fmt::Debug, sync::Arc, time::Duration};
 
 use data_types::{
     partition_template::NamespacePartitionTemplateOverride, Namespace as CatalogNamespace,
-    NamespaceName, NamespaceServiceProtectionLimitsOverride, ServiceLimitUpdate,
+    NamespaceId, NamespaceName, NamespaceServiceProtectionLimitsOverride, ServiceLimitUpdate,
+    Timestamp,
 };
 use generated_types::influxdata::iox::namespace::v1::*;
 use iox_catalog::interface::{Catalog, SoftDeletedRows};
 use observability_deps::tracing::{debug, info, warn};
 use tonic::{Request, Response, Status};
 
+/// The default period after deletion during which a namespace can be restored.
+pub const DEFAULT_UNDELETE_GRACE_PERIOD: Duration = Duration::from_secs(14 * 24 * 60 * 60);
+
+/// An observer of namespaces renamed or restored through the
+/// [`NamespaceService`].
+///
+/// Implementations are notified after the change has been committed to the
+/// catalog, and are expected to invalidate any state that caches the affected
+/// namespace by name.
+pub trait NamespaceChangeObserver: Debug + Send + Sync {
+    /// The namespace with ID `namespace_id` was renamed from `old_name` to
+    /// `new_name`.
+    fn namespace_renamed(
+        &self,
+        old_name: &NamespaceName<'static>,
+        new_name: &NamespaceName<'static>,
+        namespace_id: NamespaceId,
+    );
+
+    /// The soft-deleted namespace `name` with ID `namespace_id` was restored.
+    fn namespace_undeleted(&self, name: &NamespaceName<'static>, namespace_id: NamespaceId);
+}
+
+/// A [`NamespaceChangeObserver`] that does nothing.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NopNamespaceChangeObserver;
+
+impl NamespaceChangeObserver for NopNamespaceChangeObserver {
+    fn namespace_renamed(
+        &self,
+        _old_name: &NamespaceName<'static>,
+        _new_name: &NamespaceName<'static>,
+        _namespace_id: NamespaceId,
+    ) {
+    }
+
+    fn namespace_undeleted(&self, _name: &NamespaceName<'static>, _namespace_id: NamespaceId) {}
+}
+
 /// Implementation of the gRPC namespace service
 #[derive(Debug)]
 pub struct NamespaceService {
     /// Catalog.
     catalog: Arc<dyn Catalog>,
+
+    /// The period after deletion during which a namespace can be restored.
+    undelete_grace_period: Duration,
+
+    /// Notified of renamed and restored namespaces.
+    change_observer: Arc<dyn NamespaceChangeObserver>,
 }
 
 impl NamespaceService {
     pub fn new(catalog: Arc<dyn Catalog>) -> Self {
-        Self { catalog }
+        Self {
+            catalog,
+            undelete_grace_period: DEFAULT_UNDELETE_GRACE_PERIOD,
+            change_observer: Arc::new(NopNamespaceChangeObserver),
+        }
+    }
+
+    /// Only allow namespaces deleted within `grace_period` to be restored.
+    ///
+    /// This MUST NOT exceed the grace period after which the garbage collector
+    /// flags the parquet files of deleted namespaces for deletion.
+    pub fn with_undelete_grace_period(mut self, grace_period: Duration) -> Self {
+        self.undelete_grace_period = grace_period;
+        self
+    }
+
+    /// Notify `observer` of namespaces renamed or restored through this
+    /// service.
+    pub fn with_change_observer(mut self, observer: Arc<dyn NamespaceChangeObserver>) -> Self {
+        self.change_observer = observer;
+        self
     }
 }
 
@@ -139,6 +205,87 @@ impl namespace_service_server::NamespaceService for NamespaceService {
         Ok(Response::new(Default::default()))
     }
 
+    async fn undelete_namespace(
+        &self,
+        request: Request<UndeleteNamespaceRequest>,
+    ) -> Result<Response<UndeleteNamespaceResponse>, Status> {
+        let namespace_name = NamespaceName::try_from(request.into_inner().name)
+            .map_err(|v| Status::invalid_argument(v.to_string()))?;
+
+        let deleted_after =
+            Timestamp::from(self.catalog.time_provider().now() - self.undelete_grace_period);
+
+        debug!(%namespace_name, ?deleted_after, "restoring namespace");
+
+        let namespace = self
+            .catalog
+            .repositories()
+            .await
+            .namespaces()
+            .undelete(&namespace_name, deleted_after)
+            .await
+            .map_err(|e| {
+                warn!(error=%e, %namespace_name, "failed to restore namespace");
+                status_from_catalog_namespace_error(e)
+            })?;
+
+        self.change_observer
+            .namespace_undeleted(&namespace_name, namespace.id);
+
+        info!(%namespace_name, namespace_id = %namespace.id, "restored namespace");
+
+        Ok(Response::new(UndeleteNamespaceResponse {
+            namespace: Some(namespace_to_proto(&namespace)),
+        }))
+    }
+
+    async fn rename_namespace(
+        &self,
+        request: Request<RenameNamespaceRequest>,
+    ) -> Result<Response<RenameNamespaceResponse>, Status> {
+        let RenameNamespaceRequest { name, new_name } = request.into_inner();
+
+        // Both names are validated, so the old name can be handed to the
+        // change observer.
+        let namespace_name =
+            NamespaceName::try_from(name).map_err(|v| Status::invalid_argument(v.to_string()))?;
+        let new_name = NamespaceName::try_from(new_name)
+            .map_err(|v| Status::invalid_argument(v.to_string()))?;
+
+        debug!(%namespace_name, %new_name, "renaming namespace");
+
+        let namespace = self
+            .catalog
+            .repositories()
+            .await
+            .namespaces()
+            .rename(&namespace_name, &new_name)
+            .await
+            .map_err(|e| {
+                warn!(error=%e, %namespace_name, %new_name, "failed to rename namespace");
+                match e {
+                    iox_catalog::interface::Error::NameExists { name } => Status::already_exists(
+                        format!("A namespace with the name `{name}` already exists"),
+                    ),
+                    other => status_from_catalog_namespace_error(other),
+                }
+            })?;
+
+        self.change_observer
+            .namespace_renamed(&namespace_name, &new_name, namespace.id);
+
+        info!(
+            %namespace_name,
+            %new_name,
+            namespace_id = %namespace.id,
+            "renamed namespace"
+        );
+
+        Ok(Response::new(RenameNamespaceResponse {
+            namespace: Some(namespace_to_proto(&namespace)),
+        }))
+    }
+
     async fn update_namespace_retention(
         &self,
         request: Request<UpdateNamespaceRetentionRequest>,
@@ -226,6 +373,45 @@ impl namespace_service_server::NamespaceService for NamespaceService {
                     );
                     status_from_catalog_namespace_error(e)
                 }),
+            ServiceLimitUpdate::MaxConcurrentQueries(new_max_queries) => repos
+                .namespaces()
+                .update_concurrent_query_limit(&namespace_name, new_max_queries)
+                .await
+                .map_err(|e| {
+                    warn!(
+                        error = %e,
+                        %namespace_name,
+                        %new_max_queries,
+                        "failed to update concurrent query limit for namespace",
+                    );
+                    status_from_catalog_namespace_error(e)
+                }),
+            ServiceLimitUpdate::MaxQueryMemoryBytes(new_max_bytes) => repos
+                .namespaces()
+                .update_query_memory_limit(&namespace_name, new_max_bytes)
+                .await
+                .map_err(|e| {
+                    warn!(
+                        error = %e,
+                        %namespace_name,
+                        %new_max_bytes,
+                        "failed to update query memory limit for namespace",
+                    );
+                    status_from_catalog_namespace_error(e)
+                }),
+            ServiceLimitUpdate::QueryTimeout(new_timeout) => repos
+                .namespaces()
+                .update_query_timeout(&namespace_name, new_timeout)
+                .await
+                .map_err(|e| {
+                    warn!(
+                        error = %e,
+                        %namespace_name,
+                        %new_timeout,
+                        "failed to update query timeout for namespace",
+                    );
+                    status_from_catalog_namespace_error(e)
+                }),
         }?;
 
         info!(
@@ -233,6 +419,7 @@ impl namespace_service_server::NamespaceService for NamespaceService {
             namespace_id = %namespace.id,
             max_tables = %namespace.max_tables,
             max_columns_per_table = %namespace.max_columns_per_table,
+            query_limits = ?namespace.query_limits,
             "updated namespace service protection limits",
         );
 
@@ -253,6 +440,15 @@ pub fn namespace_to_proto(namespace: &CatalogNamespace) -> Namespace {
         max_tables: namespace.max_tables.get(),
         max_columns_per_table: namespace.max_columns_per_table.get(),
         partition_template: namespace.partition_template.as_proto().cloned(),
+        max_concurrent_queries: namespace
+            .query_limits
+            .max_concurrent_queries
+            .map(|v| v.get()),
+        max_query_memory_bytes: namespace
+            .query_limits
+            .max_query_memory_bytes
+            .map(|v| v.get()),
+        query_timeout_ms: namespace.query_limits.query_timeout_ms.map(|v| v.get()),
     }
 }
 
@@ -278,13 +474,17 @@ fn status_from_catalog_namespace_error(err: iox_catalog::interface::Error) -> St
         iox_catalog::interface::Error::NamespaceNotFoundByName { .. } => {
             Status::not_found(err.to_string())
         }
+        iox_catalog::interface::Error::NamespaceNotDeleted { .. }
+        | iox_catalog::interface::Error::NamespaceUndeleteExpired { .. } => {
+            Status::failed_precondition(err.to_string())
+        }
         _ => Status::internal(err.to_string()),
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::time::Duration;
+    use std::sync::Mutex;
 
     use assert_matches::assert_matches;
     use data_types::partition_template::PARTITION_BY_DAY_PROTO;
@@ -461,6 +661,36 @@ mod tests {
         assert_eq!(updated_ns.id, created_ns.id);
         assert_eq!(updated_ns.max_tables, want_max_tables);
         assert_eq!(updated_ns.max_columns_per_table, want_max_columns_per_table);
+        assert_eq!(updated_ns.max_concurrent_queries, None);
+
+        // Update the query limits
+        for limit_update in [
+            LimitUpdate::MaxConcurrentQueries(4),
+            LimitUpdate::MaxQueryMemoryBytes(1024),
+            LimitUpdate::QueryTimeoutMs(5_000),
+        ] {
+            handler
+                .update_namespace_service_protection_limit(Request::new(
+                    UpdateNamespaceServiceProtectionLimitRequest {
+                        name: NS_NAME.to_string(),
+                        limit_update: Some(limit_update),
+                    },
+                ))
+                .await
+                .expect("failed to update namespace");
+        }
+        let current = handler
+            .get_namespaces(Request::new(Default::default()))
+            .await
+            .expect("must return namespaces")
+            .into_inner()
+            .namespaces;
+        assert_matches!(current.as_slice(), [ns] => {
+            assert_eq!(ns.max_tables, want_max_tables);
+            assert_eq!(ns.max_concurrent_queries, Some(4));
+            assert_eq!(ns.max_query_memory_bytes, Some(1024));
+            assert_eq!(ns.query_timeout_ms, Some(5_000));
+        });
 
         // Deleting the namespace should cause it to disappear
         handler
@@ -651,6 +881,18 @@ mod tests {
                 "invalid namespace update request for max columns per table limit should fail",
             );
         assert_eq!(status.code(), Code::InvalidArgument);
+
+        // ...and any attempt to set a query limit to a negative value.
+        let status = handler
+            .update_namespace_service_protection_limit(Request::new(
+                UpdateNamespaceServiceProtectionLimitRequest {
+                    name: NS_NAME.to_string(),
+                    limit_update: Some(LimitUpdate::QueryTimeoutMs(-1)),
+                },
+            ))
+            .await
+            .expect_err("invalid namespace update request for query timeout should fail");
+        assert_eq!(status.code(), Code::InvalidArgument);
     }
 
     #[tokio::test]
@@ -684,6 +926,206 @@ mod tests {
         assert_eq!(created_ns.max_columns_per_table, max_columns_per_table);
     }
 
+    #[derive(Debug, Default)]
+    struct MockChangeObserver {
+        renamed: Mutex<Vec<(String, String, NamespaceId)>>,
+        undeleted: Mutex<Vec<(String, NamespaceId)>>,
+    }
+
+    impl NamespaceChangeObserver for MockChangeObserver {
+        fn namespace_renamed(
+            &self,
+            old_name: &NamespaceName<'static>,
+            new_name: &NamespaceName<'static>,
+            namespace_id: NamespaceId,
+        ) {
+            self.renamed.lock().unwrap().push((
+                old_name.to_string(),
+                new_name.to_string(),
+                namespace_id,
+            ));
+        }
+
+        fn namespace_undeleted(&self, name: &NamespaceName<'static>, namespace_id: NamespaceId) {
+            self.undeleted
+                .lock()
+                .unwrap()
+                .push((name.to_string(), namespace_id));
+        }
+    }
+
+    async fn create_namespace(handler: &NamespaceService, name: &str) -> Namespace {
+        handler
+            .create_namespace(Request::new(CreateNamespaceRequest {
+                name: name.to_string(),
+                retention_period_ns: None,
+                partition_template: None,
+                service_protection_limits: None,
+            }))
+            .await
+            .expect("failed to create namespace")
+            .into_inner()
+            .namespace
+            .expect("no namespace in response")
+    }
+
+    #[tokio::test]
+    async fn test_undelete() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let observer = Arc::new(MockChangeObserver::default());
+        let handler = NamespaceService::new(Arc::clone(&catalog))
+            .with_change_observer(Arc::clone(&observer) as _);
+
+        let created_ns = create_namespace(&handler, NS_NAME).await;
+
+        // A namespace that is not deleted cannot be restored.
+        let status = handler
+            .undelete_namespace(Request::new(UndeleteNamespaceRequest {
+                name: NS_NAME.to_string(),
+            }))
+            .await
+            .expect_err("namespace is not deleted");
+        assert_eq!(status.code(), Code::FailedPrecondition);
+
+        // Nor can a namespace that never existed.
+        let status = handler
+            .undelete_namespace(Request::new(UndeleteNamespaceRequest {
+                name: "platanos".to_string(),
+            }))
+            .await
+            .expect_err("namespace does not exist");
+        assert_eq!(status.code(), Code::NotFound);
+
+        handler
+            .delete_namespace(Request::new(DeleteNamespaceRequest {
+                name: NS_NAME.to_string(),
+            }))
+            .await
+            .expect("must delete");
+
+        // Restoring the namespace within the grace period succeeds.
+        let restored_ns = handler
+            .undelete_namespace(Request::new(UndeleteNamespaceRequest {
+                name: NS_NAME.to_string(),
+            }))
+            .await
+            .expect("failed to restore namespace")
+            .into_inner()
+            .namespace
+            .expect("no namespace in response");
+        assert_eq!(restored_ns, created_ns);
+
+        let current = handler
+            .get_namespaces(Request::new(Default::default()))
+            .await
+            .expect("must return namespaces")
+            .into_inner()
+            .namespaces;
+        assert_matches!(current.as_slice(), [ns] => {
+            assert_eq!(ns, &created_ns);
+        });
+
+        assert_matches!(observer.undeleted.lock().unwrap().as_slice(), [(name, id)] => {
+            assert_eq!(name, NS_NAME);
+            assert_eq!(id.get(), created_ns.id);
+        });
+    }
+
+    #[tokio::test]
+    async fn test_undelete_grace_period_expired() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let handler =
+            NamespaceService::new(Arc::clone(&catalog)).with_undelete_grace_period(Duration::ZERO);
+
+        create_namespace(&handler, NS_NAME).await;
+        handler
+            .delete_namespace(Request::new(DeleteNamespaceRequest {
+                name: NS_NAME.to_string(),
+            }))
+            .await
+            .expect("must delete");
+
+        // Ensure the deletion lies strictly outside the (empty) grace period.
+        tokio::time::sleep(Duration::from_millis(1)).await;
+
+        let status = handler
+            .undelete_namespace(Request::new(UndeleteNamespaceRequest {
+                name: NS_NAME.to_string(),
+            }))
+            .await
+            .expect_err("grace period expired");
+        assert_eq!(status.code(), Code::FailedPrecondition);
+    }
+
+    #[tokio::test]
+    async fn test_rename() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let observer = Arc::new(MockChangeObserver::default());
+        let handler = NamespaceService::new(Arc::clone(&catalog))
+            .with_change_observer(Arc::clone(&observer) as _);
+
+        let created_ns = create_namespace(&handler, NS_NAME).await;
+        create_namespace(&handler, "platanos").await;
+
+        // Renaming to the name of an existing namespace fails.
+        let status = handler
+            .rename_namespace(Request::new(RenameNamespaceRequest {
+                name: NS_NAME.to_string(),
+                new_name: "platanos".to_string(),
+            }))
+            .await
+            .expect_err("name is in use");
+        assert_eq!(status.code(), Code::AlreadyExists);
+
+        // As does renaming a namespace that does not exist.
+        let status = handler
+            .rename_namespace(Request::new(RenameNamespaceRequest {
+                name: "missing".to_string(),
+                new_name: "whatever".to_string(),
+            }))
+            .await
+            .expect_err("namespace does not exist");
+        assert_eq!(status.code(), Code::NotFound);
+
+        // Or renaming to an invalid name.
+        let status = handler
+            .rename_namespace(Request::new(RenameNamespaceRequest {
+                name: NS_NAME.to_string(),
+                new_name: "🍌".to_string(),
+            }))
+            .await
+            .expect_err("invalid namespace name");
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        // None of the failed attempts were observed.
+        assert!(observer.renamed.lock().unwrap().is_empty());
+
+        let renamed_ns = handler
+            .rename_namespace(Request::new(RenameNamespaceRequest {
+                name: NS_NAME.to_string(),
+                new_name: "bananas2".to_string(),
+            }))
+            .await
+            .expect("failed to rename namespace")
+            .into_inner()
+            .namespace
+            .expect("no namespace in response");
+        assert_eq!(renamed_ns.id, created_ns.id);
+        assert_eq!(renamed_ns.name, "bananas2");
+
+        assert_matches!(observer.renamed.lock().unwrap().as_slice(), [(old, new, id)] => {
+            assert_eq!(old, NS_NAME);
+            assert_eq!(new, "bananas2");
+            assert_eq!(id.get(), created_ns.id);
+        });
+
+        // The old name is free to be reused.
+        create_namespace(&handler, NS_NAME).await;
+    }
+
     macro_rules! test_create_namespace_name {
         (
             $test_name:ident,
@@ -22,26 +22,149 @@
 // Workaround for "unused crate" lint false positives.
 use workspace_hack as _;
 
-use std::sync::Arc;
+use std::{fmt::Debug, sync::Arc};
 
-use data_types::{partition_template::TablePartitionTemplateOverride, NamespaceName};
+use data_types::{
+    partition_template::TablePartitionTemplateOverride, ColumnId, Namespace, NamespaceId,
+    NamespaceName, TableId,
+};
 use generated_types::influxdata::iox::table::v1::*;
-use iox_catalog::interface::{Catalog, SoftDeletedRows};
+use iox_catalog::interface::{Catalog, RepoCollection, SoftDeletedRows};
 use observability_deps::tracing::{debug, error, info, warn};
 use tonic::{Request, Response, Status};
 
+/// An observer of table and column deletions performed through the
+/// [`TableService`].
+///
+/// Implementations are notified after the deletion has been committed to the
+/// catalog, and are expected to invalidate any state that caches the deleted
+/// schema elements.
+pub trait DeletionObserver: Debug + Send + Sync {
+    /// The table `table_name` with ID `table_id` was deleted from `namespace`.
+    fn table_deleted(
+        &self,
+        namespace: &NamespaceName<'static>,
+        table_name: &str,
+        table_id: TableId,
+    );
+
+    /// The column `column_name` with ID `column_id` was deleted from the
+    /// table `table_name` with ID `table_id` in `namespace`, with ID
+    /// `namespace_id`.
+    fn column_deleted(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_id: NamespaceId,
+        table_name: &str,
+        table_id: TableId,
+        column_name: &str,
+        column_id: ColumnId,
+    );
+}
+
+impl<T> DeletionObserver for Arc<T>
+where
+    T: DeletionObserver,
+{
+    fn table_deleted(
+        &self,
+        namespace: &NamespaceName<'static>,
+        table_name: &str,
+        table_id: TableId,
+    ) {
+        T::table_deleted(self, namespace, table_name, table_id)
+    }
+
+    fn column_deleted(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_id: NamespaceId,
+        table_name: &str,
+        table_id: TableId,
+        column_name: &str,
+        column_id: ColumnId,
+    ) {
+        T::column_deleted(
+            self,
+            namespace,
+            namespace_id,
+            table_name,
+            table_id,
+            column_name,
+            column_id,
+        )
+    }
+}
+
+/// A [`DeletionObserver`] that does nothing.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NopDeletionObserver;
+
+impl DeletionObserver for NopDeletionObserver {
+    fn table_deleted(
+        &self,
+        _namespace: &NamespaceName<'static>,
+        _table_name: &str,
+        _table_id: TableId,
+    ) {
+    }
+
+    fn column_deleted(
+        &self,
+        _namespace: &NamespaceName<'static>,
+        _namespace_id: NamespaceId,
+        _table_name: &str,
+        _table_id: TableId,
+        _column_name: &str,
+        _column_id: ColumnId,
+    ) {
+    }
+}
+
 /// Implementation of the table gRPC service
 #[derive(Debug)]
 pub struct TableService {
     /// Catalog.
     catalog: Arc<dyn Catalog>,
+
+    /// Notified of table and column deletions.
+    deletion_observer: Arc<dyn DeletionObserver>,
 }
 
 impl TableService {
     /// Create a new `TableService` instance
     pub fn new(catalog: Arc<dyn Catalog>) -> Self {
-        Self { catalog }
+        Self {
+            catalog,
+            deletion_observer: Arc::new(NopDeletionObserver),
+        }
     }
+
+    /// Notify `observer` of all tables and columns deleted through this
+    /// service.
+    pub fn with_deletion_observer(self, observer: Arc<dyn DeletionObserver>) -> Self {
+        Self {
+            deletion_observer: observer,
+            ..self
+        }
+    }
+}
+
+/// Resolve the (not deleted) namespace named `namespace_name`.
+async fn get_namespace(
+    repos: &mut dyn RepoCollection,
+    namespace_name: &NamespaceName<'static>,
+) -> Result<Namespace, Status> {
+    repos
+        .namespaces()
+        .get_by_name(namespace_name, SoftDeletedRows::ExcludeDeleted)
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?
+        .ok_or_else(|| {
+            Status::not_found(format!(
+                "Could not find a namespace with name {namespace_name}"
+            ))
+        })
 }
 
 #[tonic::async_trait]
@@ -143,11 +266,132 @@ impl table_service_server::TableService for TableService {
             table: Some(table.into()),
         }))
     }
+
+    // soft-delete a table
+    async fn delete_table(
+        &self,
+        request: Request<DeleteTableRequest>,
+    ) -> Result<Response<DeleteTableResponse>, Status> {
+        let mut repos = self.catalog.repositories().await;
+
+        let DeleteTableRequest {
+            namespace_name,
+            table_name,
+        } = request.into_inner();
+
+        let namespace_name = NamespaceName::try_from(namespace_name)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        debug!(%table_name, %namespace_name, "deleting table");
+
+        let namespace = get_namespace(&mut *repos, &namespace_name).await?;
+
+        let table = repos
+            .tables()
+            .soft_delete(namespace.id, &table_name)
+            .await
+            .map_err(|e| {
+                warn!(error=%e, %table_name, %namespace_name, "failed to delete table");
+                match e {
+                    iox_catalog::interface::Error::TableNotFoundByName { .. } => {
+                        Status::not_found(format!(
+                            "Could not find a table with name `{table_name}` \
+                                in the namespace `{namespace_name}`"
+                        ))
+                    }
+                    other => Status::internal(other.to_string()),
+                }
+            })?;
+
+        self.deletion_observer
+            .table_deleted(&namespace_name, &table_name, table.id);
+
+        info!(%table_name, table_id = %table.id, %namespace_name, "deleted table");
+
+        Ok(Response::new(DeleteTableResponse {}))
+    }
+
+    // soft-delete a column of a table
+    async fn delete_column(
+        &self,
+        request: Request<DeleteColumnRequest>,
+    ) -> Result<Response<DeleteColumnResponse>, Status> {
+        let mut repos = self.catalog.repositories().await;
+
+        let DeleteColumnRequest {
+            namespace_name,
+            table_name,
+            column_name,
+        } = request.into_inner();
+
+        let namespace_name = NamespaceName::try_from(namespace_name)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        debug!(%column_name, %table_name, %namespace_name, "deleting column");
+
+        let namespace = get_namespace(&mut *repos, &namespace_name).await?;
+
+        let table = repos
+            .tables()
+            .get_by_namespace_and_name(namespace.id, &table_name)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| {
+                Status::not_found(format!(
+                    "Could not find a table with name `{table_name}` \
+                        in the namespace `{namespace_name}`"
+                ))
+            })?;
+
+        let column = repos
+            .columns()
+            .soft_delete(table.id, &column_name)
+            .await
+            .map_err(|e| {
+                warn!(error=%e, %column_name, %table_name, %namespace_name, "failed to delete column");
+                match e {
+                    iox_catalog::interface::Error::ColumnNotFoundByName { .. } => {
+                        Status::not_found(format!(
+                            "Could not find a column with name `{column_name}` \
+                                in the table `{table_name}`"
+                        ))
+                    }
+                    iox_catalog::interface::Error::ColumnDeleteNotAllowed { reason, .. } => {
+                        Status::failed_precondition(format!(
+                            "The column `{column_name}` cannot be deleted: {reason}"
+                        ))
+                    }
+                    other => Status::internal(other.to_string()),
+                }
+            })?;
+
+        self.deletion_observer.column_deleted(
+            &namespace_name,
+            namespace.id,
+            &table_name,
+            table.id,
+            &column_name,
+            column.id,
+        );
+
+        info!(
+            %column_name,
+            column_id = ?column.id,
+            %table_name,
+            table_id = %table.id,
+            %namespace_name,
+            "deleted column"
+        );
+
+        Ok(Response::new(DeleteColumnResponse {}))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use data_types::{partition_template::NamespacePartitionTemplateOverride, TableId};
+    use std::sync::Mutex;
+
+    use data_types::{partition_template::NamespacePartitionTemplateOverride, ColumnType, TableId};
     use generated_types::influxdata::iox::{
         partition_template::v1::{template_part, PartitionTemplate, TemplatePart},
         table::v1::table_service_server::TableService as _,
@@ -157,6 +401,54 @@ mod tests {
 
     use super::*;
 
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    enum Deleted {
+        Table(String, TableId),
+        Column(String, TableId, String, ColumnId),
+    }
+
+    #[derive(Debug, Default)]
+    struct MockDeletionObserver {
+        calls: Mutex<Vec<Deleted>>,
+    }
+
+    impl MockDeletionObserver {
+        fn calls(&self) -> Vec<Deleted> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    impl DeletionObserver for MockDeletionObserver {
+        fn table_deleted(
+            &self,
+            _namespace: &NamespaceName<'static>,
+            table_name: &str,
+            table_id: TableId,
+        ) {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(Deleted::Table(table_name.to_string(), table_id));
+        }
+
+        fn column_deleted(
+            &self,
+            _namespace: &NamespaceName<'static>,
+            _namespace_id: NamespaceId,
+            table_name: &str,
+            table_id: TableId,
+            column_name: &str,
+            column_id: ColumnId,
+        ) {
+            self.calls.lock().unwrap().push(Deleted::Column(
+                table_name.to_string(),
+                table_id,
+                column_name.to_string(),
+                column_id,
+            ));
+        }
+    }
+
     #[tokio::test]
     async fn test_get_tables() {
         let catalog: Arc<dyn Catalog> =
@@ -466,4 +758,177 @@ mod tests {
         let all_tables = catalog.repositories().await.tables().list().await.unwrap();
         assert!(all_tables.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_delete_table() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let observer = Arc::new(MockDeletionObserver::default());
+        let handler = TableService::new(Arc::clone(&catalog))
+            .with_deletion_observer(Arc::clone(&observer) as _);
+
+        let namespace = arbitrary_namespace(&mut *catalog.repositories().await, "grapes").await;
+        let created_table = handler
+            .create_table(Request::new(CreateTableRequest {
+                name: "varietals".into(),
+                namespace: namespace.name.clone(),
+                partition_template: None,
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .table
+            .unwrap();
+
+        let request = DeleteTableRequest {
+            namespace_name: namespace.name.clone(),
+            table_name: "varietals".into(),
+        };
+
+        handler
+            .delete_table(Request::new(request.clone()))
+            .await
+            .expect("delete should succeed");
+
+        assert_eq!(
+            observer.calls(),
+            [Deleted::Table(
+                "varietals".into(),
+                TableId::new(created_table.id)
+            )]
+        );
+
+        let tables = handler
+            .get_tables(Request::new(GetTablesRequest {
+                namespace_name: namespace.name.clone(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .tables;
+        assert!(tables.is_empty());
+
+        // Deleting it again fails with a "not found" error
+        let error = handler
+            .delete_table(Request::new(request))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Code::NotFound);
+        assert_eq!(
+            error.message(),
+            "Could not find a table with name `varietals` in the namespace `grapes`"
+        );
+        assert_eq!(observer.calls().len(), 1);
+
+        // The name can be reused
+        handler
+            .create_table(Request::new(CreateTableRequest {
+                name: "varietals".into(),
+                namespace: namespace.name.clone(),
+                partition_template: None,
+            }))
+            .await
+            .expect("table name should be reusable");
+    }
+
+    #[tokio::test]
+    async fn test_delete_column() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let observer = Arc::new(MockDeletionObserver::default());
+        let handler = TableService::new(Arc::clone(&catalog))
+            .with_deletion_observer(Arc::clone(&observer) as _);
+
+        let namespace = arbitrary_namespace(&mut *catalog.repositories().await, "grapes").await;
+        let table = handler
+            .create_table(Request::new(CreateTableRequest {
+                name: "varietals".into(),
+                namespace: namespace.name.clone(),
+                partition_template: None,
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .table
+            .unwrap();
+        let table_id = TableId::new(table.id);
+
+        let column = {
+            let mut repos = catalog.repositories().await;
+            repos
+                .columns()
+                .create_or_get("time", table_id, ColumnType::Time)
+                .await
+                .unwrap();
+            repos
+                .columns()
+                .create_or_get("sweetness", table_id, ColumnType::F64)
+                .await
+                .unwrap()
+        };
+
+        let delete = |column_name: &str| DeleteColumnRequest {
+            namespace_name: namespace.name.clone(),
+            table_name: "varietals".into(),
+            column_name: column_name.into(),
+        };
+
+        handler
+            .delete_column(Request::new(delete("sweetness")))
+            .await
+            .expect("delete should succeed");
+        assert_eq!(
+            observer.calls(),
+            [Deleted::Column(
+                "varietals".into(),
+                table_id,
+                "sweetness".into(),
+                column.id
+            )]
+        );
+
+        let columns = catalog
+            .repositories()
+            .await
+            .columns()
+            .list_by_table_id(table_id)
+            .await
+            .unwrap();
+        assert_eq!(columns.len(), 1);
+        assert_eq!(columns[0].name, "time");
+
+        // Deleting it again fails with a "not found" error
+        let error = handler
+            .delete_column(Request::new(delete("sweetness")))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Code::NotFound);
+        assert_eq!(
+            error.message(),
+            "Could not find a column with name `sweetness` in the table `varietals`"
+        );
+
+        // The time column cannot be deleted
+        let error = handler
+            .delete_column(Request::new(delete("time")))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Code::FailedPrecondition);
+        assert_eq!(
+            error.message(),
+            "The column `time` cannot be deleted: the time column is required"
+        );
+
+        // Nor can a column in a table that does not exist
+        let error = handler
+            .delete_column(Request::new(DeleteColumnRequest {
+                table_name: "does_not_exist".into(),
+                ..delete("time")
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Code::NotFound);
+
+        assert_eq!(observer.calls().len(), 1);
+    }
 }
@@ -207,6 +207,19 @@ impl TestConfig {
         )
     }
 
+    /// Configure the router the querier writes data produced by queries to,
+    /// such as the results of an InfluxQL `SELECT ... INTO` statement.
+    pub fn with_router_address(self, router_config: &TestConfig) -> Self {
+        self.with_env(
+            "INFLUXDB_IOX_ROUTER_ADDRESS",
+            router_config
+                .addrs()
+                .router_grpc_api()
+                .client_base()
+                .as_ref(),
+        )
+    }
+
     pub fn with_rpc_write_replicas(self, rpc_write_replicas: NonZeroUsize) -> Self {
         self.with_env(
             "INFLUXDB_IOX_RPC_WRITE_REPLICAS",
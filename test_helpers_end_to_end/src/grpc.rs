@@ -21,6 +21,8 @@ pub struct GrpcRequestBuilder {
     group_keys: Option<Vec<String>>,
     // also used for read_window_aggregate requests
     aggregate_type: Option<AggregateType>,
+    // for the quantile aggregate
+    quantile: Option<f64>,
 
     window_every: Option<i64>,
     offset: Option<i64>,
@@ -351,6 +353,16 @@ impl GrpcRequestBuilder {
         }
     }
 
+    /// Set the quantile for a quantile aggregate on a read_group or
+    /// read_window_aggregate request
+    pub fn quantile(self, quantile: f64) -> Self {
+        assert!(self.quantile.is_none(), "Overwriting existing quantile");
+        Self {
+            quantile: Some(quantile),
+            ..self
+        }
+    }
+
     /// Set the window_every field for a read_window_aggregate request
     pub fn window_every(self, window_every: i64) -> Self {
         assert!(
@@ -446,8 +458,10 @@ impl GrpcRequestBuilder {
 
     /// Creates a read group request
     pub fn build_read_group(self) -> tonic::Request<ReadGroupRequest> {
+        let quantile = self.quantile.unwrap_or_default();
         let aggregate = self.aggregate_type.map(|aggregate_type| Aggregate {
             r#type: aggregate_type.into(),
+            quantile,
         });
 
         let group_keys = self.group_keys.unwrap_or_default();
@@ -469,11 +483,13 @@ impl GrpcRequestBuilder {
     /// Creates a read window_aggregate request
     pub fn build_read_window_aggregate(self) -> tonic::Request<ReadWindowAggregateRequest> {
         // we support only a single aggregate for now
+        let quantile = self.quantile.unwrap_or_default();
         let aggregate = self
             .aggregate_type
             .map(|aggregate_type| {
                 vec![Aggregate {
                     r#type: aggregate_type.into(),
+                    quantile,
                 }]
             })
             .expect("No aggregate specified, can't create read_window_aggregate request");
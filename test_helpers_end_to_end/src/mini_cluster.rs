@@ -204,7 +204,8 @@ impl MiniCluster {
     pub async fn create_non_shared(database_url: String) -> Self {
         let ingester_config = TestConfig::new_ingester(&database_url);
         let router_config = TestConfig::new_router(&ingester_config);
-        let querier_config = TestConfig::new_querier(&ingester_config);
+        let querier_config =
+            TestConfig::new_querier(&ingester_config).with_router_address(&router_config);
         let compactor_config = TestConfig::new_compactor(&ingester_config);
 
         // Set up the cluster  ====================================
@@ -225,7 +226,8 @@ impl MiniCluster {
     pub async fn create_non_shared_never_persist(database_url: String) -> Self {
         let ingester_config = TestConfig::new_ingester_never_persist(&database_url);
         let router_config = TestConfig::new_router(&ingester_config);
-        let querier_config = TestConfig::new_querier(&ingester_config);
+        let querier_config =
+            TestConfig::new_querier(&ingester_config).with_router_address(&router_config);
         let compactor_config = TestConfig::new_compactor(&ingester_config);
 
         // Set up the cluster  ====================================
@@ -60,9 +60,15 @@ where
         let mut decompressing_read = FrameDecoder::new(hashing_read);
 
         let mut data = Vec::with_capacity(100);
-        decompressing_read
-            .read_to_end(&mut data)
-            .context(UnableToReadDataSnafu)?;
+        if let Err(e) = decompressing_read.read_to_end(&mut data) {
+            // Consume the remainder of the entry so that, should the length
+            // prefix be intact, the next read starts at the next entry rather
+            // than part way through this one.
+            let mut remaining = decompressing_read.into_inner().inner;
+            let _ = io::copy(&mut remaining, &mut io::sink());
+
+            return Err(e).context(UnableToReadDataSnafu);
+        }
 
         let (actual_compressed_len, actual_checksum) = decompressing_read.into_inner().checksum();
 
@@ -322,6 +328,36 @@ mod tests {
         assert!(entry.is_none());
     }
 
+    #[test]
+    fn unsuccessful_read_corrupt_data() {
+        let mut segment_file = FakeSegmentFile::new();
+        let bad_entry_input =
+            FakeSegmentEntry::new(b"hello").with_compressed_data(b"not snappy".to_vec());
+        segment_file.add_entry(bad_entry_input);
+
+        let good_entry_input = FakeSegmentEntry::new(b"goodbye");
+        segment_file.add_entry(good_entry_input.clone());
+
+        let data = segment_file.data();
+        let mut reader = ClosedSegmentFileReader::new(data.as_slice());
+
+        reader.read_header().unwrap();
+
+        let read_fail = reader.one_entry();
+        assert_matches!(read_fail, Err(Error::UnableToReadData { source: e }) => {
+            assert_ne!(e.kind(), std::io::ErrorKind::UnexpectedEof);
+        });
+
+        // The remainder of the corrupt entry is skipped, so the next entry can
+        // still be read.
+        let entry_output_2 = reader.one_entry().unwrap().unwrap();
+        let expected_2 = SegmentEntry::from(&good_entry_input);
+        assert_eq!(entry_output_2.data, expected_2.data);
+
+        let entry = reader.one_entry().unwrap();
+        assert!(entry.is_none());
+    }
+
     #[derive(Debug)]
     struct FakeSegmentFile {
         id: SegmentId,
@@ -362,6 +398,7 @@ mod tests {
     struct FakeSegmentEntry {
         checksum: Option<u32>,
         compressed_len: Option<u32>,
+        compressed_data: Option<Vec<u8>>,
         uncompressed_data: Vec<u8>,
     }
 
@@ -370,6 +407,7 @@ mod tests {
             Self {
                 checksum: None,
                 compressed_len: None,
+                compressed_data: None,
                 uncompressed_data: data.to_vec(),
             }
         }
@@ -381,6 +419,13 @@ mod tests {
             }
         }
 
+        fn with_compressed_data(self, compressed_data: Vec<u8>) -> Self {
+            Self {
+                compressed_data: Some(compressed_data),
+                ..self
+            }
+        }
+
         fn with_checksum(self, checksum: u32) -> Self {
             Self {
                 checksum: Some(checksum),
@@ -397,6 +442,10 @@ mod tests {
         }
 
         fn compressed_data(&self) -> Vec<u8> {
+            if let Some(data) = &self.compressed_data {
+                return data.clone();
+            }
+
             let mut encoder = snap::write::FrameEncoder::new(Vec::new());
             encoder.write_all(&self.uncompressed_data).unwrap();
             encoder.into_inner().expect("cannot fail to flush to a Vec")
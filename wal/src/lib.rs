@@ -134,6 +134,11 @@ pub enum Error {
         path: PathBuf,
     },
 
+    QuarantineClosedSegment {
+        source: std::io::Error,
+        path: PathBuf,
+    },
+
     UnableToWrite {
         source: blocking::WriterError,
     },
@@ -198,12 +203,34 @@ pub(crate) fn build_segment_path(dir: impl Into<PathBuf>, id: SegmentId) -> Path
     path
 }
 
+/// Returns the file name a closed segment with the specified `id`, closed at
+/// `closed_at_nanos` nanoseconds since the epoch, is archived under.
+///
+/// Names sort lexicographically in the order the segments were closed in.
+pub fn archived_segment_name(id: SegmentId, closed_at_nanos: i64) -> String {
+    format!("{closed_at_nanos:020}_{id}.{SEGMENT_FILE_EXTENSION}")
+}
+
+/// Parses a file name produced by [`archived_segment_name()`], returning the
+/// [`SegmentId`] and the time the segment was closed at, in nanoseconds since
+/// the epoch.
+pub fn parse_archived_segment_name(name: &str) -> Option<(SegmentId, i64)> {
+    let stem = name
+        .strip_suffix(SEGMENT_FILE_EXTENSION)?
+        .strip_suffix('.')?;
+    let (closed_at, id) = stem.split_once('_')?;
+    Some((SegmentId::new(id.parse().ok()?), closed_at.parse().ok()?))
+}
+
 /// The first bytes written into a segment file to identify it and its version.
 // TODO: What's the expected way of upgrading -- what happens when we need version 31?
 type FileTypeIdentifier = [u8; 8];
 const FILE_TYPE_IDENTIFIER: &FileTypeIdentifier = b"INFLUXV3";
 /// File extension for segment files.
 const SEGMENT_FILE_EXTENSION: &str = "dat";
+/// Name of the directory within the WAL root that damaged segment files are
+/// moved to by [`Wal::quarantine()`].
+pub const QUARANTINE_DIR_NAME: &str = "quarantine";
 
 /// The main type representing one WAL for one ingester instance.
 ///
@@ -326,6 +353,11 @@ impl Wal {
         s.closed_segments.values().cloned().collect()
     }
 
+    /// Get the closed segment with the specified ID, if any.
+    pub fn closed_segment(&self, id: SegmentId) -> Option<ClosedSegment> {
+        self.segments.lock().closed_segments.get(&id).cloned()
+    }
+
     /// Open a reader to a closed segment
     pub fn reader_for_segment(&self, id: SegmentId) -> Result<ClosedSegmentFileReader> {
         let path = build_segment_path(&self.root, id);
@@ -423,6 +455,30 @@ impl Wal {
             .context(SegmentNotFoundSnafu { id })?;
         std::fs::remove_file(&closed.path).context(DeleteClosedSegmentSnafu { path: closed.path })
     }
+
+    /// Moves the specified closed segment into the [`QUARANTINE_DIR_NAME`]
+    /// directory within the WAL root, returning its new path.
+    ///
+    /// A quarantined segment is no longer tracked by this [`Wal`] and is not
+    /// replayed when the WAL is next opened, but its contents are retained for
+    /// inspection.
+    pub async fn quarantine(&self, id: SegmentId) -> Result<PathBuf> {
+        let closed = self
+            .segments
+            .lock()
+            .closed_segments
+            .remove(&id)
+            .context(SegmentNotFoundSnafu { id })?;
+
+        let dir = self.root.join(QUARANTINE_DIR_NAME);
+        std::fs::create_dir_all(&dir).context(QuarantineClosedSegmentSnafu { path: &dir })?;
+
+        let path = build_segment_path(dir, id);
+        std::fs::rename(&closed.path, &path)
+            .context(QuarantineClosedSegmentSnafu { path: &closed.path })?;
+
+        Ok(path)
+    }
 }
 
 impl Drop for Wal {
@@ -671,6 +727,10 @@ impl ClosedSegment {
     pub fn size(&self) -> u64 {
         self.size
     }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
 }
 
 #[cfg(test)]
@@ -809,6 +869,53 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn quarantine_segment() {
+        let dir = test_helpers::tmp_dir().unwrap();
+        let wal = Wal::new(dir.path()).await.unwrap();
+
+        let (closed, _) = wal.rotate().unwrap();
+
+        let path = wal.quarantine(closed.id()).await.unwrap();
+        assert_eq!(
+            path,
+            build_segment_path(dir.path().join(QUARANTINE_DIR_NAME), closed.id())
+        );
+        assert!(path.exists());
+        assert!(!closed.path.exists());
+
+        // The segment is no longer tracked, and cannot be quarantined twice.
+        assert!(wal.closed_segments().is_empty());
+        assert_matches!(
+            wal.quarantine(closed.id()).await,
+            Err(Error::SegmentNotFound { .. })
+        );
+
+        // The quarantine directory is ignored when the WAL is reopened.
+        drop(wal);
+        let wal = Wal::new(dir.path()).await.unwrap();
+        let closed_ids = wal
+            .closed_segments()
+            .iter()
+            .map(|c| c.id())
+            .collect::<Vec<_>>();
+        assert!(!closed_ids.contains(&closed.id()));
+    }
+
+    #[test]
+    fn archived_segment_name_round_trip() {
+        let name = archived_segment_name(SegmentId::new(42), 1_694_000_000_000_000_000);
+        assert_eq!(name, "01694000000000000000_42.dat");
+        assert_eq!(
+            parse_archived_segment_name(&name),
+            Some((SegmentId::new(42), 1_694_000_000_000_000_000))
+        );
+
+        assert_eq!(parse_archived_segment_name("42.dat"), None);
+        assert_eq!(parse_archived_segment_name("1_42.parquet"), None);
+        assert_eq!(parse_archived_segment_name("bananas_42.dat"), None);
+    }
+
     #[tokio::test]
     async fn decode_write_op_entries() {
         let dir = test_helpers::tmp_dir().unwrap();
@@ -967,6 +1074,8 @@ mod tests {
             database_id: TEST_NAMESPACE_ID.get(),
             predicate: None,
             table_name: "bananas".into(),
+            table_id: 44,
+            column_names: vec![],
         }
     }
 
@@ -976,6 +1085,7 @@ mod tests {
             parquet_file_uuid: "b4N4N4Z".into(),
             partition_id: 43,
             table_id: 44,
+            column_names: vec![],
         }
     }
 }